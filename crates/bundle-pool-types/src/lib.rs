@@ -0,0 +1,71 @@
+//! Types of the bundle pool that are shared with its RPC API.
+//!
+//! The bundle pool itself is implemented by `reth-bundle-pool`.
+#![doc(
+    html_logo_url = "https://raw.githubusercontent.com/paradigmxyz/reth/main/assets/reth-docs.png",
+    html_favicon_url = "https://avatars0.githubusercontent.com/u/97369466?s=256",
+    issue_tracker_base_url = "https://github.com/paradigmxyz/reth/issues/"
+)]
+#![cfg_attr(not(test), warn(unused_crate_dependencies))]
+#![cfg_attr(docsrs, feature(doc_cfg, doc_auto_cfg))]
+
+use alloy_primitives::B256;
+use serde::{Deserialize, Serialize};
+
+/// The inclusion status of a bundle, as returned by `eth_getBundleStatus`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "status", rename_all = "camelCase")]
+pub enum BundleStatus {
+    /// The bundle is in the pool and waits to be included.
+    Pending,
+    /// All transactions of the bundle were included in a canonical block.
+    #[serde(rename_all = "camelCase")]
+    Included {
+        /// The number of the block.
+        block_number: u64,
+        /// The hash of the block.
+        block_hash: B256,
+    },
+    /// Some, but not all, transactions of the bundle were included in a canonical block, so the
+    /// bundle can't be included anymore.
+    #[serde(rename_all = "camelCase")]
+    Failed {
+        /// The number of the block.
+        block_number: u64,
+        /// The hash of the block.
+        block_hash: B256,
+    },
+    /// The bundle wasn't included until its last block or max timestamp.
+    Expired,
+    /// The bundle was cancelled.
+    Cancelled,
+    /// The bundle was replaced by another bundle with the same replacement UUID.
+    #[serde(rename_all = "camelCase")]
+    Replaced {
+        /// The hash of the new bundle.
+        by: B256,
+    },
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn serde_bundle_status() {
+        let status = BundleStatus::Failed { block_number: 1, block_hash: B256::ZERO };
+        let json = serde_json::to_value(&status).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "status": "failed",
+                "blockNumber": 1,
+                "blockHash": B256::ZERO,
+            })
+        );
+        assert_eq!(serde_json::from_value::<BundleStatus>(json).unwrap(), status);
+
+        let json = serde_json::to_value(BundleStatus::Pending).unwrap();
+        assert_eq!(json, serde_json::json!({ "status": "pending" }));
+    }
+}
@@ -0,0 +1,156 @@
+use alloy_consensus::Transaction;
+use alloy_primitives::{map::B256HashSet, Keccak256, B256};
+use reth_payload_util::PayloadTransactionGroup;
+use reth_primitives::{RecoveredTx, TransactionSigned};
+
+/// A bundle of transactions that's included in a block all together and in order, or not at all.
+///
+/// A bundle can be included in the blocks `block_number..=max_block_number` whose timestamps are
+/// within the optional min and max timestamps. All transactions must succeed, except for those
+/// listed in the reverting transaction hashes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Bundle {
+    hash: B256,
+    transactions: Vec<RecoveredTx>,
+    block_number: u64,
+    max_block_number: u64,
+    min_timestamp: Option<u64>,
+    max_timestamp: Option<u64>,
+    reverting_tx_hashes: Vec<B256>,
+    replacement_uuid: Option<String>,
+}
+
+impl Bundle {
+    /// Creates a new bundle of the transactions that targets the given block.
+    ///
+    /// The hash of the bundle is the keccak256 hash of the concatenated transaction hashes, like
+    /// the bundle hash of `eth_callBundle`.
+    pub fn new(transactions: Vec<RecoveredTx>, block_number: u64) -> Self {
+        let mut hasher = Keccak256::new();
+        for tx in &transactions {
+            hasher.update(tx.hash());
+        }
+        Self {
+            hash: hasher.finalize(),
+            transactions,
+            block_number,
+            max_block_number: block_number,
+            min_timestamp: None,
+            max_timestamp: None,
+            reverting_tx_hashes: Vec::new(),
+            replacement_uuid: None,
+        }
+    }
+
+    /// Sets the last block the bundle can be included in.
+    pub const fn with_max_block_number(mut self, max_block_number: u64) -> Self {
+        self.max_block_number = max_block_number;
+        self
+    }
+
+    /// Sets the minimum and maximum timestamps of the blocks the bundle can be included in.
+    pub const fn with_timestamps(
+        mut self,
+        min_timestamp: Option<u64>,
+        max_timestamp: Option<u64>,
+    ) -> Self {
+        self.min_timestamp = min_timestamp;
+        self.max_timestamp = max_timestamp;
+        self
+    }
+
+    /// Sets the hashes of the transactions that may revert.
+    pub fn with_reverting_tx_hashes(mut self, reverting_tx_hashes: Vec<B256>) -> Self {
+        self.reverting_tx_hashes = reverting_tx_hashes;
+        self
+    }
+
+    /// Sets the UUID that identifies the bundle for replacements and cancellations.
+    pub fn with_replacement_uuid(mut self, replacement_uuid: Option<String>) -> Self {
+        self.replacement_uuid = replacement_uuid;
+        self
+    }
+
+    /// Returns the hash of the bundle.
+    pub const fn hash(&self) -> B256 {
+        self.hash
+    }
+
+    /// Returns the transactions of the bundle.
+    pub fn transactions(&self) -> &[RecoveredTx] {
+        &self.transactions
+    }
+
+    /// Returns the first block the bundle can be included in.
+    pub const fn block_number(&self) -> u64 {
+        self.block_number
+    }
+
+    /// Returns the last block the bundle can be included in.
+    pub const fn max_block_number(&self) -> u64 {
+        self.max_block_number
+    }
+
+    /// Returns the hashes of the transactions that may revert.
+    pub fn reverting_tx_hashes(&self) -> &[B256] {
+        &self.reverting_tx_hashes
+    }
+
+    /// Returns the UUID that identifies the bundle for replacements and cancellations.
+    pub fn replacement_uuid(&self) -> Option<&str> {
+        self.replacement_uuid.as_deref()
+    }
+
+    /// Returns `true` if the bundle can be included in the block with the given number and
+    /// timestamp.
+    pub fn is_eligible(&self, block_number: u64, timestamp: u64) -> bool {
+        (self.block_number..=self.max_block_number).contains(&block_number) &&
+            self.min_timestamp.is_none_or(|min| timestamp >= min) &&
+            self.max_timestamp.is_none_or(|max| timestamp <= max)
+    }
+
+    /// Returns `true` if the bundle can't be included in any block after the block with the given
+    /// number and timestamp.
+    pub fn is_expired(&self, block_number: u64, timestamp: u64) -> bool {
+        block_number >= self.max_block_number ||
+            self.max_timestamp.is_some_and(|max| timestamp >= max)
+    }
+
+    /// Returns `true` if all transactions of the bundle are in the given set.
+    pub fn is_included(&self, tx_hashes: &B256HashSet) -> bool {
+        self.transactions.iter().all(|tx| tx_hashes.contains(&tx.hash()))
+    }
+
+    /// Returns `true` if any transaction of the bundle is in the given set.
+    ///
+    /// A bundle whose transactions are only partially included can't be included anymore.
+    pub fn is_partially_included(&self, tx_hashes: &B256HashSet) -> bool {
+        self.transactions.iter().any(|tx| tx_hashes.contains(&tx.hash()))
+    }
+
+    /// Returns the priority fees that the bundle pays if all of its transactions use their whole
+    /// gas limit.
+    ///
+    /// This is only an estimate of the profit of the bundle, which also includes direct transfers
+    /// to the beneficiary of the block and is only known once the bundle is simulated.
+    pub fn estimated_priority_fees(&self, base_fee: u64) -> u128 {
+        self.transactions
+            .iter()
+            .map(|tx| {
+                tx.as_signed().effective_tip_per_gas(base_fee).unwrap_or_default() *
+                    tx.as_signed().gas_limit() as u128
+            })
+            .sum()
+    }
+
+    /// Converts the bundle into a transaction group of the payload builder that must pay at least
+    /// the given amount per gas used.
+    pub fn into_group(
+        self,
+        min_profit_per_gas: u128,
+    ) -> PayloadTransactionGroup<TransactionSigned> {
+        PayloadTransactionGroup::new(self.transactions)
+            .with_reverting_tx_hashes(self.reverting_tx_hashes)
+            .with_min_profit_per_gas(min_profit_per_gas)
+    }
+}
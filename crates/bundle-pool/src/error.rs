@@ -0,0 +1,28 @@
+/// Error returned when a bundle is rejected by the [`BundlePool`](crate::BundlePool).
+#[derive(Debug, thiserror::Error)]
+pub enum BundlePoolError {
+    /// The bundle doesn't contain any transactions.
+    #[error("bundle missing txs")]
+    EmptyBundle,
+    /// The bundle contains more transactions than allowed by the pool.
+    #[error("bundle has {actual} txs, exceeding the limit of {max}")]
+    TooManyTransactions {
+        /// The number of transactions of the bundle.
+        actual: usize,
+        /// The maximum number of transactions of a bundle.
+        max: usize,
+    },
+    /// The bundle contains a blob transaction, whose sidecar isn't available to the payload
+    /// builder.
+    #[error("blob transactions are not supported in bundles")]
+    BlobTransaction,
+    /// The block range or the timestamps of the bundle are invalid.
+    #[error("invalid bundle inclusion: {0}")]
+    InvalidInclusion(&'static str),
+    /// The bundle is already in the pool.
+    #[error("bundle already known")]
+    AlreadyKnown,
+    /// The pool has reached its maximum number of bundles.
+    #[error("bundle pool is full")]
+    PoolFull,
+}
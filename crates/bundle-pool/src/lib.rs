@@ -0,0 +1,38 @@
+//! A pool of transaction bundles for the local payload builder.
+//!
+//! Bundles are submitted with `eth_sendBundle` and `mev_sendBundle`, which are implemented by
+//! `reth_rpc::EthBundleSubmission`. A [`Bundle`] targets a range of blocks, optionally limited by a
+//! min and max timestamp, and only the transactions listed in its reverting transaction hashes may
+//! revert.
+//!
+//! The [`BundlePool`] is a
+//! [`PayloadTransactionSource`](reth_payload_util::PayloadTransactionSource) that's registered in
+//! the [`BuildPhase::BeforePool`](reth_payload_util::BuildPhase::BeforePool) of the payload
+//! builder, e.g. with `EthereumBuilderConfig::with_transaction_sources`, or by the Ethereum node
+//! with `--builder.bundles`. The eligible bundles are simulated on top of the payload and executed
+//! in the order of the profit per gas they pay to the beneficiary of the block, ahead of the pool
+//! transactions. Each of them is executed atomically, and is discarded if it pays less than the
+//! minimum profit of the pool.
+//!
+//! The [`maintain_bundle_pool`] task removes the included, failed and expired bundles from the
+//! pool, and keeps their [`BundleStatus`], which can be queried with `eth_getBundleStatus`.
+#![doc(
+    html_logo_url = "https://raw.githubusercontent.com/paradigmxyz/reth/main/assets/reth-docs.png",
+    html_favicon_url = "https://avatars0.githubusercontent.com/u/97369466?s=256",
+    issue_tracker_base_url = "https://github.com/paradigmxyz/reth/issues/"
+)]
+#![cfg_attr(not(test), warn(unused_crate_dependencies))]
+#![cfg_attr(docsrs, feature(doc_cfg, doc_auto_cfg))]
+
+mod bundle;
+pub use bundle::Bundle;
+pub use reth_bundle_pool_types::BundleStatus;
+
+mod error;
+pub use error::BundlePoolError;
+
+mod maintain;
+pub use maintain::maintain_bundle_pool;
+
+mod pool;
+pub use pool::{BundlePool, BundlePoolConfig};
@@ -0,0 +1,22 @@
+use crate::BundlePool;
+use alloy_consensus::BlockHeader;
+use alloy_primitives::map::B256HashSet;
+use futures_util::{Stream, StreamExt};
+use reth_chain_state::CanonStateNotification;
+
+/// Keeps the [`BundlePool`] up to date with the canonical chain.
+///
+/// On every new canonical block, the bundles that were fully or partially included in the block
+/// and the bundles that expired are removed from the pool, and their status is recorded.
+pub async fn maintain_bundle_pool<St>(pool: BundlePool, mut events: St)
+where
+    St: Stream<Item = CanonStateNotification> + Send + Unpin + 'static,
+{
+    while let Some(event) = events.next().await {
+        for block in event.committed().blocks_iter() {
+            let tx_hashes =
+                block.body.transactions.iter().map(|tx| tx.hash()).collect::<B256HashSet>();
+            pool.on_canonical_block(block.number(), block.hash(), block.timestamp(), &tx_hashes);
+        }
+    }
+}
@@ -0,0 +1,394 @@
+use crate::{Bundle, BundlePoolError, BundleStatus};
+use alloy_primitives::{
+    map::{B256HashMap, B256HashSet, HashMap},
+    B256,
+};
+use parking_lot::RwLock;
+use reth_payload_util::{PayloadSourceContext, PayloadTransactionGroup, PayloadTransactionSource};
+use reth_primitives::TransactionSigned;
+use schnellru::{ByLength, LruMap};
+use std::{cmp::Reverse, fmt, sync::Arc};
+use tracing::trace;
+
+/// Configuration of the [`BundlePool`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BundlePoolConfig {
+    /// The maximum number of bundles in the pool.
+    pub max_bundles: usize,
+    /// The maximum number of transactions of a bundle.
+    pub max_bundle_transactions: usize,
+    /// The maximum number of blocks between the first and the last block of a bundle.
+    pub max_block_range: u64,
+    /// The minimum amount per gas used that a bundle must pay to the beneficiary of the block,
+    /// through priority fees and direct transfers, to be included.
+    pub min_profit_per_gas: u128,
+    /// The maximum number of bundles that are no longer pending whose status is kept.
+    pub max_statuses: u32,
+}
+
+impl BundlePoolConfig {
+    /// Creates a new configuration with the default limits.
+    pub const fn new() -> Self {
+        Self {
+            max_bundles: 10_000,
+            max_bundle_transactions: 50,
+            max_block_range: 100,
+            min_profit_per_gas: 1,
+            max_statuses: 100_000,
+        }
+    }
+}
+
+impl Default for BundlePoolConfig {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A pool of the bundles that were submitted for inclusion in the blocks built by the local
+/// payload builder.
+///
+/// The pool is a [`PayloadTransactionSource`] that provides the bundles that are eligible for the
+/// block being built. The payload builder orders them by the profit they pay when simulated, and
+/// bundles with the same profit per gas by their estimated priority fees.
+#[derive(Clone)]
+pub struct BundlePool {
+    config: Arc<BundlePoolConfig>,
+    inner: Arc<RwLock<PoolInner>>,
+}
+
+impl BundlePool {
+    /// Creates a new empty pool.
+    pub fn new(config: BundlePoolConfig) -> Self {
+        let inner = PoolInner {
+            bundles: Default::default(),
+            by_replacement_uuid: Default::default(),
+            statuses: LruMap::new(ByLength::new(config.max_statuses)),
+        };
+        Self { config: Arc::new(config), inner: Arc::new(RwLock::new(inner)) }
+    }
+
+    /// Returns the configuration of the pool.
+    pub fn config(&self) -> &BundlePoolConfig {
+        &self.config
+    }
+
+    /// Returns the number of bundles in the pool.
+    pub fn len(&self) -> usize {
+        self.inner.read().bundles.len()
+    }
+
+    /// Returns `true` if the pool is empty.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the bundle with the given hash.
+    pub fn get(&self, hash: &B256) -> Option<Bundle> {
+        self.inner.read().bundles.get(hash).cloned()
+    }
+
+    /// Returns the status of the bundle with the given hash, if it's still known.
+    pub fn status(&self, hash: &B256) -> Option<BundleStatus> {
+        let mut inner = self.inner.write();
+        if inner.bundles.contains_key(hash) {
+            return Some(BundleStatus::Pending)
+        }
+        inner.statuses.get(hash).cloned()
+    }
+
+    /// Adds a bundle to the pool, and returns its hash.
+    ///
+    /// A bundle with the same replacement UUID as a pooled one replaces it.
+    pub fn add_bundle(&self, bundle: Bundle) -> Result<B256, BundlePoolError> {
+        if bundle.transactions().is_empty() {
+            return Err(BundlePoolError::EmptyBundle)
+        }
+        if bundle.transactions().len() > self.config.max_bundle_transactions {
+            return Err(BundlePoolError::TooManyTransactions {
+                actual: bundle.transactions().len(),
+                max: self.config.max_bundle_transactions,
+            })
+        }
+        if bundle.transactions().iter().any(|tx| tx.is_eip4844()) {
+            return Err(BundlePoolError::BlobTransaction)
+        }
+        if bundle.block_number() == 0 || bundle.max_block_number() < bundle.block_number() {
+            return Err(BundlePoolError::InvalidInclusion("invalid block range"))
+        }
+        if bundle.max_block_number() - bundle.block_number() > self.config.max_block_range {
+            return Err(BundlePoolError::InvalidInclusion("block range too large"))
+        }
+
+        let mut inner = self.inner.write();
+        let hash = bundle.hash();
+        if inner.bundles.contains_key(&hash) {
+            return Err(BundlePoolError::AlreadyKnown)
+        }
+
+        let replaced =
+            bundle.replacement_uuid().and_then(|uuid| inner.by_replacement_uuid.get(uuid)).copied();
+        if let Some(replaced) = replaced {
+            inner.remove(&replaced, BundleStatus::Replaced { by: hash });
+        } else if inner.bundles.len() >= self.config.max_bundles {
+            return Err(BundlePoolError::PoolFull)
+        }
+
+        if let Some(uuid) = bundle.replacement_uuid() {
+            inner.by_replacement_uuid.insert(uuid.to_string(), hash);
+        }
+        inner.bundles.insert(hash, bundle);
+        Ok(hash)
+    }
+
+    /// Cancels the bundle with the given replacement UUID, and returns `true` if it was pending.
+    pub fn cancel_bundle(&self, replacement_uuid: &str) -> bool {
+        let mut inner = self.inner.write();
+        let Some(hash) = inner.by_replacement_uuid.get(replacement_uuid).copied() else {
+            return false
+        };
+        inner.remove(&hash, BundleStatus::Cancelled);
+        true
+    }
+
+    /// Returns the bundles that can be included in the block with the given number and timestamp.
+    pub fn eligible_bundles(&self, block_number: u64, timestamp: u64) -> Vec<Bundle> {
+        self.inner
+            .read()
+            .bundles
+            .values()
+            .filter(|bundle| bundle.is_eligible(block_number, timestamp))
+            .cloned()
+            .collect()
+    }
+
+    /// Updates the pool with a new canonical block that contains the transactions with the given
+    /// hashes.
+    ///
+    /// Bundles whose transactions are all or only partially included in the block, and bundles
+    /// that can't be included in any later block, are removed from the pool.
+    pub fn on_canonical_block(
+        &self,
+        block_number: u64,
+        block_hash: B256,
+        timestamp: u64,
+        tx_hashes: &B256HashSet,
+    ) {
+        let mut inner = self.inner.write();
+        let finished = inner
+            .bundles
+            .values()
+            .filter_map(|bundle| {
+                if bundle.is_included(tx_hashes) {
+                    Some((bundle.hash(), BundleStatus::Included { block_number, block_hash }))
+                } else if bundle.is_partially_included(tx_hashes) {
+                    Some((bundle.hash(), BundleStatus::Failed { block_number, block_hash }))
+                } else if bundle.is_expired(block_number, timestamp) {
+                    Some((bundle.hash(), BundleStatus::Expired))
+                } else {
+                    None
+                }
+            })
+            .collect::<Vec<_>>();
+
+        for (hash, status) in finished {
+            trace!(target: "bundle_pool", %hash, ?status, block_number, "removing bundle");
+            inner.remove(&hash, status);
+        }
+    }
+}
+
+impl PayloadTransactionSource<TransactionSigned> for BundlePool {
+    fn transactions(
+        &self,
+        ctx: &PayloadSourceContext<'_, TransactionSigned>,
+    ) -> Vec<PayloadTransactionGroup<TransactionSigned>> {
+        let mut bundles = self.eligible_bundles(ctx.block_number, ctx.timestamp);
+        bundles.sort_by_cached_key(|bundle| Reverse(bundle.estimated_priority_fees(ctx.base_fee)));
+        bundles
+            .into_iter()
+            .map(|bundle| bundle.into_group(self.config.min_profit_per_gas))
+            .collect()
+    }
+
+    fn order_by_profit(&self) -> bool {
+        true
+    }
+}
+
+impl Default for BundlePool {
+    fn default() -> Self {
+        Self::new(BundlePoolConfig::default())
+    }
+}
+
+impl fmt::Debug for BundlePool {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BundlePool")
+            .field("config", &self.config)
+            .field("len", &self.len())
+            .finish_non_exhaustive()
+    }
+}
+
+struct PoolInner {
+    /// The pending bundles by their hash.
+    bundles: B256HashMap<Bundle>,
+    /// The hashes of the pending bundles by their replacement UUID.
+    by_replacement_uuid: HashMap<String, B256>,
+    /// The statuses of the bundles that are no longer pending.
+    statuses: LruMap<B256, BundleStatus>,
+}
+
+impl PoolInner {
+    /// Removes the bundle from the pool and records its final status.
+    fn remove(&mut self, hash: &B256, status: BundleStatus) {
+        let Some(bundle) = self.bundles.remove(hash) else { return };
+        if let Some(uuid) = bundle.replacement_uuid() {
+            if self.by_replacement_uuid.get(uuid) == Some(hash) {
+                self.by_replacement_uuid.remove(uuid);
+            }
+        }
+        self.statuses.insert(*hash, status);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_consensus::TxLegacy;
+    use alloy_primitives::{Address, PrimitiveSignature as Signature};
+    use reth_primitives::RecoveredTx;
+
+    fn transaction(nonce: u64, gas_price: u128) -> RecoveredTx {
+        let tx = TxLegacy { nonce, gas_price, gas_limit: 21_000, ..Default::default() };
+        RecoveredTx::from_signed_transaction(
+            TransactionSigned::new_unhashed(tx.into(), Signature::test_signature()),
+            Address::random(),
+        )
+    }
+
+    #[test]
+    fn bundle_inclusion_window() {
+        let bundle = Bundle::new(vec![transaction(0, 10)], 10)
+            .with_max_block_number(12)
+            .with_timestamps(Some(100), Some(200));
+
+        assert!(!bundle.is_eligible(9, 150));
+        assert!(bundle.is_eligible(10, 150));
+        assert!(bundle.is_eligible(12, 150));
+        assert!(!bundle.is_eligible(13, 150));
+        assert!(!bundle.is_eligible(11, 99));
+        assert!(!bundle.is_eligible(11, 201));
+
+        assert!(!bundle.is_expired(11, 150));
+        assert!(bundle.is_expired(12, 150));
+        assert!(bundle.is_expired(11, 200));
+    }
+
+    #[test]
+    fn replace_and_cancel_bundle() {
+        let pool = BundlePool::new(BundlePoolConfig::default());
+        let uuid = Some("uuid".to_string());
+
+        let hash = pool
+            .add_bundle(
+                Bundle::new(vec![transaction(0, 10)], 1).with_replacement_uuid(uuid.clone()),
+            )
+            .unwrap();
+
+        let replacement = pool
+            .add_bundle(Bundle::new(vec![transaction(1, 10)], 1).with_replacement_uuid(uuid))
+            .unwrap();
+        assert_eq!(pool.status(&hash), Some(BundleStatus::Replaced { by: replacement }));
+        assert_eq!(pool.status(&replacement), Some(BundleStatus::Pending));
+        assert_eq!(pool.len(), 1);
+
+        assert!(pool.cancel_bundle("uuid"));
+        assert!(!pool.cancel_bundle("uuid"));
+        assert_eq!(pool.status(&replacement), Some(BundleStatus::Cancelled));
+        assert!(pool.is_empty());
+    }
+
+    #[test]
+    fn reject_invalid_bundles() {
+        let pool = BundlePool::new(BundlePoolConfig::default());
+        assert!(matches!(
+            pool.add_bundle(Bundle::new(Vec::new(), 1)),
+            Err(BundlePoolError::EmptyBundle)
+        ));
+        assert!(matches!(
+            pool.add_bundle(Bundle::new(vec![transaction(0, 10)], 0)),
+            Err(BundlePoolError::InvalidInclusion(_))
+        ));
+        assert!(matches!(
+            pool.add_bundle(Bundle::new(vec![transaction(0, 10)], 2).with_max_block_number(1)),
+            Err(BundlePoolError::InvalidInclusion(_))
+        ));
+
+        let bundle = Bundle::new(vec![transaction(0, 10)], 1);
+        pool.add_bundle(bundle.clone()).unwrap();
+        assert!(matches!(pool.add_bundle(bundle), Err(BundlePoolError::AlreadyKnown)));
+    }
+
+    #[test]
+    fn canonical_blocks_finish_bundles() {
+        let pool = BundlePool::new(BundlePoolConfig::default());
+        let included = Bundle::new(vec![transaction(0, 10), transaction(1, 10)], 1);
+        let expired = Bundle::new(vec![transaction(2, 10)], 1);
+        let later = Bundle::new(vec![transaction(3, 10)], 2);
+        let failed = Bundle::new(vec![transaction(4, 10), transaction(5, 10)], 2);
+        for bundle in [&included, &expired, &later, &failed] {
+            pool.add_bundle(bundle.clone()).unwrap();
+        }
+
+        let block_hash = B256::random();
+        let tx_hashes = included
+            .transactions()
+            .iter()
+            .chain(&failed.transactions()[..1])
+            .map(|tx| tx.hash())
+            .collect();
+        pool.on_canonical_block(1, block_hash, 12, &tx_hashes);
+
+        assert_eq!(
+            pool.status(&included.hash()),
+            Some(BundleStatus::Included { block_number: 1, block_hash })
+        );
+        assert_eq!(
+            pool.status(&failed.hash()),
+            Some(BundleStatus::Failed { block_number: 1, block_hash })
+        );
+        assert_eq!(pool.status(&expired.hash()), Some(BundleStatus::Expired));
+        assert_eq!(pool.status(&later.hash()), Some(BundleStatus::Pending));
+    }
+
+    #[test]
+    fn source_orders_bundles_by_priority_fees() {
+        let pool = BundlePool::new(BundlePoolConfig::default());
+        let cheap = Bundle::new(vec![transaction(0, 10)], 1);
+        let expensive =
+            Bundle::new(vec![transaction(1, 20)], 1).with_reverting_tx_hashes(vec![B256::ZERO]);
+        let later = Bundle::new(vec![transaction(2, 30)], 2);
+        for bundle in [&cheap, &expensive, &later] {
+            pool.add_bundle(bundle.clone()).unwrap();
+        }
+
+        let groups = pool.transactions(&PayloadSourceContext {
+            parent_hash: B256::ZERO,
+            block_number: 1,
+            timestamp: 12,
+            base_fee: 5,
+            gas_limit: 30_000_000,
+            gas_used: 0,
+            executed_transactions: &[],
+        });
+        assert_eq!(groups.len(), 2);
+        assert_eq!(groups[0].transactions(), expensive.transactions());
+        assert!(groups[0].may_revert(&B256::ZERO));
+        assert!(!groups[0].may_revert(&expensive.transactions()[0].hash()));
+        assert_eq!(groups[0].min_profit_per_gas(), pool.config().min_profit_per_gas);
+        assert_eq!(groups[1].transactions(), cheap.transactions());
+        assert!(pool.order_by_profit());
+    }
+}
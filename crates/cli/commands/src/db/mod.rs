@@ -11,6 +11,7 @@ mod clear;
 mod diff;
 mod get;
 mod list;
+mod snapshot;
 mod stats;
 /// DB List TUI
 mod tui;
@@ -50,6 +51,8 @@ pub enum Subcommands {
     Version,
     /// Returns the full database path
     Path,
+    /// Creates and restores snapshots of the database and the static files
+    Snapshot(snapshot::Command),
 }
 
 /// `db_ro_exec` opens a database in read-only mode, and then execute with the provided command
@@ -69,6 +72,13 @@ impl<C: ChainSpecParser<ChainSpec: EthChainSpec + EthereumHardforks>> Command<C>
         let db_path = data_dir.db();
         let static_files_path = data_dir.static_files();
 
+        // restoring a snapshot creates the database
+        if let Subcommands::Snapshot(command) = &self.command {
+            if command.creates_database() {
+                return command.execute::<N, C>(&self.env)
+            }
+        }
+
         // ensure the provided datadir exist
         eyre::ensure!(
             data_dir.data_dir().is_dir(),
@@ -148,6 +158,9 @@ impl<C: ChainSpecParser<ChainSpec: EthChainSpec + EthereumHardforks>> Command<C>
             Subcommands::Path => {
                 println!("{}", db_path.display());
             }
+            Subcommands::Snapshot(command) => {
+                command.execute::<N, C>(&self.env)?;
+            }
         }
 
         Ok(())
@@ -0,0 +1,549 @@
+use crate::common::{AccessRights, CliNodeTypes, Environment, EnvironmentArgs};
+use alloy_primitives::{BlockNumber, Keccak256, TxNumber, B256};
+use clap::{Parser, Subcommand};
+use eyre::{bail, ensure};
+use reth_chainspec::EthChainSpec;
+use reth_cli::chainspec::ChainSpecParser;
+use reth_db::{
+    init_db,
+    version::{get_db_version, DB_VERSION},
+    DatabaseEnv, RawDupSort, RawTable, TableViewer, Tables,
+};
+use reth_db_api::{
+    cursor::{DbCursorRO, DbCursorRW, DbDupCursorRW},
+    database::Database,
+    models::{StorageBeforeTx, StoredAccountChangeSet, StoredStorageChangeSet},
+    table::{DupSort, Table},
+    transaction::{DbTx, DbTxMut},
+};
+use reth_node_builder::NodeTypesWithDBAdapter;
+use reth_primitives::EthPrimitives;
+use reth_provider::{
+    providers::StaticFileProvider, BlockBodyIndicesProvider, BlockHashReader, BlockNumReader,
+    ChangeSetReader, DBProvider, HeaderProvider, ProviderError, ProviderFactory, ReceiptProvider,
+    StageCheckpointReader, StaticFileProviderFactory, StorageChangeSetReader, TransactionsProvider,
+};
+use reth_stages::StageId;
+use reth_static_file_types::StaticFileSegment;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::BTreeMap,
+    io::{self, Read},
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+use tracing::info;
+
+/// The name of the manifest file of a snapshot.
+const MANIFEST_FILE_NAME: &str = "manifest.json";
+
+/// The name of the database directory of a snapshot.
+const DB_DIR: &str = "db";
+
+/// The name of the static files directory of a snapshot.
+const STATIC_FILES_DIR: &str = "static_files";
+
+/// The number of entries that are copied into the snapshot database per transaction.
+const COPY_BATCH_SIZE: usize = 1_000_000;
+
+/// `reth db snapshot` command
+#[derive(Debug, Parser)]
+pub struct Command {
+    #[command(subcommand)]
+    command: Subcommands,
+}
+
+/// `reth db snapshot` subcommands
+#[derive(Debug, Subcommand)]
+pub enum Subcommands {
+    /// Creates a snapshot of the database and the static files.
+    ///
+    /// The database is copied from a single read transaction, so this can run while the node is
+    /// running. The static files are copied up to the height of the copied database.
+    Create {
+        /// The directory to write the snapshot to, it must not exist yet.
+        path: PathBuf,
+    },
+    /// Verifies a snapshot and restores it into the datadir.
+    ///
+    /// All files are verified against the manifest of the snapshot before anything is written.
+    /// The datadir must not contain a database or static files yet.
+    Restore {
+        /// The directory of the snapshot.
+        path: PathBuf,
+    },
+}
+
+impl Command {
+    /// Returns `true` if the command creates the database of the datadir.
+    pub const fn creates_database(&self) -> bool {
+        matches!(self.command, Subcommands::Restore { .. })
+    }
+
+    /// Execute `db snapshot` command
+    pub fn execute<N: CliNodeTypes, C: ChainSpecParser<ChainSpec = N::ChainSpec>>(
+        &self,
+        env: &EnvironmentArgs<C>,
+    ) -> eyre::Result<()> {
+        match &self.command {
+            Subcommands::Create { path } => create::<N, C>(env, path),
+            Subcommands::Restore { path } => restore::<N, C>(env, path),
+        }
+    }
+}
+
+/// The manifest of a snapshot.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct SnapshotManifest {
+    /// The chain id of the snapshot.
+    chain_id: u64,
+    /// The version of the database.
+    db_version: u64,
+    /// The highest block that all stages have finished.
+    block_number: BlockNumber,
+    /// The checkpoints of all stages.
+    stage_checkpoints: BTreeMap<String, BlockNumber>,
+    /// The highest block and transaction of every static file segment.
+    static_files: BTreeMap<StaticFileSegment, SegmentRange>,
+    /// All files of the snapshot.
+    files: Vec<SnapshotFile>,
+}
+
+/// The range of a static file segment.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+struct SegmentRange {
+    /// The highest block of the segment.
+    highest_block: Option<BlockNumber>,
+    /// The highest transaction of the segment.
+    highest_tx: Option<TxNumber>,
+}
+
+/// A file of a snapshot.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct SnapshotFile {
+    /// The path of the file, relative to the snapshot directory.
+    path: String,
+    /// The size of the file.
+    size: u64,
+    /// The keccak256 hash of the file.
+    hash: B256,
+}
+
+fn create<N: CliNodeTypes, C: ChainSpecParser<ChainSpec = N::ChainSpec>>(
+    env: &EnvironmentArgs<C>,
+    path: &Path,
+) -> eyre::Result<()> {
+    ensure!(!path.exists(), "snapshot directory {} already exists", path.display());
+
+    let Environment { provider_factory, config, data_dir } = env.init::<N>(AccessRights::RO)?;
+
+    let snapshot_db = init_db(path.join(DB_DIR), env.db.database_args())?;
+    let provider = provider_factory.provider()?.disable_long_read_transaction_safety();
+    for table in Tables::ALL {
+        let entries = table.view(&CopyViewer { tx: provider.tx_ref(), db: &snapshot_db })?;
+        info!(target: "reth::cli", %table, entries, "Copied table");
+    }
+
+    // Static files are committed before the database, so the static files that are opened after
+    // the read transaction are at least at the height of the copied database.
+    let static_files_path = path.join(STATIC_FILES_DIR);
+    let static_file_provider =
+        copy_static_files(&provider, &data_dir.static_files(), &static_files_path)?;
+    drop(provider);
+    info!(target: "reth::cli", "Copied static files");
+
+    let factory = ProviderFactory::<NodeTypesWithDBAdapter<N, Arc<DatabaseEnv>>>::new(
+        Arc::new(snapshot_db),
+        env.chain.clone(),
+        static_file_provider,
+    );
+    let has_receipt_pruning = config.prune.as_ref().is_some_and(|a| a.has_receipts_pruning());
+    let static_file_provider = factory.static_file_provider();
+    if let Some(target) =
+        static_file_provider.check_consistency(&factory.provider()?, has_receipt_pruning)?
+    {
+        bail!("static files are behind the database ({target}), the snapshot must be retaken")
+    }
+
+    let provider = factory.provider()?;
+    let stage_checkpoints = provider
+        .get_all_checkpoints()?
+        .into_iter()
+        .map(|(stage, checkpoint)| (stage, checkpoint.block_number))
+        .collect::<BTreeMap<_, _>>();
+    let block_number = stage_checkpoints.get("Finish").copied().unwrap_or_default();
+    let static_files = [
+        StaticFileSegment::Headers,
+        StaticFileSegment::Transactions,
+        StaticFileSegment::Receipts,
+        StaticFileSegment::AccountChangeSets,
+        StaticFileSegment::StorageChangeSets,
+    ]
+    .into_iter()
+    .map(|segment| {
+        (
+            segment,
+            SegmentRange {
+                highest_block: static_file_provider.get_highest_static_file_block(segment),
+                highest_tx: static_file_provider.get_highest_static_file_tx(segment),
+            },
+        )
+    })
+    .collect();
+    drop(provider);
+    drop(static_file_provider);
+    drop(factory);
+
+    let manifest = SnapshotManifest {
+        chain_id: env.chain.chain().id(),
+        db_version: DB_VERSION,
+        block_number,
+        stage_checkpoints,
+        static_files,
+        files: snapshot_files(path)?,
+    };
+    reth_fs_util::write_json_file(&path.join(MANIFEST_FILE_NAME), &manifest)?;
+
+    info!(target: "reth::cli", block_number, path = %path.display(), "Created snapshot");
+
+    Ok(())
+}
+
+fn restore<N: CliNodeTypes, C: ChainSpecParser<ChainSpec = N::ChainSpec>>(
+    env: &EnvironmentArgs<C>,
+    path: &Path,
+) -> eyre::Result<()> {
+    let manifest: SnapshotManifest = reth_fs_util::read_json_file(&path.join(MANIFEST_FILE_NAME))?;
+    ensure!(
+        manifest.chain_id == env.chain.chain().id(),
+        "snapshot is for chain {}, not for chain {}",
+        manifest.chain_id,
+        env.chain.chain().id()
+    );
+    ensure!(
+        manifest.db_version == DB_VERSION,
+        "snapshot has database version {}, expected {DB_VERSION}",
+        manifest.db_version
+    );
+    ensure!(
+        get_db_version(path.join(DB_DIR))? == manifest.db_version,
+        "database version of the snapshot does not match its manifest"
+    );
+    verify_files(path, &manifest.files)?;
+    info!(target: "reth::cli", files = manifest.files.len(), "Verified snapshot");
+
+    let data_dir = env.datadir.clone().resolve_datadir(env.chain.chain());
+    for dir in [data_dir.db(), data_dir.static_files()] {
+        ensure!(
+            !dir.exists() || reth_fs_util::read_dir(&dir)?.next().is_none(),
+            "{} is not empty",
+            dir.display()
+        );
+    }
+
+    for file in &manifest.files {
+        let target = match file.path.split_once('/') {
+            Some((DB_DIR, name)) => data_dir.db().join(name),
+            Some((STATIC_FILES_DIR, name)) => data_dir.static_files().join(name),
+            _ => bail!("unexpected file {} in snapshot manifest", file.path),
+        };
+        let Some(parent) = target.parent() else {
+            bail!("invalid target path {} of snapshot file {}", target.display(), file.path)
+        };
+        reth_fs_util::create_dir_all(parent)?;
+        io::copy(
+            &mut reth_fs_util::open(path.join(&file.path))?,
+            &mut reth_fs_util::create_file(&target)?,
+        )?;
+    }
+
+    // opening the restored storage verifies the genesis and the consistency of the static files
+    let Environment { provider_factory, .. } = env.init::<N>(AccessRights::RW)?;
+    let block_number = provider_factory.provider()?.best_block_number()?;
+
+    info!(target: "reth::cli", block_number, "Restored snapshot");
+
+    Ok(())
+}
+
+/// Copies all entries of a table into the database of the snapshot.
+struct CopyViewer<'a, TX> {
+    tx: &'a TX,
+    db: &'a DatabaseEnv,
+}
+
+impl<TX: DbTx> TableViewer<usize> for CopyViewer<'_, TX> {
+    type Error = eyre::Report;
+
+    fn view<T: Table>(&self) -> Result<usize, Self::Error> {
+        let mut cursor = self.tx.cursor_read::<RawTable<T>>()?;
+        let mut walker = cursor.walk(None)?;
+        let mut entries = 0;
+        loop {
+            let tx = self.db.tx_mut()?;
+            let mut snapshot_cursor = tx.cursor_write::<RawTable<T>>()?;
+            let mut batch = 0;
+            for entry in walker.by_ref().take(COPY_BATCH_SIZE) {
+                let (key, value) = entry?;
+                snapshot_cursor.append(key, value)?;
+                batch += 1;
+            }
+            drop(snapshot_cursor);
+            tx.commit()?;
+
+            entries += batch;
+            if batch < COPY_BATCH_SIZE {
+                return Ok(entries)
+            }
+        }
+    }
+
+    fn view_dupsort<T: DupSort>(&self) -> Result<usize, Self::Error> {
+        let mut cursor = self.tx.cursor_dup_read::<RawDupSort<T>>()?;
+        let mut walker = cursor.walk(None)?;
+        let mut entries = 0;
+        loop {
+            let tx = self.db.tx_mut()?;
+            let mut snapshot_cursor = tx.cursor_dup_write::<RawDupSort<T>>()?;
+            let mut batch = 0;
+            for entry in walker.by_ref().take(COPY_BATCH_SIZE) {
+                let (key, value) = entry?;
+                snapshot_cursor.append_dup(key, value)?;
+                batch += 1;
+            }
+            drop(snapshot_cursor);
+            tx.commit()?;
+
+            entries += batch;
+            if batch < COPY_BATCH_SIZE {
+                return Ok(entries)
+            }
+        }
+    }
+}
+
+/// Copies the static files up to the height of the database that is read by the provider, and
+/// returns the static file provider of the copy.
+///
+/// Static files whose block range ends before the range of the last copied block of their segment
+/// are not written to anymore, and are copied as they are. The rows of the last static file of
+/// every segment are read through a static file provider, which only sees committed rows, and are
+/// written to the copy up to the height of the database. The node can therefore keep appending to
+/// its static files while they are copied.
+fn copy_static_files<Provider>(
+    provider: &Provider,
+    from: &Path,
+    to: &Path,
+) -> eyre::Result<StaticFileProvider<EthPrimitives>>
+where
+    Provider: StageCheckpointReader + BlockBodyIndicesProvider,
+{
+    reth_fs_util::create_dir_all(to)?;
+    let source = StaticFileProvider::<EthPrimitives>::read_only(from, false)?;
+
+    // the height of every segment is capped by the checkpoint of the stage that writes it, like
+    // in the consistency check of the static files
+    let mut heights = BTreeMap::new();
+    for (segment, stage) in [
+        (StaticFileSegment::Headers, StageId::Headers),
+        (StaticFileSegment::Transactions, StageId::Bodies),
+        (StaticFileSegment::Receipts, StageId::Execution),
+        (StaticFileSegment::AccountChangeSets, StageId::Execution),
+        (StaticFileSegment::StorageChangeSets, StageId::Execution),
+    ] {
+        let Some(highest) = source.get_highest_static_file_block(segment) else { continue };
+        let checkpoint = provider.get_stage_checkpoint(stage)?.unwrap_or_default().block_number;
+        let height = highest.min(checkpoint);
+        heights.insert(segment, (source.find_fixed_range(height).start(), height));
+    }
+
+    for entry in reth_fs_util::read_dir(from)? {
+        let path = entry?.path();
+        let Some(name) = path.file_name().and_then(|name| name.to_str()) else { continue };
+        let jar = match name.rsplit_once('.') {
+            Some((jar, "conf" | "off" | "idx")) => jar,
+            _ => name,
+        };
+        let Some((segment, range)) = StaticFileSegment::parse_filename(jar) else { continue };
+        if heights.get(&segment).is_some_and(|(tail_start, _)| range.end() < *tail_start) {
+            io::copy(
+                &mut reth_fs_util::open(&path)?,
+                &mut reth_fs_util::create_file(to.join(name))?,
+            )?;
+        }
+    }
+
+    let target = StaticFileProvider::<EthPrimitives>::read_write(to)?;
+    for (segment, (tail_start, height)) in heights {
+        let mut writer = target.get_writer(tail_start, segment)?;
+        for number in tail_start..=height {
+            match segment {
+                StaticFileSegment::Headers => {
+                    let header = source
+                        .header_by_number(number)?
+                        .ok_or_else(|| ProviderError::HeaderNotFound(number.into()))?;
+                    let total_difficulty = source
+                        .header_td_by_number(number)?
+                        .ok_or(ProviderError::TotalDifficultyNotFound(number))?;
+                    let hash = source
+                        .block_hash(number)?
+                        .ok_or_else(|| ProviderError::HeaderNotFound(number.into()))?;
+                    writer.append_header(&header, total_difficulty, &hash)?;
+                }
+                StaticFileSegment::Transactions | StaticFileSegment::Receipts => {
+                    writer.increment_block(number)?;
+                    let body = provider
+                        .block_body_indices(number)?
+                        .ok_or(ProviderError::BlockBodyIndicesNotFound(number))?;
+                    for tx_num in body.tx_num_range() {
+                        if segment.is_receipts() {
+                            let receipt = source
+                                .receipt(tx_num)?
+                                .ok_or(ProviderError::ReceiptNotFound(tx_num.into()))?;
+                            writer.append_receipt(tx_num, &receipt)?;
+                        } else {
+                            let transaction = source
+                                .transaction_by_id(tx_num)?
+                                .ok_or(ProviderError::TransactionNotFound(tx_num.into()))?;
+                            writer.append_transaction(tx_num, &transaction)?;
+                        }
+                    }
+                }
+                StaticFileSegment::AccountChangeSets => {
+                    let changes = source.account_block_changeset(number)?;
+                    writer.append_account_changeset(number, &StoredAccountChangeSet { changes })?;
+                }
+                StaticFileSegment::StorageChangeSets => {
+                    let changes = source
+                        .storage_changeset(number)?
+                        .into_iter()
+                        .map(|(key, entry)| StorageBeforeTx { address: key.address(), entry })
+                        .collect();
+                    writer.append_storage_changeset(number, &StoredStorageChangeSet { changes })?;
+                }
+            }
+        }
+        writer.commit()?;
+    }
+
+    Ok(target)
+}
+
+/// Returns the database and static files of the snapshot, without lock files.
+fn snapshot_files(path: &Path) -> eyre::Result<Vec<SnapshotFile>> {
+    let mut files = Vec::new();
+    for dir in [DB_DIR, STATIC_FILES_DIR] {
+        for entry in reth_fs_util::read_dir(path.join(dir))? {
+            let file_path = entry?.path();
+            let Some(name) = file_path.file_name().and_then(|name| name.to_str()) else { continue };
+            if name == "lock" || name == "mdbx.lck" {
+                continue
+            }
+            let (size, hash) = hash_file(&file_path)?;
+            files.push(SnapshotFile { path: format!("{dir}/{name}"), size, hash });
+        }
+    }
+    files.sort_unstable_by(|a, b| a.path.cmp(&b.path));
+    Ok(files)
+}
+
+/// Verifies the size and hash of all files of the snapshot.
+fn verify_files(path: &Path, files: &[SnapshotFile]) -> eyre::Result<()> {
+    for file in files {
+        let (size, hash) = hash_file(&path.join(&file.path))?;
+        ensure!(
+            size == file.size && hash == file.hash,
+            "snapshot file {} does not match the manifest",
+            file.path
+        );
+    }
+    Ok(())
+}
+
+/// Returns the size and keccak256 hash of a file.
+fn hash_file(path: &Path) -> eyre::Result<(u64, B256)> {
+    let mut file = reth_fs_util::open(path)?;
+    let mut hasher = Keccak256::new();
+    let mut buf = vec![0; 1 << 20];
+    let mut size = 0;
+    loop {
+        let read = file.read(&mut buf)?;
+        if read == 0 {
+            break
+        }
+        hasher.update(&buf[..read]);
+        size += read as u64;
+    }
+    Ok((size, hasher.finalize()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_ethereum_cli::chainspec::EthereumChainSpecParser;
+    use reth_node_ethereum::EthereumNode;
+
+    fn env(datadir: &Path) -> EnvironmentArgs<EthereumChainSpecParser> {
+        EnvironmentArgs::parse_from([
+            "reth",
+            "--chain",
+            "dev",
+            "--datadir",
+            datadir.to_str().unwrap(),
+        ])
+    }
+
+    #[test]
+    fn snapshot_roundtrip() {
+        let source = tempfile::tempdir().unwrap();
+        let target = tempfile::tempdir().unwrap();
+        let snapshot = tempfile::tempdir().unwrap();
+        let snapshot_path = snapshot.path().join("snapshot");
+
+        let source_env = env(source.path());
+        let genesis_hash = {
+            let Environment { provider_factory, .. } =
+                source_env.init::<EthereumNode>(AccessRights::RW).unwrap();
+            provider_factory.block_hash(0).unwrap().unwrap()
+        };
+
+        create::<EthereumNode, _>(&source_env, &snapshot_path).unwrap();
+        let manifest: SnapshotManifest =
+            reth_fs_util::read_json_file(&snapshot_path.join(MANIFEST_FILE_NAME)).unwrap();
+        assert_eq!(manifest.block_number, 0);
+        assert_eq!(manifest.static_files[&StaticFileSegment::Headers].highest_block, Some(0));
+
+        let target_env = env(target.path());
+        restore::<EthereumNode, _>(&target_env, &snapshot_path).unwrap();
+
+        let Environment { provider_factory, .. } =
+            target_env.init::<EthereumNode>(AccessRights::RO).unwrap();
+        assert_eq!(provider_factory.block_hash(0).unwrap(), Some(genesis_hash));
+        assert_eq!(provider_factory.provider().unwrap().best_block_number().unwrap(), 0);
+
+        // a restored snapshot is never overwritten
+        assert!(restore::<EthereumNode, _>(&target_env, &snapshot_path).is_err());
+    }
+
+    #[test]
+    fn verify_snapshot_files() {
+        let dir = tempfile::tempdir().unwrap();
+        for sub_dir in [DB_DIR, STATIC_FILES_DIR] {
+            reth_fs_util::create_dir_all(dir.path().join(sub_dir)).unwrap();
+        }
+        reth_fs_util::write(dir.path().join("db/mdbx.dat"), b"database").unwrap();
+        reth_fs_util::write(dir.path().join("db/lock"), b"1").unwrap();
+        reth_fs_util::write(dir.path().join("static_files/static_file_headers_0_499999"), b"jar")
+            .unwrap();
+
+        let files = snapshot_files(dir.path()).unwrap();
+        assert_eq!(
+            files.iter().map(|file| file.path.as_str()).collect::<Vec<_>>(),
+            ["db/mdbx.dat", "static_files/static_file_headers_0_499999"]
+        );
+        verify_files(dir.path(), &files).unwrap();
+
+        reth_fs_util::write(dir.path().join("db/mdbx.dat"), b"databasf").unwrap();
+        assert!(verify_files(dir.path(), &files).is_err());
+    }
+}
@@ -0,0 +1,214 @@
+//! Command that exports pre-merge history to era1 archives.
+use crate::common::{AccessRights, CliNodeTypes, Environment, EnvironmentArgs};
+use alloy_consensus::Header;
+use alloy_primitives::{B256, U256};
+use clap::Parser;
+use eyre::{bail, OptionExt};
+use reth_chainspec::{EthChainSpec, EthereumHardfork, EthereumHardforks, Hardforks};
+use reth_cli::chainspec::ChainSpecParser;
+use reth_era::{era1_file_name, receipt_envelope, Era1Block, Era1Writer, MAX_BLOCKS_PER_ERA1};
+use reth_evm::{env::EvmEnv, ConfigureEvm};
+use reth_node_core::version::SHORT_VERSION;
+use reth_primitives::{BlockWithSenders, SealedHeader, TransactionSigned};
+use reth_provider::{
+    BlockHashReader, BlockReader, HashedPostStateProvider, HeaderProvider, ReceiptProvider,
+    StageCheckpointReader, StateProviderBox, StateRootProvider, TransactionVariant,
+};
+use reth_revm::{
+    database::StateProviderDatabase,
+    db::{states::bundle_state::BundleRetention, State},
+    primitives::{db::DatabaseCommit, EnvWithHandlerCfg},
+};
+use reth_stages::StageId;
+use std::{io::BufWriter, path::PathBuf, sync::Arc};
+use tracing::info;
+
+/// Exports pre-merge history to era1 archives.
+///
+/// The blocks must have been executed, as the archives contain the receipts of the blocks.
+#[derive(Debug, Parser)]
+pub struct ExportEraCommand<C: ChainSpecParser> {
+    #[command(flatten)]
+    env: EnvironmentArgs<C>,
+
+    /// The first epoch of 8192 blocks to export.
+    #[arg(long, value_name = "EPOCH", default_value_t = 0)]
+    first_epoch: u64,
+
+    /// The last epoch of 8192 blocks to export.
+    ///
+    /// Defaults to the last epoch that is complete in the database. The epoch of the merge
+    /// ends with the last block before the merge.
+    #[arg(long, value_name = "EPOCH")]
+    last_epoch: Option<u64>,
+
+    /// The directory to write the era1 files to.
+    ///
+    /// Receipts of blocks before Byzantium contain the state root after every transaction,
+    /// which is not stored, so these blocks are re-executed to recompute the state roots.
+    #[arg(value_name = "EXPORT_PATH", verbatim_doc_comment)]
+    path: PathBuf,
+}
+
+impl<C: ChainSpecParser<ChainSpec: EthChainSpec + EthereumHardforks>> ExportEraCommand<C> {
+    /// Execute `export-era` command
+    pub async fn execute<N, E, F>(self, evm_config: F) -> eyre::Result<()>
+    where
+        N: CliNodeTypes<ChainSpec = C::ChainSpec>,
+        E: ConfigureEvm<Header = Header, Transaction = TransactionSigned>,
+        F: FnOnce(Arc<N::ChainSpec>) -> E,
+    {
+        info!(target: "reth::cli", "reth {} starting", SHORT_VERSION);
+
+        let Environment { provider_factory, .. } = self.env.init::<N>(AccessRights::RO)?;
+        let provider = provider_factory.provider()?;
+        let chain_spec = provider.chain_spec();
+        let evm_config = evm_config(chain_spec.clone());
+        let paris = chain_spec.fork(EthereumHardfork::Paris);
+        let chain = chain_spec.chain();
+        let network =
+            chain.named().map_or_else(|| chain.id().to_string(), |chain| chain.to_string());
+
+        // receipts are only available for executed blocks
+        let last_block =
+            provider.get_stage_checkpoint(StageId::Execution)?.unwrap_or_default().block_number;
+
+        reth_fs_util::create_dir_all(&self.path)?;
+
+        for epoch in self.first_epoch..=self.last_epoch.unwrap_or(u64::MAX) {
+            let start = epoch * MAX_BLOCKS_PER_ERA1 as u64;
+            let tmp_path = self.path.join(format!("{network}-{epoch:05}.era1.tmp"));
+            let mut writer =
+                Era1Writer::new(BufWriter::new(reth_fs_util::create_file(&tmp_path)?))?;
+
+            let mut merged = false;
+            for number in start..start + MAX_BLOCKS_PER_ERA1 as u64 {
+                if number > last_block {
+                    drop(writer);
+                    reth_fs_util::remove_file(&tmp_path)?;
+                    if self.last_epoch.is_some() {
+                        bail!(
+                            "epoch {epoch} is not complete, blocks are executed up to {last_block}"
+                        )
+                    }
+                    info!(target: "reth::cli", epoch, last_block, "Stopping at incomplete epoch");
+                    return Ok(())
+                }
+
+                let block = provider
+                    .block_with_senders(number.into(), TransactionVariant::NoHash)?
+                    .ok_or_eyre("missing block")?;
+                let total_difficulty =
+                    provider.header_td_by_number(number)?.ok_or_eyre("missing total difficulty")?;
+                if paris.active_at_ttd(total_difficulty, block.header.difficulty) {
+                    merged = true;
+                    break
+                }
+                let hash = provider.block_hash(number)?.ok_or_eyre("missing block hash")?;
+                let receipts = provider
+                    .receipts_by_block(number.into())?
+                    .ok_or_else(|| eyre::eyre!("receipts of block {number} are pruned"))?;
+
+                let receipts =
+                    if chain_spec.is_byzantium_active_at_block(number) || receipts.is_empty() {
+                        receipts.iter().map(|receipt| receipt_envelope(receipt, None)).collect()
+                    } else {
+                        let roots = intermediate_state_roots(
+                            provider_factory.history_by_block_number(number - 1)?,
+                            &evm_config,
+                            &block,
+                            total_difficulty,
+                            chain_spec.is_spurious_dragon_active_at_block(number),
+                        )?;
+                        receipts
+                            .iter()
+                            .zip(roots)
+                            .map(|(receipt, root)| receipt_envelope(receipt, Some(root)))
+                            .collect()
+                    };
+
+                let BlockWithSenders { block, .. } = block;
+                writer.append(&Era1Block {
+                    header: SealedHeader::new(block.header, hash),
+                    body: block.body,
+                    receipts,
+                    total_difficulty,
+                })?;
+            }
+
+            if writer.is_empty() {
+                drop(writer);
+                reth_fs_util::remove_file(&tmp_path)?;
+                break
+            }
+            let (_, root) = writer.finish()?;
+            let path = self.path.join(era1_file_name(&network, epoch, root));
+            reth_fs_util::rename(&tmp_path, &path)?;
+            info!(target: "reth::cli", epoch, path = %path.display(), "Exported era1 file");
+
+            if merged {
+                break
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Re-executes a block on top of the state of its parent and returns the state root after each of
+/// its transactions.
+fn intermediate_state_roots<E>(
+    state: StateProviderBox,
+    evm_config: &E,
+    block: &BlockWithSenders,
+    total_difficulty: U256,
+    state_clear: bool,
+) -> eyre::Result<Vec<B256>>
+where
+    E: ConfigureEvm<Header = Header, Transaction = TransactionSigned>,
+{
+    let mut db = State::builder()
+        .with_database(StateProviderDatabase::new(&state))
+        .with_bundle_update()
+        .build();
+    db.set_state_clear_flag(state_clear);
+
+    let EvmEnv { cfg_env_with_handler_cfg, block_env } =
+        evm_config.cfg_and_block_env(&block.header, total_difficulty);
+    let env = EnvWithHandlerCfg::new_with_cfg_env(
+        cfg_env_with_handler_cfg,
+        block_env,
+        Default::default(),
+    );
+    let mut evm = evm_config.evm_with_env(&mut db, env);
+
+    let mut roots = Vec::with_capacity(block.body.transactions.len());
+    for (sender, transaction) in block.transactions_with_sender() {
+        evm_config.fill_tx_env(evm.tx_mut(), transaction, *sender);
+        let result = evm.transact().map_err(|err| {
+            eyre::eyre!("failed to execute transaction {}: {err}", transaction.hash())
+        })?;
+        evm.db_mut().commit(result.state);
+        evm.db_mut().merge_transitions(BundleRetention::PlainState);
+
+        let hashed_state = state.hashed_post_state(&evm.db_mut().bundle_state);
+        roots.push(state.state_root(hashed_state)?);
+    }
+
+    Ok(roots)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_ethereum_cli::chainspec::EthereumChainSpecParser;
+
+    #[test]
+    fn parse_export_era_command() {
+        let args: ExportEraCommand<EthereumChainSpecParser> =
+            ExportEraCommand::parse_from(["reth", "--last-epoch", "3", "era1"]);
+        assert_eq!(args.first_epoch, 0);
+        assert_eq!(args.last_epoch, Some(3));
+        assert_eq!(args.path, PathBuf::from("era1"));
+    }
+}
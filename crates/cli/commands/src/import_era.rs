@@ -0,0 +1,266 @@
+//! Command that imports pre-merge history from era1 archives.
+use crate::common::{AccessRights, CliNodeTypes, Environment, EnvironmentArgs};
+use alloy_consensus::{BlockHeader, Eip658Value, ReceiptEnvelope};
+use alloy_primitives::U256;
+use clap::Parser;
+use eyre::{bail, ensure, OptionExt};
+use reth_beacon_consensus::EthBeaconConsensus;
+use reth_chainspec::{EthChainSpec, EthereumHardforks};
+use reth_cli::chainspec::ChainSpecParser;
+use reth_consensus::{Consensus, HeaderValidator};
+use reth_db::tables;
+use reth_db_api::transaction::DbTxMut;
+use reth_era::{parse_era1_file_name, receipt_envelope, Era1Block, Era1Reader};
+use reth_evm::execute::{BatchExecutor, BlockExecutorProvider};
+use reth_node_core::version::SHORT_VERSION;
+use reth_primitives::{
+    proofs::calculate_receipt_root, Block, EthPrimitives, SealedBlock, StaticFileSegment,
+};
+use reth_provider::{
+    providers::ProviderNodeTypes, BlockHashReader, BlockWriter, ChainSpecProvider, HeaderProvider,
+    LatestStateProviderRef, OriginalValuesKnown, ProviderFactory, StageCheckpointReader,
+    StageCheckpointWriter, StateWriter, StaticFileProviderFactory, StaticFileWriter,
+    StorageLocation,
+};
+use reth_revm::database::StateProviderDatabase;
+use reth_stages::{StageCheckpoint, StageId};
+use std::{
+    io::BufReader,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+use tracing::info;
+
+/// Imports pre-merge history from era1 archives.
+///
+/// The headers and transactions of the blocks are written to the static files directly. The
+/// blocks are then executed, and the receipts they produce are checked against the receipts of
+/// the archive before they are written. The remaining stages run once the node is started.
+#[derive(Debug, Parser)]
+pub struct ImportEraCommand<C: ChainSpecParser> {
+    #[command(flatten)]
+    env: EnvironmentArgs<C>,
+
+    /// The path to an era1 file, or to a directory of era1 files.
+    ///
+    /// Every file is verified against its accumulator root before it is committed. The blocks
+    /// must continue the local chain, which must be fully executed, blocks that are already in
+    /// the database are skipped.
+    ///
+    /// The receipts of every block are verified against the receipts root of its header, and
+    /// against the receipts of executing the block.
+    #[arg(value_name = "IMPORT_PATH", verbatim_doc_comment)]
+    path: PathBuf,
+}
+
+impl<C: ChainSpecParser<ChainSpec: EthChainSpec + EthereumHardforks>> ImportEraCommand<C> {
+    /// Execute `import-era` command
+    pub async fn execute<N, E, F>(self, executor: F) -> eyre::Result<()>
+    where
+        N: CliNodeTypes<ChainSpec = C::ChainSpec>,
+        E: BlockExecutorProvider<Primitives = EthPrimitives>,
+        F: FnOnce(Arc<N::ChainSpec>) -> E,
+    {
+        info!(target: "reth::cli", "reth {} starting", SHORT_VERSION);
+
+        let Environment { provider_factory, .. } = self.env.init::<N>(AccessRights::RW)?;
+        let consensus = EthBeaconConsensus::new(provider_factory.chain_spec());
+        let executor = executor(provider_factory.chain_spec());
+
+        let files = era1_files(&self.path)?;
+        info!(target: "reth::cli", files = files.len(), "Importing era1 files");
+
+        for path in files {
+            let provider_rw = provider_factory.provider_rw()?;
+
+            let tip = provider_rw
+                .get_stage_checkpoint(StageId::Headers)?
+                .unwrap_or_default()
+                .block_number;
+            let bodies =
+                provider_rw.get_stage_checkpoint(StageId::Bodies)?.unwrap_or_default().block_number;
+            ensure!(
+                tip == bodies,
+                "headers are synced to block {tip}, but bodies only to block {bodies}, unwind the headers first"
+            );
+            let executed = provider_rw
+                .get_stage_checkpoint(StageId::Execution)?
+                .unwrap_or_default()
+                .block_number;
+            ensure!(
+                tip == executed,
+                "blocks are synced to block {tip}, but only executed to block {executed}, start the node to execute them first"
+            );
+
+            let mut parent = provider_rw.sealed_header(tip)?.ok_or_eyre("missing tip header")?;
+            let mut parent_td =
+                provider_rw.header_td_by_number(tip)?.ok_or_eyre("missing tip total difficulty")?;
+
+            let mut reader = Era1Reader::new(BufReader::new(reth_fs_util::open(&path)?))?;
+            let mut imported = Vec::new();
+            {
+                let static_file_provider = provider_rw.static_file_provider();
+                let mut headers_writer =
+                    static_file_provider.latest_writer(StaticFileSegment::Headers)?;
+
+                while let Some(Era1Block { header, body, receipts, total_difficulty }) =
+                    reader.next_block()?
+                {
+                    let number = header.number;
+                    if number <= tip {
+                        ensure!(
+                            provider_rw.block_hash(number)? == Some(header.hash()),
+                            "block {number} of {} does not match the local chain",
+                            path.display()
+                        );
+                        continue
+                    }
+
+                    consensus.validate_header(&header)?;
+                    consensus.validate_header_against_parent(&header, &parent)?;
+                    ensure!(
+                        total_difficulty == parent_td + header.difficulty(),
+                        "invalid total difficulty of block {number}"
+                    );
+                    consensus
+                        .validate_header_with_total_difficulty(header.header(), total_difficulty)?;
+
+                    let block = SealedBlock::new(header, body);
+                    consensus.validate_block_pre_execution(&block)?;
+                    ensure!(
+                        receipts.len() == block.body.transactions.len(),
+                        "block {number} has {} transactions, but {} receipts",
+                        block.body.transactions.len(),
+                        receipts.len()
+                    );
+                    ensure!(
+                        calculate_receipt_root(&receipts) == block.receipts_root,
+                        "receipts root mismatch of block {number}"
+                    );
+
+                    headers_writer.append_header(&block.header, total_difficulty, &block.hash())?;
+                    provider_rw.tx_ref().put::<tables::HeaderNumbers>(block.hash(), number)?;
+                    provider_rw.append_block_bodies(
+                        vec![(number, Some(block.body.clone()))],
+                        StorageLocation::StaticFiles,
+                    )?;
+
+                    parent = block.header.clone();
+                    parent_td = total_difficulty;
+                    imported.push((block, total_difficulty, receipts));
+                }
+            }
+
+            for stage in [StageId::Headers, StageId::Bodies] {
+                provider_rw.save_stage_checkpoint(stage, StageCheckpoint::new(parent.number))?;
+            }
+            provider_rw.commit()?;
+
+            // the headers must be committed to the static files before the blocks are executed,
+            // so that the executor can look up the hashes of the previous blocks
+            execute_blocks(&provider_factory, &executor, &imported)?;
+
+            info!(target: "reth::cli", file = %path.display(), imported = imported.len(), tip = parent.number, "Imported era1 file");
+        }
+
+        info!(target: "reth::cli", "Era1 import finished, start the node to build the hashed state and the trie of the imported blocks");
+
+        Ok(())
+    }
+}
+
+/// Executes the imported blocks on top of the latest state, verifies their receipts against the
+/// receipts of the era1 file, and writes the state changes and receipts.
+fn execute_blocks<N, E>(
+    provider_factory: &ProviderFactory<N>,
+    executor: &E,
+    blocks: &[(SealedBlock, U256, Vec<ReceiptEnvelope>)],
+) -> eyre::Result<()>
+where
+    N: ProviderNodeTypes<Primitives = EthPrimitives>,
+    E: BlockExecutorProvider<Primitives = EthPrimitives>,
+{
+    let Some((last, _, _)) = blocks.last() else { return Ok(()) };
+
+    let provider_rw = provider_factory.provider_rw()?;
+    let mut batch = executor
+        .batch_executor(StateProviderDatabase::new(LatestStateProviderRef::new(&provider_rw)));
+    batch.set_tip(last.number);
+    for (block, total_difficulty, _) in blocks {
+        let block = block.clone().try_seal_with_senders::<Block>().map_err(|block| {
+            eyre::eyre!("failed to recover the senders of block {}", block.number)
+        })?;
+        batch.execute_and_verify_one((&block.unseal(), *total_difficulty).into())?;
+    }
+    let outcome = batch.finalize();
+
+    for (block, _, receipts) in blocks {
+        let executed = outcome.receipts_by_block(block.number);
+        ensure!(
+            executed.len() == receipts.len(),
+            "block {} produced {} receipts, but the era1 file has {}",
+            block.number,
+            executed.len(),
+            receipts.len()
+        );
+        for (index, (executed, receipt)) in executed.iter().zip(receipts).enumerate() {
+            let executed = executed.as_ref().ok_or_eyre("missing executed receipt")?;
+            // the state root after the transaction of receipts before Byzantium is already
+            // covered by the receipts root
+            let post_state = match receipt.as_receipt().map(|receipt| receipt.status) {
+                Some(Eip658Value::PostState(root)) => Some(root),
+                _ => None,
+            };
+            ensure!(
+                receipt_envelope(executed, post_state) == *receipt,
+                "receipt {index} of block {} does not match the executed receipt",
+                block.number
+            );
+        }
+    }
+
+    provider_rw.write_state(outcome, OriginalValuesKnown::Yes, StorageLocation::StaticFiles)?;
+    provider_rw.save_stage_checkpoint(StageId::Execution, StageCheckpoint::new(last.number))?;
+    provider_rw.commit()?;
+
+    Ok(())
+}
+
+/// Returns the era1 file at the path, or the era1 files of the directory at the path ordered by
+/// epoch.
+fn era1_files(path: &Path) -> eyre::Result<Vec<PathBuf>> {
+    if path.is_file() {
+        return Ok(vec![path.to_path_buf()])
+    }
+
+    let mut files = Vec::new();
+    for entry in reth_fs_util::read_dir(path)? {
+        let path = entry?.path();
+        let Some((_, epoch, _)) =
+            path.file_name().and_then(|name| name.to_str()).and_then(parse_era1_file_name)
+        else {
+            continue
+        };
+        files.push((epoch, path));
+    }
+    if files.is_empty() {
+        bail!("no era1 files found in {}", path.display())
+    }
+    files.sort_unstable();
+
+    Ok(files.into_iter().map(|(_, path)| path).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_ethereum_cli::chainspec::EthereumChainSpecParser;
+
+    #[test]
+    fn parse_import_era_command() {
+        let args: ImportEraCommand<EthereumChainSpecParser> =
+            ImportEraCommand::parse_from(["reth", "--chain", "sepolia", "era1"]);
+        assert_eq!(args.env.chain.chain, 11155111u64);
+        assert_eq!(args.path, PathBuf::from("era1"));
+    }
+}
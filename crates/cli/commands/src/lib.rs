@@ -12,7 +12,9 @@ pub mod common;
 pub mod config_cmd;
 pub mod db;
 pub mod dump_genesis;
+pub mod export_era;
 pub mod import;
+pub mod import_era;
 pub mod init_cmd;
 pub mod init_state;
 pub mod node;
@@ -1,5 +1,6 @@
 //! Configuration files.
 
+use alloy_primitives::{Address, Selector};
 use eyre::eyre;
 use reth_network_types::{PeersConfig, SessionsConfig};
 use reth_prune_types::PruneModes;
@@ -7,8 +8,9 @@ use reth_stages_types::ExecutionStageThresholds;
 use serde::{Deserialize, Deserializer, Serialize};
 use std::{
     ffi::OsStr,
-    fs,
+    fmt, fs,
     path::{Path, PathBuf},
+    str::FromStr,
     time::Duration,
 };
 
@@ -31,6 +33,10 @@ pub struct Config {
     pub peers: PeersConfig,
     /// Configuration for peer sessions.
     pub sessions: SessionsConfig,
+    /// Configuration for the transaction pool.
+    pub txpool: TxPoolConfig,
+    /// Configuration for the propagation of transactions to peers.
+    pub tx_propagation: TxPropagationConfig,
 }
 
 impl Config {
@@ -61,7 +67,8 @@ impl Config {
 
     /// Returns the [`PeersConfig`] for the node.
     ///
-    /// If a peers file is provided, the basic nodes from the file are added to the configuration.
+    /// If a peers file is provided, the basic nodes from the file are added to the configuration,
+    /// along with the state observed about them in previous runs.
     pub fn peers_config_with_basic_nodes_from_file(
         &self,
         peers_file: Option<&Path>,
@@ -239,6 +246,11 @@ pub struct ExecutionConfig {
         deserialize_with = "deserialize_duration"
     )]
     pub max_duration: Option<Duration>,
+    /// The number of blocks after which the execution stage verifies the state root in the
+    /// background while it keeps executing, instead of leaving it to the merkle stage.
+    ///
+    /// Disabled if not set.
+    pub state_root_chunk_size: Option<u64>,
 }
 
 impl Default for ExecutionConfig {
@@ -250,6 +262,7 @@ impl Default for ExecutionConfig {
             max_cumulative_gas: Some(30_000_000 * 50_000),
             // 10 minutes
             max_duration: Some(Duration::from_secs(10 * 60)),
+            state_root_chunk_size: None,
         }
     }
 }
@@ -409,6 +422,7 @@ impl PruneConfig {
                     receipts,
                     account_history,
                     storage_history,
+                    bodies_history,
                     receipts_log_filter,
                 },
         } = other;
@@ -424,6 +438,7 @@ impl PruneConfig {
         self.segments.receipts = self.segments.receipts.or(receipts);
         self.segments.account_history = self.segments.account_history.or(account_history);
         self.segments.storage_history = self.segments.storage_history.or(storage_history);
+        self.segments.bodies_history = self.segments.bodies_history.or(bodies_history);
 
         if self.segments.receipts_log_filter.0.is_empty() && !receipts_log_filter.0.is_empty() {
             self.segments.receipts_log_filter = receipts_log_filter;
@@ -431,6 +446,126 @@ impl PruneConfig {
     }
 }
 
+/// Transaction pool configuration.
+#[derive(Debug, Clone, Default, Deserialize, PartialEq, Eq, Serialize)]
+#[serde(default)]
+pub struct TxPoolConfig {
+    /// Policies a transaction must pass for acceptance into the pool.
+    pub admission: AdmissionConfig,
+}
+
+/// Configuration of the admission policies of the transaction pool.
+///
+/// All policies are disabled by default.
+#[derive(Debug, Clone, Default, Deserialize, PartialEq, Eq, Serialize)]
+#[serde(default)]
+pub struct AdmissionConfig {
+    /// Path to a file with denied addresses, one per line, for example the OFAC sanctions list.
+    ///
+    /// Transactions sent from or to any of the addresses are rejected.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub deny_list_file: Option<PathBuf>,
+    /// Additional denied addresses.
+    pub denied_addresses: Vec<Address>,
+    /// Limit of the number of transactions per sender and interval.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sender_rate_limit: Option<SenderRateLimitConfig>,
+    /// Minimum priority fee in wei of the transactions of the regular subpools.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min_priority_fee: Option<u64>,
+    /// Minimum priority fee in wei of the transactions of the blob subpool.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min_blob_priority_fee: Option<u64>,
+    /// Denied 4-byte function selectors.
+    pub denied_selectors: Vec<Selector>,
+    /// Maximum size in bytes of the input data of a transaction.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_calldata_size: Option<usize>,
+}
+
+/// Limit of the number of transactions per sender and interval.
+#[derive(Debug, Clone, Copy, Deserialize, PartialEq, Eq, Serialize)]
+pub struct SenderRateLimitConfig {
+    /// Maximum number of transactions per sender in an interval.
+    pub max_transactions: usize,
+    /// Length of the interval.
+    #[serde(with = "humantime_serde")]
+    pub interval: Duration,
+}
+
+/// Transaction propagation configuration.
+#[derive(Debug, Clone, Deserialize, PartialEq, Eq, Serialize)]
+#[serde(default)]
+pub struct TxPropagationConfig {
+    /// The policies that adjust how new pending transactions are propagated to peers, applied in
+    /// order.
+    pub policies: Vec<TxPropagationPolicy>,
+    /// Max encoded length of the full transactions sent to a peer per interval, if the
+    /// [`TxPropagationPolicy::ByteBudget`] policy is enabled.
+    ///
+    /// Default: 1 MiB
+    pub byte_budget: usize,
+    /// The interval the byte budget is reset at.
+    ///
+    /// Default: 1s
+    #[serde(with = "humantime_serde")]
+    pub byte_budget_interval: Duration,
+}
+
+impl Default for TxPropagationConfig {
+    fn default() -> Self {
+        Self {
+            policies: Vec::new(),
+            byte_budget: 1024 * 1024,
+            byte_budget_interval: Duration::from_secs(1),
+        }
+    }
+}
+
+/// A policy that adjusts how new pending transactions are propagated to peers.
+#[derive(Debug, Clone, Copy, Deserialize, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum TxPropagationPolicy {
+    /// Send full transactions to the peers that first announced the most transactions that were
+    /// later mined.
+    UsefulPeers,
+    /// Always send full transactions to trusted peers.
+    TrustedFull,
+    /// Only announce the hashes of transactions to inbound peers.
+    InboundHashes,
+    /// Limit the encoded length of the full transactions sent to a peer per interval.
+    ByteBudget,
+}
+
+impl TxPropagationPolicy {
+    /// Returns the name of the policy, as used in the config and on the command line.
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Self::UsefulPeers => "useful-peers",
+            Self::TrustedFull => "trusted-full",
+            Self::InboundHashes => "inbound-hashes",
+            Self::ByteBudget => "byte-budget",
+        }
+    }
+}
+
+impl fmt::Display for TxPropagationPolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for TxPropagationPolicy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        [Self::UsefulPeers, Self::TrustedFull, Self::InboundHashes, Self::ByteBudget]
+            .into_iter()
+            .find(|policy| policy.as_str() == s)
+            .ok_or_else(|| format!("unknown transaction propagation policy: {s}"))
+    }
+}
+
 /// Helper type to support older versions of Duration deserialization.
 fn deserialize_duration<'de, D>(deserializer: D) -> Result<Option<Duration>, D::Error>
 where
@@ -451,7 +586,7 @@ where
 
 #[cfg(test)]
 mod tests {
-    use super::{Config, EXTENSION};
+    use super::{Config, Selector, SenderRateLimitConfig, TxPropagationPolicy, EXTENSION};
     use crate::PruneConfig;
     use alloy_primitives::Address;
     use reth_network_peers::TrustedPeer;
@@ -943,6 +1078,7 @@ receipts = 'full'
                 receipts: Some(PruneMode::Distance(1000)),
                 account_history: None,
                 storage_history: Some(PruneMode::Before(5000)),
+                bodies_history: None,
                 receipts_log_filter: ReceiptsLogPruneConfig(BTreeMap::from([(
                     Address::random(),
                     PruneMode::Full,
@@ -958,6 +1094,7 @@ receipts = 'full'
                 receipts: Some(PruneMode::Full),
                 account_history: Some(PruneMode::Distance(2000)),
                 storage_history: Some(PruneMode::Distance(3000)),
+                bodies_history: Some(PruneMode::Distance(100_000)),
                 receipts_log_filter: ReceiptsLogPruneConfig(BTreeMap::from([
                     (Address::random(), PruneMode::Distance(1000)),
                     (Address::random(), PruneMode::Before(2000)),
@@ -976,9 +1113,30 @@ receipts = 'full'
         assert_eq!(config1.segments.receipts, Some(PruneMode::Distance(1000)));
         assert_eq!(config1.segments.account_history, Some(PruneMode::Distance(2000)));
         assert_eq!(config1.segments.storage_history, Some(PruneMode::Before(5000)));
+        assert_eq!(config1.segments.bodies_history, Some(PruneMode::Distance(100_000)));
         assert_eq!(config1.segments.receipts_log_filter, original_filter);
     }
 
+    #[test]
+    fn test_prune_bodies_history() {
+        let s = r"#
+[prune]
+block_interval = 5
+
+[prune.segments.bodies_history]
+distance = 100000
+#";
+        let conf: Config = toml::from_str(s).unwrap();
+        assert_eq!(conf.prune.unwrap().segments.bodies_history, Some(PruneMode::Distance(100_000)));
+
+        let s = r"#
+[prune.segments.bodies_history]
+distance = 64
+#";
+        let err = toml::from_str::<Config>(s).unwrap_err().to_string();
+        assert!(err.contains("invalid value: integer `64`"), "{}", err);
+    }
+
     #[test]
     fn test_conf_trust_nodes_only() {
         let trusted_nodes_only = r"#
@@ -1019,4 +1177,65 @@ connect_trusted_nodes_only = true
             assert!(conf.peers.trusted_nodes.contains(&node));
         }
     }
+
+    #[test]
+    fn test_txpool_admission() {
+        let reth_toml = r#"
+[txpool.admission]
+deny_list_file = "ofac.txt"
+denied_addresses = ["0xdac17f958d2ee523a2206206994597c13d831ec7"]
+sender_rate_limit = { max_transactions = 16, interval = "1m" }
+min_priority_fee = 1_000_000_000
+denied_selectors = ["0xa9059cbb"]
+max_calldata_size = 65536
+"#;
+
+        let conf: Config = toml::from_str(reth_toml).unwrap();
+        let admission = &conf.txpool.admission;
+        assert_eq!(admission.deny_list_file, Some("ofac.txt".into()));
+        assert_eq!(
+            admission.denied_addresses,
+            vec![Address::from_str("0xdac17f958d2ee523a2206206994597c13d831ec7").unwrap()]
+        );
+        assert_eq!(
+            admission.sender_rate_limit,
+            Some(SenderRateLimitConfig { max_transactions: 16, interval: Duration::from_secs(60) })
+        );
+        assert_eq!(admission.min_priority_fee, Some(1_000_000_000));
+        assert_eq!(admission.min_blob_priority_fee, None);
+        assert_eq!(admission.denied_selectors, vec![Selector::from([0xa9, 0x05, 0x9c, 0xbb])]);
+        assert_eq!(admission.max_calldata_size, Some(65536));
+
+        // roundtrip
+        let conf: Config = toml::from_str(&toml::to_string(&conf).unwrap()).unwrap();
+        assert_eq!(&conf.txpool.admission, admission);
+    }
+
+    #[test]
+    fn test_tx_propagation() {
+        let reth_toml = r#"
+[tx_propagation]
+policies = ["inbound-hashes", "byte-budget", "trusted-full"]
+byte_budget_interval = "500ms"
+"#;
+
+        let conf: Config = toml::from_str(reth_toml).unwrap();
+        let tx_propagation = &conf.tx_propagation;
+        assert_eq!(
+            tx_propagation.policies,
+            vec![
+                TxPropagationPolicy::InboundHashes,
+                TxPropagationPolicy::ByteBudget,
+                TxPropagationPolicy::TrustedFull
+            ]
+        );
+        assert_eq!(tx_propagation.byte_budget, 1024 * 1024);
+        assert_eq!(tx_propagation.byte_budget_interval, Duration::from_millis(500));
+        assert_eq!("useful-peers".parse(), Ok(TxPropagationPolicy::UsefulPeers));
+        assert!("useful_peers".parse::<TxPropagationPolicy>().is_err());
+
+        // roundtrip
+        let conf: Config = toml::from_str(&toml::to_string(&conf).unwrap()).unwrap();
+        assert_eq!(&conf.tx_propagation, tx_propagation);
+    }
 }
@@ -1,6 +1,7 @@
 //! `BeaconConsensusEngine` external API
 
 use crate::{BeaconConsensusEngineEvent, BeaconForkChoiceUpdateError};
+use alloy_primitives::BlockNumber;
 use alloy_rpc_types_engine::{
     ExecutionPayload, ExecutionPayloadSidecar, ForkchoiceState, ForkchoiceUpdated, PayloadStatus,
 };
@@ -9,7 +10,7 @@ use reth_engine_primitives::{
     BeaconEngineMessage, BeaconOnNewPayloadError, EngineApiMessageVersion, EngineTypes,
     OnForkChoiceUpdated,
 };
-use reth_errors::RethResult;
+use reth_errors::{RethError, RethResult};
 use reth_tokio_util::{EventSender, EventStream};
 use tokio::sync::{mpsc::UnboundedSender, oneshot};
 
@@ -97,6 +98,16 @@ where
         let _ = self.to_engine.send(BeaconEngineMessage::TransitionConfigurationExchanged);
     }
 
+    /// Sends a message to the beacon consensus engine to rewind the canonical chain to the given
+    /// block and waits for a response.
+    ///
+    /// Note: this is a destructive action, blocks above the new head are removed.
+    pub async fn set_head(&self, number: BlockNumber) -> RethResult<()> {
+        let (tx, rx) = oneshot::channel();
+        let _ = self.to_engine.send(BeaconEngineMessage::SetHead { number, tx });
+        rx.await.map_err(RethError::other)?
+    }
+
     /// Creates a new [`BeaconConsensusEngineEvent`] listener stream.
     pub fn event_listener(&self) -> EventStream<BeaconConsensusEngineEvent> {
         self.event_sender.new_listener()
@@ -1886,6 +1886,11 @@ where
                         BeaconEngineMessage::TransitionConfigurationExchanged => {
                             this.blockchain.on_transition_configuration_exchanged();
                         }
+                        BeaconEngineMessage::SetHead { tx, .. } => {
+                            let _ = tx.send(Err(RethError::msg(
+                                "setting the head is not supported by the legacy engine",
+                            )));
+                        }
                     }
                     continue
                 }
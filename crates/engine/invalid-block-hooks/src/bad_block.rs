@@ -0,0 +1,315 @@
+use crate::witness::{re_execute, ReExecutedBlock};
+use alloy_consensus::BlockHeader;
+use alloy_primitives::B256;
+use alloy_rpc_types_debug::ExecutionWitness;
+use parking_lot::Mutex;
+use reth_chainspec::{EthChainSpec, EthereumHardforks};
+use reth_engine_primitives::{BadBlockReader, BadBlockRecord, InvalidBlockHook};
+use reth_errors::{RethError, RethResult};
+use reth_evm::ConfigureEvm;
+use reth_primitives::{NodePrimitives, SealedBlockFor, SealedBlockWithSenders, SealedHeader};
+use reth_provider::{BlockExecutionOutput, ChainSpecProvider, StateProviderFactory};
+use reth_tracing::tracing::{debug, warn};
+use reth_trie::updates::TrieUpdates;
+use std::{
+    io::{BufWriter, Write},
+    path::{Path, PathBuf},
+    sync::{mpsc, Arc},
+};
+
+/// The default number of bad blocks that are kept by the [`BadBlockStore`].
+pub const DEFAULT_BAD_BLOCKS_LIMIT: usize = 10;
+
+/// A bounded, on-disk store of bad blocks.
+///
+/// Every block is stored in a separate `<number>_<hash>.json` file in the store directory. If the
+/// store grows beyond its limit, the blocks with the lowest numbers are removed.
+///
+/// The store is cheap to clone, all clones access the same directory.
+#[derive(Debug, Clone)]
+pub struct BadBlockStore {
+    inner: Arc<BadBlockStoreInner>,
+}
+
+#[derive(Debug)]
+struct BadBlockStoreInner {
+    /// The directory the bad blocks are stored in.
+    directory: PathBuf,
+    /// The maximum number of bad blocks to keep.
+    limit: usize,
+    /// Serializes writes to the store.
+    write_lock: Mutex<()>,
+}
+
+impl BadBlockStore {
+    /// Creates a new store in the given directory that keeps at most `limit` bad blocks.
+    ///
+    /// The directory is created on the first insert.
+    pub fn new(directory: impl Into<PathBuf>, limit: usize) -> Self {
+        Self {
+            inner: Arc::new(BadBlockStoreInner {
+                directory: directory.into(),
+                limit,
+                write_lock: Mutex::new(()),
+            }),
+        }
+    }
+
+    /// Returns the directory of the store.
+    pub fn directory(&self) -> &Path {
+        &self.inner.directory
+    }
+
+    /// Inserts a bad block into the store, evicting the lowest blocks if the store is full.
+    pub fn insert(&self, bad_block: &BadBlockRecord) -> eyre::Result<()> {
+        let _lock = self.inner.write_lock.lock();
+
+        reth_fs_util::create_dir_all(&self.inner.directory)?;
+        let path =
+            self.inner.directory.join(format!("{}_{}.json", bad_block.number, bad_block.hash));
+        reth_fs_util::atomic_write_file(&path, |file| {
+            let mut writer = BufWriter::new(file);
+            serde_json::to_writer(&mut writer, bad_block)?;
+            writer.flush()?;
+            Ok::<_, eyre::Report>(())
+        })?;
+
+        let mut entries = self.entries()?;
+        if entries.len() > self.inner.limit {
+            entries.sort_unstable_by_key(|(number, _, _)| std::cmp::Reverse(*number));
+            for (number, hash, path) in entries.drain(self.inner.limit..) {
+                debug!(target: "engine::invalid_block_hooks::bad_block", number, %hash, "Evicting bad block");
+                reth_fs_util::remove_file(path)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Returns the bad block with the given hash, if it is in the store.
+    pub fn get(&self, hash: B256) -> eyre::Result<Option<BadBlockRecord>> {
+        self.entries()?
+            .into_iter()
+            .find(|(_, entry_hash, _)| *entry_hash == hash)
+            .map(|(_, _, path)| Ok(reth_fs_util::read_json_file(&path)?))
+            .transpose()
+    }
+
+    /// Returns all bad blocks in the store, highest block first.
+    pub fn all(&self) -> eyre::Result<Vec<BadBlockRecord>> {
+        let mut entries = self.entries()?;
+        entries.sort_unstable_by_key(|(number, _, _)| std::cmp::Reverse(*number));
+        entries.into_iter().map(|(_, _, path)| Ok(reth_fs_util::read_json_file(&path)?)).collect()
+    }
+
+    /// Returns the number, hash and path of all bad blocks in the store directory.
+    fn entries(&self) -> eyre::Result<Vec<(u64, B256, PathBuf)>> {
+        if !self.inner.directory.exists() {
+            return Ok(Vec::new())
+        }
+
+        let mut entries = Vec::new();
+        for entry in reth_fs_util::read_dir(&self.inner.directory)? {
+            let path = entry?.path();
+            let Some((number, hash)) = path
+                .file_name()
+                .and_then(|name| name.to_str())
+                .and_then(|name| name.strip_suffix(".json"))
+                .and_then(|name| name.split_once('_'))
+            else {
+                continue
+            };
+            let (Ok(number), Ok(hash)) = (number.parse(), hash.parse()) else { continue };
+            entries.push((number, hash, path));
+        }
+
+        Ok(entries)
+    }
+}
+
+impl BadBlockReader for BadBlockStore {
+    fn bad_blocks(&self) -> RethResult<Vec<BadBlockRecord>> {
+        self.all().map_err(RethError::msg)
+    }
+
+    fn bad_block(&self, hash: B256) -> RethResult<Option<BadBlockRecord>> {
+        self.get(hash).map_err(RethError::msg)
+    }
+}
+
+/// Records invalid blocks in a [`BadBlockStore`].
+///
+/// Blocks that could be executed are re-executed to generate an execution witness that is stored
+/// alongside the block. The re-execution and the writes to the store happen on a dedicated
+/// thread, so the engine is not blocked by them.
+pub struct BadBlockHook<N: NodePrimitives> {
+    /// Sender half of the channel to the background thread.
+    to_worker: mpsc::Sender<BadBlockJob<N>>,
+}
+
+impl<N: NodePrimitives> BadBlockHook<N> {
+    /// Spawns the background thread that records the bad blocks and returns the hook that feeds
+    /// it.
+    ///
+    /// The thread exits once the hook is dropped.
+    pub fn spawn<P, EvmConfig>(provider: P, evm_config: EvmConfig, store: BadBlockStore) -> Self
+    where
+        P: StateProviderFactory
+            + ChainSpecProvider<ChainSpec: EthChainSpec + EthereumHardforks>
+            + Send
+            + Sync
+            + 'static,
+        EvmConfig: ConfigureEvm<Header = N::BlockHeader, Transaction = N::SignedTx>,
+    {
+        let (to_worker, from_hook) = mpsc::channel();
+        let worker = BadBlockWorker { provider, evm_config, store, pending_witness: None };
+        std::thread::Builder::new()
+            .name("Bad Block Hook".to_string())
+            .spawn(move || worker.run(from_hook))
+            .unwrap();
+
+        Self { to_worker }
+    }
+
+    /// Sends the job to the background thread.
+    fn send(&self, job: BadBlockJob<N>) {
+        if self.to_worker.send(job).is_err() {
+            warn!(target: "engine::invalid_block_hooks::bad_block", "Bad block recorder has exited");
+        }
+    }
+}
+
+impl<N: NodePrimitives> std::fmt::Debug for BadBlockHook<N> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("BadBlockHook").finish_non_exhaustive()
+    }
+}
+
+impl<N: NodePrimitives> InvalidBlockHook<N> for BadBlockHook<N> {
+    fn on_invalid_block(
+        &self,
+        parent_header: &SealedHeader<N::BlockHeader>,
+        block: &SealedBlockWithSenders<N::Block>,
+        _output: &BlockExecutionOutput<N::Receipt>,
+        _trie_updates: Option<(&TrieUpdates, B256)>,
+    ) {
+        self.send(BadBlockJob::Execute {
+            parent_header: parent_header.clone(),
+            block: block.clone(),
+        });
+    }
+
+    fn on_invalid_block_error(
+        &self,
+        block: &SealedBlockFor<N::Block>,
+        error: &(dyn core::error::Error + Send + Sync),
+    ) {
+        self.send(BadBlockJob::Record { block: block.clone(), error: error.to_string() });
+    }
+}
+
+/// A job of the [`BadBlockWorker`].
+enum BadBlockJob<N: NodePrimitives> {
+    /// Re-execute the block to generate its execution witness.
+    Execute { parent_header: SealedHeader<N::BlockHeader>, block: SealedBlockWithSenders<N::Block> },
+    /// Store the block with the error it was rejected with.
+    Record { block: SealedBlockFor<N::Block>, error: String },
+}
+
+/// Re-executes and stores the bad blocks sent by the [`BadBlockHook`].
+struct BadBlockWorker<P, EvmConfig> {
+    /// The provider to read the historical state and do the EVM execution.
+    provider: P,
+    /// The EVM configuration to use for the execution.
+    evm_config: EvmConfig,
+    /// The store to record the bad blocks in.
+    store: BadBlockStore,
+    /// The witness of the last executed invalid block, waiting for its validation error.
+    pending_witness: Option<(B256, ExecutionWitness)>,
+}
+
+impl<P, EvmConfig> BadBlockWorker<P, EvmConfig> {
+    /// Processes jobs until the hook is dropped.
+    fn run<N>(mut self, from_hook: mpsc::Receiver<BadBlockJob<N>>)
+    where
+        N: NodePrimitives,
+        P: StateProviderFactory + ChainSpecProvider<ChainSpec: EthChainSpec + EthereumHardforks>,
+        EvmConfig: ConfigureEvm<Header = N::BlockHeader, Transaction = N::SignedTx>,
+    {
+        while let Ok(job) = from_hook.recv() {
+            match job {
+                BadBlockJob::Execute { parent_header, block } => {
+                    match re_execute::<N, _, _>(
+                        &self.provider,
+                        &self.evm_config,
+                        &parent_header,
+                        &block,
+                    ) {
+                        Ok(ReExecutedBlock { witness, .. }) => {
+                            self.pending_witness = Some((block.hash(), witness));
+                        }
+                        Err(err) => {
+                            warn!(target: "engine::invalid_block_hooks::bad_block", %err, "Failed to generate witness")
+                        }
+                    }
+                }
+                BadBlockJob::Record { block, error } => {
+                    let witness = self
+                        .pending_witness
+                        .take()
+                        .and_then(|(hash, witness)| (hash == block.hash()).then_some(witness));
+                    let bad_block = BadBlockRecord {
+                        hash: block.hash(),
+                        number: block.number(),
+                        rlp: alloy_rlp::encode(block.unseal::<N::Block>()).into(),
+                        error,
+                        witness,
+                    };
+                    if let Err(err) = self.store.insert(&bad_block) {
+                        warn!(target: "engine::invalid_block_hooks::bad_block", %err, "Failed to store bad block");
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::Bytes;
+
+    fn bad_block(number: u64) -> BadBlockRecord {
+        BadBlockRecord {
+            hash: B256::with_last_byte(number as u8),
+            number,
+            rlp: Bytes::from_static(&[0xc0]),
+            error: "invalid block".to_string(),
+            witness: None,
+        }
+    }
+
+    #[test]
+    fn insert_evicts_lowest_blocks() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = BadBlockStore::new(dir.path().join("bad_blocks"), 2);
+        assert!(store.all().unwrap().is_empty());
+
+        for number in [2, 1, 3] {
+            store.insert(&bad_block(number)).unwrap();
+        }
+
+        assert_eq!(store.all().unwrap(), vec![bad_block(3), bad_block(2)]);
+        assert_eq!(store.get(bad_block(2).hash).unwrap(), Some(bad_block(2)));
+        assert_eq!(store.get(bad_block(1).hash).unwrap(), None);
+
+        // a reopened store sees the same blocks
+        let store = BadBlockStore::new(dir.path().join("bad_blocks"), 2);
+        assert_eq!(store.all().unwrap(), vec![bad_block(3), bad_block(2)]);
+
+        // the blocks are served to the RPC through the reader
+        let reader: Arc<dyn BadBlockReader> = Arc::new(store);
+        assert_eq!(reader.bad_blocks().unwrap(), vec![bad_block(3), bad_block(2)]);
+        assert_eq!(reader.bad_block(bad_block(3).hash).unwrap(), Some(bad_block(3)));
+    }
+}
@@ -1,5 +1,7 @@
 //! Invalid block hook implementations.
 
+mod bad_block;
 mod witness;
 
+pub use bad_block::{BadBlockHook, BadBlockStore, DEFAULT_BAD_BLOCKS_LIMIT};
 pub use witness::InvalidBlockWitnessHook;
@@ -1,6 +1,7 @@
 use alloy_consensus::BlockHeader;
 use alloy_primitives::{keccak256, B256, U256};
 use alloy_rpc_types_debug::ExecutionWitness;
+use alloy_rpc_types_eth::Block;
 use eyre::OptionExt;
 use pretty_assertions::Comparison;
 use reth_chainspec::{EthChainSpec, EthereumHardforks};
@@ -11,17 +12,151 @@ use reth_evm::{
 };
 use reth_primitives::{NodePrimitives, SealedBlockWithSenders, SealedHeader};
 use reth_primitives_traits::SignedTransaction;
-use reth_provider::{BlockExecutionOutput, ChainSpecProvider, StateProviderFactory};
+use reth_provider::{
+    BlockExecutionOutput, ChainSpecProvider, StateProviderBox, StateProviderFactory,
+};
 use reth_revm::{
-    database::StateProviderDatabase, db::states::bundle_state::BundleRetention,
-    primitives::EnvWithHandlerCfg, DatabaseCommit, StateBuilder,
+    database::StateProviderDatabase,
+    db::{states::bundle_state::BundleRetention, BundleState},
+    primitives::EnvWithHandlerCfg,
+    DatabaseCommit, StateBuilder,
 };
 use reth_rpc_api::DebugApiClient;
 use reth_tracing::tracing::warn;
-use reth_trie::{updates::TrieUpdates, HashedStorage};
+use reth_trie::{updates::TrieUpdates, HashedPostState, HashedStorage};
 use serde::Serialize;
 use std::{collections::HashMap, fmt::Debug, fs::File, io::Write, path::PathBuf};
 
+/// The result of re-executing an invalid block.
+pub(crate) struct ReExecutedBlock {
+    /// The execution witness of all state accessed during the re-execution.
+    pub(crate) witness: ExecutionWitness,
+    /// The bundle state after the re-execution.
+    pub(crate) bundle_state: BundleState,
+    /// The hashed state of all accessed accounts and storage slots.
+    pub(crate) hashed_state: HashedPostState,
+    /// The state provider of the parent block.
+    pub(crate) state_provider: StateProviderBox,
+}
+
+/// Re-executes the block on top of its parent state and generates an execution witness for
+/// all accessed state.
+pub(crate) fn re_execute<N, P, EvmConfig>(
+    provider: &P,
+    evm_config: &EvmConfig,
+    parent_header: &SealedHeader<N::BlockHeader>,
+    block: &SealedBlockWithSenders<N::Block>,
+) -> eyre::Result<ReExecutedBlock>
+where
+    N: NodePrimitives,
+    P: StateProviderFactory + ChainSpecProvider<ChainSpec: EthChainSpec + EthereumHardforks>,
+    EvmConfig: ConfigureEvm<Header = N::BlockHeader, Transaction = N::SignedTx>,
+{
+    // TODO(alexey): unify with `DebugApi::debug_execution_witness`
+
+    // Setup database.
+    let mut db = StateBuilder::new()
+        .with_database(StateProviderDatabase::new(
+            provider.state_by_block_hash(parent_header.hash())?,
+        ))
+        .with_bundle_update()
+        .build();
+
+    // Setup environment for the execution.
+    let EvmEnv { cfg_env_with_handler_cfg, block_env } =
+        evm_config.cfg_and_block_env(block.header(), U256::MAX);
+
+    // Setup EVM
+    let mut evm = evm_config.evm_with_env(
+        &mut db,
+        EnvWithHandlerCfg::new_with_cfg_env(
+            cfg_env_with_handler_cfg,
+            block_env,
+            Default::default(),
+        ),
+    );
+
+    let mut system_caller = SystemCaller::new(evm_config.clone(), provider.chain_spec());
+
+    // Apply pre-block system contract calls.
+    system_caller.apply_pre_execution_changes(&block.clone().unseal().block, &mut evm)?;
+
+    // Re-execute all of the transactions in the block to load all touched accounts into
+    // the cache DB.
+    for tx in block.transactions() {
+        evm_config.fill_tx_env(
+            evm.tx_mut(),
+            tx,
+            tx.recover_signer().ok_or_eyre("failed to recover sender")?,
+        );
+        let result = evm.transact()?;
+        evm.db_mut().commit(result.state);
+    }
+
+    drop(evm);
+
+    // use U256::MAX here for difficulty, because fetching it is annoying
+    // NOTE: This is not mut because we are not doing the DAO irregular state change here
+    let balance_increments = post_block_balance_increments(
+        provider.chain_spec().as_ref(),
+        &block.clone().unseal().block,
+        U256::MAX,
+    );
+
+    // increment balances
+    db.increment_balances(balance_increments)?;
+
+    // Merge all state transitions
+    db.merge_transitions(BundleRetention::Reverts);
+
+    // Take the bundle state
+    let bundle_state = db.take_bundle();
+
+    // Initialize a map of preimages.
+    let mut state_preimages = HashMap::default();
+
+    // Grab all account proofs for the data accessed during block execution.
+    //
+    // Note: We grab *all* accounts in the cache here, as the `BundleState` prunes
+    // referenced accounts + storage slots.
+    let mut hashed_state = db.database.hashed_post_state(&bundle_state);
+    for (address, account) in db.cache.accounts {
+        let hashed_address = keccak256(address);
+        hashed_state
+            .accounts
+            .insert(hashed_address, account.account.as_ref().map(|a| a.info.clone().into()));
+
+        let storage = hashed_state
+            .storages
+            .entry(hashed_address)
+            .or_insert_with(|| HashedStorage::new(account.status.was_destroyed()));
+
+        if let Some(account) = account.account {
+            state_preimages.insert(hashed_address, alloy_rlp::encode(address).into());
+
+            for (slot, value) in account.storage {
+                let slot = B256::from(slot);
+                let hashed_slot = keccak256(slot);
+                storage.storage.insert(hashed_slot, value);
+
+                state_preimages.insert(hashed_slot, alloy_rlp::encode(slot).into());
+            }
+        }
+    }
+
+    // Generate an execution witness for the aggregated state of accessed accounts.
+    // Destruct the cache database to retrieve the state provider.
+    let state_provider = db.database.into_inner();
+    let state = state_provider.witness(Default::default(), hashed_state.clone())?;
+    let witness = ExecutionWitness {
+        state: HashMap::from_iter(state),
+        codes: Default::default(),
+        keys: state_preimages,
+    };
+
+    Ok(ReExecutedBlock { witness, bundle_state, hashed_state, state_provider })
+}
+
 /// Generates a witness for the given block and saves it to a file.
 #[derive(Debug)]
 pub struct InvalidBlockWitnessHook<P, EvmConfig> {
@@ -67,110 +202,10 @@ where
         N: NodePrimitives,
         EvmConfig: ConfigureEvm<Header = N::BlockHeader, Transaction = N::SignedTx>,
     {
-        // TODO(alexey): unify with `DebugApi::debug_execution_witness`
-
-        // Setup database.
-        let mut db = StateBuilder::new()
-            .with_database(StateProviderDatabase::new(
-                self.provider.state_by_block_hash(parent_header.hash())?,
-            ))
-            .with_bundle_update()
-            .build();
-
-        // Setup environment for the execution.
-        let EvmEnv { cfg_env_with_handler_cfg, block_env } =
-            self.evm_config.cfg_and_block_env(block.header(), U256::MAX);
-
-        // Setup EVM
-        let mut evm = self.evm_config.evm_with_env(
-            &mut db,
-            EnvWithHandlerCfg::new_with_cfg_env(
-                cfg_env_with_handler_cfg,
-                block_env,
-                Default::default(),
-            ),
-        );
-
-        let mut system_caller =
-            SystemCaller::new(self.evm_config.clone(), self.provider.chain_spec());
-
-        // Apply pre-block system contract calls.
-        system_caller.apply_pre_execution_changes(&block.clone().unseal().block, &mut evm)?;
-
-        // Re-execute all of the transactions in the block to load all touched accounts into
-        // the cache DB.
-        for tx in block.transactions() {
-            self.evm_config.fill_tx_env(
-                evm.tx_mut(),
-                tx,
-                tx.recover_signer().ok_or_eyre("failed to recover sender")?,
-            );
-            let result = evm.transact()?;
-            evm.db_mut().commit(result.state);
-        }
-
-        drop(evm);
-
-        // use U256::MAX here for difficulty, because fetching it is annoying
-        // NOTE: This is not mut because we are not doing the DAO irregular state change here
-        let balance_increments = post_block_balance_increments(
-            self.provider.chain_spec().as_ref(),
-            &block.clone().unseal().block,
-            U256::MAX,
-        );
-
-        // increment balances
-        db.increment_balances(balance_increments)?;
-
-        // Merge all state transitions
-        db.merge_transitions(BundleRetention::Reverts);
-
-        // Take the bundle state
-        let mut bundle_state = db.take_bundle();
-
-        // Initialize a map of preimages.
-        let mut state_preimages = HashMap::default();
-
-        // Grab all account proofs for the data accessed during block execution.
-        //
-        // Note: We grab *all* accounts in the cache here, as the `BundleState` prunes
-        // referenced accounts + storage slots.
-        let mut hashed_state = db.database.hashed_post_state(&bundle_state);
-        for (address, account) in db.cache.accounts {
-            let hashed_address = keccak256(address);
-            hashed_state
-                .accounts
-                .insert(hashed_address, account.account.as_ref().map(|a| a.info.clone().into()));
-
-            let storage = hashed_state
-                .storages
-                .entry(hashed_address)
-                .or_insert_with(|| HashedStorage::new(account.status.was_destroyed()));
-
-            if let Some(account) = account.account {
-                state_preimages.insert(hashed_address, alloy_rlp::encode(address).into());
-
-                for (slot, value) in account.storage {
-                    let slot = B256::from(slot);
-                    let hashed_slot = keccak256(slot);
-                    storage.storage.insert(hashed_slot, value);
-
-                    state_preimages.insert(hashed_slot, alloy_rlp::encode(slot).into());
-                }
-            }
-        }
-
-        // Generate an execution witness for the aggregated state of accessed accounts.
-        // Destruct the cache database to retrieve the state provider.
-        let state_provider = db.database.into_inner();
-        let state = state_provider.witness(Default::default(), hashed_state.clone())?;
+        let ReExecutedBlock { witness: response, mut bundle_state, hashed_state, state_provider } =
+            re_execute::<N, _, _>(&self.provider, &self.evm_config, parent_header, block)?;
 
         // Write the witness to the output directory.
-        let response = ExecutionWitness {
-            state: HashMap::from_iter(state),
-            codes: Default::default(),
-            keys: state_preimages,
-        };
         let re_executed_witness_path = self.save_file(
             format!("{}_{}.witness.re_executed.json", block.number(), block.hash()),
             &response,
@@ -178,8 +213,11 @@ where
         if let Some(healthy_node_client) = &self.healthy_node_client {
             // Compare the witness against the healthy node.
             let healthy_node_witness = futures::executor::block_on(async move {
-                DebugApiClient::debug_execution_witness(healthy_node_client, block.number().into())
-                    .await
+                DebugApiClient::<Block>::debug_execution_witness(
+                    healthy_node_client,
+                    block.number().into(),
+                )
+                .await
             })?;
 
             let healthy_path = self.save_file(
@@ -11,7 +11,8 @@
 pub mod miner;
 pub mod payload;
 pub mod service;
+pub mod state;
 
-pub use miner::MiningMode;
+pub use miner::{LocalDevMiner, LocalMinerHandle, MiningMode};
 pub use payload::LocalPayloadAttributesBuilder;
 pub use service::LocalEngineService;
@@ -1,26 +1,43 @@
 //! Contains the implementation of the mining mode for the local engine.
 
+use crate::state;
 use alloy_consensus::BlockHeader;
-use alloy_primitives::{TxHash, B256};
+use alloy_primitives::{map::HashMap, Address, TxHash, B256};
 use alloy_rpc_types_engine::{CancunPayloadFields, ExecutionPayloadSidecar, ForkchoiceState};
 use eyre::OptionExt;
-use futures_util::{stream::Fuse, StreamExt};
+use futures_util::{
+    future::BoxFuture,
+    stream::{Fuse, StreamExt},
+    FutureExt,
+};
+use reth_chain_state::ExecutedBlock;
 use reth_chainspec::EthereumHardforks;
-use reth_engine_primitives::{BeaconEngineMessage, EngineApiMessageVersion, EngineTypes};
+use reth_engine_primitives::{
+    is_impersonated_signature, AccountPatch, BeaconEngineMessage, DevMiner, DevMiningMode,
+    EngineApiMessageVersion, EngineTypes,
+};
+use reth_errors::{RethError, RethResult};
 use reth_payload_builder::PayloadBuilderHandle;
 use reth_payload_builder_primitives::PayloadBuilder;
-use reth_payload_primitives::{BuiltPayload, PayloadAttributesBuilder, PayloadKind, PayloadTypes};
-use reth_provider::{BlockReader, ChainSpecProvider};
+use reth_payload_primitives::{
+    BuiltPayload, PayloadAttributesBuilder, PayloadBuilderAttributes, PayloadKind, PayloadTypes,
+};
+use reth_provider::{BlockReader, ChainSpecProvider, StateProviderFactory, StateReader};
 use reth_rpc_types_compat::engine::payload::block_to_payload;
 use reth_transaction_pool::TransactionPool;
 use std::{
+    collections::BTreeMap,
+    fmt,
     future::Future,
     pin::Pin,
     task::{Context, Poll},
     time::{Duration, UNIX_EPOCH},
 };
 use tokio::{
-    sync::{mpsc::UnboundedSender, oneshot},
+    sync::{
+        mpsc::{unbounded_channel, UnboundedReceiver, UnboundedSender},
+        oneshot,
+    },
     time::Interval,
 };
 use tokio_stream::wrappers::ReceiverStream;
@@ -34,6 +51,9 @@ pub enum MiningMode {
     Instant(Fuse<ReceiverStream<TxHash>>),
     /// In this mode a block is built at a fixed interval.
     Interval(Interval),
+    /// In this mode blocks are only built when explicitly requested through a
+    /// [`LocalMinerHandle`].
+    Manual,
 }
 
 impl MiningMode {
@@ -48,6 +68,11 @@ impl MiningMode {
         let start = tokio::time::Instant::now() + duration;
         Self::Interval(tokio::time::interval_at(start, duration))
     }
+
+    /// Returns `true` if this is [`MiningMode::Instant`].
+    pub const fn is_instant(&self) -> bool {
+        matches!(self, Self::Instant(_))
+    }
 }
 
 impl Future for MiningMode {
@@ -69,10 +94,154 @@ impl Future for MiningMode {
                 }
                 Poll::Pending
             }
+            Self::Manual => Poll::Pending,
         }
     }
 }
 
+/// Commands that can be sent to a running [`LocalMiner`] through a [`LocalMinerHandle`].
+#[derive(Debug)]
+pub enum LocalMinerCommand {
+    /// Mine the given number of blocks, optionally with a fixed timestamp interval between them.
+    Mine {
+        /// Number of blocks to mine.
+        blocks: u64,
+        /// Seconds between the timestamps of consecutive blocks.
+        interval: Option<u64>,
+        /// Returns the hashes of the mined blocks.
+        tx: oneshot::Sender<eyre::Result<Vec<B256>>>,
+    },
+    /// Replace the current mining mode.
+    SetMode(MiningMode),
+    /// Returns whether the miner is in [`MiningMode::Instant`].
+    IsInstant(oneshot::Sender<bool>),
+    /// Set the timestamp of the next block.
+    SetNextBlockTimestamp {
+        /// The timestamp of the next block.
+        timestamp: u64,
+        /// Returns an error if the timestamp is not after the latest block.
+        tx: oneshot::Sender<eyre::Result<()>>,
+    },
+    /// Shift the clock used for block timestamps forward by the given number of seconds.
+    IncreaseTime {
+        /// Seconds to add to the clock.
+        seconds: u64,
+        /// Returns the total offset of the clock in seconds.
+        tx: oneshot::Sender<i64>,
+    },
+    /// Set the clock used for block timestamps to the given time.
+    SetTime {
+        /// The current time of the clock.
+        timestamp: u64,
+        /// Returns the total offset of the clock in seconds.
+        tx: oneshot::Sender<i64>,
+    },
+    /// Set a fixed interval between the timestamps of consecutive blocks, or remove it.
+    SetBlockTimestampInterval(Option<u64>),
+    /// Add the given state modifications to the overlay that is applied to the next block.
+    ModifyState(HashMap<Address, AccountPatch>),
+    /// Returns the overlay of state modifications that is applied to the next block.
+    PendingState(oneshot::Sender<HashMap<Address, AccountPatch>>),
+    /// Returns all non-empty accounts of the latest state with the overlay applied.
+    DumpState(oneshot::Sender<eyre::Result<BTreeMap<Address, AccountPatch>>>),
+    /// Reorg the chain so that the new head is built on top of the given block.
+    Revert {
+        /// The block to build on top of.
+        block_hash: B256,
+        /// Returns the hash of the new head.
+        tx: oneshot::Sender<eyre::Result<B256>>,
+    },
+}
+
+/// A handle to interact with a running [`LocalMiner`].
+#[derive(Debug, Clone)]
+pub struct LocalMinerHandle {
+    to_miner: UnboundedSender<LocalMinerCommand>,
+}
+
+impl LocalMinerHandle {
+    /// Creates a new handle and the receiving end for the commands.
+    pub fn new() -> (Self, UnboundedReceiver<LocalMinerCommand>) {
+        let (to_miner, rx) = unbounded_channel();
+        (Self { to_miner }, rx)
+    }
+
+    /// Sends a command and waits for the response.
+    async fn request<R>(
+        &self,
+        cmd: impl FnOnce(oneshot::Sender<R>) -> LocalMinerCommand,
+    ) -> eyre::Result<R> {
+        let (tx, rx) = oneshot::channel();
+        self.to_miner.send(cmd(tx)).map_err(|_| eyre::eyre!("local miner is not running"))?;
+        Ok(rx.await?)
+    }
+
+    /// Mines `blocks` blocks and returns their hashes.
+    pub async fn mine(&self, blocks: u64, interval: Option<u64>) -> eyre::Result<Vec<B256>> {
+        self.request(|tx| LocalMinerCommand::Mine { blocks, interval, tx }).await?
+    }
+
+    /// Replaces the mining mode of the miner.
+    pub fn set_mode(&self, mode: MiningMode) -> eyre::Result<()> {
+        self.to_miner
+            .send(LocalMinerCommand::SetMode(mode))
+            .map_err(|_| eyre::eyre!("local miner is not running"))
+    }
+
+    /// Returns `true` if blocks are mined as soon as transactions arrive.
+    pub async fn is_instant(&self) -> eyre::Result<bool> {
+        self.request(LocalMinerCommand::IsInstant).await
+    }
+
+    /// Sets the timestamp of the next mined block.
+    pub async fn set_next_block_timestamp(&self, timestamp: u64) -> eyre::Result<()> {
+        self.request(|tx| LocalMinerCommand::SetNextBlockTimestamp { timestamp, tx }).await?
+    }
+
+    /// Shifts the clock forward and returns the total offset in seconds.
+    pub async fn increase_time(&self, seconds: u64) -> eyre::Result<i64> {
+        self.request(|tx| LocalMinerCommand::IncreaseTime { seconds, tx }).await
+    }
+
+    /// Sets the clock to the given timestamp and returns the total offset in seconds.
+    pub async fn set_time(&self, timestamp: u64) -> eyre::Result<i64> {
+        self.request(|tx| LocalMinerCommand::SetTime { timestamp, tx }).await
+    }
+
+    /// Sets or removes the fixed timestamp interval between blocks.
+    pub fn set_block_timestamp_interval(&self, interval: Option<u64>) -> eyre::Result<()> {
+        self.to_miner
+            .send(LocalMinerCommand::SetBlockTimestampInterval(interval))
+            .map_err(|_| eyre::eyre!("local miner is not running"))
+    }
+
+    /// Adds the given state modifications to the overlay that is applied to the next block.
+    pub fn modify_state(&self, patches: HashMap<Address, AccountPatch>) -> eyre::Result<()> {
+        self.to_miner
+            .send(LocalMinerCommand::ModifyState(patches))
+            .map_err(|_| eyre::eyre!("local miner is not running"))
+    }
+
+    /// Returns the overlay of state modifications that is applied to the next block.
+    pub async fn pending_state(&self) -> eyre::Result<HashMap<Address, AccountPatch>> {
+        self.request(LocalMinerCommand::PendingState).await
+    }
+
+    /// Returns all non-empty accounts of the latest state with the overlay applied.
+    pub async fn dump_state(&self) -> eyre::Result<BTreeMap<Address, AccountPatch>> {
+        self.request(LocalMinerCommand::DumpState).await?
+    }
+
+    /// Reorgs the chain on top of the given block and returns the hash of the new head.
+    ///
+    /// Note: the engine only moves forward, so the new head is a fresh block built on top of
+    /// `block_hash` rather than `block_hash` itself. Its state is the state of `block_hash` plus
+    /// any pending transactions that are still valid.
+    pub async fn revert(&self, block_hash: B256) -> eyre::Result<B256> {
+        self.request(|tx| LocalMinerCommand::Revert { block_hash, tx }).await?
+    }
+}
+
 /// Local miner advancing the chain/
 #[derive(Debug)]
 pub struct LocalMiner<EngineT: EngineTypes, Provider, B> {
@@ -82,6 +251,10 @@ pub struct LocalMiner<EngineT: EngineTypes, Provider, B> {
     payload_attributes_builder: B,
     /// Sender for events to engine.
     to_engine: UnboundedSender<BeaconEngineMessage<EngineT>>,
+    /// Sender for locally built blocks that are inserted into the tree as already executed.
+    to_tree: UnboundedSender<ExecutedBlock>,
+    /// Receiver for commands sent through [`LocalMinerHandle`]s.
+    commands: UnboundedReceiver<LocalMinerCommand>,
     /// The mining mode for the engine
     mode: MiningMode,
     /// The payload builder for the engine
@@ -90,37 +263,61 @@ pub struct LocalMiner<EngineT: EngineTypes, Provider, B> {
     last_timestamp: u64,
     /// Stores latest mined blocks.
     last_block_hashes: Vec<B256>,
+    /// Timestamp to use for the next block, if set.
+    next_timestamp: Option<u64>,
+    /// Offset in seconds that is applied to the system clock.
+    time_offset: i64,
+    /// Fixed interval between block timestamps, if set.
+    timestamp_interval: Option<u64>,
+    /// State modifications to apply to the next block.
+    pending_patches: HashMap<Address, AccountPatch>,
 }
 
 impl<EngineT, Provider, B> LocalMiner<EngineT, Provider, B>
 where
     EngineT: EngineTypes,
-    Provider: BlockReader + ChainSpecProvider<ChainSpec: EthereumHardforks> + 'static,
+    Provider: BlockReader
+        + StateProviderFactory
+        + StateReader
+        + ChainSpecProvider<ChainSpec: EthereumHardforks>
+        + 'static,
     B: PayloadAttributesBuilder<<EngineT as PayloadTypes>::PayloadAttributes>,
 {
     /// Spawns a new [`LocalMiner`] with the given parameters.
+    ///
+    /// Returns a [`LocalMinerHandle`] that can be used to control the miner.
     pub fn spawn_new(
         provider: Provider,
         payload_attributes_builder: B,
         to_engine: UnboundedSender<BeaconEngineMessage<EngineT>>,
+        to_tree: UnboundedSender<ExecutedBlock>,
         mode: MiningMode,
         payload_builder: PayloadBuilderHandle<EngineT>,
-    ) {
+    ) -> LocalMinerHandle {
         let latest_header =
             provider.sealed_header(provider.best_block_number().unwrap()).unwrap().unwrap();
+        let (handle, commands) = LocalMinerHandle::new();
 
         let miner = Self {
             provider,
             payload_attributes_builder,
             to_engine,
+            to_tree,
+            commands,
             mode,
             payload_builder,
             last_timestamp: latest_header.timestamp(),
             last_block_hashes: vec![latest_header.hash()],
+            next_timestamp: None,
+            time_offset: 0,
+            timestamp_interval: None,
+            pending_patches: HashMap::default(),
         };
 
         // Spawn the miner
         tokio::spawn(miner.run());
+
+        handle
     }
 
     /// Runs the [`LocalMiner`] in a loop, polling the miner and building payloads.
@@ -134,6 +331,10 @@ where
                         error!(target: "engine::local", "Error advancing the chain: {:?}", e);
                     }
                 }
+                // Handle commands from the miner handles
+                Some(cmd) = self.commands.recv() => {
+                    self.on_command(cmd).await;
+                }
                 // send FCU once in a while
                 _ = fcu_interval.tick() => {
                     if let Err(e) = self.update_forkchoice_state().await {
@@ -144,6 +345,100 @@ where
         }
     }
 
+    /// Handles a [`LocalMinerCommand`].
+    async fn on_command(&mut self, cmd: LocalMinerCommand) {
+        match cmd {
+            LocalMinerCommand::Mine { blocks, interval, tx } => {
+                let mut hashes = Vec::with_capacity(blocks as usize);
+                let mut res = Ok(());
+                for i in 0..blocks {
+                    if let Some(interval) = interval.filter(|_| i > 0) {
+                        self.next_timestamp = Some(self.last_timestamp + interval);
+                    }
+                    match self.advance().await {
+                        Ok(hash) => hashes.push(hash),
+                        Err(err) => {
+                            res = Err(err);
+                            break
+                        }
+                    }
+                }
+                let _ = tx.send(res.map(|_| hashes));
+            }
+            LocalMinerCommand::SetMode(mode) => self.mode = mode,
+            LocalMinerCommand::IsInstant(tx) => {
+                let _ = tx.send(self.mode.is_instant());
+            }
+            LocalMinerCommand::SetNextBlockTimestamp { timestamp, tx } => {
+                let res = if timestamp <= self.last_timestamp {
+                    Err(eyre::eyre!(
+                        "timestamp {timestamp} is not after the latest block timestamp {}",
+                        self.last_timestamp
+                    ))
+                } else {
+                    self.next_timestamp = Some(timestamp);
+                    Ok(())
+                };
+                let _ = tx.send(res);
+            }
+            LocalMinerCommand::IncreaseTime { seconds, tx } => {
+                self.time_offset = self.time_offset.saturating_add(seconds as i64);
+                let _ = tx.send(self.time_offset);
+            }
+            LocalMinerCommand::SetTime { timestamp, tx } => {
+                self.time_offset = timestamp as i64 - Self::now() as i64;
+                let _ = tx.send(self.time_offset);
+            }
+            LocalMinerCommand::SetBlockTimestampInterval(interval) => {
+                self.timestamp_interval = interval;
+            }
+            LocalMinerCommand::ModifyState(patches) => {
+                for (address, patch) in patches {
+                    self.pending_patches.entry(address).or_default().merge(patch);
+                }
+            }
+            LocalMinerCommand::PendingState(tx) => {
+                let _ = tx.send(self.pending_patches.clone());
+            }
+            LocalMinerCommand::DumpState(tx) => {
+                let _ = tx.send(self.dump_state());
+            }
+            LocalMinerCommand::Revert { block_hash, tx } => {
+                let _ = tx.send(self.revert(block_hash).await);
+            }
+        }
+    }
+
+    /// Returns all non-empty accounts of the latest state with the pending overlay applied.
+    fn dump_state(&self) -> eyre::Result<BTreeMap<Address, AccountPatch>> {
+        let mut accounts = state::dump_state(&self.provider)?;
+        for (address, patch) in &self.pending_patches {
+            let account = accounts.entry(*address).or_default();
+            account.merge(patch.clone());
+            account.storage.retain(|_, value| !value.is_zero());
+        }
+        Ok(accounts)
+    }
+
+    /// Returns the current system time in seconds.
+    fn now() -> u64 {
+        std::time::SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("cannot be earlier than UNIX_EPOCH")
+            .as_secs()
+    }
+
+    /// Returns the timestamp for the next block.
+    fn next_timestamp(&mut self) -> u64 {
+        if let Some(timestamp) = self.next_timestamp.take() {
+            return timestamp
+        }
+        if let Some(interval) = self.timestamp_interval {
+            return self.last_timestamp + interval
+        }
+        std::cmp::max(self.last_timestamp + 1, Self::now().saturating_add_signed(self.time_offset))
+    }
+
     /// Returns current forkchoice state.
     fn forkchoice_state(&self) -> ForkchoiceState {
         ForkchoiceState {
@@ -177,16 +472,9 @@ where
         Ok(())
     }
 
-    /// Generates payload attributes for a new block, passes them to FCU and inserts built payload
-    /// through newPayload.
-    async fn advance(&mut self) -> eyre::Result<()> {
-        let timestamp = std::cmp::max(
-            self.last_timestamp + 1,
-            std::time::SystemTime::now()
-                .duration_since(UNIX_EPOCH)
-                .expect("cannot be earlier than UNIX_EPOCH")
-                .as_secs(),
-        );
+    /// Generates payload attributes for a new block, passes them to FCU and inserts built payload.
+    async fn advance(&mut self) -> eyre::Result<B256> {
+        let timestamp = self.next_timestamp();
 
         let (tx, rx) = oneshot::channel();
         self.to_engine.send(BeaconEngineMessage::ForkchoiceUpdated {
@@ -209,6 +497,33 @@ where
             eyre::bail!("No payload")
         };
 
+        let hash = self.insert_payload(payload).await?;
+        self.last_timestamp = timestamp;
+        self.push_block_hash(hash);
+        self.update_forkchoice_state().await?;
+
+        Ok(hash)
+    }
+
+    /// Inserts a built payload into the tree.
+    ///
+    /// Payloads are submitted through newPayload, so they are validated like any other block. If
+    /// the pending overlay of state modifications is not empty, it is applied to the execution
+    /// output of the payload instead, and the modified block is inserted into the tree directly
+    /// because it is no longer valid on its own.
+    ///
+    /// Payloads that include transactions of impersonated accounts are inserted directly as well,
+    /// because their senders can't be recovered from the signatures.
+    async fn insert_payload(&mut self, payload: EngineT::BuiltPayload) -> eyre::Result<B256> {
+        let executed = payload.executed_block();
+        if !self.pending_patches.is_empty() ||
+            executed.as_ref().is_some_and(has_impersonated_transactions)
+        {
+            let executed =
+                executed.ok_or_eyre("Payload without execution output can't be modified")?;
+            return self.insert_executed(executed)
+        }
+
         let block = payload.block();
 
         let cancun_fields =
@@ -221,7 +536,7 @@ where
 
         let (tx, rx) = oneshot::channel();
         self.to_engine.send(BeaconEngineMessage::NewPayload {
-            payload: block_to_payload(payload.block().clone()),
+            payload: block_to_payload(block.clone()),
             // todo: prague support
             sidecar: cancun_fields
                 .map(ExecutionPayloadSidecar::v3)
@@ -235,14 +550,149 @@ where
             eyre::bail!("Invalid payload")
         }
 
+        Ok(block.hash())
+    }
+
+    /// Builds a block on top of the given block and makes it the new head.
+    async fn revert(&mut self, block_hash: B256) -> eyre::Result<B256> {
+        if self.provider.header(&block_hash)?.is_none() {
+            eyre::bail!("Unknown block {block_hash}")
+        }
+
+        let timestamp = self.next_timestamp();
+        let attributes = EngineT::PayloadBuilderAttributes::try_new(
+            block_hash,
+            self.payload_attributes_builder.build(timestamp),
+            EngineApiMessageVersion::default() as u8,
+        )
+        .map_err(|err| eyre::eyre!("Invalid payload attributes: {err}"))?;
+        let payload_id = self.payload_builder.send_new_payload(attributes).await??;
+
+        let Some(Ok(payload)) =
+            self.payload_builder.resolve_kind(payload_id, PayloadKind::WaitForPending).await
+        else {
+            eyre::bail!("No payload")
+        };
+        let hash = self.insert_payload(payload).await?;
+
+        if let Some(pos) = self.last_block_hashes.iter().position(|hash| *hash == block_hash) {
+            self.last_block_hashes.truncate(pos + 1);
+        } else {
+            self.last_block_hashes = vec![block_hash];
+        }
         self.last_timestamp = timestamp;
-        self.last_block_hashes.push(block.hash());
+        self.push_block_hash(hash);
+        self.update_forkchoice_state().await?;
+
+        Ok(hash)
+    }
+
+    /// Applies the pending state modifications to the block and sends it to the tree.
+    fn insert_executed(&mut self, executed: ExecutedBlock) -> eyre::Result<B256> {
+        let parent_state = self.provider.state_by_block_hash(executed.block().parent_hash)?;
+        let executed = state::apply_patches(
+            executed,
+            std::mem::take(&mut self.pending_patches),
+            &*parent_state,
+        )?;
+
+        let hash = executed.block().hash();
+        self.to_tree.send(executed)?;
+        Ok(hash)
+    }
+
+    /// Records a new head block.
+    fn push_block_hash(&mut self, hash: B256) {
+        self.last_block_hashes.push(hash);
         // ensure we keep at most 64 blocks
         if self.last_block_hashes.len() > 64 {
             self.last_block_hashes =
                 self.last_block_hashes.split_off(self.last_block_hashes.len() - 64);
         }
+    }
+}
 
-        Ok(())
+/// Returns `true` if the block includes a transaction of an impersonated account.
+fn has_impersonated_transactions(executed: &ExecutedBlock) -> bool {
+    executed
+        .block()
+        .body
+        .transactions
+        .iter()
+        .zip(executed.senders.iter())
+        .any(|(tx, sender)| is_impersonated_signature(&tx.signature, *sender))
+}
+
+/// A [`DevMiner`] that controls a running [`LocalMiner`].
+#[derive(Clone)]
+pub struct LocalDevMiner<Pool> {
+    /// Handle to the local miner.
+    handle: LocalMinerHandle,
+    /// The pool that triggers blocks in [`DevMiningMode::Auto`].
+    pool: Pool,
+}
+
+impl<Pool> LocalDevMiner<Pool> {
+    /// Creates a new [`LocalDevMiner`].
+    pub const fn new(handle: LocalMinerHandle, pool: Pool) -> Self {
+        Self { handle, pool }
+    }
+}
+
+impl<Pool> fmt::Debug for LocalDevMiner<Pool> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LocalDevMiner").field("handle", &self.handle).finish_non_exhaustive()
+    }
+}
+
+impl<Pool: TransactionPool + 'static> DevMiner for LocalDevMiner<Pool> {
+    fn mine(&self, blocks: u64, interval: Option<u64>) -> BoxFuture<'_, RethResult<Vec<B256>>> {
+        async move { self.handle.mine(blocks, interval).await.map_err(RethError::msg) }.boxed()
+    }
+
+    fn set_mining_mode(&self, mode: DevMiningMode) -> RethResult<()> {
+        let mode = match mode {
+            DevMiningMode::Auto => MiningMode::instant(self.pool.clone()),
+            DevMiningMode::Interval(duration) => MiningMode::interval(duration),
+            DevMiningMode::Manual => MiningMode::Manual,
+        };
+        self.handle.set_mode(mode).map_err(RethError::msg)
+    }
+
+    fn is_automine(&self) -> BoxFuture<'_, RethResult<bool>> {
+        async move { self.handle.is_instant().await.map_err(RethError::msg) }.boxed()
+    }
+
+    fn set_next_block_timestamp(&self, timestamp: u64) -> BoxFuture<'_, RethResult<()>> {
+        async move { self.handle.set_next_block_timestamp(timestamp).await.map_err(RethError::msg) }
+            .boxed()
+    }
+
+    fn increase_time(&self, seconds: u64) -> BoxFuture<'_, RethResult<i64>> {
+        async move { self.handle.increase_time(seconds).await.map_err(RethError::msg) }.boxed()
+    }
+
+    fn set_time(&self, timestamp: u64) -> BoxFuture<'_, RethResult<i64>> {
+        async move { self.handle.set_time(timestamp).await.map_err(RethError::msg) }.boxed()
+    }
+
+    fn set_block_timestamp_interval(&self, interval: Option<u64>) -> RethResult<()> {
+        self.handle.set_block_timestamp_interval(interval).map_err(RethError::msg)
+    }
+
+    fn modify_state(&self, patches: HashMap<Address, AccountPatch>) -> RethResult<()> {
+        self.handle.modify_state(patches).map_err(RethError::msg)
+    }
+
+    fn pending_state(&self) -> BoxFuture<'_, RethResult<HashMap<Address, AccountPatch>>> {
+        async move { self.handle.pending_state().await.map_err(RethError::msg) }.boxed()
+    }
+
+    fn dump_state(&self) -> BoxFuture<'_, RethResult<BTreeMap<Address, AccountPatch>>> {
+        async move { self.handle.dump_state().await.map_err(RethError::msg) }.boxed()
+    }
+
+    fn revert(&self, block_hash: B256) -> BoxFuture<'_, RethResult<B256>> {
+        async move { self.handle.revert(block_hash).await.map_err(RethError::msg) }.boxed()
     }
 }
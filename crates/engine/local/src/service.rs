@@ -14,9 +14,10 @@ use std::{
     task::{Context, Poll},
 };
 
-use crate::miner::{LocalMiner, MiningMode};
+use crate::miner::{LocalMiner, LocalMinerHandle, MiningMode};
 use futures_util::{Stream, StreamExt};
 use reth_beacon_consensus::{BeaconConsensusEngineEvent, EngineNodeTypes};
+use reth_chain_state::ExecutedBlock;
 use reth_chainspec::EthChainSpec;
 use reth_consensus::FullConsensus;
 use reth_engine_primitives::{BeaconEngineMessage, EngineValidator};
@@ -34,10 +35,11 @@ use reth_evm::execute::BlockExecutorProvider;
 use reth_node_types::BlockTy;
 use reth_payload_builder::PayloadBuilderHandle;
 use reth_payload_primitives::{PayloadAttributesBuilder, PayloadTypes};
+use reth_primitives::EthPrimitives;
 use reth_provider::{providers::BlockchainProvider2, ChainSpecProvider, ProviderFactory};
 use reth_prune::PrunerWithFactory;
 use reth_stages_api::MetricEventsSender;
-use tokio::sync::mpsc::UnboundedSender;
+use tokio::sync::mpsc::{unbounded_channel, UnboundedReceiver, UnboundedSender};
 use tracing::error;
 
 /// Provides a local dev service engine that can be used to drive the
@@ -55,6 +57,10 @@ where
     handler: EngineApiRequestHandler<EngineApiRequest<N::Engine, N::Primitives>, N::Primitives>,
     /// Receiver for incoming requests (from the engine API endpoint) that need to be processed.
     incoming_requests: EngineMessageStream<N::Engine>,
+    /// Receiver for blocks built by the local miner that are inserted as already executed.
+    mined_blocks: UnboundedReceiver<ExecutedBlock<N::Primitives>>,
+    /// Handle to the spawned local miner.
+    miner_handle: LocalMinerHandle,
 }
 
 impl<N> LocalEngineService<N>
@@ -80,6 +86,7 @@ where
         payload_attributes_builder: B,
     ) -> Self
     where
+        N: EngineNodeTypes<Primitives = EthPrimitives>,
         B: PayloadAttributesBuilder<<N::Engine as PayloadTypes>::PayloadAttributes>,
         V: EngineValidator<N::Engine, Block = BlockTy<N>>,
     {
@@ -106,15 +113,22 @@ where
 
         let handler = EngineApiRequestHandler::new(to_tree_tx, from_tree);
 
-        LocalMiner::spawn_new(
+        let (to_tree, mined_blocks) = unbounded_channel();
+        let miner_handle = LocalMiner::spawn_new(
             blockchain_db,
             payload_attributes_builder,
             to_engine,
+            to_tree,
             mode,
             payload_builder,
         );
 
-        Self { handler, incoming_requests: from_engine }
+        Self { handler, incoming_requests: from_engine, mined_blocks, miner_handle }
+    }
+
+    /// Returns a handle to the local miner.
+    pub fn miner_handle(&self) -> LocalMinerHandle {
+        self.miner_handle.clone()
     }
 }
 
@@ -144,6 +158,12 @@ where
             }
         }
 
+        // insert blocks built by the miner before processing any forkchoice updates for them
+        while let Poll::Ready(Some(block)) = this.mined_blocks.poll_recv(cx) {
+            this.handler
+                .on_event(FromEngine::Request(EngineApiRequest::InsertExecutedBlock(block)));
+        }
+
         // forward incoming requests to the handler
         while let Poll::Ready(Some(req)) = this.incoming_requests.poll_next_unpin(cx) {
             this.handler.on_event(FromEngine::Request(req.into()));
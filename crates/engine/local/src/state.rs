@@ -0,0 +1,344 @@
+//! Support for inspecting and manually modifying the state of the local chain.
+
+use alloy_primitives::{
+    keccak256,
+    map::{Entry, HashMap, HashSet},
+    Address, B256, U256,
+};
+use reth_chain_state::ExecutedBlock;
+use reth_chainspec::EthChainSpec;
+use reth_engine_primitives::AccountPatch;
+use reth_primitives::SealedBlock;
+use reth_primitives_traits::SealedHeader;
+use reth_provider::{
+    BlockNumReader, ChainSpecProvider, StateProvider, StateProviderFactory, StateReader,
+};
+use reth_revm::{
+    db::{
+        states::{reverts::AccountInfoRevert, StorageSlot},
+        AccountRevert, AccountStatus, BundleAccount, RevertToSlot,
+    },
+    primitives::{AccountInfo, Bytecode, KECCAK_EMPTY},
+};
+use reth_trie::{HashedPostState, KeccakKeyHasher};
+use std::{collections::BTreeMap, sync::Arc};
+
+/// Applies the given patches to an already executed block.
+///
+/// The patches are folded into the block's bundle state and reverts, after which the hashed
+/// state and the state root are recomputed on top of the parent state and the block is resealed.
+pub(crate) fn apply_patches(
+    executed: ExecutedBlock,
+    patches: HashMap<Address, AccountPatch>,
+    parent_state: &dyn StateProvider,
+) -> eyre::Result<ExecutedBlock> {
+    if patches.is_empty() {
+        return Ok(executed)
+    }
+
+    let ExecutedBlock { block, senders, execution_output, .. } = executed;
+    let mut execution_output = Arc::unwrap_or_clone(execution_output);
+    let bundle = &mut execution_output.bundle;
+
+    for (address, patch) in patches {
+        let account = match bundle.state.entry(address) {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => {
+                let original = parent_state.basic_account(address)?.map(AccountInfo::from);
+                if bundle.reverts.is_empty() {
+                    bundle.reverts.push(Vec::new());
+                }
+                bundle.reverts[0].push((
+                    address,
+                    AccountRevert {
+                        account: original
+                            .clone()
+                            .map(AccountInfoRevert::RevertTo)
+                            .unwrap_or(AccountInfoRevert::DeleteIt),
+                        storage: Default::default(),
+                        previous_status: AccountStatus::Loaded,
+                        wipe_storage: false,
+                    },
+                ));
+                entry.insert(BundleAccount {
+                    info: original.clone(),
+                    status: if original.is_some() {
+                        AccountStatus::Changed
+                    } else {
+                        AccountStatus::InMemoryChange
+                    },
+                    original_info: original,
+                    storage: Default::default(),
+                })
+            }
+        };
+        let mut info = account.info.clone().unwrap_or_default();
+        if let Some(balance) = patch.balance {
+            info.balance = balance;
+        }
+        if let Some(nonce) = patch.nonce {
+            info.nonce = nonce;
+        }
+        if let Some(code) = patch.code {
+            if code.is_empty() {
+                info.code_hash = KECCAK_EMPTY;
+                info.code = None;
+            } else {
+                let bytecode = Bytecode::new_raw(code);
+                info.code_hash = keccak256(bytecode.original_byte_slice());
+                bundle.contracts.insert(info.code_hash, bytecode.clone());
+                info.code = Some(bytecode);
+            }
+        }
+        account.info = Some(info);
+
+        let mut storage_reverts = Vec::new();
+        for (slot, value) in patch.storage {
+            let slot = U256::from_be_bytes(slot.0);
+            let value = U256::from_be_bytes(value.0);
+            if let Some(present) = account.storage.get_mut(&slot) {
+                present.present_value = value;
+            } else {
+                let original = parent_state.storage(address, slot.into())?.unwrap_or_default();
+                account.storage.insert(slot, StorageSlot::new_changed(original, value));
+                storage_reverts.push((slot, RevertToSlot::Some(original)));
+            }
+        }
+
+        if !storage_reverts.is_empty() {
+            if let Some((_, revert)) = bundle
+                .reverts
+                .first_mut()
+                .and_then(|reverts| reverts.iter_mut().find(|(addr, _)| *addr == address))
+            {
+                revert.storage.extend(storage_reverts);
+            } else {
+                // the account was touched by the block without any changes that need to be
+                // reverted, so only the storage needs to be restored
+                if bundle.reverts.is_empty() {
+                    bundle.reverts.push(Vec::new());
+                }
+                bundle.reverts[0].push((
+                    address,
+                    AccountRevert {
+                        account: AccountInfoRevert::DoNothing,
+                        storage: storage_reverts.into_iter().collect(),
+                        previous_status: account.status,
+                        wipe_storage: false,
+                    },
+                ));
+            }
+        }
+    }
+
+    let hashed_state = HashedPostState::from_bundle_state::<KeccakKeyHasher>(&bundle.state);
+    let (state_root, trie) = parent_state.state_root_with_updates(hashed_state.clone())?;
+
+    let mut header = block.header.clone_header();
+    header.state_root = state_root;
+    let block = SealedBlock::new(SealedHeader::seal(header), block.body.clone());
+
+    Ok(ExecutedBlock::new(
+        Arc::new(block),
+        senders,
+        Arc::new(execution_output),
+        Arc::new(hashed_state),
+        Arc::new(trie),
+    ))
+}
+
+/// Collects all non-empty accounts of the latest state.
+///
+/// Accounts and storage slots are discovered from the genesis allocation and the state changes
+/// of every block, which is only feasible for the short chains of dev nodes.
+pub(crate) fn dump_state<P>(provider: &P) -> eyre::Result<BTreeMap<Address, AccountPatch>>
+where
+    P: StateProviderFactory + StateReader + BlockNumReader + ChainSpecProvider,
+{
+    let mut touched: BTreeMap<Address, HashSet<B256>> = BTreeMap::new();
+    for (address, account) in &provider.chain_spec().genesis().alloc {
+        touched
+            .entry(*address)
+            .or_default()
+            .extend(account.storage.iter().flatten().map(|(k, _)| *k));
+    }
+    for number in 1..=provider.best_block_number()? {
+        let Some(outcome) = provider.get_state(number)? else { continue };
+        for (address, account) in outcome.bundle_accounts_iter() {
+            touched
+                .entry(address)
+                .or_default()
+                .extend(account.storage.keys().map(|slot| B256::from(*slot)));
+        }
+    }
+
+    let state = provider.latest()?;
+    let mut accounts = BTreeMap::new();
+    for (address, slots) in touched {
+        let Some(account) = state.basic_account(address)? else { continue };
+        let code = state
+            .account_code(address)?
+            .map(|code| code.original_bytes())
+            .filter(|code| !code.is_empty());
+        let mut storage = HashMap::default();
+        for slot in slots {
+            let value = state.storage(address, slot)?.unwrap_or_default();
+            if !value.is_zero() {
+                storage.insert(slot, B256::from(value));
+            }
+        }
+        accounts.insert(
+            address,
+            AccountPatch {
+                balance: Some(account.balance),
+                nonce: Some(account.nonce),
+                code,
+                storage,
+            },
+        );
+    }
+
+    Ok(accounts)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::Bytes;
+    use reth_provider::{
+        test_utils::{ExtendedAccount, MockEthProvider},
+        ExecutionOutcome,
+    };
+    use reth_trie::updates::TrieUpdates;
+
+    fn empty_block() -> ExecutedBlock {
+        ExecutedBlock::new(
+            Arc::new(SealedBlock::default()),
+            Arc::new(Vec::new()),
+            Arc::new(ExecutionOutcome::default()),
+            Arc::new(HashedPostState::default()),
+            Arc::new(TrieUpdates::default()),
+        )
+    }
+
+    #[test]
+    fn patches_untouched_account() {
+        let address = Address::random();
+        let slot = B256::with_last_byte(1);
+        let parent_state = MockEthProvider::default();
+        parent_state.add_account(
+            address,
+            ExtendedAccount::new(1, U256::from(10)).extend_storage([(slot, U256::from(2))]),
+        );
+        let state_root = B256::random();
+        parent_state.add_state_root(state_root);
+
+        let code = Bytes::from_static(&[0x60, 0x00]);
+        let patch = AccountPatch {
+            balance: Some(U256::from(100)),
+            code: Some(code.clone()),
+            storage: HashMap::from_iter([(slot, B256::with_last_byte(3))]),
+            ..Default::default()
+        };
+        let executed =
+            apply_patches(empty_block(), HashMap::from_iter([(address, patch)]), &parent_state)
+                .unwrap();
+
+        // the block is resealed with the recomputed state root
+        assert_eq!(executed.block().state_root, state_root);
+        assert_eq!(executed.block().hash(), executed.block().header.hash_slow());
+
+        let bundle = &executed.execution_output.bundle;
+        let account = &bundle.state[&address];
+        let info = account.info.as_ref().unwrap();
+        assert_eq!(info.balance, U256::from(100));
+        assert_eq!(info.nonce, 1);
+        assert_eq!(info.code_hash, keccak256(&code));
+        assert!(bundle.contracts.contains_key(&info.code_hash));
+        let patched_slot = &account.storage[&U256::from(1)];
+        assert_eq!(patched_slot.previous_or_original_value, U256::from(2));
+        assert_eq!(patched_slot.present_value, U256::from(3));
+
+        // the reverts of the block restore the original account and storage
+        let (reverted, revert) = &bundle.reverts[0][0];
+        assert_eq!(*reverted, address);
+        let AccountInfoRevert::RevertTo(original) = &revert.account else {
+            panic!("unexpected account revert {:?}", revert.account)
+        };
+        assert_eq!(original.balance, U256::from(10));
+        assert_eq!(original.nonce, 1);
+        assert_eq!(revert.storage[&U256::from(1)], RevertToSlot::Some(U256::from(2)));
+
+        assert!(executed.hashed_state.accounts.contains_key(&keccak256(address)));
+    }
+
+    #[test]
+    fn patches_new_account() {
+        let address = Address::random();
+        let parent_state = MockEthProvider::default();
+
+        let patch = AccountPatch { nonce: Some(5), ..Default::default() };
+        let executed =
+            apply_patches(empty_block(), HashMap::from_iter([(address, patch)]), &parent_state)
+                .unwrap();
+
+        let bundle = &executed.execution_output.bundle;
+        let account = &bundle.state[&address];
+        assert_eq!(account.status, AccountStatus::InMemoryChange);
+        assert_eq!(account.info.as_ref().unwrap().nonce, 5);
+        assert_eq!(account.info.as_ref().unwrap().balance, U256::ZERO);
+        assert_eq!(bundle.reverts[0][0].1.account, AccountInfoRevert::DeleteIt);
+    }
+
+    #[test]
+    fn patch_overrides_block_changes() {
+        let address = Address::random();
+        let parent_state = MockEthProvider::default();
+        parent_state.add_account(address, ExtendedAccount::new(0, U256::from(10)));
+
+        // the block itself already changed the slot
+        let mut outcome = ExecutionOutcome::default();
+        outcome.bundle.state.insert(
+            address,
+            BundleAccount {
+                info: Some(AccountInfo { balance: U256::from(10), ..Default::default() }),
+                original_info: Some(AccountInfo { balance: U256::from(10), ..Default::default() }),
+                storage: [(U256::from(1), StorageSlot::new_changed(U256::ZERO, U256::from(7)))]
+                    .into_iter()
+                    .collect(),
+                status: AccountStatus::Changed,
+            },
+        );
+        outcome.bundle.reverts.push(vec![(
+            address,
+            AccountRevert {
+                account: AccountInfoRevert::DoNothing,
+                storage: [(U256::from(1), RevertToSlot::Some(U256::ZERO))].into_iter().collect(),
+                previous_status: AccountStatus::Loaded,
+                wipe_storage: false,
+            },
+        )]);
+        let executed = ExecutedBlock::new(
+            Arc::new(SealedBlock::default()),
+            Arc::new(Vec::new()),
+            Arc::new(outcome),
+            Arc::new(HashedPostState::default()),
+            Arc::new(TrieUpdates::default()),
+        );
+
+        let patch = AccountPatch {
+            storage: HashMap::from_iter([(B256::with_last_byte(1), B256::with_last_byte(9))]),
+            ..Default::default()
+        };
+        let executed =
+            apply_patches(executed, HashMap::from_iter([(address, patch)]), &parent_state).unwrap();
+
+        let bundle = &executed.execution_output.bundle;
+        let slot = &bundle.state[&address].storage[&U256::from(1)];
+        assert_eq!(slot.previous_or_original_value, U256::ZERO);
+        assert_eq!(slot.present_value, U256::from(9));
+        // the existing revert still restores the value before the block
+        assert_eq!(bundle.reverts[0].len(), 1);
+        assert_eq!(bundle.reverts[0][0].1.storage[&U256::from(1)], RevertToSlot::Some(U256::ZERO));
+    }
+}
@@ -0,0 +1,39 @@
+use alloy_primitives::{Bytes, B256};
+use alloy_rlp::Decodable;
+use alloy_rpc_types_debug::ExecutionWitness;
+use core::fmt;
+use reth_errors::RethResult;
+use serde::{Deserialize, Serialize};
+
+/// A block that was rejected as invalid, as recorded by the engine.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BadBlockRecord {
+    /// Hash of the block.
+    pub hash: B256,
+    /// Number of the block.
+    pub number: u64,
+    /// RLP encoded block.
+    pub rlp: Bytes,
+    /// The validation error the block was rejected with.
+    pub error: String,
+    /// The execution witness of the block, if it could be executed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub witness: Option<ExecutionWitness>,
+}
+
+impl BadBlockRecord {
+    /// Decodes the block from its RLP encoding.
+    pub fn decode_block<B: Decodable>(&self) -> alloy_rlp::Result<B> {
+        B::decode(&mut self.rlp.as_ref())
+    }
+}
+
+/// Provides access to the bad blocks recorded by the engine.
+pub trait BadBlockReader: fmt::Debug + Send + Sync {
+    /// Returns all recorded bad blocks, highest block first.
+    fn bad_blocks(&self) -> RethResult<Vec<BadBlockRecord>>;
+
+    /// Returns the recorded bad block with the given hash, if any.
+    fn bad_block(&self, hash: B256) -> RethResult<Option<BadBlockRecord>>;
+}
@@ -0,0 +1,165 @@
+use alloy_primitives::{map::HashMap, Address, Bytes, PrimitiveSignature as Signature, B256, U256};
+use core::{fmt, time::Duration};
+use futures::future::BoxFuture;
+use reth_errors::{RethError, RethResult};
+use std::collections::BTreeMap;
+
+/// Controls the block production of a dev node.
+///
+/// This is implemented by the local miner of the dev engine and used to serve the `anvil_` and
+/// `hardhat_` namespaces.
+pub trait DevMiner: fmt::Debug + Send + Sync {
+    /// Mines `blocks` blocks, optionally with `interval` seconds between their timestamps, and
+    /// returns their hashes.
+    fn mine(&self, blocks: u64, interval: Option<u64>) -> BoxFuture<'_, RethResult<Vec<B256>>>;
+
+    /// Replaces the mining mode.
+    fn set_mining_mode(&self, mode: DevMiningMode) -> RethResult<()>;
+
+    /// Returns `true` if a block is mined as soon as a transaction arrives.
+    fn is_automine(&self) -> BoxFuture<'_, RethResult<bool>>;
+
+    /// Sets the timestamp of the next block.
+    fn set_next_block_timestamp(&self, timestamp: u64) -> BoxFuture<'_, RethResult<()>>;
+
+    /// Shifts the clock used for block timestamps forward and returns the total offset in seconds.
+    fn increase_time(&self, seconds: u64) -> BoxFuture<'_, RethResult<i64>>;
+
+    /// Sets the clock used for block timestamps and returns the total offset in seconds.
+    fn set_time(&self, timestamp: u64) -> BoxFuture<'_, RethResult<i64>>;
+
+    /// Sets or removes a fixed interval in seconds between the timestamps of consecutive blocks.
+    fn set_block_timestamp_interval(&self, interval: Option<u64>) -> RethResult<()>;
+
+    /// Adds state modifications to the pending overlay that is applied to the next block.
+    fn modify_state(&self, patches: HashMap<Address, AccountPatch>) -> RethResult<()>;
+
+    /// Returns the pending overlay of state modifications.
+    fn pending_state(&self) -> BoxFuture<'_, RethResult<HashMap<Address, AccountPatch>>>;
+
+    /// Returns all non-empty accounts of the latest state with the pending overlay applied.
+    fn dump_state(&self) -> BoxFuture<'_, RethResult<BTreeMap<Address, AccountPatch>>>;
+
+    /// Reorgs the chain so that the new head is a block built on top of the given block, and
+    /// returns the hash of the new head.
+    fn revert(&self, block_hash: B256) -> BoxFuture<'_, RethResult<B256>>;
+}
+
+/// A [`DevMiner`] for nodes that don't produce their own blocks.
+///
+/// All requests fail.
+#[derive(Debug, Clone, Copy, Default)]
+#[non_exhaustive]
+pub struct NoopDevMiner;
+
+impl NoopDevMiner {
+    /// Returns the error of all requests.
+    fn unavailable<T>() -> RethResult<T> {
+        Err(RethError::msg("dev mining is only available in dev mode"))
+    }
+}
+
+impl DevMiner for NoopDevMiner {
+    fn mine(&self, _blocks: u64, _interval: Option<u64>) -> BoxFuture<'_, RethResult<Vec<B256>>> {
+        Box::pin(async { Self::unavailable() })
+    }
+
+    fn set_mining_mode(&self, _mode: DevMiningMode) -> RethResult<()> {
+        Self::unavailable()
+    }
+
+    fn is_automine(&self) -> BoxFuture<'_, RethResult<bool>> {
+        Box::pin(async { Self::unavailable() })
+    }
+
+    fn set_next_block_timestamp(&self, _timestamp: u64) -> BoxFuture<'_, RethResult<()>> {
+        Box::pin(async { Self::unavailable() })
+    }
+
+    fn increase_time(&self, _seconds: u64) -> BoxFuture<'_, RethResult<i64>> {
+        Box::pin(async { Self::unavailable() })
+    }
+
+    fn set_time(&self, _timestamp: u64) -> BoxFuture<'_, RethResult<i64>> {
+        Box::pin(async { Self::unavailable() })
+    }
+
+    fn set_block_timestamp_interval(&self, _interval: Option<u64>) -> RethResult<()> {
+        Self::unavailable()
+    }
+
+    fn modify_state(&self, _patches: HashMap<Address, AccountPatch>) -> RethResult<()> {
+        Self::unavailable()
+    }
+
+    fn pending_state(&self) -> BoxFuture<'_, RethResult<HashMap<Address, AccountPatch>>> {
+        Box::pin(async { Self::unavailable() })
+    }
+
+    fn dump_state(&self) -> BoxFuture<'_, RethResult<BTreeMap<Address, AccountPatch>>> {
+        Box::pin(async { Self::unavailable() })
+    }
+
+    fn revert(&self, _block_hash: B256) -> BoxFuture<'_, RethResult<B256>> {
+        Box::pin(async { Self::unavailable() })
+    }
+}
+
+/// Returns the placeholder signature of the transactions of an impersonated account.
+///
+/// The `r` value is the sender, so the transactions of different senders have different hashes.
+/// The sender can't be recovered from the signature, so blocks that include such transactions are
+/// inserted by the dev miner without validating the signatures, see
+/// [`is_impersonated_signature`].
+pub fn impersonated_signature(sender: Address) -> Signature {
+    Signature::new(U256::from_be_slice(sender.as_slice()), U256::from(1), false)
+}
+
+/// Returns `true` if the signature is the placeholder signature of a transaction of the given
+/// impersonated sender, see [`impersonated_signature`].
+pub fn is_impersonated_signature(signature: &Signature, sender: Address) -> bool {
+    *signature == impersonated_signature(sender)
+}
+
+/// The mining mode of a [`DevMiner`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DevMiningMode {
+    /// A block is mined as soon as a transaction arrives.
+    Auto,
+    /// A block is mined at a fixed interval.
+    Interval(Duration),
+    /// Blocks are only mined on request.
+    Manual,
+}
+
+/// A manual modification of a single account.
+///
+/// Every field that is set overrides the value the account has after the transactions of the
+/// block the modification is applied to.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AccountPatch {
+    /// The new balance of the account.
+    pub balance: Option<U256>,
+    /// The new nonce of the account.
+    pub nonce: Option<u64>,
+    /// The new code of the account, empty code removes it.
+    pub code: Option<Bytes>,
+    /// Storage slots to overwrite.
+    pub storage: HashMap<B256, B256>,
+}
+
+impl AccountPatch {
+    /// Merges `other` into this patch, values of `other` take precedence.
+    pub fn merge(&mut self, other: Self) {
+        if other.balance.is_some() {
+            self.balance = other.balance;
+        }
+        if other.nonce.is_some() {
+            self.nonce = other.nonce;
+        }
+        if other.code.is_some() {
+            self.code = other.code;
+        }
+        self.storage.extend(other.storage);
+    }
+}
@@ -1,6 +1,6 @@
 use alloy_primitives::B256;
 use reth_execution_types::BlockExecutionOutput;
-use reth_primitives::{NodePrimitives, SealedBlockWithSenders, SealedHeader};
+use reth_primitives::{NodePrimitives, SealedBlockFor, SealedBlockWithSenders, SealedHeader};
 use reth_trie::updates::TrieUpdates;
 
 /// An invalid block hook.
@@ -13,6 +13,18 @@ pub trait InvalidBlockHook<N: NodePrimitives>: Send + Sync {
         output: &BlockExecutionOutput<N::Receipt>,
         trie_updates: Option<(&TrieUpdates, B256)>,
     );
+
+    /// Invoked when a block failed validation, with the error it was rejected with.
+    ///
+    /// This is called for every invalid block, including the ones that were rejected before they
+    /// could be executed. If the block was executed, [`InvalidBlockHook::on_invalid_block`] has
+    /// been invoked for it before.
+    fn on_invalid_block_error(
+        &self,
+        _block: &SealedBlockFor<N::Block>,
+        _error: &(dyn core::error::Error + Send + Sync),
+    ) {
+    }
 }
 
 impl<F, N> InvalidBlockHook<N> for F
@@ -25,6 +25,15 @@ pub use message::{BeaconEngineMessage, OnForkChoiceUpdated};
 mod invalid_block_hook;
 pub use invalid_block_hook::InvalidBlockHook;
 
+mod bad_block;
+pub use bad_block::{BadBlockReader, BadBlockRecord};
+
+mod dev;
+pub use dev::{
+    impersonated_signature, is_impersonated_signature, AccountPatch, DevMiner, DevMiningMode,
+    NoopDevMiner,
+};
+
 pub use reth_payload_primitives::{
     BuiltPayload, EngineApiMessageVersion, EngineObjectValidationError, PayloadOrAttributes,
     PayloadTypes,
@@ -1,4 +1,5 @@
 use crate::{BeaconOnNewPayloadError, EngineApiMessageVersion, EngineTypes, ForkchoiceStatus};
+use alloy_primitives::BlockNumber;
 use alloy_rpc_types_engine::{
     ExecutionPayload, ExecutionPayloadSidecar, ForkChoiceUpdateResult, ForkchoiceState,
     ForkchoiceUpdateError, ForkchoiceUpdated, PayloadId, PayloadStatus, PayloadStatusEnum,
@@ -162,6 +163,13 @@ pub enum BeaconEngineMessage<Engine: EngineTypes> {
     },
     /// Message with exchanged transition configuration.
     TransitionConfigurationExchanged,
+    /// Message to rewind the canonical chain to the given block (`debug_setHead`).
+    SetHead {
+        /// The number of the new canonical head.
+        number: BlockNumber,
+        /// The sender for returning the result of the rewind.
+        tx: oneshot::Sender<RethResult<()>>,
+    },
 }
 
 impl<Engine: EngineTypes> Display for BeaconEngineMessage<Engine> {
@@ -188,6 +196,9 @@ impl<Engine: EngineTypes> Display for BeaconEngineMessage<Engine> {
             Self::TransitionConfigurationExchanged => {
                 write!(f, "TransitionConfigurationExchanged")
             }
+            Self::SetHead { number, .. } => {
+                write!(f, "SetHead(number: {number})")
+            }
         }
     }
 }
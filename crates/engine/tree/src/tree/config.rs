@@ -32,6 +32,9 @@ pub struct TreeConfig {
     /// This is used as a cutoff to prevent long-running sequential block execution when we receive
     /// a batch of downloaded blocks.
     max_execute_block_batch_size: usize,
+    /// Whether to compute the state root concurrently with block execution using the
+    /// [`StateRootTask`](super::root::StateRootTask).
+    use_state_root_task: bool,
 }
 
 impl Default for TreeConfig {
@@ -42,6 +45,7 @@ impl Default for TreeConfig {
             block_buffer_limit: DEFAULT_BLOCK_BUFFER_LIMIT,
             max_invalid_header_cache_length: DEFAULT_MAX_INVALID_HEADER_CACHE_LENGTH,
             max_execute_block_batch_size: DEFAULT_MAX_EXECUTE_BLOCK_BATCH_SIZE,
+            use_state_root_task: false,
         }
     }
 }
@@ -54,6 +58,7 @@ impl TreeConfig {
         block_buffer_limit: u32,
         max_invalid_header_cache_length: u32,
         max_execute_block_batch_size: usize,
+        use_state_root_task: bool,
     ) -> Self {
         Self {
             persistence_threshold,
@@ -61,6 +66,7 @@ impl TreeConfig {
             block_buffer_limit,
             max_invalid_header_cache_length,
             max_execute_block_batch_size,
+            use_state_root_task,
         }
     }
 
@@ -89,6 +95,11 @@ impl TreeConfig {
         self.max_execute_block_batch_size
     }
 
+    /// Returns whether to use the state root task for computing the state root.
+    pub const fn use_state_root_task(&self) -> bool {
+        self.use_state_root_task
+    }
+
     /// Setter for persistence threshold.
     pub const fn with_persistence_threshold(mut self, persistence_threshold: u64) -> Self {
         self.persistence_threshold = persistence_threshold;
@@ -127,4 +138,10 @@ impl TreeConfig {
         self.max_execute_block_batch_size = max_execute_block_batch_size;
         self
     }
+
+    /// Setter for whether to use the state root task for computing the state root.
+    pub const fn with_state_root_task(mut self, use_state_root_task: bool) -> Self {
+        self.use_state_root_task = use_state_root_task;
+        self
+    }
 }
@@ -1,6 +1,6 @@
 use alloy_primitives::B256;
 use reth_engine_primitives::InvalidBlockHook;
-use reth_primitives::{NodePrimitives, SealedBlockWithSenders, SealedHeader};
+use reth_primitives::{NodePrimitives, SealedBlockFor, SealedBlockWithSenders, SealedHeader};
 use reth_provider::BlockExecutionOutput;
 use reth_trie::updates::TrieUpdates;
 
@@ -41,4 +41,14 @@ impl<N: NodePrimitives> InvalidBlockHook<N> for InvalidBlockHooks<N> {
             hook.on_invalid_block(parent_header, block, output, trie_updates);
         }
     }
+
+    fn on_invalid_block_error(
+        &self,
+        block: &SealedBlockFor<N::Block>,
+        error: &(dyn core::error::Error + Send + Sync),
+    ) {
+        for hook in &self.0 {
+            hook.on_invalid_block_error(block, error);
+        }
+    }
 }
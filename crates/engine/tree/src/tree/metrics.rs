@@ -5,6 +5,7 @@ use reth_metrics::{
     Metrics,
 };
 use reth_trie::updates::TrieUpdates;
+use std::time::Duration;
 
 /// Metrics for the `EngineApi`.
 #[derive(Debug, Default)]
@@ -56,6 +57,17 @@ pub(crate) struct BlockValidationMetrics {
     pub(crate) state_root_histogram: Histogram,
     /// Latest state root duration
     pub(crate) state_root_duration: Gauge,
+    /// Histogram of the state root task duration, from the first state update to the result
+    pub(crate) state_root_task_histogram: Histogram,
+    /// Histogram of the part of the state root task duration that overlapped with block
+    /// execution and validation
+    pub(crate) state_root_task_overlapped_histogram: Histogram,
+    /// Latest ratio of the state root task duration that overlapped with block execution and
+    /// validation
+    pub(crate) state_root_task_overlap_ratio: Gauge,
+    /// Total number of state root task results that were discarded in favor of the fallback
+    /// state root computation
+    pub(crate) state_root_task_fallbacks_total: Counter,
 }
 
 impl BlockValidationMetrics {
@@ -66,4 +78,16 @@ impl BlockValidationMetrics {
         self.state_root_duration.set(elapsed_as_secs);
         self.state_root_histogram.record(elapsed_as_secs);
     }
+
+    /// Records the duration of the state root task, and how much of it overlapped with block
+    /// execution and validation given the time spent waiting for its result.
+    pub(crate) fn record_state_root_task(&self, total_time: Duration, waited: Duration) {
+        let total_as_secs = total_time.as_secs_f64();
+        let overlapped_as_secs = total_time.saturating_sub(waited).as_secs_f64();
+        self.state_root_task_histogram.record(total_as_secs);
+        self.state_root_task_overlapped_histogram.record(overlapped_as_secs);
+        if total_as_secs > 0.0 {
+            self.state_root_task_overlap_ratio.set(overlapped_as_secs / total_as_secs);
+        }
+    }
 }
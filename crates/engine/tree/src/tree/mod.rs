@@ -3,7 +3,10 @@ use crate::{
     chain::FromOrchestrator,
     engine::{DownloadRequest, EngineApiEvent, EngineApiKind, EngineApiRequest, FromEngine},
     persistence::PersistenceHandle,
-    tree::metrics::EngineApiMetrics,
+    tree::{
+        metrics::EngineApiMetrics,
+        root::{PreservedSparseTrie, StateRootComputeOutcome, StateRootConfig, StateRootTask},
+    },
 };
 use alloy_consensus::BlockHeader;
 use alloy_eips::BlockNumHash;
@@ -30,8 +33,8 @@ use reth_engine_primitives::{
     BeaconEngineMessage, BeaconOnNewPayloadError, EngineApiMessageVersion, EngineTypes,
     EngineValidator, ForkchoiceStateTracker, OnForkChoiceUpdated,
 };
-use reth_errors::{ConsensusError, ProviderResult};
-use reth_evm::execute::BlockExecutorProvider;
+use reth_errors::{ConsensusError, ProviderResult, RethError, RethResult};
+use reth_evm::{execute::BlockExecutorProvider, system_calls::OnStateHook};
 use reth_payload_builder::PayloadBuilderHandle;
 use reth_payload_builder_primitives::PayloadBuilder;
 use reth_payload_primitives::PayloadBuilderAttributes;
@@ -41,13 +44,17 @@ use reth_primitives::{
 };
 use reth_primitives_traits::Block;
 use reth_provider::{
-    providers::ConsistentDbView, BlockReader, DatabaseProviderFactory, ExecutionOutcome,
-    HashedPostStateProvider, ProviderError, StateCommitmentProvider, StateProviderBox,
-    StateProviderFactory, StateReader, StateRootProvider, TransactionVariant,
+    providers::ConsistentDbView, BlockReader, DBProvider, DatabaseProviderFactory,
+    ExecutionOutcome, HashedPostStateProvider, ProviderError, StateCommitmentProvider,
+    StateProviderBox, StateProviderFactory, StateReader, StateRootProvider, TransactionVariant,
 };
 use reth_revm::database::StateProviderDatabase;
 use reth_stages_api::ControlFlow;
-use reth_trie::{updates::TrieUpdates, HashedPostState, TrieInput};
+use reth_trie::{
+    hashed_cursor::HashedPostStateCursorFactory, proof::ProofBlindedProviderFactory,
+    trie_cursor::InMemoryTrieCursorFactory, updates::TrieUpdates, HashedPostState, TrieInput,
+};
+use reth_trie_db::{DatabaseHashedCursorFactory, DatabaseTrieCursorFactory};
 use reth_trie_parallel::root::{ParallelStateRoot, ParallelStateRootError};
 use revm_primitives::EvmState;
 use std::{
@@ -59,7 +66,7 @@ use std::{
         mpsc::{Receiver, RecvError, RecvTimeoutError, Sender},
         Arc,
     },
-    time::Instant,
+    time::{Duration, Instant},
 };
 use tokio::sync::{
     mpsc::{unbounded_channel, UnboundedReceiver, UnboundedSender},
@@ -361,6 +368,22 @@ impl<N: NodePrimitives> TreeState<N> {
         }
     }
 
+    /// Removes all blocks above the given block number, including sidechains.
+    fn remove_blocks_above(&mut self, number: BlockNumber) {
+        let blocks_to_remove = self
+            .blocks_by_number
+            .range((Bound::Excluded(number), Bound::Unbounded))
+            .flat_map(|(_, blocks)| blocks.iter().map(|b| b.block.hash()))
+            .collect::<Vec<_>>();
+        for hash in blocks_to_remove {
+            if let Some((removed, _)) = self.remove_by_hash(hash) {
+                debug!(target: "engine::tree", num_hash=?removed.block.num_hash(), "Removed block above the new head");
+            }
+        }
+
+        self.persisted_trie_updates.retain(|_, (block_num, _)| *block_num <= number);
+    }
+
     /// Updates the canonical head to the given block.
     fn set_canonical_head(&mut self, new_head: BlockNumHash) {
         self.current_canonical_head = new_head;
@@ -511,6 +534,9 @@ where
     invalid_block_hook: Box<dyn InvalidBlockHook<N>>,
     /// The engine API variant of this handler
     engine_kind: EngineApiKind,
+    /// The sparse trie of the last state root computed with the state root task, reused for the
+    /// state root computation of the child block.
+    preserved_sparse_trie: Option<PreservedSparseTrie>,
 }
 
 impl<N, P: Debug, E: Debug, T: EngineTypes + Debug, V: Debug> std::fmt::Debug
@@ -535,6 +561,10 @@ where
             .field("metrics", &self.metrics)
             .field("invalid_block_hook", &format!("{:p}", self.invalid_block_hook))
             .field("engine_kind", &self.engine_kind)
+            .field(
+                "preserved_sparse_trie",
+                &self.preserved_sparse_trie.as_ref().map(PreservedSparseTrie::block_hash),
+            )
             .finish()
     }
 }
@@ -592,6 +622,7 @@ where
             incoming_tx,
             invalid_block_hook: Box::new(NoopInvalidBlockHook),
             engine_kind,
+            preserved_sparse_trie: None,
         }
     }
 
@@ -1292,6 +1323,11 @@ where
                                 self.canonical_in_memory_state
                                     .on_transition_configuration_exchanged();
                             }
+                            BeaconEngineMessage::SetHead { number, tx } => {
+                                if let Err(err) = tx.send(self.on_set_head(number)) {
+                                    error!(target: "engine::tree", "Failed to send event: {err:?}");
+                                }
+                            }
                         }
                     }
                 }
@@ -1408,6 +1444,87 @@ where
         self.try_connect_buffered_blocks(self.state.tree_state.current_canonical_head)
     }
 
+    /// Rewinds the canonical chain to the given block number.
+    ///
+    /// All executed blocks above the new head are removed from the tree. Blocks above the new head
+    /// that are already persisted are removed from disk by the persistence task.
+    fn on_set_head(&mut self, number: BlockNumber) -> RethResult<()> {
+        if !self.backfill_sync_state.is_idle() {
+            return Err(RethError::msg("cannot set the head while backfill sync is in progress"))
+        }
+
+        let current_head = self.state.tree_state.canonical_block_number();
+        if number > current_head {
+            return Err(RethError::msg(format!(
+                "block {number} is above the canonical head {current_head}"
+            )))
+        }
+        if number == current_head {
+            return Ok(())
+        }
+
+        let start = Instant::now();
+        debug!(target: "engine::tree", number, current_head, "Setting canonical head");
+
+        // walk back the in-memory canonical chain until the new head or a persisted block
+        let mut reorged = Vec::new();
+        let mut current_hash = self.state.tree_state.canonical_block_hash();
+        while let Some(executed) = self.state.tree_state.executed_block_by_hash(current_hash) {
+            if executed.block.number() <= number {
+                break
+            }
+            current_hash = executed.block.parent_hash();
+            reorged.push(executed.clone());
+        }
+
+        let new_head = match self.state.tree_state.executed_block_by_hash(current_hash) {
+            Some(executed) => executed.block.header.clone(),
+            None => self
+                .provider
+                .sealed_header(number)?
+                .ok_or_else(|| ProviderError::HeaderNotFound(number.into()))?,
+        };
+
+        self.state.tree_state.remove_blocks_above(number);
+        self.metrics.engine.executed_blocks.set(self.state.tree_state.block_count() as f64);
+
+        // blocks above the new head may be on disk or in the process of being persisted
+        if self.persistence_state.in_progress() ||
+            number < self.persistence_state.last_persisted_block.number
+        {
+            self.persistence_state.schedule_removal(number);
+        }
+
+        self.state.tree_state.set_canonical_head(new_head.num_hash());
+        self.canonical_in_memory_state
+            .update_chain(NewCanonicalChain::Reorg { new: Vec::new(), old: reorged });
+
+        // the safe and finalized blocks can't be above the canonical head
+        if self
+            .canonical_in_memory_state
+            .get_safe_num_hash()
+            .is_some_and(|safe| safe.number > number)
+        {
+            self.canonical_in_memory_state.set_safe(new_head.clone());
+        }
+        if self
+            .canonical_in_memory_state
+            .get_finalized_num_hash()
+            .is_some_and(|finalized| finalized.number > number)
+        {
+            self.canonical_in_memory_state.set_finalized(new_head.clone());
+        }
+        self.canonical_in_memory_state.set_canonical_head(new_head.clone());
+        self.metrics.tree.canonical_chain_height.set(number as f64);
+
+        self.emit_event(BeaconConsensusEngineEvent::CanonicalChainCommitted(
+            Box::new(new_head),
+            start.elapsed(),
+        ));
+
+        Ok(())
+    }
+
     /// Attempts to make the given target canonical.
     ///
     /// This will update the tracked canonical in memory state and do the necessary housekeeping.
@@ -2007,6 +2124,17 @@ where
             trace!(target: "engine::tree", ?new_first, ?old_first, "Reorg detected, new and old first blocks");
 
             self.update_reorg_metrics(old.len());
+
+            // the preserved sparse trie can't be used for the children of a reorged block anymore
+            if self
+                .preserved_sparse_trie
+                .as_ref()
+                .is_some_and(|trie| old.iter().any(|block| block.block.hash() == trie.block_hash()))
+            {
+                debug!(target: "engine::tree", "Dropping preserved sparse trie of reorged block");
+                self.preserved_sparse_trie = None;
+            }
+
             self.reinsert_reorged_blocks(new.clone());
             self.reinsert_reorged_blocks(old.clone());
         }
@@ -2224,46 +2352,138 @@ where
 
         let exec_time = Instant::now();
 
-        // TODO: create StateRootTask with the receiving end of a channel and
-        // pass the sending end of the channel to the state hook.
-        let noop_state_hook = |_state: &EvmState| {};
-        let output = self.metrics.executor.execute_metered(
-            executor,
-            (&block, U256::MAX).into(),
-            Box::new(noop_state_hook),
-        )?;
+        // We only compute the state root concurrently with execution if nothing is being persisted
+        // to the database, for the same reasons as for the parallel state root computation below.
+        let persistence_in_progress = self.persistence_state.in_progress();
+        let state_root_task_input = if !persistence_in_progress && self.config.use_state_root_task()
+        {
+            self.state_root_task_input(block.header().parent_hash())
+        } else {
+            None
+        };
 
-        trace!(target: "engine::tree", elapsed=?exec_time.elapsed(), ?block_number, "Executed block");
+        // The sparse trie preserved from the parent block can only be used once, because it is
+        // updated in place by the state root task. It is dropped if the block is not its child,
+        // because the tree moved on from the block it was preserved for.
+        let preserved_sparse_trie = self
+            .preserved_sparse_trie
+            .take()
+            .filter(|trie| {
+                let is_parent = trie.block_hash() == block.header().parent_hash();
+                if !is_parent {
+                    debug!(target: "engine::tree", ?block_hash, preserved = ?trie.block_hash(), "Dropping preserved sparse trie of a block that is not the parent");
+                }
+                is_parent
+            })
+            .filter(|_| state_root_task_input.is_some())
+            .map(PreservedSparseTrie::into_trie);
+
+        let (output, hashed_state, state_root_task_result) = std::thread::scope(
+            |scope| -> Result<_, InsertBlockErrorKindTwo> {
+                let (state_hook, state_root_handle) = match &state_root_task_input {
+                    Some((config, provider_ro)) => {
+                        let blinded_provider_factory = ProofBlindedProviderFactory::new(
+                            InMemoryTrieCursorFactory::new(
+                                DatabaseTrieCursorFactory::new(provider_ro.tx_ref()),
+                                &config.nodes_sorted,
+                            ),
+                            HashedPostStateCursorFactory::new(
+                                DatabaseHashedCursorFactory::new(provider_ro.tx_ref()),
+                                &config.state_sorted,
+                            ),
+                            config.prefix_sets.clone(),
+                        );
+                        let task = if let Some(sparse_trie) = preserved_sparse_trie {
+                            trace!(target: "engine::tree", ?block_hash, "Using preserved sparse trie of parent block");
+                            StateRootTask::new_with_sparse_trie(
+                                config.clone(),
+                                blinded_provider_factory,
+                                sparse_trie,
+                            )
+                        } else {
+                            StateRootTask::new(config.clone(), blinded_provider_factory)
+                        };
+                        let state_hook = Box::new(task.state_hook()) as Box<dyn OnStateHook>;
+                        (state_hook, Some(task.spawn(scope)))
+                    }
+                    None => {
+                        let noop_state_hook = |_state: &EvmState| {};
+                        (Box::new(noop_state_hook) as Box<dyn OnStateHook>, None)
+                    }
+                };
 
-        if let Err(err) = self.consensus.validate_block_post_execution(
-            &block,
-            PostExecutionInput::new(&output.receipts, &output.requests),
-        ) {
-            // call post-block hook
-            self.invalid_block_hook.on_invalid_block(
-                &parent_block,
-                &block.seal_slow(),
-                &output,
-                None,
-            );
-            return Err(err.into())
-        }
+                let output = self.metrics.executor.execute_metered(
+                    executor,
+                    (&block, U256::MAX).into(),
+                    state_hook,
+                )?;
+
+                trace!(target: "engine::tree", elapsed=?exec_time.elapsed(), ?block_number, "Executed block");
+
+                if let Err(err) = self.consensus.validate_block_post_execution(
+                    &block,
+                    PostExecutionInput::new(&output.receipts, &output.requests),
+                ) {
+                    // call post-block hook
+                    self.invalid_block_hook.on_invalid_block(
+                        &parent_block,
+                        &block.clone().seal_slow(),
+                        &output,
+                        None,
+                    );
+                    return Err(err.into())
+                }
+
+                let hashed_state = self.provider.hashed_post_state(&output.state);
 
-        let hashed_state = self.provider.hashed_post_state(&output.state);
+                let state_root_task_result = state_root_handle.map(|handle| {
+                    let wait_time = Instant::now();
+                    let result = handle.wait_for_result();
+                    (result, wait_time.elapsed())
+                });
+
+                Ok((output, hashed_state, state_root_task_result))
+            },
+        )?;
 
         trace!(target: "engine::tree", block=?sealed_block.num_hash(), "Calculating block state root");
         let root_time = Instant::now();
         let mut state_root_result = None;
-
-        // TODO: switch to calculate state root using `StateRootTask`.
+        let mut state_root_task_wait = Duration::ZERO;
+        let mut state_root_task_trie = None;
+
+        if let Some((result, waited)) = state_root_task_result {
+            state_root_task_wait = waited;
+            match result {
+                Ok(StateRootComputeOutcome {
+                    state_root: (state_root, trie_output),
+                    total_time,
+                    trie,
+                    ..
+                }) => {
+                    self.metrics.block_validation.record_state_root_task(total_time, waited);
+                    if state_root == block.header().state_root() {
+                        state_root_result = Some((state_root, trie_output));
+                        state_root_task_trie = Some(trie);
+                    } else {
+                        // the block is only rejected if the fallback computation agrees
+                        debug!(target: "engine::tree", block=?sealed_block.num_hash(), ?state_root, "State root task returned a mismatched state root, falling back");
+                        self.metrics.block_validation.state_root_task_fallbacks_total.increment(1);
+                    }
+                }
+                Err(error) => {
+                    debug!(target: "engine::tree", block=?sealed_block.num_hash(), %error, "State root task failed, falling back");
+                    self.metrics.block_validation.state_root_task_fallbacks_total.increment(1);
+                }
+            }
+        }
 
         // We attempt to compute state root in parallel if we are currently not persisting anything
         // to database. This is safe, because the database state cannot change until we
         // finish parallel computation. It is important that nothing is being persisted as
         // we are computing in parallel, because we initialize a different database transaction
         // per thread and it might end up with a different view of the database.
-        let persistence_in_progress = self.persistence_state.in_progress();
-        if !persistence_in_progress {
+        if state_root_result.is_none() && !persistence_in_progress {
             state_root_result = match self
                 .compute_state_root_parallel(block.header().parent_hash(), &hashed_state)
             {
@@ -2297,7 +2517,7 @@ where
             .into())
         }
 
-        let root_elapsed = root_time.elapsed();
+        let root_elapsed = root_time.elapsed() + state_root_task_wait;
         self.metrics.block_validation.record_state_root(&trie_output, root_elapsed.as_secs_f64());
         debug!(target: "engine::tree", ?root_elapsed, block=?sealed_block.num_hash(), "Calculated state root");
 
@@ -2318,6 +2538,11 @@ where
         self.state.tree_state.insert_executed(executed);
         self.metrics.engine.executed_blocks.set(self.state.tree_state.block_count() as f64);
 
+        // keep the sparse trie around for the state root computation of the child block
+        if let Some(trie) = state_root_task_trie {
+            self.preserved_sparse_trie = Some(PreservedSparseTrie::new(block_hash, trie));
+        }
+
         // emit insert event
         let elapsed = start.elapsed();
         let engine_event = if self.is_fork(block_hash)? {
@@ -2331,6 +2556,32 @@ where
         Ok(InsertPayloadOk2::Inserted(BlockStatus2::Valid))
     }
 
+    /// Returns the configuration and the database provider of a [`StateRootTask`] computing the
+    /// state root of a block on top of the given parent.
+    ///
+    /// Returns `None` if the input could not be created, the state root is computed after
+    /// execution in that case.
+    fn state_root_task_input(
+        &self,
+        parent_hash: B256,
+    ) -> Option<(StateRootConfig<P>, <P as DatabaseProviderFactory>::Provider)> {
+        let input = ConsistentDbView::new_with_latest_tip(self.provider.clone()).and_then(
+            |consistent_view| {
+                let trie_input = self.compute_trie_input(&consistent_view, parent_hash)?;
+                let provider_ro = consistent_view.provider_ro()?;
+                Ok((StateRootConfig::new_from_input(consistent_view, trie_input), provider_ro))
+            },
+        );
+
+        match input {
+            Ok(input) => Some(input),
+            Err(error) => {
+                debug!(target: "engine::tree", %parent_hash, %error, "Failed to create state root task input");
+                None
+            }
+        }
+    }
+
     /// Compute state root for the given hashed post state in parallel.
     ///
     /// # Returns
@@ -2344,10 +2595,23 @@ where
         parent_hash: B256,
         hashed_state: &HashedPostState,
     ) -> Result<(B256, TrieUpdates), ParallelStateRootError> {
-        // TODO: when we switch to calculate state root using `StateRootTask` this
-        // method can be still useful to calculate the required `TrieInput` to
-        // create the task.
         let consistent_view = ConsistentDbView::new_with_latest_tip(self.provider.clone())?;
+        let mut input = self.compute_trie_input(&consistent_view, parent_hash)?;
+
+        // Extend with block we are validating root for.
+        input.append_ref(hashed_state);
+
+        ParallelStateRoot::new(consistent_view, input).incremental_root_with_updates()
+    }
+
+    /// Returns the [`TrieInput`] with the changes of all blocks between the persisted state of the
+    /// given view and the given parent block, required to compute the state root of a block on
+    /// top of the parent.
+    fn compute_trie_input(
+        &self,
+        consistent_view: &ConsistentDbView<P>,
+        parent_hash: B256,
+    ) -> ProviderResult<TrieInput> {
         let mut input = TrieInput::default();
 
         if let Some((historical, blocks)) = self.state.tree_state.blocks_by_hash(parent_hash) {
@@ -2367,10 +2631,7 @@ where
             input.append(revert_state);
         }
 
-        // Extend with block we are validating root for.
-        input.append_ref(hashed_state);
-
-        ParallelStateRoot::new(consistent_view, input).incremental_root_with_updates()
+        Ok(input)
     }
 
     /// Handles an error that occurred while inserting a block.
@@ -2392,6 +2653,7 @@ where
         // invalid headers cache and `Ok` with [PayloadStatusEnum::Invalid] is
         // returned.
         warn!(target: "engine::tree", invalid_hash=?block.hash(), invalid_number=?block.number(), %validation_err, "Invalid block error on new payload");
+        self.invalid_block_hook.on_invalid_block_error(&block, &validation_err);
         let latest_valid_hash = if validation_err.is_block_pre_merge() {
             // zero hash must be returned if block is pre-merge
             Some(B256::ZERO)
@@ -2634,21 +2896,30 @@ pub enum AdvancePersistenceError {
 mod tests {
     use super::*;
     use crate::persistence::PersistenceAction;
-    use alloy_consensus::Header;
-    use alloy_primitives::Bytes;
+    use alloy_consensus::{Header, EMPTY_ROOT_HASH};
+    use alloy_eips::eip1559::ETHEREUM_BLOCK_GAS_LIMIT;
+    use alloy_primitives::{Address, Bytes};
     use alloy_rlp::Decodable;
     use alloy_rpc_types_engine::{CancunPayloadFields, ExecutionPayloadSidecar};
     use assert_matches::assert_matches;
     use reth_beacon_consensus::EthBeaconConsensus;
     use reth_chain_state::{test_utils::TestBlockBuilder, BlockState};
-    use reth_chainspec::{ChainSpec, HOLESKY, MAINNET};
+    use reth_chainspec::{ChainSpec, ChainSpecBuilder, HOLESKY, MAINNET};
+    use reth_db_common::init::init_genesis;
     use reth_engine_primitives::ForkchoiceStatus;
     use reth_ethereum_engine_primitives::{EthEngineTypes, EthereumEngineValidator};
     use reth_evm::test_utils::MockExecutorProvider;
-    use reth_primitives::{Block, BlockExt, EthPrimitives};
-    use reth_provider::test_utils::MockEthProvider;
+    use reth_primitives::{Account, Block, BlockExt, EthPrimitives};
+    use reth_provider::{
+        providers::BlockchainProvider2,
+        test_utils::{
+            create_test_provider_factory_with_chain_spec, MockEthProvider, MockNodeTypesWithDB,
+        },
+    };
+    use reth_revm::db::BundleState;
     use reth_rpc_types_compat::engine::{block_to_payload_v1, payload::block_to_payload_v3};
-    use reth_trie::updates::TrieUpdates;
+    use reth_trie::{root::state_root_unhashed, updates::TrieUpdates};
+    use revm_primitives::AccountInfo;
     use std::{
         str::FromStr,
         sync::mpsc::{channel, Sender},
@@ -3634,6 +3905,42 @@ mod tests {
             .any(|b| b.block.number == 4 && b.block.hash() == blocks[4].block.hash()));
     }
 
+    #[tokio::test]
+    async fn test_set_head() {
+        let chain_spec = MAINNET.clone();
+        let mut test_harness = TestHarness::new(chain_spec);
+        let mut test_block_builder = TestBlockBuilder::default();
+
+        let blocks: Vec<_> = test_block_builder.get_executed_blocks(0..10).collect();
+        test_harness = test_harness.with_blocks(blocks.clone());
+        test_harness.tree.persistence_state.last_persisted_block = blocks[3].block.num_hash();
+
+        // a sidechain above the new head is removed as well
+        let fork_block =
+            test_block_builder.get_executed_block_with_number(8, blocks[6].block.hash());
+        let fork_block_hash = fork_block.block.hash();
+        test_harness.tree.state.tree_state.insert_executed(fork_block);
+
+        assert!(test_harness.tree.on_set_head(10).is_err());
+
+        // rewind in memory
+        test_harness.tree.on_set_head(6).unwrap();
+        test_harness.check_canon_head(blocks[6].block.hash());
+        assert_eq!(
+            test_harness.tree.canonical_in_memory_state.get_canonical_head().hash(),
+            blocks[6].block.hash()
+        );
+        assert!(test_harness.tree.canonical_in_memory_state.state_by_number(7).is_none());
+        assert!(test_harness.tree.state.tree_state.block_by_hash(blocks[7].block.hash()).is_none());
+        assert!(test_harness.tree.state.tree_state.block_by_hash(fork_block_hash).is_none());
+        assert!(test_harness.tree.persistence_state.remove_above_state.is_empty());
+
+        // rewind below the last persisted block
+        test_harness.tree.on_set_head(2).unwrap();
+        test_harness.check_canon_head(blocks[2].block.hash());
+        assert_eq!(test_harness.tree.persistence_state.remove_above_state, [2]);
+    }
+
     #[tokio::test]
     async fn test_engine_tree_fcu_missing_head() {
         let chain_spec = MAINNET.clone();
@@ -4135,4 +4442,238 @@ mod tests {
         let fork_tip_hash = side_chain.last().unwrap().hash();
         test_harness.send_fcu(fork_tip_hash, ForkchoiceStatus::Invalid).await;
     }
+
+    /// A tree on top of a database that computes the state roots with the state root task.
+    struct StateRootTaskHarness {
+        tree: EngineApiTreeHandler<
+            EthPrimitives,
+            BlockchainProvider2<MockNodeTypesWithDB>,
+            MockExecutorProvider,
+            EthEngineTypes,
+            EthereumEngineValidator,
+        >,
+        executor_provider: MockExecutorProvider,
+        block_builder: TestBlockBuilder,
+        genesis: SealedHeader,
+        /// Balances of all accounts in the post state of each built block.
+        balances: HashMap<B256, BTreeMap<Address, U256>>,
+        _from_tree_rx: UnboundedReceiver<EngineApiEvent>,
+        _action_rx: Receiver<PersistenceAction>,
+    }
+
+    impl StateRootTaskHarness {
+        fn new() -> Self {
+            // mainnet rules with an empty genesis state
+            let mut genesis = MAINNET.genesis.clone();
+            genesis.alloc.clear();
+            genesis.gas_limit = ETHEREUM_BLOCK_GAS_LIMIT;
+            let chain_spec = Arc::new(ChainSpecBuilder::from(&*MAINNET).genesis(genesis).build());
+
+            let factory = create_test_provider_factory_with_chain_spec(chain_spec.clone());
+            init_genesis(&factory).unwrap();
+            let provider = BlockchainProvider2::new(factory).unwrap();
+            let executor_provider = MockExecutorProvider::default();
+
+            let (action_tx, action_rx) = channel();
+            let (from_tree_tx, from_tree_rx) = unbounded_channel();
+            let (to_payload_service, _payload_command_rx) = unbounded_channel();
+
+            let genesis = SealedHeader::seal(chain_spec.genesis_header().clone());
+            let tree = EngineApiTreeHandler::new(
+                provider.clone(),
+                executor_provider.clone(),
+                Arc::new(EthBeaconConsensus::new(chain_spec.clone())),
+                EthereumEngineValidator::new(chain_spec.clone()),
+                from_tree_tx,
+                EngineApiTreeState::new(10, 10, genesis.num_hash()),
+                provider.canonical_in_memory_state(),
+                PersistenceHandle::new(action_tx),
+                PersistenceState::default(),
+                PayloadBuilderHandle::new(to_payload_service),
+                TreeConfig::default().with_state_root_task(true),
+                EngineApiKind::Ethereum,
+            );
+
+            Self {
+                tree,
+                executor_provider,
+                block_builder: TestBlockBuilder::default().with_chain_spec((*chain_spec).clone()),
+                balances: HashMap::from_iter([(genesis.hash(), BTreeMap::new())]),
+                genesis,
+                _from_tree_rx: from_tree_rx,
+                _action_rx: action_rx,
+            }
+        }
+
+        /// Builds a block on top of the given parent that sets the balances of the given accounts
+        /// and queues its execution outcome.
+        fn build_block(
+            &mut self,
+            parent: &SealedHeader,
+            changes: &[(Address, U256)],
+        ) -> SealedBlockWithSenders {
+            let mut block =
+                self.block_builder.generate_random_block(parent.number + 1, parent.hash());
+            let receipts = self.block_builder.get_execution_outcome(block.clone()).receipts;
+
+            let mut balances = self.balances[&parent.hash()].clone();
+            let mut bundle = BundleState::builder(block.number..=block.number);
+            for (address, balance) in changes {
+                if let Some(previous) = balances.insert(*address, *balance) {
+                    bundle = bundle
+                        .state_original_account_info(*address, AccountInfo::from_balance(previous));
+                }
+                bundle = bundle
+                    .state_present_account_info(*address, AccountInfo::from_balance(*balance));
+            }
+
+            let mut header = block.header.header().clone();
+            header.state_root = state_root_unhashed(balances.iter().map(|(address, balance)| {
+                (
+                    *address,
+                    Account { balance: *balance, ..Default::default() }
+                        .into_trie_account(EMPTY_ROOT_HASH),
+                )
+            }));
+            block.block.header = SealedHeader::seal(header);
+
+            self.balances.insert(block.hash(), balances);
+            self.executor_provider.extend([ExecutionOutcome::new(
+                bundle.build(),
+                receipts,
+                block.number,
+                Vec::new(),
+            )]);
+            block
+        }
+    }
+
+    #[test]
+    fn test_state_root_task() {
+        reth_tracing::init_test_tracing();
+        let mut test_harness = StateRootTaskHarness::new();
+
+        let genesis = test_harness.genesis.clone();
+        let block = test_harness.build_block(
+            &genesis,
+            &[
+                (Address::with_last_byte(1), U256::from(1)),
+                (Address::with_last_byte(2), U256::from(2)),
+            ],
+        );
+        assert_eq!(
+            test_harness.tree.insert_block(block.clone()).unwrap(),
+            InsertPayloadOk2::Inserted(BlockStatus2::Valid)
+        );
+
+        // the root was computed by the task, which preserves its trie for the child block
+        assert_eq!(
+            test_harness.tree.preserved_sparse_trie.as_ref().map(|trie| trie.block_hash()),
+            Some(block.hash())
+        );
+    }
+
+    #[test]
+    fn test_state_root_task_fallback() {
+        reth_tracing::init_test_tracing();
+        let mut test_harness = StateRootTaskHarness::new();
+
+        // without any state changes the trie of the task is never revealed and the task fails
+        let genesis = test_harness.genesis.clone();
+        let block = test_harness.build_block(&genesis, &[]);
+        assert_eq!(
+            test_harness.tree.insert_block(block.clone()).unwrap(),
+            InsertPayloadOk2::Inserted(BlockStatus2::Valid)
+        );
+        assert!(test_harness.tree.preserved_sparse_trie.is_none());
+
+        // the parallel state root still rejects an invalid state root
+        let mut block = test_harness
+            .build_block(&block.block.header, &[(Address::with_last_byte(1), U256::from(1))]);
+        let mut header = block.header.header().clone();
+        header.state_root = B256::random();
+        block.block.header = SealedHeader::seal(header);
+        assert_matches!(
+            test_harness.tree.insert_block(block).unwrap_err().kind(),
+            InsertBlockErrorKindTwo::Consensus(ConsensusError::BodyStateRootDiff(_))
+        );
+        assert!(test_harness.tree.preserved_sparse_trie.is_none());
+    }
+
+    #[test]
+    fn test_state_root_task_consecutive_blocks() {
+        reth_tracing::init_test_tracing();
+        let mut test_harness = StateRootTaskHarness::new();
+
+        let mut parent = test_harness.genesis.clone();
+        for i in 1..=3u8 {
+            // update the account created by the parent block and create a new one
+            let block = test_harness.build_block(
+                &parent,
+                &[
+                    (Address::with_last_byte(i), U256::from(i)),
+                    (Address::with_last_byte(i + 1), U256::from(i)),
+                ],
+            );
+            assert_eq!(
+                test_harness.tree.insert_block(block.clone()).unwrap(),
+                InsertPayloadOk2::Inserted(BlockStatus2::Valid)
+            );
+            // the task computed the state root with the trie preserved for the parent block
+            assert_eq!(
+                test_harness.tree.preserved_sparse_trie.as_ref().map(|trie| trie.block_hash()),
+                Some(block.hash())
+            );
+            parent = block.block.header;
+        }
+    }
+
+    #[test]
+    fn test_state_root_task_fork() {
+        reth_tracing::init_test_tracing();
+        let mut test_harness = StateRootTaskHarness::new();
+
+        let genesis = test_harness.genesis.clone();
+        let block_1 =
+            test_harness.build_block(&genesis, &[(Address::with_last_byte(1), U256::from(1))]);
+        test_harness.tree.insert_block(block_1.clone()).unwrap();
+
+        let block_2a = test_harness.build_block(
+            &block_1.block.header,
+            &[
+                (Address::with_last_byte(1), U256::from(2)),
+                (Address::with_last_byte(2), U256::from(2)),
+            ],
+        );
+        test_harness.tree.insert_block(block_2a.clone()).unwrap();
+
+        // the trie preserved for block 2a doesn't hold the state of block 1 anymore
+        let block_2b = test_harness.build_block(
+            &block_1.block.header,
+            &[
+                (Address::with_last_byte(1), U256::from(3)),
+                (Address::with_last_byte(3), U256::from(3)),
+            ],
+        );
+        assert_eq!(
+            test_harness.tree.insert_block(block_2b.clone()).unwrap(),
+            InsertPayloadOk2::Inserted(BlockStatus2::Valid)
+        );
+        assert_eq!(
+            test_harness.tree.preserved_sparse_trie.as_ref().map(|trie| trie.block_hash()),
+            Some(block_2b.hash())
+        );
+
+        // the trie preserved for block 2b is dropped for a child of block 2a
+        let block_3a = test_harness
+            .build_block(&block_2a.block.header, &[(Address::with_last_byte(2), U256::from(4))]);
+        assert_eq!(
+            test_harness.tree.insert_block(block_3a.clone()).unwrap(),
+            InsertPayloadOk2::Inserted(BlockStatus2::Valid)
+        );
+        assert_eq!(
+            test_harness.tree.preserved_sparse_trie.as_ref().map(|trie| trie.block_hash()),
+            Some(block_3a.hash())
+        );
+    }
 }
@@ -3,26 +3,26 @@
 use alloy_primitives::map::HashSet;
 use rayon::iter::{ParallelBridge, ParallelIterator};
 use reth_evm::system_calls::OnStateHook;
-use reth_execution_errors::StateProofError;
 use reth_provider::{
-    providers::ConsistentDbView, BlockReader, DBProvider, DatabaseProviderFactory,
-    StateCommitmentProvider,
+    providers::ConsistentDbView, BlockReader, DatabaseProviderFactory, StateCommitmentProvider,
 };
 use reth_trie::{
-    proof::Proof, updates::TrieUpdates, HashedPostState, HashedStorage, MultiProof,
-    MultiProofTargets, Nibbles, TrieInput,
+    prefix_set::TriePrefixSetsMut,
+    updates::{TrieUpdates, TrieUpdatesSorted},
+    HashedPostState, HashedPostStateSorted, HashedStorage, MultiProof, MultiProofTargets, Nibbles,
+    TrieInput,
 };
-use reth_trie_db::DatabaseProof;
-use reth_trie_parallel::root::ParallelStateRootError;
+use reth_trie_parallel::{proof::ParallelProof, root::ParallelStateRootError};
 use reth_trie_sparse::{
-    blinded::{BlindedProvider, BlindedProviderFactory},
+    blinded::{BlindedProvider, BlindedProviderFactory, DefaultBlindedProviderFactory},
     errors::{SparseStateTrieError, SparseStateTrieResult, SparseTrieError, SparseTrieErrorKind},
     SparseStateTrie,
 };
 use revm_primitives::{keccak256, EvmState, B256};
 use std::{
-    collections::BTreeMap,
+    collections::{BTreeMap, VecDeque},
     ops::Deref,
+    panic::{self, AssertUnwindSafe},
     sync::{
         mpsc::{self, channel, Receiver, Sender},
         Arc,
@@ -35,6 +35,12 @@ use tracing::{debug, error, trace};
 /// The level below which the sparse trie hashes are calculated in [`update_sparse_trie`].
 const SPARSE_TRIE_INCREMENTAL_LEVEL: usize = 2;
 
+/// The depth a [`PreservedSparseTrie`] is pruned to.
+///
+/// The upper levels of the trie are revealed for almost every block, while the number of their
+/// nodes is bounded no matter how many nodes were revealed over time.
+const PRESERVED_SPARSE_TRIE_DEPTH: usize = 4;
+
 /// Outcome of the state root computation, including the state root itself with
 /// the trie updates and the total time spent.
 #[derive(Debug)]
@@ -45,6 +51,43 @@ pub struct StateRootComputeOutcome {
     pub total_time: Duration,
     /// The time spent calculating the state root since the last state update
     pub time_from_last_update: Duration,
+    /// The sparse trie the state root was calculated with, detached from the blinded node
+    /// provider of the task.
+    pub trie: SparseStateTrie,
+}
+
+/// The sparse trie of the state root computation of a block, kept in memory to be reused for the
+/// state root computation of its child block.
+///
+/// The trie represents the post state of the block, so the nodes revealed for the block don't
+/// need to be fetched again for its child.
+#[derive(Debug)]
+pub struct PreservedSparseTrie {
+    /// Hash of the block whose post state the trie represents.
+    block_hash: B256,
+    /// The sparse trie.
+    trie: SparseStateTrie,
+}
+
+impl PreservedSparseTrie {
+    /// Creates a new preserved sparse trie for the block with the given hash.
+    ///
+    /// The trie is pruned to [`PRESERVED_SPARSE_TRIE_DEPTH`], the pruned nodes are fetched again
+    /// once they're needed.
+    pub fn new(block_hash: B256, mut trie: SparseStateTrie) -> Self {
+        trie.prune(PRESERVED_SPARSE_TRIE_DEPTH);
+        Self { block_hash, trie }
+    }
+
+    /// Returns the hash of the block whose post state the trie represents.
+    pub const fn block_hash(&self) -> B256 {
+        self.block_hash
+    }
+
+    /// Consumes the type and returns the sparse trie.
+    pub fn into_trie(self) -> SparseStateTrie {
+        self.trie
+    }
 }
 
 /// Result of the state root calculation
@@ -52,13 +95,11 @@ pub(crate) type StateRootResult = Result<StateRootComputeOutcome, ParallelStateR
 
 /// Handle to a spawned state root task.
 #[derive(Debug)]
-#[allow(dead_code)]
 pub struct StateRootHandle {
     /// Channel for receiving the final result.
     rx: mpsc::Receiver<StateRootResult>,
 }
 
-#[allow(dead_code)]
 impl StateRootHandle {
     /// Creates a new handle from a receiver.
     pub(crate) const fn new(rx: mpsc::Receiver<StateRootResult>) -> Self {
@@ -66,34 +107,56 @@ impl StateRootHandle {
     }
 
     /// Waits for the state root calculation to complete.
+    ///
+    /// Returns an error if the task exited without sending a result.
     pub fn wait_for_result(self) -> StateRootResult {
-        self.rx.recv().expect("state root task was dropped without sending result")
+        self.rx.recv().unwrap_or_else(|_| {
+            Err(ParallelStateRootError::Other(
+                "state root task was dropped without sending result".into(),
+            ))
+        })
     }
 }
 
 /// Common configuration for state root tasks
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct StateRootConfig<Factory> {
     /// View over the state in the database.
     pub consistent_view: ConsistentDbView<Factory>,
-    /// Latest trie input.
-    pub input: Arc<TrieInput>,
+    /// The sorted collection of cached in-memory intermediate trie nodes that
+    /// can be reused for computation.
+    pub nodes_sorted: Arc<TrieUpdatesSorted>,
+    /// The sorted in-memory overlay hashed state.
+    pub state_sorted: Arc<HashedPostStateSorted>,
+    /// The collection of prefix sets for the computation. Since the prefix sets _always_
+    /// invalidate the in-memory nodes, not all keys from `state_sorted` might be present here,
+    /// if we have cached nodes for them.
+    pub prefix_sets: Arc<TriePrefixSetsMut>,
+}
+
+impl<Factory> StateRootConfig<Factory> {
+    /// Creates a new state root config from the consistent view and the trie input.
+    pub fn new_from_input(consistent_view: ConsistentDbView<Factory>, input: TrieInput) -> Self {
+        Self {
+            consistent_view,
+            nodes_sorted: Arc::new(input.nodes.into_sorted()),
+            state_sorted: Arc::new(input.state.into_sorted()),
+            prefix_sets: Arc::new(input.prefix_sets),
+        }
+    }
 }
 
 /// Messages used internally by the state root task
 #[derive(Debug)]
-#[allow(dead_code)]
-pub enum StateRootMessage<BPF: BlindedProviderFactory> {
+pub enum StateRootMessage {
     /// New state update from transaction execution
     StateUpdate(EvmState),
     /// Proof calculation completed for a specific state update
     ProofCalculated(Box<ProofCalculated>),
     /// Error during proof calculation
-    ProofCalculationError(StateProofError),
-    /// State root calculation completed
+    ProofCalculationError(ParallelStateRootError),
+    /// State root calculation completed, the updated sparse trie was sent back to the task.
     RootCalculated {
-        /// The updated sparse trie
-        trie: Box<SparseStateTrie<BPF>>,
         /// Time taken to calculate the root
         elapsed: Duration,
     },
@@ -184,25 +247,23 @@ impl ProofSequencer {
 }
 
 /// A wrapper for the sender that signals completion when dropped
-#[allow(dead_code)]
-pub(crate) struct StateHookSender<BPF: BlindedProviderFactory>(Sender<StateRootMessage<BPF>>);
+pub(crate) struct StateHookSender(Sender<StateRootMessage>);
 
-#[allow(dead_code)]
-impl<BPF: BlindedProviderFactory> StateHookSender<BPF> {
-    pub(crate) const fn new(inner: Sender<StateRootMessage<BPF>>) -> Self {
+impl StateHookSender {
+    pub(crate) const fn new(inner: Sender<StateRootMessage>) -> Self {
         Self(inner)
     }
 }
 
-impl<BPF: BlindedProviderFactory> Deref for StateHookSender<BPF> {
-    type Target = Sender<StateRootMessage<BPF>>;
+impl Deref for StateHookSender {
+    type Target = Sender<StateRootMessage>;
 
     fn deref(&self) -> &Self::Target {
         &self.0
     }
 }
 
-impl<BPF: BlindedProviderFactory> Drop for StateHookSender<BPF> {
+impl Drop for StateHookSender {
     fn drop(&mut self) {
         // Send completion signal when the sender is dropped
         let _ = self.0.send(StateRootMessage::FinishedStateUpdates);
@@ -254,19 +315,35 @@ pub struct StateRootTask<Factory, BPF: BlindedProviderFactory> {
     /// Task configuration.
     config: StateRootConfig<Factory>,
     /// Receiver for state root related messages.
-    rx: Receiver<StateRootMessage<BPF>>,
+    rx: Receiver<StateRootMessage>,
     /// Sender for state root related messages.
-    tx: Sender<StateRootMessage<BPF>>,
+    tx: Sender<StateRootMessage>,
+    /// Receiver for the sparse trie returned by a completed root calculation.
+    ///
+    /// The trie is kept out of [`StateRootMessage`], so that the state hook doesn't borrow from
+    /// the blinded provider and can be handed to the executor.
+    sparse_trie_rx: Receiver<Box<SparseStateTrie<BPF>>>,
+    /// Sender for the sparse trie returned by a completed root calculation.
+    sparse_trie_tx: Sender<Box<SparseStateTrie<BPF>>>,
     /// Proof targets that have been already fetched.
     fetched_proof_targets: MultiProofTargets,
     /// Proof sequencing handler.
     proof_sequencer: ProofSequencer,
+    /// State updates with their proof targets and sequence numbers, waiting for a free slot to
+    /// calculate the proof.
+    pending_proofs: VecDeque<(HashedPostState, MultiProofTargets, u64)>,
+    /// Number of proof calculations currently in progress.
+    inflight_proofs: usize,
+    /// Maximum number of concurrent proof calculations.
+    ///
+    /// Every proof calculation blocks its thread until the storage proofs it spawns onto the
+    /// rayon pool are done, so this must stay below the size of the pool.
+    max_concurrent_proofs: usize,
     /// The sparse trie used for the state root calculation. If [`None`], then update is in
     /// progress.
     sparse_trie: Option<Box<SparseStateTrie<BPF>>>,
 }
 
-#[allow(dead_code)]
 impl<'env, Factory, ABP, SBP, BPF> StateRootTask<Factory, BPF>
 where
     Factory: DatabaseProviderFactory<Provider: BlockReader>
@@ -284,36 +361,66 @@ where
 {
     /// Creates a new state root task with the unified message channel
     pub fn new(config: StateRootConfig<Factory>, blinded_provider: BPF) -> Self {
+        Self::new_with_sparse_trie(config, blinded_provider, SparseStateTrie::default())
+    }
+
+    /// Creates a new state root task that continues with the given sparse trie, preserved from
+    /// the state root computation of the parent block.
+    ///
+    /// Nodes that were already revealed in the sparse trie are not fetched again.
+    pub fn new_with_sparse_trie(
+        config: StateRootConfig<Factory>,
+        blinded_provider: BPF,
+        sparse_trie: SparseStateTrie,
+    ) -> Self {
         let (tx, rx) = channel();
+        let (sparse_trie_tx, sparse_trie_rx) = channel();
 
         Self {
             config,
             rx,
             tx,
-            fetched_proof_targets: Default::default(),
+            sparse_trie_rx,
+            sparse_trie_tx,
+            fetched_proof_targets: sparse_trie.revealed().clone(),
             proof_sequencer: ProofSequencer::new(),
-            sparse_trie: Some(Box::new(SparseStateTrie::new(blinded_provider).with_updates(true))),
+            pending_proofs: VecDeque::new(),
+            inflight_proofs: 0,
+            max_concurrent_proofs: (rayon::current_num_threads() / 2).max(1),
+            sparse_trie: Some(Box::new(
+                sparse_trie.with_provider_factory(blinded_provider).with_updates(true),
+            )),
         }
     }
 
     /// Spawns the state root task and returns a handle to await its result.
     pub fn spawn<'scope>(self, scope: &'scope thread::Scope<'scope, 'env>) -> StateRootHandle {
         let (tx, rx) = mpsc::sync_channel(1);
-        std::thread::Builder::new()
-            .name("State Root Task".to_string())
-            .spawn_scoped(scope, move || {
+        let spawned = std::thread::Builder::new().name("State Root Task".to_string()).spawn_scoped(
+            scope,
+            move || {
                 debug!(target: "engine::tree", "Starting state root task");
 
-                let result = rayon::scope(|scope| self.run(scope));
+                // a panic would otherwise be propagated to the caller when the scope ends
+                let result =
+                    panic::catch_unwind(AssertUnwindSafe(|| rayon::scope(|scope| self.run(scope))))
+                        .unwrap_or_else(|_| {
+                            Err(ParallelStateRootError::Other("state root task panicked".into()))
+                        });
                 let _ = tx.send(result);
-            })
-            .expect("failed to spawn state root thread");
+            },
+        );
+        // the sender is dropped if the thread couldn't be spawned, which the handle reports as an
+        // error
+        if let Err(error) = spawned {
+            error!(target: "engine::root", %error, "Failed to spawn state root thread");
+        }
 
         StateRootHandle::new(rx)
     }
 
     /// Returns a state hook to be used to send state updates to this task.
-    pub fn state_hook(&self) -> impl OnStateHook {
+    pub fn state_hook(&self) -> impl OnStateHook + 'static {
         let state_hook = StateHookSender::new(self.tx.clone());
 
         move |state: &EvmState| {
@@ -325,43 +432,61 @@ where
 
     /// Handles state updates.
     ///
-    /// Returns proof targets derived from the state update.
-    fn on_state_update(
-        scope: &rayon::Scope<'env>,
-        view: ConsistentDbView<Factory>,
-        input: Arc<TrieInput>,
-        update: EvmState,
-        fetched_proof_targets: &mut MultiProofTargets,
-        proof_sequence_number: u64,
-        state_root_message_sender: Sender<StateRootMessage<BPF>>,
-    ) {
+    /// Derives proof targets from the state update and queues the proof calculation for them.
+    fn on_state_update(&mut self, scope: &rayon::Scope<'env>, update: EvmState) {
         let hashed_state_update = evm_state_to_hashed_post_state(update);
 
-        let proof_targets = get_proof_targets(&hashed_state_update, fetched_proof_targets);
-        extend_multi_proof_targets_ref(fetched_proof_targets, &proof_targets);
+        let proof_targets = get_proof_targets(&hashed_state_update, &self.fetched_proof_targets);
+        extend_multi_proof_targets_ref(&mut self.fetched_proof_targets, &proof_targets);
 
-        // Dispatch proof gathering for this state update
-        scope.spawn(move |_| {
-            let provider = match view.provider_ro() {
-                Ok(provider) => provider,
-                Err(error) => {
-                    error!(target: "engine::root", ?error, "Could not get provider");
-                    return;
-                }
+        let sequence_number = self.proof_sequencer.next_sequence();
+        self.pending_proofs.push_back((hashed_state_update, proof_targets, sequence_number));
+        self.spawn_pending_proofs(scope);
+    }
+
+    /// Dispatches queued proof calculations while there are free slots.
+    fn spawn_pending_proofs(&mut self, scope: &rayon::Scope<'env>) {
+        while self.inflight_proofs < self.max_concurrent_proofs {
+            let Some((state_update, targets, sequence_number)) = self.pending_proofs.pop_front()
+            else {
+                break
             };
 
-            // TODO: replace with parallel proof
-            let result = Proof::overlay_multiproof(
-                provider.tx_ref(),
-                // TODO(alexey): this clone can be expensive, we should avoid it
-                input.as_ref().clone(),
-                proof_targets.clone(),
+            self.inflight_proofs += 1;
+            Self::spawn_proof_calculation(
+                scope,
+                self.config.clone(),
+                state_update,
+                targets,
+                sequence_number,
+                self.tx.clone(),
             );
+        }
+    }
+
+    /// Spawns the multiproof calculation for the given proof targets.
+    fn spawn_proof_calculation(
+        scope: &rayon::Scope<'env>,
+        config: StateRootConfig<Factory>,
+        state_update: HashedPostState,
+        proof_targets: MultiProofTargets,
+        proof_sequence_number: u64,
+        state_root_message_sender: Sender<StateRootMessage>,
+    ) {
+        scope.spawn(move |_| {
+            let result = ParallelProof::new(
+                config.consistent_view,
+                config.nodes_sorted,
+                config.state_sorted,
+                config.prefix_sets,
+            )
+            .with_branch_node_hash_masks(true)
+            .multiproof(proof_targets.clone());
             match result {
                 Ok(proof) => {
                     let _ = state_root_message_sender.send(StateRootMessage::ProofCalculated(
                         Box::new(ProofCalculated {
-                            state_update: hashed_state_update,
+                            state_update,
                             targets: proof_targets,
                             proof,
                             sequence_number: proof_sequence_number,
@@ -425,6 +550,7 @@ where
         let targets = get_proof_targets(&state, &targets);
 
         let tx = self.tx.clone();
+        let sparse_trie_tx = self.sparse_trie_tx.clone();
         scope.spawn(move |_| {
             let result = update_sparse_trie(trie, multiproof, targets, state);
             match result {
@@ -434,7 +560,9 @@ where
                         ?elapsed,
                         "Root calculation completed, sending result"
                     );
-                    let _ = tx.send(StateRootMessage::RootCalculated { trie, elapsed });
+                    // the trie is sent first, so it's available once the message is received
+                    let _ = sparse_trie_tx.send(trie);
+                    let _ = tx.send(StateRootMessage::RootCalculated { elapsed });
                 }
                 Err(e) => {
                     let _ = tx.send(StateRootMessage::RootCalculationError(e));
@@ -443,13 +571,63 @@ where
         });
     }
 
+    /// Returns `true` if all state updates were received and applied to the sparse trie.
+    fn is_finished(
+        &self,
+        updates_finished: bool,
+        updates_received: u64,
+        proofs_processed: u64,
+        current_state_update: &HashedPostState,
+        current_multiproof: &MultiProof,
+    ) -> bool {
+        updates_finished &&
+            proofs_processed >= updates_received &&
+            !self.proof_sequencer.has_pending() &&
+            current_state_update.is_empty() &&
+            current_multiproof.account_subtree.is_empty() &&
+            current_multiproof.storages.is_empty() &&
+            // no root calculation is in progress
+            self.sparse_trie.is_some()
+    }
+
+    /// Calculates the final state root from the sparse trie.
+    fn finish(
+        &mut self,
+        first_update_time: Option<Instant>,
+        last_update_time: Option<Instant>,
+    ) -> StateRootResult {
+        let total_time = first_update_time.map(|time| time.elapsed()).unwrap_or_default();
+        let time_from_last_update = last_update_time.map(|time| time.elapsed()).unwrap_or_default();
+        debug!(target: "engine::root", ?total_time, ?time_from_last_update, "Calculating final state root");
+
+        let Some(mut trie) = self.sparse_trie.take() else {
+            return Err(ParallelStateRootError::Other(
+                "sparse trie update is still in progress".into(),
+            ))
+        };
+        // the trie is blind if there were no state updates for a trie that wasn't preserved
+        let Some(root) = trie.root() else {
+            return Err(ParallelStateRootError::Other("sparse trie is not revealed".into()))
+        };
+        let Some(trie_updates) = trie.take_trie_updates() else {
+            return Err(ParallelStateRootError::Other("sparse trie doesn't retain updates".into()))
+        };
+
+        Ok(StateRootComputeOutcome {
+            state_root: (root, trie_updates),
+            total_time,
+            time_from_last_update,
+            trie: trie.with_provider_factory(DefaultBlindedProviderFactory),
+        })
+    }
+
     fn run(mut self, scope: &rayon::Scope<'env>) -> StateRootResult {
         let mut current_state_update = HashedPostState::default();
         let mut current_proof_targets = MultiProofTargets::default();
         let mut current_multiproof = MultiProof::default();
 
-        let mut updates_received = 0;
-        let mut proofs_processed = 0;
+        let mut updates_received = 0u64;
+        let mut proofs_processed = 0u64;
         let mut roots_calculated = 0;
 
         let mut updates_finished = false;
@@ -476,22 +654,28 @@ where
                             total_updates = updates_received,
                             "Received new state update"
                         );
-                        Self::on_state_update(
-                            scope,
-                            self.config.consistent_view.clone(),
-                            self.config.input.clone(),
-                            update,
-                            &mut self.fetched_proof_targets,
-                            self.proof_sequencer.next_sequence(),
-                            self.tx.clone(),
-                        );
+                        self.on_state_update(scope, update);
                     }
                     StateRootMessage::FinishedStateUpdates => {
                         trace!(target: "engine::root", "Finished state updates");
                         updates_finished = true;
+
+                        // all roots might have been calculated already, e.g. if there were no
+                        // state updates at all
+                        if self.is_finished(
+                            updates_finished,
+                            updates_received,
+                            proofs_processed,
+                            &current_state_update,
+                            &current_multiproof,
+                        ) {
+                            return self.finish(first_update_time, last_update_time)
+                        }
                     }
                     StateRootMessage::ProofCalculated(proof_calculated) => {
                         proofs_processed += 1;
+                        self.inflight_proofs -= 1;
+                        self.spawn_pending_proofs(scope);
                         debug!(
                             target: "engine::root",
                             sequence = proof_calculated.sequence_number,
@@ -526,7 +710,7 @@ where
                             }
                         }
                     }
-                    StateRootMessage::RootCalculated { trie, elapsed } => {
+                    StateRootMessage::RootCalculated { elapsed } => {
                         roots_calculated += 1;
                         debug!(
                             target: "engine::root",
@@ -536,24 +720,29 @@ where
                             updates = updates_received,
                             "Computed intermediate root"
                         );
+                        let Ok(trie) = self.sparse_trie_rx.recv() else {
+                            return Err(ParallelStateRootError::Other(
+                                "Sparse trie channel closed unexpectedly".into(),
+                            ))
+                        };
                         self.sparse_trie = Some(trie);
 
-                        let has_new_proofs = !current_multiproof.account_subtree.is_empty() ||
-                            !current_multiproof.storages.is_empty();
-                        let all_proofs_received = proofs_processed >= updates_received;
-                        let no_pending = !self.proof_sequencer.has_pending();
+                        let has_new_updates = !current_multiproof.account_subtree.is_empty() ||
+                            !current_multiproof.storages.is_empty() ||
+                            !current_state_update.is_empty();
 
                         trace!(
                             target: "engine::root",
-                            has_new_proofs,
-                            all_proofs_received,
-                            no_pending,
+                            has_new_updates,
+                            proofs = proofs_processed,
+                            updates = updates_received,
                             ?updates_finished,
                             "State check"
                         );
 
-                        // only spawn new calculation if we have accumulated new proofs
-                        if has_new_proofs {
+                        // only spawn new calculation if we have accumulated new proofs or state
+                        // updates
+                        if has_new_updates {
                             debug!(
                                 target: "engine::root",
                                 account_proofs = current_multiproof.account_subtree.len(),
@@ -566,36 +755,21 @@ where
                                 std::mem::take(&mut current_proof_targets),
                                 std::mem::take(&mut current_multiproof),
                             );
-                        } else if all_proofs_received && no_pending && updates_finished {
-                            let total_time = first_update_time
-                                .expect("first update time should be set")
-                                .elapsed();
-                            let time_from_last_update =
-                                last_update_time.expect("last update time should be set").elapsed();
+                        } else if self.is_finished(
+                            updates_finished,
+                            updates_received,
+                            proofs_processed,
+                            &current_state_update,
+                            &current_multiproof,
+                        ) {
                             debug!(
                                 target: "engine::root",
                                 total_updates = updates_received,
                                 total_proofs = proofs_processed,
                                 roots_calculated,
-                                ?total_time,
-                                ?time_from_last_update,
                                 "All proofs processed, ending calculation"
                             );
-
-                            let mut trie = self
-                                .sparse_trie
-                                .take()
-                                .expect("sparse trie update should not be in progress");
-                            let root = trie.root().expect("sparse trie should be revealed");
-                            let trie_updates = trie
-                                .take_trie_updates()
-                                .expect("sparse trie should have updates retention enabled");
-
-                            return Ok(StateRootComputeOutcome {
-                                state_root: (root, trie_updates),
-                                total_time,
-                                time_from_last_update,
-                            });
+                            return self.finish(first_update_time, last_update_time)
                         }
                     }
                     StateRootMessage::ProofCalculationError(e) => {
@@ -745,7 +919,9 @@ mod tests {
     use super::*;
     use reth_primitives::{Account as RethAccount, StorageEntry};
     use reth_provider::{
-        providers::ConsistentDbView, test_utils::create_test_provider_factory, HashingWriter,
+        providers::ConsistentDbView,
+        test_utils::{create_test_provider_factory, MockNodeTypesWithDB},
+        HashingWriter, ProviderFactory,
     };
     use reth_testing_utils::generators::{self, Rng};
     use reth_trie::{
@@ -757,7 +933,6 @@ mod tests {
         Account as RevmAccount, AccountInfo, AccountStatus, Address, EvmState, EvmStorageSlot,
         HashMap, B256, KECCAK_EMPTY, U256,
     };
-    use std::sync::Arc;
 
     fn convert_revm_to_reth_account(revm_account: &RevmAccount) -> RethAccount {
         RethAccount {
@@ -865,13 +1040,13 @@ mod tests {
             }
         }
 
-        let config = StateRootConfig {
-            consistent_view: ConsistentDbView::new(factory, None),
-            input: Arc::new(TrieInput::from_state(hashed_state)),
-        };
+        let config = StateRootConfig::new_from_input(
+            ConsistentDbView::new(factory, None),
+            TrieInput::from_state(hashed_state),
+        );
         let provider = config.consistent_view.provider_ro().unwrap();
-        let nodes_sorted = config.input.nodes.clone().into_sorted();
-        let state_sorted = config.input.state.clone().into_sorted();
+        let nodes_sorted = config.nodes_sorted.clone();
+        let state_sorted = config.state_sorted.clone();
         let blinded_provider_factory = ProofBlindedProviderFactory::new(
             InMemoryTrieCursorFactory::new(
                 DatabaseTrieCursorFactory::new(provider.tx_ref()),
@@ -881,7 +1056,7 @@ mod tests {
                 DatabaseHashedCursorFactory::new(provider.tx_ref()),
                 &state_sorted,
             ),
-            Arc::new(config.input.prefix_sets.clone()),
+            config.prefix_sets.clone(),
         );
         let (root_from_task, _) = std::thread::scope(|std_scope| {
             let task = StateRootTask::new(config, blinded_provider_factory);
@@ -904,6 +1079,94 @@ mod tests {
         );
     }
 
+    /// Runs the state root task with the given trie input and preserved sparse trie for the state
+    /// updates.
+    fn run_state_root_task(
+        factory: ProviderFactory<MockNodeTypesWithDB>,
+        input: TrieInput,
+        sparse_trie: Option<SparseStateTrie>,
+        state_updates: Vec<EvmState>,
+    ) -> StateRootComputeOutcome {
+        let config = StateRootConfig::new_from_input(ConsistentDbView::new(factory, None), input);
+        let provider = config.consistent_view.provider_ro().unwrap();
+        let nodes_sorted = config.nodes_sorted.clone();
+        let state_sorted = config.state_sorted.clone();
+        let blinded_provider_factory = ProofBlindedProviderFactory::new(
+            InMemoryTrieCursorFactory::new(
+                DatabaseTrieCursorFactory::new(provider.tx_ref()),
+                &nodes_sorted,
+            ),
+            HashedPostStateCursorFactory::new(
+                DatabaseHashedCursorFactory::new(provider.tx_ref()),
+                &state_sorted,
+            ),
+            config.prefix_sets.clone(),
+        );
+        std::thread::scope(|std_scope| {
+            let task = match sparse_trie {
+                Some(sparse_trie) => StateRootTask::new_with_sparse_trie(
+                    config,
+                    blinded_provider_factory,
+                    sparse_trie,
+                ),
+                None => StateRootTask::new(config, blinded_provider_factory),
+            };
+            let mut state_hook = task.state_hook();
+            let handle = task.spawn(std_scope);
+
+            for update in state_updates {
+                state_hook.on_state(&update);
+            }
+            drop(state_hook);
+
+            handle.wait_for_result().expect("task failed")
+        })
+    }
+
+    #[test]
+    fn test_state_root_task_preserved_sparse_trie() {
+        reth_tracing::init_test_tracing();
+
+        let factory = create_test_provider_factory();
+        let mut accumulated_state: HashMap<Address, (RethAccount, HashMap<B256, U256>)> =
+            HashMap::default();
+        let mut accumulate = |updates: &[EvmState]| {
+            for (address, account) in updates.iter().flatten() {
+                let entry = accumulated_state.entry(*address).or_default();
+                entry.0 = convert_revm_to_reth_account(account);
+                entry
+                    .1
+                    .extend(account.storage.iter().map(|(k, v)| (B256::from(*k), v.present_value)));
+            }
+            state_root(accumulated_state.clone())
+        };
+
+        // the first block is computed from scratch
+        let first_updates = create_mock_state_updates(10, 10);
+        let first_root = accumulate(&first_updates);
+        let mut first_state = HashedPostState::default();
+        for update in &first_updates {
+            first_state.extend(evm_state_to_hashed_post_state(update.clone()));
+        }
+        let first_outcome =
+            run_state_root_task(factory.clone(), TrieInput::default(), None, first_updates);
+        assert_eq!(first_outcome.state_root.0, first_root);
+
+        // the second block continues with the sparse trie of the first block, on top of its state,
+        // the pruned nodes are revealed again
+        let mut first_trie = first_outcome.trie;
+        first_trie.prune(1);
+        let second_updates = create_mock_state_updates(10, 10);
+        let second_root = accumulate(&second_updates);
+        let second_outcome = run_state_root_task(
+            factory,
+            TrieInput::from_state(first_state),
+            Some(first_trie),
+            second_updates,
+        );
+        assert_eq!(second_outcome.state_root.0, second_root);
+    }
+
     #[test]
     fn test_add_proof_in_sequence() {
         let mut sequencer = ProofSequencer::new();
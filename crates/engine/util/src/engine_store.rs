@@ -91,7 +91,8 @@ impl EngineMessageStore {
                 )?;
             }
             // noop
-            BeaconEngineMessage::TransitionConfigurationExchanged => (),
+            BeaconEngineMessage::TransitionConfigurationExchanged |
+            BeaconEngineMessage::SetHead { .. } => (),
         };
         Ok(())
     }
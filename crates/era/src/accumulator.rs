@@ -0,0 +1,76 @@
+use crate::MAX_BLOCKS_PER_ERA1;
+use alloy_primitives::{B256, U256};
+use sha2::{Digest, Sha256};
+
+/// The depth of the merkle tree of the accumulator, `log2(MAX_BLOCKS_PER_ERA1)`.
+const ACCUMULATOR_DEPTH: u32 = MAX_BLOCKS_PER_ERA1.trailing_zeros();
+
+/// The accumulator record of a block: its hash and the total difficulty of the chain up to and
+/// including the block.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HeaderRecord {
+    /// The hash of the block.
+    pub block_hash: B256,
+    /// The total difficulty of the chain at the block.
+    pub total_difficulty: U256,
+}
+
+impl HeaderRecord {
+    /// Returns the SSZ hash tree root of the record.
+    fn hash_tree_root(&self) -> B256 {
+        sha256_pair(self.block_hash, B256::from(self.total_difficulty.to_le_bytes::<32>()))
+    }
+}
+
+/// Computes the accumulator root of the records of an era1 file.
+///
+/// This is the SSZ hash tree root of the records as a `List[HeaderRecord, 8192]`, the records must
+/// therefore not exceed [`MAX_BLOCKS_PER_ERA1`].
+pub fn accumulator_root(records: &[HeaderRecord]) -> B256 {
+    debug_assert!(records.len() <= MAX_BLOCKS_PER_ERA1, "too many accumulator records");
+
+    let mut layer = records.iter().map(HeaderRecord::hash_tree_root).collect::<Vec<_>>();
+    let mut zero_hash = B256::ZERO;
+    for _ in 0..ACCUMULATOR_DEPTH {
+        if layer.len() % 2 == 1 {
+            layer.push(zero_hash);
+        }
+        layer = layer.chunks_exact(2).map(|pair| sha256_pair(pair[0], pair[1])).collect();
+        zero_hash = sha256_pair(zero_hash, zero_hash);
+    }
+    let root = layer.first().copied().unwrap_or(zero_hash);
+
+    // mix in the length of the list
+    sha256_pair(root, B256::from(U256::from(records.len()).to_le_bytes::<32>()))
+}
+
+fn sha256_pair(left: B256, right: B256) -> B256 {
+    let mut hasher = Sha256::new();
+    hasher.update(left);
+    hasher.update(right);
+    B256::from_slice(&hasher.finalize())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn accumulator_root_of_single_record() {
+        let record =
+            HeaderRecord { block_hash: B256::repeat_byte(1), total_difficulty: U256::from(2) };
+
+        // a single leaf is hashed up the tree with the zero hashes of every level
+        let mut root = record.hash_tree_root();
+        let mut zero_hash = B256::ZERO;
+        for _ in 0..13 {
+            root = sha256_pair(root, zero_hash);
+            zero_hash = sha256_pair(zero_hash, zero_hash);
+        }
+        let mut length = B256::ZERO;
+        length[0] = 1;
+
+        assert_eq!(accumulator_root(&[record]), sha256_pair(root, length));
+        assert_ne!(accumulator_root(&[record, record]), accumulator_root(&[record]));
+    }
+}
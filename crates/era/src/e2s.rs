@@ -0,0 +1,144 @@
+//! The e2store format, a type-length-value format that era1 files are built on.
+//!
+//! Every entry consists of an 8 byte header and its data. The header contains the type of the
+//! entry as a little-endian `u16`, the length of the data as a little-endian `u32` and two
+//! reserved bytes that must be zero.
+//!
+//! See <https://github.com/status-im/nimbus-eth2/blob/stable/docs/e2store.md>.
+
+use crate::EraError;
+use std::io::{self, Read, Write};
+
+/// The size of an entry header.
+pub const HEADER_SIZE: u64 = 8;
+
+/// An e2store entry.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Entry {
+    /// The type of the entry.
+    pub entry_type: u16,
+    /// The data of the entry.
+    pub data: Vec<u8>,
+}
+
+/// Reads e2store entries and keeps track of the offset in the input.
+#[derive(Debug)]
+pub struct E2StoreReader<R> {
+    reader: R,
+    position: u64,
+}
+
+impl<R: Read> E2StoreReader<R> {
+    /// Creates a new reader that starts at offset zero.
+    pub const fn new(reader: R) -> Self {
+        Self { reader, position: 0 }
+    }
+
+    /// Returns the offset of the next entry.
+    pub const fn position(&self) -> u64 {
+        self.position
+    }
+
+    /// Reads the next entry, returns `None` at the end of the input.
+    pub fn read_entry(&mut self) -> Result<Option<Entry>, EraError> {
+        let mut header = [0u8; HEADER_SIZE as usize];
+        let mut read = 0;
+        while read < header.len() {
+            match self.reader.read(&mut header[read..]) {
+                Ok(0) if read == 0 => return Ok(None),
+                Ok(0) => return Err(EraError::UnexpectedEof),
+                Ok(n) => read += n,
+                Err(err) if err.kind() == io::ErrorKind::Interrupted => {}
+                Err(err) => return Err(err.into()),
+            }
+        }
+
+        let entry_type = u16::from_le_bytes([header[0], header[1]]);
+        let len = u32::from_le_bytes([header[2], header[3], header[4], header[5]]) as u64;
+        if header[6..] != [0, 0] {
+            return Err(EraError::ReservedNotZero { offset: self.position })
+        }
+
+        // don't trust the length for the allocation, the input might be truncated
+        let mut data = Vec::new();
+        if (&mut self.reader).take(len).read_to_end(&mut data)? as u64 != len {
+            return Err(EraError::UnexpectedEof)
+        }
+
+        self.position += HEADER_SIZE + len;
+        Ok(Some(Entry { entry_type, data }))
+    }
+}
+
+/// Writes e2store entries and keeps track of the offset in the output.
+#[derive(Debug)]
+pub struct E2StoreWriter<W> {
+    writer: W,
+    position: u64,
+}
+
+impl<W: Write> E2StoreWriter<W> {
+    /// Creates a new writer that starts at offset zero.
+    pub const fn new(writer: W) -> Self {
+        Self { writer, position: 0 }
+    }
+
+    /// Returns the offset of the next entry.
+    pub const fn position(&self) -> u64 {
+        self.position
+    }
+
+    /// Writes an entry.
+    pub fn write_entry(&mut self, entry_type: u16, data: &[u8]) -> Result<(), EraError> {
+        let len = u32::try_from(data.len())
+            .map_err(|_| EraError::InvalidEntryLength { entry_type, len: data.len() })?;
+
+        let mut header = [0u8; HEADER_SIZE as usize];
+        header[..2].copy_from_slice(&entry_type.to_le_bytes());
+        header[2..6].copy_from_slice(&len.to_le_bytes());
+        self.writer.write_all(&header)?;
+        self.writer.write_all(data)?;
+
+        self.position += HEADER_SIZE + len as u64;
+        Ok(())
+    }
+
+    /// Flushes the output and returns it.
+    pub fn into_inner(mut self) -> io::Result<W> {
+        self.writer.flush()?;
+        Ok(self.writer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn entries_roundtrip() {
+        let mut writer = E2StoreWriter::new(Vec::new());
+        writer.write_entry(0x3265, &[]).unwrap();
+        writer.write_entry(0x03, &[1, 2, 3]).unwrap();
+        assert_eq!(writer.position(), 2 * HEADER_SIZE + 3);
+
+        let bytes = writer.into_inner().unwrap();
+        assert_eq!(&bytes[..HEADER_SIZE as usize], &[0x65, 0x32, 0, 0, 0, 0, 0, 0]);
+
+        let mut reader = E2StoreReader::new(bytes.as_slice());
+        assert_eq!(
+            reader.read_entry().unwrap(),
+            Some(Entry { entry_type: 0x3265, data: Vec::new() })
+        );
+        assert_eq!(reader.position(), HEADER_SIZE);
+        assert_eq!(
+            reader.read_entry().unwrap(),
+            Some(Entry { entry_type: 0x03, data: vec![1, 2, 3] })
+        );
+        assert_eq!(reader.read_entry().unwrap(), None);
+
+        // a truncated entry is an error
+        let mut reader = E2StoreReader::new(&bytes[..bytes.len() - 1]);
+        reader.read_entry().unwrap();
+        assert!(matches!(reader.read_entry(), Err(EraError::UnexpectedEof)));
+    }
+}
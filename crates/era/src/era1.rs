@@ -0,0 +1,456 @@
+use crate::{
+    accumulator_root,
+    e2s::{E2StoreReader, E2StoreWriter},
+    EraError, HeaderRecord, MAX_BLOCKS_PER_ERA1,
+};
+use alloy_consensus::{Eip658Value, Header, ReceiptEnvelope, ReceiptWithBloom};
+use alloy_primitives::{BlockNumber, B256, U256};
+use alloy_rlp::{Decodable, Encodable};
+use reth_primitives::{BlockBody, Receipt, SealedHeader, TxType};
+use std::io::{Read, Write};
+
+/// The version entry that starts every era1 file.
+const VERSION: u16 = 0x3265;
+/// A snappy framed, RLP encoded header.
+const COMPRESSED_HEADER: u16 = 0x03;
+/// A snappy framed, RLP encoded block body.
+const COMPRESSED_BODY: u16 = 0x04;
+/// A snappy framed, RLP encoded list of receipts.
+const COMPRESSED_RECEIPTS: u16 = 0x05;
+/// The total difficulty of a block as a little-endian `uint256`.
+const TOTAL_DIFFICULTY: u16 = 0x06;
+/// The accumulator root of the file.
+const ACCUMULATOR: u16 = 0x07;
+/// The index of the block offsets in the file.
+const BLOCK_INDEX: u16 = 0x3266;
+
+/// A block of an era1 file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Era1Block {
+    /// The header of the block.
+    pub header: SealedHeader,
+    /// The body of the block.
+    pub body: BlockBody,
+    /// The receipts of the transactions of the block.
+    ///
+    /// Receipts of blocks before Byzantium contain the intermediate state root instead of the
+    /// status code.
+    pub receipts: Vec<ReceiptEnvelope>,
+    /// The total difficulty of the chain up to and including the block.
+    pub total_difficulty: U256,
+}
+
+impl Era1Block {
+    /// Returns the number of the block.
+    pub const fn number(&self) -> BlockNumber {
+        self.header.header().number
+    }
+
+    /// Returns the accumulator record of the block.
+    pub const fn header_record(&self) -> HeaderRecord {
+        HeaderRecord { block_hash: self.header.hash(), total_difficulty: self.total_difficulty }
+    }
+}
+
+/// Converts a receipt into the receipt envelope of its transaction type, as stored in era1 files.
+///
+/// Receipts of blocks before Byzantium contain the state root after the transaction instead of
+/// the status code, which must be passed as `post_state`.
+pub fn receipt_envelope(receipt: &Receipt, post_state: Option<B256>) -> ReceiptEnvelope {
+    let envelope = ReceiptWithBloom {
+        receipt: alloy_consensus::Receipt {
+            status: post_state.map_or(Eip658Value::Eip658(receipt.success), Eip658Value::PostState),
+            cumulative_gas_used: receipt.cumulative_gas_used as u128,
+            logs: receipt.logs.clone(),
+        },
+        logs_bloom: receipt.bloom_slow(),
+    };
+    match receipt.tx_type {
+        TxType::Eip2930 => ReceiptEnvelope::Eip2930(envelope),
+        TxType::Eip1559 => ReceiptEnvelope::Eip1559(envelope),
+        TxType::Eip4844 => ReceiptEnvelope::Eip4844(envelope),
+        TxType::Eip7702 => ReceiptEnvelope::Eip7702(envelope),
+        _ => ReceiptEnvelope::Legacy(envelope),
+    }
+}
+
+/// Returns the file name of an era1 file: `<network>-<epoch>-<short accumulator root>.era1`.
+pub fn era1_file_name(network: &str, epoch: u64, accumulator_root: B256) -> String {
+    format!("{network}-{epoch:05}-{}.era1", alloy_primitives::hex::encode(&accumulator_root[..4]))
+}
+
+/// Parses the network, epoch and short accumulator root from the name of an era1 file.
+pub fn parse_era1_file_name(name: &str) -> Option<(&str, u64, &str)> {
+    let name = name.strip_suffix(".era1")?;
+    let (rest, short_root) = name.rsplit_once('-')?;
+    let (network, epoch) = rest.rsplit_once('-')?;
+    Some((network, epoch.parse().ok()?, short_root))
+}
+
+/// Reads the blocks of an era1 file.
+///
+/// The accumulator root and the block index at the end of the file are verified against the
+/// blocks of the file after the last block was read.
+#[derive(Debug)]
+pub struct Era1Reader<R> {
+    reader: E2StoreReader<R>,
+    /// The accumulator records of the blocks read so far.
+    records: Vec<HeaderRecord>,
+    /// The offsets of the blocks read so far.
+    offsets: Vec<u64>,
+    /// The number of the first block of the file.
+    start_block: Option<BlockNumber>,
+    /// The verified accumulator root, set once all blocks were read.
+    accumulator_root: Option<B256>,
+}
+
+impl<R: Read> Era1Reader<R> {
+    /// Creates a new reader and reads the version entry of the file.
+    pub fn new(reader: R) -> Result<Self, EraError> {
+        let mut reader = E2StoreReader::new(reader);
+        let version = reader.read_entry()?.ok_or(EraError::UnexpectedEof)?;
+        if version.entry_type != VERSION {
+            return Err(EraError::UnexpectedEntry { expected: VERSION, got: version.entry_type })
+        }
+        if !version.data.is_empty() {
+            return Err(EraError::InvalidEntryLength {
+                entry_type: VERSION,
+                len: version.data.len(),
+            })
+        }
+
+        Ok(Self {
+            reader,
+            records: Vec::new(),
+            offsets: Vec::new(),
+            start_block: None,
+            accumulator_root: None,
+        })
+    }
+
+    /// Returns the accumulator root of the file, once all blocks were read and it was verified.
+    pub const fn accumulator_root(&self) -> Option<B256> {
+        self.accumulator_root
+    }
+
+    /// Reads the next block, returns `None` once all blocks were read and the accumulator root
+    /// and block index were verified.
+    pub fn next_block(&mut self) -> Result<Option<Era1Block>, EraError> {
+        if self.accumulator_root.is_some() {
+            return Ok(None)
+        }
+
+        let offset = self.reader.position();
+        let entry = self.reader.read_entry()?.ok_or(EraError::UnexpectedEof)?;
+        match entry.entry_type {
+            COMPRESSED_HEADER => {}
+            ACCUMULATOR => {
+                self.finish(&entry.data)?;
+                return Ok(None)
+            }
+            got => return Err(EraError::UnexpectedEntry { expected: COMPRESSED_HEADER, got }),
+        }
+
+        let header = SealedHeader::seal(decode_compressed::<Header>(&entry.data)?);
+        let body = decode_compressed(&self.read_entry(COMPRESSED_BODY)?)?;
+        let receipts = decode_compressed(&self.read_entry(COMPRESSED_RECEIPTS)?)?;
+        let total_difficulty = self.read_entry(TOTAL_DIFFICULTY)?;
+        if total_difficulty.len() != 32 {
+            return Err(EraError::InvalidEntryLength {
+                entry_type: TOTAL_DIFFICULTY,
+                len: total_difficulty.len(),
+            })
+        }
+        let total_difficulty = U256::from_le_slice(&total_difficulty);
+
+        let block = Era1Block { header, body, receipts, total_difficulty };
+        let start_block = *self.start_block.get_or_insert(block.number());
+        let expected = start_block + self.records.len() as u64;
+        if block.number() != expected {
+            return Err(EraError::NonConsecutiveBlock { expected, got: block.number() })
+        }
+        if self.records.len() == MAX_BLOCKS_PER_ERA1 {
+            return Err(EraError::InvalidBlockCount(self.records.len() + 1))
+        }
+
+        self.records.push(block.header_record());
+        self.offsets.push(offset);
+        Ok(Some(block))
+    }
+
+    /// Reads the next entry, which must be of the given type, and returns its data.
+    fn read_entry(&mut self, entry_type: u16) -> Result<Vec<u8>, EraError> {
+        let entry = self.reader.read_entry()?.ok_or(EraError::UnexpectedEof)?;
+        if entry.entry_type != entry_type {
+            return Err(EraError::UnexpectedEntry { expected: entry_type, got: entry.entry_type })
+        }
+        Ok(entry.data)
+    }
+
+    /// Verifies the accumulator root and the block index of the file.
+    fn finish(&mut self, accumulator: &[u8]) -> Result<(), EraError> {
+        if self.records.is_empty() {
+            return Err(EraError::InvalidBlockCount(0))
+        }
+        if accumulator.len() != 32 {
+            return Err(EraError::InvalidEntryLength {
+                entry_type: ACCUMULATOR,
+                len: accumulator.len(),
+            })
+        }
+        let expected = B256::from_slice(accumulator);
+        let got = accumulator_root(&self.records);
+        if expected != got {
+            return Err(EraError::AccumulatorMismatch { expected, got })
+        }
+
+        // the index holds the start block, the offsets of the blocks relative to the index entry
+        // and the number of blocks
+        let index_offset = self.reader.position();
+        let index = self.read_entry(BLOCK_INDEX)?;
+        if index.len() != 16 + 8 * self.offsets.len() {
+            return Err(EraError::InvalidBlockIndex)
+        }
+        let mut words = index
+            .chunks_exact(8)
+            .map(|word| u64::from_le_bytes(word.try_into().expect("chunks of 8 bytes")));
+        if words.next() != self.start_block {
+            return Err(EraError::InvalidBlockIndex)
+        }
+        for offset in &self.offsets {
+            let relative = words.next().expect("length checked") as i64;
+            if index_offset.checked_add_signed(relative) != Some(*offset) {
+                return Err(EraError::InvalidBlockIndex)
+            }
+        }
+        if words.next() != Some(self.offsets.len() as u64) {
+            return Err(EraError::InvalidBlockIndex)
+        }
+
+        if self.reader.read_entry()?.is_some() {
+            return Err(EraError::TrailingData)
+        }
+
+        self.accumulator_root = Some(got);
+        Ok(())
+    }
+}
+
+/// Writes blocks to an era1 file.
+#[derive(Debug)]
+pub struct Era1Writer<W> {
+    writer: E2StoreWriter<W>,
+    /// The accumulator records of the blocks written so far.
+    records: Vec<HeaderRecord>,
+    /// The offsets of the blocks written so far.
+    offsets: Vec<u64>,
+    /// The number of the first block of the file.
+    start_block: Option<BlockNumber>,
+}
+
+impl<W: Write> Era1Writer<W> {
+    /// Creates a new writer and writes the version entry of the file.
+    pub fn new(writer: W) -> Result<Self, EraError> {
+        let mut writer = E2StoreWriter::new(writer);
+        writer.write_entry(VERSION, &[])?;
+        Ok(Self { writer, records: Vec::new(), offsets: Vec::new(), start_block: None })
+    }
+
+    /// Returns the number of blocks appended so far.
+    pub fn len(&self) -> usize {
+        self.records.len()
+    }
+
+    /// Returns `true` if no blocks were appended yet.
+    pub fn is_empty(&self) -> bool {
+        self.records.is_empty()
+    }
+
+    /// Appends a block to the file.
+    ///
+    /// The block must directly follow the previously appended block.
+    pub fn append(&mut self, block: &Era1Block) -> Result<(), EraError> {
+        if self.records.len() == MAX_BLOCKS_PER_ERA1 {
+            return Err(EraError::InvalidBlockCount(self.records.len() + 1))
+        }
+        let start_block = *self.start_block.get_or_insert(block.number());
+        let expected = start_block + self.records.len() as u64;
+        if block.number() != expected {
+            return Err(EraError::NonConsecutiveBlock { expected, got: block.number() })
+        }
+
+        self.offsets.push(self.writer.position());
+        self.writer.write_entry(COMPRESSED_HEADER, &encode_compressed(block.header.header())?)?;
+        self.writer.write_entry(COMPRESSED_BODY, &encode_compressed(&block.body)?)?;
+        self.writer.write_entry(COMPRESSED_RECEIPTS, &encode_compressed(&block.receipts)?)?;
+        self.writer.write_entry(TOTAL_DIFFICULTY, &block.total_difficulty.to_le_bytes::<32>())?;
+        self.records.push(block.header_record());
+        Ok(())
+    }
+
+    /// Writes the accumulator root and the block index, and returns the output and the
+    /// accumulator root.
+    pub fn finish(mut self) -> Result<(W, B256), EraError> {
+        let Some(start_block) = self.start_block else {
+            return Err(EraError::InvalidBlockCount(0))
+        };
+
+        let root = accumulator_root(&self.records);
+        self.writer.write_entry(ACCUMULATOR, root.as_slice())?;
+
+        let index_offset = self.writer.position() as i64;
+        let mut index = Vec::with_capacity(16 + 8 * self.offsets.len());
+        index.extend_from_slice(&start_block.to_le_bytes());
+        for offset in &self.offsets {
+            index.extend_from_slice(&(*offset as i64 - index_offset).to_le_bytes());
+        }
+        index.extend_from_slice(&(self.offsets.len() as u64).to_le_bytes());
+        self.writer.write_entry(BLOCK_INDEX, &index)?;
+
+        Ok((self.writer.into_inner()?, root))
+    }
+}
+
+/// Decodes a snappy framed, RLP encoded value.
+fn decode_compressed<T: Decodable>(data: &[u8]) -> Result<T, EraError> {
+    let mut rlp = Vec::new();
+    snap::read::FrameDecoder::new(data).read_to_end(&mut rlp)?;
+    Ok(T::decode(&mut rlp.as_slice())?)
+}
+
+/// RLP encodes and snappy frames a value.
+fn encode_compressed<T: Encodable>(value: &T) -> Result<Vec<u8>, EraError> {
+    let mut encoder = snap::write::FrameEncoder::new(Vec::new());
+    encoder.write_all(&alloy_rlp::encode(value))?;
+    encoder.into_inner().map_err(|err| EraError::Io(err.into_error()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_consensus::Receipt as EnvelopeReceipt;
+
+    fn blocks(start: BlockNumber, count: u64) -> Vec<Era1Block> {
+        (start..start + count)
+            .map(|number| Era1Block {
+                header: SealedHeader::seal(Header {
+                    number,
+                    difficulty: U256::from(number),
+                    ..Default::default()
+                }),
+                body: BlockBody::default(),
+                receipts: vec![ReceiptEnvelope::Legacy(ReceiptWithBloom {
+                    receipt: EnvelopeReceipt {
+                        status: Eip658Value::PostState(B256::with_last_byte(number as u8)),
+                        cumulative_gas_used: 21_000,
+                        logs: Vec::new(),
+                    },
+                    logs_bloom: Default::default(),
+                })],
+                total_difficulty: U256::from(number * (number + 1) / 2),
+            })
+            .collect()
+    }
+
+    fn write(blocks: &[Era1Block]) -> (Vec<u8>, B256) {
+        let mut writer = Era1Writer::new(Vec::new()).unwrap();
+        for block in blocks {
+            writer.append(block).unwrap();
+        }
+        writer.finish().unwrap()
+    }
+
+    fn read(bytes: &[u8]) -> Result<(Vec<Era1Block>, Option<B256>), EraError> {
+        let mut reader = Era1Reader::new(bytes)?;
+        let mut blocks = Vec::new();
+        while let Some(block) = reader.next_block()? {
+            blocks.push(block);
+        }
+        Ok((blocks, reader.accumulator_root()))
+    }
+
+    #[test]
+    fn era1_roundtrip() {
+        let blocks = blocks(8192, 3);
+        let (bytes, root) = write(&blocks);
+        assert_eq!(
+            root,
+            accumulator_root(&blocks.iter().map(Era1Block::header_record).collect::<Vec<_>>())
+        );
+
+        let (read_blocks, read_root) = read(&bytes).unwrap();
+        assert_eq!(read_blocks, blocks);
+        assert_eq!(read_root, Some(root));
+
+        let name = era1_file_name("mainnet", 1, root);
+        assert_eq!(name.len(), "mainnet-00001-01234567.era1".len());
+        assert_eq!(
+            parse_era1_file_name(&name).map(|(network, epoch, _)| (network, epoch)),
+            Some(("mainnet", 1))
+        );
+    }
+
+    #[test]
+    fn era1_rejects_invalid_files() {
+        // a block with a wrong total difficulty doesn't match the accumulator root
+        let mut blocks = blocks(0, 2);
+        let (bytes, _) = write(&blocks);
+        blocks[1].total_difficulty += U256::from(1);
+        let (mut tampered, _) = write(&blocks);
+        let accumulator_offset = tampered.len() - 8 - 32 - 8 * 4;
+        tampered[accumulator_offset..accumulator_offset + 32]
+            .copy_from_slice(&bytes[accumulator_offset..accumulator_offset + 32]);
+        assert!(matches!(read(&tampered), Err(EraError::AccumulatorMismatch { .. })));
+
+        // a truncated file is missing the block index
+        assert!(matches!(read(&bytes[..bytes.len() - 1]), Err(EraError::UnexpectedEof)));
+
+        // blocks must be consecutive
+        let mut writer = Era1Writer::new(Vec::new()).unwrap();
+        writer.append(&blocks[1]).unwrap();
+        assert!(matches!(
+            writer.append(&blocks[0]),
+            Err(EraError::NonConsecutiveBlock { expected: 2, got: 0 })
+        ));
+    }
+
+    #[test]
+    fn receipt_envelope_status() {
+        let receipt = Receipt {
+            tx_type: TxType::Eip1559,
+            success: false,
+            cumulative_gas_used: 21_000,
+            ..Default::default()
+        };
+        let envelope = receipt_envelope(&receipt, None);
+        assert!(matches!(envelope, ReceiptEnvelope::Eip1559(_)));
+        assert_eq!(envelope.as_receipt().unwrap().status, Eip658Value::Eip658(false));
+
+        // receipts before Byzantium carry the state root after the transaction
+        let post_state = B256::repeat_byte(1);
+        let envelope = receipt_envelope(&receipt, Some(post_state));
+        assert_eq!(envelope.as_receipt().unwrap().status, Eip658Value::PostState(post_state));
+        assert_eq!(envelope.as_receipt().unwrap().cumulative_gas_used, 21_000);
+    }
+
+    /// Reads the first mainnet era1 file from the path in the `ERA1_MAINNET_00000` environment
+    /// variable, and checks its accumulator root against the root in its canonical name.
+    #[test]
+    #[ignore = "requires the mainnet-00000-5ec1ffb8.era1 file"]
+    fn mainnet_accumulator_root() {
+        let path = std::env::var("ERA1_MAINNET_00000").expect("ERA1_MAINNET_00000 is not set");
+        let (blocks, root) = read(&std::fs::read(path).unwrap()).unwrap();
+
+        assert_eq!(blocks.len(), MAX_BLOCKS_PER_ERA1);
+        assert_eq!(
+            blocks[0].header_record(),
+            HeaderRecord {
+                block_hash: alloy_primitives::b256!(
+                    "d4e56740f876aef8c010b86a40d5f56745a118d0906a34e69aec8c0db1cb8fa3"
+                ),
+                total_difficulty: U256::from(0x400000000u64),
+            }
+        );
+        assert_eq!(era1_file_name("mainnet", 0, root.unwrap()), "mainnet-00000-5ec1ffb8.era1");
+    }
+}
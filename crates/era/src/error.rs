@@ -0,0 +1,63 @@
+use alloy_primitives::B256;
+
+/// Errors that can occur when reading or writing era1 files.
+#[derive(Debug, thiserror::Error)]
+pub enum EraError {
+    /// An I/O error.
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+    /// A payload could not be RLP decoded.
+    #[error(transparent)]
+    Rlp(#[from] alloy_rlp::Error),
+    /// The reserved bytes of an e2store entry header are not zero.
+    #[error("e2store entry at offset {offset} has non-zero reserved bytes")]
+    ReservedNotZero {
+        /// The offset of the entry in the file.
+        offset: u64,
+    },
+    /// The file ended in the middle of an entry or before the block index.
+    #[error("unexpected end of file")]
+    UnexpectedEof,
+    /// An entry had a different type than expected.
+    #[error("unexpected e2store entry type {got:#06x}, expected {expected:#06x}")]
+    UnexpectedEntry {
+        /// The expected entry type.
+        expected: u16,
+        /// The actual entry type.
+        got: u16,
+    },
+    /// An entry had an invalid length.
+    #[error("e2store entry of type {entry_type:#06x} has invalid length {len}")]
+    InvalidEntryLength {
+        /// The entry type.
+        entry_type: u16,
+        /// The length of the entry.
+        len: usize,
+    },
+    /// The blocks of the file are not consecutive.
+    #[error("expected block {expected}, got block {got}")]
+    NonConsecutiveBlock {
+        /// The expected block number.
+        expected: u64,
+        /// The actual block number.
+        got: u64,
+    },
+    /// The file contains no blocks or more than
+    /// [`MAX_BLOCKS_PER_ERA1`](crate::MAX_BLOCKS_PER_ERA1) blocks.
+    #[error("era1 file must contain between 1 and 8192 blocks, got {0}")]
+    InvalidBlockCount(usize),
+    /// The accumulator root of the file does not match the blocks of the file.
+    #[error("accumulator root mismatch: file has {expected}, blocks hash to {got}")]
+    AccumulatorMismatch {
+        /// The accumulator root stored in the file.
+        expected: B256,
+        /// The accumulator root of the blocks in the file.
+        got: B256,
+    },
+    /// The block index does not match the blocks of the file.
+    #[error("block index does not match the blocks of the file")]
+    InvalidBlockIndex,
+    /// There is data after the block index.
+    #[error("unexpected data after the block index")]
+    TrailingData,
+}
@@ -0,0 +1,32 @@
+//! Era1 history archives.
+//!
+//! Era1 files store the pre-merge history of Ethereum in batches of up to
+//! [`MAX_BLOCKS_PER_ERA1`] blocks. Every file contains the headers, bodies, receipts and total
+//! difficulties of its blocks, an accumulator root that commits to the hashes and total
+//! difficulties of all blocks, and an index of the block offsets in the file.
+//!
+//! See <https://github.com/eth-clients/e2store-format-specs/blob/main/formats/era1.md>.
+
+#![doc(
+    html_logo_url = "https://raw.githubusercontent.com/paradigmxyz/reth/main/assets/reth-docs.png",
+    html_favicon_url = "https://avatars0.githubusercontent.com/u/97369466?s=256",
+    issue_tracker_base_url = "https://github.com/paradigmxyz/reth/issues/"
+)]
+#![cfg_attr(not(test), warn(unused_crate_dependencies))]
+#![cfg_attr(docsrs, feature(doc_cfg, doc_auto_cfg))]
+
+mod accumulator;
+pub use accumulator::{accumulator_root, HeaderRecord};
+
+pub mod e2s;
+
+mod era1;
+pub use era1::{
+    era1_file_name, parse_era1_file_name, receipt_envelope, Era1Block, Era1Reader, Era1Writer,
+};
+
+mod error;
+pub use error::EraError;
+
+/// The maximum number of blocks in an era1 file.
+pub const MAX_BLOCKS_PER_ERA1: usize = 8192;
@@ -2,16 +2,18 @@
 
 use reth_basic_payload_builder::{BasicPayloadJobGenerator, BasicPayloadJobGeneratorConfig};
 use reth_beacon_consensus::EthBeaconConsensus;
+use reth_bundle_pool::{maintain_bundle_pool, BundlePool};
 use reth_chainspec::ChainSpec;
 use reth_ethereum_engine_primitives::{
     EthBuiltPayload, EthPayloadAttributes, EthPayloadBuilderAttributes,
 };
-use reth_ethereum_payload_builder::EthereumBuilderConfig;
+use reth_ethereum_payload_builder::{maintain_simulated_payments, EthereumBuilderConfig};
 use reth_evm::execute::BasicBlockExecutorProvider;
 use reth_evm_ethereum::execute::EthExecutionStrategyFactory;
 use reth_network::{EthNetworkPrimitives, NetworkHandle, PeersInfo};
 use reth_node_api::{
-    AddOnsContext, ConfigureEvm, FullNodeComponents, HeaderTy, NodeTypesWithDB, TxTy,
+    AddOnsContext, ConfigureEvm, FullNodeComponents, HeaderTy, NodeTypesWithDB, PayloadBuilder,
+    TxTy,
 };
 use reth_node_builder::{
     components::{
@@ -23,12 +25,14 @@ use reth_node_builder::{
     BuilderContext, Node, NodeAdapter, NodeComponentsBuilder, PayloadBuilderConfig, PayloadTypes,
 };
 use reth_payload_builder::{PayloadBuilderHandle, PayloadBuilderService};
+use reth_payload_util::{BuildPhase, PayloadTransactionSources, SourceBudget};
 use reth_primitives::{EthPrimitives, PooledTransaction};
 use reth_provider::{CanonStateSubscriptions, EthStorage};
 use reth_rpc::EthApi;
 use reth_tracing::tracing::{debug, info};
 use reth_transaction_pool::{
-    blobstore::DiskFileBlobStore, EthTransactionPool, PoolTransaction, TransactionPool,
+    blobstore::DiskFileBlobStore, EthPooledTransaction, EthTransactionValidator, PoolTransaction,
+    SimulatedPaymentOrdering, SimulatedPayments, TransactionPool, TransactionPoolExt,
     TransactionValidationTaskExecutor,
 };
 use reth_trie_db::MerklePatriciaTrie;
@@ -39,9 +43,19 @@ use crate::{EthEngineTypes, EthEvmConfig};
 pub use reth_ethereum_engine_primitives::EthereumEngineValidator;
 
 /// Type configuration for a regular Ethereum node.
-#[derive(Debug, Default, Clone, Copy)]
+///
+/// The [`BundlePool`] of the node is shared by its payload builder and its RPC, and is only used if
+/// the node is started with `--builder.bundles`. The [`SimulatedPayments`] are shared by its
+/// transaction pool and its payload builder, and are only maintained if the node is started with
+/// `--builder.simulated-ordering`.
+#[derive(Debug, Default, Clone)]
 #[non_exhaustive]
-pub struct EthereumNode;
+pub struct EthereumNode {
+    /// The pool of the bundles that are submitted to the payload builder.
+    bundle_pool: BundlePool,
+    /// The simulated payments the pool transactions are ordered by.
+    simulated_payments: SimulatedPayments,
+}
 
 impl EthereumNode {
     /// Returns a [`ComponentsBuilder`] configured for a regular Ethereum node.
@@ -120,10 +134,19 @@ where
 
     fn components_builder(&self) -> Self::ComponentsBuilder {
         Self::components()
+            .pool(
+                EthereumPoolBuilder::default()
+                    .with_simulated_payments(self.simulated_payments.clone()),
+            )
+            .payload(
+                EthereumPayloadBuilder::default()
+                    .with_bundle_pool(self.bundle_pool.clone())
+                    .with_simulated_payments(self.simulated_payments.clone()),
+            )
     }
 
     fn add_ons(&self) -> Self::AddOns {
-        EthereumAddOns::default()
+        EthereumAddOns::default().with_bundle_pool(self.bundle_pool.clone())
     }
 }
 
@@ -153,14 +176,35 @@ where
     }
 }
 
+/// The transaction pool of a regular Ethereum node.
+///
+/// The transactions are ordered by their [`SimulatedPayments`], and by their coinbase tip until
+/// they are simulated, which is the only ordering if the node isn't started with
+/// `--builder.simulated-ordering`.
+pub type EthereumTransactionPool<Client, S> = reth_transaction_pool::Pool<
+    TransactionValidationTaskExecutor<EthTransactionValidator<Client, EthPooledTransaction>>,
+    SimulatedPaymentOrdering<EthPooledTransaction>,
+    S,
+>;
+
 /// A basic ethereum transaction pool.
 ///
 /// This contains various settings that can be configured and take precedence over the node's
 /// config.
-#[derive(Debug, Default, Clone, Copy)]
+#[derive(Debug, Default, Clone)]
 #[non_exhaustive]
 pub struct EthereumPoolBuilder {
-    // TODO add options for txpool args
+    /// The simulated payments the transactions are ordered by.
+    simulated_payments: SimulatedPayments,
+}
+
+impl EthereumPoolBuilder {
+    /// Sets the simulated payments the transactions are ordered by, which are maintained by the
+    /// [`EthereumPayloadBuilder`] if the node is started with `--builder.simulated-ordering`.
+    pub fn with_simulated_payments(mut self, payments: SimulatedPayments) -> Self {
+        self.simulated_payments = payments;
+        self
+    }
 }
 
 impl<Types, Node> PoolBuilder<Node> for EthereumPoolBuilder
@@ -168,16 +212,19 @@ where
     Types: NodeTypesWithEngine<ChainSpec = ChainSpec, Primitives = EthPrimitives>,
     Node: FullNodeTypes<Types = Types>,
 {
-    type Pool = EthTransactionPool<Node::Provider, DiskFileBlobStore>;
+    type Pool = EthereumTransactionPool<Node::Provider, DiskFileBlobStore>;
 
     async fn build_pool(self, ctx: &BuilderContext<Node>) -> eyre::Result<Self::Pool> {
         let data_dir = ctx.config().datadir();
         let pool_config = ctx.pool_config();
         let blob_store = DiskFileBlobStore::open(data_dir.blobstore(), Default::default())?;
+        let admission_policies = ctx.admission_policies()?;
+        let readmissions = admission_policies.readmissions();
         let validator = TransactionValidationTaskExecutor::eth_builder(ctx.chain_spec())
             .with_head_timestamp(ctx.head().timestamp)
             .kzg_settings(ctx.kzg_settings()?)
             .with_local_transactions_config(pool_config.local_transactions_config.clone())
+            .with_admission_policies(admission_policies)
             .with_additional_tasks(ctx.config().txpool.additional_validation_tasks)
             .build_with_tasks(
                 ctx.provider().clone(),
@@ -185,8 +232,12 @@ where
                 blob_store.clone(),
             );
 
-        let transaction_pool =
-            reth_transaction_pool::Pool::eth_pool(validator, blob_store, pool_config);
+        let transaction_pool = reth_transaction_pool::Pool::new(
+            validator,
+            SimulatedPaymentOrdering::new(self.simulated_payments),
+            blob_store,
+            pool_config,
+        );
         info!(target: "reth::cli", "Transaction pool initialized");
         let transactions_path = data_dir.txpool_transactions();
 
@@ -209,6 +260,37 @@ where
                 },
             );
 
+            if ctx.config().txpool.journal {
+                let journal_config =
+                    reth_transaction_pool::maintain::TransactionPoolJournalConfig::new(
+                        data_dir.txpool_journal(),
+                    )
+                    .with_interval(ctx.config().txpool.journal_interval)
+                    .with_readmissions(readmissions.clone());
+
+                ctx.task_executor().spawn_critical_with_graceful_shutdown_signal(
+                    "transaction pool journal task",
+                    |shutdown| {
+                        reth_transaction_pool::maintain::journal_transactions_task(
+                            shutdown,
+                            pool.clone(),
+                            journal_config,
+                        )
+                    },
+                );
+            }
+
+            if let Some(archive) = ctx.config().txpool.blob_archive(data_dir.blob_archive()) {
+                ctx.task_executor().spawn_critical(
+                    "blob archive task",
+                    reth_transaction_pool::maintain::archive_blob_sidecars_task(
+                        pool.clone(),
+                        archive,
+                        ctx.provider().canonical_state_stream(),
+                    ),
+                );
+            }
+
             // spawn the maintenance task
             ctx.task_executor().spawn_critical(
                 "txpool maintenance task",
@@ -217,7 +299,8 @@ where
                     pool,
                     chain_events,
                     ctx.task_executor().clone(),
-                    Default::default(),
+                    reth_transaction_pool::maintain::MaintainPoolConfig::default()
+                        .with_readmissions(readmissions),
                 ),
             );
             debug!(target: "reth::cli", "Spawned txpool maintenance task");
@@ -230,9 +313,32 @@ where
 /// A basic ethereum payload service.
 #[derive(Clone, Default, Debug)]
 #[non_exhaustive]
-pub struct EthereumPayloadBuilder;
+pub struct EthereumPayloadBuilder {
+    /// The bundle pool that's included ahead of the pool transactions if the node is started
+    /// with `--builder.bundles`.
+    bundle_pool: Option<BundlePool>,
+    /// The simulated payments of the pool transactions that are maintained if the node is
+    /// started with `--builder.simulated-ordering`.
+    simulated_payments: Option<SimulatedPayments>,
+}
 
 impl EthereumPayloadBuilder {
+    /// Sets the bundle pool whose bundles are included ahead of the pool transactions, if the
+    /// node is started with `--builder.bundles`.
+    pub fn with_bundle_pool(mut self, pool: BundlePool) -> Self {
+        self.bundle_pool = Some(pool);
+        self
+    }
+
+    /// Sets the simulated payments the pool transactions are ordered by, which are maintained if
+    /// the node is started with `--builder.simulated-ordering`.
+    ///
+    /// These must be the payments of the [`SimulatedPaymentOrdering`] of the pool.
+    pub fn with_simulated_payments(mut self, payments: SimulatedPayments) -> Self {
+        self.simulated_payments = Some(payments);
+        self
+    }
+
     /// A helper method initializing [`PayloadBuilderService`] with the given EVM config.
     pub fn spawn<Types, Node, Evm, Pool>(
         self,
@@ -244,7 +350,7 @@ impl EthereumPayloadBuilder {
         Types: NodeTypesWithEngine<ChainSpec = ChainSpec, Primitives = EthPrimitives>,
         Node: FullNodeTypes<Types = Types>,
         Evm: ConfigureEvm<Header = HeaderTy<Types>, Transaction = TxTy<Node::Types>>,
-        Pool: TransactionPool<Transaction: PoolTransaction<Consensus = TxTy<Node::Types>>>
+        Pool: TransactionPoolExt<Transaction: PoolTransaction<Consensus = TxTy<Node::Types>>>
             + Unpin
             + 'static,
         Types::Engine: PayloadTypes<
@@ -254,10 +360,29 @@ impl EthereumPayloadBuilder {
         >,
     {
         let conf = ctx.payload_builder_config();
-        let payload_builder = reth_ethereum_payload_builder::EthereumPayloadBuilder::new(
-            evm_config,
-            EthereumBuilderConfig::new(conf.extra_data_bytes()).with_gas_limit(conf.gas_limit()),
-        );
+        let mut builder_config =
+            EthereumBuilderConfig::new(conf.extra_data_bytes()).with_gas_limit(conf.gas_limit());
+        if let Some(bundle_pool) = self.bundle_pool.filter(|_| ctx.config().builder.bundles) {
+            ctx.task_executor().spawn_critical(
+                "bundle pool maintenance task",
+                maintain_bundle_pool(bundle_pool.clone(), ctx.provider().canonical_state_stream()),
+            );
+            info!(target: "reth::cli", "Bundle pool initialized");
+
+            builder_config = builder_config.with_transaction_sources(
+                PayloadTransactionSources::new().with_source(
+                    BuildPhase::BeforePool,
+                    SourceBudget::unlimited(),
+                    bundle_pool,
+                ),
+            );
+        }
+        let simulated_payments =
+            self.simulated_payments.filter(|_| ctx.config().builder.simulated_ordering);
+        let simulation = simulated_payments
+            .map(|payments| (payments, pool.clone(), evm_config.clone(), builder_config.clone()));
+        let payload_builder =
+            reth_ethereum_payload_builder::EthereumPayloadBuilder::new(evm_config, builder_config);
 
         let payload_job_config = BasicPayloadJobGeneratorConfig::default()
             .interval(conf.interval())
@@ -276,6 +401,32 @@ impl EthereumPayloadBuilder {
 
         ctx.task_executor().spawn_critical("payload builder service", Box::pin(payload_service));
 
+        if let Some((payments, pool, evm_config, builder_config)) = simulation {
+            let client = ctx.provider().clone();
+            let chain_events = ctx.provider().canonical_state_stream();
+            let task_spawner = Box::new(ctx.task_executor().clone());
+            let payload_builder = payload_builder.clone();
+            ctx.task_executor().spawn_critical(
+                "simulated payments task",
+                Box::pin(async move {
+                    // the pending transactions are simulated in the environment of the payloads
+                    let Ok(payload_events) = payload_builder.subscribe().await else { return };
+                    maintain_simulated_payments(
+                        payments,
+                        client,
+                        pool,
+                        evm_config,
+                        builder_config,
+                        chain_events,
+                        payload_events.into_attributes_stream(),
+                        task_spawner,
+                    )
+                    .await
+                }),
+            );
+            info!(target: "reth::cli", "Simulated transaction ordering enabled");
+        }
+
         Ok(payload_builder)
     }
 }
@@ -284,7 +435,7 @@ impl<Types, Node, Pool> PayloadServiceBuilder<Node, Pool> for EthereumPayloadBui
 where
     Types: NodeTypesWithEngine<ChainSpec = ChainSpec, Primitives = EthPrimitives>,
     Node: FullNodeTypes<Types = Types>,
-    Pool: TransactionPool<Transaction: PoolTransaction<Consensus = TxTy<Node::Types>>>
+    Pool: TransactionPoolExt<Transaction: PoolTransaction<Consensus = TxTy<Node::Types>>>
         + Unpin
         + 'static,
     Types::Engine: PayloadTypes<
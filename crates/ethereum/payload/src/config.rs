@@ -1,5 +1,7 @@
 use alloy_eips::eip1559::ETHEREUM_BLOCK_GAS_LIMIT;
 use alloy_primitives::Bytes;
+use reth_payload_util::PayloadTransactionSources;
+use reth_primitives::TransactionSigned;
 use reth_primitives_traits::constants::GAS_LIMIT_BOUND_DIVISOR;
 
 /// Settings for the Ethereum builder.
@@ -9,12 +11,18 @@ pub struct EthereumBuilderConfig {
     pub extra_data: Bytes,
     /// Desired gas limit.
     pub desired_gas_limit: u64,
+    /// Sources of transactions that are included in addition to the pool transactions.
+    pub transaction_sources: PayloadTransactionSources<TransactionSigned>,
 }
 
 impl EthereumBuilderConfig {
     /// Create new payload builder config.
     pub const fn new(extra_data: Bytes) -> Self {
-        Self { extra_data, desired_gas_limit: ETHEREUM_BLOCK_GAS_LIMIT }
+        Self {
+            extra_data,
+            desired_gas_limit: ETHEREUM_BLOCK_GAS_LIMIT,
+            transaction_sources: PayloadTransactionSources::new(),
+        }
     }
 
     /// Set desired gas limit.
@@ -22,6 +30,15 @@ impl EthereumBuilderConfig {
         self.desired_gas_limit = desired_gas_limit;
         self
     }
+
+    /// Set the sources of transactions that are included in addition to the pool transactions.
+    pub fn with_transaction_sources(
+        mut self,
+        transaction_sources: PayloadTransactionSources<TransactionSigned>,
+    ) -> Self {
+        self.transaction_sources = transaction_sources;
+        self
+    }
 }
 
 impl EthereumBuilderConfig {
@@ -27,10 +27,10 @@ use reth_execution_types::ExecutionOutcome;
 use reth_payload_builder::{EthBuiltPayload, EthPayloadBuilderAttributes};
 use reth_payload_builder_primitives::PayloadBuilderError;
 use reth_payload_primitives::PayloadBuilderAttributes;
+use reth_payload_util::BuildPhase;
 use reth_primitives::{
     proofs::{self},
-    Block, BlockBody, BlockExt, EthereumHardforks, InvalidTransactionError, Receipt,
-    TransactionSigned,
+    Block, BlockBody, BlockExt, EthereumHardforks, InvalidTransactionError, TransactionSigned,
 };
 use reth_revm::database::StateProviderDatabase;
 use reth_transaction_pool::{
@@ -50,7 +50,13 @@ use tracing::{debug, trace, warn};
 
 mod config;
 pub use config::*;
+
+mod simulation;
 use reth_storage_api::StateProviderFactory;
+pub use simulation::maintain_simulated_payments;
+
+mod sources;
+use sources::{execute_source_phase, ExecutionInfo};
 
 type BestTransactionsIter<Pool> = Box<
     dyn BestTransactions<Item = Arc<ValidPoolTransaction<<Pool as TransactionPool>::Transaction>>>,
@@ -186,19 +192,15 @@ where
     let PayloadConfig { parent_header, attributes } = config;
 
     debug!(target: "payload_builder", id=%attributes.id, parent_header = ?parent_header.hash(), parent_number = parent_header.number, "building new payload");
-    let mut cumulative_gas_used = 0;
-    let mut sum_blob_gas_used = 0;
     let block_gas_limit: u64 = initialized_block_env.gas_limit.to::<u64>();
     let base_fee = initialized_block_env.basefee.to::<u64>();
 
-    let mut executed_txs = Vec::new();
-    let mut executed_senders = Vec::new();
+    let mut info = ExecutionInfo::default();
 
     let mut best_txs = best_txs(BestTransactionsAttributes::new(
         base_fee,
         initialized_block_env.get_blob_gasprice().map(|gasprice| gasprice as u64),
     ));
-    let mut total_fees = U256::ZERO;
 
     let block_number = initialized_block_env.number.to::<u64>();
 
@@ -240,10 +242,26 @@ where
     );
     let mut evm = evm_config.evm_with_env(&mut db, env);
 
-    let mut receipts = Vec::new();
+    // execute the transactions of the sources that precede the pool
+    for phase in [BuildPhase::TopOfBlock, BuildPhase::BeforePool] {
+        if execute_source_phase(
+            &mut **evm.db_mut(),
+            &evm_config,
+            &builder_config.transaction_sources,
+            phase,
+            parent_header.hash(),
+            &initialized_cfg,
+            &initialized_block_env,
+            &cancel,
+            &mut info,
+        )? {
+            return Ok(BuildOutcome::Cancelled)
+        }
+    }
+
     while let Some(pool_tx) = best_txs.next() {
         // ensure we still have capacity for this transaction
-        if cumulative_gas_used + pool_tx.gas_limit() > block_gas_limit {
+        if info.cumulative_gas_used + pool_tx.gas_limit() > block_gas_limit {
             // we can't fit this transaction into the block, so we need to mark it as invalid
             // which also removes all dependent transaction from the iterator before we can
             // continue
@@ -266,12 +284,12 @@ where
         // the EIP-4844 can still fit in the block
         if let Some(blob_tx) = tx.transaction.as_eip4844() {
             let tx_blob_gas = blob_tx.blob_gas();
-            if sum_blob_gas_used + tx_blob_gas > MAX_DATA_GAS_PER_BLOCK {
+            if info.sum_blob_gas_used + tx_blob_gas > MAX_DATA_GAS_PER_BLOCK {
                 // we can't fit this _blob_ transaction into the block, so we mark it as
                 // invalid, which removes its dependent transactions from
                 // the iterator. This is similar to the gas limit condition
                 // for regular transactions above.
-                trace!(target: "payload_builder", tx=?tx.hash, ?sum_blob_gas_used, ?tx_blob_gas, "skipping blob transaction because it would exceed the max data gas per block");
+                trace!(target: "payload_builder", tx=?tx.hash, sum_blob_gas_used=?info.sum_blob_gas_used, ?tx_blob_gas, "skipping blob transaction because it would exceed the max data gas per block");
                 best_txs.mark_invalid(
                     &pool_tx,
                     InvalidPoolTransactionError::ExceedsGasLimit(
@@ -319,43 +337,39 @@ where
         // commit changes
         evm.db_mut().commit(state);
 
-        // add to the total blob gas used if the transaction successfully executed
-        if let Some(blob_tx) = tx.transaction.as_eip4844() {
-            let tx_blob_gas = blob_tx.blob_gas();
-            sum_blob_gas_used += tx_blob_gas;
+        let is_blob_tx = tx.is_eip4844();
+        info.record(tx, result, base_fee);
 
-            // if we've reached the max data gas per block, we can skip blob txs entirely
-            if sum_blob_gas_used == MAX_DATA_GAS_PER_BLOCK {
-                best_txs.skip_blobs();
-            }
+        // if we've reached the max data gas per block, we can skip blob txs entirely
+        if is_blob_tx && info.sum_blob_gas_used == MAX_DATA_GAS_PER_BLOCK {
+            best_txs.skip_blobs();
         }
+    }
 
-        let gas_used = result.gas_used();
-
-        // add gas used by the transaction to cumulative gas used, before creating the receipt
-        cumulative_gas_used += gas_used;
-
-        // Push transaction changeset and calculate header bloom filter for receipt.
-        #[allow(clippy::needless_update)] // side-effect of optimism fields
-        receipts.push(Some(Receipt {
-            tx_type: tx.tx_type(),
-            success: result.is_success(),
-            cumulative_gas_used,
-            logs: result.into_logs().into_iter().map(Into::into).collect(),
-            ..Default::default()
-        }));
-
-        // update add to total fees
-        let miner_fee = tx
-            .effective_tip_per_gas(Some(base_fee))
-            .expect("fee is always valid; execution succeeded");
-        total_fees += U256::from(miner_fee) * U256::from(gas_used);
-
-        // append sender and transaction to the respective lists
-        executed_senders.push(tx.signer());
-        executed_txs.push(tx.into_signed());
+    // execute the transactions of the sources that follow the pool
+    if execute_source_phase(
+        &mut **evm.db_mut(),
+        &evm_config,
+        &builder_config.transaction_sources,
+        BuildPhase::EndOfBlock,
+        parent_header.hash(),
+        &initialized_cfg,
+        &initialized_block_env,
+        &cancel,
+        &mut info,
+    )? {
+        return Ok(BuildOutcome::Cancelled)
     }
 
+    let ExecutionInfo {
+        executed_txs,
+        executed_senders,
+        receipts,
+        cumulative_gas_used,
+        sum_blob_gas_used,
+        total_fees,
+    } = info;
+
     // check if we have a better block
     if !is_better_payload(best_payload.as_ref(), total_fees) {
         // Release db
@@ -0,0 +1,425 @@
+//! Simulation of the pending transactions for the
+//! [`SimulatedPaymentOrdering`](reth_transaction_pool::SimulatedPaymentOrdering).
+
+use crate::EthereumBuilderConfig;
+use alloy_consensus::Header;
+use alloy_primitives::{
+    map::{AddressHashMap, AddressHashSet, B256HashSet},
+    B256, U256,
+};
+use futures_util::{stream, Stream, StreamExt};
+use reth_chain_state::CanonStateNotification;
+use reth_errors::RethError;
+use reth_evm::{env::EvmEnv, ConfigureEvm, NextBlockEnvAttributes};
+use reth_payload_builder::EthPayloadBuilderAttributes;
+use reth_payload_primitives::PayloadBuilderAttributes;
+use reth_primitives::TransactionSigned;
+use reth_revm::database::StateProviderDatabase;
+use reth_storage_api::{HeaderProvider, StateProviderFactory};
+use reth_tasks::TaskSpawner;
+use reth_transaction_pool::{
+    NewSubpoolTransactionStream, PoolTransaction, SimulatedPayments, SubPool,
+    TransactionListenerKind, TransactionPoolExt, ValidPoolTransaction,
+};
+use revm::{
+    db::CacheDB,
+    primitives::{EnvWithHandlerCfg, TxEnv},
+    Database, DatabaseCommit,
+};
+use std::sync::Arc;
+use tracing::{debug, trace};
+
+/// The maximum number of events that are handled in one batch.
+const MAX_SIMULATION_BATCH_SIZE: usize = 256;
+
+/// An event handled by [`maintain_simulated_payments`].
+enum SimulationEvent<T: PoolTransaction> {
+    /// The canonical chain has a new tip.
+    CanonStateChanged(B256),
+    /// The attributes of a new payload were received.
+    Attributes(EthPayloadBuilderAttributes),
+    /// A new transaction was added to the pending subpool.
+    NewPendingTransaction(Arc<ValidPoolTransaction<T>>),
+}
+
+/// Keeps the [`SimulatedPayments`] of the pending transactions up to date.
+///
+/// Every pending transaction is executed on top of the parent of the payload that is being built,
+/// in the environment of that payload, and the balance change of its fee recipient per unit of gas
+/// is recorded. The environment is derived from the latest payload attributes like the payload
+/// builder does, so nothing is simulated until the first attributes are received. The transactions
+/// of a sender are executed in nonce order on top of each other, but independently of the
+/// transactions of other senders.
+///
+/// All pending transactions are simulated again for the attributes of every new payload, while
+/// new pending transactions are simulated on top of the earlier transactions of their sender. The
+/// simulations run on the blocking pool of the task spawner, after which only the senders whose
+/// payments changed are reprioritized.
+#[allow(clippy::too_many_arguments)]
+pub async fn maintain_simulated_payments<Client, Pool, EvmConfig, St, A>(
+    payments: SimulatedPayments,
+    client: Client,
+    pool: Pool,
+    evm_config: EvmConfig,
+    builder_config: EthereumBuilderConfig,
+    events: St,
+    attributes: A,
+    task_spawner: Box<dyn TaskSpawner>,
+) where
+    Client: StateProviderFactory + HeaderProvider<Header = Header> + Clone + 'static,
+    Pool: TransactionPoolExt<Transaction: PoolTransaction<Consensus = TransactionSigned>>
+        + Clone
+        + 'static,
+    EvmConfig: ConfigureEvm<Header = Header, Transaction = TransactionSigned>,
+    St: Stream<Item = CanonStateNotification> + Send + Unpin + 'static,
+    A: Stream<Item = EthPayloadBuilderAttributes> + Send + Unpin + 'static,
+{
+    let pending = NewSubpoolTransactionStream::new(
+        pool.new_transactions_listener_for(TransactionListenerKind::All),
+        SubPool::Pending,
+    )
+    .map(|event| SimulationEvent::NewPendingTransaction(event.transaction));
+    let events = stream::select(
+        events.map(|notification| SimulationEvent::CanonStateChanged(notification.tip().hash())),
+        attributes.map(SimulationEvent::Attributes),
+    );
+    let mut events = stream::select(events, pending).ready_chunks(MAX_SIMULATION_BATCH_SIZE);
+
+    // the attributes of the payload whose environment the transactions are simulated in
+    let mut next_payload: Option<EthPayloadBuilderAttributes> = None;
+
+    while let Some(batch) = events.next().await {
+        let mut resimulate = false;
+        let mut senders = AddressHashSet::default();
+        for event in batch {
+            match event {
+                SimulationEvent::CanonStateChanged(tip) => {
+                    // the payments are kept until the attributes for the new tip are received
+                    if next_payload.as_ref().is_some_and(|attributes| attributes.parent() != tip) {
+                        next_payload = None;
+                    }
+                }
+                SimulationEvent::Attributes(attributes) => {
+                    resimulate |= next_payload
+                        .as_ref()
+                        .is_none_or(|current| current.payload_id() != attributes.payload_id());
+                    next_payload = Some(attributes);
+                }
+                SimulationEvent::NewPendingTransaction(tx) => {
+                    senders.insert(tx.sender());
+                }
+            }
+        }
+
+        let Some(attributes) = next_payload.clone() else { continue };
+        let transactions = if resimulate {
+            let transactions = pool.pending_transactions();
+            // drop the payments of the transactions that are no longer pending
+            let hashes = transactions.iter().map(|tx| *tx.hash()).collect::<B256HashSet>();
+            payments.retain(|hash| hashes.contains(hash));
+            transactions
+        } else if !senders.is_empty() {
+            // the later transactions of a sender must be simulated on top of the earlier ones
+            pool.pending_transactions()
+                .into_iter()
+                .filter(|tx| senders.contains(&tx.sender()))
+                .collect()
+        } else {
+            continue
+        };
+
+        let simulation = {
+            let payments = payments.clone();
+            let client = client.clone();
+            let pool = pool.clone();
+            let evm_config = evm_config.clone();
+            let builder_config = builder_config.clone();
+            Box::pin(async move {
+                match simulate_payments(
+                    &client,
+                    &evm_config,
+                    &builder_config,
+                    &attributes,
+                    transactions,
+                    &payments,
+                ) {
+                    Ok(changed) => {
+                        trace!(target: "payload_builder", changed = changed.len(), "Simulated pending transactions");
+                        if !changed.is_empty() {
+                            pool.reprioritize_pending_transactions(changed.into_iter().collect());
+                        }
+                    }
+                    Err(err) => {
+                        debug!(target: "payload_builder", %err, "Failed to simulate pending transactions");
+                    }
+                }
+            })
+        };
+        let _ = task_spawner.spawn_blocking(simulation).await;
+    }
+}
+
+/// Simulates the transactions on top of the parent of the payload with the given attributes, and
+/// records their payments to the fee recipient per unit of gas.
+///
+/// The payments of the transactions that can't be executed are removed.
+///
+/// Returns the senders whose payments changed.
+fn simulate_payments<Client, EvmConfig, T>(
+    client: &Client,
+    evm_config: &EvmConfig,
+    builder_config: &EthereumBuilderConfig,
+    attributes: &EthPayloadBuilderAttributes,
+    transactions: Vec<Arc<ValidPoolTransaction<T>>>,
+    payments: &SimulatedPayments,
+) -> Result<AddressHashSet, RethError>
+where
+    Client: StateProviderFactory + HeaderProvider<Header = Header>,
+    EvmConfig: ConfigureEvm<Header = Header, Transaction = TransactionSigned>,
+    T: PoolTransaction<Consensus = TransactionSigned>,
+{
+    let Some(parent) = client.sealed_header_by_hash(attributes.parent())? else {
+        return Ok(Default::default())
+    };
+    let coinbase = attributes.suggested_fee_recipient();
+    let EvmEnv { cfg_env_with_handler_cfg, block_env } = evm_config
+        .next_cfg_and_block_env(
+            &parent,
+            NextBlockEnvAttributes {
+                timestamp: attributes.timestamp(),
+                suggested_fee_recipient: coinbase,
+                prev_randao: attributes.prev_randao(),
+                gas_limit: builder_config.gas_limit(parent.gas_limit),
+            },
+        )
+        .map_err(RethError::other)?;
+    let env =
+        EnvWithHandlerCfg::new_with_cfg_env(cfg_env_with_handler_cfg, block_env, TxEnv::default());
+    let state = client.state_by_block_hash(parent.hash())?;
+
+    let mut by_sender = AddressHashMap::<Vec<Arc<ValidPoolTransaction<T>>>>::default();
+    for tx in transactions {
+        by_sender.entry(tx.sender()).or_default().push(tx);
+    }
+
+    let mut changed = AddressHashSet::default();
+    for (sender, mut transactions) in by_sender {
+        transactions.sort_by_key(|tx| tx.nonce());
+
+        let mut db = CacheDB::new(StateProviderDatabase::new(&state));
+        let mut evm = evm_config.evm_with_env(&mut db, env.clone());
+        let mut transactions = transactions.into_iter();
+        for tx in transactions.by_ref() {
+            let tx_hash = *tx.hash();
+            let tx = tx.to_consensus();
+            *evm.tx_mut() = evm_config.tx_env(tx.as_signed(), tx.signer());
+
+            let balance_before = evm
+                .db_mut()
+                .basic(coinbase)
+                .map_err(RethError::other)?
+                .map(|account| account.balance)
+                .unwrap_or_default();
+            let Ok(result) = evm.transact() else {
+                // the later transactions of the sender can't be executed either
+                if payments.remove(&tx_hash).is_some() {
+                    changed.insert(sender);
+                }
+                break
+            };
+
+            let balance_after =
+                result.state.get(&coinbase).map_or(balance_before, |account| account.info.balance);
+            let gas_used = result.result.gas_used().max(1);
+            let payment = balance_after.saturating_sub(balance_before) / U256::from(gas_used);
+            if payments.insert(tx_hash, payment) != Some(payment) {
+                changed.insert(sender);
+            }
+
+            evm.db_mut().commit(result.state);
+        }
+        for tx in transactions {
+            if payments.remove(tx.hash()).is_some() {
+                changed.insert(sender);
+            }
+        }
+    }
+
+    Ok(changed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_consensus::constants::ETH_TO_WEI;
+    use alloy_primitives::{Address, Bytes};
+    use reth_chainspec::ChainSpecBuilder;
+    use reth_evm_ethereum::EthEvmConfig;
+    use reth_provider::test_utils::{ExtendedAccount, MockEthProvider};
+    use reth_tasks::TokioTaskExecutor;
+    use reth_transaction_pool::{
+        blobstore::InMemoryBlobStore,
+        test_utils::{MockTransaction, MockTransactionFactory, MockTransactionValidator},
+        Pool, SimulatedPaymentOrdering, TransactionOrigin, TransactionPool,
+    };
+    use std::time::Duration;
+
+    const PARENT_HASH: B256 = B256::repeat_byte(1);
+
+    /// Returns a provider with a Cancun parent block with a base fee of 7 wei, in which the given
+    /// senders are funded.
+    fn provider(senders: &[Address]) -> MockEthProvider {
+        let provider = MockEthProvider::default();
+        provider.add_header(
+            PARENT_HASH,
+            Header {
+                number: 1,
+                timestamp: 1_000,
+                gas_limit: 30_000_000,
+                gas_used: 15_000_000,
+                base_fee_per_gas: Some(7),
+                blob_gas_used: Some(0),
+                excess_blob_gas: Some(0),
+                parent_beacon_block_root: Some(B256::ZERO),
+                ..Default::default()
+            },
+        );
+        for sender in senders {
+            provider.add_account(*sender, ExtendedAccount::new(0, U256::from(ETH_TO_WEI)));
+        }
+        provider
+    }
+
+    fn evm_config() -> EthEvmConfig {
+        EthEvmConfig::new(Arc::new(ChainSpecBuilder::mainnet().cancun_activated().build()))
+    }
+
+    /// Returns the attributes of the payload on top of the parent block.
+    fn attributes(fee_recipient: Address) -> EthPayloadBuilderAttributes {
+        EthPayloadBuilderAttributes {
+            parent: PARENT_HASH,
+            timestamp: 1_012,
+            suggested_fee_recipient: fee_recipient,
+            prev_randao: B256::repeat_byte(2),
+            parent_beacon_block_root: Some(B256::ZERO),
+            ..Default::default()
+        }
+    }
+
+    /// Returns a transfer that pays a priority fee of `tip` wei per gas at a base fee of 7 wei.
+    fn transfer(sender: Address, nonce: u64, tip: u128) -> MockTransaction {
+        MockTransaction::eip1559()
+            .with_sender(sender)
+            .with_nonce(nonce)
+            .with_gas_limit(21_000)
+            .with_max_fee(100)
+            .with_priority_fee(tip)
+    }
+
+    #[test]
+    fn simulate_payments_per_sender() {
+        let (a, b, unfunded) = (Address::random(), Address::random(), Address::random());
+        let provider = provider(&[a, b]);
+        let mut f = MockTransactionFactory::default();
+
+        let a0 = f.validated_arc(transfer(a, 0, 2));
+        // can only be executed on top of the first transaction of the sender
+        let a1 = f.validated_arc(transfer(a, 1, 2));
+        // pays 10 wei per gas to the fee recipient in addition to the priority fee
+        let b0 = f.validated_arc(transfer(b, 0, 2).with_value(U256::from(210_000)));
+        let c0 = f.validated_arc(transfer(unfunded, 0, 2));
+        let fee_recipient = b0.to().unwrap();
+
+        let payments = SimulatedPayments::default();
+        payments.insert(*c0.hash(), U256::from(1));
+        let transactions = vec![a1.clone(), b0.clone(), a0.clone(), c0.clone()];
+
+        let changed = simulate_payments(
+            &provider,
+            &evm_config(),
+            &EthereumBuilderConfig::new(Bytes::new()),
+            &attributes(fee_recipient),
+            transactions.clone(),
+            &payments,
+        )
+        .unwrap();
+        assert_eq!(changed, AddressHashSet::from_iter([a, b, unfunded]));
+        assert_eq!(payments.get(a0.hash()), Some(U256::from(2)));
+        assert_eq!(payments.get(a1.hash()), Some(U256::from(2)));
+        assert_eq!(payments.get(b0.hash()), Some(U256::from(12)));
+        assert_eq!(payments.get(c0.hash()), None);
+
+        // simulating the same transactions again doesn't change any payment
+        let changed = simulate_payments(
+            &provider,
+            &evm_config(),
+            &EthereumBuilderConfig::new(Bytes::new()),
+            &attributes(fee_recipient),
+            transactions,
+            &payments,
+        )
+        .unwrap();
+        assert!(changed.is_empty());
+        assert_eq!(payments.len(), 3);
+    }
+
+    #[test]
+    fn simulate_payments_unknown_parent() {
+        let mut f = MockTransactionFactory::default();
+        let tx = f.validated_arc(transfer(Address::random(), 0, 2));
+        let payments = SimulatedPayments::default();
+
+        let changed = simulate_payments(
+            &MockEthProvider::default(),
+            &evm_config(),
+            &EthereumBuilderConfig::new(Bytes::new()),
+            &attributes(Address::random()),
+            vec![tx],
+            &payments,
+        )
+        .unwrap();
+        assert!(changed.is_empty());
+        assert!(payments.is_empty());
+    }
+
+    #[tokio::test]
+    async fn maintain_reprioritizes_by_simulated_payments() {
+        let (a, b) = (Address::random(), Address::random());
+        let payments = SimulatedPayments::default();
+        let pool = Pool::new(
+            MockTransactionValidator::default(),
+            SimulatedPaymentOrdering::<MockTransaction>::new(payments.clone()),
+            InMemoryBlobStore::default(),
+            Default::default(),
+        );
+
+        let high_tip = transfer(a, 0, 5);
+        // pays 10 wei per gas to the fee recipient in addition to the priority fee
+        let high_payment = transfer(b, 0, 2).with_value(U256::from(210_000));
+        let fee_recipient = high_payment.to().unwrap();
+        pool.add_transaction(TransactionOrigin::External, high_tip.clone()).await.unwrap();
+        pool.add_transaction(TransactionOrigin::External, high_payment.clone()).await.unwrap();
+        assert_eq!(pool.best_transactions().next().unwrap().hash(), high_tip.get_hash());
+
+        tokio::spawn(maintain_simulated_payments(
+            payments.clone(),
+            provider(&[a, b]),
+            pool.clone(),
+            evm_config(),
+            EthereumBuilderConfig::new(Bytes::new()),
+            stream::pending(),
+            stream::iter([attributes(fee_recipient)]).chain(stream::pending()),
+            Box::new(TokioTaskExecutor::default()),
+        ));
+
+        tokio::time::timeout(Duration::from_secs(10), async {
+            while pool.best_transactions().next().unwrap().hash() != high_payment.get_hash() {
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+        })
+        .await
+        .unwrap();
+        assert_eq!(payments.get(high_tip.get_hash()), Some(U256::from(5)));
+        assert_eq!(payments.get(high_payment.get_hash()), Some(U256::from(12)));
+    }
+}
@@ -0,0 +1,359 @@
+//! Execution of the [`PayloadTransactionSource`](reth_payload_util::PayloadTransactionSource)s of
+//! the Ethereum payload builder.
+
+use alloy_consensus::Header;
+use alloy_eips::eip4844::MAX_DATA_GAS_PER_BLOCK;
+use alloy_primitives::{Address, B256, U256};
+use reth_basic_payload_builder::{
+    execute_transaction_group, order_transaction_groups_by_profit, Cancelled,
+};
+use reth_errors::ProviderError;
+use reth_evm::ConfigureEvm;
+use reth_payload_builder_primitives::PayloadBuilderError;
+use reth_payload_util::{BuildPhase, PayloadSourceContext, PayloadTransactionSources};
+use reth_primitives::{Receipt, RecoveredTx, TransactionSigned};
+use revm::{
+    primitives::{BlockEnv, CfgEnvWithHandlerCfg, EnvWithHandlerCfg, ExecutionResult, TxEnv},
+    Database, State,
+};
+use tracing::trace;
+
+/// The transactions executed so far and their byproducts.
+#[derive(Debug, Default)]
+pub(crate) struct ExecutionInfo {
+    /// All executed transactions.
+    pub(crate) executed_txs: Vec<TransactionSigned>,
+    /// The senders of the executed transactions.
+    pub(crate) executed_senders: Vec<Address>,
+    /// The receipts of the executed transactions.
+    pub(crate) receipts: Vec<Option<Receipt>>,
+    /// All gas used so far.
+    pub(crate) cumulative_gas_used: u64,
+    /// All blob gas used so far.
+    pub(crate) sum_blob_gas_used: u64,
+    /// The fees of all executed transactions.
+    pub(crate) total_fees: U256,
+}
+
+impl ExecutionInfo {
+    /// Records a successfully executed pool transaction, which pays its priority fee to the
+    /// beneficiary of the block.
+    pub(crate) fn record(
+        &mut self,
+        tx: RecoveredTx<TransactionSigned>,
+        result: ExecutionResult,
+        base_fee: u64,
+    ) {
+        let miner_fee = tx
+            .effective_tip_per_gas(Some(base_fee))
+            .expect("fee is always valid; execution succeeded");
+        let gas_used = result.gas_used();
+        self.push(tx, result);
+
+        // update add to total fees
+        self.total_fees += U256::from(miner_fee) * U256::from(gas_used);
+    }
+
+    /// Appends a successfully executed transaction, without accounting for its fees.
+    fn push(&mut self, tx: RecoveredTx<TransactionSigned>, result: ExecutionResult) {
+        // add to the total blob gas used if the transaction successfully executed
+        if let Some(blob_tx) = tx.transaction.as_eip4844() {
+            self.sum_blob_gas_used += blob_tx.blob_gas();
+        }
+
+        // add gas used by the transaction to cumulative gas used, before creating the receipt
+        self.cumulative_gas_used += result.gas_used();
+
+        // Push transaction changeset and calculate header bloom filter for receipt.
+        #[allow(clippy::needless_update)] // side-effect of optimism fields
+        self.receipts.push(Some(Receipt {
+            tx_type: tx.tx_type(),
+            success: result.is_success(),
+            cumulative_gas_used: self.cumulative_gas_used,
+            logs: result.into_logs().into_iter().map(Into::into).collect(),
+            ..Default::default()
+        }));
+
+        // append sender and transaction to the respective lists
+        self.executed_senders.push(tx.signer());
+        self.executed_txs.push(tx.into_signed());
+    }
+}
+
+/// Executes the transaction groups of all sources registered for the given phase.
+///
+/// Groups that don't fit into the block or into the budget of their source are skipped. Groups with
+/// an invalid transaction, a transaction that reverts without being allowed to, or that pay less
+/// than their minimum profit are discarded as a whole, see [`execute_transaction_group`].
+///
+/// The fees of an included group are the increase of the balance of the beneficiary of the block,
+/// so they account for direct transfers as well.
+///
+/// The groups of a source that orders them by profit are simulated on top of the payload first,
+/// see [`order_transaction_groups_by_profit`].
+///
+/// Returns `Ok(true)` if the job was cancelled.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn execute_source_phase<EvmConfig, DB>(
+    db: &mut State<DB>,
+    evm_config: &EvmConfig,
+    sources: &PayloadTransactionSources<TransactionSigned>,
+    phase: BuildPhase,
+    parent_hash: B256,
+    cfg: &CfgEnvWithHandlerCfg,
+    block_env: &BlockEnv,
+    cancel: &Cancelled,
+    info: &mut ExecutionInfo,
+) -> Result<bool, PayloadBuilderError>
+where
+    EvmConfig: ConfigureEvm<Header = Header, Transaction = TransactionSigned>,
+    DB: Database<Error = ProviderError>,
+{
+    let block_gas_limit: u64 = block_env.gas_limit.to::<u64>();
+    let base_fee = block_env.basefee.to::<u64>();
+    let env = EnvWithHandlerCfg::new_with_cfg_env(cfg.clone(), block_env.clone(), TxEnv::default());
+
+    for (budget, source) in sources.phase(phase) {
+        let mut groups = source.transactions(&PayloadSourceContext {
+            parent_hash,
+            block_number: block_env.number.to(),
+            timestamp: block_env.timestamp.to(),
+            base_fee,
+            gas_limit: block_gas_limit,
+            gas_used: info.cumulative_gas_used,
+            executed_transactions: &info.executed_txs,
+        });
+        if source.order_by_profit() {
+            groups = order_transaction_groups_by_profit(evm_config, &env, db, groups)?;
+        }
+
+        let mut source_gas_used = 0;
+        let mut source_blob_gas_used = 0;
+        for group in groups {
+            // check if the job was cancelled, if so we can exit early
+            if cancel.is_cancelled() {
+                return Ok(true)
+            }
+
+            let group_gas_limit = group.gas_limit();
+            let group_blob_gas = group.blob_gas();
+            if info.cumulative_gas_used + group_gas_limit > block_gas_limit ||
+                info.sum_blob_gas_used + group_blob_gas > MAX_DATA_GAS_PER_BLOCK ||
+                !budget.allows(
+                    source_gas_used + group_gas_limit,
+                    source_blob_gas_used + group_blob_gas,
+                )
+            {
+                trace!(target: "payload_builder", ?phase, group_gas_limit, group_blob_gas, "skipping transaction group that exceeds the available gas");
+                continue
+            }
+
+            let Some(executed) = execute_transaction_group(evm_config, env.clone(), db, &group)?
+            else {
+                trace!(target: "payload_builder", ?phase, "discarded transaction group");
+                continue
+            };
+
+            let (gas_used, blob_gas_used) = (info.cumulative_gas_used, info.sum_blob_gas_used);
+            info.total_fees += executed.profit;
+            for (tx, result) in executed.commit(db) {
+                info.push(tx, result);
+            }
+
+            source_gas_used += info.cumulative_gas_used - gas_used;
+            source_blob_gas_used += info.sum_blob_gas_used - blob_gas_used;
+        }
+    }
+
+    Ok(false)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_consensus::{constants::ETH_TO_WEI, TxEip1559};
+    use alloy_primitives::{address, Bytes, PrimitiveSignature as Signature, TxKind};
+    use reth_chainspec::MAINNET;
+    use reth_evm_ethereum::EthEvmConfig;
+    use reth_payload_util::{PayloadTransactionGroup, PayloadTransactionSource, SourceBudget};
+    use reth_primitives::{Account, Transaction};
+    use reth_revm::{database::StateProviderDatabase, test_utils::StateProviderTest};
+    use revm::primitives::{CfgEnv, SpecId};
+
+    const SENDER: Address = address!("0000000000000000000000000000000000000001");
+    const COINBASE: Address = address!("0000000000000000000000000000000000000002");
+    /// A contract that always reverts.
+    const REVERTER: Address = address!("0000000000000000000000000000000000000003");
+
+    /// A source that always returns the same groups.
+    struct StaticSource {
+        groups: Vec<PayloadTransactionGroup<TransactionSigned>>,
+        order_by_profit: bool,
+    }
+
+    impl PayloadTransactionSource<TransactionSigned> for StaticSource {
+        fn transactions(
+            &self,
+            _: &PayloadSourceContext<'_, TransactionSigned>,
+        ) -> Vec<PayloadTransactionGroup<TransactionSigned>> {
+            self.groups.clone()
+        }
+
+        fn order_by_profit(&self) -> bool {
+            self.order_by_profit
+        }
+    }
+
+    /// Returns a transaction of the sender that pays a priority fee of 2 wei per gas.
+    fn tx(nonce: u64, to: Address, value: u64) -> RecoveredTx<TransactionSigned> {
+        let tx = Transaction::Eip1559(TxEip1559 {
+            chain_id: 1,
+            nonce,
+            gas_limit: 100_000,
+            max_fee_per_gas: 10,
+            max_priority_fee_per_gas: 2,
+            to: TxKind::Call(to),
+            value: U256::from(value),
+            ..Default::default()
+        });
+        TransactionSigned::new_unhashed(tx, Signature::test_signature()).with_signer(SENDER)
+    }
+
+    /// Executes the groups in the given order, see [`execute_source`].
+    fn execute(
+        groups: Vec<PayloadTransactionGroup<TransactionSigned>>,
+    ) -> (ExecutionInfo, State<StateProviderDatabase<StateProviderTest>>) {
+        execute_source(StaticSource { groups, order_by_profit: false })
+    }
+
+    /// Executes the source as the only source of the [`BuildPhase::BeforePool`] phase, with a base
+    /// fee of 1 wei.
+    fn execute_source(
+        source: StaticSource,
+    ) -> (ExecutionInfo, State<StateProviderDatabase<StateProviderTest>>) {
+        let mut state = StateProviderTest::default();
+        state.insert_account(
+            SENDER,
+            Account { balance: U256::from(ETH_TO_WEI), ..Default::default() },
+            None,
+            Default::default(),
+        );
+        // PUSH0 PUSH0 REVERT
+        state.insert_account(
+            REVERTER,
+            Account::default(),
+            Some(Bytes::from_static(&[0x5f, 0x5f, 0xfd])),
+            Default::default(),
+        );
+        let mut db = State::builder()
+            .with_database(StateProviderDatabase::new(state))
+            .with_bundle_update()
+            .build();
+
+        let sources = PayloadTransactionSources::new().with_source(
+            BuildPhase::BeforePool,
+            SourceBudget::unlimited(),
+            source,
+        );
+        let block_env = BlockEnv {
+            number: U256::from(1),
+            coinbase: COINBASE,
+            gas_limit: U256::from(30_000_000),
+            basefee: U256::from(1),
+            ..Default::default()
+        };
+        let mut info = ExecutionInfo::default();
+        let cancelled = execute_source_phase(
+            &mut db,
+            &EthEvmConfig::new(MAINNET.clone()),
+            &sources,
+            BuildPhase::BeforePool,
+            B256::ZERO,
+            &CfgEnvWithHandlerCfg::new_with_spec_id(CfgEnv::default(), SpecId::CANCUN),
+            &block_env,
+            &Cancelled::default(),
+            &mut info,
+        )
+        .unwrap();
+        assert!(!cancelled);
+
+        (info, db)
+    }
+
+    fn sender_nonce(db: &mut State<StateProviderDatabase<StateProviderTest>>) -> u64 {
+        db.basic(SENDER).unwrap().unwrap().nonce
+    }
+
+    #[test]
+    fn discards_group_with_invalid_transaction() {
+        let included = tx(0, COINBASE, 0);
+        // the nonce gap makes the second transaction invalid
+        let (info, mut db) = execute(vec![
+            PayloadTransactionGroup::new(vec![tx(0, COINBASE, 0), tx(2, COINBASE, 0)]),
+            // only valid if the first transaction of the discarded group was rolled back
+            PayloadTransactionGroup::single(included.clone()),
+        ]);
+
+        assert_eq!(info.executed_txs, vec![included.into_signed()]);
+        assert_eq!(info.receipts.len(), 1);
+        assert_eq!(info.cumulative_gas_used, 21_000);
+        assert_eq!(sender_nonce(&mut db), 1);
+    }
+
+    #[test]
+    fn discards_group_with_disallowed_revert() {
+        let transfer = tx(0, COINBASE, 0);
+        let revert = tx(1, REVERTER, 0);
+        let (info, mut db) = execute(vec![
+            PayloadTransactionGroup::new(vec![transfer.clone(), revert.clone()])
+                .with_reverting_tx_hashes(Vec::new()),
+            PayloadTransactionGroup::new(vec![transfer.clone(), revert.clone()])
+                .with_reverting_tx_hashes(vec![revert.hash()]),
+        ]);
+
+        assert_eq!(info.executed_txs, vec![transfer.into_signed(), revert.into_signed()]);
+        assert_eq!(
+            info.receipts
+                .iter()
+                .map(|receipt| receipt.as_ref().unwrap().success)
+                .collect::<Vec<_>>(),
+            vec![true, false]
+        );
+        assert_eq!(sender_nonce(&mut db), 2);
+    }
+
+    #[test]
+    fn discards_unprofitable_group() {
+        // a transfer uses 21000 gas and pays a priority fee of 2 wei per gas
+        let tip_only = tx(0, COINBASE, 0);
+        let with_transfer = tx(0, COINBASE, 2 * 21_000);
+        let (info, mut db) = execute(vec![
+            PayloadTransactionGroup::single(tip_only).with_min_profit_per_gas(3),
+            PayloadTransactionGroup::single(with_transfer.clone()).with_min_profit_per_gas(3),
+        ]);
+
+        assert_eq!(info.executed_txs, vec![with_transfer.into_signed()]);
+        assert_eq!(sender_nonce(&mut db), 1);
+        // the fees include the direct transfer to the beneficiary
+        assert_eq!(info.total_fees, U256::from(4 * 21_000));
+        assert_eq!(db.basic(COINBASE).unwrap().unwrap().balance, info.total_fees);
+    }
+
+    #[test]
+    fn orders_groups_by_simulated_profit() {
+        // both transactions use nonce 0, so only the first executed one is included
+        let tip_only = tx(0, COINBASE, 0);
+        let with_transfer = tx(0, COINBASE, 4 * 21_000);
+        let groups = vec![
+            PayloadTransactionGroup::single(tip_only.clone()),
+            PayloadTransactionGroup::single(with_transfer.clone()),
+        ];
+
+        let (info, _) = execute(groups.clone());
+        assert_eq!(info.executed_txs, vec![tip_only.into_signed()]);
+
+        let (info, _) = execute_source(StaticSource { groups, order_by_profit: true });
+        assert_eq!(info.executed_txs, vec![with_transfer.into_signed()]);
+        assert_eq!(info.total_fees, U256::from(6 * 21_000));
+    }
+}
@@ -19,7 +19,7 @@ use reth_primitives::{BlockWithSenders, EthPrimitives, NodePrimitives, Receipt,
 use reth_prune_types::PruneModes;
 use reth_storage_errors::provider::{ProviderError, ProviderResult};
 use revm::State;
-use revm_primitives::db::Database;
+use revm_primitives::{db::Database, Account, AccountStatus, EvmState, EvmStorageSlot};
 use std::{fmt::Display, sync::Arc};
 
 impl<C: Send + Sync, N: NodePrimitives> EvmEnvProvider<N::BlockHeader>
@@ -105,12 +105,47 @@ impl<DB> Executor<DB> for MockExecutorProvider {
     fn execute_with_state_hook<F>(
         self,
         input: Self::Input<'_>,
-        _: F,
+        mut state_hook: F,
     ) -> Result<Self::Output, Self::Error>
     where
         F: OnStateHook,
     {
-        <Self as Executor<DB>>::execute(self, input)
+        let output = <Self as Executor<DB>>::execute(self, input)?;
+
+        // report all changes of the mocked result at once, like a block with a single transaction
+        if !output.state.state.is_empty() {
+            let state: EvmState = output
+                .state
+                .state
+                .iter()
+                .map(|(address, account)| {
+                    let mut evm_account = Account {
+                        info: account.info.clone().unwrap_or_default(),
+                        storage: account
+                            .storage
+                            .iter()
+                            .map(|(slot, value)| {
+                                (
+                                    *slot,
+                                    EvmStorageSlot::new_changed(
+                                        value.previous_or_original_value,
+                                        value.present_value,
+                                    ),
+                                )
+                            })
+                            .collect(),
+                        status: AccountStatus::Touched,
+                    };
+                    if account.info.is_none() {
+                        evm_account.mark_selfdestruct();
+                    }
+                    (*address, evm_account)
+                })
+                .collect();
+            state_hook.on_state(&state);
+        }
+
+        Ok(output)
     }
 }
 
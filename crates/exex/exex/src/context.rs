@@ -1,4 +1,5 @@
 use crate::{ExExContextDyn, ExExEvent, ExExNotifications, ExExNotificationsStream};
+use alloy_primitives::BlockNumber;
 use reth_exex_types::ExExHead;
 use reth_node_api::{FullNodeComponents, NodePrimitives, NodeTypes};
 use reth_node_core::node_config::NodeConfig;
@@ -118,6 +119,19 @@ where
     pub fn set_notifications_with_head(&mut self, head: ExExHead) {
         self.notifications.set_with_head(head);
     }
+
+    /// Sets notifications stream to [`crate::ExExNotificationsWithHead`], a stream of notifications
+    /// for all blocks starting from the provided block number, inclusive.
+    pub fn set_notifications_since(&mut self, block_number: BlockNumber) -> eyre::Result<()> {
+        self.notifications.set_since(block_number)
+    }
+
+    /// Sets notifications stream to [`crate::ExExNotificationsWithHead`], a stream of notifications
+    /// resuming from the last [`ExExEvent::FinishedHeight`] sent by the `ExEx` before the node
+    /// was restarted, or starting from the provided block number if there's none.
+    pub fn set_notifications_resumed(&mut self, block_number: BlockNumber) -> eyre::Result<()> {
+        self.notifications.set_resumed(block_number)
+    }
 }
 
 #[cfg(test)]
@@ -149,6 +163,8 @@ mod tests {
                 self.ctx.task_executor();
                 self.ctx.set_notifications_without_head();
                 self.ctx.set_notifications_with_head(ExExHead { block: Default::default() });
+                self.ctx.set_notifications_since(1)?;
+                self.ctx.set_notifications_resumed(1)?;
                 Ok(())
             }
         }
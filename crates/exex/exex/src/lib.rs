@@ -23,6 +23,14 @@
 //! event. To clarify: if the `ExEx` emits `ExExEvent::FinishedHeight(0)` it will receive
 //! notifications for any `block_number > 0`.
 //!
+//! # Resuming
+//!
+//! The last emitted `ExExEvent::FinishedHeight` of every `ExEx` is persisted alongside the WAL. An
+//! `ExEx` that calls [`ExExContext::set_notifications_resumed`] on startup will receive all
+//! notifications after that height, replayed from the WAL or backfilled from the database, and
+//! then the live notifications. An `ExEx` can also ask for all notifications since a specific
+//! block with [`ExExContext::set_notifications_since`].
+//!
 //! [`Future`]: std::future::Future
 //! [`ExExContext`]: crate::ExExContext
 //! [`CanonStateNotification`]: reth_provider::CanonStateNotification
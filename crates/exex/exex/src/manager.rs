@@ -93,7 +93,8 @@ impl<N: NodePrimitives> ExExHandle<N> {
     /// Create a new handle for the given `ExEx`.
     ///
     /// Returns the handle, as well as a [`UnboundedSender`] for [`ExExEvent`]s and a
-    /// [`mpsc::Receiver`] for [`ExExNotification`]s that should be given to the `ExEx`.
+    /// [`mpsc::Receiver`] for [`ExExNotification`]s that should be given to the `ExEx`. The
+    /// notifications carry the finished height of the `ExEx` persisted in the WAL, if any.
     pub fn new<P, E: BlockExecutorProvider<Primitives = N>>(
         id: String,
         node_head: Head,
@@ -103,8 +104,10 @@ impl<N: NodePrimitives> ExExHandle<N> {
     ) -> (Self, UnboundedSender<ExExEvent>, ExExNotifications<P, E>) {
         let (notification_tx, notification_rx) = mpsc::channel(1);
         let (event_tx, event_rx) = mpsc::unbounded_channel();
+        let finished_height = wal_handle.finished_height(&id);
         let notifications =
-            ExExNotifications::new(node_head, provider, executor, notification_rx, wal_handle);
+            ExExNotifications::new(node_head, provider, executor, notification_rx, wal_handle)
+                .with_finished_height(finished_height);
 
         (
             Self {
@@ -245,6 +248,9 @@ pub struct ExExManager<P, N: NodePrimitives> {
 
     /// Write-Ahead Log for the [`ExExNotification`]s.
     wal: Wal<N>,
+    /// The lowest finished height among all `ExEx`'s at the time the finished heights were last
+    /// persisted in the WAL.
+    persisted_finished_height: Option<u64>,
     /// A stream of finalized headers.
     finalized_header_stream: ForkChoiceStream<SealedHeader<N::BlockHeader>>,
 
@@ -305,6 +311,7 @@ where
             finished_height: finished_height_tx,
 
             wal,
+            persisted_finished_height: None,
             finalized_header_stream,
 
             handle: ExExManagerHandle {
@@ -418,6 +425,28 @@ where
 
         Ok(())
     }
+
+    /// Persists the finished heights of all `ExEx`'s in the WAL if the lowest finished height
+    /// differs from the one at the time of the last write.
+    fn persist_finished_heights(&mut self) -> eyre::Result<()> {
+        let lowest_finished_height = self
+            .exex_handles
+            .iter()
+            .filter_map(|exex| exex.finished_height.map(|height| height.number))
+            .min();
+        if lowest_finished_height == self.persisted_finished_height {
+            return Ok(())
+        }
+
+        self.wal.set_finished_heights(
+            self.exex_handles
+                .iter()
+                .filter_map(|exex| exex.finished_height.map(|height| (exex.id.clone(), height))),
+        )?;
+        self.persisted_finished_height = lowest_finished_height;
+
+        Ok(())
+    }
 }
 
 impl<P, N> Future for ExExManager<P, N>
@@ -428,7 +457,8 @@ where
     type Output = eyre::Result<()>;
 
     /// Main loop of the [`ExExManager`]. The order of operations is as follows:
-    /// 1. Handle incoming ExEx events. We do it before finalizing the WAL, because it depends on
+    /// 1. Handle incoming ExEx events and persist the finished heights in the WAL if the lowest
+    ///    finished height has changed. We do it before finalizing the WAL, because it depends on
     ///    the latest state of [`ExExEvent::FinishedHeight`] events.
     /// 2. Finalize the WAL with the finalized header, if necessary.
     /// 3. Drain [`ExExManagerHandle`] notifications, push them to the internal buffer and update
@@ -442,15 +472,27 @@ where
         let this = self.get_mut();
 
         // Handle incoming ExEx events
+        let mut has_finished_heights = false;
         for exex in &mut this.exex_handles {
             while let Poll::Ready(Some(event)) = exex.receiver.poll_recv(cx) {
                 debug!(target: "exex::manager", exex_id = %exex.id, ?event, "Received event from ExEx");
                 exex.metrics.events_sent_total.increment(1);
                 match event {
-                    ExExEvent::FinishedHeight(height) => exex.finished_height = Some(height),
+                    ExExEvent::FinishedHeight(height) => {
+                        exex.finished_height = Some(height);
+                        has_finished_heights = true;
+                    }
                 }
             }
         }
+        // Persist the finished heights, so that the ExExes can resume from them after a restart.
+        //
+        // Persisting does a synchronous write, so it's only done when the lowest finished height
+        // changes. ExExes that are ahead of it may receive some notifications again after a
+        // restart, which they need to handle anyway.
+        if has_finished_heights {
+            this.persist_finished_heights()?;
+        }
 
         // Drain the finalized header stream and finalize the WAL with the last header
         let mut last_finalized_header = None;
@@ -904,6 +946,10 @@ mod tests {
 
         // The finished height should be updated to the lower block height
         assert_eq!(finished_height, FinishedExExHeight::Height(42));
+
+        // The finished height is persisted in the WAL, and is available after a restart
+        let wal: Wal = Wal::new(temp_dir.path()).unwrap();
+        assert_eq!(wal.handle().finished_height("test_exex"), Some(block));
     }
 
     #[tokio::test]
@@ -961,6 +1007,35 @@ mod tests {
 
         // The finished height should be updated to the lower block height
         assert_eq!(finished_height, FinishedExExHeight::Height(10));
+
+        // The finished heights of both ExExes are persisted
+        let wal: Wal = Wal::new(temp_dir.path()).unwrap();
+        assert_eq!(wal.handle().finished_height("test_exex1"), Some(block1));
+        assert_eq!(wal.handle().finished_height("test_exex2"), Some(block2));
+
+        // Advancing the higher finished height doesn't change the lowest one, so it's not persisted
+        let block3 = BlockNumHash::new(50, B256::random());
+        event_tx1.send(ExExEvent::FinishedHeight(block3)).unwrap();
+        let _ = pinned_manager.as_mut().poll(&mut cx);
+        assert_eq!(
+            pinned_manager
+                .exex_handles
+                .iter()
+                .find(|exex| exex.id == "test_exex1")
+                .unwrap()
+                .finished_height,
+            Some(block3)
+        );
+        let wal: Wal = Wal::new(temp_dir.path()).unwrap();
+        assert_eq!(wal.handle().finished_height("test_exex1"), Some(block1));
+
+        // Advancing the lowest finished height persists the finished heights of all ExExes
+        let block4 = BlockNumHash::new(20, B256::random());
+        event_tx2.send(ExExEvent::FinishedHeight(block4)).unwrap();
+        let _ = pinned_manager.as_mut().poll(&mut cx);
+        let wal: Wal = Wal::new(temp_dir.path()).unwrap();
+        assert_eq!(wal.handle().finished_height("test_exex1"), Some(block3));
+        assert_eq!(wal.handle().finished_height("test_exex2"), Some(block4));
     }
 
     #[tokio::test]
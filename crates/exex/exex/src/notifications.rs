@@ -1,14 +1,19 @@
 use crate::{BackfillJobFactory, ExExNotification, StreamBackfillJob, WalHandle};
 use alloy_consensus::BlockHeader;
+use alloy_eips::BlockNumHash;
+use alloy_primitives::BlockNumber;
 use futures::{Stream, StreamExt};
 use reth_chainspec::Head;
 use reth_evm::execute::BlockExecutorProvider;
 use reth_exex_types::ExExHead;
 use reth_node_api::NodePrimitives;
 use reth_primitives::EthPrimitives;
-use reth_provider::{BlockReader, Chain, HeaderProvider, StateProviderFactory};
+use reth_provider::{
+    BlockReader, Chain, ChainSplit, ChainSplitTarget, HeaderProvider, StateProviderFactory,
+};
 use reth_tracing::tracing::debug;
 use std::{
+    collections::VecDeque,
     fmt::Debug,
     pin::Pin,
     sync::Arc,
@@ -25,6 +30,8 @@ where
     E: BlockExecutorProvider,
 {
     inner: ExExNotificationsInner<P, E>,
+    /// The finished height of the ExEx persisted before the node was restarted.
+    finished_height: Option<BlockNumHash>,
 }
 
 /// A trait, that represents a stream of [`ExExNotification`]s. The stream will emit notifications
@@ -61,6 +68,46 @@ pub trait ExExNotificationsStream<N: NodePrimitives = EthPrimitives>:
     fn with_head(self, exex_head: ExExHead) -> Self
     where
         Self: Sized;
+
+    /// Sets [`ExExNotificationsStream`] to a stream of [`ExExNotification`]s for all blocks
+    /// starting from the given block number, inclusive.
+    ///
+    /// The head of the stream is set to the canonical parent of the given block, and the blocks
+    /// up to the node head are replayed from the WAL, or backfilled from the database if they're
+    /// not in the WAL anymore. Block numbers `0` and `1` are equivalent, because the genesis
+    /// block is never emitted.
+    ///
+    /// See the documentation of [`ExExNotificationsWithHead`] for more details.
+    fn set_since(&mut self, block_number: BlockNumber) -> eyre::Result<()>;
+
+    /// Returns a new [`ExExNotificationsStream`] for all blocks starting from the given block
+    /// number, inclusive.
+    ///
+    /// See the documentation of [`ExExNotificationsStream::set_since`] for more details.
+    fn since(self, block_number: BlockNumber) -> eyre::Result<Self>
+    where
+        Self: Sized;
+
+    /// Returns the last height that the ExEx has sent with
+    /// [`ExExEvent::FinishedHeight`](crate::ExExEvent::FinishedHeight) before the node was
+    /// restarted, if any.
+    fn finished_height(&self) -> Option<BlockNumHash>;
+
+    /// Sets [`ExExNotificationsStream`] to resume from the last persisted
+    /// [`ExExNotificationsStream::finished_height`], or to a stream of notifications since the
+    /// given block number if the ExEx has never sent a finished height.
+    ///
+    /// This allows an ExEx to crash and resume without losing any notifications, as long as it
+    /// only sends the finished height after it has durably processed the block.
+    fn set_resumed(&mut self, block_number: BlockNumber) -> eyre::Result<()> {
+        match self.finished_height() {
+            Some(block) => {
+                self.set_with_head(ExExHead { block });
+                Ok(())
+            }
+            None => self.set_since(block_number),
+        }
+    }
 }
 
 #[derive(Debug)]
@@ -98,6 +145,24 @@ where
                 notifications,
                 wal_handle,
             )),
+            finished_height: None,
+        }
+    }
+
+    /// Sets the finished height of the ExEx persisted before the node was restarted.
+    ///
+    /// See [`ExExNotificationsStream::finished_height`].
+    pub const fn with_finished_height(mut self, finished_height: Option<BlockNumHash>) -> Self {
+        self.finished_height = finished_height;
+        self
+    }
+
+    /// Returns the provider of the inner stream.
+    const fn provider(&self) -> &P {
+        match &self.inner {
+            ExExNotificationsInner::WithoutHead(notifications) => &notifications.provider,
+            ExExNotificationsInner::WithHead(notifications) => &notifications.provider,
+            ExExNotificationsInner::Invalid => unreachable!(),
         }
     }
 }
@@ -152,6 +217,25 @@ where
         self.set_with_head(exex_head);
         self
     }
+
+    fn set_since(&mut self, block_number: BlockNumber) -> eyre::Result<()> {
+        let head_number = block_number.saturating_sub(1);
+        let head_hash = self
+            .provider()
+            .block_hash(head_number)?
+            .ok_or_else(|| eyre::eyre!("block {head_number} not found in the database"))?;
+        self.set_with_head(ExExHead { block: BlockNumHash::new(head_number, head_hash) });
+        Ok(())
+    }
+
+    fn since(mut self, block_number: BlockNumber) -> eyre::Result<Self> {
+        self.set_since(block_number)?;
+        Ok(self)
+    }
+
+    fn finished_height(&self) -> Option<BlockNumHash> {
+        self.finished_height
+    }
 }
 
 impl<P, E> Stream for ExExNotifications<P, E>
@@ -250,6 +334,10 @@ where
 /// `exex_head.number == 10`, then the first notification will be with `block.number == 11`. An
 /// `exex_head.number` of 10 indicates that the ExEx has processed up to block 10, and is ready to
 /// process block 11.
+///
+/// If the ExEx head is behind the node head, the missing blocks are replayed from the canonical
+/// notifications in the WAL, and the blocks below the WAL are backfilled from the node database
+/// first.
 #[derive(Debug)]
 pub struct ExExNotificationsWithHead<P, E>
 where
@@ -269,6 +357,9 @@ where
     pending_check_backfill: bool,
     /// The backfill job to run before consuming any notifications.
     backfill_job: Option<StreamBackfillJob<E, P, Chain<E::Primitives>>>,
+    /// The canonical chains from the WAL to replay after the backfill job and before consuming
+    /// any notifications.
+    wal_chains: VecDeque<Arc<Chain<E::Primitives>>>,
 }
 
 impl<P, E> ExExNotificationsWithHead<P, E>
@@ -294,6 +385,7 @@ where
             pending_check_canonical: true,
             pending_check_backfill: true,
             backfill_job: None,
+            wal_chains: VecDeque::new(),
         }
     }
 }
@@ -350,21 +442,32 @@ where
     /// canonical chain.
     ///
     /// Possible situations are:
-    /// - ExEx is behind the node head (`node_head.number < exex_head.number`). Backfill from the
-    ///   node database.
+    /// - ExEx is behind the node head (`node_head.number < exex_head.number`). Replay the canonical
+    ///   chains from the WAL, and backfill the blocks below them from the node database.
     /// - ExEx is at the same block number as the node head (`node_head.number ==
     ///   exex_head.number`). Nothing to do.
     fn check_backfill(&mut self) -> eyre::Result<()> {
-        let backfill_job_factory =
-            BackfillJobFactory::new(self.executor.clone(), self.provider.clone());
         match self.exex_head.block.number.cmp(&self.node_head.number) {
             std::cmp::Ordering::Less => {
-                // ExEx is behind the node head, start backfill
-                debug!(target: "exex::notifications", "ExEx is behind the node head and on the canonical chain, starting backfill");
-                let backfill = backfill_job_factory
-                    .backfill(self.exex_head.block.number + 1..=self.node_head.number)
-                    .into_stream();
-                self.backfill_job = Some(backfill);
+                let wal_chains = self.canonical_wal_chains()?;
+                let backfill_to = wal_chains
+                    .front()
+                    .map_or(self.node_head.number, |chain| chain.first().number() - 1);
+
+                if self.exex_head.block.number < backfill_to {
+                    // ExEx is behind the WAL, start backfill
+                    debug!(target: "exex::notifications", to_block = backfill_to, "ExEx is behind the node head and on the canonical chain, starting backfill");
+                    let backfill =
+                        BackfillJobFactory::new(self.executor.clone(), self.provider.clone())
+                            .backfill(self.exex_head.block.number + 1..=backfill_to)
+                            .into_stream();
+                    self.backfill_job = Some(backfill);
+                }
+
+                if !wal_chains.is_empty() {
+                    debug!(target: "exex::notifications", chains = wal_chains.len(), from_block = backfill_to + 1, "Replaying canonical chains from the WAL");
+                }
+                self.wal_chains = wal_chains;
             }
             std::cmp::Ordering::Equal => {
                 debug!(target: "exex::notifications", "ExEx is at the node head");
@@ -376,6 +479,48 @@ where
 
         Ok(())
     }
+
+    /// Returns the committed chains from the WAL that form the canonical chain up to the node
+    /// head, without gaps, in ascending order. The returned chains don't include any blocks at or
+    /// below the ExEx head.
+    fn canonical_wal_chains(&self) -> eyre::Result<VecDeque<Arc<Chain<E::Primitives>>>> {
+        let exex_head = self.exex_head.block.number;
+        let mut chains = self.wal_handle.get_committed_chains_above(exex_head)?;
+
+        // Walk back from the node head, picking the latest committed chain ending at the current
+        // canonical block every time.
+        let mut canonical_chains = VecDeque::new();
+        let mut tip = self.node_head.number;
+        while tip > exex_head {
+            let Some(tip_hash) = self.provider.block_hash(tip)? else { break };
+            let Some(position) =
+                chains.iter().rposition(|chain| chain.tip().num_hash() == (tip, tip_hash).into())
+            else {
+                break
+            };
+
+            let chain = chains.remove(position);
+            tip = chain.first().number().saturating_sub(1);
+            canonical_chains.push_front(chain);
+        }
+
+        // The first chain can start at or below the ExEx head, so we need to cut the blocks that
+        // the ExEx has already processed.
+        if let Some(first) = canonical_chains.pop_front() {
+            if first.first().number() <= exex_head {
+                match Arc::unwrap_or_clone(first).split(ChainSplitTarget::Number(exex_head)) {
+                    ChainSplit::Split { pending, .. } | ChainSplit::NoSplitPending(pending) => {
+                        canonical_chains.push_front(Arc::new(pending))
+                    }
+                    ChainSplit::NoSplitCanonical(_) => {}
+                }
+            } else {
+                canonical_chains.push_front(first);
+            }
+        }
+
+        Ok(canonical_chains)
+    }
 }
 
 impl<P, E> Stream for ExExNotificationsWithHead<P, E>
@@ -418,6 +563,11 @@ where
             this.backfill_job = None;
         }
 
+        if let Some(chain) = this.wal_chains.pop_front() {
+            debug!(target: "exex::notifications", range = ?chain.range(), "Replaying a chain from the WAL");
+            return Poll::Ready(Some(Ok(ExExNotification::ChainCommitted { new: chain })))
+        }
+
         let Some(notification) = ready!(this.notifications.poll_recv(cx)) else {
             return Poll::Ready(None)
         };
@@ -447,7 +597,7 @@ mod tests {
     use reth_primitives::{Block, BlockExt};
     use reth_provider::{
         providers::BlockchainProvider2, test_utils::create_test_provider_factory, BlockWriter,
-        Chain, DatabaseProviderFactory, StorageLocation,
+        Chain, DatabaseProviderFactory, ExecutionOutcome, StorageLocation,
     };
     use reth_testing_utils::generators::{self, random_block, BlockParams};
     use tokio::sync::mpsc;
@@ -536,6 +686,184 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn exex_notifications_behind_head_wal_replay() -> eyre::Result<()> {
+        let mut rng = generators::rng();
+
+        let temp_dir = tempfile::tempdir().unwrap();
+        let wal = Wal::new(temp_dir.path()).unwrap();
+
+        let provider_factory = create_test_provider_factory();
+        let genesis_hash = init_genesis(&provider_factory)?;
+        let genesis_block = provider_factory
+            .block(genesis_hash.into())?
+            .ok_or_else(|| eyre::eyre!("genesis block not found"))?;
+
+        let provider = BlockchainProvider2::new(provider_factory.clone())?;
+
+        let block_1 = random_block(
+            &mut rng,
+            genesis_block.number + 1,
+            BlockParams { parent: Some(genesis_hash), tx_count: Some(0), ..Default::default() },
+        )
+        .seal_with_senders::<reth_primitives::Block>()
+        .ok_or_eyre("failed to recover senders")?;
+        let block_2 = random_block(
+            &mut rng,
+            block_1.number + 1,
+            BlockParams { parent: Some(block_1.hash()), tx_count: Some(0), ..Default::default() },
+        )
+        .seal_with_senders::<reth_primitives::Block>()
+        .ok_or_eyre("failed to recover senders")?;
+        let block_2_reorged = random_block(
+            &mut rng,
+            block_1.number + 1,
+            BlockParams { parent: Some(block_1.hash()), tx_count: Some(0), ..Default::default() },
+        )
+        .seal_with_senders::<reth_primitives::Block>()
+        .ok_or_eyre("failed to recover senders")?;
+
+        let provider_rw = provider_factory.provider_rw()?;
+        provider_rw.insert_block(block_1.clone(), StorageLocation::Database)?;
+        provider_rw.insert_block(block_2.clone(), StorageLocation::Database)?;
+        provider_rw.commit()?;
+
+        // The WAL contains the canonical block 2, and a block 2 that was reorged out later
+        let canonical_notification = ExExNotification::ChainCommitted {
+            new: Arc::new(Chain::new(vec![block_2.clone()], Default::default(), None)),
+        };
+        wal.commit(&canonical_notification)?;
+        wal.commit(&ExExNotification::ChainCommitted {
+            new: Arc::new(Chain::new(vec![block_2_reorged], Default::default(), None)),
+        })?;
+
+        let node_head = Head { number: block_2.number, hash: block_2.hash(), ..Default::default() };
+        let exex_head =
+            ExExHead { block: BlockNumHash { number: genesis_block.number, hash: genesis_hash } };
+
+        let notification = ExExNotification::ChainCommitted {
+            new: Arc::new(Chain::new(
+                vec![random_block(
+                    &mut rng,
+                    node_head.number + 1,
+                    BlockParams { parent: Some(node_head.hash), ..Default::default() },
+                )
+                .seal_with_senders()
+                .ok_or_eyre("failed to recover senders")?],
+                Default::default(),
+                None,
+            )),
+        };
+
+        let (notifications_tx, notifications_rx) = mpsc::channel(1);
+
+        notifications_tx.send(notification.clone()).await?;
+
+        let mut notifications = ExExNotificationsWithoutHead::new(
+            node_head,
+            provider,
+            EthExecutorProvider::mainnet(),
+            notifications_rx,
+            wal.handle(),
+        )
+        .with_head(exex_head);
+
+        // First notification is the backfill of the block below the WAL
+        assert_eq!(
+            notifications.next().await.transpose()?,
+            Some(ExExNotification::ChainCommitted {
+                new: Arc::new(
+                    BackfillJobFactory::new(
+                        notifications.executor.clone(),
+                        notifications.provider.clone()
+                    )
+                    .backfill(1..=1)
+                    .next()
+                    .ok_or_eyre("failed to backfill")??
+                )
+            })
+        );
+
+        // Second notification is the canonical chain replayed from the WAL
+        assert_eq!(notifications.next().await.transpose()?, Some(canonical_notification));
+
+        // Third notification is the actual notification that we sent before
+        assert_eq!(notifications.next().await.transpose()?, Some(notification));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn exex_notifications_since_wal_replay() -> eyre::Result<()> {
+        let mut rng = generators::rng();
+
+        let temp_dir = tempfile::tempdir().unwrap();
+        let wal = Wal::new(temp_dir.path()).unwrap();
+
+        let provider_factory = create_test_provider_factory();
+        let genesis_hash = init_genesis(&provider_factory)?;
+
+        let provider = BlockchainProvider2::new(provider_factory.clone())?;
+
+        let block_1 = random_block(
+            &mut rng,
+            1,
+            BlockParams { parent: Some(genesis_hash), tx_count: Some(0), ..Default::default() },
+        )
+        .seal_with_senders::<reth_primitives::Block>()
+        .ok_or_eyre("failed to recover senders")?;
+        let block_2 = random_block(
+            &mut rng,
+            2,
+            BlockParams { parent: Some(block_1.hash()), tx_count: Some(0), ..Default::default() },
+        )
+        .seal_with_senders::<reth_primitives::Block>()
+        .ok_or_eyre("failed to recover senders")?;
+
+        let provider_rw = provider_factory.provider_rw()?;
+        provider_rw.insert_block(block_1.clone(), StorageLocation::Database)?;
+        provider_rw.insert_block(block_2.clone(), StorageLocation::Database)?;
+        provider_rw.commit()?;
+
+        let execution_outcome = ExecutionOutcome {
+            first_block: 1,
+            receipts: (0..2).map(|_| Vec::new()).collect(),
+            ..Default::default()
+        };
+        wal.commit(&ExExNotification::ChainCommitted {
+            new: Arc::new(Chain::new(
+                vec![block_1, block_2.clone()],
+                execution_outcome.clone(),
+                None,
+            )),
+        })?;
+
+        let node_head = Head { number: block_2.number, hash: block_2.hash(), ..Default::default() };
+        let (notifications_tx, notifications_rx) = mpsc::channel(1);
+
+        let mut notifications = ExExNotifications::new(
+            node_head,
+            provider,
+            EthExecutorProvider::mainnet(),
+            notifications_rx,
+            wal.handle(),
+        )
+        .since(2)?;
+
+        // The block 1 that the ExEx doesn't need is cut from the WAL chain
+        assert_eq!(
+            notifications.next().await.transpose()?,
+            Some(ExExNotification::ChainCommitted {
+                new: Arc::new(Chain::new(vec![block_2], execution_outcome.split_at(2).1, None))
+            })
+        );
+
+        drop(notifications_tx);
+        assert_eq!(notifications.next().await.transpose()?, None);
+
+        Ok(())
+    }
+
     #[tokio::test]
     async fn exex_notifications_same_head_canonical() -> eyre::Result<()> {
         let temp_dir = tempfile::tempdir().unwrap();
@@ -1,6 +1,6 @@
 use std::{
     cmp::Reverse,
-    collections::{BinaryHeap, HashSet},
+    collections::{BTreeSet, BinaryHeap, HashSet},
 };
 
 use alloy_consensus::BlockHeader;
@@ -92,6 +92,19 @@ impl BlockCache {
         self.committed_blocks.get(block_hash).map(|entry| entry.0)
     }
 
+    /// Returns the sorted file IDs of the notifications that have committed blocks above the given
+    /// block number.
+    pub(super) fn get_file_ids_with_committed_blocks_above(
+        &self,
+        block_number: BlockNumber,
+    ) -> BTreeSet<u32> {
+        self.committed_blocks
+            .values()
+            .filter(|(_, block)| block.block.number > block_number)
+            .map(|(file_id, _)| *file_id)
+            .collect()
+    }
+
     /// Inserts the blocks from the notification into the cache with the given file ID.
     pub(super) fn insert_notification_blocks_with_file_id<N: NodePrimitives>(
         &mut self,
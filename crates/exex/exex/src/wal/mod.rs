@@ -10,6 +10,7 @@ mod metrics;
 use metrics::Metrics;
 
 use std::{
+    collections::BTreeMap,
     path::Path,
     sync::{
         atomic::{AtomicU32, Ordering},
@@ -18,9 +19,10 @@ use std::{
 };
 
 use alloy_eips::BlockNumHash;
-use alloy_primitives::B256;
+use alloy_primitives::{BlockNumber, B256};
 use parking_lot::{RwLock, RwLockReadGuard};
 use reth_exex_types::ExExNotification;
+use reth_provider::Chain;
 use reth_tracing::tracing::{debug, instrument};
 
 /// WAL is a write-ahead log (WAL) that stores the notifications sent to ExExes.
@@ -33,6 +35,9 @@ use reth_tracing::tracing::{debug, instrument};
 /// 1. On every new canonical chain notification, call [`Wal::commit`].
 /// 2. When the chain is finalized, call [`Wal::finalize`] to prevent the infinite growth of the
 ///    WAL.
+///
+/// The WAL also persists the last finished heights of the ExExes, so that they can resume from
+/// them after a restart. See [`Wal::set_finished_heights`].
 #[derive(Debug, Clone)]
 pub struct Wal<N: NodePrimitives = EthPrimitives> {
     inner: Arc<WalInner<N>>,
@@ -65,6 +70,15 @@ where
         self.inner.finalize(to_block)
     }
 
+    /// Persists the finished heights of the ExExes by their IDs, overwriting the previously
+    /// persisted heights of the same ExExes.
+    pub fn set_finished_heights(
+        &self,
+        finished_heights: impl IntoIterator<Item = (String, BlockNumHash)>,
+    ) -> eyre::Result<()> {
+        self.inner.set_finished_heights(finished_heights)
+    }
+
     /// Returns an iterator over all notifications in the WAL.
     pub fn iter_notifications(
         &self,
@@ -86,6 +100,8 @@ struct WalInner<N: NodePrimitives> {
     storage: Storage<N>,
     /// WAL block cache. See [`cache::BlockCache`] docs for more details.
     block_cache: RwLock<BlockCache>,
+    /// The last persisted finished heights of the ExExes by their IDs.
+    finished_heights: RwLock<BTreeMap<String, BlockNumHash>>,
     metrics: Metrics,
 }
 
@@ -94,10 +110,13 @@ where
     N: NodePrimitives,
 {
     fn new(directory: impl AsRef<Path>) -> eyre::Result<Self> {
+        let storage = Storage::new(directory)?;
+        let finished_heights = storage.read_finished_heights()?;
         let mut wal = Self {
             next_file_id: AtomicU32::new(0),
-            storage: Storage::new(directory)?,
+            storage,
             block_cache: RwLock::new(BlockCache::default()),
+            finished_heights: RwLock::new(finished_heights),
             metrics: Metrics::default(),
         };
         wal.fill_block_cache()?;
@@ -178,6 +197,26 @@ where
         Ok(())
     }
 
+    #[instrument(skip_all)]
+    fn set_finished_heights(
+        &self,
+        finished_heights: impl IntoIterator<Item = (String, BlockNumHash)>,
+    ) -> eyre::Result<()> {
+        let mut persisted_heights = self.finished_heights.write();
+
+        let mut updated = persisted_heights.clone();
+        updated.extend(finished_heights);
+        if updated == *persisted_heights {
+            return Ok(())
+        }
+
+        self.storage.write_finished_heights(&updated)?;
+        debug!(target: "exex::wal", finished_heights = ?updated, "Finished heights were persisted");
+        *persisted_heights = updated;
+
+        Ok(())
+    }
+
     fn update_metrics(&self, block_cache: &BlockCache, size_delta: i64) {
         self.metrics.size_bytes.increment(size_delta as f64);
         self.metrics.notifications_count.set(block_cache.notification_max_blocks.len() as f64);
@@ -229,6 +268,34 @@ where
             .read_notification(file_id)
             .map(|entry| entry.map(|(notification, _)| notification))
     }
+
+    /// Returns the committed chains of all notifications that have committed blocks above the
+    /// given block number, in the order they were committed.
+    ///
+    /// The chains are not guaranteed to be canonical, and can overlap with each other in case of
+    /// reorgs.
+    pub fn get_committed_chains_above(
+        &self,
+        block_number: BlockNumber,
+    ) -> eyre::Result<Vec<Arc<Chain<N>>>> {
+        let file_ids =
+            self.wal.block_cache().get_file_ids_with_committed_blocks_above(block_number);
+
+        let mut chains = Vec::with_capacity(file_ids.len());
+        for file_id in file_ids {
+            let Some((notification, _)) = self.wal.storage.read_notification(file_id)? else {
+                continue
+            };
+            chains.extend(notification.committed_chain());
+        }
+
+        Ok(chains)
+    }
+
+    /// Returns the last persisted finished height of the ExEx with the given ID.
+    pub fn finished_height(&self, exex_id: &str) -> Option<BlockNumHash> {
+        self.wal.finished_heights.read().get(exex_id).copied()
+    }
 }
 
 #[cfg(test)]
@@ -1,9 +1,11 @@
 use std::{
+    collections::BTreeMap,
     fs::File,
     ops::RangeInclusive,
     path::{Path, PathBuf},
 };
 
+use alloy_eips::BlockNumHash;
 use eyre::OptionExt;
 use reth_exex_types::ExExNotification;
 use reth_node_api::NodePrimitives;
@@ -13,6 +15,8 @@ use tracing::instrument;
 
 static FILE_EXTENSION: &str = "wal";
 
+static FINISHED_HEIGHTS_FILE_NAME: &str = "finished_heights.msgpack";
+
 /// The underlying WAL storage backed by a directory of files.
 ///
 /// Each notification is represented by a single file that contains a MessagePack-encoded
@@ -174,12 +178,47 @@ where
 
         Ok(file_path.metadata()?.len())
     }
+
+    /// Reads the finished heights of the ExExes by their IDs.
+    #[instrument(skip(self))]
+    pub(super) fn read_finished_heights(&self) -> eyre::Result<BTreeMap<String, BlockNumHash>> {
+        let file_path = self.path.join(FINISHED_HEIGHTS_FILE_NAME);
+        debug!(target: "exex::wal::storage", ?file_path, "Reading finished heights from WAL");
+
+        let mut file = match File::open(&file_path) {
+            Ok(file) => file,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(BTreeMap::new()),
+            Err(err) => return Err(reth_fs_util::FsPathError::open(err, &file_path).into()),
+        };
+
+        rmp_serde::decode::from_read(&mut file).map_err(|err| {
+            eyre::eyre!("failed to decode finished heights from {file_path:?}: {err:?}")
+        })
+    }
+
+    /// Writes the finished heights of the ExExes by their IDs.
+    #[instrument(skip(self, finished_heights))]
+    pub(super) fn write_finished_heights(
+        &self,
+        finished_heights: &BTreeMap<String, BlockNumHash>,
+    ) -> eyre::Result<()> {
+        let file_path = self.path.join(FINISHED_HEIGHTS_FILE_NAME);
+        debug!(target: "exex::wal::storage", ?file_path, "Writing finished heights to WAL");
+
+        reth_fs_util::atomic_write_file(&file_path, |file| {
+            rmp_serde::encode::write(file, finished_heights)
+        })?;
+
+        Ok(())
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use std::{fs::File, sync::Arc};
+    use std::{collections::BTreeMap, fs::File, sync::Arc};
 
+    use alloy_eips::BlockNumHash;
+    use alloy_primitives::B256;
     use eyre::OptionExt;
     use reth_exex_types::ExExNotification;
     use reth_provider::Chain;
@@ -237,4 +276,23 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_finished_heights_roundtrip() -> eyre::Result<()> {
+        let temp_dir = tempfile::tempdir()?;
+        let storage: Storage = Storage::new(&temp_dir)?;
+        assert!(storage.read_finished_heights()?.is_empty());
+
+        let finished_heights = BTreeMap::from([
+            ("exex-1".to_string(), BlockNumHash::new(1, B256::random())),
+            ("exex-2".to_string(), BlockNumHash::new(2, B256::random())),
+        ]);
+        storage.write_finished_heights(&finished_heights)?;
+        assert_eq!(storage.read_finished_heights()?, finished_heights);
+
+        // The finished heights are not a notification file
+        assert_eq!(storage.files_range()?, None);
+
+        Ok(())
+    }
 }
@@ -0,0 +1,91 @@
+use crate::{
+    protocol::{
+        client_framed, decode_server_message, encode, ClientMessage, FrameReader, FrameWriter,
+        ServerMessage, PROTOCOL_VERSION,
+    },
+    RemoteExExError,
+};
+use alloy_eips::BlockNumHash;
+use futures::{SinkExt, Stream, StreamExt};
+use interprocess::local_socket::{
+    tokio::prelude::LocalSocketStream, traits::tokio::Stream as _, GenericFilePath, ToFsName,
+};
+use reth_exex_types::ExExNotification;
+use reth_primitives::EthPrimitives;
+use reth_primitives_traits::NodePrimitives;
+use std::{
+    marker::PhantomData,
+    pin::Pin,
+    task::{ready, Context, Poll},
+};
+
+/// A client of the [`RemoteExEx`](crate::RemoteExEx).
+///
+/// The client is a stream of [`ExExNotification`]s. The processed blocks must be reported with
+/// [`RemoteExExClient::send_finished_height`], otherwise the node will not prune them.
+#[derive(Debug)]
+pub struct RemoteExExClient<N: NodePrimitives = EthPrimitives> {
+    reader: FrameReader<LocalSocketStream>,
+    writer: FrameWriter<LocalSocketStream>,
+    _primitives: PhantomData<N>,
+}
+
+impl<N: NodePrimitives> RemoteExExClient<N> {
+    /// Connects to the server listening at the given endpoint, and subscribes to the notifications
+    /// for all blocks after the given head.
+    ///
+    /// If the head is `None`, the server sends the notifications for all blocks after the last
+    /// finished height sent by the client, or only the new notifications if there's none.
+    pub async fn connect(
+        endpoint: &str,
+        head: Option<BlockNumHash>,
+    ) -> Result<Self, RemoteExExError> {
+        let name = endpoint.to_fs_name::<GenericFilePath>()?;
+        let (mut reader, mut writer) = client_framed(LocalSocketStream::connect(name).await?);
+
+        writer.send(encode(&ClientMessage::Subscribe { version: PROTOCOL_VERSION, head })?).await?;
+
+        // The server closes the connection if another client is already connected
+        let frame = reader.next().await.ok_or(RemoteExExError::ConnectionClosed)??;
+        match decode_server_message::<N>(&frame)? {
+            ServerMessage::Subscribed { version } if version == PROTOCOL_VERSION => {}
+            ServerMessage::Subscribed { version } => {
+                return Err(RemoteExExError::UnsupportedVersion(version))
+            }
+            ServerMessage::Notification(_) => {
+                return Err(RemoteExExError::UnexpectedMessage("notification before subscription"))
+            }
+        }
+
+        Ok(Self { reader, writer, _primitives: PhantomData })
+    }
+
+    /// Sends the highest block processed by the client to the server. The node is allowed to
+    /// prune all blocks up to it.
+    pub async fn send_finished_height(
+        &mut self,
+        height: BlockNumHash,
+    ) -> Result<(), RemoteExExError> {
+        self.writer.send(encode(&ClientMessage::FinishedHeight(height))?).await?;
+        Ok(())
+    }
+}
+
+impl<N: NodePrimitives> Stream for RemoteExExClient<N> {
+    type Item = Result<ExExNotification<N>, RemoteExExError>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+
+        let Some(frame) = ready!(this.reader.poll_next_unpin(cx)) else { return Poll::Ready(None) };
+        let notification =
+            frame.map_err(Into::into).and_then(|frame| match decode_server_message::<N>(&frame)? {
+                ServerMessage::Notification(notification) => Ok(notification.into()),
+                ServerMessage::Subscribed { .. } => {
+                    Err(RemoteExExError::UnexpectedMessage("subscription after subscription"))
+                }
+            });
+
+        Poll::Ready(Some(notification))
+    }
+}
@@ -0,0 +1,21 @@
+use crate::protocol::PROTOCOL_VERSION;
+
+/// Errors of the remote `ExEx` transport.
+#[derive(Debug, thiserror::Error)]
+pub enum RemoteExExError {
+    /// Error while reading from or writing to the socket.
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+    /// Error while encoding or decoding a message.
+    #[error("failed to encode or decode a message: {0}")]
+    Codec(#[from] bincode::Error),
+    /// The other side of the connection uses an unsupported protocol version.
+    #[error("unsupported protocol version {0}, expected {PROTOCOL_VERSION}")]
+    UnsupportedVersion(u32),
+    /// A message was received that is not valid in the current state of the connection.
+    #[error("unexpected message: {0}")]
+    UnexpectedMessage(&'static str),
+    /// The connection was closed by the other side.
+    #[error("connection closed")]
+    ConnectionClosed,
+}
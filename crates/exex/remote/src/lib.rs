@@ -0,0 +1,33 @@
+//! Transport for running execution extensions (`ExEx`) out of process.
+//!
+//! The node side is the [`RemoteExEx`], an `ExEx` that listens on a local socket (a Unix domain
+//! socket, or a named pipe on Windows) and forwards every [`ExExNotification`] to the connected
+//! [`RemoteExExClient`]. The client sends back the finished heights, which are forwarded to the
+//! node as `ExExEvent::FinishedHeight` events, so the node keeps its pruning guarantees for the
+//! remote `ExEx` as well.
+//!
+//! The wire schema is described in the [`protocol`] module. The node side requires the `server`
+//! feature, which is enabled by default. Clients can disable the default features to avoid
+//! depending on the node crates.
+//!
+//! [`ExExNotification`]: reth_exex_types::ExExNotification
+#![doc(
+    html_logo_url = "https://raw.githubusercontent.com/paradigmxyz/reth/main/assets/reth-docs.png",
+    html_favicon_url = "https://avatars0.githubusercontent.com/u/97369466?s=256",
+    issue_tracker_base_url = "https://github.com/paradigmxyz/reth/issues/"
+)]
+#![cfg_attr(not(test), warn(unused_crate_dependencies))]
+#![cfg_attr(docsrs, feature(doc_cfg, doc_auto_cfg))]
+
+mod client;
+pub use client::RemoteExExClient;
+
+mod error;
+pub use error::RemoteExExError;
+
+pub mod protocol;
+
+#[cfg(feature = "server")]
+mod server;
+#[cfg(feature = "server")]
+pub use server::RemoteExEx;
@@ -0,0 +1,173 @@
+//! Wire schema of the remote `ExEx` transport.
+//!
+//! Every message is a frame of a big-endian `u32` length prefix, followed by the message encoded
+//! with `bincode`. Notifications are encoded with the bincode-compatible
+//! [`serde_bincode_compat::ExExNotification`] type. The frames sent by the client are limited to
+//! [`MAX_CLIENT_FRAME_LENGTH`], and the ones sent by the server to [`MAX_SERVER_FRAME_LENGTH`].
+//!
+//! A connection goes as follows:
+//! 1. The client connects and sends [`ClientMessage::Subscribe`] with its [`PROTOCOL_VERSION`] and
+//!    head.
+//! 2. The server replies with [`ServerMessage::Subscribed`] with its [`PROTOCOL_VERSION`], and
+//!    closes the connection if the versions don't match.
+//! 3. The server sends [`ServerMessage::Notification`] for every notification, while the client
+//!    sends [`ClientMessage::FinishedHeight`] whenever it has processed a block.
+//!
+//! Only one client can be connected to the server at a time, the connections of other clients are
+//! closed before the subscription is answered. Any change to the messages below must bump the
+//! [`PROTOCOL_VERSION`].
+
+use crate::RemoteExExError;
+use alloy_eips::BlockNumHash;
+use bytes::Bytes;
+use reth_exex_types::serde_bincode_compat;
+use reth_primitives::EthPrimitives;
+use reth_primitives_traits::NodePrimitives;
+use serde::{Deserialize, Serialize};
+use tokio::io::{AsyncRead, AsyncWrite, ReadHalf, WriteHalf};
+use tokio_util::codec::{FramedRead, FramedWrite, LengthDelimitedCodec};
+
+/// The version of the wire protocol.
+pub const PROTOCOL_VERSION: u32 = 1;
+
+/// The maximum length of a frame sent by the client. Client messages have a fixed small size.
+pub const MAX_CLIENT_FRAME_LENGTH: usize = 1024;
+
+/// The maximum length of a frame sent by the server. A single notification can contain many blocks
+/// with their state changes, so the frames are only limited by the `u32` length prefix.
+pub const MAX_SERVER_FRAME_LENGTH: usize = u32::MAX as usize;
+
+/// The reading half of a connection, yielding the received frames.
+pub type FrameReader<S> = FramedRead<ReadHalf<S>, LengthDelimitedCodec>;
+
+/// The writing half of a connection, accepting the frames to send.
+pub type FrameWriter<S> = FramedWrite<WriteHalf<S>, LengthDelimitedCodec>;
+
+/// A message sent by the client to the server.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ClientMessage {
+    /// Subscribes to the notifications. It's the first message sent by the client.
+    Subscribe {
+        /// The protocol version of the client.
+        version: u32,
+        /// The latest block that the client has fully processed. The server will send the
+        /// notifications for all blocks after it.
+        ///
+        /// If `None`, the server sends the notifications for all blocks after the last finished
+        /// height sent by the client, or only the new notifications if there's none.
+        head: Option<BlockNumHash>,
+    },
+    /// The highest block processed by the client. The node is allowed to prune all blocks up to
+    /// it, see `ExExEvent::FinishedHeight`.
+    FinishedHeight(BlockNumHash),
+}
+
+/// A message sent by the server to the client.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(bound = "")]
+pub enum ServerMessage<'a, N: NodePrimitives = EthPrimitives> {
+    /// The subscription was accepted.
+    Subscribed {
+        /// The protocol version of the server.
+        version: u32,
+    },
+    /// A notification of the node.
+    Notification(#[serde(borrow)] serde_bincode_compat::ExExNotification<'a, N>),
+}
+
+/// Creates the codec used to frame the messages, with the given maximum frame length.
+pub fn codec(max_frame_length: usize) -> LengthDelimitedCodec {
+    LengthDelimitedCodec::builder().max_frame_length(max_frame_length).new_codec()
+}
+
+/// Splits the server side of a connection into a reader of client frames and a writer of server
+/// frames.
+pub fn server_framed<S: AsyncRead + AsyncWrite>(stream: S) -> (FrameReader<S>, FrameWriter<S>) {
+    let (reader, writer) = tokio::io::split(stream);
+    (
+        FramedRead::new(reader, codec(MAX_CLIENT_FRAME_LENGTH)),
+        FramedWrite::new(writer, codec(MAX_SERVER_FRAME_LENGTH)),
+    )
+}
+
+/// Splits the client side of a connection into a reader of server frames and a writer of client
+/// frames.
+pub fn client_framed<S: AsyncRead + AsyncWrite>(stream: S) -> (FrameReader<S>, FrameWriter<S>) {
+    let (reader, writer) = tokio::io::split(stream);
+    (
+        FramedRead::new(reader, codec(MAX_SERVER_FRAME_LENGTH)),
+        FramedWrite::new(writer, codec(MAX_CLIENT_FRAME_LENGTH)),
+    )
+}
+
+/// Encodes the message into a frame.
+pub fn encode<T: Serialize>(message: &T) -> Result<Bytes, RemoteExExError> {
+    Ok(bincode::serialize(message)?.into())
+}
+
+/// Decodes a [`ClientMessage`] from a frame.
+pub fn decode_client_message(frame: &[u8]) -> Result<ClientMessage, RemoteExExError> {
+    Ok(bincode::deserialize(frame)?)
+}
+
+/// Decodes a [`ServerMessage`] from a frame.
+pub fn decode_server_message<N: NodePrimitives>(
+    frame: &[u8],
+) -> Result<ServerMessage<'_, N>, RemoteExExError> {
+    Ok(bincode::deserialize(frame)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::B256;
+    use futures::{SinkExt, StreamExt};
+    use tokio::io::AsyncWriteExt;
+
+    #[test]
+    fn client_message_roundtrip() {
+        for message in [
+            ClientMessage::Subscribe { version: PROTOCOL_VERSION, head: None },
+            ClientMessage::Subscribe {
+                version: PROTOCOL_VERSION,
+                head: Some(BlockNumHash::new(1, B256::random())),
+            },
+            ClientMessage::FinishedHeight(BlockNumHash::new(2, B256::random())),
+        ] {
+            assert_eq!(decode_client_message(&encode(&message).unwrap()).unwrap(), message);
+        }
+    }
+
+    #[test]
+    fn server_message_roundtrip() {
+        let frame =
+            encode::<ServerMessage<'_>>(&ServerMessage::Subscribed { version: PROTOCOL_VERSION })
+                .unwrap();
+        assert!(matches!(
+            decode_server_message::<EthPrimitives>(&frame).unwrap(),
+            ServerMessage::Subscribed { version: PROTOCOL_VERSION }
+        ));
+    }
+
+    #[tokio::test]
+    async fn client_frame_length_is_limited() {
+        let (client, server) = tokio::io::duplex(4 * MAX_CLIENT_FRAME_LENGTH);
+        let (_client_reader, mut client_writer) = client_framed(client);
+        let (mut server_reader, _server_writer) = server_framed(server);
+
+        let message = ClientMessage::Subscribe { version: PROTOCOL_VERSION, head: None };
+        client_writer.send(encode(&message).unwrap()).await.unwrap();
+        let frame = server_reader.next().await.unwrap().unwrap();
+        assert_eq!(decode_client_message(&frame).unwrap(), message);
+
+        // The client codec doesn't encode frames over the limit
+        let oversized = Bytes::from(vec![0; MAX_CLIENT_FRAME_LENGTH + 1]);
+        assert!(client_writer.send(oversized.clone()).await.is_err());
+
+        // The server codec doesn't decode them either
+        let mut stream = client_writer.into_inner();
+        stream.write_u32(oversized.len() as u32).await.unwrap();
+        stream.write_all(&oversized).await.unwrap();
+        assert!(server_reader.next().await.unwrap().is_err());
+    }
+}
@@ -0,0 +1,206 @@
+use crate::{
+    protocol::{
+        decode_client_message, encode, server_framed, ClientMessage, ServerMessage,
+        PROTOCOL_VERSION,
+    },
+    RemoteExExError,
+};
+use alloy_eips::BlockNumHash;
+use futures::{SinkExt, StreamExt, TryStreamExt};
+use interprocess::local_socket::{
+    tokio::prelude::{LocalSocketListener, LocalSocketStream},
+    traits::tokio::Listener,
+    GenericFilePath, ListenerOptions, ToFsName,
+};
+use reth_exex::{ExExContext, ExExEvent, ExExHead, ExExNotificationsStream};
+use reth_exex_types::serde_bincode_compat;
+use reth_node_api::{FullNodeComponents, NodeTypes};
+use reth_primitives_traits::NodePrimitives;
+use std::fmt::Debug;
+use tracing::{debug, info, warn};
+
+/// An `ExEx` that serves the notifications to a [`RemoteExExClient`](crate::RemoteExExClient) in
+/// another process over a local socket.
+///
+/// The finished heights sent by the client are forwarded to the node. When the client
+/// reconnects without a head, the notifications are resumed after the last finished height,
+/// including the one persisted by the node before a restart.
+///
+/// Only one client is served at a time, because the node tracks a single finished height per
+/// `ExEx`. While a client is connected, the connections of other clients are closed right away, so
+/// their [`RemoteExExClient::connect`](crate::RemoteExExClient::connect) fails. Install one
+/// [`RemoteExEx`] with its own endpoint per remote process.
+///
+/// The `ExEx` can be installed with `NodeBuilder::install_exex`:
+/// ```ignore
+/// builder.install_exex("remote", |ctx| async move {
+///     Ok(RemoteExEx::new(ctx, "/tmp/reth-exex.ipc").run())
+/// })
+/// ```
+pub struct RemoteExEx<Node: FullNodeComponents> {
+    ctx: ExExContext<Node>,
+    endpoint: String,
+    /// The last finished height sent by the client.
+    finished_height: Option<BlockNumHash>,
+}
+
+impl<Node: FullNodeComponents> Debug for RemoteExEx<Node> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RemoteExEx")
+            .field("endpoint", &self.endpoint)
+            .field("finished_height", &self.finished_height)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<Node> RemoteExEx<Node>
+where
+    Node: FullNodeComponents,
+    Node::Types: NodeTypes<Primitives: NodePrimitives>,
+{
+    /// Creates a new [`RemoteExEx`] that will listen at the given endpoint.
+    pub fn new(ctx: ExExContext<Node>, endpoint: impl Into<String>) -> Self {
+        let finished_height = ctx.notifications.finished_height();
+        Self { ctx, endpoint: endpoint.into(), finished_height }
+    }
+
+    /// Listens at the endpoint and serves the notifications to one client at a time, until the
+    /// node shuts down.
+    pub async fn run(mut self) -> eyre::Result<()> {
+        let name = self.endpoint.as_str().to_fs_name::<GenericFilePath>()?;
+        let listener = ListenerOptions::new().name(name).create_tokio()?;
+        info!(target: "exex::remote", endpoint = %self.endpoint, "Listening for a remote ExEx");
+
+        loop {
+            let stream = listener.accept().await?;
+            debug!(target: "exex::remote", "Remote ExEx connected");
+
+            match self.serve(&listener, stream).await {
+                Ok(true) => {
+                    debug!(target: "exex::remote", "Remote ExEx disconnected");
+                }
+                // The notifications stream has ended, the node is shutting down
+                Ok(false) => return Ok(()),
+                Err(err) => {
+                    warn!(target: "exex::remote", %err, "Remote ExEx connection failed");
+                }
+            }
+        }
+    }
+
+    /// Serves the notifications to the connected client, and closes the connections of other
+    /// clients in the meantime.
+    ///
+    /// Returns `true` if the client has disconnected, and `false` if the notifications stream has
+    /// ended.
+    async fn serve(
+        &mut self,
+        listener: &LocalSocketListener,
+        stream: LocalSocketStream,
+    ) -> eyre::Result<bool> {
+        let (mut reader, mut writer) = server_framed(stream);
+
+        let Some(frame) = reader.next().await.transpose()? else { return Ok(true) };
+        let ClientMessage::Subscribe { version, head } = decode_client_message(&frame)? else {
+            return Err(
+                RemoteExExError::UnexpectedMessage("finished height before subscription").into()
+            )
+        };
+        writer
+            .send(encode::<ServerMessage<'_, <Node::Types as NodeTypes>::Primitives>>(
+                &ServerMessage::Subscribed { version: PROTOCOL_VERSION },
+            )?)
+            .await?;
+        if version != PROTOCOL_VERSION {
+            return Err(RemoteExExError::UnsupportedVersion(version).into())
+        }
+
+        match head.or(self.finished_height) {
+            Some(block) => self.ctx.set_notifications_with_head(ExExHead { block }),
+            None => self.ctx.set_notifications_without_head(),
+        }
+        debug!(target: "exex::remote", ?head, finished_height = ?self.finished_height, "Remote ExEx subscribed");
+
+        loop {
+            tokio::select! {
+                notification = self.ctx.notifications.try_next() => {
+                    let Some(notification) = notification? else { return Ok(false) };
+                    let message = ServerMessage::Notification(
+                        serde_bincode_compat::ExExNotification::from(&notification),
+                    );
+                    writer.send(encode(&message)?).await?;
+                }
+                frame = reader.next() => {
+                    let Some(frame) = frame.transpose()? else { return Ok(true) };
+                    match decode_client_message(&frame)? {
+                        ClientMessage::FinishedHeight(height) => {
+                            self.finished_height = Some(height);
+                            self.ctx.events.send(ExExEvent::FinishedHeight(height))?;
+                        }
+                        ClientMessage::Subscribe { .. } => {
+                            return Err(RemoteExExError::UnexpectedMessage(
+                                "subscription after subscription",
+                            )
+                            .into())
+                        }
+                    }
+                }
+                stream = listener.accept() => {
+                    drop(stream?);
+                    warn!(target: "exex::remote", "Rejected a remote ExEx, another one is already connected");
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::RemoteExExClient;
+    use reth_exex::ExExNotification;
+    use reth_primitives::EthPrimitives;
+    use reth_provider::{Chain, ExecutionOutcome};
+    use std::{sync::Arc, time::Duration};
+
+    #[tokio::test]
+    async fn serve_notifications() -> eyre::Result<()> {
+        let (ctx, mut handle) = reth_exex_test_utils::test_exex_context().await?;
+
+        let temp_dir = tempfile::tempdir()?;
+        let endpoint = temp_dir.path().join("exex.ipc").to_string_lossy().into_owned();
+        let server = tokio::spawn(RemoteExEx::new(ctx, endpoint.clone()).run());
+
+        // Wait for the server to start listening
+        let mut client = loop {
+            match RemoteExExClient::connect(&endpoint, None).await {
+                Ok(client) => break client,
+                Err(_) => tokio::time::sleep(Duration::from_millis(10)).await,
+            }
+        };
+
+        let chain = Chain::from_block(handle.genesis.clone(), ExecutionOutcome::default(), None);
+        handle.send_notification_chain_committed(chain.clone()).await?;
+        assert_eq!(
+            client.next().await.transpose()?,
+            Some(ExExNotification::ChainCommitted { new: Arc::new(chain.clone()) })
+        );
+
+        // The finished height is forwarded to the node
+        client.send_finished_height(handle.genesis.num_hash()).await?;
+        let event = handle.events_rx.recv().await;
+        assert_eq!(event, Some(ExExEvent::FinishedHeight(handle.genesis.num_hash())));
+
+        // Other clients are rejected while a client is connected
+        assert!(RemoteExExClient::<EthPrimitives>::connect(&endpoint, None).await.is_err());
+        handle.send_notification_chain_committed(chain.clone()).await?;
+        assert_eq!(
+            client.next().await.transpose()?,
+            Some(ExExNotification::ChainCommitted { new: Arc::new(chain) })
+        );
+
+        server.abort();
+
+        Ok(())
+    }
+}
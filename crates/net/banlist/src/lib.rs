@@ -112,6 +112,12 @@ impl BanList {
         self.banned_peers.contains_key(peer_id)
     }
 
+    /// Returns the timestamp until which the peer is banned, if it is banned temporarily.
+    #[inline]
+    pub fn peer_banned_until(&self, peer_id: &PeerId) -> Option<Instant> {
+        self.banned_peers.get(peer_id).copied().flatten()
+    }
+
     /// Unbans the ip address
     pub fn unban_ip(&mut self, ip: &IpAddr) {
         self.banned_ips.remove(ip);
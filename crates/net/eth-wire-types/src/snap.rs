@@ -0,0 +1,392 @@
+//! Types for the snap wire protocol: <https://github.com/ethereum/devp2p/blob/master/caps/snap.md>
+//!
+//! Unlike `eth` requests, every `snap/1` message carries its request id as the first field of the
+//! message list instead of wrapping the payload into a
+//! [`RequestPair`](crate::message::RequestPair).
+
+use alloy_consensus::{constants::KECCAK_EMPTY, EMPTY_ROOT_HASH};
+use alloy_primitives::{Bytes, B256, U256};
+use alloy_rlp::{
+    Decodable, Encodable, RlpDecodable, RlpDecodableWrapper, RlpEncodable, RlpEncodableWrapper,
+};
+use bytes::{Buf, BufMut, BytesMut};
+use reth_codecs_derive::add_arbitrary_tests;
+use reth_primitives_traits::Account;
+
+/// Requests an unknown number of accounts from a given account trie, starting at the specified
+/// account hash and capped by the maximum allowed response size in bytes.
+#[derive(Clone, Debug, PartialEq, Eq, RlpEncodable, RlpDecodable, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(any(test, feature = "arbitrary"), derive(arbitrary::Arbitrary))]
+#[add_arbitrary_tests(rlp)]
+pub struct GetAccountRange {
+    /// Request id used to match up the response.
+    pub request_id: u64,
+    /// Root hash of the account trie to serve.
+    pub root_hash: B256,
+    /// Account hash of the first account to retrieve.
+    pub starting_hash: B256,
+    /// Account hash after which to stop serving data.
+    pub limit_hash: B256,
+    /// Soft limit at which to stop returning data.
+    pub response_bytes: u64,
+}
+
+/// The slim RLP representation of an account used by the snap protocol.
+///
+/// The storage root and code hash are encoded as empty byte strings if the account has no storage
+/// or no code respectively.
+#[derive(Clone, Debug, PartialEq, Eq, RlpEncodable, RlpDecodable, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(any(test, feature = "arbitrary"), derive(arbitrary::Arbitrary))]
+#[add_arbitrary_tests(rlp)]
+pub struct SlimAccount {
+    /// Account nonce.
+    pub nonce: u64,
+    /// Account balance.
+    pub balance: U256,
+    /// Storage root of the account, empty if the account has no storage.
+    pub storage_root: Bytes,
+    /// Hash of the account bytecode, empty if the account has no code.
+    pub code_hash: Bytes,
+}
+
+impl SlimAccount {
+    /// Creates the slim representation of the given account with the given storage root.
+    pub fn new(account: Account, storage_root: B256) -> Self {
+        let storage_root = if storage_root == EMPTY_ROOT_HASH {
+            Bytes::new()
+        } else {
+            Bytes::copy_from_slice(storage_root.as_slice())
+        };
+        let code_hash = match account.bytecode_hash {
+            Some(code_hash) if code_hash != KECCAK_EMPTY => {
+                Bytes::copy_from_slice(code_hash.as_slice())
+            }
+            _ => Bytes::new(),
+        };
+        Self { nonce: account.nonce, balance: account.balance, storage_root, code_hash }
+    }
+}
+
+/// A single account in an [`AccountRange`] response.
+#[derive(Clone, Debug, PartialEq, Eq, RlpEncodable, RlpDecodable, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(any(test, feature = "arbitrary"), derive(arbitrary::Arbitrary))]
+#[add_arbitrary_tests(rlp)]
+pub struct AccountData {
+    /// Hash of the account address.
+    pub hash: B256,
+    /// Slim representation of the account.
+    pub body: SlimAccount,
+}
+
+/// The response to [`GetAccountRange`], containing consecutive accounts and the merkle proofs for
+/// the boundaries of the range.
+#[derive(Clone, Debug, PartialEq, Eq, RlpEncodable, RlpDecodable, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(any(test, feature = "arbitrary"), derive(arbitrary::Arbitrary))]
+#[add_arbitrary_tests(rlp)]
+pub struct AccountRange {
+    /// Id of the request this is a response for.
+    pub request_id: u64,
+    /// List of consecutive accounts from the trie.
+    pub accounts: Vec<AccountData>,
+    /// List of trie nodes proving the account range.
+    pub proof: Vec<Bytes>,
+}
+
+/// Requests the storage slots of multiple accounts' storage tries.
+///
+/// The origin and limit only apply to the first and last account respectively, the storage of
+/// every other account is requested in full.
+#[derive(Clone, Debug, PartialEq, Eq, RlpEncodable, RlpDecodable, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(any(test, feature = "arbitrary"), derive(arbitrary::Arbitrary))]
+#[add_arbitrary_tests(rlp)]
+pub struct GetStorageRanges {
+    /// Request id used to match up the response.
+    pub request_id: u64,
+    /// Root hash of the account trie to serve.
+    pub root_hash: B256,
+    /// Account hashes of the storage tries to serve.
+    pub account_hashes: Vec<B256>,
+    /// Storage slot hash of the first slot to retrieve, empty to start at the beginning.
+    pub starting_hash: Bytes,
+    /// Storage slot hash after which to stop serving, empty to serve until the end.
+    pub limit_hash: Bytes,
+    /// Soft limit at which to stop returning data.
+    pub response_bytes: u64,
+}
+
+/// A single storage slot in a [`StorageRanges`] response.
+#[derive(Clone, Debug, PartialEq, Eq, RlpEncodable, RlpDecodable, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(any(test, feature = "arbitrary"), derive(arbitrary::Arbitrary))]
+#[add_arbitrary_tests(rlp)]
+pub struct StorageData {
+    /// Hash of the storage slot key.
+    pub hash: B256,
+    /// RLP encoded storage value.
+    pub data: Bytes,
+}
+
+/// The response to [`GetStorageRanges`], containing consecutive storage slots of the requested
+/// accounts and optionally the merkle proof of the last, partially served range.
+#[derive(Clone, Debug, PartialEq, Eq, RlpEncodable, RlpDecodable, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(any(test, feature = "arbitrary"), derive(arbitrary::Arbitrary))]
+#[add_arbitrary_tests(rlp)]
+pub struct StorageRanges {
+    /// Id of the request this is a response for.
+    pub request_id: u64,
+    /// List of storage slots for every served account.
+    pub slots: Vec<Vec<StorageData>>,
+    /// List of trie nodes proving the last storage range, if it is incomplete.
+    pub proof: Vec<Bytes>,
+}
+
+/// Requests a number of contract bytecodes by hash.
+#[derive(Clone, Debug, PartialEq, Eq, RlpEncodable, RlpDecodable, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(any(test, feature = "arbitrary"), derive(arbitrary::Arbitrary))]
+#[add_arbitrary_tests(rlp)]
+pub struct GetByteCodes {
+    /// Request id used to match up the response.
+    pub request_id: u64,
+    /// Code hashes to retrieve the code for.
+    pub hashes: Vec<B256>,
+    /// Soft limit at which to stop returning data.
+    pub response_bytes: u64,
+}
+
+/// The response to [`GetByteCodes`], containing the requested bytecodes in request order.
+#[derive(Clone, Debug, PartialEq, Eq, RlpEncodable, RlpDecodable, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(any(test, feature = "arbitrary"), derive(arbitrary::Arbitrary))]
+#[add_arbitrary_tests(rlp)]
+pub struct ByteCodes {
+    /// Id of the request this is a response for.
+    pub request_id: u64,
+    /// The requested bytecodes.
+    pub codes: Vec<Bytes>,
+}
+
+/// A set of trie node paths of a [`GetTrieNodes`] request.
+///
+/// The first path is the compact encoded path of a node in the account trie. If more paths follow,
+/// the first path is the full account hash and the remaining paths are the compact encoded paths
+/// of nodes in the storage trie of that account.
+#[derive(Clone, Debug, PartialEq, Eq, RlpEncodableWrapper, RlpDecodableWrapper, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(any(test, feature = "arbitrary"), derive(arbitrary::Arbitrary))]
+#[add_arbitrary_tests(rlp)]
+pub struct TrieNodePathSet(pub Vec<Bytes>);
+
+/// Requests a number of state trie nodes by path.
+#[derive(Clone, Debug, PartialEq, Eq, RlpEncodable, RlpDecodable, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(any(test, feature = "arbitrary"), derive(arbitrary::Arbitrary))]
+#[add_arbitrary_tests(rlp)]
+pub struct GetTrieNodes {
+    /// Request id used to match up the response.
+    pub request_id: u64,
+    /// Root hash of the account trie to serve.
+    pub root_hash: B256,
+    /// Trie node paths to retrieve the nodes for, grouped by storage trie.
+    pub paths: Vec<TrieNodePathSet>,
+    /// Soft limit at which to stop returning data.
+    pub response_bytes: u64,
+}
+
+/// The response to [`GetTrieNodes`], containing the requested trie nodes in request order.
+#[derive(Clone, Debug, PartialEq, Eq, RlpEncodable, RlpDecodable, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(any(test, feature = "arbitrary"), derive(arbitrary::Arbitrary))]
+#[add_arbitrary_tests(rlp)]
+pub struct TrieNodes {
+    /// Id of the request this is a response for.
+    pub request_id: u64,
+    /// The requested trie nodes.
+    pub nodes: Vec<Bytes>,
+}
+
+/// Represents message IDs for snap protocol messages.
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SnapMessageId {
+    /// Requests a range of accounts.
+    GetAccountRange = 0x00,
+    /// Represents a range of accounts.
+    AccountRange = 0x01,
+    /// Requests ranges of storage slots.
+    GetStorageRanges = 0x02,
+    /// Represents ranges of storage slots.
+    StorageRanges = 0x03,
+    /// Requests contract bytecodes.
+    GetByteCodes = 0x04,
+    /// Represents contract bytecodes.
+    ByteCodes = 0x05,
+    /// Requests trie nodes.
+    GetTrieNodes = 0x06,
+    /// Represents trie nodes.
+    TrieNodes = 0x07,
+}
+
+impl SnapMessageId {
+    /// Returns the max value.
+    pub const fn max() -> u8 {
+        Self::TrieNodes as u8
+    }
+}
+
+impl TryFrom<u8> for SnapMessageId {
+    type Error = u8;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            0x00 => Ok(Self::GetAccountRange),
+            0x01 => Ok(Self::AccountRange),
+            0x02 => Ok(Self::GetStorageRanges),
+            0x03 => Ok(Self::StorageRanges),
+            0x04 => Ok(Self::GetByteCodes),
+            0x05 => Ok(Self::ByteCodes),
+            0x06 => Ok(Self::GetTrieNodes),
+            0x07 => Ok(Self::TrieNodes),
+            _ => Err(value),
+        }
+    }
+}
+
+/// Represents a message in the snap wire protocol, version 1.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SnapMessage {
+    /// Represents a `GetAccountRange` request.
+    GetAccountRange(GetAccountRange),
+    /// Represents an `AccountRange` response.
+    AccountRange(AccountRange),
+    /// Represents a `GetStorageRanges` request.
+    GetStorageRanges(GetStorageRanges),
+    /// Represents a `StorageRanges` response.
+    StorageRanges(StorageRanges),
+    /// Represents a `GetByteCodes` request.
+    GetByteCodes(GetByteCodes),
+    /// Represents a `ByteCodes` response.
+    ByteCodes(ByteCodes),
+    /// Represents a `GetTrieNodes` request.
+    GetTrieNodes(GetTrieNodes),
+    /// Represents a `TrieNodes` response.
+    TrieNodes(TrieNodes),
+}
+
+impl SnapMessage {
+    /// Returns the message's ID.
+    pub const fn message_id(&self) -> SnapMessageId {
+        match self {
+            Self::GetAccountRange(_) => SnapMessageId::GetAccountRange,
+            Self::AccountRange(_) => SnapMessageId::AccountRange,
+            Self::GetStorageRanges(_) => SnapMessageId::GetStorageRanges,
+            Self::StorageRanges(_) => SnapMessageId::StorageRanges,
+            Self::GetByteCodes(_) => SnapMessageId::GetByteCodes,
+            Self::ByteCodes(_) => SnapMessageId::ByteCodes,
+            Self::GetTrieNodes(_) => SnapMessageId::GetTrieNodes,
+            Self::TrieNodes(_) => SnapMessageId::TrieNodes,
+        }
+    }
+
+    /// Returns the request id of the message.
+    pub const fn request_id(&self) -> u64 {
+        match self {
+            Self::GetAccountRange(msg) => msg.request_id,
+            Self::AccountRange(msg) => msg.request_id,
+            Self::GetStorageRanges(msg) => msg.request_id,
+            Self::StorageRanges(msg) => msg.request_id,
+            Self::GetByteCodes(msg) => msg.request_id,
+            Self::ByteCodes(msg) => msg.request_id,
+            Self::GetTrieNodes(msg) => msg.request_id,
+            Self::TrieNodes(msg) => msg.request_id,
+        }
+    }
+
+    /// Encodes the message, prefixed with its message ID relative to the `snap` capability.
+    pub fn encoded(&self) -> BytesMut {
+        let mut buf = BytesMut::new();
+        buf.put_u8(self.message_id() as u8);
+        match self {
+            Self::GetAccountRange(msg) => msg.encode(&mut buf),
+            Self::AccountRange(msg) => msg.encode(&mut buf),
+            Self::GetStorageRanges(msg) => msg.encode(&mut buf),
+            Self::StorageRanges(msg) => msg.encode(&mut buf),
+            Self::GetByteCodes(msg) => msg.encode(&mut buf),
+            Self::ByteCodes(msg) => msg.encode(&mut buf),
+            Self::GetTrieNodes(msg) => msg.encode(&mut buf),
+            Self::TrieNodes(msg) => msg.encode(&mut buf),
+        }
+        buf
+    }
+
+    /// Decodes a message that is prefixed with its message ID relative to the `snap` capability.
+    pub fn decode_message(buf: &mut &[u8]) -> alloy_rlp::Result<Self> {
+        if buf.is_empty() {
+            return Err(alloy_rlp::Error::InputTooShort)
+        }
+        let id = SnapMessageId::try_from(buf[0])
+            .map_err(|_| alloy_rlp::Error::Custom("unknown snap message id"))?;
+        buf.advance(1);
+
+        let message = match id {
+            SnapMessageId::GetAccountRange => Self::GetAccountRange(Decodable::decode(buf)?),
+            SnapMessageId::AccountRange => Self::AccountRange(Decodable::decode(buf)?),
+            SnapMessageId::GetStorageRanges => Self::GetStorageRanges(Decodable::decode(buf)?),
+            SnapMessageId::StorageRanges => Self::StorageRanges(Decodable::decode(buf)?),
+            SnapMessageId::GetByteCodes => Self::GetByteCodes(Decodable::decode(buf)?),
+            SnapMessageId::ByteCodes => Self::ByteCodes(Decodable::decode(buf)?),
+            SnapMessageId::GetTrieNodes => Self::GetTrieNodes(Decodable::decode(buf)?),
+            SnapMessageId::TrieNodes => Self::TrieNodes(Decodable::decode(buf)?),
+        };
+        Ok(message)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::{b256, hex};
+
+    #[test]
+    fn slim_account_empty_storage_and_code() {
+        let account = Account { nonce: 1, balance: U256::from(2), bytecode_hash: None };
+        let slim = SlimAccount::new(account, EMPTY_ROOT_HASH);
+        assert!(slim.storage_root.is_empty());
+        assert!(slim.code_hash.is_empty());
+
+        let mut buf = Vec::new();
+        slim.encode(&mut buf);
+        assert_eq!(buf, hex!("c401028080"));
+    }
+
+    #[test]
+    fn roundtrip_snap_message() {
+        let message = SnapMessage::GetAccountRange(GetAccountRange {
+            request_id: 1111,
+            root_hash: b256!("1111111111111111111111111111111111111111111111111111111111111111"),
+            starting_hash: B256::ZERO,
+            limit_hash: B256::repeat_byte(0xff),
+            response_bytes: 512 * 1024,
+        });
+        let encoded = message.encoded();
+        assert_eq!(encoded[0], SnapMessageId::GetAccountRange as u8);
+
+        let decoded = SnapMessage::decode_message(&mut &encoded[..]).unwrap();
+        assert_eq!(decoded, message);
+        assert_eq!(decoded.request_id(), 1111);
+    }
+
+    #[test]
+    fn decode_unknown_snap_message_id() {
+        let buf = [SnapMessageId::max() + 1, 0xc0];
+        assert!(SnapMessage::decode_message(&mut &buf[..]).is_err());
+    }
+}
@@ -27,6 +27,6 @@ pub use peers::{
     kind::PeerKind,
     reputation::{is_banned_reputation, ReputationChangeOutcome, DEFAULT_REPUTATION},
     state::PeerConnectionState,
-    ConnectionsConfig, Peer, PeersConfig,
+    ConnectionsConfig, Peer, PeerResponseStats, PeersConfig, PersistedPeer,
 };
 pub use session::{SessionLimits, SessionsConfig};
@@ -11,7 +11,7 @@ use reth_net_banlist::BanList;
 use reth_network_peers::{NodeRecord, TrustedPeer};
 use tracing::info;
 
-use crate::{BackoffKind, ReputationChangeWeights};
+use crate::{BackoffKind, PersistedPeer, ReputationChangeWeights};
 
 /// Maximum number of available slots for outbound sessions.
 pub const DEFAULT_MAX_COUNT_PEERS_OUTBOUND: u32 = 100;
@@ -144,6 +144,9 @@ pub struct PeersConfig {
     /// Basic nodes to connect to.
     #[cfg_attr(feature = "serde", serde(skip))]
     pub basic_nodes: HashSet<NodeRecord>,
+    /// The state of peers observed in previous runs, restored on startup.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub persisted_peers: Vec<PersistedPeer>,
     /// How long to ban bad peers.
     #[cfg_attr(feature = "serde", serde(with = "humantime_serde"))]
     pub ban_duration: Duration,
@@ -178,6 +181,7 @@ impl Default for PeersConfig {
             trusted_nodes: Default::default(),
             trusted_nodes_only: false,
             basic_nodes: Default::default(),
+            persisted_peers: Default::default(),
             max_backoff_count: 5,
             incoming_ip_throttle_duration: INBOUND_IP_THROTTLE_DURATION,
         }
@@ -249,6 +253,12 @@ impl PeersConfig {
         self
     }
 
+    /// The state of peers observed in previous runs, restored on startup.
+    pub fn with_persisted_peers(mut self, peers: Vec<PersistedPeer>) -> Self {
+        self.persisted_peers = peers;
+        self
+    }
+
     /// Configures the max allowed backoff count.
     pub const fn with_max_backoff_count(mut self, max_backoff_count: u8) -> Self {
         self.max_backoff_count = max_backoff_count;
@@ -276,6 +286,9 @@ impl PeersConfig {
     }
 
     /// Read from file nodes available at launch. Ignored if None.
+    ///
+    /// With the `serde` feature, the observed state of the peers written alongside them is
+    /// restored as well, see [`PersistedPeer`].
     pub fn with_basic_nodes_from_file(
         self,
         optional_file: Option<impl AsRef<Path>>,
@@ -287,8 +300,17 @@ impl PeersConfig {
             Err(e) => Err(e)?,
         };
         info!(target: "net::peers", file = %file_path.as_ref().display(), "Loading saved peers");
-        let nodes: HashSet<NodeRecord> = serde_json::from_reader(reader)?;
-        Ok(self.with_basic_nodes(nodes))
+        #[cfg(feature = "serde")]
+        {
+            let peers = crate::peers::persisted::read_persisted_peers(reader)?;
+            let nodes = peers.iter().map(|peer| peer.record).collect();
+            Ok(self.with_basic_nodes(nodes).with_persisted_peers(peers))
+        }
+        #[cfg(not(feature = "serde"))]
+        {
+            let nodes: HashSet<NodeRecord> = serde_json::from_reader(reader)?;
+            Ok(self.with_basic_nodes(nodes))
+        }
     }
 
     /// Returns settings for testing
@@ -1,12 +1,16 @@
 pub mod addr;
 pub mod config;
 pub mod kind;
+pub mod persisted;
 pub mod reputation;
 pub mod state;
 
 pub use config::{ConnectionsConfig, PeersConfig};
+pub use persisted::PersistedPeer;
 pub use reputation::{Reputation, ReputationChange, ReputationChangeKind, ReputationChangeWeights};
 
+use std::{sync::Arc, time::SystemTime};
+
 use reth_ethereum_forks::ForkId;
 use tracing::trace;
 
@@ -35,6 +39,12 @@ pub struct Peer {
     /// Counts number of times the peer was backed off due to a severe
     /// [`BackoffKind`](crate::BackoffKind).
     pub severe_backoff_counter: u8,
+    /// When the peer was last seen in an active session.
+    pub last_seen: Option<SystemTime>,
+    /// The client version the peer reported in its last session.
+    pub client_version: Option<Arc<str>>,
+    /// The responses the peer sent to our requests.
+    pub responses: PeerResponseStats,
 }
 
 // === impl Peer ===
@@ -66,6 +76,9 @@ impl Peer {
             kind: Default::default(),
             backed_off: false,
             severe_backoff_counter: 0,
+            last_seen: None,
+            client_version: None,
+            responses: PeerResponseStats::default(),
         }
     }
 
@@ -136,3 +149,37 @@ impl Peer {
         matches!(self.kind, PeerKind::Static)
     }
 }
+
+/// Counts the responses a peer sent to our requests.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct PeerResponseStats {
+    /// Number of responses that contained the requested data.
+    pub useful: u64,
+    /// Number of responses that were empty, invalid or failed.
+    pub useless: u64,
+}
+
+impl PeerResponseStats {
+    /// Records a response.
+    pub const fn record(&mut self, useful: bool) {
+        if useful {
+            self.useful = self.useful.saturating_add(1);
+        } else {
+            self.useless = self.useless.saturating_add(1);
+        }
+    }
+
+    /// Returns the share of useful responses, if there were any responses.
+    pub fn useful_rate(&self) -> Option<f64> {
+        let total = self.useful.saturating_add(self.useless);
+        (total > 0).then(|| self.useful as f64 / total as f64)
+    }
+
+    /// Returns the number of useful responses in excess of the useless ones, which is used to
+    /// prefer historically good peers when dialing.
+    pub const fn score(&self) -> i64 {
+        (self.useful as i64).saturating_sub(self.useless as i64)
+    }
+}
@@ -0,0 +1,116 @@
+//! State of a peer that is persisted across restarts.
+
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use reth_network_peers::NodeRecord;
+
+use crate::{PeerResponseStats, DEFAULT_REPUTATION};
+
+/// A known peer and what was observed about it, as written to the persisted peers file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct PersistedPeer {
+    /// Where to reach the peer.
+    pub record: NodeRecord,
+    /// Reputation of the peer.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub reputation: i32,
+    /// Unix timestamp in seconds until which the peer is banned, if it is.
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Option::is_none"))]
+    pub banned_until: Option<u64>,
+    /// Unix timestamp in seconds when the peer was last seen in an active session.
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Option::is_none"))]
+    pub last_seen: Option<u64>,
+    /// The client version the peer reported in its last session.
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Option::is_none"))]
+    pub client_version: Option<String>,
+    /// The responses the peer sent to our requests.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub responses: PeerResponseStats,
+}
+
+impl PersistedPeer {
+    /// Returns a new entry for a peer about which nothing was observed yet.
+    pub const fn new(record: NodeRecord) -> Self {
+        Self {
+            record,
+            reputation: DEFAULT_REPUTATION,
+            banned_until: None,
+            last_seen: None,
+            client_version: None,
+            responses: PeerResponseStats { useful: 0, useless: 0 },
+        }
+    }
+
+    /// Returns the remaining ban duration at the given time, if the peer is still banned.
+    pub fn remaining_ban(&self, now: SystemTime) -> Option<Duration> {
+        let banned_until = UNIX_EPOCH + Duration::from_secs(self.banned_until?);
+        banned_until.duration_since(now).ok().filter(|remaining| !remaining.is_zero())
+    }
+
+    /// Returns the time the peer was last seen, if ever.
+    pub fn last_seen_time(&self) -> Option<SystemTime> {
+        self.last_seen.map(|secs| UNIX_EPOCH + Duration::from_secs(secs))
+    }
+}
+
+impl From<NodeRecord> for PersistedPeer {
+    fn from(record: NodeRecord) -> Self {
+        Self::new(record)
+    }
+}
+
+/// Converts the time to a unix timestamp in seconds.
+pub fn unix_timestamp(time: SystemTime) -> u64 {
+    time.duration_since(UNIX_EPOCH).unwrap_or_default().as_secs()
+}
+
+/// An entry of the persisted peers file, which only contained [`NodeRecord`]s in earlier versions.
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+#[serde(untagged)]
+enum PersistedPeerEntry {
+    Record(NodeRecord),
+    Peer(PersistedPeer),
+}
+
+/// Reads the persisted peers, accepting both entries with observed state and plain
+/// [`NodeRecord`]s.
+#[cfg(feature = "serde")]
+pub fn read_persisted_peers(reader: impl std::io::Read) -> serde_json::Result<Vec<PersistedPeer>> {
+    let entries: Vec<PersistedPeerEntry> = serde_json::from_reader(reader)?;
+    Ok(entries
+        .into_iter()
+        .map(|entry| match entry {
+            PersistedPeerEntry::Record(record) => record.into(),
+            PersistedPeerEntry::Peer(peer) => peer,
+        })
+        .collect())
+}
+
+#[cfg(all(test, feature = "serde"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn read_records_and_peers() {
+        let record: NodeRecord = "enode://6f8a80d14311c39f35f516fa664deaaaa13e85b2f7493f37f6144d86991ec012937307647bd3b9a82abe2974e1407241d54947bbb39763a4cac9f77166ad92a0@10.3.58.6:30303?discport=30301".parse().unwrap();
+        let peer = PersistedPeer {
+            reputation: -1024,
+            banned_until: Some(1_700_000_000),
+            last_seen: Some(1_600_000_000),
+            client_version: Some("reth/v1.1.4".to_string()),
+            responses: PeerResponseStats { useful: 10, useless: 2 },
+            ..PersistedPeer::new(record)
+        };
+
+        let json = format!(r#"["{record}", {}]"#, serde_json::to_string(&peer).unwrap());
+        let peers = read_persisted_peers(json.as_bytes()).unwrap();
+        assert_eq!(peers, vec![PersistedPeer::new(record), peer.clone()]);
+
+        let now = UNIX_EPOCH + Duration::from_secs(1_699_999_000);
+        assert_eq!(peer.remaining_ban(now), Some(Duration::from_secs(1_000)));
+        assert_eq!(peer.remaining_ban(now + Duration::from_secs(1_000)), None);
+    }
+}
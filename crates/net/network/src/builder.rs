@@ -2,9 +2,11 @@
 
 use crate::{
     eth_requests::EthRequestHandler,
+    snap::{SnapProtocolHandler, SnapRequestHandler},
     transactions::{TransactionsManager, TransactionsManagerConfig},
     NetworkHandle, NetworkManager,
 };
+use reth_chain_state::CanonStateSubscriptions;
 use reth_eth_wire::{EthNetworkPrimitives, NetworkPrimitives};
 use reth_network_api::test_utils::PeersHandleProvider;
 use reth_transaction_pool::TransactionPool;
@@ -14,6 +16,10 @@ use tokio::sync::mpsc;
 /// 256 requests with malicious 10MB body requests is 2.6GB which can be absorbed by the node.
 pub(crate) const ETH_REQUEST_CHANNEL_CAPACITY: usize = 256;
 
+/// We set the max channel capacity of the `SnapRequestHandler` to 256, like the
+/// `EthRequestHandler`, responses are capped at 2MB each.
+pub(crate) const SNAP_REQUEST_CHANNEL_CAPACITY: usize = 256;
+
 /// A builder that can configure all components of the network.
 #[allow(missing_debug_implementations)]
 pub struct NetworkBuilder<Tx, Eth, N: NetworkPrimitives = EthNetworkPrimitives> {
@@ -66,6 +72,20 @@ impl<Tx, Eth, N: NetworkPrimitives> NetworkBuilder<Tx, Eth, N> {
         NetworkBuilder { network, request_handler, transactions }
     }
 
+    /// Creates a new [`SnapRequestHandler`] and announces the `snap/1` capability on all new
+    /// connections of the network.
+    ///
+    /// The returned handler serves the `snap` requests of all peers and must be spawned.
+    pub fn snap_request_handler<Client: CanonStateSubscriptions>(
+        &mut self,
+        client: Client,
+    ) -> SnapRequestHandler<Client> {
+        let (tx, rx) = mpsc::channel(SNAP_REQUEST_CHANNEL_CAPACITY);
+        self.network.add_rlpx_sub_protocol(SnapProtocolHandler::new(tx));
+        let peers = self.network.handle().peers_handle().clone();
+        SnapRequestHandler::new(client, peers, rx)
+    }
+
     /// Creates a new [`TransactionsManager`] and wires it to the network.
     pub fn transactions<Pool: TransactionPool>(
         self,
@@ -323,7 +323,7 @@ impl<N: NetworkPrimitives> NetworkConfigBuilder<N> {
     }
 
     /// Configures the transactions manager with the given config.
-    pub const fn transactions_manager_config(mut self, config: TransactionsManagerConfig) -> Self {
+    pub fn transactions_manager_config(mut self, config: TransactionsManagerConfig) -> Self {
         self.transactions_manager_config = config;
         self
     }
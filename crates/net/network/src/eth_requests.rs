@@ -6,6 +6,7 @@ use crate::{
 };
 use alloy_consensus::{BlockHeader, ReceiptWithBloom, TxReceipt};
 use alloy_eips::BlockHashOrNumber;
+use alloy_primitives::{BlockNumber, B256};
 use alloy_rlp::Encodable;
 use futures::StreamExt;
 use reth_eth_wire::{
@@ -143,6 +144,27 @@ where
         headers
     }
 
+    /// Returns the earliest block whose transactions and receipts are still available, if the
+    /// history of older blocks has been expired.
+    ///
+    /// This is read once per request, so that all blocks of a response are checked against the
+    /// same height, and no lookups are done if no history has been expired.
+    fn earliest_history_height(&self) -> Option<BlockNumber> {
+        self.client.earliest_history_height().ok().filter(|height| *height > 0)
+    }
+
+    /// Returns `true` if the transactions and receipts of the block with the given hash have been
+    /// expired, only its header is still available then.
+    ///
+    /// Responses end before the first expired block, so that the served bodies and receipts still
+    /// match the requested hashes in order.
+    fn is_history_expired(&self, hash: B256, earliest_history_height: BlockNumber) -> bool {
+        self.client
+            .block_number(hash)
+            .unwrap_or_default()
+            .is_some_and(|number| number < earliest_history_height)
+    }
+
     fn on_headers_request(
         &self,
         _peer_id: PeerId,
@@ -166,8 +188,13 @@ where
         let mut bodies = Vec::new();
 
         let mut total_bytes = 0;
+        let earliest_history_height = self.earliest_history_height();
 
         for hash in request.0 {
+            if earliest_history_height.is_some_and(|height| self.is_history_expired(hash, height)) {
+                break
+            }
+
             if let Some(block) = self.client.block_by_hash(hash).unwrap_or_default() {
                 let (_, body) = block.split();
                 total_bytes += body.length();
@@ -195,8 +222,13 @@ where
         let mut receipts = Vec::new();
 
         let mut total_bytes = 0;
+        let earliest_history_height = self.earliest_history_height();
 
         for hash in request.0 {
+            if earliest_history_height.is_some_and(|height| self.is_history_expired(hash, height)) {
+                break
+            }
+
             if let Some(receipts_by_block) =
                 self.client.receipts_by_block(BlockHashOrNumber::Hash(hash)).unwrap_or_default()
             {
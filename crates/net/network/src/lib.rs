@@ -129,6 +129,7 @@ pub mod import;
 pub mod message;
 pub mod peers;
 pub mod protocol;
+pub mod snap;
 pub mod transactions;
 
 mod budget;
@@ -49,7 +49,7 @@ use reth_network_api::{
     EthProtocolInfo, NetworkEvent, NetworkStatus, PeerInfo, PeerRequest,
 };
 use reth_network_peers::{NodeRecord, PeerId};
-use reth_network_types::ReputationChangeKind;
+use reth_network_types::{PersistedPeer, ReputationChangeKind};
 use reth_storage_api::BlockNumReader;
 use reth_tasks::shutdown::GracefulShutdown;
 use reth_tokio_util::EventSender;
@@ -387,9 +387,21 @@ impl<N: NetworkPrimitives> NetworkManager<N> {
         self.swarm.state().peers().handle()
     }
 
+    /// Returns the state of all peers in the peer set, as restored on startup from the persistent
+    /// peers file.
+    pub fn all_persisted_peers(&self) -> impl Iterator<Item = PersistedPeer> + '_ {
+        self.swarm.state().peers().iter_persisted_peers()
+    }
+
     /// Collect the peers from the [`NetworkManager`] and write them to the given
     /// `persistent_peers_file`.
+    ///
+    /// With the `serde` feature, the reputation, ban expiry, last-seen time, client version and
+    /// response stats of the peers are written as well.
     pub fn write_peers_to_file(&self, persistent_peers_file: &Path) -> Result<(), FsPathError> {
+        #[cfg(feature = "serde")]
+        let known_peers = self.all_persisted_peers().collect::<Vec<_>>();
+        #[cfg(not(feature = "serde"))]
         let known_peers = self.all_peers().collect::<Vec<_>>();
         persistent_peers_file.parent().map(fs::create_dir_all).transpose()?;
         reth_fs_util::write_json_file(persistent_peers_file, &known_peers)?;
@@ -739,8 +751,20 @@ impl<N: NetworkPrimitives> NetworkManager<N> {
                     self.swarm.state_mut().peers_mut().on_active_outgoing_established(peer_id);
                 }
 
+                self.swarm
+                    .state_mut()
+                    .peers_mut()
+                    .on_session_client_version(peer_id, client_version.clone());
+
                 self.update_active_connection_metrics();
 
+                let peer_kind = self
+                    .swarm
+                    .state()
+                    .peers()
+                    .peer_by_id(peer_id)
+                    .map(|(_, kind)| kind)
+                    .unwrap_or_default();
                 let session_info = SessionInfo {
                     peer_id,
                     remote_addr,
@@ -748,6 +772,8 @@ impl<N: NetworkPrimitives> NetworkManager<N> {
                     capabilities,
                     status,
                     version,
+                    direction,
+                    peer_kind,
                 };
 
                 self.event_sender
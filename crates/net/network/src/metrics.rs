@@ -331,6 +331,31 @@ pub struct EthRequestHandlerMetrics {
     pub(crate) acc_duration_poll_eth_req_handler: Gauge,
 }
 
+/// Metrics for the `SnapRequestHandler`
+#[derive(Metrics)]
+#[metrics(scope = "network")]
+pub struct SnapRequestHandlerMetrics {
+    /// Number of `GetAccountRange` requests received
+    pub(crate) snap_account_range_requests_received_total: Counter,
+
+    /// Number of `GetStorageRanges` requests received
+    pub(crate) snap_storage_ranges_requests_received_total: Counter,
+
+    /// Number of `GetByteCodes` requests received
+    pub(crate) snap_bytecodes_requests_received_total: Counter,
+
+    /// Number of `GetTrieNodes` requests received
+    pub(crate) snap_trie_nodes_requests_received_total: Counter,
+
+    /// Number of requests that were answered with an empty response because the requested state
+    /// root is not served
+    pub(crate) snap_unavailable_root_requests_total: Counter,
+
+    /// Duration in seconds of call to poll
+    /// [`SnapRequestHandler`](crate::snap::SnapRequestHandler).
+    pub(crate) acc_duration_poll_snap_req_handler: Gauge,
+}
+
 /// Eth67 announcement metrics, track entries by `TxType`
 #[derive(Metrics)]
 #[metrics(scope = "network.transaction_fetcher")]
@@ -12,11 +12,13 @@ use reth_net_banlist::BanList;
 use reth_network_api::test_utils::{PeerCommand, PeersHandle};
 use reth_network_peers::{NodeRecord, PeerId};
 use reth_network_types::{
+    is_banned_reputation,
     peers::{
         config::PeerBackoffDurations,
+        persisted::unix_timestamp,
         reputation::{DEFAULT_REPUTATION, MAX_TRUSTED_PEER_REPUTATION_CHANGE},
     },
-    ConnectionsConfig, Peer, PeerAddr, PeerConnectionState, PeerKind, PeersConfig,
+    ConnectionsConfig, Peer, PeerAddr, PeerConnectionState, PeerKind, PeersConfig, PersistedPeer,
     ReputationChangeKind, ReputationChangeOutcome, ReputationChangeWeights,
 };
 use std::{
@@ -24,8 +26,9 @@ use std::{
     fmt::Display,
     io::{self},
     net::{IpAddr, SocketAddr},
+    sync::Arc,
     task::{Context, Poll},
-    time::Duration,
+    time::{Duration, SystemTime},
 };
 use thiserror::Error;
 use tokio::{
@@ -93,12 +96,13 @@ impl PeersManager {
             refill_slots_interval,
             connection_info,
             reputation_weights,
-            ban_list,
+            mut ban_list,
             ban_duration,
             backoff_durations,
             trusted_nodes,
             trusted_nodes_only,
             basic_nodes,
+            persisted_peers,
             max_backoff_count,
             incoming_ip_throttle_duration,
         } = config;
@@ -131,6 +135,26 @@ impl PeersManager {
             });
         }
 
+        // restore what was observed about the peers in previous runs
+        let system_now = SystemTime::now();
+        for persisted in persisted_peers {
+            let NodeRecord { address, tcp_port, udp_port, id } = persisted.record;
+            let peer = peers.entry(id).or_insert_with(|| {
+                Peer::new(PeerAddr::new_with_ports(address, tcp_port, Some(udp_port)))
+            });
+            peer.reputation = persisted.reputation;
+            peer.last_seen = persisted.last_seen_time();
+            peer.client_version = persisted.client_version.as_deref().map(Arc::from);
+            peer.responses = persisted.responses;
+
+            if let Some(remaining) = persisted.remaining_ban(system_now) {
+                ban_list.ban_peer_until(id, std::time::Instant::now() + remaining);
+            } else if peer.is_banned() {
+                // the ban expired while the node was offline
+                peer.unban();
+            }
+        }
+
         Self {
             peers,
             trusted_peer_ids,
@@ -176,6 +200,35 @@ impl PeersManager {
         })
     }
 
+    /// Returns the state of all peers that should be restored on the next startup.
+    pub(crate) fn iter_persisted_peers(&self) -> impl Iterator<Item = PersistedPeer> + '_ {
+        let (now, system_now) = (std::time::Instant::now(), SystemTime::now());
+        self.peers.iter().map(move |(peer_id, v)| {
+            let banned_until = self
+                .ban_list
+                .peer_banned_until(peer_id)
+                .map(|until| unix_timestamp(system_now + until.saturating_duration_since(now)));
+            PersistedPeer {
+                record: NodeRecord::new_with_ports(
+                    v.addr.tcp().ip(),
+                    v.addr.tcp().port(),
+                    v.addr.udp().map(|addr| addr.port()),
+                    *peer_id,
+                ),
+                // a banned reputation is only kept as long as the ban itself
+                reputation: if banned_until.is_none() && is_banned_reputation(v.reputation) {
+                    DEFAULT_REPUTATION
+                } else {
+                    v.reputation
+                },
+                banned_until,
+                last_seen: v.last_seen.map(unix_timestamp),
+                client_version: v.client_version.as_deref().map(String::from),
+                responses: v.responses,
+            }
+        })
+    }
+
     /// Returns the `NodeRecord` and `PeerKind` for the given peer id
     pub(crate) fn peer_by_id(&self, peer_id: PeerId) -> Option<(NodeRecord, PeerKind)> {
         self.peers.get(&peer_id).map(|v| {
@@ -346,6 +399,7 @@ impl PeersManager {
                 }
 
                 peer.state = PeerConnectionState::In;
+                peer.last_seen = Some(SystemTime::now());
 
                 is_trusted = is_trusted || peer.is_trusted();
             }
@@ -354,6 +408,7 @@ impl PeersManager {
                 // disconnect, because we only know the outgoing port
                 let mut peer = Peer::with_state(PeerAddr::from_tcp(addr), PeerConnectionState::In);
                 peer.remove_after_disconnect = true;
+                peer.last_seen = Some(SystemTime::now());
                 entry.insert(peer);
                 self.queued_actions.push_back(PeerAction::PeerAdded(peer_id));
             }
@@ -525,6 +580,7 @@ impl PeersManager {
                     // session to that peer
                     entry.get_mut().severe_backoff_counter = 0;
                     entry.get_mut().state = PeerConnectionState::Idle;
+                    entry.get_mut().last_seen = Some(SystemTime::now());
                     return
                 }
             }
@@ -540,6 +596,21 @@ impl PeersManager {
             self.connection_info.decr_state(peer.state);
             self.connection_info.inc_out();
             peer.state = PeerConnectionState::Out;
+            peer.last_seen = Some(SystemTime::now());
+        }
+    }
+
+    /// Called when the client version of a peer is known, after its session was established.
+    pub(crate) fn on_session_client_version(&mut self, peer_id: PeerId, client_version: Arc<str>) {
+        if let Some(peer) = self.peers.get_mut(&peer_id) {
+            peer.client_version = Some(client_version);
+        }
+    }
+
+    /// Called when a peer responded to a request, to track how useful its responses are.
+    pub(crate) fn on_peer_response(&mut self, peer_id: &PeerId, useful: bool) {
+        if let Some(peer) = self.peers.get_mut(peer_id) {
+            peer.responses.record(useful);
         }
     }
 
@@ -553,6 +624,9 @@ impl PeersManager {
         peer_id: &PeerId,
         err: &EthStreamError,
     ) {
+        if let Some(peer) = self.peers.get_mut(peer_id) {
+            peer.last_seen = Some(SystemTime::now());
+        }
         self.on_connection_failure(remote_addr, peer_id, err, ReputationChangeKind::Dropped)
     }
 
@@ -832,6 +906,9 @@ impl PeersManager {
 
     /// Returns the idle peer with the highest reputation.
     ///
+    /// Among peers with the same reputation, the ones that sent more useful responses to our
+    /// requests, including in previous runs, are preferred.
+    ///
     /// Peers that are `trusted` or `static`, see [`PeerKind`], are prioritized as long as they're
     /// not currently marked as banned or backed off.
     ///
@@ -860,8 +937,11 @@ impl PeersManager {
                 return Some((*maybe_better.0, maybe_better.1))
             }
 
-            // otherwise we keep track of the best peer using the reputation
-            if maybe_better.1.reputation > best_peer.1.reputation {
+            // otherwise we keep track of the best peer using the reputation, and prefer peers that
+            // were historically more useful
+            if (maybe_better.1.reputation, maybe_better.1.responses.score()) >
+                (best_peer.1.reputation, best_peer.1.responses.score())
+            {
                 best_peer = maybe_better;
             }
         }
@@ -1147,9 +1227,13 @@ mod tests {
     };
     use reth_net_banlist::BanList;
     use reth_network_api::Direction;
-    use reth_network_peers::{PeerId, TrustedPeer};
+    use reth_network_peers::{NodeRecord, PeerId, TrustedPeer};
     use reth_network_types::{
-        peers::reputation::DEFAULT_REPUTATION, BackoffKind, ReputationChangeKind,
+        peers::{
+            persisted::unix_timestamp,
+            reputation::{BANNED_REPUTATION, DEFAULT_REPUTATION},
+        },
+        BackoffKind, PeerResponseStats, PersistedPeer, ReputationChangeKind,
     };
     use std::{
         future::{poll_fn, Future},
@@ -1157,7 +1241,7 @@ mod tests {
         net::{IpAddr, Ipv4Addr, SocketAddr},
         pin::Pin,
         task::{Context, Poll},
-        time::Duration,
+        time::{Duration, SystemTime},
     };
     use url::Host;
 
@@ -2223,6 +2307,78 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn test_restore_persisted_peers() {
+        let record = |port| {
+            NodeRecord::new(
+                SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 1, 2)), port),
+                PeerId::random(),
+            )
+        };
+        let now = unix_timestamp(SystemTime::now());
+        let banned = PersistedPeer {
+            reputation: BANNED_REPUTATION - 1,
+            banned_until: Some(now + 3600),
+            ..PersistedPeer::new(record(8008))
+        };
+        let ban_expired = PersistedPeer {
+            reputation: BANNED_REPUTATION - 1,
+            banned_until: Some(now - 1),
+            ..PersistedPeer::new(record(8009))
+        };
+        let good = PersistedPeer {
+            last_seen: Some(now - 60),
+            client_version: Some("reth/v1.1.4".to_string()),
+            responses: PeerResponseStats { useful: 10, useless: 1 },
+            ..PersistedPeer::new(record(8010))
+        };
+
+        let config = PeersConfig::test().with_persisted_peers(vec![
+            banned.clone(),
+            ban_expired.clone(),
+            good.clone(),
+        ]);
+        let peers = PeersManager::new(config);
+
+        assert!(peers.ban_list.is_banned_peer(&banned.record.id));
+        assert!(!peers.ban_list.is_banned_peer(&ban_expired.record.id));
+        assert_eq!(peers.get_reputation(&ban_expired.record.id), Some(DEFAULT_REPUTATION));
+
+        let mut persisted = peers.iter_persisted_peers().collect::<Vec<_>>();
+        persisted.sort_by_key(|peer| peer.record.tcp_port);
+        // the ban expiry may be off by a second due to the conversion
+        assert!(persisted[0].banned_until.unwrap().abs_diff(now + 3600) <= 1);
+        assert_eq!(persisted[0].reputation, BANNED_REPUTATION - 1);
+        assert_eq!(persisted[1], PersistedPeer::new(ban_expired.record));
+        assert_eq!(persisted[2], good);
+    }
+
+    #[tokio::test]
+    async fn test_useful_peers_are_preferred() {
+        let config = PeersConfig::test().with_max_outbound(1);
+        let mut peers = PeersManager::new(config);
+
+        let useless_peer = PeerId::random();
+        let useful_peer = PeerId::random();
+        for (peer, port) in [(useless_peer, 8008), (useful_peer, 8009)] {
+            let socket_addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 1, 2)), port);
+            peers.add_peer(peer, PeerAddr::from_tcp(socket_addr), None);
+        }
+        peers.on_peer_response(&useful_peer, true);
+        peers.on_peer_response(&useless_peer, false);
+
+        loop {
+            match event!(peers) {
+                PeerAction::PeerAdded(_) => continue,
+                PeerAction::Connect { peer_id, .. } => {
+                    assert_eq!(peer_id, useful_peer);
+                    break
+                }
+                _ => unreachable!(),
+            }
+        }
+    }
+
     #[tokio::test]
     async fn test_connect_trusted_nodes_only() {
         let trusted_peer = PeerId::random();
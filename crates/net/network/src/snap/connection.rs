@@ -0,0 +1,200 @@
+//! The `snap` `RLPx` sub-protocol and its per-peer connections.
+
+use super::IncomingSnapRequest;
+use crate::protocol::{ConnectionHandler, OnNotSupported, ProtocolHandler};
+use alloy_primitives::bytes::BytesMut;
+use futures::{future::BoxFuture, stream::FuturesUnordered, FutureExt, Stream, StreamExt};
+use reth_eth_wire::{
+    capability::SharedCapabilities, multiplex::ProtocolConnection, protocol::Protocol, SnapMessage,
+};
+use reth_network_api::{Direction, PeerId};
+use reth_network_p2p::error::RequestResult;
+use std::{
+    fmt,
+    net::SocketAddr,
+    pin::Pin,
+    task::{ready, Context, Poll},
+};
+use tokio::sync::{mpsc, oneshot};
+use tracing::{debug, trace};
+
+/// The [`ProtocolHandler`] that announces the `snap/1` capability and forwards the requests of all
+/// `snap` connections to the [`SnapRequestHandler`](super::SnapRequestHandler).
+#[derive(Debug, Clone)]
+pub struct SnapProtocolHandler {
+    /// Sender half of the request channel of the request handler.
+    to_request_handler: mpsc::Sender<IncomingSnapRequest>,
+}
+
+impl SnapProtocolHandler {
+    /// Creates a new protocol handler that forwards all requests to the given channel.
+    pub const fn new(to_request_handler: mpsc::Sender<IncomingSnapRequest>) -> Self {
+        Self { to_request_handler }
+    }
+}
+
+impl ProtocolHandler for SnapProtocolHandler {
+    type ConnectionHandler = SnapConnectionHandler;
+
+    fn on_incoming(&self, _socket_addr: SocketAddr) -> Option<Self::ConnectionHandler> {
+        Some(SnapConnectionHandler { to_request_handler: self.to_request_handler.clone() })
+    }
+
+    fn on_outgoing(
+        &self,
+        _socket_addr: SocketAddr,
+        _peer_id: PeerId,
+    ) -> Option<Self::ConnectionHandler> {
+        Some(SnapConnectionHandler { to_request_handler: self.to_request_handler.clone() })
+    }
+}
+
+/// The [`ConnectionHandler`] of a single `snap` connection.
+#[derive(Debug)]
+pub struct SnapConnectionHandler {
+    to_request_handler: mpsc::Sender<IncomingSnapRequest>,
+}
+
+impl ConnectionHandler for SnapConnectionHandler {
+    type Connection = SnapConnection;
+
+    fn protocol(&self) -> Protocol {
+        Protocol::snap_1()
+    }
+
+    fn on_unsupported_by_peer(
+        self,
+        _supported: &SharedCapabilities,
+        _direction: Direction,
+        _peer_id: PeerId,
+    ) -> OnNotSupported {
+        // `snap` is optional, the `eth` session is still useful
+        OnNotSupported::KeepAlive
+    }
+
+    fn into_connection(
+        self,
+        _direction: Direction,
+        peer_id: PeerId,
+        conn: ProtocolConnection,
+    ) -> Self::Connection {
+        SnapConnection {
+            peer_id,
+            conn,
+            to_request_handler: self.to_request_handler,
+            pending_responses: FuturesUnordered::new(),
+        }
+    }
+}
+
+/// A `snap` connection with a single peer.
+///
+/// Decodes the requests of the peer, delegates them to the request handler and yields the encoded
+/// responses. The connection is closed if the peer sends a message that can't be decoded.
+pub struct SnapConnection {
+    /// The peer this connection belongs to.
+    peer_id: PeerId,
+    /// The raw messages received from the peer.
+    conn: ProtocolConnection,
+    /// Sender half of the request channel of the request handler.
+    to_request_handler: mpsc::Sender<IncomingSnapRequest>,
+    /// Responses that are still being served by the request handler.
+    pending_responses: FuturesUnordered<BoxFuture<'static, Option<SnapMessage>>>,
+}
+
+impl SnapConnection {
+    /// Delegates a request of the peer to the request handler.
+    fn on_message(&mut self, msg: SnapMessage) {
+        let peer_id = self.peer_id;
+        let id = msg.message_id();
+        let (request, response) = match msg {
+            SnapMessage::GetAccountRange(request) => {
+                let (tx, rx) = oneshot::channel();
+                (
+                    IncomingSnapRequest::GetAccountRange { peer_id, request, response: tx },
+                    into_response(rx, SnapMessage::AccountRange),
+                )
+            }
+            SnapMessage::GetStorageRanges(request) => {
+                let (tx, rx) = oneshot::channel();
+                (
+                    IncomingSnapRequest::GetStorageRanges { peer_id, request, response: tx },
+                    into_response(rx, SnapMessage::StorageRanges),
+                )
+            }
+            SnapMessage::GetByteCodes(request) => {
+                let (tx, rx) = oneshot::channel();
+                (
+                    IncomingSnapRequest::GetByteCodes { peer_id, request, response: tx },
+                    into_response(rx, SnapMessage::ByteCodes),
+                )
+            }
+            SnapMessage::GetTrieNodes(request) => {
+                let (tx, rx) = oneshot::channel();
+                (
+                    IncomingSnapRequest::GetTrieNodes { peer_id, request, response: tx },
+                    into_response(rx, SnapMessage::TrieNodes),
+                )
+            }
+            SnapMessage::AccountRange(_) |
+            SnapMessage::StorageRanges(_) |
+            SnapMessage::ByteCodes(_) |
+            SnapMessage::TrieNodes(_) => {
+                // we never send requests, so there's nothing to match the response to
+                trace!(target: "net::snap", %peer_id, ?id, "Ignoring unrequested snap response");
+                return
+            }
+        };
+
+        if self.to_request_handler.try_send(request).is_ok() {
+            self.pending_responses.push(response);
+        } else {
+            // the request handler is saturated, the peer will time out the request
+            trace!(target: "net::snap", %peer_id, "Dropping snap request, request handler is busy");
+        }
+    }
+}
+
+impl Stream for SnapConnection {
+    type Item = BytesMut;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+
+        loop {
+            if let Poll::Ready(Some(response)) = this.pending_responses.poll_next_unpin(cx) {
+                if let Some(response) = response {
+                    return Poll::Ready(Some(response.encoded()))
+                }
+                continue
+            }
+
+            let Some(msg) = ready!(this.conn.poll_next_unpin(cx)) else { return Poll::Ready(None) };
+
+            match SnapMessage::decode_message(&mut &msg[..]) {
+                Ok(msg) => this.on_message(msg),
+                Err(err) => {
+                    debug!(target: "net::snap", peer_id=%this.peer_id, %err, "Failed to decode snap message, closing connection");
+                    return Poll::Ready(None)
+                }
+            }
+        }
+    }
+}
+
+impl fmt::Debug for SnapConnection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SnapConnection")
+            .field("peer_id", &self.peer_id)
+            .field("pending_responses", &self.pending_responses.len())
+            .finish_non_exhaustive()
+    }
+}
+
+/// Maps the response channel of a request to the [`SnapMessage`] to send to the peer.
+fn into_response<T: Send + 'static>(
+    rx: oneshot::Receiver<RequestResult<T>>,
+    f: fn(T) -> SnapMessage,
+) -> BoxFuture<'static, Option<SnapMessage>> {
+    rx.map(move |response| response.ok()?.ok().map(f)).boxed()
+}
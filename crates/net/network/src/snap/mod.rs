@@ -0,0 +1,12 @@
+//! Support for serving the `snap/1` protocol: <https://github.com/ethereum/devp2p/blob/master/caps/snap.md>
+//!
+//! `snap` is a satellite protocol of `eth` that is announced as an additional `RLPx`
+//! sub-protocol, see [`SnapProtocolHandler`]. All requests of the `snap` connections are served by
+//! the [`SnapRequestHandler`] from the hashed state and trie tables of the database.
+
+mod connection;
+mod requests;
+mod state;
+
+pub use connection::{SnapConnection, SnapConnectionHandler, SnapProtocolHandler};
+pub use requests::{IncomingSnapRequest, SnapRequestHandler};
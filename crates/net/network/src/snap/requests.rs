@@ -0,0 +1,684 @@
+//! Serves `snap` requests from the hashed state and trie tables.
+
+use crate::{
+    budget::DEFAULT_BUDGET_TRY_DRAIN_DOWNLOADERS,
+    metered_poll_nested_stream_with_budget,
+    metrics::SnapRequestHandlerMetrics,
+    snap::state::{persisted_block_number, window_start, ServedStates, StateOverlay},
+};
+use alloy_consensus::{constants::KECCAK_EMPTY, BlockHeader};
+use alloy_primitives::{
+    map::{B256HashMap, B256HashSet, HashMap},
+    Bytes, B256,
+};
+use alloy_rlp::Encodable;
+use futures::StreamExt;
+use reth_chain_state::{CanonStateNotificationStream, CanonStateSubscriptions};
+use reth_db::tables;
+use reth_db_api::{transaction::DbTx, DatabaseError};
+use reth_eth_wire::{
+    AccountData, AccountRange, ByteCodes, GetAccountRange, GetByteCodes, GetStorageRanges,
+    GetTrieNodes, SlimAccount, StorageData, StorageRanges, TrieNodes,
+};
+use reth_network_api::test_utils::PeersHandle;
+use reth_network_p2p::error::RequestResult;
+use reth_network_peers::PeerId;
+use reth_network_types::ReputationChangeKind;
+use reth_storage_api::{
+    errors::provider::ProviderResult, BlockNumReader, DBProvider, DatabaseProviderFactory,
+    HeaderProvider,
+};
+use reth_trie::{
+    hashed_cursor::{HashedCursor, HashedCursorFactory},
+    prefix_set::{PrefixSet, TriePrefixSetsMut},
+    proof::{Proof, StorageProof},
+    MultiProofTargets, Nibbles, StorageRoot,
+};
+use reth_trie_db::{DatabaseStorageRoot, DatabaseTrieCursorFactory};
+use std::{
+    future::Future,
+    pin::Pin,
+    sync::Arc,
+    task::{Context, Poll},
+    time::Duration,
+};
+use tokio::sync::{mpsc::Receiver, oneshot};
+use tokio_stream::wrappers::ReceiverStream;
+use tracing::debug;
+
+// Limits: <https://github.com/ethereum/go-ethereum/blob/v1.14.12/eth/protocols/snap/handler.go#L34-L58>
+
+/// Maximum size of replies to data retrievals, requests asking for more are capped to this.
+const SOFT_RESPONSE_LIMIT: u64 = 2 * 1024 * 1024;
+
+/// Maximum number of bytecodes to serve.
+///
+/// Used to limit lookups.
+const MAX_CODE_LOOKUPS: usize = 1024;
+
+/// Maximum number of trie nodes to serve.
+///
+/// Used to limit lookups.
+const MAX_TRIE_NODE_LOOKUPS: usize = 1024;
+
+/// Manages `snap` related requests on top of the p2p network.
+///
+/// The states of the last 128 canonical blocks are served. The persisted state is read from the
+/// hashed state and trie tables, older states are derived from the changesets and newer states from
+/// the canonical state notifications. Requests for any other state root are answered with an empty
+/// response, which the `snap/1` spec defines as "state not available".
+///
+/// This can be spawned to another task and is supposed to be run as background service.
+#[derive(Debug)]
+#[must_use = "Manager does nothing unless polled."]
+pub struct SnapRequestHandler<C: CanonStateSubscriptions> {
+    /// The client type that can open database transactions.
+    client: C,
+    /// Used for reporting peers that send malformed requests.
+    peers: PeersHandle,
+    /// Incoming requests from the `snap` connections.
+    incoming_requests: ReceiverStream<IncomingSnapRequest>,
+    /// Updates of the canonical chain, used to track the served states.
+    canonical_state: CanonStateNotificationStream<C::Primitives>,
+    /// The recent states that are served.
+    served_states: ServedStates,
+    /// Metrics for the snap request handler.
+    metrics: SnapRequestHandlerMetrics,
+}
+
+// === impl SnapRequestHandler ===
+
+impl<C: CanonStateSubscriptions> SnapRequestHandler<C> {
+    /// Create a new instance
+    pub fn new(client: C, peers: PeersHandle, incoming: Receiver<IncomingSnapRequest>) -> Self {
+        let canonical_state = client.canonical_state_stream();
+        Self {
+            client,
+            peers,
+            incoming_requests: ReceiverStream::new(incoming),
+            canonical_state,
+            served_states: Default::default(),
+            metrics: Default::default(),
+        }
+    }
+}
+
+impl<C> SnapRequestHandler<C>
+where
+    C: DatabaseProviderFactory + HeaderProvider + BlockNumReader + CanonStateSubscriptions,
+{
+    /// Loads the state roots of the window that ends at the current canonical tip.
+    fn load_served_roots(&mut self) -> ProviderResult<()> {
+        let tip = self.client.best_block_number()?;
+        let headers = self.client.headers_range(window_start(tip)..=tip)?;
+        self.served_states
+            .insert_roots(headers.iter().map(|header| (header.number(), header.state_root())));
+        Ok(())
+    }
+
+    /// Returns the changes of the state with the given root relative to the hashed state in the
+    /// database, `None` if the state is not served.
+    fn served_state<TX: DbTx>(
+        &mut self,
+        tx: &TX,
+        root_hash: B256,
+    ) -> ProviderResult<Option<Arc<StateOverlay>>> {
+        let overlay =
+            match (self.served_states.block_number(root_hash), persisted_block_number(tx)?) {
+                (Some(number), Some(persisted)) => {
+                    self.served_states.overlay(tx, number, persisted)?
+                }
+                _ => None,
+            };
+        if overlay.is_none() {
+            self.metrics.snap_unavailable_root_requests_total.increment(1);
+        }
+        Ok(overlay)
+    }
+
+    /// Reports a peer that sent a malformed request.
+    fn report_bad_message(&self, peer_id: PeerId) {
+        self.peers.reputation_change(peer_id, ReputationChangeKind::BadMessage);
+    }
+
+    /// Returns the list of accounts starting at the requested origin with the proofs for the range
+    /// boundaries.
+    fn get_account_range_response(
+        &mut self,
+        request: GetAccountRange,
+    ) -> ProviderResult<AccountRange> {
+        let GetAccountRange { request_id, root_hash, starting_hash, limit_hash, response_bytes } =
+            request;
+        let mut response = AccountRange { request_id, ..Default::default() };
+
+        let provider = self.client.database_provider_ro()?;
+        let tx = provider.tx_ref();
+        let Some(overlay) = self.served_state(tx, root_hash)? else { return Ok(response) };
+        let hashed_cursor_factory = overlay.hashed_cursor_factory(tx);
+        let prefix_sets = overlay.prefix_sets();
+
+        let response_limit = response_bytes.min(SOFT_RESPONSE_LIMIT) as usize;
+        let mut total_bytes = 0;
+
+        let mut cursor = hashed_cursor_factory.hashed_account_cursor()?;
+        let mut entry = cursor.seek(starting_hash)?;
+        while let Some((hashed_address, account)) = entry {
+            let storage_root = StorageRoot::from_tx_hashed(tx, hashed_address)
+                .with_hashed_cursor_factory(hashed_cursor_factory.clone())
+                .with_prefix_set(storage_prefix_set(&prefix_sets, hashed_address))
+                .root()
+                .map_err(DatabaseError::from)?;
+            let account =
+                AccountData { hash: hashed_address, body: SlimAccount::new(account, storage_root) };
+
+            total_bytes += account.length();
+            response.accounts.push(account);
+
+            // the first account past the limit is still served, it proves that there are no
+            // further accounts in the requested range
+            if hashed_address >= limit_hash || total_bytes > response_limit {
+                break
+            }
+
+            entry = cursor.next()?;
+        }
+
+        // prove the origin and the last served account, this proves that there are no gaps in the
+        // range
+        let mut targets = MultiProofTargets::default();
+        targets.insert(starting_hash, B256HashSet::default());
+        if let Some(last) = response.accounts.last() {
+            targets.insert(last.hash, B256HashSet::default());
+        }
+        let multiproof = Proof::new(DatabaseTrieCursorFactory::new(tx), hashed_cursor_factory)
+            .with_prefix_sets_mut(prefix_sets)
+            .multiproof(targets)?;
+        response.proof = multiproof
+            .account_subtree
+            .into_nodes_sorted()
+            .into_iter()
+            .map(|(_, node)| node)
+            .collect();
+
+        Ok(response)
+    }
+
+    /// Returns the storage slots of the requested accounts.
+    ///
+    /// Only the last served storage range can be incomplete, in which case it is proven.
+    fn get_storage_ranges_response(
+        &mut self,
+        request: GetStorageRanges,
+    ) -> ProviderResult<StorageRanges> {
+        let GetStorageRanges {
+            request_id,
+            root_hash,
+            account_hashes,
+            starting_hash,
+            limit_hash,
+            response_bytes,
+        } = request;
+        let mut response = StorageRanges { request_id, ..Default::default() };
+
+        let provider = self.client.database_provider_ro()?;
+        let tx = provider.tx_ref();
+        let Some(overlay) = self.served_state(tx, root_hash)? else { return Ok(response) };
+        let hashed_cursor_factory = overlay.hashed_cursor_factory(tx);
+        let prefix_sets = overlay.prefix_sets();
+
+        let response_limit = response_bytes.min(SOFT_RESPONSE_LIMIT) as usize;
+        let mut total_bytes = 0;
+
+        let num_accounts = account_hashes.len();
+        for (idx, hashed_address) in account_hashes.into_iter().enumerate() {
+            if total_bytes >= response_limit {
+                break
+            }
+
+            // the origin only applies to the first and the limit only to the last account
+            let origin =
+                (idx == 0).then(|| slot_hash(&starting_hash)).flatten().unwrap_or_default();
+            let limit = (idx == num_accounts - 1)
+                .then(|| slot_hash(&limit_hash))
+                .flatten()
+                .unwrap_or(B256::repeat_byte(0xff));
+
+            let mut slots = Vec::new();
+            let mut aborted = false;
+
+            let mut cursor = hashed_cursor_factory.hashed_storage_cursor(hashed_address)?;
+            let mut entry = cursor.seek(origin)?;
+            while let Some((hashed_slot, value)) = entry {
+                if total_bytes >= response_limit {
+                    aborted = true;
+                    break
+                }
+
+                let slot = StorageData { hash: hashed_slot, data: alloy_rlp::encode(value).into() };
+                total_bytes += slot.length();
+                slots.push(slot);
+
+                if hashed_slot >= limit {
+                    break
+                }
+
+                entry = cursor.next()?;
+            }
+
+            let last = slots.last().map(|slot| slot.hash);
+            if !slots.is_empty() {
+                response.slots.push(slots);
+            }
+
+            // a range that doesn't start at the beginning or was cut short must be proven, no
+            // further accounts are served after it
+            if !origin.is_zero() || (aborted && last.is_some()) {
+                let mut targets = B256HashSet::from_iter([origin]);
+                targets.extend(last);
+                let multiproof = StorageProof::new_hashed(
+                    DatabaseTrieCursorFactory::new(tx),
+                    hashed_cursor_factory.clone(),
+                    hashed_address,
+                )
+                .with_prefix_set_mut(
+                    prefix_sets
+                        .storage_prefix_sets
+                        .get(&hashed_address)
+                        .cloned()
+                        .unwrap_or_default(),
+                )
+                .storage_multiproof(targets)?;
+                response.proof = multiproof
+                    .subtree
+                    .into_nodes_sorted()
+                    .into_iter()
+                    .map(|(_, node)| node)
+                    .collect();
+                break
+            }
+        }
+
+        Ok(response)
+    }
+
+    /// Returns the requested bytecodes, in request order.
+    fn get_bytecodes_response(&self, request: GetByteCodes) -> ProviderResult<ByteCodes> {
+        let GetByteCodes { request_id, hashes, response_bytes } = request;
+        let mut response = ByteCodes { request_id, ..Default::default() };
+
+        let provider = self.client.database_provider_ro()?;
+        let tx = provider.tx_ref();
+
+        let response_limit = response_bytes.min(SOFT_RESPONSE_LIMIT) as usize;
+        let mut total_bytes = 0;
+
+        for hash in hashes.into_iter().take(MAX_CODE_LOOKUPS) {
+            let code = if hash == KECCAK_EMPTY {
+                Bytes::new()
+            } else if let Some(code) = tx.get::<tables::Bytecodes>(hash)? {
+                code.original_bytes()
+            } else {
+                // unknown codes are skipped, the requester matches the codes by hash
+                continue
+            };
+
+            total_bytes += code.len();
+            response.codes.push(code);
+
+            if total_bytes > response_limit {
+                break
+            }
+        }
+
+        Ok(response)
+    }
+
+    /// Returns the requested trie nodes, in request order.
+    ///
+    /// Responses end before the first node that can't be found. Peers that request malformed paths
+    /// are reported.
+    fn get_trie_nodes_response(
+        &mut self,
+        peer_id: PeerId,
+        request: GetTrieNodes,
+    ) -> ProviderResult<TrieNodes> {
+        let GetTrieNodes { request_id, root_hash, paths, response_bytes } = request;
+        let mut response = TrieNodes { request_id, ..Default::default() };
+
+        // resolve all requested paths first, so that all nodes can be proven at once
+        let mut requested = Vec::new();
+        let mut targets = MultiProofTargets::default();
+        let mut malformed = false;
+        'paths: for path_set in paths {
+            if requested.len() >= MAX_TRIE_NODE_LOOKUPS {
+                break
+            }
+            match path_set.0.as_slice() {
+                [] => {}
+                [account_path] => {
+                    let Some(path) = compact_to_nibbles(account_path) else {
+                        malformed = true;
+                        break
+                    };
+                    targets.entry(padded_key(&path)).or_default();
+                    requested.push((None, path));
+                }
+                [account_hash, storage_paths @ ..] => {
+                    if account_hash.len() != B256::len_bytes() {
+                        malformed = true;
+                        break
+                    }
+                    let hashed_address = B256::from_slice(account_hash);
+                    let slots = targets.entry(hashed_address).or_default();
+                    for storage_path in storage_paths {
+                        let Some(path) = compact_to_nibbles(storage_path) else {
+                            malformed = true;
+                            break 'paths
+                        };
+                        slots.insert(padded_key(&path));
+                        requested.push((Some(hashed_address), path));
+                    }
+                }
+            }
+        }
+        requested.truncate(MAX_TRIE_NODE_LOOKUPS);
+        if malformed {
+            self.report_bad_message(peer_id);
+        }
+
+        let provider = self.client.database_provider_ro()?;
+        let tx = provider.tx_ref();
+        let Some(overlay) = self.served_state(tx, root_hash)? else { return Ok(response) };
+
+        let multiproof =
+            Proof::new(DatabaseTrieCursorFactory::new(tx), overlay.hashed_cursor_factory(tx))
+                .with_prefix_sets_mut(overlay.prefix_sets())
+                .multiproof(targets)?;
+        let account_nodes: HashMap<Nibbles, Bytes> =
+            multiproof.account_subtree.into_nodes_sorted().into_iter().collect();
+        let storage_nodes: B256HashMap<HashMap<Nibbles, Bytes>> = multiproof
+            .storages
+            .into_iter()
+            .map(|(hashed_address, storage)| {
+                (hashed_address, storage.subtree.into_nodes_sorted().into_iter().collect())
+            })
+            .collect();
+
+        let response_limit = response_bytes.min(SOFT_RESPONSE_LIMIT) as usize;
+        let mut total_bytes = 0;
+
+        for (hashed_address, path) in requested {
+            let nodes = match hashed_address {
+                None => Some(&account_nodes),
+                Some(hashed_address) => storage_nodes.get(&hashed_address),
+            };
+            let Some(node) = nodes.and_then(|nodes| nodes.get(&path)).cloned() else { break };
+
+            total_bytes += node.len();
+            response.nodes.push(node);
+
+            if total_bytes > response_limit {
+                break
+            }
+        }
+
+        Ok(response)
+    }
+
+    fn on_account_range_request(
+        &mut self,
+        peer_id: PeerId,
+        request: GetAccountRange,
+        response: oneshot::Sender<RequestResult<AccountRange>>,
+    ) {
+        self.metrics.snap_account_range_requests_received_total.increment(1);
+        let request_id = request.request_id;
+        let account_range = self.get_account_range_response(request).unwrap_or_else(|err| {
+            debug!(target: "net::snap", %peer_id, %err, "Failed to serve account range");
+            AccountRange { request_id, ..Default::default() }
+        });
+        let _ = response.send(Ok(account_range));
+    }
+
+    fn on_storage_ranges_request(
+        &mut self,
+        peer_id: PeerId,
+        request: GetStorageRanges,
+        response: oneshot::Sender<RequestResult<StorageRanges>>,
+    ) {
+        self.metrics.snap_storage_ranges_requests_received_total.increment(1);
+        let request_id = request.request_id;
+        let storage_ranges = self.get_storage_ranges_response(request).unwrap_or_else(|err| {
+            debug!(target: "net::snap", %peer_id, %err, "Failed to serve storage ranges");
+            StorageRanges { request_id, ..Default::default() }
+        });
+        let _ = response.send(Ok(storage_ranges));
+    }
+
+    fn on_bytecodes_request(
+        &self,
+        peer_id: PeerId,
+        request: GetByteCodes,
+        response: oneshot::Sender<RequestResult<ByteCodes>>,
+    ) {
+        self.metrics.snap_bytecodes_requests_received_total.increment(1);
+        let request_id = request.request_id;
+        let bytecodes = self.get_bytecodes_response(request).unwrap_or_else(|err| {
+            debug!(target: "net::snap", %peer_id, %err, "Failed to serve bytecodes");
+            ByteCodes { request_id, ..Default::default() }
+        });
+        let _ = response.send(Ok(bytecodes));
+    }
+
+    fn on_trie_nodes_request(
+        &mut self,
+        peer_id: PeerId,
+        request: GetTrieNodes,
+        response: oneshot::Sender<RequestResult<TrieNodes>>,
+    ) {
+        self.metrics.snap_trie_nodes_requests_received_total.increment(1);
+        let request_id = request.request_id;
+        let trie_nodes = self.get_trie_nodes_response(peer_id, request).unwrap_or_else(|err| {
+            debug!(target: "net::snap", %peer_id, %err, "Failed to serve trie nodes");
+            TrieNodes { request_id, ..Default::default() }
+        });
+        let _ = response.send(Ok(trie_nodes));
+    }
+}
+
+/// An endless future.
+///
+/// This should be spawned or used as part of `tokio::select!`.
+impl<C> Future for SnapRequestHandler<C>
+where
+    C: DatabaseProviderFactory + HeaderProvider + BlockNumReader + CanonStateSubscriptions + Unpin,
+{
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+
+        if this.served_states.is_empty() {
+            if let Err(err) = this.load_served_roots() {
+                debug!(target: "net::snap", %err, "Failed to load served state roots");
+            }
+        }
+
+        // track the canonical chain before serving requests for its states
+        while let Poll::Ready(Some(notification)) = this.canonical_state.poll_next_unpin(cx) {
+            this.served_states.on_canonical_state(&notification);
+        }
+
+        let mut acc = Duration::ZERO;
+        let maybe_more_incoming_requests = metered_poll_nested_stream_with_budget!(
+            acc,
+            "net::snap",
+            "Incoming snap requests stream",
+            DEFAULT_BUDGET_TRY_DRAIN_DOWNLOADERS,
+            this.incoming_requests.poll_next_unpin(cx),
+            |incoming| {
+                match incoming {
+                    IncomingSnapRequest::GetAccountRange { peer_id, request, response } => {
+                        this.on_account_range_request(peer_id, request, response)
+                    }
+                    IncomingSnapRequest::GetStorageRanges { peer_id, request, response } => {
+                        this.on_storage_ranges_request(peer_id, request, response)
+                    }
+                    IncomingSnapRequest::GetByteCodes { peer_id, request, response } => {
+                        this.on_bytecodes_request(peer_id, request, response)
+                    }
+                    IncomingSnapRequest::GetTrieNodes { peer_id, request, response } => {
+                        this.on_trie_nodes_request(peer_id, request, response)
+                    }
+                }
+            },
+        );
+
+        this.metrics.acc_duration_poll_snap_req_handler.set(acc.as_secs_f64());
+
+        // stream is fully drained and import futures pending
+        if maybe_more_incoming_requests {
+            // make sure we're woken up again
+            cx.waker().wake_by_ref();
+        }
+
+        Poll::Pending
+    }
+}
+
+/// Returns the frozen prefix set of the changed storage slots of the given account.
+fn storage_prefix_set(prefix_sets: &TriePrefixSetsMut, hashed_address: B256) -> PrefixSet {
+    prefix_sets.storage_prefix_sets.get(&hashed_address).cloned().unwrap_or_default().freeze()
+}
+
+/// Returns the storage slot hash of a `GetStorageRanges` origin or limit, `None` if it is empty.
+///
+/// Shorter values are left padded, like geth does.
+fn slot_hash(bytes: &[u8]) -> Option<B256> {
+    if bytes.is_empty() {
+        return None
+    }
+    let bytes = &bytes[bytes.len().saturating_sub(B256::len_bytes())..];
+    Some(B256::left_padding_from(bytes))
+}
+
+/// Decodes a hex-prefix (compact) encoded trie path.
+///
+/// Returns `None` if the path is malformed.
+fn compact_to_nibbles(compact: &[u8]) -> Option<Nibbles> {
+    let (&first, rest) = compact.split_first()?;
+    let flag = first >> 4;
+    if flag > 3 {
+        return None
+    }
+
+    let mut nibbles = Vec::with_capacity(rest.len() * 2 + 1);
+    // odd length paths store their first nibble in the prefix byte
+    if flag & 1 == 1 {
+        nibbles.push(first & 0x0f);
+    }
+    for byte in rest {
+        nibbles.push(byte >> 4);
+        nibbles.push(byte & 0x0f);
+    }
+
+    (nibbles.len() <= 2 * B256::len_bytes()).then(|| Nibbles::from_nibbles_unchecked(nibbles))
+}
+
+/// Returns the key that is right padded with zeros, used as a proof target to reach the node at
+/// the given path.
+fn padded_key(path: &Nibbles) -> B256 {
+    let mut key = B256::ZERO;
+    let packed = path.pack();
+    key[..packed.len()].copy_from_slice(&packed);
+    key
+}
+
+/// All `snap` requests delegated by the `snap` connections.
+#[derive(Debug)]
+pub enum IncomingSnapRequest {
+    /// Request a range of accounts from the peer.
+    ///
+    /// The response should be sent through the channel.
+    GetAccountRange {
+        /// The ID of the peer that requested the account range.
+        peer_id: PeerId,
+        /// The specific account range requested.
+        request: GetAccountRange,
+        /// The channel sender for the response containing the account range.
+        response: oneshot::Sender<RequestResult<AccountRange>>,
+    },
+    /// Request ranges of storage slots from the peer.
+    ///
+    /// The response should be sent through the channel.
+    GetStorageRanges {
+        /// The ID of the peer that requested the storage ranges.
+        peer_id: PeerId,
+        /// The specific storage ranges requested.
+        request: GetStorageRanges,
+        /// The channel sender for the response containing the storage ranges.
+        response: oneshot::Sender<RequestResult<StorageRanges>>,
+    },
+    /// Request contract bytecodes from the peer.
+    ///
+    /// The response should be sent through the channel.
+    GetByteCodes {
+        /// The ID of the peer that requested the bytecodes.
+        peer_id: PeerId,
+        /// The specific bytecodes requested.
+        request: GetByteCodes,
+        /// The channel sender for the response containing the bytecodes.
+        response: oneshot::Sender<RequestResult<ByteCodes>>,
+    },
+    /// Request trie nodes from the peer.
+    ///
+    /// The response should be sent through the channel.
+    GetTrieNodes {
+        /// The ID of the peer that requested the trie nodes.
+        peer_id: PeerId,
+        /// The specific trie nodes requested.
+        request: GetTrieNodes,
+        /// The channel sender for the response containing the trie nodes.
+        response: oneshot::Sender<RequestResult<TrieNodes>>,
+    },
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::hex;
+
+    #[test]
+    fn decode_compact_paths() {
+        // even extension path
+        assert_eq!(
+            compact_to_nibbles(&hex!("001234")),
+            Some(Nibbles::from_nibbles_unchecked([1, 2, 3, 4]))
+        );
+        // odd extension path
+        assert_eq!(
+            compact_to_nibbles(&hex!("1123")),
+            Some(Nibbles::from_nibbles_unchecked([1, 2, 3]))
+        );
+        // root path
+        assert_eq!(compact_to_nibbles(&hex!("00")), Some(Nibbles::default()));
+        // invalid flag
+        assert_eq!(compact_to_nibbles(&hex!("4012")), None);
+        assert_eq!(compact_to_nibbles(&[]), None);
+    }
+
+    #[test]
+    fn pad_paths_to_keys() {
+        let key = padded_key(&Nibbles::from_nibbles_unchecked([0xa, 0xb, 0xc]));
+        assert_eq!(key[..2], hex!("abc0"));
+        assert!(key[2..].iter().all(|byte| *byte == 0));
+        assert_eq!(padded_key(&Nibbles::default()), B256::ZERO);
+    }
+
+    #[test]
+    fn storage_range_slot_hashes() {
+        assert_eq!(slot_hash(&[]), None);
+        assert_eq!(slot_hash(&[0x01]), Some(B256::with_last_byte(1)));
+        assert_eq!(slot_hash(B256::repeat_byte(0xff).as_slice()), Some(B256::repeat_byte(0xff)));
+    }
+}
@@ -0,0 +1,307 @@
+//! Tracks the recent states that are served to `snap` peers.
+
+use alloy_consensus::BlockHeader;
+use alloy_primitives::{BlockNumber, B256};
+use reth_chain_state::CanonStateNotification;
+use reth_db::tables;
+use reth_db_api::transaction::DbTx;
+use reth_primitives_traits::NodePrimitives;
+use reth_stages_types::StageId;
+use reth_storage_api::errors::provider::ProviderResult;
+use reth_trie::{
+    hashed_cursor::HashedPostStateCursorFactory, prefix_set::TriePrefixSetsMut, HashedPostState,
+    HashedPostStateSorted, KeccakKeyHasher,
+};
+use reth_trie_db::{DatabaseHashedCursorFactory, DatabaseHashedPostState};
+use std::{collections::BTreeMap, sync::Arc};
+
+/// Number of recent canonical blocks whose state is served.
+///
+/// Peers keep requesting the state of their pivot block while the chain advances, geth serves the
+/// same number of recent states.
+pub(crate) const SERVED_STATES_WINDOW: u64 = 128;
+
+/// The stages that write the hashed state and trie tables.
+///
+/// The tables only hold a consistent state if all of them finished at the same block.
+const STATE_STAGES: [StageId; 3] =
+    [StageId::AccountHashing, StageId::StorageHashing, StageId::MerkleExecute];
+
+/// The recent canonical states that can be served.
+///
+/// The state of the persisted block is read from the database as is. Older states within the
+/// window are derived from the changesets, newer states from the hashed states announced by the
+/// canonical state notifications.
+#[derive(Debug, Default)]
+pub(crate) struct ServedStates {
+    /// State roots of the canonical blocks in the window, by block number.
+    roots: BTreeMap<BlockNumber, B256>,
+    /// Hashed states of the canonical blocks in the window that might not be persisted yet.
+    in_memory: BTreeMap<BlockNumber, InMemoryState>,
+    /// The last built overlay with the number of the block it is for and the number of the
+    /// persisted block it applies to.
+    overlay: Option<(BlockNumber, BlockNumber, Arc<StateOverlay>)>,
+}
+
+impl ServedStates {
+    /// Returns `true` if no state roots are known yet.
+    pub(crate) fn is_empty(&self) -> bool {
+        self.roots.is_empty()
+    }
+
+    /// Inserts the state roots of canonical blocks.
+    pub(crate) fn insert_roots(&mut self, roots: impl IntoIterator<Item = (BlockNumber, B256)>) {
+        self.roots.extend(roots);
+        self.truncate();
+    }
+
+    /// Returns the number of the block with the given state root, if it is within the window.
+    pub(crate) fn block_number(&self, root: B256) -> Option<BlockNumber> {
+        self.roots
+            .iter()
+            .rev()
+            .find_map(|(number, state_root)| (*state_root == root).then_some(*number))
+    }
+
+    /// Updates the window with a new canonical chain.
+    ///
+    /// All blocks that are replaced by the committed chain are removed.
+    pub(crate) fn on_canonical_state<N: NodePrimitives>(
+        &mut self,
+        notification: &CanonStateNotification<N>,
+    ) {
+        let chain = notification.committed();
+        let first = chain.first().number();
+        let tip = chain.tip().number();
+
+        self.roots.split_off(&first);
+        self.in_memory.split_off(&first);
+        self.overlay = None;
+
+        self.roots.extend(chain.headers().map(|header| (header.number(), header.state_root())));
+        for number in first.max(window_start(tip))..=tip {
+            if let Some(outcome) = chain.execution_outcome_at_block(number) {
+                let state = outcome.hash_state_slow::<KeccakKeyHasher>();
+                self.in_memory.insert(number, InMemoryState { first, state });
+            }
+        }
+        self.truncate();
+    }
+
+    /// Returns the changes of the state of the given block relative to the persisted state,
+    /// `None` if they are not known.
+    pub(crate) fn overlay<TX: DbTx>(
+        &mut self,
+        tx: &TX,
+        number: BlockNumber,
+        persisted: BlockNumber,
+    ) -> ProviderResult<Option<Arc<StateOverlay>>> {
+        if let Some((overlay_number, overlay_persisted, overlay)) = &self.overlay {
+            if *overlay_number == number && *overlay_persisted == persisted {
+                return Ok(Some(overlay.clone()))
+            }
+        }
+
+        // persisted blocks are never needed again, the states of later blocks are applied to the
+        // persisted state
+        self.in_memory = self.in_memory.split_off(&(persisted + 1));
+
+        let state = if number < persisted {
+            HashedPostState::from_reverts::<KeccakKeyHasher>(tx, number + 1)?
+        } else if number > persisted {
+            let Some(state) = self.in_memory_state(number, persisted) else { return Ok(None) };
+            state
+        } else {
+            HashedPostState::default()
+        };
+
+        let overlay = Arc::new(StateOverlay::new(state));
+        self.overlay = Some((number, persisted, overlay.clone()));
+        Ok(Some(overlay))
+    }
+
+    /// Returns the changes of the blocks after the persisted one up to the given block.
+    ///
+    /// Each committed chain holds its changes since its first block, so the state of the last
+    /// block of each chain up to the given block is applied in order. Returns `None` if the
+    /// chains don't cover all blocks after the persisted one.
+    fn in_memory_state(
+        &self,
+        number: BlockNumber,
+        persisted: BlockNumber,
+    ) -> Option<HashedPostState> {
+        let mut chains: Vec<(BlockNumber, &InMemoryState)> = Vec::new();
+        for (block, state) in self.in_memory.range(..=number) {
+            match chains.last_mut() {
+                Some(last) if last.1.first == state.first => *last = (*block, state),
+                _ => chains.push((*block, state)),
+            }
+        }
+
+        let mut next = persisted + 1;
+        let mut hashed_state = HashedPostState::default();
+        for (block, state) in chains {
+            if state.first > next {
+                return None
+            }
+            hashed_state.extend_ref(&state.state);
+            next = block + 1;
+        }
+        (next == number + 1).then_some(hashed_state)
+    }
+
+    /// Removes all blocks that are outside of the window.
+    fn truncate(&mut self) {
+        let Some(tip) = self.roots.last_key_value().map(|(number, _)| *number) else { return };
+        let start = window_start(tip);
+        self.roots = self.roots.split_off(&start);
+        self.in_memory = self.in_memory.split_off(&start);
+    }
+}
+
+/// The hashed state of a block that was announced by a canonical state notification.
+#[derive(Debug)]
+struct InMemoryState {
+    /// Number of the first block of the committed chain the block was announced with.
+    first: BlockNumber,
+    /// Changes of the committed chain up to and including the block.
+    state: HashedPostState,
+}
+
+/// Changes of a served state relative to the state in the database.
+#[derive(Debug)]
+pub(crate) struct StateOverlay {
+    /// The changed accounts and storage slots.
+    state: HashedPostStateSorted,
+    /// The trie paths of all changes.
+    prefix_sets: TriePrefixSetsMut,
+}
+
+impl StateOverlay {
+    fn new(state: HashedPostState) -> Self {
+        let prefix_sets = state.construct_prefix_sets();
+        Self { state: state.into_sorted(), prefix_sets }
+    }
+
+    /// Returns a hashed cursor factory that reads the database with the changes applied.
+    pub(crate) fn hashed_cursor_factory<'a, TX>(
+        &'a self,
+        tx: &'a TX,
+    ) -> HashedPostStateCursorFactory<'a, DatabaseHashedCursorFactory<'a, TX>> {
+        HashedPostStateCursorFactory::new(DatabaseHashedCursorFactory::new(tx), &self.state)
+    }
+
+    /// Returns the trie paths of all changes.
+    pub(crate) fn prefix_sets(&self) -> TriePrefixSetsMut {
+        self.prefix_sets.clone()
+    }
+}
+
+/// Returns the number of the first block of the window that ends at the given tip.
+pub(crate) const fn window_start(tip: BlockNumber) -> BlockNumber {
+    (tip + 1).saturating_sub(SERVED_STATES_WINDOW)
+}
+
+/// Returns the number of the block whose state the hashed state and trie tables hold, `None` if
+/// they are being updated.
+pub(crate) fn persisted_block_number<TX: DbTx>(tx: &TX) -> ProviderResult<Option<BlockNumber>> {
+    let Some(finished) = tx.get::<tables::StageCheckpoints>(StageId::Finish.to_string())? else {
+        return Ok(None)
+    };
+    for stage in STATE_STAGES {
+        let checkpoint = tx.get::<tables::StageCheckpoints>(stage.to_string())?;
+        if checkpoint.map(|checkpoint| checkpoint.block_number) != Some(finished.block_number) {
+            return Ok(None)
+        }
+    }
+    Ok(Some(finished.block_number))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::{keccak256, Address, U256};
+    use reth_db_api::{models::AccountBeforeTx, transaction::DbTxMut};
+    use reth_primitives_traits::Account;
+    use reth_provider::test_utils::create_test_provider_factory;
+    use reth_stages_types::StageCheckpoint;
+    use reth_storage_api::DBProvider;
+    use reth_trie::{test_utils::state_root, StateRoot};
+    use reth_trie_db::DatabaseStateRoot;
+
+    fn state(block: u8) -> HashedPostState {
+        HashedPostState::default().with_accounts([(
+            keccak256([block]),
+            Some(Account { nonce: block as u64, balance: U256::ZERO, bytecode_hash: None }),
+        )])
+    }
+
+    #[test]
+    fn window_of_roots() {
+        let mut states = ServedStates::default();
+        states.insert_roots((0..200).map(|number| (number, B256::with_last_byte(number as u8))));
+
+        assert_eq!(states.block_number(B256::with_last_byte(199)), Some(199));
+        assert_eq!(states.block_number(B256::with_last_byte(72)), Some(72));
+        // outside of the window
+        assert_eq!(states.block_number(B256::with_last_byte(71)), None);
+    }
+
+    #[test]
+    fn in_memory_chains_must_cover_unpersisted_blocks() {
+        let mut states = ServedStates::default();
+        // chain 11..=12 followed by chain 13
+        states.in_memory.insert(11, InMemoryState { first: 11, state: state(11) });
+        states.in_memory.insert(12, InMemoryState { first: 11, state: state(12) });
+        states.in_memory.insert(13, InMemoryState { first: 13, state: state(13) });
+
+        let hashed = states.in_memory_state(13, 10).unwrap();
+        assert_eq!(hashed.accounts.len(), 2);
+        assert!(hashed.accounts.contains_key(&keccak256([12])));
+        assert!(hashed.accounts.contains_key(&keccak256([13])));
+
+        let hashed = states.in_memory_state(11, 10).unwrap();
+        assert_eq!(hashed.accounts.len(), 1);
+        assert!(hashed.accounts.contains_key(&keccak256([11])));
+
+        // block 10 is neither persisted nor announced
+        assert!(states.in_memory_state(13, 9).is_none());
+        // block 14 is unknown
+        assert!(states.in_memory_state(14, 10).is_none());
+    }
+
+    #[test]
+    fn historical_state_from_changesets() {
+        let factory = create_test_provider_factory();
+        let provider = factory.provider_rw().unwrap();
+        let tx = provider.tx_ref();
+
+        let (created, existing) = (Address::with_last_byte(1), Address::with_last_byte(2));
+        let account = Account { nonce: 1, balance: U256::from(10), bytecode_hash: None };
+        tx.put::<tables::HashedAccounts>(keccak256(created), account).unwrap();
+        tx.put::<tables::HashedAccounts>(keccak256(existing), account).unwrap();
+        tx.put::<tables::AccountChangeSets>(1, AccountBeforeTx { address: created, info: None })
+            .unwrap();
+        for stage in STATE_STAGES.into_iter().chain([StageId::Finish]) {
+            tx.put::<tables::StageCheckpoints>(stage.to_string(), StageCheckpoint::new(1)).unwrap();
+        }
+        assert_eq!(persisted_block_number(tx).unwrap(), Some(1));
+
+        let mut states = ServedStates::default();
+        let overlay = states.overlay(tx, 0, 1).unwrap().unwrap();
+        let root = StateRoot::from_tx(tx)
+            .with_hashed_cursor_factory(overlay.hashed_cursor_factory(tx))
+            .with_prefix_sets(overlay.prefix_sets().freeze())
+            .root()
+            .unwrap();
+        assert_eq!(root, state_root([(existing, (account, std::iter::empty()))]));
+
+        // the trie tables lag behind the hashed state
+        tx.put::<tables::StageCheckpoints>(
+            StageId::MerkleExecute.to_string(),
+            StageCheckpoint::new(0),
+        )
+        .unwrap();
+        assert_eq!(persisted_block_number(tx).unwrap(), None);
+    }
+}
@@ -404,9 +404,13 @@ impl<N: NetworkPrimitives> NetworkState<N> {
     fn on_eth_response(&mut self, peer: PeerId, resp: PeerResponseResult<N>) {
         let outcome = match resp {
             PeerResponseResult::BlockHeaders(res) => {
+                let useful = res.as_ref().is_ok_and(|headers| !headers.is_empty());
+                self.peers_manager.on_peer_response(&peer, useful);
                 self.state_fetcher.on_block_headers_response(peer, res)
             }
             PeerResponseResult::BlockBodies(res) => {
+                let useful = res.as_ref().is_ok_and(|bodies| !bodies.is_empty());
+                self.peers_manager.on_peer_response(&peer, useful);
                 self.state_fetcher.on_block_bodies_response(peer, res)
             }
             _ => None,
@@ -6,6 +6,8 @@ pub mod config;
 pub mod constants;
 /// Component responsible for fetching transactions from [`NewPooledTransactionHashes`].
 pub mod fetcher;
+/// Pluggable policies that decide how new pending transactions are propagated to peers.
+pub mod policy;
 pub mod validation;
 
 pub use self::constants::{
@@ -13,6 +15,9 @@ pub use self::constants::{
     SOFT_LIMIT_BYTE_SIZE_POOLED_TRANSACTIONS_RESPONSE,
 };
 pub use config::{TransactionFetcherConfig, TransactionPropagationMode, TransactionsManagerConfig};
+pub use policy::{
+    PeerAssignment, PeerPropagation, TransactionPropagationPolicy, TransactionPropagationPolicyKind,
+};
 pub use validation::*;
 
 pub(crate) use fetcher::{FetchEvent, TransactionFetcher};
@@ -27,11 +32,16 @@ use crate::{
     cache::LruCache,
     duration_metered_exec, metered_poll_nested_stream_with_budget,
     metrics::{TransactionsManagerMetrics, NETWORK_POOL_TRANSACTIONS_SCOPE},
-    NetworkHandle,
+    poll_nested_stream_with_budget, NetworkHandle,
 };
+use alloy_eips::eip2718::Encodable2718;
 use alloy_primitives::{TxHash, B256};
 use constants::SOFT_LIMIT_COUNT_HASHES_IN_NEW_POOLED_TRANSACTIONS_BROADCAST_MESSAGE;
-use futures::{stream::FuturesUnordered, Future, StreamExt};
+use futures::{
+    stream::{BoxStream, FuturesUnordered},
+    Future, StreamExt,
+};
+use policy::{PropagationPolicies, DEFAULT_PROPAGATION_POLICY_LABEL};
 use reth_eth_wire::{
     DedupPayload, EthNetworkPrimitives, EthVersion, GetPooledTransactions, HandleMempoolData,
     HandleVersionedMempoolData, NetworkPrimitives, NewPooledTransactionHashes,
@@ -41,24 +51,25 @@ use reth_eth_wire::{
 use reth_metrics::common::mpsc::UnboundedMeteredReceiver;
 use reth_network_api::{
     events::{PeerEvent, SessionInfo},
-    NetworkEvent, NetworkEventListenerProvider, PeerRequest, PeerRequestSender, Peers,
+    Direction, NetworkEvent, NetworkEventListenerProvider, PeerRequest, PeerRequestSender, Peers,
 };
 use reth_network_p2p::{
     error::{RequestError, RequestResult},
     sync::SyncStateProvider,
 };
 use reth_network_peers::PeerId;
-use reth_network_types::ReputationChangeKind;
+use reth_network_types::{PeerKind, ReputationChangeKind};
 use reth_primitives::{transaction::SignedTransactionIntoRecoveredExt, TransactionSigned};
 use reth_primitives_traits::SignedTransaction;
 use reth_tokio_util::EventStream;
 use reth_transaction_pool::{
     error::{PoolError, PoolResult},
-    GetPooledTransactionLimit, PoolTransaction, PropagateKind, PropagatedTransactions,
-    TransactionPool, ValidPoolTransaction,
+    FullTransactionEvent, GetPooledTransactionLimit, PoolTransaction, PropagateKind,
+    PropagatedTransactions, TransactionPool, ValidPoolTransaction,
 };
 use std::{
     collections::{hash_map::Entry, HashMap, HashSet},
+    fmt,
     pin::Pin,
     sync::{
         atomic::{AtomicUsize, Ordering},
@@ -276,6 +287,12 @@ pub struct TransactionsManager<Pool, N: NetworkPrimitives = EthNetworkPrimitives
     transaction_events: UnboundedMeteredReceiver<NetworkTransactionEvent<N>>,
     /// How the `TransactionsManager` is configured.
     config: TransactionsManagerConfig,
+    /// The policies that adjust how new pending transactions are propagated.
+    propagation_policies: PropagationPolicies,
+    /// A stream of mined transactions, if any propagation policy tracks them.
+    ///
+    /// Subscribed to on the first poll.
+    mined_transactions: Option<MinedTransactions>,
     /// `TransactionsManager` metrics
     metrics: TransactionsManagerMetrics,
 }
@@ -307,6 +324,11 @@ impl<Pool: TransactionPool, N: NetworkPrimitives> TransactionsManager<Pool, N> {
             .capacity_pending_pool_imports
             .increment(pending_pool_imports_info.max_pending_pool_imports as u64);
 
+        let mut propagation_policies = PropagationPolicies::default();
+        for policy in &transactions_manager_config.propagation_policies {
+            propagation_policies.push(policy.build());
+        }
+
         Self {
             pool,
             network,
@@ -327,10 +349,19 @@ impl<Pool: TransactionPool, N: NetworkPrimitives> TransactionsManager<Pool, N> {
                 NETWORK_POOL_TRANSACTIONS_SCOPE,
             ),
             config: transactions_manager_config,
+            propagation_policies,
+            mined_transactions: None,
             metrics,
         }
     }
 
+    /// Adds a policy that adjusts how new pending transactions are propagated, after the policies
+    /// of the [`TransactionsManagerConfig`].
+    pub fn with_propagation_policy(mut self, policy: impl TransactionPropagationPolicy) -> Self {
+        self.propagation_policies.push(Box::new(policy));
+        self
+    }
+
     /// Returns a new handle that can send commands to this type.
     pub fn handle(&self) -> TransactionsHandle<N> {
         TransactionsHandle { manager_tx: self.command_tx.clone() }
@@ -502,6 +533,11 @@ where
         };
         let client = peer.client_version.clone();
 
+        if !self.propagation_policies.is_empty() {
+            let hashes = msg.iter_hashes().copied().collect::<Vec<_>>();
+            self.propagation_policies.on_transactions_received(peer_id, &hashes);
+        }
+
         // keep track of the transactions the peer knows
         let mut count_txns_already_seen_by_peer = 0;
         for tx in msg.iter_hashes().copied() {
@@ -876,12 +912,40 @@ where
         let max_num_full = self.config.propagation_mode.full_peer_count(self.peers.len());
 
         // Note: Assuming ~random~ order due to random state of the peers map hasher
-        for (peer_idx, (peer_id, peer)) in self.peers.iter_mut().enumerate() {
-            // determine whether to send full tx objects or hashes.
-            let mut builder = if peer_idx > max_num_full {
-                PropagateTransactionsBuilder::pooled(peer.version)
-            } else {
-                PropagateTransactionsBuilder::full(peer.version)
+        let mut assignments = self
+            .peers
+            .iter()
+            .enumerate()
+            .map(|(peer_idx, (peer_id, peer))| PeerAssignment {
+                peer_id: *peer_id,
+                kind: peer.kind,
+                direction: peer.direction,
+                version: peer.version,
+                // determine whether to send full tx objects or hashes.
+                propagation: if peer_idx > max_num_full {
+                    PeerPropagation::Hashes
+                } else {
+                    PeerPropagation::Full
+                },
+            })
+            .collect::<Vec<_>>();
+
+        // let the policies adjust the assignments
+        let labels = self.propagation_policies.assign(&mut assignments);
+
+        for (PeerAssignment { peer_id, propagation, .. }, label) in
+            assignments.into_iter().zip(labels)
+        {
+            let Some(peer) = self.peers.get_mut(&peer_id) else { continue };
+            peer.propagation_policy = label;
+
+            let mut builder = match propagation {
+                PeerPropagation::Full => PropagateTransactionsBuilder::full(peer.version),
+                PeerPropagation::Hashes => PropagateTransactionsBuilder::pooled(peer.version),
+                PeerPropagation::Skip => {
+                    trace!(target: "net::tx", ?peer_id, policy = label, "Skipping propagation to peer");
+                    continue
+                }
             };
 
             if propagation_mode.is_forced() {
@@ -905,6 +969,7 @@ where
             }
 
             let PropagateTransactions { pooled, full } = builder.build();
+            let (mut num_hashes, mut num_full, mut full_bytes) = (0, 0, 0);
 
             // send hashes if any
             if let Some(mut new_pooled_hashes) = pooled {
@@ -914,15 +979,16 @@ where
                     .truncate(SOFT_LIMIT_COUNT_HASHES_IN_NEW_POOLED_TRANSACTIONS_BROADCAST_MESSAGE);
 
                 for hash in new_pooled_hashes.iter_hashes().copied() {
-                    propagated.0.entry(hash).or_default().push(PropagateKind::Hash(*peer_id));
+                    propagated.0.entry(hash).or_default().push(PropagateKind::Hash(peer_id));
                     // mark transaction as seen by peer
                     peer.seen_transactions.insert(hash);
                 }
+                num_hashes = new_pooled_hashes.len();
 
                 trace!(target: "net::tx", ?peer_id, num_txs=?new_pooled_hashes.len(), "Propagating tx hashes to peer");
 
                 // send hashes of transactions
-                self.network.send_transactions_hashes(*peer_id, new_pooled_hashes);
+                self.network.send_transactions_hashes(peer_id, new_pooled_hashes);
             }
 
             // send full transactions, if any
@@ -932,16 +998,21 @@ where
                         .0
                         .entry(*tx.tx_hash())
                         .or_default()
-                        .push(PropagateKind::Full(*peer_id));
+                        .push(PropagateKind::Full(peer_id));
                     // mark transaction as seen by peer
                     peer.seen_transactions.insert(*tx.tx_hash());
+                    full_bytes += tx.encode_2718_len();
                 }
+                num_full = new_full_transactions.len();
 
                 trace!(target: "net::tx", ?peer_id, num_txs=?new_full_transactions.len(), "Propagating full transactions to peer");
 
                 // send full transactions
-                self.network.send_transactions(*peer_id, new_full_transactions);
+                self.network.send_transactions(peer_id, new_full_transactions);
             }
+
+            self.propagation_policies
+                .on_transactions_propagated(label, peer_id, num_full, full_bytes, num_hashes);
         }
 
         // Update propagated transactions metrics
@@ -976,6 +1047,8 @@ where
                 let _ = response.send(Ok(PooledTransactions::default()));
                 return
             }
+            self.propagation_policies.on_hashes_requested(peer.propagation_policy, request.0.len());
+
             let transactions = self.pool.get_pooled_transaction_elements(
                 request.0,
                 GetPooledTransactionLimit::ResponseSizeSoftLimit(
@@ -1039,13 +1112,15 @@ where
         info: SessionInfo,
         messages: PeerRequestSender<PeerRequest<N>>,
     ) {
-        let SessionInfo { peer_id, client_version, version, .. } = info;
+        let SessionInfo { peer_id, client_version, version, direction, peer_kind, .. } = info;
 
         // Insert a new peer into the peerset.
         let peer = PeerMetadata::<N>::new(
             messages,
             version,
             client_version,
+            peer_kind,
+            direction,
             self.config.max_transactions_seen_by_peer_history,
         );
         let peer = match self.peers.entry(peer_id) {
@@ -1092,6 +1167,7 @@ where
                 // remove the peer
                 self.peers.remove(&peer_id);
                 self.transaction_fetcher.remove_peer(&peer_id);
+                self.propagation_policies.on_peer_disconnected(peer_id);
             }
             NetworkEvent::ActivePeerSession { info, messages } => {
                 // process active peer session and broadcast available transaction from the pool
@@ -1181,6 +1257,11 @@ where
             }
         }
 
+        if source.is_broadcast() && !self.propagation_policies.is_empty() {
+            let hashes = transactions.iter().map(|tx| *tx.tx_hash()).collect::<Vec<_>>();
+            self.propagation_policies.on_transactions_received(peer_id, &hashes);
+        }
+
         // 1. filter out txns already inserted into pool
         let txns_count_pre_pool_filter = transactions.len();
         self.pool.retain_unknown(&mut transactions);
@@ -1446,6 +1527,29 @@ where
             poll_durations.acc_pending_fetch
         );
 
+        // Advance mined transactions, if tracked by a propagation policy.
+        if this.mined_transactions.is_none() &&
+            this.propagation_policies.tracks_mined_transactions()
+        {
+            let mined = this.pool.all_transactions_event_listener().filter_map(|event| {
+                futures::future::ready(match event {
+                    FullTransactionEvent::Mined { tx_hash, .. } => Some(tx_hash),
+                    _ => None,
+                })
+            });
+            this.mined_transactions = Some(MinedTransactions(mined.boxed()));
+        }
+        let maybe_more_mined_txns = match this.mined_transactions.as_mut() {
+            Some(MinedTransactions(mined)) => poll_nested_stream_with_budget!(
+                "net::tx",
+                "Mined transactions stream",
+                DEFAULT_BUDGET_TRY_DRAIN_STREAM,
+                mined.poll_next_unpin(cx),
+                |hash| this.propagation_policies.on_transaction_mined(hash)
+            ),
+            None => false,
+        };
+
         // Advance commands (propagate/fetch/serve txns).
         let maybe_more_commands = metered_poll_nested_stream_with_budget!(
             poll_durations.acc_cmds,
@@ -1464,7 +1568,8 @@ where
             maybe_more_tx_events ||
             maybe_more_tx_fetch_events ||
             maybe_more_pool_imports ||
-            maybe_more_pending_txns
+            maybe_more_pending_txns ||
+            maybe_more_mined_txns
         {
             // make sure we're woken up again
             cx.waker().wake_by_ref();
@@ -1767,6 +1872,12 @@ pub struct PeerMetadata<N: NetworkPrimitives = EthNetworkPrimitives> {
     version: EthVersion,
     /// The peer's client version.
     client_version: Arc<str>,
+    /// The kind of the peer.
+    kind: PeerKind,
+    /// The direction of the peer's session.
+    direction: Direction,
+    /// The propagation policy that last changed how transactions are propagated to the peer.
+    propagation_policy: &'static str,
 }
 
 impl<N: NetworkPrimitives> PeerMetadata<N> {
@@ -1775,6 +1886,8 @@ impl<N: NetworkPrimitives> PeerMetadata<N> {
         request_tx: PeerRequestSender<PeerRequest<N>>,
         version: EthVersion,
         client_version: Arc<str>,
+        kind: PeerKind,
+        direction: Direction,
         max_transactions_seen_by_peer: u32,
     ) -> Self {
         Self {
@@ -1782,10 +1895,22 @@ impl<N: NetworkPrimitives> PeerMetadata<N> {
             request_tx,
             version,
             client_version,
+            kind,
+            direction,
+            propagation_policy: DEFAULT_PROPAGATION_POLICY_LABEL,
         }
     }
 }
 
+/// The stream of the hashes of mined transactions.
+struct MinedTransactions(BoxStream<'static, TxHash>);
+
+impl fmt::Debug for MinedTransactions {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MinedTransactions").finish_non_exhaustive()
+    }
+}
+
 /// Commands to send to the [`TransactionsManager`]
 #[derive(Debug)]
 enum TransactionsCommand<N: NetworkPrimitives = EthNetworkPrimitives> {
@@ -1949,6 +2074,8 @@ mod tests {
                 PeerRequestSender::new(peer_id, to_mock_session_tx),
                 version,
                 Arc::from(""),
+                PeerKind::Basic,
+                Direction::Incoming,
                 DEFAULT_MAX_COUNT_TRANSACTIONS_SEEN_BY_PEER,
             ),
             to_mock_session_rx,
@@ -2454,6 +2581,8 @@ mod tests {
             capabilities: Arc::new(vec![].into()),
             status: Arc::new(Default::default()),
             version: EthVersion::Eth68,
+            direction: Direction::Incoming,
+            peer_kind: PeerKind::Basic,
         };
         let messages: PeerRequestSender<PeerRequest> = PeerRequestSender::new(peer_id, tx);
         tx_manager
@@ -0,0 +1,509 @@
+use crate::cache::LruMap;
+use alloy_primitives::TxHash;
+use reth_eth_wire::EthVersion;
+use reth_metrics::{metrics::Counter, Metrics};
+use reth_network_api::Direction;
+use reth_network_peers::PeerId;
+use reth_network_types::PeerKind;
+use std::{
+    collections::HashMap,
+    fmt,
+    time::{Duration, Instant},
+};
+
+/// The label of the propagation metrics of peers whose propagation was not changed by any policy,
+/// but only determined by the [`TransactionPropagationMode`](super::TransactionPropagationMode).
+pub const DEFAULT_PROPAGATION_POLICY_LABEL: &str = "default";
+
+/// Default number of transactions whose first announcer is remembered by the
+/// [`UsefulPeersPolicy`].
+pub const DEFAULT_MAX_COUNT_FIRST_ANNOUNCERS: u32 = 100_000;
+
+/// How new pending transactions are propagated to a peer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PeerPropagation {
+    /// Send full transactions. Blob transactions are still only announced.
+    Full,
+    /// Only announce the hashes of the transactions.
+    Hashes,
+    /// Don't propagate the transactions to the peer.
+    Skip,
+}
+
+/// A connected peer, with the way the next batch of new pending transactions is propagated to it.
+#[derive(Debug, Clone)]
+pub struct PeerAssignment {
+    /// The identifier of the peer.
+    pub peer_id: PeerId,
+    /// The kind of the peer.
+    pub kind: PeerKind,
+    /// The direction of the peer's session.
+    pub direction: Direction,
+    /// Negotiated eth version of the session.
+    pub version: EthVersion,
+    /// How the transactions are propagated to the peer.
+    pub propagation: PeerPropagation,
+}
+
+/// A policy that decides how new pending transactions are propagated to the connected peers.
+///
+/// The [`TransactionsManager`](super::TransactionsManager) first assigns full transactions to a
+/// number of peers according to its
+/// [`TransactionPropagationMode`](super::TransactionPropagationMode), and announces the hashes to
+/// the others. The policies are then applied in order, and each of them can change the assignments
+/// of the previous ones.
+///
+/// Policies only apply to the broadcast of new pending transactions, not to transactions that are
+/// explicitly propagated to a peer through the [`TransactionsHandle`](super::TransactionsHandle).
+pub trait TransactionPropagationPolicy: fmt::Debug + Send + Sync + Unpin + 'static {
+    /// Returns the name of the policy, which is the `policy` label of its metrics.
+    fn name(&self) -> &'static str;
+
+    /// Adjusts how the next batch of new pending transactions is propagated to the peers.
+    fn assign(&mut self, peers: &mut [PeerAssignment]);
+
+    /// Invoked when a peer announces or broadcasts transactions.
+    fn on_transactions_received(&mut self, _peer_id: PeerId, _hashes: &[TxHash]) {}
+
+    /// Invoked when new pending transactions were propagated to a peer, with the total encoded
+    /// length of the full transactions.
+    fn on_transactions_propagated(&mut self, _peer_id: PeerId, _full_bytes: usize) {}
+
+    /// Returns `true` if the policy should be notified of mined transactions.
+    fn tracks_mined_transactions(&self) -> bool {
+        false
+    }
+
+    /// Invoked when a transaction was mined, if the policy
+    /// [tracks mined transactions](Self::tracks_mined_transactions).
+    fn on_transaction_mined(&mut self, _hash: TxHash) {}
+
+    /// Invoked when the session of a peer is closed.
+    fn on_peer_disconnected(&mut self, _peer_id: PeerId) {}
+}
+
+/// The built-in [`TransactionPropagationPolicy`]s, that can be enabled in the
+/// [`TransactionsManagerConfig`](super::TransactionsManagerConfig).
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum TransactionPropagationPolicyKind {
+    /// See [`UsefulPeersPolicy`].
+    UsefulPeers,
+    /// See [`TrustedFullPolicy`].
+    TrustedFull,
+    /// See [`InboundHashesPolicy`].
+    InboundHashes,
+    /// See [`ByteBudgetPolicy`].
+    ByteBudget {
+        /// Max encoded length of the full transactions sent to a peer per interval.
+        bytes: usize,
+        /// The interval the budget is reset at.
+        interval: Duration,
+    },
+}
+
+impl TransactionPropagationPolicyKind {
+    /// Creates the policy.
+    pub fn build(&self) -> Box<dyn TransactionPropagationPolicy> {
+        match self {
+            Self::UsefulPeers => Box::new(UsefulPeersPolicy::default()),
+            Self::TrustedFull => Box::new(TrustedFullPolicy),
+            Self::InboundHashes => Box::new(InboundHashesPolicy),
+            Self::ByteBudget { bytes, interval } => {
+                Box::new(ByteBudgetPolicy::new(*bytes, *interval))
+            }
+        }
+    }
+}
+
+/// Sends the full transactions to the peers that were the first to announce the most
+/// transactions that were later mined.
+///
+/// The number of peers that receive full transactions is not changed, only which peers they are.
+#[derive(Debug)]
+pub struct UsefulPeersPolicy {
+    /// The first peer that announced a transaction.
+    first_announcers: LruMap<TxHash, PeerId>,
+    /// The number of mined transactions a peer was the first to announce.
+    scores: HashMap<PeerId, u64>,
+}
+
+impl UsefulPeersPolicy {
+    /// Creates a new policy that remembers the first announcer of the given number of
+    /// transactions.
+    pub fn new(max_first_announcers: u32) -> Self {
+        Self { first_announcers: LruMap::new(max_first_announcers), scores: HashMap::default() }
+    }
+
+    /// Returns the number of mined transactions the peer was the first to announce.
+    pub fn score(&self, peer_id: &PeerId) -> u64 {
+        self.scores.get(peer_id).copied().unwrap_or_default()
+    }
+}
+
+impl Default for UsefulPeersPolicy {
+    fn default() -> Self {
+        Self::new(DEFAULT_MAX_COUNT_FIRST_ANNOUNCERS)
+    }
+}
+
+impl TransactionPropagationPolicy for UsefulPeersPolicy {
+    fn name(&self) -> &'static str {
+        "useful_peers"
+    }
+
+    fn assign(&mut self, peers: &mut [PeerAssignment]) {
+        let mut candidates = peers
+            .iter()
+            .enumerate()
+            .filter(|(_, peer)| peer.propagation != PeerPropagation::Skip)
+            .map(|(idx, peer)| (idx, self.score(&peer.peer_id), peer.propagation))
+            .collect::<Vec<_>>();
+        let num_full = candidates.iter().filter(|(_, _, p)| *p == PeerPropagation::Full).count();
+
+        // peers with the same score keep their assignment
+        candidates.sort_by_key(|(_, score, propagation)| {
+            (std::cmp::Reverse(*score), *propagation != PeerPropagation::Full)
+        });
+        for (rank, (idx, _, _)) in candidates.into_iter().enumerate() {
+            peers[idx].propagation =
+                if rank < num_full { PeerPropagation::Full } else { PeerPropagation::Hashes };
+        }
+    }
+
+    fn on_transactions_received(&mut self, peer_id: PeerId, hashes: &[TxHash]) {
+        for hash in hashes {
+            if self.first_announcers.peek(hash).is_none() {
+                self.first_announcers.insert(*hash, peer_id);
+            }
+        }
+    }
+
+    fn tracks_mined_transactions(&self) -> bool {
+        true
+    }
+
+    fn on_transaction_mined(&mut self, hash: TxHash) {
+        if let Some(peer_id) = self.first_announcers.remove(&hash) {
+            *self.scores.entry(peer_id).or_default() += 1;
+        }
+    }
+
+    fn on_peer_disconnected(&mut self, peer_id: PeerId) {
+        self.scores.remove(&peer_id);
+    }
+}
+
+/// Always sends the full transactions to trusted peers.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TrustedFullPolicy;
+
+impl TransactionPropagationPolicy for TrustedFullPolicy {
+    fn name(&self) -> &'static str {
+        "trusted_full"
+    }
+
+    fn assign(&mut self, peers: &mut [PeerAssignment]) {
+        for peer in peers.iter_mut().filter(|peer| peer.kind.is_trusted()) {
+            peer.propagation = PeerPropagation::Full;
+        }
+    }
+}
+
+/// Only announces the hashes of the transactions to inbound peers.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct InboundHashesPolicy;
+
+impl TransactionPropagationPolicy for InboundHashesPolicy {
+    fn name(&self) -> &'static str {
+        "inbound_hashes"
+    }
+
+    fn assign(&mut self, peers: &mut [PeerAssignment]) {
+        for peer in peers.iter_mut() {
+            if peer.direction.is_incoming() && peer.propagation == PeerPropagation::Full {
+                peer.propagation = PeerPropagation::Hashes;
+            }
+        }
+    }
+}
+
+/// Limits the encoded length of the full transactions that are sent to a peer per interval.
+///
+/// Peers that exhausted their budget only receive announcements until the end of the interval.
+#[derive(Debug)]
+pub struct ByteBudgetPolicy {
+    /// Max encoded length of the full transactions sent to a peer per interval.
+    bytes: usize,
+    /// The interval the budget is reset at.
+    interval: Duration,
+    /// The start of the current interval and the bytes spent in it, per peer.
+    spent: HashMap<PeerId, (Instant, usize)>,
+}
+
+impl ByteBudgetPolicy {
+    /// Creates a new policy that sends at most `bytes` of full transactions to a peer per
+    /// `interval`.
+    pub fn new(bytes: usize, interval: Duration) -> Self {
+        Self { bytes, interval, spent: HashMap::default() }
+    }
+
+    /// Returns the bytes spent by the peer in the current interval.
+    fn spent(&self, peer_id: &PeerId, now: Instant) -> usize {
+        self.spent
+            .get(peer_id)
+            .filter(|(start, _)| now.saturating_duration_since(*start) < self.interval)
+            .map(|(_, spent)| *spent)
+            .unwrap_or_default()
+    }
+}
+
+impl TransactionPropagationPolicy for ByteBudgetPolicy {
+    fn name(&self) -> &'static str {
+        "byte_budget"
+    }
+
+    fn assign(&mut self, peers: &mut [PeerAssignment]) {
+        let now = Instant::now();
+        for peer in peers.iter_mut() {
+            if peer.propagation == PeerPropagation::Full &&
+                self.spent(&peer.peer_id, now) >= self.bytes
+            {
+                peer.propagation = PeerPropagation::Hashes;
+            }
+        }
+    }
+
+    fn on_transactions_propagated(&mut self, peer_id: PeerId, full_bytes: usize) {
+        if full_bytes == 0 {
+            return
+        }
+        let now = Instant::now();
+        let (start, spent) = self.spent.entry(peer_id).or_insert((now, 0));
+        if now.saturating_duration_since(*start) >= self.interval {
+            *start = now;
+            *spent = 0;
+        }
+        *spent += full_bytes;
+    }
+
+    fn on_peer_disconnected(&mut self, peer_id: PeerId) {
+        self.spent.remove(&peer_id);
+    }
+}
+
+/// The configured [`TransactionPropagationPolicy`]s, with the metrics of the peers they assigned.
+#[derive(Debug, Default)]
+pub(crate) struct PropagationPolicies {
+    policies: Vec<Box<dyn TransactionPropagationPolicy>>,
+    metrics: HashMap<&'static str, PropagationPolicyMetrics>,
+}
+
+impl PropagationPolicies {
+    /// Adds a policy, that is applied after the existing ones.
+    pub(crate) fn push(&mut self, policy: Box<dyn TransactionPropagationPolicy>) {
+        self.policies.push(policy);
+    }
+
+    /// Returns `true` if there are no policies.
+    pub(crate) fn is_empty(&self) -> bool {
+        self.policies.is_empty()
+    }
+
+    /// Returns `true` if any policy tracks mined transactions.
+    pub(crate) fn tracks_mined_transactions(&self) -> bool {
+        self.policies.iter().any(|policy| policy.tracks_mined_transactions())
+    }
+
+    /// Applies all policies to the assignments.
+    ///
+    /// Returns the name of the last policy that changed the assignment of every peer, or
+    /// [`DEFAULT_PROPAGATION_POLICY_LABEL`] if none did.
+    pub(crate) fn assign(&mut self, peers: &mut [PeerAssignment]) -> Vec<&'static str> {
+        let mut labels = vec![DEFAULT_PROPAGATION_POLICY_LABEL; peers.len()];
+        for policy in &mut self.policies {
+            let before = peers.iter().map(|peer| peer.propagation).collect::<Vec<_>>();
+            policy.assign(peers);
+
+            let name = policy.name();
+            for ((peer, before), label) in peers.iter().zip(before).zip(&mut labels) {
+                if peer.propagation == before {
+                    continue
+                }
+                *label = name;
+                let metrics = Self::metrics_for(&mut self.metrics, name);
+                match peer.propagation {
+                    PeerPropagation::Full => metrics.peers_upgraded_to_full.increment(1),
+                    PeerPropagation::Hashes => metrics.peers_downgraded_to_hashes.increment(1),
+                    PeerPropagation::Skip => metrics.peers_skipped.increment(1),
+                }
+            }
+        }
+        labels
+    }
+
+    /// Records the transactions propagated to a peer.
+    pub(crate) fn on_transactions_propagated(
+        &mut self,
+        label: &'static str,
+        peer_id: PeerId,
+        full_transactions: usize,
+        full_bytes: usize,
+        hashes: usize,
+    ) {
+        let metrics = self.metrics(label);
+        metrics.full_transactions.increment(full_transactions as u64);
+        metrics.full_transactions_bytes.increment(full_bytes as u64);
+        metrics.announced_hashes.increment(hashes as u64);
+
+        for policy in &mut self.policies {
+            policy.on_transactions_propagated(peer_id, full_bytes);
+        }
+    }
+
+    /// Records the hashes a peer requested after they were announced.
+    pub(crate) fn on_hashes_requested(&mut self, label: &'static str, hashes: usize) {
+        self.metrics(label).requested_hashes.increment(hashes as u64);
+    }
+
+    pub(crate) fn on_transactions_received(&mut self, peer_id: PeerId, hashes: &[TxHash]) {
+        for policy in &mut self.policies {
+            policy.on_transactions_received(peer_id, hashes);
+        }
+    }
+
+    pub(crate) fn on_transaction_mined(&mut self, hash: TxHash) {
+        for policy in self.policies.iter_mut().filter(|policy| policy.tracks_mined_transactions()) {
+            policy.on_transaction_mined(hash);
+        }
+    }
+
+    pub(crate) fn on_peer_disconnected(&mut self, peer_id: PeerId) {
+        for policy in &mut self.policies {
+            policy.on_peer_disconnected(peer_id);
+        }
+    }
+
+    fn metrics(&mut self, label: &'static str) -> &PropagationPolicyMetrics {
+        Self::metrics_for(&mut self.metrics, label)
+    }
+
+    fn metrics_for<'a>(
+        metrics: &'a mut HashMap<&'static str, PropagationPolicyMetrics>,
+        label: &'static str,
+    ) -> &'a PropagationPolicyMetrics {
+        metrics
+            .entry(label)
+            .or_insert_with(|| PropagationPolicyMetrics::new_with_labels(&[("policy", label)]))
+    }
+}
+
+/// Metrics of the peers whose propagation was last changed by a
+/// [`TransactionPropagationPolicy`], labeled by the policy.
+///
+/// Comparing the ratio of requested to announced hashes, and the full transactions sent, across
+/// the policies shows how effective their assignments are.
+#[derive(Metrics)]
+#[metrics(scope = "network.transactions.propagation")]
+pub(crate) struct PropagationPolicyMetrics {
+    /// Total number of peers the policy assigned full transactions to, that would have received
+    /// hashes.
+    pub(crate) peers_upgraded_to_full: Counter,
+    /// Total number of peers the policy assigned hashes to, that would have received full
+    /// transactions.
+    pub(crate) peers_downgraded_to_hashes: Counter,
+    /// Total number of peers the policy excluded from propagation.
+    pub(crate) peers_skipped: Counter,
+    /// Total number of full transactions sent to the peers.
+    pub(crate) full_transactions: Counter,
+    /// Total encoded length of the full transactions sent to the peers.
+    pub(crate) full_transactions_bytes: Counter,
+    /// Total number of transaction hashes announced to the peers.
+    pub(crate) announced_hashes: Counter,
+    /// Total number of transactions requested by the peers, after they were announced.
+    pub(crate) requested_hashes: Counter,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assignment(
+        kind: PeerKind,
+        direction: Direction,
+        propagation: PeerPropagation,
+    ) -> PeerAssignment {
+        PeerAssignment {
+            peer_id: PeerId::random(),
+            kind,
+            direction,
+            version: EthVersion::Eth68,
+            propagation,
+        }
+    }
+
+    #[test]
+    fn trusted_full_and_inbound_hashes() {
+        let mut peers = vec![
+            assignment(PeerKind::Trusted, Direction::Incoming, PeerPropagation::Hashes),
+            assignment(PeerKind::Basic, Direction::Incoming, PeerPropagation::Full),
+            assignment(
+                PeerKind::Basic,
+                Direction::Outgoing(PeerId::random()),
+                PeerPropagation::Full,
+            ),
+        ];
+
+        let mut policies = PropagationPolicies::default();
+        policies.push(Box::new(InboundHashesPolicy));
+        policies.push(Box::new(TrustedFullPolicy));
+        let labels = policies.assign(&mut peers);
+
+        assert_eq!(
+            peers.iter().map(|peer| peer.propagation).collect::<Vec<_>>(),
+            [PeerPropagation::Full, PeerPropagation::Hashes, PeerPropagation::Full]
+        );
+        assert_eq!(labels, ["trusted_full", "inbound_hashes", DEFAULT_PROPAGATION_POLICY_LABEL]);
+    }
+
+    #[test]
+    fn useful_peers_get_full_transactions() {
+        let mut peers = (0..3)
+            .map(|idx| {
+                let propagation =
+                    if idx == 0 { PeerPropagation::Full } else { PeerPropagation::Hashes };
+                assignment(PeerKind::Basic, Direction::Incoming, propagation)
+            })
+            .collect::<Vec<_>>();
+
+        let mut policy = UsefulPeersPolicy::default();
+        let (mined, other) = (TxHash::random(), TxHash::random());
+        policy.on_transactions_received(peers[2].peer_id, &[mined, other]);
+        // only the first announcement counts
+        policy.on_transactions_received(peers[1].peer_id, &[mined]);
+        policy.on_transaction_mined(mined);
+        assert_eq!(policy.score(&peers[2].peer_id), 1);
+        assert_eq!(policy.score(&peers[1].peer_id), 0);
+
+        policy.assign(&mut peers);
+        assert_eq!(
+            peers.iter().map(|peer| peer.propagation).collect::<Vec<_>>(),
+            [PeerPropagation::Hashes, PeerPropagation::Hashes, PeerPropagation::Full]
+        );
+    }
+
+    #[test]
+    fn byte_budget() {
+        let mut peers =
+            vec![assignment(PeerKind::Basic, Direction::Incoming, PeerPropagation::Full)];
+        let mut policy = ByteBudgetPolicy::new(100, Duration::from_secs(60));
+
+        policy.on_transactions_propagated(peers[0].peer_id, 60);
+        policy.assign(&mut peers);
+        assert_eq!(peers[0].propagation, PeerPropagation::Full);
+
+        policy.on_transactions_propagated(peers[0].peer_id, 60);
+        policy.assign(&mut peers);
+        assert_eq!(peers[0].propagation, PeerPropagation::Hashes);
+    }
+}
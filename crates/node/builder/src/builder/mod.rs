@@ -38,7 +38,13 @@ use reth_provider::{
     BlockReader, ChainSpecProvider, FullProvider,
 };
 use reth_tasks::TaskExecutor;
-use reth_transaction_pool::{PoolConfig, PoolTransaction, TransactionPool};
+use reth_transaction_pool::{
+    validate::{
+        AdmissionPolicies, DenyListPolicy, MaxCalldataPolicy, MinTipPolicy, SelectorDenyListPolicy,
+        SenderRateLimitPolicy,
+    },
+    PoolConfig, PoolTransaction, TransactionPool,
+};
 use secp256k1::SecretKey;
 use std::sync::Arc;
 use tracing::{info, trace, warn};
@@ -634,6 +640,42 @@ impl<Node: FullNodeTypes> BuilderContext<Node> {
         self.config().txpool.pool_config()
     }
 
+    /// Returns the transaction pool admission policies configured in the `reth.toml` config.
+    pub fn admission_policies(&self) -> eyre::Result<AdmissionPolicies> {
+        let config = &self.reth_config().txpool.admission;
+        let mut policies = AdmissionPolicies::new();
+
+        if config.deny_list_file.is_some() || !config.denied_addresses.is_empty() {
+            let mut deny_list = match &config.deny_list_file {
+                Some(path) => DenyListPolicy::from_file(path).map_err(|err| {
+                    eyre::eyre!("failed to read deny list {}: {err}", path.display())
+                })?,
+                None => DenyListPolicy::default(),
+            };
+            deny_list.extend(config.denied_addresses.iter().copied());
+            info!(target: "reth::cli", addresses = deny_list.len(), "Loaded transaction pool deny list");
+            policies.push(deny_list);
+        }
+        if let Some(rate_limit) = config.sender_rate_limit {
+            policies
+                .push(SenderRateLimitPolicy::new(rate_limit.max_transactions, rate_limit.interval));
+        }
+        if config.min_priority_fee.is_some() || config.min_blob_priority_fee.is_some() {
+            policies.push(MinTipPolicy::new(
+                config.min_priority_fee.map(Into::into),
+                config.min_blob_priority_fee.map(Into::into),
+            ));
+        }
+        if !config.denied_selectors.is_empty() {
+            policies.push(SelectorDenyListPolicy::new(config.denied_selectors.iter().copied()));
+        }
+        if let Some(max_size) = config.max_calldata_size {
+            policies.push(MaxCalldataPolicy::new(max_size));
+        }
+
+        Ok(policies)
+    }
+
     /// Loads `EnvKzgSettings::Default`.
     pub const fn kzg_settings(&self) -> eyre::Result<EnvKzgSettings> {
         Ok(EnvKzgSettings::Default)
@@ -692,10 +734,15 @@ impl<Node: FullNodeTypes> BuilderContext<Node> {
         Node::Provider:
             BlockReader<Receipt = N::Receipt, Block = N::Block, Header = N::BlockHeader>,
     {
-        let (handle, network, txpool, eth) = builder
-            .transactions(pool, tx_config)
-            .request_handler(self.provider().clone())
-            .split_with_handle();
+        let mut builder =
+            builder.transactions(pool, tx_config).request_handler(self.provider().clone());
+
+        if self.config().network.snap_serve {
+            let snap = builder.snap_request_handler(self.provider().clone());
+            self.executor.spawn_critical("p2p snap request handler", snap);
+        }
+
+        let (handle, network, txpool, eth) = builder.split_with_handle();
 
         self.executor.spawn_critical("p2p txpool", txpool);
         self.executor.spawn_critical("p2p eth request handler", eth);
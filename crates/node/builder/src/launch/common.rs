@@ -17,10 +17,12 @@ use reth_db_api::{database::Database, database_metrics::DatabaseMetrics};
 use reth_db_common::init::{init_genesis, InitStorageError};
 use reth_downloaders::{bodies::noop::NoopBodiesDownloader, headers::noop::NoopHeaderDownloader};
 use reth_engine_local::MiningMode;
-use reth_engine_tree::tree::{InvalidBlockHook, InvalidBlockHooks, NoopInvalidBlockHook};
+use reth_engine_tree::tree::{InvalidBlockHook, InvalidBlockHooks};
 use reth_evm::noop::NoopBlockExecutorProvider;
 use reth_fs_util as fs;
-use reth_invalid_block_hooks::InvalidBlockWitnessHook;
+use reth_invalid_block_hooks::{
+    BadBlockHook, BadBlockStore, InvalidBlockWitnessHook, DEFAULT_BAD_BLOCKS_LIMIT,
+};
 use reth_network_p2p::headers::client::HeadersClient;
 use reth_node_api::{
     FullNodePrimitives, FullNodeTypes, NodePrimitives, NodeTypes, NodeTypesWithDB,
@@ -873,16 +875,22 @@ where
     pub fn invalid_block_hook(
         &self,
     ) -> eyre::Result<Box<dyn InvalidBlockHook<<T::Types as NodeTypes>::Primitives>>> {
+        // invalid blocks are always recorded, so they can be inspected over RPC, the re-execution
+        // happens on a separate thread to not block the engine
+        let bad_block_hook = Box::new(BadBlockHook::<<T::Types as NodeTypes>::Primitives>::spawn(
+            self.blockchain_db().clone(),
+            self.components().evm_config().clone(),
+            BadBlockStore::new(self.data_dir().bad_blocks(), DEFAULT_BAD_BLOCKS_LIMIT),
+        )) as Box<dyn InvalidBlockHook<_>>;
+
         let Some(ref hook) = self.node_config().debug.invalid_block_hook else {
-            return Ok(Box::new(NoopInvalidBlockHook::default()))
+            return Ok(bad_block_hook)
         };
         let healthy_node_rpc_client = self.get_healthy_node_client()?;
 
         let output_directory = self.data_dir().invalid_block_hooks();
-        let hooks = hook
-            .iter()
-            .copied()
-            .map(|hook| {
+        let hooks = std::iter::once(Ok(bad_block_hook))
+            .chain(hook.iter().copied().map(|hook| {
                 let output_directory = output_directory.join(hook.to_string());
                 fs::create_dir_all(&output_directory)?;
 
@@ -897,7 +905,7 @@ where
                         eyre::bail!("invalid block hook {hook:?} is not implemented yet")
                     }
                 } as Box<dyn InvalidBlockHook<_>>)
-            })
+            }))
             .collect::<Result<_, _>>()?;
 
         Ok(Box::new(InvalidBlockHooks(hooks)))
@@ -1062,6 +1070,8 @@ mod tests {
                     storage_history_full: false,
                     storage_history_distance: None,
                     storage_history_before: None,
+                    bodies_history_distance: None,
+                    bodies_history_before: None,
                     receipts_log_filter: vec![],
                 },
                 ..NodeConfig::test()
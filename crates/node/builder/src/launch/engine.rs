@@ -7,7 +7,7 @@ use reth_beacon_consensus::{
 };
 use reth_chainspec::EthChainSpec;
 use reth_consensus_debug_client::{DebugConsensusClient, EtherscanBlockProvider};
-use reth_engine_local::{LocalEngineService, LocalPayloadAttributesBuilder};
+use reth_engine_local::{LocalDevMiner, LocalEngineService, LocalPayloadAttributesBuilder};
 use reth_engine_service::service::{ChainEvent, EngineService};
 use reth_engine_tree::{
     engine::{EngineApiRequest, EngineRequestHandler},
@@ -92,7 +92,7 @@ where
         let NodeBuilderWithComponents {
             adapter: NodeTypesAdapter { database },
             components_builder,
-            add_ons: AddOns { hooks, exexs: installed_exex, add_ons },
+            add_ons: AddOns { hooks, exexs: installed_exex, mut add_ons },
             config,
         } = target;
         let NodeHooks { on_component_initialized, on_node_started, .. } = hooks;
@@ -235,6 +235,10 @@ where
                 ctx.dev_mining_mode(ctx.components().pool()),
                 LocalPayloadAttributesBuilder::new(ctx.chain_spec()),
             );
+            add_ons.hooks_mut().set_dev_miner(Arc::new(LocalDevMiner::new(
+                eth_service.miner_handle(),
+                ctx.components().pool().clone(),
+            )));
 
             Either::Left(eth_service)
         } else {
@@ -284,6 +288,7 @@ where
             ),
         );
 
+        add_ons.hooks_mut().set_chain_db(Arc::new(ctx.blockchain_db().clone()));
         let RpcHandle { rpc_server_handles, rpc_registry } =
             add_ons.launch_add_ons(add_ons_ctx).await?;
 
@@ -7,10 +7,14 @@ use std::{
     ops::{Deref, DerefMut},
 };
 
-use alloy_rpc_types::engine::ClientVersionV1;
+use alloy_network::Network;
+use alloy_rpc_types::{engine::ClientVersionV1, TransactionReceipt};
 use futures::TryFutureExt;
+use reth_bundle_pool::BundlePool;
+use reth_chainspec::EthChainSpec;
+use reth_invalid_block_hooks::{BadBlockStore, DEFAULT_BAD_BLOCKS_LIMIT};
 use reth_node_api::{
-    AddOnsContext, BlockTy, EngineValidator, FullNodeComponents, NodeAddOns, NodeTypes,
+    AddOnsContext, BlockTy, DevMiner, EngineValidator, FullNodeComponents, NodeAddOns, NodeTypes,
     NodeTypesWithEngine,
 };
 use reth_node_core::{
@@ -19,21 +23,26 @@ use reth_node_core::{
 };
 use reth_payload_builder::PayloadStore;
 use reth_primitives::{EthPrimitives, PooledTransaction};
-use reth_provider::providers::ProviderNodeTypes;
+use reth_provider::{providers::ProviderNodeTypes, CanonStateSubscriptions};
 use reth_rpc::{
     eth::{EthApiTypes, FullEthApiServer},
-    EthApi,
+    ChainDb, EthApi, EthUserOperation,
+};
+use reth_rpc_api::{
+    eth::helpers::AddDevSigners, DebugApiServer, EthUserOperationApiServer, RethApiServer,
 };
-use reth_rpc_api::eth::helpers::AddDevSigners;
 use reth_rpc_builder::{
     auth::{AuthRpcModule, AuthServerHandle},
     config::RethRpcServerConfig,
-    RpcModuleBuilder, RpcRegistryInner, RpcServerHandle, TransportRpcModules,
+    RethRpcModule, RpcModuleBuilder, RpcRegistryInner, RpcServerHandle, TransportRpcModules,
 };
 use reth_rpc_engine_api::{capabilities::EngineCapabilities, EngineApi};
 use reth_tasks::TaskExecutor;
 use reth_tracing::tracing::{debug, info};
 use reth_transaction_pool::{PoolTransaction, TransactionPool};
+use reth_user_operation_pool::{
+    maintain_user_operation_pool, BundlerConfig, UserOperationPool, UserOperationPoolConfig,
+};
 use std::sync::Arc;
 
 use crate::EthApiBuilderCtx;
@@ -55,6 +64,14 @@ pub struct RpcHooks<Node: FullNodeComponents, EthApi> {
     pub on_rpc_started: Box<dyn OnRpcStarted<Node, EthApi>>,
     /// Hooks to run to configure RPC server API.
     pub extend_rpc_modules: Box<dyn ExtendRpcModules<Node, EthApi>>,
+    /// The miner of a dev node, used to serve the `anvil_` and `hardhat_` namespaces.
+    pub(crate) dev_miner: Option<Arc<dyn DevMiner>>,
+    /// Access to the database of the node, used to serve the database and state endpoints of the
+    /// `debug_` namespace.
+    pub(crate) chain_db: Option<Arc<dyn ChainDb>>,
+    /// The bundle pool of the payload builder, used to serve the bundle submission endpoints of
+    /// the `eth_` and `mev_` namespaces.
+    pub(crate) bundle_pool: Option<BundlePool>,
 }
 
 impl<Node, EthApi> Default for RpcHooks<Node, EthApi>
@@ -63,7 +80,13 @@ where
     EthApi: EthApiTypes,
 {
     fn default() -> Self {
-        Self { on_rpc_started: Box::<()>::default(), extend_rpc_modules: Box::<()>::default() }
+        Self {
+            on_rpc_started: Box::<()>::default(),
+            extend_rpc_modules: Box::<()>::default(),
+            dev_miner: None,
+            chain_db: None,
+            bundle_pool: None,
+        }
     }
 }
 
@@ -109,6 +132,18 @@ where
         self.set_extend_rpc_modules(hook);
         self
     }
+
+    /// Sets the miner of a dev node.
+    pub(crate) fn set_dev_miner(&mut self, miner: Arc<dyn DevMiner>) -> &mut Self {
+        self.dev_miner = Some(miner);
+        self
+    }
+
+    /// Sets the database that backs the database and state endpoints of the `debug_` namespace.
+    pub(crate) fn set_chain_db(&mut self, chain_db: Arc<dyn ChainDb>) -> &mut Self {
+        self.chain_db = Some(chain_db);
+        self
+    }
 }
 
 impl<Node, EthApi> fmt::Debug for RpcHooks<Node, EthApi>
@@ -389,6 +424,15 @@ impl<Node: FullNodeComponents, EthApi: EthApiTypes, EV> RpcAddOns<Node, EthApi,
         self.hooks.set_extend_rpc_modules(hook);
         self
     }
+
+    /// Sets the bundle pool of the payload builder.
+    ///
+    /// If the node is started with `--builder.bundles`, the bundles of `eth_sendBundle` and
+    /// `mev_sendBundle` are added to this pool.
+    pub fn with_bundle_pool(mut self, pool: BundlePool) -> Self {
+        self.hooks.bundle_pool = Some(pool);
+        self
+    }
 }
 
 impl<Node, EthApi, EV> Default for RpcAddOns<Node, EthApi, EV>
@@ -439,7 +483,7 @@ where
         let engine_api = EngineApi::new(
             node.provider().clone(),
             config.chain.clone(),
-            beacon_engine_handle,
+            beacon_engine_handle.clone(),
             PayloadStore::new(node.payload_builder().clone()),
             node.pool().clone(),
             Box::new(node.task_executor().clone()),
@@ -453,7 +497,10 @@ where
         let module_config = config.rpc.transport_rpc_module_config();
         debug!(target: "reth::cli", http=?module_config.http(), ws=?module_config.ws(), "Using RPC module config");
 
-        let (mut modules, mut auth_module, registry) = RpcModuleBuilder::default()
+        let RpcHooks { on_rpc_started, extend_rpc_modules, dev_miner, chain_db, bundle_pool } =
+            hooks;
+
+        let mut registry = RpcModuleBuilder::default()
             .with_provider(node.provider().clone())
             .with_pool(node.pool().clone())
             .with_network(node.network().clone())
@@ -462,18 +509,45 @@ where
             .with_evm_config(node.evm_config().clone())
             .with_block_executor(node.block_executor().clone())
             .with_consensus(node.consensus().clone())
-            .build_with_auth_server(
-                module_config,
-                engine_api,
+            .into_registry(
+                module_config.config().cloned().unwrap_or_default(),
                 eth_api_builder,
                 Arc::new(engine_validator),
             );
+        // the dev namespaces are backed by the local miner if the node is in dev mode
+        if let Some(miner) = dev_miner {
+            registry.set_dev_miner(miner);
+        }
+        // bundles are submitted to the payload builder if the bundle pool is enabled
+        if let Some(pool) = bundle_pool.filter(|_| config.builder.bundles) {
+            registry.set_bundle_pool(pool);
+        }
+        let mut modules = registry.create_transport_rpc_modules(module_config);
+        let mut auth_module = registry.create_auth_module(engine_api);
 
         // in dev mode we generate 20 random dev-signer accounts
         if config.dev.dev {
             registry.eth_api().with_dev_accounts();
         }
 
+        // serve the bad blocks recorded by the engine and back the admin endpoints with the node
+        let bad_blocks =
+            BadBlockStore::new(config.datadir().bad_blocks(), DEFAULT_BAD_BLOCKS_LIMIT);
+        let mut debug_api = registry
+            .debug_api()
+            .with_bad_blocks(Arc::new(bad_blocks))
+            .with_set_head(Arc::new(beacon_engine_handle));
+        if let Some(chain_db) = chain_db {
+            debug_api = debug_api.with_chain_db(chain_db);
+        }
+        modules.replace_if_module_configured(RethRpcModule::Debug, debug_api.into_rpc())?;
+
+        // serve the archived blob sidecars, if the archive is enabled
+        if let Some(blob_archive) = config.txpool.blob_archive(config.datadir().blob_archive()) {
+            let reth_api = registry.reth_api().with_blob_archive(blob_archive);
+            modules.replace_if_module_configured(RethRpcModule::Reth, reth_api.into_rpc())?;
+        }
+
         let mut registry = RpcRegistry { registry };
         let ctx = RpcContext {
             node: node.clone(),
@@ -483,8 +557,6 @@ where
             auth_module: &mut auth_module,
         };
 
-        let RpcHooks { on_rpc_started, extend_rpc_modules } = hooks;
-
         ext(ctx.modules, ctx.auth_module)?;
         extend_rpc_modules.extend_rpc_modules(ctx)?;
 
@@ -538,7 +610,7 @@ where
         Types: ProviderNodeTypes<Primitives = EthPrimitives>,
         Pool: TransactionPool<Transaction: PoolTransaction<Pooled = PooledTransaction>>,
     >,
-    EthApi: EthApiTypes
+    EthApi: EthApiTypes<NetworkTypes: Network<ReceiptResponse = TransactionReceipt>>
         + FullEthApiServer<Provider = N::Provider, Pool = N::Pool, Network = N::Network>
         + AddDevSigners
         + Unpin
@@ -547,11 +619,55 @@ where
 {
     type Handle = RpcHandle<N, EthApi>;
 
-    async fn launch_add_ons(self, ctx: AddOnsContext<'_, N>) -> eyre::Result<Self::Handle> {
+    async fn launch_add_ons(mut self, ctx: AddOnsContext<'_, N>) -> eyre::Result<Self::Handle> {
+        // serve the bundler methods of the user operation pool in the `eth` namespace
+        if ctx.config.txpool.user_operations {
+            let pool = spawn_user_operation_pool(&ctx)?;
+            let extend_rpc_modules =
+                std::mem::replace(&mut self.hooks.extend_rpc_modules, Box::<()>::default());
+            self.hooks.set_extend_rpc_modules(move |ctx: RpcContext<'_, N, EthApi>| {
+                let user_operations = EthUserOperation::new(ctx.registry.eth_api().clone(), pool);
+                ctx.modules
+                    .merge_if_module_configured(RethRpcModule::Eth, user_operations.into_rpc())?;
+                extend_rpc_modules.extend_rpc_modules(ctx)
+            });
+        }
+
         self.launch_add_ons_with(ctx, |_, _| Ok(())).await
     }
 }
 
+/// Creates the [`UserOperationPool`] and spawns its maintenance task, which also bundles the user
+/// operations if a bundler key is configured.
+fn spawn_user_operation_pool<N>(ctx: &AddOnsContext<'_, N>) -> eyre::Result<UserOperationPool>
+where
+    N: FullNodeComponents<Types: ProviderNodeTypes<Primitives = EthPrimitives>>,
+{
+    let bundler = ctx
+        .config
+        .txpool
+        .user_operations_bundler_key
+        .as_deref()
+        .map(BundlerConfig::from_key_file)
+        .transpose()?;
+    let pool = UserOperationPool::new(UserOperationPoolConfig::new(ctx.config.chain.chain().id()));
+
+    let node = &ctx.node;
+    info!(target: "reth::cli", entry_point=%pool.config().entry_point, bundler=bundler.is_some(), "User operation pool initialized");
+    node.task_executor().spawn_critical(
+        "user operation pool maintenance task",
+        maintain_user_operation_pool(
+            pool.clone(),
+            node.provider().clone(),
+            node.pool().clone(),
+            node.provider().canonical_state_stream(),
+            bundler,
+        ),
+    );
+
+    Ok(pool)
+}
+
 /// Helper trait implemented for add-ons producing [`RpcHandle`]. Used by common node launcher
 /// implementations.
 pub trait RethRpcAddOns<N: FullNodeComponents>:
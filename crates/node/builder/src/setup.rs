@@ -16,7 +16,11 @@ use reth_network_p2p::{
 };
 use reth_node_api::{BodyTy, HeaderTy};
 use reth_provider::{providers::ProviderNodeTypes, ProviderFactory};
-use reth_stages::{prelude::DefaultStages, stages::ExecutionStage, Pipeline, StageSet};
+use reth_stages::{
+    prelude::DefaultStages,
+    stages::{ExecutionStage, ExecutionStateRoot},
+    Pipeline, StageSet,
+};
 use reth_static_file::StaticFileProducer;
 use reth_tasks::TaskExecutor;
 use reth_tracing::tracing::debug;
@@ -100,6 +104,18 @@ where
 
     let prune_modes = prune_config.map(|prune| prune.segments).unwrap_or_default();
 
+    let mut execution_stage = ExecutionStage::new(
+        executor.clone(),
+        stage_config.execution.into(),
+        stage_config.execution_external_clean_threshold(),
+        prune_modes.clone(),
+        exex_manager_handle,
+    );
+    if let Some(chunk_size) = stage_config.execution.state_root_chunk_size {
+        execution_stage = execution_stage
+            .with_state_root(ExecutionStateRoot::new(provider_factory.clone(), chunk_size));
+    }
+
     let pipeline = builder
         .with_tip_sender(tip_tx)
         .with_metrics_tx(metrics_tx)
@@ -110,17 +126,11 @@ where
                 Arc::clone(&consensus),
                 header_downloader,
                 body_downloader,
-                executor.clone(),
-                stage_config.clone(),
-                prune_modes.clone(),
-            )
-            .set(ExecutionStage::new(
                 executor,
-                stage_config.execution.into(),
-                stage_config.execution_external_clean_threshold(),
+                stage_config.clone(),
                 prune_modes,
-                exex_manager_handle,
-            )),
+            )
+            .set(execution_stage),
         )
         .build(provider_factory, static_file_producer);
 
@@ -4,11 +4,13 @@ use std::{
     net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, SocketAddrV4, SocketAddrV6},
     ops::Not,
     path::PathBuf,
+    time::Duration,
 };
 
 use clap::Args;
+use humantime::parse_duration;
 use reth_chainspec::EthChainSpec;
-use reth_config::Config;
+use reth_config::{Config, TxPropagationConfig, TxPropagationPolicy};
 use reth_discv4::{NodeRecord, DEFAULT_DISCOVERY_ADDR, DEFAULT_DISCOVERY_PORT};
 use reth_discv5::{
     discv5::ListenConfig, DEFAULT_COUNT_BOOTSTRAP_LOOKUPS, DEFAULT_DISCOVERY_V5_PORT,
@@ -26,7 +28,7 @@ use reth_network::{
                 DEFAULT_MAX_COUNT_PENDING_POOL_IMPORTS, DEFAULT_MAX_COUNT_TRANSACTIONS_SEEN_BY_PEER,
             },
         },
-        TransactionFetcherConfig, TransactionsManagerConfig,
+        TransactionFetcherConfig, TransactionPropagationPolicyKind, TransactionsManagerConfig,
         DEFAULT_SOFT_LIMIT_BYTE_SIZE_POOLED_TRANSACTIONS_RESP_ON_PACK_GET_POOLED_TRANSACTIONS_REQ,
         SOFT_LIMIT_BYTE_SIZE_POOLED_TRANSACTIONS_RESPONSE,
     },
@@ -149,6 +151,35 @@ pub struct NetworkArgs {
     #[arg(long = "max-tx-pending-fetch", value_name = "COUNT", default_value_t = DEFAULT_MAX_CAPACITY_CACHE_PENDING_FETCH, verbatim_doc_comment)]
     pub max_capacity_cache_txns_pending_fetch: u32,
 
+    /// Comma separated policies that adjust how new pending transactions are propagated to
+    /// peers, applied in order.
+    ///
+    /// Possible values: useful-peers, trusted-full, inbound-hashes, byte-budget
+    ///
+    /// Overrides the policies of the `[tx_propagation]` section of the config.
+    #[arg(long = "tx-propagation-policies", value_name = "POLICIES", value_delimiter = ',')]
+    pub tx_propagation_policies: Option<Vec<TxPropagationPolicy>>,
+
+    /// Max encoded length of the full transactions sent to a peer per interval, if the
+    /// `byte-budget` propagation policy is enabled.
+    ///
+    /// Default is 1 MiB, or the value of the config.
+    #[arg(long = "tx-propagation-byte-budget", value_name = "BYTES")]
+    pub tx_propagation_byte_budget: Option<usize>,
+
+    /// The interval the byte budget of the `byte-budget` propagation policy is reset at, e.g.
+    /// `500ms` or `1s`.
+    ///
+    /// Default is 1s, or the value of the config.
+    #[arg(long = "tx-propagation-byte-budget-interval", value_name = "DURATION", value_parser = parse_duration)]
+    pub tx_propagation_byte_budget_interval: Option<Duration>,
+
+    /// Serve the `snap/1` protocol to peers that snap sync.
+    ///
+    /// The states of the last 128 canonical blocks are served.
+    #[arg(long = "snap.serve", default_value_t = false)]
+    pub snap_serve: bool,
+
     /// Name of network interface used to communicate with peers.
     ///
     /// If flag is set, but no value is passed, the default interface for docker `eth0` is tried.
@@ -227,6 +258,7 @@ impl NetworkArgs {
             ),
             max_transactions_seen_by_peer_history: self.max_seen_tx_history,
             propagation_mode: Default::default(),
+            propagation_policies: self.tx_propagation_policies(&config.tx_propagation),
         };
 
         // Configure basic network stack
@@ -266,6 +298,32 @@ impl NetworkArgs {
             ))
     }
 
+    /// Returns the transaction propagation policies.
+    ///
+    /// The CLI arguments take precedence over the `[tx_propagation]` section of the config.
+    pub fn tx_propagation_policies(
+        &self,
+        config: &TxPropagationConfig,
+    ) -> Vec<TransactionPropagationPolicyKind> {
+        let policies = self.tx_propagation_policies.as_ref().unwrap_or(&config.policies);
+        policies
+            .iter()
+            .map(|policy| match policy {
+                TxPropagationPolicy::UsefulPeers => TransactionPropagationPolicyKind::UsefulPeers,
+                TxPropagationPolicy::TrustedFull => TransactionPropagationPolicyKind::TrustedFull,
+                TxPropagationPolicy::InboundHashes => {
+                    TransactionPropagationPolicyKind::InboundHashes
+                }
+                TxPropagationPolicy::ByteBudget => TransactionPropagationPolicyKind::ByteBudget {
+                    bytes: self.tx_propagation_byte_budget.unwrap_or(config.byte_budget),
+                    interval: self
+                        .tx_propagation_byte_budget_interval
+                        .unwrap_or(config.byte_budget_interval),
+                },
+            })
+            .collect()
+    }
+
     /// If `no_persist_peers` is false then this returns the path to the persistent peers file path.
     pub fn persistent_peers_file(&self, peers_file: PathBuf) -> Option<PathBuf> {
         self.no_persist_peers.not().then_some(peers_file)
@@ -331,6 +389,10 @@ impl Default for NetworkArgs {
             max_pending_pool_imports: DEFAULT_MAX_COUNT_PENDING_POOL_IMPORTS,
             max_seen_tx_history: DEFAULT_MAX_COUNT_TRANSACTIONS_SEEN_BY_PEER,
             max_capacity_cache_txns_pending_fetch: DEFAULT_MAX_CAPACITY_CACHE_PENDING_FETCH,
+            tx_propagation_policies: None,
+            tx_propagation_byte_budget: None,
+            tx_propagation_byte_budget_interval: None,
+            snap_serve: false,
             net_if: None,
         }
     }
@@ -594,6 +656,46 @@ mod tests {
         }
     }
 
+    #[test]
+    fn parse_tx_propagation_args() {
+        let config = TxPropagationConfig {
+            policies: vec![TxPropagationPolicy::UsefulPeers],
+            ..Default::default()
+        };
+
+        let args = CommandParser::<NetworkArgs>::parse_from(["reth"]).args;
+        assert_eq!(
+            args.tx_propagation_policies(&config),
+            vec![TransactionPropagationPolicyKind::UsefulPeers]
+        );
+
+        let args = CommandParser::<NetworkArgs>::parse_from([
+            "reth",
+            "--tx-propagation-policies",
+            "inbound-hashes,byte-budget",
+            "--tx-propagation-byte-budget-interval",
+            "500ms",
+        ])
+        .args;
+        assert_eq!(
+            args.tx_propagation_policies(&config),
+            vec![
+                TransactionPropagationPolicyKind::InboundHashes,
+                TransactionPropagationPolicyKind::ByteBudget {
+                    bytes: config.byte_budget,
+                    interval: Duration::from_millis(500),
+                },
+            ]
+        );
+
+        assert!(CommandParser::<NetworkArgs>::try_parse_from([
+            "reth",
+            "--tx-propagation-policies",
+            "all"
+        ])
+        .is_err());
+    }
+
     #[cfg(not(feature = "optimism"))]
     #[test]
     fn network_args_default_sanity_test() {
@@ -35,6 +35,21 @@ pub struct PayloadBuilderArgs {
     /// Maximum number of tasks to spawn for building a payload.
     #[arg(long = "builder.max-tasks", default_value = "3", value_parser = RangedU64ValueParser::<usize>::new().range(1..))]
     pub max_payload_tasks: usize,
+
+    /// Flag to enable the bundle pool, whose bundles are included by the payload builder ahead
+    /// of the pool transactions.
+    ///
+    /// The bundles are submitted with `eth_sendBundle` and `mev_sendBundle`.
+    #[arg(long = "builder.bundles")]
+    pub bundles: bool,
+
+    /// Flag to order the pool transactions by their simulated payment to the fee recipient per
+    /// unit of gas, instead of by their priority fee.
+    ///
+    /// The pending transactions are simulated on top of the parent of the payload that is being
+    /// built, so this also includes direct transfers to the fee recipient.
+    #[arg(long = "builder.simulated-ordering")]
+    pub simulated_ordering: bool,
 }
 
 impl Default for PayloadBuilderArgs {
@@ -45,6 +60,8 @@ impl Default for PayloadBuilderArgs {
             interval: Duration::from_secs(1),
             deadline: SLOT_DURATION,
             max_payload_tasks: 3,
+            bundles: false,
+            simulated_ordering: false,
         }
     }
 }
@@ -166,6 +183,23 @@ mod tests {
         assert_eq!(args.interval, Duration::from_secs(50));
     }
 
+    #[test]
+    fn test_args_with_bundles() {
+        let args =
+            CommandParser::<PayloadBuilderArgs>::parse_from(["reth", "--builder.bundles"]).args;
+        assert!(args.bundles);
+    }
+
+    #[test]
+    fn test_args_with_simulated_ordering() {
+        let args = CommandParser::<PayloadBuilderArgs>::parse_from([
+            "reth",
+            "--builder.simulated-ordering",
+        ])
+        .args;
+        assert!(args.simulated_ordering);
+    }
+
     #[test]
     fn test_args_with_ms_interval() {
         let args =
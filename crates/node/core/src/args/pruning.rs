@@ -82,6 +82,24 @@ pub struct PruningArgs {
     #[arg(long = "prune.storagehistory.before", value_name = "BLOCK_NUMBER", conflicts_with_all = &["storage_history_full", "storage_history_distance"])]
     pub storage_history_before: Option<BlockNumber>,
 
+    // Bodies History
+    /// Prune transactions and receipts before the `head-N` block number, while keeping all
+    /// headers. In other words, keep the bodies of the last N + 1 blocks.
+    #[arg(
+        long = "prune.bodies.distance",
+        value_name = "BLOCKS",
+        conflicts_with = "bodies_history_before"
+    )]
+    pub bodies_history_distance: Option<u64>,
+    /// Prune transactions and receipts before the specified block number, while keeping all
+    /// headers. The specified block number is not pruned.
+    #[arg(
+        long = "prune.bodies.before",
+        value_name = "BLOCK_NUMBER",
+        conflicts_with = "bodies_history_distance"
+    )]
+    pub bodies_history_before: Option<BlockNumber>,
+
     // Receipts Log Filter
     /// Configure receipts log filter. Format:
     /// <`address`>:<`prune_mode`>[,<`address`>:<`prune_mode`>...] Where <`prune_mode`> can be
@@ -111,6 +129,7 @@ impl PruningArgs {
                         .or(Some(PruneMode::Distance(MINIMUM_PRUNING_DISTANCE))),
                     account_history: Some(PruneMode::Distance(MINIMUM_PRUNING_DISTANCE)),
                     storage_history: Some(PruneMode::Distance(MINIMUM_PRUNING_DISTANCE)),
+                    bodies_history: None,
                     receipts_log_filter: ReceiptsLogPruneConfig(
                         chain_spec
                             .deposit_contract()
@@ -141,6 +160,9 @@ impl PruningArgs {
         if let Some(mode) = self.storage_history_prune_mode() {
             config.segments.storage_history = Some(mode);
         }
+        if let Some(mode) = self.bodies_history_prune_mode() {
+            config.segments.bodies_history = Some(mode);
+        }
 
         Some(config)
     }
@@ -203,6 +225,16 @@ impl PruningArgs {
             None
         }
     }
+
+    const fn bodies_history_prune_mode(&self) -> Option<PruneMode> {
+        if let Some(distance) = self.bodies_history_distance {
+            Some(PruneMode::Distance(distance))
+        } else if let Some(block_number) = self.bodies_history_before {
+            Some(PruneMode::Before(block_number))
+        } else {
+            None
+        }
+    }
 }
 
 pub(crate) fn parse_receipts_log_filter(
@@ -267,6 +299,32 @@ mod tests {
         assert_eq!(args, default_args);
     }
 
+    #[test]
+    fn bodies_history_args() {
+        let args = CommandParser::<PruningArgs>::parse_from([
+            "reth",
+            "--full",
+            "--prune.bodies.distance",
+            "100000",
+        ])
+        .args;
+        let config = args.prune_config(&reth_chainspec::MAINNET.as_ref()).unwrap();
+        assert_eq!(config.segments.bodies_history, Some(PruneMode::Distance(100000)));
+        assert_eq!(
+            config.segments.account_history,
+            Some(PruneMode::Distance(MINIMUM_PRUNING_DISTANCE))
+        );
+
+        assert!(CommandParser::<PruningArgs>::try_parse_from([
+            "reth",
+            "--prune.bodies.distance",
+            "100000",
+            "--prune.bodies.before",
+            "100"
+        ])
+        .is_err());
+    }
+
     #[test]
     fn test_parse_receipts_log_filter() {
         let filter1 = "0x0000000000000000000000000000000000000001:full";
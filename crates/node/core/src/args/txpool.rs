@@ -4,15 +4,22 @@ use crate::cli::config::RethTransactionPoolConfig;
 use alloy_eips::eip1559::{ETHEREUM_BLOCK_GAS_LIMIT, MIN_PROTOCOL_BASE_FEE};
 use alloy_primitives::Address;
 use clap::Args;
+use reth_cli_util::parse_duration_from_secs;
 use reth_transaction_pool::{
-    blobstore::disk::DEFAULT_MAX_CACHED_BLOBS,
+    blobstore::{
+        disk::DEFAULT_MAX_CACHED_BLOBS, BlobArchive, DEFAULT_BLOB_ARCHIVE_RETENTION_BLOCKS,
+    },
+    maintain::DEFAULT_JOURNAL_INTERVAL,
     pool::{NEW_TX_LISTENER_BUFFER_SIZE, PENDING_TX_LISTENER_BUFFER_SIZE},
     validate::DEFAULT_MAX_TX_INPUT_BYTES,
     LocalTransactionConfig, PoolConfig, PriceBumpConfig, SubPoolLimit, DEFAULT_PRICE_BUMP,
     DEFAULT_TXPOOL_ADDITIONAL_VALIDATION_TASKS, MAX_NEW_PENDING_TXS_NOTIFICATIONS,
     REPLACE_BLOB_PRICE_BUMP, TXPOOL_MAX_ACCOUNT_SLOTS_PER_SENDER,
-    TXPOOL_SUBPOOL_MAX_SIZE_MB_DEFAULT, TXPOOL_SUBPOOL_MAX_TXS_DEFAULT,
+    TXPOOL_MAX_HISTORY_EVENTS_DEFAULT, TXPOOL_SUBPOOL_MAX_SIZE_MB_DEFAULT,
+    TXPOOL_SUBPOOL_MAX_TXS_DEFAULT,
 };
+use std::{path::PathBuf, time::Duration};
+
 /// Parameters for debugging purposes
 #[derive(Debug, Clone, Args, PartialEq, Eq)]
 #[command(next_help_heading = "TxPool")]
@@ -75,6 +82,36 @@ pub struct TxPoolArgs {
     /// Flag to toggle local transaction propagation.
     #[arg(long = "txpool.no-local-transactions-propagation")]
     pub no_local_transactions_propagation: bool,
+    /// Flag to make private transactions public once they missed their deadline, instead of
+    /// dropping them.
+    #[arg(long = "txpool.propagate-expired-private-transactions")]
+    pub propagate_expired_private_transactions: bool,
+    /// Flag to persist all transactions of the pool, including the blob sidecars, across
+    /// restarts.
+    #[arg(long = "txpool.journal")]
+    pub journal: bool,
+    /// Interval at which the transaction pool journal is saved.
+    #[arg(long = "txpool.journal-interval", value_parser = parse_duration_from_secs, default_value = "60", value_name = "SECONDS")]
+    pub journal_interval: Duration,
+    /// Flag to archive the blob sidecars of included transactions, so they can be served over RPC
+    /// after they have been dropped by the transaction pool.
+    #[arg(long = "txpool.blob-archive")]
+    pub blob_archive: bool,
+    /// Number of blocks to keep the archived blob sidecars for, 0 keeps them forever.
+    #[arg(long = "txpool.blob-archive-retention", default_value_t = DEFAULT_BLOB_ARCHIVE_RETENTION_BLOCKS, value_name = "BLOCKS")]
+    pub blob_archive_retention: u64,
+    /// Flag to enable the ERC-4337 user operation pool and its bundler RPC methods in the `eth`
+    /// namespace.
+    #[arg(long = "txpool.user-operations")]
+    pub user_operations: bool,
+    /// Path to a file with the hex encoded private key of the bundler, which bundles the user
+    /// operations into `handleOps` transactions that are added to the transaction pool.
+    #[arg(
+        long = "txpool.user-operations-bundler-key",
+        value_name = "PATH",
+        requires = "user_operations"
+    )]
+    pub user_operations_bundler_key: Option<PathBuf>,
     /// Number of additional transaction validation tasks to spawn.
     #[arg(long = "txpool.additional-validation-tasks", alias = "txpool.additional_validation_tasks", default_value_t = DEFAULT_TXPOOL_ADDITIONAL_VALIDATION_TASKS)]
     pub additional_validation_tasks: usize,
@@ -91,6 +128,10 @@ pub struct TxPoolArgs {
     /// iterators.
     #[arg(long = "txpool.max-new-pending-txs-notifications", alias = "txpool.max-new-pending-txs-notifications", default_value_t = MAX_NEW_PENDING_TXS_NOTIFICATIONS)]
     pub max_new_pending_txs_notifications: usize,
+
+    /// Max number of recent transaction events to keep for `txpool_getTransactionHistory`.
+    #[arg(long = "txpool.max-history-events", default_value_t = TXPOOL_MAX_HISTORY_EVENTS_DEFAULT)]
+    pub max_history_events: usize,
 }
 
 impl Default for TxPoolArgs {
@@ -112,14 +153,30 @@ impl Default for TxPoolArgs {
             no_locals: false,
             locals: Default::default(),
             no_local_transactions_propagation: false,
+            propagate_expired_private_transactions: false,
+            journal: false,
+            journal_interval: DEFAULT_JOURNAL_INTERVAL,
+            blob_archive: false,
+            blob_archive_retention: DEFAULT_BLOB_ARCHIVE_RETENTION_BLOCKS,
+            user_operations: false,
+            user_operations_bundler_key: None,
             additional_validation_tasks: DEFAULT_TXPOOL_ADDITIONAL_VALIDATION_TASKS,
             pending_tx_listener_buffer_size: PENDING_TX_LISTENER_BUFFER_SIZE,
             new_tx_listener_buffer_size: NEW_TX_LISTENER_BUFFER_SIZE,
             max_new_pending_txs_notifications: MAX_NEW_PENDING_TXS_NOTIFICATIONS,
+            max_history_events: TXPOOL_MAX_HISTORY_EVENTS_DEFAULT,
         }
     }
 }
 
+impl TxPoolArgs {
+    /// Returns the blob archive in the given directory, if it's enabled.
+    pub fn blob_archive(&self, directory: PathBuf) -> Option<BlobArchive> {
+        let retention = (self.blob_archive_retention != 0).then_some(self.blob_archive_retention);
+        self.blob_archive.then(|| BlobArchive::new(directory, retention))
+    }
+}
+
 impl RethTransactionPoolConfig for TxPoolArgs {
     /// Returns transaction pool configuration.
     fn pool_config(&self) -> PoolConfig {
@@ -128,6 +185,7 @@ impl RethTransactionPoolConfig for TxPoolArgs {
                 no_exemptions: self.no_locals,
                 local_addresses: self.locals.clone().into_iter().collect(),
                 propagate_local_transactions: !self.no_local_transactions_propagation,
+                propagate_expired_private_transactions: self.propagate_expired_private_transactions,
             },
             pending_limit: SubPoolLimit {
                 max_txs: self.pending_max_count,
@@ -155,6 +213,7 @@ impl RethTransactionPoolConfig for TxPoolArgs {
             pending_tx_listener_buffer_size: self.pending_tx_listener_buffer_size,
             new_tx_listener_buffer_size: self.new_tx_listener_buffer_size,
             max_new_pending_txs_notifications: self.max_new_pending_txs_notifications,
+            max_history_events: self.max_history_events,
         }
     }
 }
@@ -188,4 +247,54 @@ mod tests {
         .args;
         assert_eq!(args.locals, vec![Address::ZERO]);
     }
+
+    #[test]
+    fn txpool_parse_journal() {
+        let args = CommandParser::<TxPoolArgs>::parse_from([
+            "reth",
+            "--txpool.journal",
+            "--txpool.journal-interval",
+            "300",
+        ])
+        .args;
+        assert!(args.journal);
+        assert_eq!(args.journal_interval, Duration::from_secs(300));
+    }
+
+    #[test]
+    fn txpool_parse_blob_archive() {
+        let args = CommandParser::<TxPoolArgs>::parse_from([
+            "reth",
+            "--txpool.blob-archive",
+            "--txpool.blob-archive-retention",
+            "0",
+        ])
+        .args;
+        let archive = args.blob_archive(PathBuf::from("blobarchive")).unwrap();
+        assert_eq!(archive.retention(), None);
+
+        let args = CommandParser::<TxPoolArgs>::parse_from(["reth"]).args;
+        assert!(args.blob_archive(PathBuf::from("blobarchive")).is_none());
+    }
+
+    #[test]
+    fn txpool_parse_user_operations() {
+        let args = CommandParser::<TxPoolArgs>::parse_from([
+            "reth",
+            "--txpool.user-operations",
+            "--txpool.user-operations-bundler-key",
+            "bundler.key",
+        ])
+        .args;
+        assert!(args.user_operations);
+        assert_eq!(args.user_operations_bundler_key, Some(PathBuf::from("bundler.key")));
+
+        // the bundler requires the user operation pool
+        assert!(CommandParser::<TxPoolArgs>::try_parse_from([
+            "reth",
+            "--txpool.user-operations-bundler-key",
+            "bundler.key",
+        ])
+        .is_err());
+    }
 }
@@ -330,6 +330,20 @@ impl<D> ChainPath<D> {
         self.data_dir().join("txpool-transactions-backup.rlp")
     }
 
+    /// Returns the path to the transaction pool journal file
+    ///
+    /// `<DIR>/<CHAIN_ID>/txpool-journal.rlp`
+    pub fn txpool_journal(&self) -> PathBuf {
+        self.data_dir().join("txpool-journal.rlp")
+    }
+
+    /// Returns the path to the blob archive directory for this chain.
+    ///
+    /// `<DIR>/<CHAIN_ID>/blobarchive`
+    pub fn blob_archive(&self) -> PathBuf {
+        self.data_dir().join("blobarchive")
+    }
+
     /// Returns the path to the config file for this chain.
     ///
     /// `<DIR>/<CHAIN_ID>/reth.toml`
@@ -351,6 +365,13 @@ impl<D> ChainPath<D> {
         self.data_dir().join("invalid_block_hooks")
     }
 
+    /// Returns the path to the bad blocks directory for this chain.
+    ///
+    /// `<DIR>/<CHAIN_ID>/bad_blocks`
+    pub fn bad_blocks(&self) -> PathBuf {
+        self.data_dir().join("bad_blocks")
+    }
+
     /// Returns the path to the ExEx WAL directory for this chain.
     pub fn exex_wal(&self) -> PathBuf {
         self.data_dir().join("exex/wal")
@@ -419,6 +419,8 @@ where
         let Self { pool_config_overrides } = self;
         let data_dir = ctx.config().datadir();
         let blob_store = DiskFileBlobStore::open(data_dir.blobstore(), Default::default())?;
+        let admission_policies = ctx.admission_policies()?;
+        let readmissions = admission_policies.readmissions();
 
         let validator = TransactionValidationTaskExecutor::eth_builder(Arc::new(
             ctx.chain_spec().inner.clone(),
@@ -426,6 +428,7 @@ where
         .no_eip4844()
         .with_head_timestamp(ctx.head().timestamp)
         .kzg_settings(ctx.kzg_settings()?)
+        .with_admission_policies(admission_policies)
         .with_additional_tasks(
             pool_config_overrides
                 .additional_validation_tasks
@@ -467,6 +470,26 @@ where
                 },
             );
 
+            if ctx.config().txpool.journal {
+                let journal_config =
+                    reth_transaction_pool::maintain::TransactionPoolJournalConfig::new(
+                        data_dir.txpool_journal(),
+                    )
+                    .with_interval(ctx.config().txpool.journal_interval)
+                    .with_readmissions(readmissions.clone());
+
+                ctx.task_executor().spawn_critical_with_graceful_shutdown_signal(
+                    "transaction pool journal task",
+                    |shutdown| {
+                        reth_transaction_pool::maintain::journal_transactions_task(
+                            shutdown,
+                            pool.clone(),
+                            journal_config,
+                        )
+                    },
+                );
+            }
+
             // spawn the maintenance task
             ctx.task_executor().spawn_critical(
                 "txpool maintenance task",
@@ -475,7 +498,8 @@ where
                     pool,
                     chain_events,
                     ctx.task_executor().clone(),
-                    Default::default(),
+                    reth_transaction_pool::maintain::MaintainPoolConfig::default()
+                        .with_readmissions(readmissions),
                 ),
             );
             debug!(target: "reth::cli", "Spawned txpool maintenance task");
@@ -555,7 +579,7 @@ where
     {
         let payload_builder = reth_optimism_payload_builder::OpPayloadBuilder::with_builder_config(
             evm_config,
-            OpBuilderConfig { da_config: self.da_config },
+            OpBuilderConfig::new(self.da_config),
         )
         .with_transactions(self.best_transactions)
         .set_compute_pending_block(self.compute_pending_block);
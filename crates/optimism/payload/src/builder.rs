@@ -22,7 +22,9 @@ use reth_optimism_consensus::calculate_receipt_root_no_memo_optimism;
 use reth_optimism_forks::OpHardforks;
 use reth_payload_builder_primitives::PayloadBuilderError;
 use reth_payload_primitives::PayloadBuilderAttributes;
-use reth_payload_util::PayloadTransactions;
+use reth_payload_util::{
+    BuildPhase, PayloadSourceContext, PayloadTransactionSources, PayloadTransactions,
+};
 use reth_primitives::{
     proofs, transaction::SignedTransactionIntoRecoveredExt, Block, BlockBody, BlockExt, Receipt,
     SealedHeader, TransactionSigned, TxType,
@@ -139,6 +141,7 @@ where
             initialized_block_env: block_env,
             cancel,
             best_payload,
+            transaction_sources: self.config.transaction_sources.clone(),
         };
 
         let builder = OpBuilder { pool, best: self.best_transactions.clone() };
@@ -207,6 +210,7 @@ where
             initialized_block_env: block_env,
             cancel: Default::default(),
             best_payload: Default::default(),
+            transaction_sources: self.config.transaction_sources.clone(),
         };
 
         let state_provider = client.state_by_block_hash(ctx.parent().hash())?;
@@ -276,7 +280,8 @@ where
 ///
 /// Depending on whether the node acts as a sequencer and is allowed to include additional
 /// transactions (`no_tx_pool == false`):
-/// 4. include additional transactions
+/// 4. include additional transactions: the transactions of the configured
+///    [`PayloadTransactionSources`] in their [`BuildPhase`]s around the transactions of the pool
 ///
 /// And finally
 /// 5. build the block: compute all roots (txs, state)
@@ -315,13 +320,25 @@ where
         // 3. execute sequencer transactions
         let mut info = ctx.execute_sequencer_transactions(state)?;
 
-        // 4. if mem pool transactions are requested we execute them
+        // 4. if mem pool transactions are requested we execute them, surrounded by the
+        // transactions of the sources
         if !ctx.attributes().no_tx_pool {
+            for phase in [BuildPhase::TopOfBlock, BuildPhase::BeforePool] {
+                if ctx.execute_source_transactions(phase, &mut info, state)?.is_some() {
+                    return Ok(BuildOutcomeKind::Cancelled)
+                }
+            }
+
             let best_txs = best.best_transactions(pool, ctx.best_transaction_attributes());
             if ctx.execute_best_transactions::<_, Pool>(&mut info, state, best_txs)?.is_some() {
                 return Ok(BuildOutcomeKind::Cancelled)
             }
 
+            if ctx.execute_source_transactions(BuildPhase::EndOfBlock, &mut info, state)?.is_some()
+            {
+                return Ok(BuildOutcomeKind::Cancelled)
+            }
+
             // check if the new payload is even more valuable
             if !ctx.is_better_payload(info.total_fees) {
                 // can skip building the block
@@ -562,6 +579,8 @@ pub struct OpPayloadBuilderCtx<EvmConfig> {
     pub cancel: Cancelled,
     /// The currently best payload.
     pub best_payload: Option<OpBuiltPayload>,
+    /// Sources of transactions that are included in addition to the pool transactions.
+    pub transaction_sources: PayloadTransactionSources<TransactionSigned>,
 }
 
 impl<EvmConfig> OpPayloadBuilderCtx<EvmConfig> {
@@ -938,4 +957,109 @@ where
 
         Ok(None)
     }
+
+    /// Executes the transaction groups of all sources registered for the given phase and updates
+    /// the execution info.
+    ///
+    /// Groups that don't fit into the block or into the budget of their source are skipped.
+    /// Groups with an invalid transaction, a transaction that reverts without being allowed to, or
+    /// that pay less than their minimum profit are discarded as a whole. The groups of a source
+    /// that orders them by profit are simulated on top of the payload first.
+    ///
+    /// Returns `Ok(Some(())` if the job was cancelled.
+    pub fn execute_source_transactions<DB>(
+        &self,
+        phase: BuildPhase,
+        info: &mut ExecutionInfo,
+        db: &mut State<DB>,
+    ) -> Result<Option<()>, PayloadBuilderError>
+    where
+        DB: Database<Error = ProviderError>,
+    {
+        let block_gas_limit = self.block_gas_limit();
+        let base_fee = self.base_fee();
+        let env = EnvWithHandlerCfg::new_with_cfg_env(
+            self.initialized_cfg.clone(),
+            self.initialized_block_env.clone(),
+            TxEnv::default(),
+        );
+
+        for (budget, source) in self.transaction_sources.phase(phase) {
+            let mut groups = source.transactions(&PayloadSourceContext {
+                parent_hash: self.parent().hash(),
+                block_number: self.block_number(),
+                timestamp: self.attributes().timestamp(),
+                base_fee,
+                gas_limit: block_gas_limit,
+                gas_used: info.cumulative_gas_used,
+                executed_transactions: &info.executed_transactions,
+            });
+            if source.order_by_profit() {
+                groups = order_transaction_groups_by_profit(&self.evm_config, &env, db, groups)?;
+            }
+
+            let mut source_gas_used = 0;
+            for group in groups {
+                // check if the job was cancelled, if so we can exit early
+                if self.cancel.is_cancelled() {
+                    return Ok(Some(()))
+                }
+
+                // A sequencer's block should never contain blob or deposit transactions from a
+                // source.
+                if group
+                    .transactions()
+                    .iter()
+                    .any(|tx| tx.is_eip4844() || tx.tx_type() == TxType::Deposit as u8)
+                {
+                    trace!(target: "payload_builder", ?phase, "skipping transaction group with blob or deposit transaction");
+                    continue
+                }
+
+                let group_gas_limit = group.gas_limit();
+                if info.cumulative_gas_used + group_gas_limit > block_gas_limit ||
+                    !budget.allows(source_gas_used + group_gas_limit, 0)
+                {
+                    trace!(target: "payload_builder", ?phase, group_gas_limit, "skipping transaction group that exceeds the available gas");
+                    continue
+                }
+
+                let Some(executed) =
+                    execute_transaction_group(&self.evm_config, env.clone(), db, &group)?
+                else {
+                    trace!(target: "payload_builder", ?phase, "discarded transaction group");
+                    continue
+                };
+
+                let cumulative_gas_used = info.cumulative_gas_used;
+                // the fees of the group are the increase of the balance of the beneficiary
+                info.total_fees += executed.profit;
+                for (tx, result) in executed.commit(db) {
+                    let gas_used = result.gas_used();
+
+                    // add gas used by the transaction to cumulative gas used, before creating the
+                    // receipt
+                    info.cumulative_gas_used += gas_used;
+
+                    // Push transaction changeset and calculate header bloom filter for receipt.
+                    info.receipts.push(Some(Receipt {
+                        tx_type: tx.tx_type(),
+                        success: result.is_success(),
+                        cumulative_gas_used: info.cumulative_gas_used,
+                        logs: result.into_logs().into_iter().map(Into::into).collect(),
+                        deposit_nonce: None,
+                        deposit_receipt_version: None,
+                    }));
+
+                    // append sender and transaction to the respective lists
+                    info.executed_senders.push(tx.signer());
+                    info.executed_transactions.push(tx.into_signed());
+                }
+
+                source_gas_used += info.cumulative_gas_used - cumulative_gas_used;
+            }
+        }
+
+        Ok(None)
+    }
 }
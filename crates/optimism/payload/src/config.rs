@@ -1,5 +1,7 @@
 //! Additional configuration for the OP builder
 
+use reth_payload_util::PayloadTransactionSources;
+use reth_primitives::TransactionSigned;
 use std::sync::{atomic::AtomicU64, Arc};
 
 /// Settings for the OP builder.
@@ -7,12 +9,23 @@ use std::sync::{atomic::AtomicU64, Arc};
 pub struct OpBuilderConfig {
     /// Data availability configuration for the OP builder.
     pub da_config: OpDAConfig,
+    /// Sources of transactions that are included in addition to the pool transactions.
+    pub transaction_sources: PayloadTransactionSources<TransactionSigned>,
 }
 
 impl OpBuilderConfig {
     /// Creates a new OP builder configuration with the given data availability configuration.
     pub const fn new(da_config: OpDAConfig) -> Self {
-        Self { da_config }
+        Self { da_config, transaction_sources: PayloadTransactionSources::new() }
+    }
+
+    /// Sets the sources of transactions that are included in addition to the pool transactions.
+    pub fn with_transaction_sources(
+        mut self,
+        transaction_sources: PayloadTransactionSources<TransactionSigned>,
+    ) -> Self {
+        self.transaction_sources = transaction_sources;
+        self
     }
 
     /// Returns the Data Availability configuration for the OP builder, if it has configured
@@ -0,0 +1,150 @@
+use alloy_primitives::{Address, U256};
+use reth_evm::ConfigureEvm;
+use reth_payload_builder_primitives::PayloadBuilderError;
+use reth_payload_util::PayloadTransactionGroup;
+use reth_primitives::RecoveredTx;
+use reth_primitives_traits::SignedTransaction;
+use reth_provider::ProviderError;
+use revm::{
+    primitives::{EVMError, EnvWithHandlerCfg, EvmState, ExecutionResult, ResultAndState},
+    Database, DatabaseCommit, State,
+};
+use std::cmp::Reverse;
+use tracing::trace;
+
+/// A [`PayloadTransactionGroup`] that was executed on top of a payload, but isn't committed to its
+/// state yet.
+#[derive(Debug)]
+pub struct ExecutedTransactionGroup<T> {
+    /// The executed transactions with their results and state changes, in execution order.
+    pub transactions: Vec<(RecoveredTx<T>, ExecutionResult, EvmState)>,
+    /// The amount the group paid to the beneficiary of the block, through priority fees and
+    /// direct transfers.
+    pub profit: U256,
+}
+
+impl<T> ExecutedTransactionGroup<T> {
+    /// Returns the gas used by all transactions of the group.
+    pub fn gas_used(&self) -> u64 {
+        self.transactions.iter().map(|(_, result, _)| result.gas_used()).sum()
+    }
+
+    /// Commits the state changes of the group to the given state and returns the executed
+    /// transactions with their results, in execution order.
+    pub fn commit<DB: Database>(
+        self,
+        db: &mut State<DB>,
+    ) -> Vec<(RecoveredTx<T>, ExecutionResult)> {
+        self.transactions
+            .into_iter()
+            .map(|(tx, result, state)| {
+                db.commit(state);
+                (tx, result)
+            })
+            .collect()
+    }
+}
+
+/// Executes the transactions of a [`PayloadTransactionGroup`] on top of the given state, without
+/// modifying it.
+///
+/// The transactions are executed on an overlay [`State`] that reads through to `db` and only holds
+/// the changes of the group, so discarding a group never requires restoring the state of the
+/// payload. The changes of an included group are applied with [`ExecutedTransactionGroup::commit`].
+///
+/// Returns `None` if the group must be discarded, because one of its transactions is invalid or
+/// reverts without being allowed to, or because the group pays less than its minimum profit to
+/// the beneficiary of the block.
+pub fn execute_transaction_group<EvmConfig, DB>(
+    evm_config: &EvmConfig,
+    env: EnvWithHandlerCfg,
+    db: &mut State<DB>,
+    group: &PayloadTransactionGroup<EvmConfig::Transaction>,
+) -> Result<Option<ExecutedTransactionGroup<EvmConfig::Transaction>>, PayloadBuilderError>
+where
+    EvmConfig: ConfigureEvm<Transaction: SignedTransaction>,
+    DB: Database<Error = ProviderError>,
+{
+    let coinbase = env.block.coinbase;
+    let mut overlay = State::builder().with_database(db).build();
+    let balance_before = coinbase_balance(&mut overlay, coinbase)?;
+
+    let mut transactions = Vec::with_capacity(group.transactions().len());
+    let mut evm = evm_config.evm_with_env(&mut overlay, env);
+    for tx in group.transactions() {
+        // Configure the environment for the tx.
+        *evm.tx_mut() = evm_config.tx_env(tx.as_signed(), tx.signer());
+
+        let ResultAndState { result, state } = match evm.transact() {
+            Ok(res) => res,
+            Err(EVMError::Transaction(err)) => {
+                trace!(target: "payload_builder", %err, tx=?tx.as_signed().tx_hash(), "discarding transaction group with invalid transaction");
+                return Ok(None)
+            }
+            Err(err) => {
+                // this is an error that we should treat as fatal for this attempt
+                return Err(PayloadBuilderError::EvmExecutionError(err))
+            }
+        };
+
+        if !result.is_success() && !group.may_revert(tx.as_signed().tx_hash()) {
+            trace!(target: "payload_builder", tx=?tx.as_signed().tx_hash(), "discarding transaction group with reverted transaction");
+            return Ok(None)
+        }
+
+        // the following transactions of the group are executed on top of the changes
+        evm.db_mut().commit(state.clone());
+        transactions.push((tx.clone(), result, state));
+    }
+    drop(evm);
+
+    let executed = ExecutedTransactionGroup {
+        profit: coinbase_balance(&mut overlay, coinbase)?.saturating_sub(balance_before),
+        transactions,
+    };
+    let gas_used = executed.gas_used();
+    if executed.profit < U256::from(group.min_profit_per_gas()) * U256::from(gas_used) {
+        trace!(target: "payload_builder", profit=%executed.profit, gas_used, "discarding unprofitable transaction group");
+        return Ok(None)
+    }
+
+    Ok(Some(executed))
+}
+
+/// Orders the [`PayloadTransactionGroup`]s by the profit per gas they pay to the beneficiary of
+/// the block, highest first.
+///
+/// Each group is simulated on its own on top of the given state, without modifying it, and the
+/// groups that would be discarded are dropped. Groups that pay the same profit per gas keep their
+/// order.
+pub fn order_transaction_groups_by_profit<EvmConfig, DB>(
+    evm_config: &EvmConfig,
+    env: &EnvWithHandlerCfg,
+    db: &mut State<DB>,
+    groups: Vec<PayloadTransactionGroup<EvmConfig::Transaction>>,
+) -> Result<Vec<PayloadTransactionGroup<EvmConfig::Transaction>>, PayloadBuilderError>
+where
+    EvmConfig: ConfigureEvm<Transaction: SignedTransaction>,
+    DB: Database<Error = ProviderError>,
+{
+    let mut simulated = Vec::with_capacity(groups.len());
+    for group in groups {
+        let Some(executed) = execute_transaction_group(evm_config, env.clone(), db, &group)? else {
+            continue
+        };
+        let profit_per_gas = executed.profit / U256::from(executed.gas_used().max(1));
+        simulated.push((profit_per_gas, group));
+    }
+
+    // stable, so groups with the same profit per gas keep their order
+    simulated.sort_by_key(|(profit_per_gas, _)| Reverse(*profit_per_gas));
+    Ok(simulated.into_iter().map(|(_, group)| group).collect())
+}
+
+/// Returns the balance of the beneficiary of the block.
+fn coinbase_balance<DB>(db: &mut DB, coinbase: Address) -> Result<U256, PayloadBuilderError>
+where
+    DB: Database<Error = ProviderError>,
+{
+    Ok(db.basic(coinbase)?.map(|account| account.balance).unwrap_or_default())
+}
@@ -40,9 +40,13 @@ use tokio::{
 };
 use tracing::{debug, trace, warn};
 
+mod group;
 mod metrics;
 mod stack;
 
+pub use group::{
+    execute_transaction_group, order_transaction_groups_by_profit, ExecutedTransactionGroup,
+};
 pub use stack::PayloadBuilderStack;
 
 /// The [`PayloadJobGenerator`] that creates [`BasicPayloadJob`]s.
@@ -8,8 +8,13 @@
 #![cfg_attr(not(test), warn(unused_crate_dependencies))]
 #![cfg_attr(docsrs, feature(doc_cfg, doc_auto_cfg))]
 
+mod source;
 mod traits;
 mod transaction;
 
+pub use source::{
+    BuildPhase, PayloadSourceContext, PayloadTransactionGroup, PayloadTransactionSource,
+    PayloadTransactionSources, SourceBudget,
+};
 pub use traits::PayloadTransactions;
 pub use transaction::{PayloadTransactionsChain, PayloadTransactionsFixed};
@@ -0,0 +1,320 @@
+use alloy_consensus::Transaction;
+use alloy_eips::eip4844::DATA_GAS_PER_BLOB;
+use alloy_primitives::B256;
+use reth_primitives::RecoveredTx;
+use std::{fmt, sync::Arc};
+
+/// A source of transactions that are included in a payload in addition to the transactions of the
+/// pool, e.g. bundles of an external relay, a top-of-block system transaction or end-of-block
+/// backruns.
+///
+/// Sources are registered for a [`BuildPhase`] in [`PayloadTransactionSources`] and are asked for
+/// their transactions once the payload builder reaches that phase.
+pub trait PayloadTransactionSource<T>: Send + Sync {
+    /// Returns the transaction groups of this source in the order they should be included in the
+    /// payload.
+    fn transactions(&self, ctx: &PayloadSourceContext<'_, T>) -> Vec<PayloadTransactionGroup<T>>;
+
+    /// Returns `true` if the payload builder should order the groups of this source by the profit
+    /// per gas they pay when simulated on top of the payload, instead of the order they're
+    /// returned in.
+    ///
+    /// Groups that pay the same profit per gas keep their order.
+    fn order_by_profit(&self) -> bool {
+        false
+    }
+}
+
+impl<T, F> PayloadTransactionSource<T> for F
+where
+    F: Fn(&PayloadSourceContext<'_, T>) -> Vec<PayloadTransactionGroup<T>> + Send + Sync,
+{
+    fn transactions(&self, ctx: &PayloadSourceContext<'_, T>) -> Vec<PayloadTransactionGroup<T>> {
+        self(ctx)
+    }
+}
+
+/// The payload that's being built, as seen by a [`PayloadTransactionSource`].
+#[derive(Debug)]
+pub struct PayloadSourceContext<'a, T> {
+    /// Hash of the parent block.
+    pub parent_hash: B256,
+    /// Number of the block that's being built.
+    pub block_number: u64,
+    /// Timestamp of the block that's being built.
+    pub timestamp: u64,
+    /// Base fee of the block that's being built.
+    pub base_fee: u64,
+    /// Gas limit of the block that's being built.
+    pub gas_limit: u64,
+    /// Gas used by the transactions executed so far.
+    pub gas_used: u64,
+    /// The transactions executed so far, in block order.
+    pub executed_transactions: &'a [T],
+}
+
+/// Transactions that are included in a payload all together or not at all.
+///
+/// If one of the transactions is invalid, reverts without being listed in the reverting
+/// transaction hashes, or if the group pays less than its minimum profit to the beneficiary of the
+/// block, the state changes of the whole group are discarded.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PayloadTransactionGroup<T> {
+    transactions: Vec<RecoveredTx<T>>,
+    /// The transactions that may revert, all transactions may revert if `None`.
+    reverting_tx_hashes: Option<Vec<B256>>,
+    min_profit_per_gas: u128,
+}
+
+impl<T> PayloadTransactionGroup<T> {
+    /// Creates a new group of transactions, which may all revert.
+    pub const fn new(transactions: Vec<RecoveredTx<T>>) -> Self {
+        Self { transactions, reverting_tx_hashes: None, min_profit_per_gas: 0 }
+    }
+
+    /// Creates a new group with a single transaction, which may revert.
+    pub fn single(transaction: RecoveredTx<T>) -> Self {
+        Self::new(vec![transaction])
+    }
+
+    /// Sets the hashes of the transactions that may revert, all other transactions of the group
+    /// must succeed.
+    pub fn with_reverting_tx_hashes(mut self, reverting_tx_hashes: Vec<B256>) -> Self {
+        self.reverting_tx_hashes = Some(reverting_tx_hashes);
+        self
+    }
+
+    /// Sets the minimum amount per gas used that the group must pay to the beneficiary of the
+    /// block, through priority fees and direct transfers.
+    pub const fn with_min_profit_per_gas(mut self, min_profit_per_gas: u128) -> Self {
+        self.min_profit_per_gas = min_profit_per_gas;
+        self
+    }
+
+    /// Returns the transactions of the group.
+    pub fn transactions(&self) -> &[RecoveredTx<T>] {
+        &self.transactions
+    }
+
+    /// Returns `true` if the transaction with the given hash may revert.
+    pub fn may_revert(&self, tx_hash: &B256) -> bool {
+        self.reverting_tx_hashes.as_ref().is_none_or(|hashes| hashes.contains(tx_hash))
+    }
+
+    /// Returns the minimum amount per gas used that the group must pay to the beneficiary of the
+    /// block.
+    pub const fn min_profit_per_gas(&self) -> u128 {
+        self.min_profit_per_gas
+    }
+
+    /// Consumes the group and returns its transactions.
+    pub fn into_transactions(self) -> Vec<RecoveredTx<T>> {
+        self.transactions
+    }
+}
+
+impl<T: Transaction> PayloadTransactionGroup<T> {
+    /// Returns the sum of the gas limits of all transactions of the group.
+    pub fn gas_limit(&self) -> u64 {
+        self.transactions.iter().map(|tx| tx.as_signed().gas_limit()).sum()
+    }
+
+    /// Returns the blob gas of all transactions of the group.
+    pub fn blob_gas(&self) -> u64 {
+        self.transactions
+            .iter()
+            .filter_map(|tx| tx.as_signed().blob_versioned_hashes())
+            .map(|hashes| hashes.len() as u64 * DATA_GAS_PER_BLOB)
+            .sum()
+    }
+}
+
+/// The phases of payload building in which [`PayloadTransactionSource`]s are executed.
+///
+/// Phases are executed in the order they are declared in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum BuildPhase {
+    /// Right after the pre-block system calls, before any other transaction, e.g. for system
+    /// transactions.
+    TopOfBlock,
+    /// Before the transactions of the pool, e.g. for bundles.
+    BeforePool,
+    /// After the transactions of the pool, e.g. for backruns.
+    EndOfBlock,
+}
+
+/// Limits the gas and blob gas that the transactions of a single [`PayloadTransactionSource`] may
+/// use in a payload.
+///
+/// The limits of the block itself always apply.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SourceBudget {
+    /// Maximum gas used by the source, unlimited if `None`.
+    pub gas_limit: Option<u64>,
+    /// Maximum blob gas used by the source, unlimited if `None`.
+    pub blob_gas_limit: Option<u64>,
+}
+
+impl SourceBudget {
+    /// Creates a budget without any limits.
+    pub const fn unlimited() -> Self {
+        Self { gas_limit: None, blob_gas_limit: None }
+    }
+
+    /// Sets the gas limit of the budget.
+    pub const fn with_gas_limit(mut self, gas_limit: u64) -> Self {
+        self.gas_limit = Some(gas_limit);
+        self
+    }
+
+    /// Sets the blob gas limit of the budget.
+    pub const fn with_blob_gas_limit(mut self, blob_gas_limit: u64) -> Self {
+        self.blob_gas_limit = Some(blob_gas_limit);
+        self
+    }
+
+    /// Returns `true` if the given total gas and blob gas of a source fit into the budget.
+    pub fn allows(&self, gas: u64, blob_gas: u64) -> bool {
+        self.gas_limit.is_none_or(|limit| gas <= limit) &&
+            self.blob_gas_limit.is_none_or(|limit| blob_gas <= limit)
+    }
+}
+
+/// A [`PayloadTransactionSource`] that's registered for a [`BuildPhase`].
+struct RegisteredSource<T> {
+    phase: BuildPhase,
+    budget: SourceBudget,
+    source: Arc<dyn PayloadTransactionSource<T>>,
+}
+
+impl<T> Clone for RegisteredSource<T> {
+    fn clone(&self) -> Self {
+        Self { phase: self.phase, budget: self.budget, source: self.source.clone() }
+    }
+}
+
+/// The [`PayloadTransactionSource`]s of a payload builder, ordered by their [`BuildPhase`].
+///
+/// Sources of the same phase are executed in the order they were added.
+pub struct PayloadTransactionSources<T> {
+    sources: Vec<RegisteredSource<T>>,
+}
+
+impl<T> PayloadTransactionSources<T> {
+    /// Creates an empty set of sources.
+    pub const fn new() -> Self {
+        Self { sources: Vec::new() }
+    }
+
+    /// Adds a source that's executed in the given phase within the given budget.
+    pub fn with_source<S>(mut self, phase: BuildPhase, budget: SourceBudget, source: S) -> Self
+    where
+        S: PayloadTransactionSource<T> + 'static,
+    {
+        self.sources.push(RegisteredSource { phase, budget, source: Arc::new(source) });
+        // stable, so sources of the same phase keep their order
+        self.sources.sort_by_key(|source| source.phase);
+        self
+    }
+
+    /// Returns `true` if no source is registered.
+    pub fn is_empty(&self) -> bool {
+        self.sources.is_empty()
+    }
+
+    /// Returns the sources of the given phase with their budgets, in execution order.
+    pub fn phase(
+        &self,
+        phase: BuildPhase,
+    ) -> impl Iterator<Item = (SourceBudget, &dyn PayloadTransactionSource<T>)> + '_ {
+        self.sources
+            .iter()
+            .filter(move |source| source.phase == phase)
+            .map(|source| (source.budget, &*source.source))
+    }
+}
+
+impl<T> Default for PayloadTransactionSources<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> Clone for PayloadTransactionSources<T> {
+    fn clone(&self) -> Self {
+        Self { sources: self.sources.clone() }
+    }
+}
+
+/// Two sets are equal if they share the same sources, phases and budgets.
+impl<T> PartialEq for PayloadTransactionSources<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.sources.len() == other.sources.len() &&
+            self.sources.iter().zip(&other.sources).all(|(a, b)| {
+                a.phase == b.phase && a.budget == b.budget && Arc::ptr_eq(&a.source, &b.source)
+            })
+    }
+}
+
+impl<T> Eq for PayloadTransactionSources<T> {}
+
+impl<T> fmt::Debug for PayloadTransactionSources<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list()
+            .entries(self.sources.iter().map(|source| (source.phase, source.budget)))
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn empty_source(_: &PayloadSourceContext<'_, ()>) -> Vec<PayloadTransactionGroup<()>> {
+        Vec::new()
+    }
+
+    #[test]
+    fn sources_are_ordered_by_phase() {
+        let sources = PayloadTransactionSources::<()>::new()
+            .with_source(BuildPhase::EndOfBlock, SourceBudget::unlimited(), empty_source)
+            .with_source(
+                BuildPhase::TopOfBlock,
+                SourceBudget::unlimited().with_gas_limit(1),
+                empty_source,
+            )
+            .with_source(
+                BuildPhase::TopOfBlock,
+                SourceBudget::unlimited().with_gas_limit(2),
+                empty_source,
+            );
+
+        let budgets = sources
+            .phase(BuildPhase::TopOfBlock)
+            .map(|(budget, _)| budget.gas_limit)
+            .collect::<Vec<_>>();
+        assert_eq!(budgets, vec![Some(1), Some(2)]);
+        assert_eq!(sources.phase(BuildPhase::BeforePool).count(), 0);
+        assert_eq!(sources.phase(BuildPhase::EndOfBlock).count(), 1);
+        assert_eq!(sources, sources.clone());
+    }
+
+    #[test]
+    fn group_reverts() {
+        let group = PayloadTransactionGroup::<()>::new(Vec::new());
+        assert!(group.may_revert(&B256::ZERO));
+
+        let group = group.with_reverting_tx_hashes(vec![B256::with_last_byte(1)]);
+        assert!(group.may_revert(&B256::with_last_byte(1)));
+        assert!(!group.may_revert(&B256::ZERO));
+    }
+
+    #[test]
+    fn budget_limits() {
+        let budget = SourceBudget::unlimited().with_gas_limit(100).with_blob_gas_limit(0);
+        assert!(budget.allows(100, 0));
+        assert!(!budget.allows(101, 0));
+        assert!(!budget.allows(0, DATA_GAS_PER_BLOB));
+        assert!(SourceBudget::unlimited().allows(u64::MAX, u64::MAX));
+    }
+}
@@ -6,10 +6,11 @@ use crate::{
 use itertools::Itertools;
 use reth_db::{tables, transaction::DbTxMut};
 use reth_db_api::models::ShardedKey;
-use reth_provider::DBProvider;
+use reth_provider::{ChangeSetReader, DBProvider, StaticFileProviderFactory};
 use reth_prune_types::{
     PruneMode, PrunePurpose, PruneSegment, SegmentOutput, SegmentOutputCheckpoint,
 };
+use reth_static_file_types::StaticFileSegment;
 use rustc_hash::FxHashMap;
 use tracing::{instrument, trace};
 
@@ -32,7 +33,7 @@ impl AccountHistory {
 
 impl<Provider> Segment<Provider> for AccountHistory
 where
-    Provider: DBProvider<Tx: DbTxMut>,
+    Provider: DBProvider<Tx: DbTxMut> + StaticFileProviderFactory,
 {
     fn segment(&self) -> PruneSegment {
         PruneSegment::AccountHistory
@@ -79,7 +80,44 @@ where
         // size should be up to 0.5MB + some hashmap overhead. `blocks_since_last_run` is
         // additionally limited by the `max_reorg_depth`, so no OOM is expected here.
         let mut highest_deleted_accounts = FxHashMap::default();
-        let (pruned_changesets, done) =
+
+        // Account changesets that were moved to static files are read from there block by block.
+        // They can't be deleted from a static file, so only the static files that end below the
+        // pruned block are deleted.
+        let static_file_provider = provider.static_file_provider();
+        let mut pruned_static_file_changesets = 0;
+        let mut static_file_done = true;
+        if let Some((lowest_block, highest_block)) = static_file_provider
+            .get_lowest_static_file_block(StaticFileSegment::AccountChangeSets)
+            .zip(
+                static_file_provider
+                    .get_highest_static_file_block(StaticFileSegment::AccountChangeSets),
+            )
+        {
+            for block_number in (*range.start()).max(lowest_block)..=range_end.min(highest_block) {
+                if limiter.is_limit_reached() {
+                    static_file_done = false;
+                    break
+                }
+
+                let changeset = static_file_provider.account_block_changeset(block_number)?;
+                limiter.increment_deleted_entries_count_by(changeset.len());
+                pruned_static_file_changesets += changeset.len();
+                for account in changeset {
+                    highest_deleted_accounts.insert(account.address, block_number);
+                }
+                last_changeset_pruned_block = Some(block_number);
+            }
+
+            if let Some(block_number) = last_changeset_pruned_block {
+                static_file_provider.delete_segment_below_block(
+                    StaticFileSegment::AccountChangeSets,
+                    block_number + 1,
+                )?;
+            }
+        }
+
+        let (pruned_changesets, done) = if static_file_done {
             provider.tx_ref().prune_table_with_range::<tables::AccountChangeSets>(
                 range,
                 &mut limiter,
@@ -88,13 +126,22 @@ where
                     highest_deleted_accounts.insert(account.address, block_number);
                     last_changeset_pruned_block = Some(block_number);
                 },
-            )?;
-        trace!(target: "pruner", pruned = %pruned_changesets, %done, "Pruned account history (changesets)");
+            )?
+        } else {
+            (0, false)
+        };
+        trace!(target: "pruner", pruned = %pruned_changesets, static_file_pruned = %pruned_static_file_changesets, %done, "Pruned account history (changesets)");
 
         let last_changeset_pruned_block = last_changeset_pruned_block
             // If there's more account changesets to prune, set the checkpoint block number to
             // previous, so we could finish pruning its account changesets on the next run.
-            .map(|block_number| if done { block_number } else { block_number.saturating_sub(1) })
+            .map(|block_number| {
+                if done || !static_file_done {
+                    block_number
+                } else {
+                    block_number.saturating_sub(1)
+                }
+            })
             .unwrap_or(range_end);
 
         // Sort highest deleted block numbers by account address and turn them into sharded keys.
@@ -116,7 +163,7 @@ where
 
         Ok(SegmentOutput {
             progress,
-            pruned: pruned_changesets + outcomes.deleted,
+            pruned: pruned_static_file_changesets + pruned_changesets + outcomes.deleted,
             checkpoint: Some(SegmentOutputCheckpoint {
                 block_number: Some(last_changeset_pruned_block),
                 tx_number: None,
@@ -0,0 +1,304 @@
+use crate::{
+    db_ext::DbTxPruneExt,
+    segments::{PruneInput, Segment},
+    PrunerError,
+};
+use alloy_eips::eip2718::Encodable2718;
+use alloy_primitives::BlockNumber;
+use rayon::prelude::*;
+use reth_db::{tables, transaction::DbTxMut};
+use reth_primitives_traits::NodePrimitives;
+use reth_provider::{
+    providers::StaticFileProvider, BlockReader, DBProvider, StaticFileProviderFactory,
+};
+use reth_prune_types::{
+    PruneMode, PrunePurpose, PruneSegment, SegmentOutput, SegmentOutputCheckpoint,
+};
+use reth_static_file_types::StaticFileSegment;
+use tracing::{instrument, trace};
+
+/// Expires the transactions and receipts of old blocks by deleting their static files. Headers
+/// are retained.
+///
+/// The transaction lookup, block body indices and transaction blocks entries of the expired
+/// blocks are deleted from the database first, so that lookups of expired blocks and
+/// transactions report them as missing instead of pointing into the deleted static files.
+#[derive(Debug)]
+pub struct Bodies {
+    mode: PruneMode,
+}
+
+impl Bodies {
+    pub const fn new(mode: PruneMode) -> Self {
+        Self { mode }
+    }
+
+    /// Returns the highest block whose transactions static file can be deleted when pruning up
+    /// to `to_block`.
+    ///
+    /// Static files can only be deleted as a whole, so the ones that still contain blocks after
+    /// the target block are kept until a later run. The static file of the highest block is never
+    /// deleted.
+    fn last_expired_block<N: NodePrimitives>(
+        static_file_provider: &StaticFileProvider<N>,
+        to_block: BlockNumber,
+    ) -> Option<BlockNumber> {
+        let highest_block =
+            static_file_provider.get_highest_static_file_block(StaticFileSegment::Transactions)?;
+        let limit = to_block
+            .min(static_file_provider.find_fixed_range(highest_block).start().checked_sub(1)?);
+        let range = static_file_provider.find_fixed_range(limit);
+        if range.end() == limit {
+            Some(limit)
+        } else {
+            range.start().checked_sub(1)
+        }
+    }
+}
+
+impl<Provider> Segment<Provider> for Bodies
+where
+    Provider: StaticFileProviderFactory
+        + DBProvider<Tx: DbTxMut>
+        + BlockReader<Transaction: Encodable2718>,
+{
+    fn segment(&self) -> PruneSegment {
+        PruneSegment::Bodies
+    }
+
+    fn mode(&self) -> Option<PruneMode> {
+        Some(self.mode)
+    }
+
+    fn purpose(&self) -> PrunePurpose {
+        PrunePurpose::User
+    }
+
+    #[instrument(level = "trace", target = "pruner", skip(self, provider), ret)]
+    fn prune(&self, provider: &Provider, input: PruneInput) -> Result<SegmentOutput, PrunerError> {
+        let static_file_provider = provider.static_file_provider();
+
+        let Some(to_block) = Self::last_expired_block(&static_file_provider, input.to_block) else {
+            trace!(target: "pruner", "No bodies to expire");
+            return Ok(SegmentOutput::done())
+        };
+        let block_range = input.get_start_next_block_range()..=to_block;
+        if block_range.is_empty() {
+            trace!(target: "pruner", "No bodies to expire");
+            return Ok(SegmentOutput::done())
+        }
+
+        let (Some(first_body), Some(last_body)) = (
+            provider.block_body_indices(*block_range.start())?,
+            provider.block_body_indices(to_block)?,
+        ) else {
+            return Err(PrunerError::InconsistentData(
+                "Block body indices of expired blocks not found",
+            ))
+        };
+        let tx_range = first_body.first_tx_num()..last_body.next_tx_num();
+
+        // Retrieve transactions in the range and calculate their hashes in parallel
+        let hashes = provider
+            .transactions_by_tx_range(tx_range.clone())?
+            .into_par_iter()
+            .map(|transaction| transaction.trie_hash())
+            .collect::<Vec<_>>();
+        if hashes.len() as u64 != tx_range.end - tx_range.start {
+            return Err(PrunerError::InconsistentData(
+                "Unexpected number of transaction hashes retrieved by transaction number range",
+            ))
+        }
+
+        let mut limiter = input.limiter;
+        let tx = provider.tx_ref();
+
+        // The block body indices are deleted last, because the next run needs the ones of the
+        // first block to find the transactions to delete.
+        let (mut pruned, mut done) = tx
+            .prune_table_with_iterator::<tables::TransactionHashNumbers>(
+                hashes,
+                &mut limiter,
+                |_| {},
+            )?;
+        if done {
+            let (pruned_blocks, blocks_done) = tx
+                .prune_table_with_range::<tables::TransactionBlocks>(
+                    tx_range.clone(),
+                    &mut limiter,
+                    |_| false,
+                    |_| {},
+                )?;
+            pruned += pruned_blocks;
+            done = blocks_done;
+        }
+        let mut last_pruned_block = None;
+        if done {
+            let (pruned_indices, indices_done) = tx
+                .prune_table_with_range::<tables::BlockBodyIndices>(
+                    block_range,
+                    &mut limiter,
+                    |_| false,
+                    |(block_number, _)| last_pruned_block = Some(block_number),
+                )?;
+            pruned += pruned_indices;
+            done = indices_done;
+        }
+
+        if done {
+            for segment in [StaticFileSegment::Transactions, StaticFileSegment::Receipts] {
+                let deleted =
+                    static_file_provider.delete_segment_below_block(segment, to_block + 1)?;
+                for header in deleted {
+                    pruned += header.tx_len().unwrap_or_default() as usize;
+                }
+            }
+        }
+        trace!(target: "pruner", %pruned, %done, "Pruned bodies");
+
+        Ok(SegmentOutput {
+            progress: limiter.progress(done),
+            pruned,
+            // Until the static files are deleted, the checkpoint only advances past the blocks
+            // whose body indices were deleted, so that the next run continues with the rest.
+            checkpoint: (done || last_pruned_block.is_some()).then(|| SegmentOutputCheckpoint {
+                block_number: if done { Some(to_block) } else { last_pruned_block },
+                tx_number: done.then(|| tx_range.end.checked_sub(1)).flatten().or_else(|| {
+                    input.previous_checkpoint.and_then(|checkpoint| checkpoint.tx_number)
+                }),
+            }),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::segments::{Bodies, PruneInput, PruneLimiter, Segment};
+    use alloy_primitives::B256;
+    use reth_chainspec::MAINNET;
+    use reth_db::{
+        tables,
+        test_utils::{create_test_rw_db, create_test_static_files_dir},
+        transaction::DbTx,
+    };
+    use reth_provider::{
+        providers::{StaticFileProvider, StaticFileWriter},
+        test_utils::MockNodeTypesWithDB,
+        BlockNumReader, BlockWriter, DBProvider, DatabaseProviderFactory, ProviderFactory,
+        StaticFileProviderFactory, StorageLocation, TransactionsProvider,
+    };
+    use reth_prune_types::{PruneCheckpoint, PruneMode, PruneProgress};
+    use reth_static_file_types::StaticFileSegment;
+    use reth_testing_utils::generators::{self, random_block_range, BlockRangeParams};
+
+    #[test]
+    fn prune() {
+        let (_static_dir, static_dir_path) = create_test_static_files_dir();
+        let factory = ProviderFactory::<MockNodeTypesWithDB>::new(
+            create_test_rw_db(),
+            MAINNET.clone(),
+            StaticFileProvider::read_write(static_dir_path)
+                .unwrap()
+                .with_custom_blocks_per_file(10),
+        );
+        let static_file_provider = factory.static_file_provider();
+
+        let mut rng = generators::rng();
+        let blocks = random_block_range(
+            &mut rng,
+            0..=29,
+            BlockRangeParams { parent: Some(B256::ZERO), tx_count: 1..3, ..Default::default() },
+        );
+        {
+            let provider_rw = factory.database_provider_rw().unwrap();
+            let mut tx_num = 0;
+            for block in &blocks {
+                provider_rw
+                    .insert_block(
+                        block.clone().seal_with_senders().unwrap(),
+                        StorageLocation::StaticFiles,
+                    )
+                    .unwrap();
+
+                let mut receipts_writer =
+                    static_file_provider.latest_writer(StaticFileSegment::Receipts).unwrap();
+                receipts_writer.increment_block(block.number).unwrap();
+                for _ in &block.body.transactions {
+                    receipts_writer.append_receipt(tx_num, &Default::default()).unwrap();
+                    tx_num += 1;
+                }
+            }
+            static_file_provider.commit().unwrap();
+            provider_rw.commit().unwrap();
+        }
+        assert_eq!(factory.earliest_history_height().unwrap(), 0);
+
+        let expired_transactions =
+            blocks[..20].iter().map(|block| block.body.transactions.len()).sum::<usize>();
+        let retained_transactions =
+            blocks[20..].iter().map(|block| block.body.transactions.len()).sum::<usize>();
+        let segment = Bodies::new(PruneMode::Before(25));
+
+        // The deleted entries limit is reached before all database entries are deleted, so the
+        // static files are kept.
+        let provider = factory.database_provider_rw().unwrap();
+        let input = PruneInput {
+            previous_checkpoint: None,
+            to_block: 24,
+            limiter: PruneLimiter::default().set_deleted_entries_limit(expired_transactions + 1),
+        };
+        let result = segment.prune(&provider, input).unwrap();
+        assert!(!result.progress.is_finished());
+        assert_eq!(result.pruned, expired_transactions + 1);
+        assert_eq!(result.checkpoint, None);
+        assert_eq!(factory.earliest_history_height().unwrap(), 0);
+
+        let input = PruneInput {
+            previous_checkpoint: None,
+            to_block: 24,
+            limiter: PruneLimiter::default(),
+        };
+        let result = segment.prune(&provider, input).unwrap();
+
+        // The static files of blocks 20..=29 still contain blocks that must be retained.
+        assert_eq!(result.progress, PruneProgress::Finished);
+        // The transaction lookup entries and the first transaction blocks entry were deleted by
+        // the previous run.
+        assert_eq!(result.pruned, 19 + 20 + expired_transactions * 2);
+        let checkpoint = result.checkpoint.unwrap();
+        assert_eq!(checkpoint.block_number, Some(19));
+        assert_eq!(checkpoint.tx_number, Some(expired_transactions as u64 - 1));
+        for segment in [StaticFileSegment::Transactions, StaticFileSegment::Receipts] {
+            assert_eq!(static_file_provider.get_lowest_static_file_block(segment), Some(20));
+            assert_eq!(static_file_provider.get_highest_static_file_block(segment), Some(29));
+        }
+        assert_eq!(factory.earliest_history_height().unwrap(), 20);
+
+        // Lookups of the expired blocks and transactions report them as missing.
+        let tx = provider.tx_ref();
+        assert_eq!(tx.entries::<tables::TransactionHashNumbers>().unwrap(), retained_transactions);
+        assert_eq!(tx.entries::<tables::TransactionBlocks>().unwrap(), 10);
+        assert_eq!(tx.entries::<tables::BlockBodyIndices>().unwrap(), 10);
+        let expired_hash = blocks[0].body.transactions[0].hash();
+        assert_eq!(provider.transaction_id(expired_hash).unwrap(), None);
+        let retained_hash = blocks[20].body.transactions[0].hash();
+        assert_eq!(
+            provider.transaction_id(retained_hash).unwrap(),
+            Some(expired_transactions as u64)
+        );
+
+        // Nothing is left to expire until the static file of blocks 20..=29 is complete.
+        let input = PruneInput {
+            previous_checkpoint: Some(PruneCheckpoint {
+                block_number: checkpoint.block_number,
+                tx_number: checkpoint.tx_number,
+                prune_mode: PruneMode::Before(25),
+            }),
+            to_block: 24,
+            limiter: PruneLimiter::default(),
+        };
+        let result = segment.prune(&provider, input).unwrap();
+        assert_eq!(result.pruned, 0);
+        assert_eq!(result.checkpoint, None);
+    }
+}
@@ -6,8 +6,9 @@ use crate::{
 use itertools::Itertools;
 use reth_db::{tables, transaction::DbTxMut};
 use reth_db_api::models::{storage_sharded_key::StorageShardedKey, BlockNumberAddress};
-use reth_provider::DBProvider;
+use reth_provider::{DBProvider, StaticFileProviderFactory, StorageChangeSetReader};
 use reth_prune_types::{PruneMode, PrunePurpose, PruneSegment, SegmentOutputCheckpoint};
+use reth_static_file_types::StaticFileSegment;
 use rustc_hash::FxHashMap;
 use tracing::{instrument, trace};
 
@@ -30,7 +31,7 @@ impl StorageHistory {
 
 impl<Provider> Segment<Provider> for StorageHistory
 where
-    Provider: DBProvider<Tx: DbTxMut>,
+    Provider: DBProvider<Tx: DbTxMut> + StaticFileProviderFactory,
 {
     fn segment(&self) -> PruneSegment {
         PruneSegment::StorageHistory
@@ -77,7 +78,44 @@ where
         // size should be up to 0.5MB + some hashmap overhead. `blocks_since_last_run` is
         // additionally limited by the `max_reorg_depth`, so no OOM is expected here.
         let mut highest_deleted_storages = FxHashMap::default();
-        let (pruned_changesets, done) =
+
+        // Storage changesets that were moved to static files are read from there block by block.
+        // They can't be deleted from a static file, so only the static files that end below the
+        // pruned block are deleted.
+        let static_file_provider = provider.static_file_provider();
+        let mut pruned_static_file_changesets = 0;
+        let mut static_file_done = true;
+        if let Some((lowest_block, highest_block)) = static_file_provider
+            .get_lowest_static_file_block(StaticFileSegment::StorageChangeSets)
+            .zip(
+                static_file_provider
+                    .get_highest_static_file_block(StaticFileSegment::StorageChangeSets),
+            )
+        {
+            for block_number in (*range.start()).max(lowest_block)..=range_end.min(highest_block) {
+                if limiter.is_limit_reached() {
+                    static_file_done = false;
+                    break
+                }
+
+                let changeset = static_file_provider.storage_changeset(block_number)?;
+                limiter.increment_deleted_entries_count_by(changeset.len());
+                pruned_static_file_changesets += changeset.len();
+                for (BlockNumberAddress((_, address)), entry) in changeset {
+                    highest_deleted_storages.insert((address, entry.key), block_number);
+                }
+                last_changeset_pruned_block = Some(block_number);
+            }
+
+            if let Some(block_number) = last_changeset_pruned_block {
+                static_file_provider.delete_segment_below_block(
+                    StaticFileSegment::StorageChangeSets,
+                    block_number + 1,
+                )?;
+            }
+        }
+
+        let (pruned_changesets, done) = if static_file_done {
             provider.tx_ref().prune_table_with_range::<tables::StorageChangeSets>(
                 BlockNumberAddress::range(range),
                 &mut limiter,
@@ -86,13 +124,22 @@ where
                     highest_deleted_storages.insert((address, entry.key), block_number);
                     last_changeset_pruned_block = Some(block_number);
                 },
-            )?;
-        trace!(target: "pruner", deleted = %pruned_changesets, %done, "Pruned storage history (changesets)");
+            )?
+        } else {
+            (0, false)
+        };
+        trace!(target: "pruner", deleted = %pruned_changesets, static_file_deleted = %pruned_static_file_changesets, %done, "Pruned storage history (changesets)");
 
         let last_changeset_pruned_block = last_changeset_pruned_block
             // If there's more storage changesets to prune, set the checkpoint block number to
             // previous, so we could finish pruning its storage changesets on the next run.
-            .map(|block_number| if done { block_number } else { block_number.saturating_sub(1) })
+            .map(|block_number| {
+                if done || !static_file_done {
+                    block_number
+                } else {
+                    block_number.saturating_sub(1)
+                }
+            })
             .unwrap_or(range_end);
 
         // Sort highest deleted block numbers by account address and storage key and turn them into
@@ -119,7 +166,7 @@ where
 
         Ok(SegmentOutput {
             progress,
-            pruned: pruned_changesets + outcomes.deleted,
+            pruned: pruned_static_file_changesets + pruned_changesets + outcomes.deleted,
             checkpoint: Some(SegmentOutputCheckpoint {
                 block_number: Some(last_changeset_pruned_block),
                 tx_number: None,
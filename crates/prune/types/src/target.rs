@@ -37,6 +37,16 @@ pub struct PruneModes {
         deserialize_with = "deserialize_opt_prune_mode_with_min_blocks::<MINIMUM_PRUNING_DISTANCE, _>"
     )]
     pub storage_history: Option<PruneMode>,
+    /// Bodies history pruning configuration.
+    ///
+    /// Transactions and receipts of the blocks before the target are expired by deleting their
+    /// static files, while the headers are retained. Static files are only deleted as a whole,
+    /// so the history of some blocks before the target may still be available.
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        deserialize_with = "deserialize_opt_prune_mode_with_min_blocks::<MINIMUM_PRUNING_DISTANCE, _>"
+    )]
+    pub bodies_history: Option<PruneMode>,
     /// Receipts pruning configuration by retaining only those receipts that contain logs emitted
     /// by the specified addresses, discarding others. This setting is overridden by `receipts`.
     ///
@@ -59,6 +69,7 @@ impl PruneModes {
             receipts: Some(PruneMode::Full),
             account_history: Some(PruneMode::Full),
             storage_history: Some(PruneMode::Full),
+            bodies_history: Some(PruneMode::Full),
             receipts_log_filter: Default::default(),
         }
     }
@@ -2,7 +2,7 @@ use jsonrpsee::{core::RpcResult, proc_macros::rpc};
 
 use alloy_primitives::{Address, Bytes, B256, U256};
 use alloy_rpc_types_anvil::{Forking, Metadata, MineOptions, NodeInfo};
-use alloy_rpc_types_eth::Block;
+use alloy_rpc_types_eth::{Block, TransactionRequest};
 
 /// Anvil rpc interface.
 /// https://book.getfoundry.sh/reference/anvil/#custom-methods
@@ -163,3 +163,12 @@ pub trait AnvilApi {
     #[method(name = "removePoolTransactions")]
     async fn anvil_remove_pool_transactions(&self, address: Address) -> RpcResult<()>;
 }
+
+/// The `eth_` methods that behave differently on a node that serves the `anvil_` namespace.
+#[cfg_attr(not(feature = "client"), rpc(server, namespace = "eth"))]
+#[cfg_attr(feature = "client", rpc(server, client, namespace = "eth"))]
+pub trait AnvilEthApi {
+    /// Sends a transaction, which may come from an impersonated account, and returns its hash.
+    #[method(name = "sendTransaction")]
+    async fn send_transaction(&self, request: TransactionRequest) -> RpcResult<B256>;
+}
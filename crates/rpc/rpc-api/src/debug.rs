@@ -1,16 +1,19 @@
 use alloy_eips::{BlockId, BlockNumberOrTag};
-use alloy_primitives::{Address, Bytes, B256};
+use alloy_json_rpc::RpcObject;
+use alloy_primitives::{Address, Bytes, B256, U256, U64};
 use alloy_rpc_types_debug::ExecutionWitness;
-use alloy_rpc_types_eth::{transaction::TransactionRequest, Block, Bundle, StateContext};
+use alloy_rpc_types_eth::{transaction::TransactionRequest, Bundle, StateContext};
 use alloy_rpc_types_trace::geth::{
-    BlockTraceResult, GethDebugTracingCallOptions, GethDebugTracingOptions, GethTrace, TraceResult,
+    GethDebugTracingCallOptions, GethDebugTracingOptions, GethTrace, TraceResult,
 };
 use jsonrpsee::{core::RpcResult, proc_macros::rpc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 
 /// Debug rpc interface.
 #[cfg_attr(not(feature = "client"), rpc(server, namespace = "debug"))]
 #[cfg_attr(feature = "client", rpc(server, client, namespace = "debug"))]
-pub trait DebugApi {
+pub trait DebugApi<B: RpcObject> {
     /// Returns an RLP-encoded header.
     #[method(name = "getRawHeader")]
     async fn raw_header(&self, block_id: BlockId) -> RpcResult<Bytes>;
@@ -35,16 +38,24 @@ pub trait DebugApi {
 
     /// Returns an array of recent bad blocks that the client has seen on the network.
     #[method(name = "getBadBlocks")]
-    async fn bad_blocks(&self) -> RpcResult<Vec<Block>>;
+    async fn bad_blocks(&self) -> RpcResult<Vec<BadBlock<B>>>;
 
     /// Returns the structured logs created during the execution of EVM between two blocks
-    /// (excluding start) as a JSON object.
-    #[method(name = "traceChain")]
+    /// (excluding start).
+    ///
+    /// The blocks are traced in parallel and the result of every block is streamed as a separate
+    /// notification, in block order.
+    #[subscription(
+        name = "traceChain",
+        unsubscribe = "traceChain_unsubscribe",
+        item = alloy_rpc_types_trace::geth::BlockTraceResult
+    )]
     async fn debug_trace_chain(
         &self,
         start_exclusive: BlockNumberOrTag,
         end_inclusive: BlockNumberOrTag,
-    ) -> RpcResult<Vec<BlockTraceResult>>;
+        opts: Option<GethDebugTracingOptions>,
+    ) -> jsonrpsee::core::SubscriptionResult;
 
     /// The `debug_traceBlock` method will return a full stack trace of all invoked opcodes of all
     /// transaction that were included in this block.
@@ -148,10 +159,10 @@ pub trait DebugApi {
     async fn debug_backtrace_at(&self, location: &str) -> RpcResult<()>;
 
     /// Enumerates all accounts at a given block with paging capability. `maxResults` are returned
-    /// in the page and the items have keys that come after the `start` key (hashed address).
+    /// in the page and the items have keys that come after the `start` key.
     ///
-    /// If incompletes is false, then accounts for which the key preimage (i.e: the address) doesn't
-    /// exist in db are skipped. NB: geth by default does not store preimages.
+    /// Unlike geth, which pages by hashed address, the accounts are ordered and paged by address,
+    /// because reth keeps the plain state. All addresses are known, so `incompletes` has no effect.
     #[method(name = "accountRange")]
     async fn debug_account_range(
         &self,
@@ -161,7 +172,7 @@ pub trait DebugApi {
         nocode: bool,
         nostorage: bool,
         incompletes: bool,
-    ) -> RpcResult<()>;
+    ) -> RpcResult<AccountRangeResult>;
 
     /// Turns on block profiling for the given duration and writes profile data to disk. It uses a
     /// profile rate of 1 for most accurate information. If a different rate is desired, set the
@@ -174,9 +185,12 @@ pub trait DebugApi {
     #[method(name = "chaindbCompact")]
     async fn debug_chaindb_compact(&self) -> RpcResult<()>;
 
-    /// Returns leveldb properties of the key-value database.
+    /// Returns properties of the key-value database.
+    ///
+    /// Supported properties are `stats`, which returns the number of entries of every table, and
+    /// the name of a table, which returns the number of entries of that table.
     #[method(name = "chaindbProperty")]
-    async fn debug_chaindb_property(&self, property: String) -> RpcResult<()>;
+    async fn debug_chaindb_property(&self, property: String) -> RpcResult<String>;
 
     /// Turns on CPU profiling for the given duration and writes profile data to disk.
     #[method(name = "cpuProfile")]
@@ -185,16 +199,26 @@ pub trait DebugApi {
     /// Retrieves an ancient binary blob from the freezer. The freezer is a collection of
     /// append-only immutable files. The first argument `kind` specifies which table to look up data
     /// from. The list of all table kinds are as follows:
+    ///  - `headers`: the RLP encoded block header
+    ///  - `hashes`: the block hash
+    ///  - `bodies`: the RLP encoded block body
+    ///  - `receipts`: the RLP encoded receipts of the block
+    ///  - `diffs`: the RLP encoded total difficulty of the block
+    ///
+    /// In reth, the ancient store are the static files.
     #[method(name = "dbAncient")]
-    async fn debug_db_ancient(&self, kind: String, number: u64) -> RpcResult<()>;
+    async fn debug_db_ancient(&self, kind: String, number: u64) -> RpcResult<Bytes>;
 
     /// Returns the number of ancient items in the ancient store.
     #[method(name = "dbAncients")]
-    async fn debug_db_ancients(&self) -> RpcResult<()>;
+    async fn debug_db_ancients(&self) -> RpcResult<u64>;
 
     /// Returns the raw value of a key stored in the database.
+    ///
+    /// The key is the name of the table and the hex encoded raw key, separated by a colon, e.g.
+    /// `PlainAccountState:0x<address>`.
     #[method(name = "dbGet")]
-    async fn debug_db_get(&self, key: String) -> RpcResult<()>;
+    async fn debug_db_get(&self, key: String) -> RpcResult<Bytes>;
 
     /// Retrieves the state that corresponds to the block number and returns a list of accounts
     /// (including storage and code).
@@ -232,17 +256,18 @@ pub trait DebugApi {
     async fn debug_get_modified_accounts_by_hash(
         &self,
         start_hash: B256,
-        end_hash: B256,
-    ) -> RpcResult<()>;
+        end_hash: Option<B256>,
+    ) -> RpcResult<Vec<Address>>;
 
     /// Returns all accounts that have changed between the two blocks specified. A change is defined
-    /// as a difference in nonce, balance, code hash or storage hash.
+    /// as a difference in nonce, balance, code hash or storage hash. With one parameter, returns
+    /// the list of accounts modified in the specified block.
     #[method(name = "getModifiedAccountsByNumber")]
     async fn debug_get_modified_accounts_by_number(
         &self,
         start_number: u64,
-        end_number: u64,
-    ) -> RpcResult<()>;
+        end_number: Option<u64>,
+    ) -> RpcResult<Vec<Address>>;
 
     /// Turns on Go runtime tracing for the given duration and writes trace data to disk.
     #[method(name = "goTrace")]
@@ -254,8 +279,8 @@ pub trait DebugApi {
     async fn debug_intermediate_roots(
         &self,
         block_hash: B256,
-        opts: Option<GethDebugTracingCallOptions>,
-    ) -> RpcResult<()>;
+        opts: Option<GethDebugTracingOptions>,
+    ) -> RpcResult<Vec<B256>>;
 
     /// Returns detailed runtime memory statistics.
     #[method(name = "memStats")]
@@ -268,8 +293,10 @@ pub trait DebugApi {
     async fn debug_mutex_profile(&self, file: String, nsec: u64) -> RpcResult<()>;
 
     /// Returns the preimage for a sha3 hash, if known.
+    ///
+    /// Note: reth does not record preimages, so this always returns an error.
     #[method(name = "preimage")]
-    async fn debug_preimage(&self, hash: B256) -> RpcResult<()>;
+    async fn debug_preimage(&self, hash: B256) -> RpcResult<Bytes>;
 
     /// Retrieves a block and returns its pretty printed form.
     #[method(name = "printBlock")]
@@ -292,7 +319,7 @@ pub trait DebugApi {
     /// Sets the current head of the local chain by block number. Note, this is a destructive action
     /// and may severely damage your chain. Use with extreme caution.
     #[method(name = "setHead")]
-    async fn debug_set_head(&self, number: u64) -> RpcResult<()>;
+    async fn debug_set_head(&self, number: U64) -> RpcResult<()>;
 
     /// Sets the rate of mutex profiling.
     #[method(name = "setMutexProfileFraction")]
@@ -308,22 +335,27 @@ pub trait DebugApi {
     #[method(name = "stacks")]
     async fn debug_stacks(&self) -> RpcResult<()>;
 
-    /// Used to obtain info about a block.
+    /// This method is similar to `debug_standardTraceBlockToFile`, but can be used to obtain info
+    /// about a block which has been rejected as invalid (for some reason).
+    ///
+    /// Returns the paths of the files the traces were written to.
     #[method(name = "standardTraceBadBlockToFile")]
     async fn debug_standard_trace_bad_block_to_file(
         &self,
-        block: BlockNumberOrTag,
-        opts: Option<GethDebugTracingCallOptions>,
-    ) -> RpcResult<()>;
+        block_hash: B256,
+        opts: Option<GethDebugTracingOptions>,
+    ) -> RpcResult<Vec<String>>;
 
-    /// This method is similar to `debug_standardTraceBlockToFile`, but can be used to obtain info
-    /// about a block which has been rejected as invalid (for some reason).
+    /// Replays the block with the standard json tracer and writes the trace of each transaction to
+    /// a separate file.
+    ///
+    /// Returns the paths of the files the traces were written to.
     #[method(name = "standardTraceBlockToFile")]
     async fn debug_standard_trace_block_to_file(
         &self,
-        block: BlockNumberOrTag,
-        opts: Option<GethDebugTracingCallOptions>,
-    ) -> RpcResult<()>;
+        block_hash: B256,
+        opts: Option<GethDebugTracingOptions>,
+    ) -> RpcResult<Vec<String>>;
 
     /// Turns on CPU profiling indefinitely, writing to the given file.
     #[method(name = "startCPUProfile")]
@@ -352,7 +384,7 @@ pub trait DebugApi {
         contract_address: Address,
         key_start: B256,
         max_result: u64,
-    ) -> RpcResult<()>;
+    ) -> RpcResult<StorageRangeResult>;
 
     /// Returns the structured logs created during the execution of EVM against a block pulled
     /// from the pool of bad ones and returns them as a JSON object. For the second parameter see
@@ -361,15 +393,21 @@ pub trait DebugApi {
     async fn debug_trace_bad_block(
         &self,
         block_hash: B256,
-        opts: Option<GethDebugTracingCallOptions>,
-    ) -> RpcResult<()>;
+        opts: Option<GethDebugTracingOptions>,
+    ) -> RpcResult<Vec<TraceResult>>;
 
     /// Sets the logging verbosity ceiling. Log messages with level up to and including the given
     /// level will be printed.
+    ///
+    /// The levels are the geth levels: 0 (off), 1 (error), 2 (warn), 3 (info), 4 (debug) and 5
+    /// (trace).
     #[method(name = "verbosity")]
     async fn debug_verbosity(&self, level: usize) -> RpcResult<()>;
 
     /// Sets the logging verbosity pattern.
+    ///
+    /// The pattern is a comma separated list of `<target>=<level>` directives, where the level is
+    /// either a geth level or a level name, e.g. `net=4,engine::tree=trace`.
     #[method(name = "vmodule")]
     async fn debug_vmodule(&self, pattern: String) -> RpcResult<()>;
 
@@ -408,3 +446,72 @@ pub trait DebugExecutionWitnessApi<Attributes> {
         attributes: Attributes,
     ) -> RpcResult<ExecutionWitness>;
 }
+
+/// A block that was rejected as invalid, returned by `debug_getBadBlocks`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BadBlock<B> {
+    /// Hash of the block.
+    pub hash: B256,
+    /// The block, with the hashes of its transactions.
+    pub block: B,
+    /// RLP encoded block.
+    pub rlp: Bytes,
+    /// The validation error the block was rejected with.
+    pub error: String,
+}
+
+/// A page of the accounts of the state at a block, returned by `debug_accountRange`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AccountRangeResult {
+    /// The state root of the block.
+    pub root: B256,
+    /// The accounts of the page.
+    pub accounts: BTreeMap<Address, DumpAccount>,
+    /// The address of the first account of the next page, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub next: Option<Address>,
+}
+
+/// An account of the state, as returned by `debug_accountRange`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DumpAccount {
+    /// The balance of the account, as a decimal string.
+    pub balance: String,
+    /// The nonce of the account.
+    pub nonce: u64,
+    /// The storage root of the account.
+    pub root: B256,
+    /// The hash of the code of the account.
+    pub code_hash: B256,
+    /// The code of the account, unless `nocode` was requested.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub code: Option<Bytes>,
+    /// The storage of the account, unless `nostorage` was requested.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub storage: Option<BTreeMap<B256, U256>>,
+    /// The address of the account.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub address: Option<Address>,
+    /// The hashed address of the account.
+    pub key: B256,
+}
+
+/// A page of the storage of an account, returned by `debug_storageRangeAt`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StorageRangeResult {
+    /// The storage slots of the page, keyed by the hashed slot.
+    pub storage: BTreeMap<B256, StorageRangeEntry>,
+    /// The first slot of the next page, if any.
+    pub next_key: Option<B256>,
+}
+
+/// A storage slot, as returned by `debug_storageRangeAt`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StorageRangeEntry {
+    /// The slot, if known.
+    pub key: Option<B256>,
+    /// The value of the slot.
+    pub value: B256,
+}
@@ -28,9 +28,12 @@ mod reth;
 mod rpc;
 mod trace;
 mod txpool;
+mod user_operation;
 mod validation;
 mod web3;
 
+pub use debug::{AccountRangeResult, BadBlock, DumpAccount, StorageRangeEntry, StorageRangeResult};
+
 /// re-export of all server traits
 pub use servers::*;
 
@@ -38,9 +41,11 @@ pub use servers::*;
 pub mod servers {
     pub use crate::{
         admin::AdminApiServer,
+        anvil::{AnvilApiServer, AnvilEthApiServer},
         debug::{DebugApiServer, DebugExecutionWitnessApiServer},
         engine::{EngineApiServer, EngineEthApiServer},
-        mev::{MevFullApiServer, MevSimApiServer},
+        hardhat::HardhatApiServer,
+        mev::{MevFullApiServer, MevSendBundleApiServer, MevSimApiServer},
         miner::MinerApiServer,
         net::NetApiServer,
         otterscan::OtterscanServer,
@@ -48,12 +53,13 @@ pub mod servers {
         rpc::RpcApiServer,
         trace::TraceApiServer,
         txpool::TxPoolApiServer,
+        user_operation::EthUserOperationApiServer,
         validation::BlockSubmissionValidationApiServer,
         web3::Web3ApiServer,
     };
     pub use reth_rpc_eth_api::{
         self as eth, EthApiServer, EthBundleApiServer, EthCallBundleApiServer, EthFilterApiServer,
-        EthPubSubApiServer,
+        EthPrivateTransactionApiServer, EthPubSubApiServer, EthSendBundleApiServer,
     };
 }
 
@@ -66,12 +72,12 @@ pub use clients::*;
 pub mod clients {
     pub use crate::{
         admin::AdminApiClient,
-        anvil::AnvilApiClient,
+        anvil::{AnvilApiClient, AnvilEthApiClient},
         debug::{DebugApiClient, DebugExecutionWitnessApiClient},
         engine::{EngineApiClient, EngineEthApiClient},
         ganache::GanacheApiClient,
         hardhat::HardhatApiClient,
-        mev::{MevFullApiClient, MevSimApiClient},
+        mev::{MevFullApiClient, MevSendBundleApiClient, MevSimApiClient},
         miner::MinerApiClient,
         net::NetApiClient,
         otterscan::OtterscanClient,
@@ -79,10 +85,12 @@ pub mod clients {
         rpc::RpcApiServer,
         trace::TraceApiClient,
         txpool::TxPoolApiClient,
+        user_operation::EthUserOperationApiClient,
         validation::BlockSubmissionValidationApiClient,
         web3::Web3ApiClient,
     };
     pub use reth_rpc_eth_api::{
         EthApiClient, EthBundleApiClient, EthCallBundleApiClient, EthFilterApiClient,
+        EthPrivateTransactionApiClient, EthSendBundleApiClient,
     };
 }
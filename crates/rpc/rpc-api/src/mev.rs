@@ -17,6 +17,19 @@ pub trait MevSimApi {
     ) -> jsonrpsee::core::RpcResult<SimBundleResponse>;
 }
 
+/// A subset of the [`MevFullApi`] interface that submits bundles to the local payload builder.
+#[cfg_attr(not(feature = "client"), rpc(server, namespace = "mev"))]
+#[cfg_attr(feature = "client", rpc(server, client, namespace = "mev"))]
+pub trait MevSendBundleApi {
+    /// Submits a bundle to the bundle pool of the local payload builder. It takes in a bundle and
+    /// provides a bundle hash as a return value.
+    #[method(name = "sendBundle")]
+    async fn send_bundle(
+        &self,
+        request: SendBundleRequest,
+    ) -> jsonrpsee::core::RpcResult<SendBundleResponse>;
+}
+
 /// Mev rpc interface.
 #[cfg_attr(not(feature = "client"), rpc(server, namespace = "mev"))]
 #[cfg_attr(feature = "client", rpc(server, client, namespace = "mev"))]
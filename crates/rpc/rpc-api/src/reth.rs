@@ -1,6 +1,7 @@
 use alloy_eips::BlockId;
-use alloy_primitives::{Address, U256};
+use alloy_primitives::{Address, B256, U256};
 use jsonrpsee::{core::RpcResult, proc_macros::rpc};
+use reth_transaction_pool::blobstore::ArchivedBlob;
 use std::collections::HashMap;
 
 /// Reth API namespace for reth-specific methods
@@ -13,4 +14,23 @@ pub trait RethApi {
         &self,
         block_id: BlockId,
     ) -> RpcResult<HashMap<Address, U256>>;
+
+    /// Returns the archived blobs of a block, ordered by their index in the block.
+    ///
+    /// Requires the blob archive to be enabled. Returns an empty list if the block has no blobs or
+    /// is outside of the retention window of the archive.
+    #[method(name = "getBlobSidecarsByBlock")]
+    async fn reth_get_blob_sidecars_by_block(
+        &self,
+        block_id: BlockId,
+    ) -> RpcResult<Vec<ArchivedBlob>>;
+
+    /// Returns the archived blobs with the given versioned hashes, in the same order.
+    ///
+    /// Requires the blob archive to be enabled. Unknown blobs are returned as `null`.
+    #[method(name = "getBlobSidecarsByVersionedHashes")]
+    async fn reth_get_blob_sidecars_by_versioned_hashes(
+        &self,
+        versioned_hashes: Vec<B256>,
+    ) -> RpcResult<Vec<Option<ArchivedBlob>>>;
 }
@@ -1,7 +1,8 @@
 use alloy_json_rpc::RpcObject;
-use alloy_primitives::Address;
+use alloy_primitives::{Address, TxHash};
 use alloy_rpc_types_txpool::{TxpoolContent, TxpoolContentFrom, TxpoolInspect, TxpoolStatus};
 use jsonrpsee::{core::RpcResult, proc_macros::rpc};
+use reth_transaction_pool::TransactionHistoryEvent;
 
 /// Txpool rpc interface.
 #[cfg_attr(not(feature = "client"), rpc(server, namespace = "txpool"))]
@@ -34,4 +35,15 @@ pub trait TxPoolApi<T: RpcObject> {
     /// See [here](https://geth.ethereum.org/docs/rpc/ns-txpool#txpool_content) for more details
     #[method(name = "content")]
     async fn txpool_content(&self) -> RpcResult<TxpoolContent<T>>;
+
+    /// Returns the recent events of the transaction with the given hash, oldest first, e.g. when it
+    /// was added to a sub-pool, and why it was replaced or discarded.
+    ///
+    /// Only the most recent events of all transactions are kept by the pool, so the history of an
+    /// old transaction may be incomplete or empty.
+    #[method(name = "getTransactionHistory")]
+    async fn txpool_get_transaction_history(
+        &self,
+        hash: TxHash,
+    ) -> RpcResult<Vec<TransactionHistoryEvent>>;
 }
@@ -0,0 +1,42 @@
+use alloy_primitives::{Address, B256};
+use alloy_rpc_types_eth::erc4337::{
+    PackedUserOperation, UserOperationGasEstimation, UserOperationReceipt,
+};
+use jsonrpsee::{core::RpcResult, proc_macros::rpc};
+
+/// The ERC-4337 bundler rpc interface for user operations of the `EntryPoint` v0.7.
+///
+/// See also <https://eips.ethereum.org/EIPS/eip-4337#rpc-methods-eth-namespace>
+#[cfg_attr(not(feature = "client"), rpc(server, namespace = "eth"))]
+#[cfg_attr(feature = "client", rpc(server, client, namespace = "eth"))]
+pub trait EthUserOperationApi {
+    /// Validates the user operation and adds it to the user operation pool, returning its hash.
+    #[method(name = "sendUserOperation")]
+    async fn send_user_operation(
+        &self,
+        user_operation: PackedUserOperation,
+        entry_point: Address,
+    ) -> RpcResult<B256>;
+
+    /// Estimates the gas limits of the user operation.
+    ///
+    /// The gas limits and fees of the user operation are ignored, and the signature may be a
+    /// dummy signature.
+    #[method(name = "estimateUserOperationGas")]
+    async fn estimate_user_operation_gas(
+        &self,
+        user_operation: PackedUserOperation,
+        entry_point: Address,
+    ) -> RpcResult<UserOperationGasEstimation>;
+
+    /// Returns the receipt of the included user operation with the given hash.
+    #[method(name = "getUserOperationReceipt")]
+    async fn get_user_operation_receipt(
+        &self,
+        user_operation_hash: B256,
+    ) -> RpcResult<Option<UserOperationReceipt>>;
+
+    /// Returns the entry points supported by the user operation pool.
+    #[method(name = "supportedEntryPoints")]
+    async fn supported_entry_points(&self) -> RpcResult<Vec<Address>>;
+}
@@ -225,9 +225,10 @@ use jsonrpsee::{
     },
     Methods, RpcModule,
 };
+use reth_bundle_pool::BundlePool;
 use reth_chainspec::EthereumHardforks;
 use reth_consensus::FullConsensus;
-use reth_engine_primitives::{EngineTypes, PayloadValidator};
+use reth_engine_primitives::{DevMiner, EngineTypes, NoopDevMiner, PayloadValidator};
 use reth_evm::{execute::BlockExecutorProvider, ConfigureEvm};
 use reth_network_api::{noop::NoopNetwork, NetworkInfo, Peers};
 use reth_primitives::{NodePrimitives, PooledTransaction};
@@ -237,8 +238,9 @@ use reth_provider::{
     StateProviderFactory,
 };
 use reth_rpc::{
-    AdminApi, DebugApi, EngineEthApi, EthBundle, MinerApi, NetApi, OtterscanApi, RPCApi, RethApi,
-    TraceApi, TxPoolApi, ValidationApi, ValidationApiConfig, Web3Api,
+    eth::sim_bundle::EthSimBundle, AdminApi, AnvilApi, DebugApi, EngineEthApi, EthBundle,
+    EthBundleSubmission, EthPrivateTransaction, HardhatApi, MinerApi, NetApi, OtterscanApi, RPCApi,
+    RethApi, TraceApi, TxPoolApi, ValidationApi, ValidationApiConfig, Web3Api,
 };
 use reth_rpc_api::servers::*;
 use reth_rpc_eth_api::{
@@ -996,6 +998,22 @@ pub struct RpcRegistryInner<
     blocking_pool_guard: BlockingTaskGuard,
     /// Contains the [Methods] of a module
     modules: HashMap<RethRpcModule, Methods>,
+    /// The miner of a dev node, which serves the `anvil_` and `hardhat_` namespaces
+    dev_miner: Option<Arc<dyn DevMiner>>,
+    /// The `anvil_` namespace handler, shared with the `hardhat_` namespace
+    anvil: Option<AnvilApi<EthApi>>,
+    /// The `eth_` and `mev_` methods that submit bundles to the bundle pool of the local payload
+    /// builder
+    bundle_submission: Option<BundleSubmissionMethods>,
+}
+
+/// The methods of [`EthBundleSubmission`], by namespace.
+#[derive(Debug, Clone)]
+struct BundleSubmissionMethods {
+    /// `eth_sendBundle`, `eth_cancelBundle` and `eth_getBundleStatus`
+    eth: Methods,
+    /// `mev_sendBundle`
+    mev: Methods,
 }
 
 // === impl RpcRegistryInner ===
@@ -1070,6 +1088,9 @@ where
             events,
             block_executor,
             payload_validator,
+            dev_miner: None,
+            anvil: None,
+            bundle_submission: None,
         }
     }
 }
@@ -1118,6 +1139,51 @@ where
         &self.provider
     }
 
+    /// Sets the miner of a dev node that serves the `anvil_` and `hardhat_` namespaces.
+    ///
+    /// On a node with a dev miner, `eth_sendTransaction` also accepts transactions of accounts
+    /// impersonated through these namespaces.
+    ///
+    /// Note: this must be set before the modules are created.
+    pub fn set_dev_miner(&mut self, miner: Arc<dyn DevMiner>) -> &mut Self {
+        self.dev_miner = Some(miner);
+        self.anvil = None;
+        self
+    }
+
+    /// Returns the miner of the dev node, if set.
+    pub fn dev_miner(&self) -> Option<&Arc<dyn DevMiner>> {
+        self.dev_miner.as_ref()
+    }
+
+    /// Sets the bundle pool of the local payload builder, which serves `eth_sendBundle` and
+    /// `eth_getBundleStatus` in the `eth_` namespace and `mev_sendBundle` in the `mev_` namespace.
+    ///
+    /// Note: this must be set before the modules are created.
+    pub fn set_bundle_pool(&mut self, pool: BundlePool) -> &mut Self
+    where
+        EthApi: EthTransactions + Clone + 'static,
+    {
+        let bundles = EthBundleSubmission::new(self.eth.api.clone(), pool);
+        self.bundle_submission = Some(BundleSubmissionMethods {
+            eth: EthSendBundleApiServer::into_rpc(bundles.clone()).into(),
+            mev: MevSendBundleApiServer::into_rpc(bundles).into(),
+        });
+        self
+    }
+
+    /// Returns the `anvil_` namespace handler, which is shared by all modules that use it.
+    ///
+    /// If no dev miner is set, all requests that need the miner fail.
+    pub fn anvil_api(&mut self) -> AnvilApi<EthApi>
+    where
+        EthApi: Clone,
+    {
+        let eth_api = self.eth.api.clone();
+        let miner = self.dev_miner.clone().unwrap_or_else(|| Arc::new(NoopDevMiner::default()));
+        self.anvil.get_or_insert_with(|| AnvilApi::new(eth_api, miner)).clone()
+    }
+
     /// Returns all installed methods
     pub fn methods(&self) -> Vec<Methods> {
         self.modules.values().cloned().collect()
@@ -1314,6 +1380,18 @@ where
         EthBundle::new(eth_api, self.blocking_pool_guard.clone())
     }
 
+    /// Instantiates [`EthPrivateTransaction`] Api
+    ///
+    /// # Panics
+    ///
+    /// If called outside of the tokio runtime. See also [`Self::eth_api`]
+    pub fn private_transaction_api(&self) -> EthPrivateTransaction<EthApi>
+    where
+        EthApi: EthTransactions,
+    {
+        EthPrivateTransaction::new(self.eth_api().clone())
+    }
+
     /// Instantiates `DebugApi`
     ///
     /// # Panics
@@ -1459,6 +1537,7 @@ where
     ) -> Vec<Methods> {
         let EthHandlers { api: eth_api, filter: eth_filter, pubsub: eth_pubsub, .. } =
             self.eth_handlers().clone();
+        let anvil_api = self.anvil_api();
 
         // Create a copy, so we can list out all the methods for rpc_ api
         let namespaces: Vec<_> = namespaces.collect();
@@ -1495,6 +1574,20 @@ where
                                     .into_rpc(),
                                 )
                                 .expect("No conflicts");
+                            module
+                                .merge(EthPrivateTransaction::new(eth_api.clone()).into_rpc())
+                                .expect("No conflicts");
+                            if let Some(bundles) = &self.bundle_submission {
+                                module.merge(bundles.eth.clone()).expect("No conflicts");
+                            }
+                            if self.dev_miner.is_some() {
+                                // transactions of impersonated accounts are sent through anvil
+                                let anvil_eth = AnvilEthApiServer::into_rpc(anvil_api.clone());
+                                for method in anvil_eth.method_names() {
+                                    module.remove_method(method);
+                                }
+                                module.merge(anvil_eth).expect("No conflicts");
+                            }
 
                             module.into()
                         }
@@ -1538,6 +1631,21 @@ where
                         .into_rpc()
                         .into(),
                         RethRpcModule::Miner => MinerApi::default().into_rpc().into(),
+                        RethRpcModule::Mev => {
+                            let mut module = EthSimBundle::new(
+                                eth_api.clone(),
+                                self.blocking_pool_guard.clone(),
+                            )
+                            .into_rpc();
+                            if let Some(bundles) = &self.bundle_submission {
+                                module.merge(bundles.mev.clone()).expect("No conflicts");
+                            }
+                            module.into()
+                        }
+                        RethRpcModule::Anvil => AnvilApiServer::into_rpc(anvil_api.clone()).into(),
+                        RethRpcModule::Hardhat => {
+                            HardhatApi::new(anvil_api.clone()).into_rpc().into()
+                        }
                     })
                     .clone()
             })
@@ -2141,6 +2249,27 @@ impl TransportRpcModules {
         Ok(())
     }
 
+    /// Replaces the methods of the given [`Methods`] in all configured transport modules if the
+    /// given [`RethRpcModule`] is configured for the transport.
+    pub fn replace_if_module_configured(
+        &mut self,
+        module: RethRpcModule,
+        other: impl Into<Methods>,
+    ) -> Result<(), RegisterMethodError> {
+        let other = other.into();
+        if self.module_config().contains_http(&module) {
+            self.replace_http(other.clone())?;
+        }
+        if self.module_config().contains_ws(&module) {
+            self.replace_ws(other.clone())?;
+        }
+        if self.module_config().contains_ipc(&module) {
+            self.replace_ipc(other)?;
+        }
+
+        Ok(())
+    }
+
     /// Merge the given [Methods] in the configured http methods.
     ///
     /// Fails if any of the methods in other is present already.
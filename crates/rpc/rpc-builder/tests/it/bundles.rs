@@ -0,0 +1,42 @@
+//! Bundle submission tests
+
+use std::sync::Arc;
+
+use alloy_primitives::B256;
+use reth_bundle_pool::{BundlePool, BundlePoolConfig, BundleStatus};
+use reth_chainspec::MAINNET;
+use reth_ethereum_engine_primitives::EthereumEngineValidator;
+use reth_rpc::EthApi;
+use reth_rpc_server_types::{RethRpcModule, RpcModuleSelection};
+
+use crate::utils::test_rpc_builder;
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_bundle_submission_requires_bundle_pool() {
+    let selection = RpcModuleSelection::from(vec![RethRpcModule::Eth, RethRpcModule::Mev]);
+    let registry = || {
+        test_rpc_builder().into_registry(
+            Default::default(),
+            Box::new(EthApi::with_spawner),
+            Arc::new(EthereumEngineValidator::new(MAINNET.clone())),
+        )
+    };
+
+    let module = registry().module_for(&selection);
+    let methods = module.method_names().collect::<Vec<_>>();
+    assert!(methods.contains(&"mev_simBundle"));
+    assert!(!methods.contains(&"eth_sendBundle"));
+    assert!(!methods.contains(&"mev_sendBundle"));
+
+    let mut registry = registry();
+    registry.set_bundle_pool(BundlePool::new(BundlePoolConfig::default()));
+    let module = registry.module_for(&selection);
+    let methods = module.method_names().collect::<Vec<_>>();
+    for method in ["eth_sendBundle", "eth_cancelBundle", "eth_getBundleStatus", "mev_sendBundle"] {
+        assert!(methods.contains(&method), "{method} is missing");
+    }
+
+    let status =
+        module.call::<_, Option<BundleStatus>>("eth_getBundleStatus", [B256::ZERO]).await.unwrap();
+    assert_eq!(status, None);
+}
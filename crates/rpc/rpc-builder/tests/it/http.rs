@@ -360,11 +360,11 @@ where
 {
     let block_id = BlockId::Number(BlockNumberOrTag::default());
 
-    DebugApiClient::raw_header(client, block_id).await.unwrap();
-    DebugApiClient::raw_block(client, block_id).await.unwrap_err();
-    DebugApiClient::raw_transaction(client, B256::default()).await.unwrap();
-    DebugApiClient::raw_receipts(client, block_id).await.unwrap();
-    assert!(is_unimplemented(DebugApiClient::bad_blocks(client).await.err().unwrap()));
+    DebugApiClient::<Block>::raw_header(client, block_id).await.unwrap();
+    DebugApiClient::<Block>::raw_block(client, block_id).await.unwrap_err();
+    DebugApiClient::<Block>::raw_transaction(client, B256::default()).await.unwrap();
+    DebugApiClient::<Block>::raw_receipts(client, block_id).await.unwrap();
+    assert!(DebugApiClient::<Block>::bad_blocks(client).await.unwrap().is_empty());
 }
 
 async fn test_basic_net_calls<C>(client: &C)
@@ -580,6 +580,45 @@ async fn test_call_debug_functions_ws() {
     test_basic_debug_calls(&client).await;
 }
 
+#[tokio::test(flavor = "multi_thread")]
+async fn test_debug_trace_chain_ws() {
+    reth_tracing::init_test_tracing();
+
+    let handle = launch_ws(vec![RethRpcModule::Debug]).await;
+    let client = handle.ws_client().await.unwrap();
+
+    // the start block must be before the end block
+    DebugApiClient::<Block>::debug_trace_chain(
+        &client,
+        BlockNumberOrTag::Number(2),
+        BlockNumberOrTag::Number(1),
+        None,
+    )
+    .await
+    .unwrap_err();
+
+    // the range is limited
+    DebugApiClient::<Block>::debug_trace_chain(
+        &client,
+        BlockNumberOrTag::Number(0),
+        BlockNumberOrTag::Number(1_000),
+        None,
+    )
+    .await
+    .unwrap_err();
+
+    // the blocks are missing, so the subscription is closed without a result
+    let mut sub = DebugApiClient::<Block>::debug_trace_chain(
+        &client,
+        BlockNumberOrTag::Number(0),
+        BlockNumberOrTag::Number(1),
+        None,
+    )
+    .await
+    .unwrap();
+    assert!(!matches!(sub.next().await, Some(Ok(_))));
+}
+
 #[tokio::test(flavor = "multi_thread")]
 async fn test_call_debug_functions_http_and_ws() {
     reth_tracing::init_test_tracing();
@@ -1,6 +1,7 @@
 #![allow(missing_docs)]
 
 mod auth;
+mod bundles;
 mod http;
 mod middleware;
 mod serde;
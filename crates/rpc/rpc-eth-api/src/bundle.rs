@@ -8,6 +8,7 @@ use alloy_rpc_types_mev::{
     EthCallBundleResponse, EthSendBundle, PrivateTransactionRequest,
 };
 use jsonrpsee::proc_macros::rpc;
+use reth_bundle_pool_types::BundleStatus;
 
 /// A subset of the [EthBundleApi] API interface that only supports `eth_callBundle`.
 #[cfg_attr(not(feature = "client"), rpc(server, namespace = "eth"))]
@@ -22,6 +23,55 @@ pub trait EthCallBundleApi {
     ) -> jsonrpsee::core::RpcResult<EthCallBundleResponse>;
 }
 
+/// A subset of the [EthBundleApi] API interface that only supports the private transactions.
+///
+/// Private transactions are kept in the local transaction pool for the local payload builder,
+/// without being propagated to the network.
+#[cfg_attr(not(feature = "client"), rpc(server, namespace = "eth"))]
+#[cfg_attr(feature = "client", rpc(server, client, namespace = "eth"))]
+pub trait EthPrivateTransactionApi {
+    /// `eth_sendPrivateTransaction` submits a transaction that is not propagated to the network.
+    ///
+    /// If the transaction is not included up to the `maxBlockNumber` of the request, it's
+    /// dropped from the pool, or made public if the node is configured to do so.
+    #[method(name = "sendPrivateTransaction")]
+    async fn send_private_transaction(
+        &self,
+        request: PrivateTransactionRequest,
+    ) -> jsonrpsee::core::RpcResult<B256>;
+
+    /// `eth_sendPrivateRawTransaction` submits a transaction that is not propagated to the
+    /// network, without a max block number.
+    #[method(name = "sendPrivateRawTransaction")]
+    async fn send_private_raw_transaction(&self, bytes: Bytes) -> jsonrpsee::core::RpcResult<B256>;
+}
+
+/// A subset of the [EthBundleApi] API interface that submits bundles to the local payload builder.
+///
+/// The bundles are kept in a bundle pool, and the payload builder includes them ahead of the
+/// transactions of the transaction pool.
+#[cfg_attr(not(feature = "client"), rpc(server, namespace = "eth"))]
+#[cfg_attr(feature = "client", rpc(server, client, namespace = "eth"))]
+pub trait EthSendBundleApi {
+    /// `eth_sendBundle` submits a bundle for inclusion in the blocks of its block number and
+    /// timestamp range.
+    #[method(name = "sendBundle")]
+    async fn send_bundle(&self, bundle: EthSendBundle)
+        -> jsonrpsee::core::RpcResult<EthBundleHash>;
+
+    /// `eth_cancelBundle` cancels the pending bundle with the replacement UUID of the request.
+    #[method(name = "cancelBundle")]
+    async fn cancel_bundle(&self, request: CancelBundleRequest) -> jsonrpsee::core::RpcResult<()>;
+
+    /// `eth_getBundleStatus` returns the inclusion status of the bundle with the given hash, if
+    /// it's still known.
+    #[method(name = "getBundleStatus")]
+    async fn get_bundle_status(
+        &self,
+        bundle_hash: B256,
+    ) -> jsonrpsee::core::RpcResult<Option<BundleStatus>>;
+}
+
 /// The __full__ Eth bundle rpc interface.
 ///
 /// See also <https://docs.flashbots.net/flashbots-auction/searchers/advanced/rpc-endpoint>
@@ -5,7 +5,7 @@ use alloy_consensus::{BlockHeader, Transaction};
 use alloy_dyn_abi::TypedData;
 use alloy_eips::{eip2718::Encodable2718, BlockId};
 use alloy_network::TransactionBuilder;
-use alloy_primitives::{Address, Bytes, TxHash, B256};
+use alloy_primitives::{Address, BlockNumber, Bytes, TxHash, B256};
 use alloy_rpc_types_eth::{transaction::TransactionRequest, BlockNumberOrTag, TransactionInfo};
 use futures::Future;
 use reth_node_api::BlockBody;
@@ -17,7 +17,10 @@ use reth_provider::{
     BlockNumReader, BlockReaderIdExt, ProviderBlock, ProviderReceipt, ProviderTx, ReceiptProvider,
     TransactionsProvider,
 };
-use reth_rpc_eth_types::{utils::binary_search, EthApiError, SignError, TransactionSource};
+use reth_rpc_eth_types::{
+    utils::{binary_search, recover_raw_transaction},
+    EthApiError, SignError, TransactionSource,
+};
 use reth_rpc_types_compat::transaction::{from_recovered, from_recovered_with_block_context};
 use reth_transaction_pool::{PoolTransaction, TransactionOrigin, TransactionPool};
 use std::sync::Arc;
@@ -66,6 +69,31 @@ pub trait EthTransactions: LoadTransaction<Provider: BlockReaderIdExt> {
         tx: Bytes,
     ) -> impl Future<Output = Result<B256, Self::Error>> + Send;
 
+    /// Decodes and recovers the transaction and submits it to the pool as a private transaction.
+    ///
+    /// Unlike [`EthTransactions::send_raw_transaction`], the transaction is never broadcast, and
+    /// it's removed from the pool if it's not included up to the given max block number.
+    ///
+    /// Returns the hash of the transaction.
+    fn send_private_raw_transaction(
+        &self,
+        tx: Bytes,
+        max_block_number: Option<BlockNumber>,
+    ) -> impl Future<Output = Result<B256, Self::Error>> + Send {
+        async move {
+            let recovered = recover_raw_transaction(&tx).map_err(Self::Error::from_eth_err)?;
+            let pool_transaction =
+                <<Self as RpcNodeCore>::Pool as TransactionPool>::Transaction::from_pooled(
+                    recovered,
+                );
+
+            self.pool()
+                .add_private_transaction(pool_transaction, max_block_number)
+                .await
+                .map_err(Self::Error::from_eth_err)
+        }
+    }
+
     /// Returns the transaction by hash.
     ///
     /// Checks the pool and state.
@@ -20,7 +20,10 @@ pub mod node;
 pub mod pubsub;
 pub mod types;
 
-pub use bundle::{EthBundleApiServer, EthCallBundleApiServer};
+pub use bundle::{
+    EthBundleApiServer, EthCallBundleApiServer, EthPrivateTransactionApiServer,
+    EthSendBundleApiServer,
+};
 pub use core::{EthApiServer, FullEthApiServer};
 pub use filter::EthFilterApiServer;
 pub use node::{RpcNodeCore, RpcNodeCoreExt};
@@ -32,7 +35,10 @@ pub use reth_rpc_types_compat::TransactionCompat;
 pub use types::{EthApiTypes, FullEthApiTypes, RpcBlock, RpcHeader, RpcReceipt, RpcTransaction};
 
 #[cfg(feature = "client")]
-pub use bundle::{EthBundleApiClient, EthCallBundleApiClient};
+pub use bundle::{
+    EthBundleApiClient, EthCallBundleApiClient, EthPrivateTransactionApiClient,
+    EthSendBundleApiClient,
+};
 #[cfg(feature = "client")]
 pub use core::EthApiClient;
 #[cfg(feature = "client")]
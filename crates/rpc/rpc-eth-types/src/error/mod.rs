@@ -667,6 +667,9 @@ pub enum RpcPoolError {
     /// constraint (blob vs normal tx)
     #[error("address already reserved")]
     AddressAlreadyReserved,
+    /// Thrown if the transaction was rejected by an admission policy of the pool
+    #[error(transparent)]
+    Rejected(InvalidPoolTransactionError),
     /// Other unspecified error
     #[error(transparent)]
     Other(Box<dyn core::error::Error + Send + Sync>),
@@ -676,7 +679,7 @@ impl From<RpcPoolError> for jsonrpsee_types::error::ErrorObject<'static> {
     fn from(error: RpcPoolError) -> Self {
         match error {
             RpcPoolError::Invalid(err) => err.into(),
-            RpcPoolError::TxPoolOverflow => {
+            RpcPoolError::TxPoolOverflow | RpcPoolError::Rejected(_) => {
                 rpc_error_with_code(EthRpcErrorCode::TransactionRejected.code(), error.to_string())
             }
             error => internal_rpc_err(error.to_string()),
@@ -719,6 +722,12 @@ impl From<InvalidPoolTransactionError> for RpcPoolError {
             InvalidPoolTransactionError::Overdraft { cost, balance } => {
                 Self::Invalid(RpcInvalidTransactionError::InsufficientFunds { cost, balance })
             }
+            err @ (InvalidPoolTransactionError::DeniedSender(_) |
+            InvalidPoolTransactionError::DeniedRecipient(_) |
+            InvalidPoolTransactionError::SenderRateLimited(_) |
+            InvalidPoolTransactionError::TipBelowMinimum { .. } |
+            InvalidPoolTransactionError::DeniedSelector(_) |
+            InvalidPoolTransactionError::CalldataTooLarge(_, _)) => Self::Rejected(err),
         }
     }
 }
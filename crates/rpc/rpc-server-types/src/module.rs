@@ -271,6 +271,12 @@ pub enum RethRpcModule {
     Flashbots,
     /// `miner_` module
     Miner,
+    /// `mev_` module
+    Mev,
+    /// `anvil_` module, only available in dev mode
+    Anvil,
+    /// `hardhat_` module, only available in dev mode
+    Hardhat,
 }
 
 // === impl RethRpcModule ===
@@ -321,6 +327,9 @@ impl FromStr for RethRpcModule {
             "ots" => Self::Ots,
             "flashbots" => Self::Flashbots,
             "miner" => Self::Miner,
+            "mev" => Self::Mev,
+            "anvil" => Self::Anvil,
+            "hardhat" => Self::Hardhat,
             _ => return Err(ParseError::VariantNotFound),
         })
     }
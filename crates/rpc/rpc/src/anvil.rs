@@ -0,0 +1,772 @@
+use alloy_consensus::{SignableTransaction, TxEnvelope, TypedTransaction};
+use alloy_eips::{
+    eip2718::{Decodable2718, Encodable2718},
+    BlockId,
+};
+use alloy_genesis::GenesisAccount;
+use alloy_network::TransactionBuilder;
+use alloy_primitives::{
+    map::{HashMap, HashSet},
+    Address, Bytes, B256, U256,
+};
+use alloy_rpc_types_anvil::{Forking, Metadata, MineOptions, NodeInfo};
+use alloy_rpc_types_eth::{Block, TransactionRequest};
+use async_trait::async_trait;
+use jsonrpsee::core::RpcResult;
+use parking_lot::{Mutex, RwLock};
+use reth_engine_primitives::{impersonated_signature, AccountPatch, DevMiner, DevMiningMode};
+use reth_primitives_traits::SignedTransaction;
+use reth_provider::{BlockHashReader, BlockNumReader, ProviderTx};
+use reth_rpc_api::{AnvilApiServer, AnvilEthApiServer};
+use reth_rpc_eth_api::{
+    helpers::{EthApiSpec, EthCall, EthTransactions, LoadState},
+    FromEthApiError, RpcNodeCore,
+};
+use reth_rpc_eth_types::{EthApiError, SignError};
+use reth_rpc_server_types::result::{internal_rpc_err, invalid_params_rpc_err};
+use reth_transaction_pool::{PoolTransaction, TransactionOrigin, TransactionPool};
+use serde::{Deserialize, Serialize};
+use std::{collections::BTreeMap, sync::Arc, time::Duration};
+
+/// `anvil` API implementation.
+///
+/// This type provides the functionality for handling `anvil` related requests on a dev node.
+///
+/// Blocks are produced by the [`DevMiner`] of the node. State modifications, such as
+/// `anvil_setBalance`, are collected in a pending overlay of the miner that is applied to the next
+/// block, so they become visible once that block is mined.
+///
+/// Transactions of impersonated accounts can't be signed. They get a placeholder signature instead
+/// and are included in the next block like any other transaction, see
+/// [`AnvilApi::send_transaction`].
+pub struct AnvilApi<Eth> {
+    inner: Arc<AnvilApiInner<Eth>>,
+}
+
+// === impl AnvilApi ===
+
+impl<Eth> AnvilApi<Eth> {
+    /// Creates a new instance of the [`AnvilApi`].
+    pub fn new(eth_api: Eth, miner: Arc<dyn DevMiner>) -> Self {
+        let inner = Arc::new(AnvilApiInner {
+            eth_api,
+            miner,
+            impersonation: Default::default(),
+            snapshots: Default::default(),
+            instance_id: B256::random(),
+        });
+        Self { inner }
+    }
+
+    /// Access the underlying `Eth` API.
+    pub fn eth_api(&self) -> &Eth {
+        &self.inner.eth_api
+    }
+
+    /// Access the miner of the node.
+    pub fn miner(&self) -> &Arc<dyn DevMiner> {
+        &self.inner.miner
+    }
+
+    /// Returns `true` if transactions of the given account don't need to be signed.
+    pub fn is_impersonated(&self, address: &Address) -> bool {
+        self.inner.impersonation.is_impersonated(address)
+    }
+
+    /// Adds the given modification of a single account to the pending overlay.
+    fn modify_account(&self, address: Address, patch: AccountPatch) -> RpcResult<()> {
+        self.inner
+            .miner
+            .modify_state(HashMap::from_iter([(address, patch)]))
+            .map_err(|err| internal_rpc_err(err.to_string()))
+    }
+}
+
+impl<Eth> AnvilApi<Eth>
+where
+    Eth: EthApiSpec + EthTransactions + EthCall + 'static,
+{
+    /// Handler for `eth_sendTransaction` on a node that serves the `anvil_` namespace.
+    ///
+    /// Transactions of accounts that are not impersonated are signed and submitted to the pool as
+    /// usual. Transactions of impersonated accounts get the placeholder
+    /// [`impersonated_signature`] of the sender instead, and are submitted to the pool as private
+    /// transactions, so they are never propagated. The dev miner includes them in the next block
+    /// without recovering their senders, so they have a receipt like any other transaction. A
+    /// block is mined right away if automine is enabled.
+    pub async fn send_transaction(
+        &self,
+        mut request: TransactionRequest,
+    ) -> Result<B256, Eth::Error> {
+        let Some(from) = request.from.filter(|from| self.is_impersonated(from)) else {
+            return EthTransactions::send_transaction(self.eth_api(), request).await
+        };
+
+        if request.nonce.is_none() {
+            request.nonce = Some(self.eth_api().next_available_nonce(from).await?);
+        }
+        request.chain_id = Some(self.eth_api().chain_id().to());
+        if request.gas.is_none() {
+            let gas =
+                self.eth_api().estimate_gas_at(request.clone(), BlockId::pending(), None).await?;
+            request.set_gas_limit(gas.to());
+        }
+
+        let transaction = request
+            .build_typed_tx()
+            .ok()
+            .and_then(|tx| impersonated_transaction::<ProviderTx<Eth::Provider>>(tx, from))
+            .ok_or(SignError::InvalidTransactionRequest)
+            .map_err(Eth::Error::from_eth_err)?;
+        let pool_transaction =
+            <<Eth as RpcNodeCore>::Pool as TransactionPool>::Transaction::try_from_consensus(
+                transaction.with_signer(from),
+            )
+            .map_err(|_| Eth::Error::from_eth_err(EthApiError::TransactionConversionError))?;
+
+        // the placeholder signature is not valid, so the transaction must not be propagated
+        let hash = self
+            .eth_api()
+            .pool()
+            .add_transaction(TransactionOrigin::Private, pool_transaction)
+            .await
+            .map_err(Eth::Error::from_eth_err)?;
+
+        // private transactions don't trigger the instant mining mode
+        let miner = &self.inner.miner;
+        if miner.is_automine().await.map_err(Eth::Error::from_eth_err)? {
+            miner.mine(1, None).await.map_err(Eth::Error::from_eth_err)?;
+        }
+
+        Ok(hash)
+    }
+}
+
+#[async_trait]
+impl<Eth> AnvilApiServer for AnvilApi<Eth>
+where
+    Eth: EthApiSpec + EthTransactions + 'static,
+{
+    /// Handler for `anvil_impersonateAccount`
+    async fn anvil_impersonate_account(&self, address: Address) -> RpcResult<()> {
+        self.inner.impersonation.accounts.write().insert(address);
+        Ok(())
+    }
+
+    /// Handler for `anvil_stopImpersonatingAccount`
+    async fn anvil_stop_impersonating_account(&self, address: Address) -> RpcResult<()> {
+        self.inner.impersonation.accounts.write().remove(&address);
+        Ok(())
+    }
+
+    /// Handler for `anvil_autoImpersonateAccount`
+    async fn anvil_auto_impersonate_account(&self, enabled: bool) -> RpcResult<()> {
+        *self.inner.impersonation.auto.write() = enabled;
+        Ok(())
+    }
+
+    /// Handler for `anvil_getAutomine`
+    async fn anvil_get_automine(&self) -> RpcResult<bool> {
+        self.inner.miner.is_automine().await.map_err(|err| internal_rpc_err(err.to_string()))
+    }
+
+    /// Handler for `anvil_mine`
+    async fn anvil_mine(&self, blocks: Option<U256>, interval: Option<U256>) -> RpcResult<()> {
+        let blocks = blocks.map(|blocks| blocks.saturating_to()).unwrap_or(1);
+        let interval = interval.map(|interval| interval.saturating_to());
+        self.inner
+            .miner
+            .mine(blocks, interval)
+            .await
+            .map_err(|err| internal_rpc_err(err.to_string()))?;
+        Ok(())
+    }
+
+    /// Handler for `anvil_setAutomine`
+    async fn anvil_set_automine(&self, enabled: bool) -> RpcResult<()> {
+        let mode = if enabled { DevMiningMode::Auto } else { DevMiningMode::Manual };
+        self.inner.miner.set_mining_mode(mode).map_err(|err| internal_rpc_err(err.to_string()))
+    }
+
+    /// Handler for `anvil_setIntervalMining`
+    async fn anvil_set_interval_mining(&self, interval: u64) -> RpcResult<()> {
+        let mode = if interval == 0 {
+            DevMiningMode::Manual
+        } else {
+            DevMiningMode::Interval(Duration::from_secs(interval))
+        };
+        self.inner.miner.set_mining_mode(mode).map_err(|err| internal_rpc_err(err.to_string()))
+    }
+
+    /// Handler for `anvil_dropTransaction`
+    async fn anvil_drop_transaction(&self, tx_hash: B256) -> RpcResult<Option<B256>> {
+        let removed = self.eth_api().pool().remove_transactions(vec![tx_hash]);
+        Ok(removed.first().map(|tx| *tx.hash()))
+    }
+
+    /// Handler for `anvil_reset`
+    async fn anvil_reset(&self, _fork: Option<Forking>) -> RpcResult<()> {
+        Err(EthApiError::Unsupported("anvil_reset is not supported").into())
+    }
+
+    /// Handler for `anvil_setRpcUrl`
+    async fn anvil_set_rpc_url(&self, _url: String) -> RpcResult<()> {
+        Err(EthApiError::Unsupported("anvil_setRpcUrl is not supported").into())
+    }
+
+    /// Handler for `anvil_setBalance`
+    async fn anvil_set_balance(&self, address: Address, balance: U256) -> RpcResult<()> {
+        self.modify_account(address, AccountPatch { balance: Some(balance), ..Default::default() })
+    }
+
+    /// Handler for `anvil_setCode`
+    async fn anvil_set_code(&self, address: Address, code: Bytes) -> RpcResult<()> {
+        self.modify_account(address, AccountPatch { code: Some(code), ..Default::default() })
+    }
+
+    /// Handler for `anvil_setNonce`
+    async fn anvil_set_nonce(&self, address: Address, nonce: U256) -> RpcResult<()> {
+        let nonce = nonce.try_into().map_err(|_| invalid_params_rpc_err("nonce too large"))?;
+        self.modify_account(address, AccountPatch { nonce: Some(nonce), ..Default::default() })
+    }
+
+    /// Handler for `anvil_setStorageAt`
+    async fn anvil_set_storage_at(
+        &self,
+        address: Address,
+        slot: U256,
+        value: B256,
+    ) -> RpcResult<bool> {
+        let storage = HashMap::from_iter([(B256::from(slot), value)]);
+        self.modify_account(address, AccountPatch { storage, ..Default::default() })?;
+        Ok(true)
+    }
+
+    /// Handler for `anvil_setCoinbase`
+    async fn anvil_set_coinbase(&self, _address: Address) -> RpcResult<()> {
+        Err(EthApiError::Unsupported("anvil_setCoinbase is not supported").into())
+    }
+
+    /// Handler for `anvil_setChainId`
+    async fn anvil_set_chain_id(&self, _chain_id: u64) -> RpcResult<()> {
+        Err(EthApiError::Unsupported("anvil_setChainId is not supported").into())
+    }
+
+    /// Handler for `anvil_setLoggingEnabled`
+    async fn anvil_set_logging_enabled(&self, _enabled: bool) -> RpcResult<()> {
+        Err(EthApiError::Unsupported("anvil_setLoggingEnabled is not supported").into())
+    }
+
+    /// Handler for `anvil_setMinGasPrice`
+    async fn anvil_set_min_gas_price(&self, _gas_price: U256) -> RpcResult<()> {
+        Err(EthApiError::Unsupported("anvil_setMinGasPrice is not supported").into())
+    }
+
+    /// Handler for `anvil_setNextBlockBaseFeePerGas`
+    async fn anvil_set_next_block_base_fee_per_gas(&self, _base_fee: U256) -> RpcResult<()> {
+        Err(EthApiError::Unsupported("anvil_setNextBlockBaseFeePerGas is not supported").into())
+    }
+
+    /// Handler for `anvil_setTime`
+    async fn anvil_set_time(&self, timestamp: u64) -> RpcResult<u64> {
+        let offset = self
+            .inner
+            .miner
+            .set_time(timestamp)
+            .await
+            .map_err(|err| internal_rpc_err(err.to_string()))?;
+        Ok(offset.max(0) as u64)
+    }
+
+    /// Handler for `anvil_dumpState`
+    async fn anvil_dump_state(&self) -> RpcResult<Bytes> {
+        let block_number =
+            self.eth_api().provider().best_block_number().map_err(EthApiError::from)?;
+        let accounts =
+            self.inner.miner.dump_state().await.map_err(|err| internal_rpc_err(err.to_string()))?;
+        let state = SerializableState::from_patches(Some(block_number), accounts);
+        let json = serde_json::to_vec(&state).map_err(|err| internal_rpc_err(err.to_string()))?;
+        Ok(json.into())
+    }
+
+    /// Handler for `anvil_loadState`
+    async fn anvil_load_state(&self, state: Bytes) -> RpcResult<bool> {
+        let state: SerializableState = serde_json::from_slice(&state)
+            .map_err(|err| invalid_params_rpc_err(format!("invalid state: {err}")))?;
+        self.inner
+            .miner
+            .modify_state(state.into_patches())
+            .map_err(|err| internal_rpc_err(err.to_string()))?;
+        Ok(true)
+    }
+
+    /// Handler for `anvil_nodeInfo`
+    async fn anvil_node_info(&self) -> RpcResult<NodeInfo> {
+        Err(EthApiError::Unsupported("anvil_nodeInfo is not supported").into())
+    }
+
+    /// Handler for `anvil_metadata`
+    async fn anvil_metadata(&self) -> RpcResult<Metadata> {
+        let provider = self.eth_api().provider();
+        let latest_block_number = provider.best_block_number().map_err(EthApiError::from)?;
+        let latest_block_hash = provider
+            .block_hash(latest_block_number)
+            .map_err(EthApiError::from)?
+            .unwrap_or_default();
+
+        Ok(Metadata {
+            client_version: format!("reth/v{}", env!("CARGO_PKG_VERSION")),
+            chain_id: self.eth_api().chain_id().to(),
+            instance_id: self.inner.instance_id,
+            latest_block_number,
+            latest_block_hash,
+            forked_network: None,
+            snapshots: self.inner.snapshots.lock().clone(),
+        })
+    }
+
+    /// Handler for `anvil_snapshot`
+    async fn anvil_snapshot(&self) -> RpcResult<U256> {
+        let provider = self.eth_api().provider();
+        let number = provider.best_block_number().map_err(EthApiError::from)?;
+        let hash = provider
+            .block_hash(number)
+            .map_err(EthApiError::from)?
+            .ok_or(EthApiError::HeaderNotFound(number.into()))?;
+
+        let mut snapshots = self.inner.snapshots.lock();
+        let id = snapshots.last_key_value().map(|(id, _)| id + U256::from(1)).unwrap_or_default();
+        snapshots.insert(id, (number, hash));
+        Ok(id)
+    }
+
+    /// Handler for `anvil_revert`
+    ///
+    /// Note: the chain is reorged on top of the snapshotted block, so the new head is a fresh
+    /// block that builds on the snapshotted state.
+    async fn anvil_revert(&self, id: U256) -> RpcResult<bool> {
+        let Some((_, hash)) = self.inner.snapshots.lock().get(&id).copied() else {
+            return Ok(false)
+        };
+        self.inner.miner.revert(hash).await.map_err(|err| internal_rpc_err(err.to_string()))?;
+        // the snapshot and all snapshots taken after it are consumed
+        self.inner.snapshots.lock().retain(|snapshot, _| *snapshot < id);
+        Ok(true)
+    }
+
+    /// Handler for `anvil_increaseTime`
+    async fn anvil_increase_time(&self, seconds: U256) -> RpcResult<i64> {
+        self.inner
+            .miner
+            .increase_time(seconds.saturating_to())
+            .await
+            .map_err(|err| internal_rpc_err(err.to_string()))
+    }
+
+    /// Handler for `anvil_setNextBlockTimestamp`
+    async fn anvil_set_next_block_timestamp(&self, seconds: u64) -> RpcResult<()> {
+        self.inner
+            .miner
+            .set_next_block_timestamp(seconds)
+            .await
+            .map_err(|err| invalid_params_rpc_err(err.to_string()))
+    }
+
+    /// Handler for `anvil_setBlockGasLimit`
+    async fn anvil_set_block_gas_limit(&self, _gas_limit: U256) -> RpcResult<bool> {
+        Err(EthApiError::Unsupported("anvil_setBlockGasLimit is not supported").into())
+    }
+
+    /// Handler for `anvil_setBlockTimestampInterval`
+    async fn anvil_set_block_timestamp_interval(&self, seconds: u64) -> RpcResult<()> {
+        self.inner
+            .miner
+            .set_block_timestamp_interval(Some(seconds))
+            .map_err(|err| internal_rpc_err(err.to_string()))
+    }
+
+    /// Handler for `anvil_removeBlockTimestampInterval`
+    async fn anvil_remove_block_timestamp_interval(&self) -> RpcResult<bool> {
+        self.inner
+            .miner
+            .set_block_timestamp_interval(None)
+            .map_err(|err| internal_rpc_err(err.to_string()))?;
+        Ok(true)
+    }
+
+    /// Handler for `anvil_mine_detailed`
+    async fn anvil_mine_detailed(&self, _opts: Option<MineOptions>) -> RpcResult<Vec<Block>> {
+        Err(EthApiError::Unsupported("anvil_mine_detailed is not supported").into())
+    }
+
+    /// Handler for `anvil_enableTraces`
+    async fn anvil_enable_traces(&self) -> RpcResult<()> {
+        Err(EthApiError::Unsupported("anvil_enableTraces is not supported").into())
+    }
+
+    /// Handler for `anvil_removePoolTransactions`
+    async fn anvil_remove_pool_transactions(&self, address: Address) -> RpcResult<()> {
+        self.eth_api().pool().remove_transactions_by_sender(address);
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl<Eth> AnvilEthApiServer for AnvilApi<Eth>
+where
+    Eth: EthApiSpec + EthTransactions + EthCall + 'static,
+{
+    /// Handler for `eth_sendTransaction`
+    async fn send_transaction(&self, request: TransactionRequest) -> RpcResult<B256> {
+        Self::send_transaction(self, request).await.map_err(Into::into)
+    }
+}
+
+impl<Eth> Clone for AnvilApi<Eth> {
+    fn clone(&self) -> Self {
+        Self { inner: Arc::clone(&self.inner) }
+    }
+}
+
+impl<Eth> std::fmt::Debug for AnvilApi<Eth> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AnvilApi").finish_non_exhaustive()
+    }
+}
+
+struct AnvilApiInner<Eth> {
+    /// The implementation of `eth` API
+    eth_api: Eth,
+    /// The miner of the dev node.
+    miner: Arc<dyn DevMiner>,
+    /// Accounts that can send transactions without a key.
+    impersonation: Impersonation,
+    /// Snapshots by id, with the number and hash of the snapshotted block.
+    snapshots: Mutex<BTreeMap<U256, (u64, B256)>>,
+    /// Random id of this node instance.
+    instance_id: B256,
+}
+
+/// State of all accounts, as returned by `anvil_dumpState`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SerializableState {
+    /// The latest block at the time the state was dumped.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub block_number: Option<u64>,
+    /// All accounts of the state.
+    pub accounts: BTreeMap<Address, GenesisAccount>,
+}
+
+impl SerializableState {
+    /// Creates the state from the given accounts.
+    fn from_patches(block_number: Option<u64>, accounts: BTreeMap<Address, AccountPatch>) -> Self {
+        let accounts = accounts
+            .into_iter()
+            .map(|(address, account)| {
+                let account = GenesisAccount {
+                    balance: account.balance.unwrap_or_default(),
+                    nonce: account.nonce,
+                    code: account.code,
+                    storage: (!account.storage.is_empty())
+                        .then(|| account.storage.into_iter().collect()),
+                    ..Default::default()
+                };
+                (address, account)
+            })
+            .collect();
+        Self { block_number, accounts }
+    }
+
+    /// Converts the state into modifications of the accounts.
+    fn into_patches(self) -> HashMap<Address, AccountPatch> {
+        self.accounts
+            .into_iter()
+            .map(|(address, account)| {
+                let patch = AccountPatch {
+                    balance: Some(account.balance),
+                    nonce: account.nonce,
+                    code: account.code,
+                    storage: account.storage.unwrap_or_default().into_iter().collect(),
+                };
+                (address, patch)
+            })
+            .collect()
+    }
+}
+
+/// Accounts that are currently impersonated.
+#[derive(Debug, Default)]
+struct Impersonation {
+    /// Explicitly impersonated accounts.
+    accounts: RwLock<HashSet<Address>>,
+    /// Whether all accounts are impersonated.
+    auto: RwLock<bool>,
+}
+
+impl Impersonation {
+    /// Returns `true` if the account is impersonated.
+    fn is_impersonated(&self, address: &Address) -> bool {
+        *self.auto.read() || self.accounts.read().contains(address)
+    }
+}
+
+/// Signs the transaction with the placeholder signature of the impersonated sender.
+fn impersonated_transaction<T: Decodable2718>(tx: TypedTransaction, from: Address) -> Option<T> {
+    let signature = impersonated_signature(from);
+    let envelope: TxEnvelope = match tx {
+        TypedTransaction::Legacy(tx) => tx.into_signed(signature).into(),
+        TypedTransaction::Eip2930(tx) => tx.into_signed(signature).into(),
+        TypedTransaction::Eip1559(tx) => tx.into_signed(signature).into(),
+        TypedTransaction::Eip4844(tx) => tx.into_signed(signature).into(),
+        TypedTransaction::Eip7702(tx) => tx.into_signed(signature).into(),
+    };
+    T::decode_2718(&mut envelope.encoded_2718().as_slice()).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::EthApi;
+    use alloy_eips::eip1559::ETHEREUM_BLOCK_GAS_LIMIT;
+    use futures::{future::BoxFuture, FutureExt};
+    use reth_engine_primitives::is_impersonated_signature;
+    use reth_errors::RethResult;
+    use reth_evm_ethereum::EthEvmConfig;
+    use reth_network_api::noop::NoopNetwork;
+    use reth_primitives::{Block, Header, TransactionSigned};
+    use reth_provider::{
+        test_utils::{ExtendedAccount, MockEthProvider},
+        ChainSpecProvider,
+    };
+    use reth_rpc_eth_types::{
+        EthStateCache, FeeHistoryCache, FeeHistoryCacheConfig, GasPriceOracle,
+    };
+    use reth_rpc_server_types::constants::{
+        DEFAULT_ETH_PROOF_WINDOW, DEFAULT_MAX_SIMULATE_BLOCKS, DEFAULT_PROOF_PERMITS,
+    };
+    use reth_tasks::pool::BlockingTaskPool;
+    use reth_transaction_pool::test_utils::{testing_pool, TestPool};
+
+    type TestEthApi = EthApi<MockEthProvider, TestPool, NoopNetwork, EthEvmConfig>;
+
+    /// A [`DevMiner`] that records the requests.
+    #[derive(Debug, Default)]
+    struct TestMiner {
+        automine: bool,
+        pending: Mutex<HashMap<Address, AccountPatch>>,
+        mined: Mutex<u64>,
+        reverted: Mutex<Vec<B256>>,
+    }
+
+    impl DevMiner for TestMiner {
+        fn mine(
+            &self,
+            blocks: u64,
+            _interval: Option<u64>,
+        ) -> BoxFuture<'_, RethResult<Vec<B256>>> {
+            *self.mined.lock() += blocks;
+            self.pending.lock().clear();
+            async { Ok(Vec::new()) }.boxed()
+        }
+
+        fn set_mining_mode(&self, _mode: DevMiningMode) -> RethResult<()> {
+            Ok(())
+        }
+
+        fn is_automine(&self) -> BoxFuture<'_, RethResult<bool>> {
+            async { Ok(self.automine) }.boxed()
+        }
+
+        fn set_next_block_timestamp(&self, _timestamp: u64) -> BoxFuture<'_, RethResult<()>> {
+            async { Ok(()) }.boxed()
+        }
+
+        fn increase_time(&self, seconds: u64) -> BoxFuture<'_, RethResult<i64>> {
+            async move { Ok(seconds as i64) }.boxed()
+        }
+
+        fn set_time(&self, _timestamp: u64) -> BoxFuture<'_, RethResult<i64>> {
+            async { Ok(0) }.boxed()
+        }
+
+        fn set_block_timestamp_interval(&self, _interval: Option<u64>) -> RethResult<()> {
+            Ok(())
+        }
+
+        fn modify_state(&self, patches: HashMap<Address, AccountPatch>) -> RethResult<()> {
+            let mut pending = self.pending.lock();
+            for (address, patch) in patches {
+                pending.entry(address).or_default().merge(patch);
+            }
+            Ok(())
+        }
+
+        fn pending_state(&self) -> BoxFuture<'_, RethResult<HashMap<Address, AccountPatch>>> {
+            async { Ok(self.pending.lock().clone()) }.boxed()
+        }
+
+        fn dump_state(&self) -> BoxFuture<'_, RethResult<BTreeMap<Address, AccountPatch>>> {
+            async { Ok(self.pending.lock().clone().into_iter().collect()) }.boxed()
+        }
+
+        fn revert(&self, block_hash: B256) -> BoxFuture<'_, RethResult<B256>> {
+            self.reverted.lock().push(block_hash);
+            async { Ok(B256::random()) }.boxed()
+        }
+    }
+
+    fn anvil_api(
+        accounts: impl IntoIterator<Item = (Address, ExtendedAccount)>,
+        miner: Arc<TestMiner>,
+    ) -> (AnvilApi<TestEthApi>, B256) {
+        let provider = MockEthProvider::default();
+        provider.extend_accounts(accounts);
+        let genesis_hash = B256::random();
+        provider.add_block(
+            genesis_hash,
+            Block {
+                header: Header { gas_limit: ETHEREUM_BLOCK_GAS_LIMIT, ..Default::default() },
+                body: Default::default(),
+            },
+        );
+
+        let evm_config = EthEvmConfig::new(provider.chain_spec());
+        let cache = EthStateCache::spawn(provider.clone(), Default::default());
+        let eth_api = EthApi::new(
+            provider.clone(),
+            testing_pool(),
+            NoopNetwork::default(),
+            cache.clone(),
+            GasPriceOracle::new(provider, Default::default(), cache),
+            ETHEREUM_BLOCK_GAS_LIMIT,
+            DEFAULT_MAX_SIMULATE_BLOCKS,
+            DEFAULT_ETH_PROOF_WINDOW,
+            BlockingTaskPool::build().expect("failed to build tracing pool"),
+            FeeHistoryCache::new(FeeHistoryCacheConfig::default()),
+            evm_config,
+            DEFAULT_PROOF_PERMITS,
+        );
+        (AnvilApi::new(eth_api, miner), genesis_hash)
+    }
+
+    #[tokio::test]
+    async fn state_modifications_are_pending_until_next_block() {
+        let miner = Arc::new(TestMiner::default());
+        let (api, _) = anvil_api([], miner.clone());
+        let address = Address::random();
+
+        api.anvil_set_balance(address, U256::from(1)).await.unwrap();
+        api.anvil_set_nonce(address, U256::from(2)).await.unwrap();
+        api.anvil_set_storage_at(address, U256::from(3), B256::with_last_byte(4)).await.unwrap();
+
+        // nothing is mined, the modifications are merged into the overlay of the next block
+        assert_eq!(*miner.mined.lock(), 0);
+        assert_eq!(
+            miner.pending.lock()[&address],
+            AccountPatch {
+                balance: Some(U256::from(1)),
+                nonce: Some(2),
+                code: None,
+                storage: HashMap::from_iter([(B256::with_last_byte(3), B256::with_last_byte(4))]),
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn dump_and_load_state_roundtrip() {
+        let miner = Arc::new(TestMiner::default());
+        let (api, _) = anvil_api([], miner.clone());
+        let address = Address::random();
+        let patch = AccountPatch {
+            balance: Some(U256::from(10)),
+            nonce: Some(1),
+            code: Some(Bytes::from_static(&[0x00])),
+            storage: HashMap::from_iter([(B256::with_last_byte(1), B256::with_last_byte(2))]),
+        };
+        miner.modify_state(HashMap::from_iter([(address, patch.clone())])).unwrap();
+
+        let dump = api.anvil_dump_state().await.unwrap();
+        miner.pending.lock().clear();
+        assert!(api.anvil_load_state(dump).await.unwrap());
+
+        assert_eq!(miner.pending.lock()[&address], patch);
+    }
+
+    #[tokio::test]
+    async fn impersonated_transaction_is_included_in_next_block() {
+        let miner = Arc::new(TestMiner { automine: true, ..Default::default() });
+        let from = Address::random();
+        let (api, _) =
+            anvil_api([(from, ExtendedAccount::new(0, U256::from(1_000_000)))], miner.clone());
+        api.anvil_impersonate_account(from).await.unwrap();
+
+        let request = TransactionRequest::default()
+            .from(from)
+            .to(Address::random())
+            .value(U256::from(100))
+            .gas_limit(21_000)
+            .max_fee_per_gas(1_000_000_000)
+            .max_priority_fee_per_gas(0);
+        let tx_hash = api.send_transaction(request.clone()).await.unwrap();
+
+        // the transaction is added to the pool as a private transaction of the sender, and mined
+        // right away
+        let tx = api.eth_api().pool().get(&tx_hash).unwrap();
+        assert_eq!(tx.sender(), from);
+        assert_eq!(tx.nonce(), 0);
+        assert_eq!(tx.origin, TransactionOrigin::Private);
+        assert_eq!(*miner.mined.lock(), 1);
+
+        // the same transaction of another sender has a different hash
+        let miner = Arc::new(TestMiner::default());
+        let other = Address::random();
+        let (api, _) = anvil_api([], miner.clone());
+        api.anvil_auto_impersonate_account(true).await.unwrap();
+        let other_hash = api.send_transaction(request.from(other)).await.unwrap();
+
+        assert_ne!(other_hash, tx_hash);
+        assert_eq!(api.eth_api().pool().get(&other_hash).unwrap().sender(), other);
+        // without automine the transaction stays in the pool
+        assert_eq!(*miner.mined.lock(), 0);
+    }
+
+    #[test]
+    fn impersonated_transaction_roundtrip() {
+        let from = Address::random();
+        let tx = TransactionRequest::default()
+            .to(Address::random())
+            .nonce(0)
+            .gas_limit(21_000)
+            .max_fee_per_gas(1)
+            .max_priority_fee_per_gas(0)
+            .build_typed_tx()
+            .unwrap();
+
+        let tx: TransactionSigned = impersonated_transaction(tx, from).unwrap();
+        assert!(is_impersonated_signature(&tx.signature, from));
+        assert!(!is_impersonated_signature(&tx.signature, Address::random()));
+    }
+
+    #[tokio::test]
+    async fn transaction_of_unknown_account_needs_signer() {
+        let miner = Arc::new(TestMiner::default());
+        let from = Address::random();
+        let (api, _) = anvil_api([(from, ExtendedAccount::new(0, U256::from(1_000)))], miner);
+
+        let request = TransactionRequest::default().from(from).to(Address::random());
+        assert!(api.send_transaction(request).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn revert_to_snapshot() {
+        let miner = Arc::new(TestMiner::default());
+        let (api, genesis_hash) = anvil_api([], miner.clone());
+
+        let id = api.anvil_snapshot().await.unwrap();
+        assert!(api.anvil_revert(id).await.unwrap());
+        assert_eq!(*miner.reverted.lock(), vec![genesis_hash]);
+
+        // the snapshot is consumed
+        assert!(!api.anvil_revert(id).await.unwrap());
+    }
+}
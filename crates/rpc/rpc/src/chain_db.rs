@@ -0,0 +1,492 @@
+//! Read access to the database of the node for the `debug` namespace.
+
+use alloy_primitives::{Address, BlockNumber, B256, U256};
+use reth_db::{
+    cursor::{DbCursorRO, DbDupCursorRO},
+    models::BlockNumberAddress,
+    table::Table,
+    tables,
+    transaction::DbTx,
+    DatabaseError, RawKey, RawTable, TableViewer, Tables,
+};
+use reth_primitives::{Account, StaticFileSegment};
+use reth_provider::{
+    BlockNumReader, ChangeSetReader, DBProvider, DatabaseProviderFactory, ProviderError,
+    ProviderResult, StateProviderFactory, StaticFileProviderFactory, StorageChangeSetReader,
+};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    ops::RangeInclusive,
+};
+
+/// The maximum number of blocks the state queries of [`ChainDb`] can reach back from the tip, and
+/// the maximum length of the block range of [`ChainDb::modified_accounts`].
+///
+/// Every block in the range is read from the changesets.
+pub const MAX_CHAIN_DB_BLOCK_RANGE: u64 = 1024;
+
+/// The maximum number of entries that are returned by a single [`ChainDb`] query.
+pub const MAX_CHAIN_DB_RESULTS: usize = 10_000;
+
+/// A page of accounts, and the address of the first account of the next page, if any.
+pub type AccountPage = (Vec<(Address, Account)>, Option<Address>);
+
+/// A page of storage slots, and the first slot of the next page, if any.
+pub type StoragePage = (Vec<(B256, U256)>, Option<B256>);
+
+/// Read access to the database of the node.
+///
+/// This gives the `debug` namespace access to the raw tables, the static files and the changesets
+/// of the node, which are not exposed by the providers the `eth` namespace is built on.
+pub trait ChainDb: Send + Sync {
+    /// Returns the raw value of the raw key in the table.
+    ///
+    /// For `DUPSORT` tables, this returns the first value of the key.
+    fn get(&self, table: Tables, key: Vec<u8>) -> ProviderResult<Option<Vec<u8>>>;
+
+    /// Returns the number of entries of every table of the database.
+    fn table_entries(&self) -> ProviderResult<Vec<(Tables, usize)>>;
+
+    /// Returns the number of blocks that are stored in the static files.
+    fn ancients(&self) -> ProviderResult<u64>;
+
+    /// Returns the addresses of all accounts whose account info or storage changed in the block
+    /// range.
+    ///
+    /// Returns `None` if the range spans more than [`MAX_CHAIN_DB_BLOCK_RANGE`] blocks or if more
+    /// than `limit` accounts changed. The `limit` is capped at [`MAX_CHAIN_DB_RESULTS`].
+    fn modified_accounts(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+        limit: usize,
+    ) -> ProviderResult<Option<BTreeSet<Address>>>;
+
+    /// Returns up to `limit` accounts of the state after the given block, ordered by address and
+    /// starting at `start`.
+    ///
+    /// The state of blocks that are more than [`MAX_CHAIN_DB_BLOCK_RANGE`] blocks behind the tip is
+    /// treated as pruned. The `limit` is capped at [`MAX_CHAIN_DB_RESULTS`].
+    ///
+    /// Also returns the address of the first account of the next page, if any.
+    fn account_range(
+        &self,
+        block: BlockNumber,
+        start: Address,
+        limit: usize,
+    ) -> ProviderResult<AccountPage>;
+
+    /// Returns up to `limit` non-empty storage slots of the account in the state after the given
+    /// block, ordered by slot and starting at `start`.
+    ///
+    /// The `overlay` contains slots that were changed on top of the state of the block, a zero
+    /// value clears the slot.
+    ///
+    /// The state of blocks that are more than [`MAX_CHAIN_DB_BLOCK_RANGE`] blocks behind the tip is
+    /// treated as pruned. The `limit` is capped at [`MAX_CHAIN_DB_RESULTS`].
+    ///
+    /// Also returns the first slot of the next page, if any.
+    fn storage_range(
+        &self,
+        block: BlockNumber,
+        address: Address,
+        start: B256,
+        limit: usize,
+        overlay: &BTreeMap<B256, U256>,
+    ) -> ProviderResult<StoragePage>;
+}
+
+impl<P> ChainDb for P
+where
+    P: DatabaseProviderFactory<Provider: BlockNumReader>
+        + StaticFileProviderFactory
+        + StateProviderFactory
+        + ChangeSetReader
+        + StorageChangeSetReader
+        + BlockNumReader
+        + Send
+        + Sync,
+{
+    fn get(&self, table: Tables, key: Vec<u8>) -> ProviderResult<Option<Vec<u8>>> {
+        let provider = self.database_provider_ro()?;
+        Ok(table.view(&GetViewer { tx: provider.tx_ref(), key })?)
+    }
+
+    fn table_entries(&self) -> ProviderResult<Vec<(Tables, usize)>> {
+        let provider = self.database_provider_ro()?;
+        let viewer = EntriesViewer { tx: provider.tx_ref() };
+        Ok(Tables::ALL
+            .iter()
+            .map(|table| Ok((*table, table.view(&viewer)?)))
+            .collect::<Result<_, DatabaseError>>()?)
+    }
+
+    fn ancients(&self) -> ProviderResult<u64> {
+        Ok(self
+            .static_file_provider()
+            .get_highest_static_file_block(StaticFileSegment::Headers)
+            .map_or(0, |block| block + 1))
+    }
+
+    fn modified_accounts(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+        limit: usize,
+    ) -> ProviderResult<Option<BTreeSet<Address>>> {
+        if range.end().saturating_sub(*range.start()) >= MAX_CHAIN_DB_BLOCK_RANGE {
+            return Ok(None)
+        }
+        let limit = limit.min(MAX_CHAIN_DB_RESULTS);
+
+        let mut addresses = BTreeSet::new();
+        for number in range {
+            addresses.extend(
+                self.account_block_changeset(number)?.into_iter().map(|change| change.address),
+            );
+            addresses
+                .extend(self.storage_changeset(number)?.into_iter().map(|(key, _)| key.address()));
+            if addresses.len() > limit {
+                return Ok(None)
+            }
+        }
+        Ok(Some(addresses))
+    }
+
+    fn account_range(
+        &self,
+        block: BlockNumber,
+        start: Address,
+        limit: usize,
+    ) -> ProviderResult<AccountPage> {
+        let provider = self.database_provider_ro()?;
+        let tip = self.best_block_number()?;
+        ensure_recent_block(block, tip)?;
+        let limit = limit.min(MAX_CHAIN_DB_RESULTS);
+        let persisted = provider.best_block_number()?;
+        let state = self.history_by_block_number(block)?;
+
+        // Accounts that existed at the block might have been destroyed since, and accounts that
+        // were created after the last persisted block are not in the plain state yet. Only the
+        // changes from the start of the page on are relevant.
+        let changes_from = block.min(persisted) + 1;
+        let mut changed = BTreeSet::new();
+
+        // Older changesets are moved to the static files, the rest is read from the database.
+        let static_file_provider = self.static_file_provider();
+        let static_files_end = static_file_provider
+            .get_highest_static_file_block(StaticFileSegment::AccountChangeSets)
+            .filter(|highest| *highest >= changes_from)
+            .map(|highest| highest.min(persisted));
+        if let Some(end) = static_files_end {
+            changed.extend(
+                static_file_provider
+                    .account_changesets_range(changes_from..=end)?
+                    .into_iter()
+                    .map(|(_, change)| change.address)
+                    .filter(|address| *address >= start),
+            );
+        }
+        let mut changesets = provider.tx_ref().cursor_dup_read::<tables::AccountChangeSets>()?;
+        for number in static_files_end.map_or(changes_from, |end| end + 1)..=persisted {
+            for entry in changesets.walk_dup(Some(number), Some(start))? {
+                changed.insert(entry?.1.address);
+            }
+        }
+        for number in persisted + 1..=tip {
+            changed.extend(
+                self.account_block_changeset(number)?
+                    .into_iter()
+                    .map(|change| change.address)
+                    .filter(|address| *address >= start),
+            );
+        }
+
+        let mut plain_state = provider.tx_ref().cursor_read::<tables::PlainAccountState>()?;
+        let mut addresses = merge_sorted(
+            plain_state.walk(Some(start))?.map(|entry| entry.map(|(address, _)| address)),
+            changed.range(start..).copied(),
+        );
+
+        let mut accounts = Vec::new();
+        while let Some(address) = addresses.next().transpose()? {
+            let Some(account) = state.basic_account(address)? else { continue };
+            if accounts.len() == limit {
+                return Ok((accounts, Some(address)))
+            }
+            accounts.push((address, account));
+        }
+        Ok((accounts, None))
+    }
+
+    fn storage_range(
+        &self,
+        block: BlockNumber,
+        address: Address,
+        start: B256,
+        limit: usize,
+        overlay: &BTreeMap<B256, U256>,
+    ) -> ProviderResult<StoragePage> {
+        let provider = self.database_provider_ro()?;
+        let tip = self.best_block_number()?;
+        ensure_recent_block(block, tip)?;
+        let limit = limit.min(MAX_CHAIN_DB_RESULTS);
+        let persisted = provider.best_block_number()?;
+        let state = self.history_by_block_number(block)?;
+
+        // Same as for accounts, slots that were set at the block might have been cleared since
+        // and slots that were set after the last persisted block are not in the plain state yet.
+        let changes_from = block.min(persisted) + 1;
+        let mut changed = overlay.range(start..).map(|(slot, _)| *slot).collect::<BTreeSet<_>>();
+
+        let static_file_provider = self.static_file_provider();
+        let static_files_end = static_file_provider
+            .get_highest_static_file_block(StaticFileSegment::StorageChangeSets)
+            .filter(|highest| *highest >= changes_from)
+            .map(|highest| highest.min(persisted));
+        if let Some(end) = static_files_end {
+            changed.extend(
+                static_file_provider
+                    .storage_changesets_range(changes_from..=end)?
+                    .into_iter()
+                    .filter(|(key, entry)| key.address() == address && entry.key >= start)
+                    .map(|(_, entry)| entry.key),
+            );
+        }
+        let mut changesets = provider.tx_ref().cursor_dup_read::<tables::StorageChangeSets>()?;
+        for number in static_files_end.map_or(changes_from, |end| end + 1)..=persisted {
+            for entry in
+                changesets.walk_dup(Some(BlockNumberAddress((number, address))), Some(start))?
+            {
+                changed.insert(entry?.1.key);
+            }
+        }
+        for number in persisted + 1..=tip {
+            changed.extend(
+                self.storage_changeset(number)?
+                    .into_iter()
+                    .filter(|(key, entry)| key.address() == address && entry.key >= start)
+                    .map(|(_, entry)| entry.key),
+            );
+        }
+
+        let mut plain_state = provider.tx_ref().cursor_dup_read::<tables::PlainStorageState>()?;
+        let mut slots = merge_sorted(
+            plain_state
+                .walk_dup(Some(address), Some(start))?
+                .map(|entry| entry.map(|(_, e)| e.key)),
+            changed.range(start..).copied(),
+        );
+
+        let mut storage = Vec::new();
+        while let Some(slot) = slots.next().transpose()? {
+            let value = match overlay.get(&slot) {
+                Some(value) => *value,
+                None => state.storage(address, slot)?.unwrap_or_default(),
+            };
+            if value.is_zero() {
+                continue
+            }
+            if storage.len() == limit {
+                return Ok((storage, Some(slot)))
+            }
+            storage.push((slot, value));
+        }
+        Ok((storage, None))
+    }
+}
+
+/// Ensures that the state of the block can be ranged over, see [`MAX_CHAIN_DB_BLOCK_RANGE`].
+fn ensure_recent_block(block: BlockNumber, tip: BlockNumber) -> ProviderResult<()> {
+    if block > tip {
+        return Err(ProviderError::StateForNumberNotFound(block))
+    }
+    if tip - block > MAX_CHAIN_DB_BLOCK_RANGE {
+        return Err(ProviderError::StateAtBlockPruned(block))
+    }
+    Ok(())
+}
+
+/// Merges a sorted fallible iterator with a sorted iterator, removing duplicates.
+fn merge_sorted<T: Ord + Copy>(
+    left: impl Iterator<Item = Result<T, DatabaseError>>,
+    right: impl Iterator<Item = T>,
+) -> impl Iterator<Item = Result<T, DatabaseError>> {
+    let mut left = left.peekable();
+    let mut right = right.peekable();
+    std::iter::from_fn(move || {
+        let next = match (left.peek(), right.peek()) {
+            (Some(Err(_)), _) => return left.next(),
+            (Some(Ok(l)), Some(r)) => (*l).min(*r),
+            (Some(Ok(l)), None) => *l,
+            (None, Some(r)) => *r,
+            (None, None) => return None,
+        };
+        if matches!(left.peek(), Some(Ok(l)) if *l == next) {
+            left.next();
+        }
+        if right.peek() == Some(&next) {
+            right.next();
+        }
+        Some(Ok(next))
+    })
+}
+
+/// Reads the raw value of a raw key of a table.
+struct GetViewer<'a, TX> {
+    tx: &'a TX,
+    key: Vec<u8>,
+}
+
+impl<TX: DbTx> TableViewer<Option<Vec<u8>>> for GetViewer<'_, TX> {
+    type Error = DatabaseError;
+
+    fn view<T: Table>(&self) -> Result<Option<Vec<u8>>, Self::Error> {
+        Ok(self
+            .tx
+            .get::<RawTable<T>>(RawKey::from_vec(self.key.clone()))?
+            .map(|value| value.raw_value().to_vec()))
+    }
+}
+
+/// Counts the entries of a table.
+struct EntriesViewer<'a, TX> {
+    tx: &'a TX,
+}
+
+impl<TX: DbTx> TableViewer<usize> for EntriesViewer<'_, TX> {
+    type Error = DatabaseError;
+
+    fn view<T: Table>(&self) -> Result<usize, Self::Error> {
+        self.tx.entries::<T>()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_db::{
+        models::{AccountBeforeTx, ShardedKey, StoredAccountChangeSet},
+        transaction::DbTxMut,
+        BlockNumberList,
+    };
+    use reth_provider::{
+        providers::{BlockchainProvider2, StaticFileWriter},
+        test_utils::{create_test_provider_factory, MockNodeTypesWithDB},
+    };
+    use reth_testing_utils::generators::{self, random_block_range, BlockRangeParams};
+
+    /// Creates a provider with the empty blocks 0 to 2, the accounts 1 to 5 in the plain state,
+    /// the account 6 that was destroyed in block 2, and the account 7 that was destroyed in block
+    /// 1, whose changeset was moved to the static files.
+    fn provider() -> BlockchainProvider2<MockNodeTypesWithDB> {
+        let factory = create_test_provider_factory();
+        let provider_rw = factory.provider_rw().unwrap();
+
+        let mut rng = generators::rng();
+        let blocks = random_block_range(
+            &mut rng,
+            0..=2,
+            BlockRangeParams { tx_count: 0..1, ..Default::default() },
+        );
+        for block in blocks {
+            provider_rw.insert_historical_block(block.seal_with_senders().unwrap()).unwrap();
+        }
+
+        let account = Account { nonce: 1, ..Default::default() };
+        let tx = provider_rw.tx_ref();
+        for byte in 1..=5 {
+            tx.put::<tables::PlainAccountState>(Address::with_last_byte(byte), account).unwrap();
+        }
+        let destroyed = Address::with_last_byte(6);
+        tx.put::<tables::AccountChangeSets>(
+            2,
+            AccountBeforeTx { address: destroyed, info: Some(account) },
+        )
+        .unwrap();
+        tx.put::<tables::AccountsHistory>(
+            ShardedKey::new(destroyed, u64::MAX),
+            BlockNumberList::new_pre_sorted([2]),
+        )
+        .unwrap();
+
+        let moved = Address::with_last_byte(7);
+        tx.put::<tables::AccountsHistory>(
+            ShardedKey::new(moved, u64::MAX),
+            BlockNumberList::new_pre_sorted([1]),
+        )
+        .unwrap();
+        let mut writer = factory
+            .static_file_provider()
+            .latest_writer(StaticFileSegment::AccountChangeSets)
+            .unwrap();
+        writer.append_account_changeset(0, &StoredAccountChangeSet::default()).unwrap();
+        writer
+            .append_account_changeset(
+                1,
+                &StoredAccountChangeSet {
+                    changes: vec![AccountBeforeTx { address: moved, info: Some(account) }],
+                },
+            )
+            .unwrap();
+        writer.commit().unwrap();
+        drop(writer);
+        provider_rw.commit().unwrap();
+
+        BlockchainProvider2::new(factory).unwrap()
+    }
+
+    fn addresses(page: &AccountPage) -> Vec<Address> {
+        page.0.iter().map(|(address, _)| *address).collect()
+    }
+
+    #[test]
+    fn account_range_pages_by_address() {
+        let provider = provider();
+
+        let page = provider.account_range(2, Address::ZERO, 3).unwrap();
+        assert_eq!(addresses(&page), (1..=3).map(Address::with_last_byte).collect::<Vec<_>>());
+        assert_eq!(page.1, Some(Address::with_last_byte(4)));
+
+        // the next page starts at the first address of the page
+        let page = provider.account_range(2, Address::with_last_byte(4), 3).unwrap();
+        assert_eq!(addresses(&page), vec![Address::with_last_byte(4), Address::with_last_byte(5)]);
+        assert_eq!(page.1, None);
+
+        // the account that was destroyed in block 2 still exists in block 1
+        let page = provider.account_range(1, Address::with_last_byte(5), 3).unwrap();
+        assert_eq!(addresses(&page), vec![Address::with_last_byte(5), Address::with_last_byte(6)]);
+
+        // the changeset of the account that was destroyed in block 1 is in the static files
+        let page = provider.account_range(0, Address::with_last_byte(6), 3).unwrap();
+        assert_eq!(addresses(&page), vec![Address::with_last_byte(6), Address::with_last_byte(7)]);
+    }
+
+    #[test]
+    fn modified_accounts_is_limited() {
+        let provider = provider();
+
+        assert_eq!(
+            provider.modified_accounts(2..=2, 10).unwrap(),
+            Some(BTreeSet::from([Address::with_last_byte(6)]))
+        );
+        assert_eq!(provider.modified_accounts(2..=2, 0).unwrap(), None);
+        assert_eq!(provider.modified_accounts(0..=MAX_CHAIN_DB_BLOCK_RANGE, 10).unwrap(), None);
+    }
+
+    #[test]
+    fn state_range_is_limited() {
+        assert!(ensure_recent_block(1, MAX_CHAIN_DB_BLOCK_RANGE + 1).is_ok());
+        assert!(matches!(
+            ensure_recent_block(0, MAX_CHAIN_DB_BLOCK_RANGE + 1),
+            Err(ProviderError::StateAtBlockPruned(0))
+        ));
+        assert!(matches!(ensure_recent_block(5, 4), Err(ProviderError::StateForNumberNotFound(5))));
+    }
+
+    #[test]
+    fn merge_sorted_removes_duplicates() {
+        let merged = merge_sorted([1, 3, 5, 6].into_iter().map(Ok), [2, 3, 6, 7].into_iter())
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        assert_eq!(merged, vec![1, 2, 3, 5, 6, 7]);
+    }
+}
@@ -1,11 +1,11 @@
 use alloy_consensus::BlockHeader;
 use alloy_eips::{eip2718::Encodable2718, BlockId, BlockNumberOrTag};
-use alloy_primitives::{Address, Bytes, B256, U256};
+use alloy_primitives::{hex, keccak256, Address, BlockNumber, Bytes, B256, U256, U64};
 use alloy_rlp::{Decodable, Encodable};
 use alloy_rpc_types_debug::ExecutionWitness;
 use alloy_rpc_types_eth::{
-    state::EvmOverrides, transaction::TransactionRequest, Block as RpcBlock, BlockError, Bundle,
-    StateContext, TransactionInfo,
+    state::EvmOverrides, transaction::TransactionRequest, BlockError, Bundle, StateContext,
+    TransactionInfo,
 };
 use alloy_rpc_types_trace::geth::{
     call::FlatCallFrame, BlockTraceResult, FourByteFrame, GethDebugBuiltInTracerType,
@@ -13,43 +13,93 @@ use alloy_rpc_types_trace::geth::{
     NoopFrame, TraceResult,
 };
 use async_trait::async_trait;
-use jsonrpsee::core::RpcResult;
+use futures::{FutureExt, Stream, StreamExt};
+use jsonrpsee::{core::RpcResult, PendingSubscriptionSink, SubscriptionMessage};
+use reth_beacon_consensus::BeaconConsensusEngineHandle;
 use reth_chainspec::EthereumHardforks;
+use reth_db::Tables;
+use reth_engine_primitives::{BadBlockReader, BadBlockRecord, EngineTypes};
+use reth_errors::{RethError, RethResult};
 use reth_evm::{
     env::EvmEnv,
     execute::{BlockExecutorProvider, Executor},
     ConfigureEvmEnv,
 };
-use reth_primitives::{BlockExt, NodePrimitives, ReceiptWithBloom, SealedBlockWithSenders};
+use reth_primitives::{
+    BlockExt, BlockWithSenders, NodePrimitives, ReceiptWithBloom, SealedBlockWithSenders,
+};
 use reth_primitives_traits::{Block as _, BlockBody, SignedTransaction};
 use reth_provider::{
-    BlockIdReader, BlockReaderIdExt, ChainSpecProvider, EvmEnvProvider, HeaderProvider,
-    ProviderBlock, ReceiptProviderIdExt, StateProofProvider, TransactionVariant,
+    BlockHashReader, BlockIdReader, BlockNumReader, BlockReader, BlockReaderIdExt,
+    ChainSpecProvider, EvmEnvProvider, HashedPostStateProvider, HeaderProvider, ProviderBlock,
+    ReceiptProvider, ReceiptProviderIdExt, StateProofProvider, StateProvider, StateRootProvider,
+    StorageRootProvider, TransactionVariant,
 };
 use reth_revm::{database::StateProviderDatabase, witness::ExecutionWitnessRecord};
-use reth_rpc_api::DebugApiServer;
+use reth_rpc_api::{
+    AccountRangeResult, BadBlock, DebugApiServer, DumpAccount, StorageRangeEntry,
+    StorageRangeResult,
+};
 use reth_rpc_eth_api::{
     helpers::{EthTransactions, TraceExt},
-    EthApiTypes, FromEthApiError, RpcNodeCore,
+    EthApiTypes, FromEthApiError, RpcBlock, RpcNodeCore,
 };
 use reth_rpc_eth_types::{EthApiError, StateCacheDb};
-use reth_rpc_server_types::{result::internal_rpc_err, ToRpcResult};
+use reth_rpc_server_types::{
+    result::{internal_rpc_err, invalid_params_rpc_err},
+    ToRpcResult,
+};
+use reth_rpc_types_compat::block::from_block_with_tx_hashes;
 use reth_tasks::pool::BlockingTaskGuard;
 use revm::{
-    db::{CacheDB, State},
+    db::{states::bundle_state::BundleRetention, CacheDB, State},
+    inspectors::TracerEip3155,
     primitives::{db::DatabaseCommit, BlockEnv, CfgEnvWithHandlerCfg, Env, EnvWithHandlerCfg},
 };
 use revm_inspectors::tracing::{
     FourByteInspector, MuxInspector, TracingInspector, TracingInspectorConfig, TransactionContext,
 };
-use std::sync::Arc;
+use std::{collections::BTreeMap, fmt::Write, sync::Arc};
 use tokio::sync::{AcquireError, OwnedSemaphorePermit};
+use tracing::{debug, level_filters::LevelFilter};
+
+use crate::{ChainDb, MAX_CHAIN_DB_BLOCK_RANGE, MAX_CHAIN_DB_RESULTS};
+
+/// The maximum number of accounts that are returned by `debug_accountRange`.
+const ACCOUNT_RANGE_MAX_RESULTS: u64 = 256;
+
+/// The maximum number of storage slots of a single account that are dumped by
+/// `debug_accountRange`.
+const ACCOUNT_RANGE_MAX_STORAGE: usize = 1024;
+
+/// The maximum number of blocks that can be traced by a single `debug_traceChain` subscription.
+const TRACE_CHAIN_MAX_BLOCKS: u64 = 100;
+
+/// Sets the canonical head of the node, used by `debug_setHead`.
+#[async_trait]
+pub trait SetHead: Send + Sync + 'static {
+    /// Rewinds the canonical chain to the given block.
+    async fn set_head(&self, number: BlockNumber) -> RethResult<()>;
+}
+
+#[async_trait]
+impl<Engine: EngineTypes> SetHead for BeaconConsensusEngineHandle<Engine> {
+    async fn set_head(&self, number: BlockNumber) -> RethResult<()> {
+        Self::set_head(self, number).await
+    }
+}
 
 /// `debug` API implementation.
 ///
 /// This type provides the functionality for handling `debug` related requests.
 pub struct DebugApi<Eth, BlockExecutor> {
     inner: Arc<DebugApiInner<Eth, BlockExecutor>>,
+    /// The bad blocks recorded by the engine, if any.
+    bad_blocks: Option<Arc<dyn BadBlockReader>>,
+    /// Access to the database of the node, if any.
+    chain_db: Option<Arc<dyn ChainDb>>,
+    /// Sets the canonical head of the node, if any.
+    head_setter: Option<Arc<dyn SetHead>>,
 }
 
 // === impl DebugApi ===
@@ -62,7 +112,26 @@ impl<Eth, BlockExecutor> DebugApi<Eth, BlockExecutor> {
         block_executor: BlockExecutor,
     ) -> Self {
         let inner = Arc::new(DebugApiInner { eth_api: eth, blocking_task_guard, block_executor });
-        Self { inner }
+        Self { inner, bad_blocks: None, chain_db: None, head_setter: None }
+    }
+
+    /// Sets the source the bad blocks are served from.
+    pub fn with_bad_blocks(mut self, bad_blocks: Arc<dyn BadBlockReader>) -> Self {
+        self.bad_blocks = Some(bad_blocks);
+        self
+    }
+
+    /// Sets the database that backs the database and state endpoints, like `debug_dbGet` and
+    /// `debug_accountRange`.
+    pub fn with_chain_db(mut self, chain_db: Arc<dyn ChainDb>) -> Self {
+        self.chain_db = Some(chain_db);
+        self
+    }
+
+    /// Sets the handle that is used to rewind the chain on `debug_setHead`.
+    pub fn with_set_head(mut self, head_setter: Arc<dyn SetHead>) -> Self {
+        self.head_setter = Some(head_setter);
+        self
     }
 
     /// Access the underlying `Eth` API.
@@ -168,6 +237,20 @@ where
             .env_with_header(block.header(), self.eth_api().evm_config().clone())
             .map_err(Eth::Error::from_eth_err)?;
 
+        self.trace_block(
+            Arc::new(self.recover_block(block)?.seal_slow()),
+            cfg_env_with_handler_cfg,
+            block_env,
+            opts,
+        )
+        .await
+    }
+
+    /// Recovers the senders of the given block.
+    fn recover_block(
+        &self,
+        block: ProviderBlock<Eth::Provider>,
+    ) -> Result<BlockWithSenders<ProviderBlock<Eth::Provider>>, Eth::Error> {
         // Depending on EIP-2 we need to recover the transactions differently
         let senders =
             if self.provider().chain_spec().is_homestead_active_at_block(block.header().number()) {
@@ -194,13 +277,600 @@ where
                     .collect::<Result<Vec<_>, Eth::Error>>()?
             };
 
-        self.trace_block(
-            Arc::new(block.with_senders_unchecked(senders).seal_slow()),
-            cfg_env_with_handler_cfg,
-            block_env,
-            opts,
-        )
-        .await
+        Ok(block.with_senders_unchecked(senders))
+    }
+
+    /// Returns the recorded bad block with the given hash.
+    fn bad_block(&self, block_hash: B256) -> Result<BadBlockRecord, Eth::Error> {
+        self.bad_blocks
+            .as_ref()
+            .map(|bad_blocks| bad_blocks.bad_block(block_hash))
+            .transpose()
+            .map_err(Eth::Error::from_eth_err)?
+            .flatten()
+            .ok_or_else(|| Eth::Error::from_eth_err(EthApiError::HeaderNotFound(block_hash.into())))
+    }
+
+    /// Returns the database of the node, if it was configured.
+    fn chain_db(&self) -> Result<&dyn ChainDb, Eth::Error> {
+        self.chain_db.as_deref().ok_or_else(|| {
+            Eth::Error::from_eth_err(EthApiError::Unsupported("database access is not available"))
+        })
+    }
+
+    /// Ensures that the state of the block is recent enough to be ranged over with the
+    /// [`ChainDb`].
+    fn ensure_recent_state(&self, number: BlockNumber) -> Result<(), Eth::Error> {
+        let tip = self.provider().best_block_number().map_err(Eth::Error::from_eth_err)?;
+        if tip.saturating_sub(number) > MAX_CHAIN_DB_BLOCK_RANGE {
+            return Err(Eth::Error::from_eth_err(EthApiError::InvalidParams(format!(
+                "state of block {number} is not available, only the state of the last \
+                 {MAX_CHAIN_DB_BLOCK_RANGE} blocks can be ranged over"
+            ))))
+        }
+        Ok(())
+    }
+
+    /// Replays the transactions of the given block with the EIP-3155 standard json tracer and
+    /// writes the trace of each transaction to a separate file in the temporary directory.
+    ///
+    /// Returns the paths of the written files.
+    async fn standard_trace_block_to_file(
+        &self,
+        block: Arc<SealedBlockWithSenders<ProviderBlock<Eth::Provider>>>,
+        opts: GethDebugTracingOptions,
+    ) -> Result<Vec<String>, Eth::Error> {
+        let EvmEnv { cfg_env_with_handler_cfg: cfg, block_env } = self
+            .provider()
+            .env_with_header(block.header(), self.eth_api().evm_config().clone())
+            .map_err(Eth::Error::from_eth_err)?;
+        let enable_memory = opts.config.enable_memory.unwrap_or_default();
+
+        let this = self.clone();
+        self.eth_api()
+            .spawn_with_state_at_block(block.parent_hash().into(), move |state| {
+                let mut files = Vec::with_capacity(block.body.transactions().len());
+                let mut db = CacheDB::new(StateProviderDatabase::new(state));
+
+                this.eth_api().apply_pre_execution_changes(&block, &mut db, &cfg, &block_env)?;
+
+                for (index, (signer, tx)) in block.transactions_with_sender().enumerate() {
+                    let path = std::env::temp_dir().join(format!(
+                        "block_{:.8}-{index}-{:.8}-{}.jsonl",
+                        block.hash(),
+                        tx.tx_hash(),
+                        rand::random::<u32>()
+                    ));
+                    let file = std::fs::File::create(&path)
+                        .map_err(|err| EthApiError::Internal(RethError::other(err)))
+                        .map_err(Eth::Error::from_eth_err)?;
+                    let mut inspector = TracerEip3155::new(Box::new(std::io::BufWriter::new(file)));
+                    if enable_memory {
+                        inspector = inspector.with_memory();
+                    }
+
+                    let env = EnvWithHandlerCfg {
+                        env: Env::boxed(
+                            cfg.cfg_env.clone(),
+                            block_env.clone(),
+                            this.eth_api().evm_config().tx_env(tx, *signer),
+                        ),
+                        handler_cfg: cfg.handler_cfg,
+                    };
+                    let (res, _) = this.eth_api().inspect(&mut db, env, &mut inspector)?;
+                    db.commit(res.state);
+
+                    files.push(path.display().to_string());
+                }
+
+                Ok(files)
+            })
+            .await
+    }
+
+    /// Returns the bad blocks recorded by the engine, highest block first.
+    pub fn debug_bad_blocks(
+        &self,
+    ) -> Result<Vec<BadBlock<RpcBlock<Eth::NetworkTypes>>>, Eth::Error> {
+        let Some(bad_blocks) = &self.bad_blocks else { return Ok(Vec::new()) };
+        let bad_blocks = bad_blocks.bad_blocks().map_err(Eth::Error::from_eth_err)?;
+
+        let mut res = Vec::with_capacity(bad_blocks.len());
+        for bad_block in bad_blocks {
+            let block = bad_block
+                .decode_block::<ProviderBlock<Eth::Provider>>()
+                .map_err(BlockError::RlpDecodeRawBlock)
+                .map_err(Eth::Error::from_eth_err)?;
+            let block = from_block_with_tx_hashes(self.recover_block(block)?, Some(bad_block.hash));
+            res.push(BadBlock {
+                hash: bad_block.hash,
+                block,
+                rlp: bad_block.rlp,
+                error: bad_block.error,
+            });
+        }
+
+        Ok(res)
+    }
+
+    /// Replays a bad block and returns the trace of each transaction.
+    pub async fn debug_trace_bad_block(
+        &self,
+        block_hash: B256,
+        opts: GethDebugTracingOptions,
+    ) -> Result<Vec<TraceResult>, Eth::Error> {
+        let bad_block = self.bad_block(block_hash)?;
+        self.debug_trace_raw_block(bad_block.rlp, opts).await
+    }
+
+    /// Replays a bad block with the standard json tracer and writes the trace of each
+    /// transaction to a file.
+    ///
+    /// Returns the paths of the written files.
+    pub async fn standard_trace_bad_block_to_file(
+        &self,
+        block_hash: B256,
+        opts: GethDebugTracingOptions,
+    ) -> Result<Vec<String>, Eth::Error> {
+        let block = self
+            .bad_block(block_hash)?
+            .decode_block::<ProviderBlock<Eth::Provider>>()
+            .map_err(BlockError::RlpDecodeRawBlock)
+            .map_err(Eth::Error::from_eth_err)?;
+        let block = Arc::new(self.recover_block(block)?.seal_slow());
+        self.standard_trace_block_to_file(block, opts).await
+    }
+
+    /// Returns a property of the database.
+    ///
+    /// Supports `stats`, which returns the number of entries of every table, and the name of a
+    /// table, which returns the number of entries of that table.
+    pub async fn debug_chaindb_property(&self, property: String) -> Result<String, Eth::Error> {
+        let this = self.clone();
+        self.eth_api()
+            .spawn_blocking_io(move |_| {
+                let chain_db = this.chain_db()?;
+                let entries = chain_db.table_entries().map_err(Eth::Error::from_eth_err)?;
+                if property == "stats" {
+                    let mut stats = String::new();
+                    for (table, entries) in entries {
+                        let _ = writeln!(stats, "{table}: {entries} entries");
+                    }
+                    return Ok(stats)
+                }
+
+                entries
+                    .into_iter()
+                    .find(|(table, _)| table.name() == property)
+                    .map(|(_, entries)| entries.to_string())
+                    .ok_or_else(|| {
+                        Eth::Error::from_eth_err(EthApiError::InvalidParams(format!(
+                            "unknown property: {property}"
+                        )))
+                    })
+            })
+            .await
+    }
+
+    /// Returns the raw value of a key of a table.
+    ///
+    /// The key is the name of the table and the hex encoded raw key, separated by a colon.
+    pub async fn debug_db_get(&self, key: String) -> Result<Bytes, Eth::Error> {
+        let (table, key) = key.split_once(':').ok_or_else(|| {
+            EthApiError::InvalidParams("key must be formatted as <table>:<hex key>".to_string())
+        })?;
+        let table = table.parse::<Tables>().map_err(EthApiError::InvalidParams)?;
+        let key = hex::decode(key).map_err(|err| EthApiError::InvalidParams(err.to_string()))?;
+
+        let this = self.clone();
+        self.eth_api()
+            .spawn_blocking_io(move |_| {
+                let chain_db = this.chain_db()?;
+                chain_db
+                    .get(table, key)
+                    .map_err(Eth::Error::from_eth_err)?
+                    .map(Into::into)
+                    .ok_or_else(|| {
+                        Eth::Error::from_eth_err(EthApiError::InvalidParams(format!(
+                            "key not found in {table}"
+                        )))
+                    })
+            })
+            .await
+    }
+
+    /// Returns the number of blocks in the static files.
+    pub async fn debug_db_ancients(&self) -> Result<u64, Eth::Error> {
+        let this = self.clone();
+        self.eth_api()
+            .spawn_blocking_io(move |_| {
+                this.chain_db()?.ancients().map_err(Eth::Error::from_eth_err)
+            })
+            .await
+    }
+
+    /// Returns an item of a block in the static files.
+    ///
+    /// The kind is one of `headers`, `hashes`, `bodies`, `receipts` and `diffs`.
+    pub async fn debug_db_ancient(&self, kind: String, number: u64) -> Result<Bytes, Eth::Error> {
+        let this = self.clone();
+        self.eth_api()
+            .spawn_blocking_io(move |_| {
+                let chain_db = this.chain_db()?;
+                if number >= chain_db.ancients().map_err(Eth::Error::from_eth_err)? {
+                    return Err(Eth::Error::from_eth_err(EthApiError::InvalidParams(format!(
+                        "block {number} is not in the static files"
+                    ))))
+                }
+
+                let provider = this.provider();
+                let item = match kind.as_str() {
+                    "headers" => provider
+                        .header_by_number(number)
+                        .map(|header| header.map(|header| alloy_rlp::encode(header).into())),
+                    "hashes" => provider
+                        .block_hash(number)
+                        .map(|hash| hash.map(|hash| hash.to_vec().into())),
+                    "bodies" => provider
+                        .block_by_number(number)
+                        .map(|block| block.map(|block| alloy_rlp::encode(block.body()).into())),
+                    "receipts" => provider.receipts_by_block(number.into()).map(|receipts| {
+                        receipts.map(|receipts| {
+                            alloy_rlp::encode(
+                                receipts
+                                    .into_iter()
+                                    .map(|receipt| {
+                                        Bytes::from(ReceiptWithBloom::from(receipt).encoded_2718())
+                                    })
+                                    .collect::<Vec<_>>(),
+                            )
+                            .into()
+                        })
+                    }),
+                    "diffs" => provider
+                        .header_td_by_number(number)
+                        .map(|td| td.map(|td| alloy_rlp::encode(td).into())),
+                    _ => {
+                        return Err(Eth::Error::from_eth_err(EthApiError::InvalidParams(format!(
+                            "unknown kind: {kind}"
+                        ))))
+                    }
+                };
+
+                item.map_err(Eth::Error::from_eth_err)?.ok_or_else(|| {
+                    Eth::Error::from_eth_err(EthApiError::HeaderNotFound(number.into()))
+                })
+            })
+            .await
+    }
+
+    /// Returns the addresses of all accounts that changed between the two blocks.
+    ///
+    /// Without an end block, returns the accounts that changed in the start block. Otherwise,
+    /// returns the accounts that changed in the blocks `(start, end]`.
+    pub async fn debug_get_modified_accounts_by_number(
+        &self,
+        start: BlockNumber,
+        end: Option<BlockNumber>,
+    ) -> Result<Vec<Address>, Eth::Error> {
+        let range = match end {
+            None => start..=start,
+            Some(end) if end > start => start + 1..=end,
+            Some(end) => {
+                return Err(Eth::Error::from_eth_err(EthApiError::InvalidParams(format!(
+                    "start block ({start}) must be less than end block ({end})"
+                ))))
+            }
+        };
+        if range.end() - range.start() >= MAX_CHAIN_DB_BLOCK_RANGE {
+            return Err(Eth::Error::from_eth_err(EthApiError::InvalidParams(format!(
+                "block range too large; currently limited to {MAX_CHAIN_DB_BLOCK_RANGE} blocks"
+            ))))
+        }
+
+        let this = self.clone();
+        self.eth_api()
+            .spawn_blocking_io(move |_| {
+                let chain_db = this.chain_db()?;
+                let tip = this.provider().best_block_number().map_err(Eth::Error::from_eth_err)?;
+                if *range.end() > tip {
+                    return Err(Eth::Error::from_eth_err(EthApiError::HeaderNotFound(
+                        (*range.end()).into(),
+                    )))
+                }
+                let addresses = chain_db
+                    .modified_accounts(range, MAX_CHAIN_DB_RESULTS)
+                    .map_err(Eth::Error::from_eth_err)?
+                    .ok_or_else(|| {
+                        Eth::Error::from_eth_err(EthApiError::InvalidParams(format!(
+                            "more than {MAX_CHAIN_DB_RESULTS} accounts changed, use a smaller \
+                             block range"
+                        )))
+                    })?;
+                Ok(addresses.into_iter().collect())
+            })
+            .await
+    }
+
+    /// Returns the addresses of all accounts that changed between the two blocks, see
+    /// [`Self::debug_get_modified_accounts_by_number`].
+    pub async fn debug_get_modified_accounts_by_hash(
+        &self,
+        start: B256,
+        end: Option<B256>,
+    ) -> Result<Vec<Address>, Eth::Error> {
+        let block_number = |hash: B256| {
+            self.provider()
+                .block_number(hash)
+                .map_err(Eth::Error::from_eth_err)?
+                .ok_or_else(|| Eth::Error::from_eth_err(EthApiError::HeaderNotFound(hash.into())))
+        };
+        let start = block_number(start)?;
+        let end = end.map(block_number).transpose()?;
+        self.debug_get_modified_accounts_by_number(start, end).await
+    }
+
+    /// Returns a page of the accounts of the state after the given block, ordered by address.
+    pub async fn debug_account_range(
+        &self,
+        block_number: BlockNumberOrTag,
+        start: Bytes,
+        max_results: u64,
+        nocode: bool,
+        nostorage: bool,
+    ) -> Result<AccountRangeResult, Eth::Error> {
+        let start = match start.len() {
+            0 => Address::ZERO,
+            20 => Address::from_slice(&start),
+            _ => {
+                return Err(Eth::Error::from_eth_err(EthApiError::InvalidParams(
+                    "start must be an address".to_string(),
+                )))
+            }
+        };
+        let max_results = if max_results == 0 || max_results > ACCOUNT_RANGE_MAX_RESULTS {
+            ACCOUNT_RANGE_MAX_RESULTS
+        } else {
+            max_results
+        };
+        let header = self
+            .provider()
+            .sealed_header_by_number_or_tag(block_number)
+            .map_err(Eth::Error::from_eth_err)?
+            .ok_or(EthApiError::HeaderNotFound(block_number.into()))?;
+        let number = header.number();
+        let root = header.state_root();
+        self.ensure_recent_state(number)?;
+
+        let this = self.clone();
+        self.eth_api()
+            .spawn_with_state_at_block(header.hash().into(), move |state| {
+                let chain_db = this.chain_db()?;
+                let (accounts, next) = chain_db
+                    .account_range(number, start, max_results as usize)
+                    .map_err(Eth::Error::from_eth_err)?;
+
+                let mut result = AccountRangeResult { root, accounts: BTreeMap::new(), next };
+                for (address, account) in accounts {
+                    let code_hash = account.get_bytecode_hash();
+                    let code = if !nocode && account.has_bytecode() {
+                        state
+                            .bytecode_by_hash(code_hash)
+                            .map_err(Eth::Error::from_eth_err)?
+                            .map(|code| code.original_bytes())
+                    } else {
+                        None
+                    };
+                    let storage = if nostorage {
+                        None
+                    } else {
+                        let (storage, next) = chain_db
+                            .storage_range(
+                                number,
+                                address,
+                                B256::ZERO,
+                                ACCOUNT_RANGE_MAX_STORAGE,
+                                &BTreeMap::new(),
+                            )
+                            .map_err(Eth::Error::from_eth_err)?;
+                        if next.is_some() {
+                            return Err(Eth::Error::from_eth_err(EthApiError::InvalidParams(
+                                format!(
+                                    "storage of {address} has more than \
+                                     {ACCOUNT_RANGE_MAX_STORAGE} slots, use nostorage and \
+                                     debug_storageRangeAt"
+                                ),
+                            )))
+                        }
+                        Some(storage.into_iter().collect())
+                    };
+
+                    result.accounts.insert(
+                        address,
+                        DumpAccount {
+                            balance: account.balance.to_string(),
+                            nonce: account.nonce,
+                            root: state
+                                .storage_root(address, Default::default())
+                                .map_err(Eth::Error::from_eth_err)?,
+                            code_hash,
+                            code,
+                            storage,
+                            address: Some(address),
+                            key: keccak256(address),
+                        },
+                    );
+                }
+
+                Ok(result)
+            })
+            .await
+    }
+
+    /// Returns a page of the storage of the account, ordered by slot, in the state before the
+    /// transaction at the given index in the block.
+    pub async fn debug_storage_range_at(
+        &self,
+        block_hash: B256,
+        tx_idx: usize,
+        address: Address,
+        key_start: B256,
+        max_result: u64,
+    ) -> Result<StorageRangeResult, Eth::Error> {
+        let block = self
+            .eth_api()
+            .block_with_senders(block_hash.into())
+            .await?
+            .ok_or(EthApiError::HeaderNotFound(block_hash.into()))?;
+        if tx_idx > block.body.transactions().len() {
+            return Err(Eth::Error::from_eth_err(EthApiError::InvalidParams(format!(
+                "transaction index {tx_idx} out of range"
+            ))))
+        }
+        // the storage is read from the state of the parent block
+        let Some(parent_number) = block.number().checked_sub(1) else {
+            return Err(Eth::Error::from_eth_err(EthApiError::InvalidParams(
+                "genesis block has no transactions".to_string(),
+            )))
+        };
+        self.ensure_recent_state(parent_number)?;
+        let EvmEnv { cfg_env_with_handler_cfg: cfg, block_env } = self
+            .provider()
+            .env_with_header(block.header(), self.eth_api().evm_config().clone())
+            .map_err(Eth::Error::from_eth_err)?;
+
+        let this = self.clone();
+        self.eth_api()
+            .spawn_with_state_at_block(block.parent_hash().into(), move |state| {
+                let chain_db = this.chain_db()?;
+                // replay the transactions before the requested one
+                let mut db = CacheDB::new(StateProviderDatabase::new(state));
+                this.eth_api().apply_pre_execution_changes(&block, &mut db, &cfg, &block_env)?;
+                for (signer, tx) in block.transactions_with_sender().take(tx_idx) {
+                    let env = EnvWithHandlerCfg {
+                        env: Env::boxed(
+                            cfg.cfg_env.clone(),
+                            block_env.clone(),
+                            this.eth_api().evm_config().tx_env(tx, *signer),
+                        ),
+                        handler_cfg: cfg.handler_cfg,
+                    };
+                    let (res, _) = this.eth_api().transact(&mut db, env)?;
+                    db.commit(res.state);
+                }
+
+                let overlay = db
+                    .accounts
+                    .get(&address)
+                    .map(|account| {
+                        account
+                            .storage
+                            .iter()
+                            .map(|(slot, value)| (B256::from(*slot), *value))
+                            .collect()
+                    })
+                    .unwrap_or_default();
+                let (storage, next_key) = chain_db
+                    .storage_range(parent_number, address, key_start, max_result as usize, &overlay)
+                    .map_err(Eth::Error::from_eth_err)?;
+
+                Ok(StorageRangeResult {
+                    storage: storage
+                        .into_iter()
+                        .map(|(slot, value)| {
+                            (
+                                keccak256(slot),
+                                StorageRangeEntry { key: Some(slot), value: value.into() },
+                            )
+                        })
+                        .collect(),
+                    next_key,
+                })
+            })
+            .await
+    }
+
+    /// Executes the block and returns the state root after each transaction.
+    ///
+    /// The block is looked up in the chain first, and in the bad block store otherwise.
+    pub async fn debug_intermediate_roots(
+        &self,
+        block_hash: B256,
+    ) -> Result<Vec<B256>, Eth::Error> {
+        let block = match self.eth_api().block_with_senders(block_hash.into()).await? {
+            Some(block) => block,
+            None => {
+                let block = self
+                    .bad_block(block_hash)?
+                    .decode_block::<ProviderBlock<Eth::Provider>>()
+                    .map_err(BlockError::RlpDecodeRawBlock)
+                    .map_err(Eth::Error::from_eth_err)?;
+                Arc::new(self.recover_block(block)?.seal_slow())
+            }
+        };
+        let EvmEnv { cfg_env_with_handler_cfg: cfg, block_env } = self
+            .provider()
+            .env_with_header(block.header(), self.eth_api().evm_config().clone())
+            .map_err(Eth::Error::from_eth_err)?;
+
+        let this = self.clone();
+        self.eth_api()
+            .spawn_with_state_at_block(block.parent_hash().into(), move |state| {
+                let mut db = State::builder()
+                    .with_database(StateProviderDatabase::new(&state))
+                    .with_bundle_update()
+                    .build();
+                this.eth_api().apply_pre_execution_changes(&block, &mut db, &cfg, &block_env)?;
+
+                let mut roots = Vec::with_capacity(block.body.transactions().len());
+                for (signer, tx) in block.transactions_with_sender() {
+                    let env = EnvWithHandlerCfg {
+                        env: Env::boxed(
+                            cfg.cfg_env.clone(),
+                            block_env.clone(),
+                            this.eth_api().evm_config().tx_env(tx, *signer),
+                        ),
+                        handler_cfg: cfg.handler_cfg,
+                    };
+                    let (res, _) = this.eth_api().transact(&mut db, env)?;
+                    db.commit(res.state);
+                    db.merge_transitions(BundleRetention::PlainState);
+
+                    let hashed_state = state.hashed_post_state(&db.bundle_state);
+                    roots.push(state.state_root(hashed_state).map_err(Eth::Error::from_eth_err)?);
+                }
+
+                Ok(roots)
+            })
+            .await
+    }
+
+    /// Replays a block with the standard json tracer and writes the trace of each transaction to
+    /// a file.
+    ///
+    /// Returns the paths of the written files.
+    pub async fn debug_standard_trace_block_to_file(
+        &self,
+        block_hash: B256,
+        opts: GethDebugTracingOptions,
+    ) -> Result<Vec<String>, Eth::Error> {
+        let block = self
+            .eth_api()
+            .block_with_senders(block_hash.into())
+            .await?
+            .ok_or(EthApiError::HeaderNotFound(block_hash.into()))?;
+        self.standard_trace_block_to_file(block, opts).await
+    }
+
+    /// Rewinds the canonical chain to the given block.
+    pub async fn debug_set_head(&self, number: BlockNumber) -> Result<(), Eth::Error> {
+        let head_setter = self.head_setter.clone().ok_or_else(|| {
+            Eth::Error::from_eth_err(EthApiError::Unsupported("setting the head is not available"))
+        })?;
+        head_setter
+            .set_head(number)
+            .await
+            .map_err(|err| Eth::Error::from_eth_err(EthApiError::Internal(err)))
     }
 
     /// Replays a block and returns the trace of each transaction.
@@ -226,6 +896,38 @@ where
         self.trace_block(block, cfg_env_with_handler_cfg, block_env, opts).await
     }
 
+    /// Returns a stream of the traces of all blocks in the range `(start_exclusive,
+    /// end_inclusive]`, in block order.
+    ///
+    /// The blocks are traced in parallel on the tracing pool, every block that is traced holds a
+    /// tracing permit.
+    pub fn trace_chain(
+        &self,
+        start_exclusive: BlockNumber,
+        end_inclusive: BlockNumber,
+        opts: GethDebugTracingOptions,
+    ) -> impl Stream<Item = Result<BlockTraceResult, Eth::Error>> + Send + Unpin + 'static {
+        let concurrency = std::thread::available_parallelism().map_or(1, |n| n.get());
+        let this = self.clone();
+        futures::stream::iter(start_exclusive + 1..=end_inclusive)
+            .map(move |number| {
+                let this = this.clone();
+                let opts = opts.clone();
+                async move {
+                    let _permit = this.acquire_trace_permit().await;
+                    let hash = this
+                        .provider()
+                        .block_hash(number)
+                        .map_err(Eth::Error::from_eth_err)?
+                        .ok_or(EthApiError::HeaderNotFound(number.into()))?;
+                    let traces = this.debug_trace_block(hash.into(), opts).await?;
+                    Ok(BlockTraceResult { block: U256::from(number), hash, traces })
+                }
+                .boxed()
+            })
+            .buffered(concurrency)
+    }
+
     /// Trace the transaction according to the provided options.
     ///
     /// Ref: <https://geth.ethereum.org/docs/developers/evm-tracing/built-in-tracers>
@@ -821,7 +1523,8 @@ where
 }
 
 #[async_trait]
-impl<Eth, BlockExecutor> DebugApiServer for DebugApi<Eth, BlockExecutor>
+impl<Eth, BlockExecutor> DebugApiServer<RpcBlock<Eth::NetworkTypes>>
+    for DebugApi<Eth, BlockExecutor>
 where
     Eth: EthApiTypes + EthTransactions + TraceExt + 'static,
     BlockExecutor:
@@ -896,17 +1599,71 @@ where
     }
 
     /// Handler for `debug_getBadBlocks`
-    async fn bad_blocks(&self) -> RpcResult<Vec<RpcBlock>> {
-        Err(internal_rpc_err("unimplemented"))
+    async fn bad_blocks(&self) -> RpcResult<Vec<BadBlock<RpcBlock<Eth::NetworkTypes>>>> {
+        Self::debug_bad_blocks(self).map_err(Into::into)
     }
 
     /// Handler for `debug_traceChain`
     async fn debug_trace_chain(
         &self,
-        _start_exclusive: BlockNumberOrTag,
-        _end_inclusive: BlockNumberOrTag,
-    ) -> RpcResult<Vec<BlockTraceResult>> {
-        Err(internal_rpc_err("unimplemented"))
+        pending: PendingSubscriptionSink,
+        start_exclusive: BlockNumberOrTag,
+        end_inclusive: BlockNumberOrTag,
+        opts: Option<GethDebugTracingOptions>,
+    ) -> jsonrpsee::core::SubscriptionResult {
+        let provider = self.provider();
+        let range = provider
+            .convert_block_number(start_exclusive)
+            .and_then(|start| Ok((start, provider.convert_block_number(end_inclusive)?)));
+        let (start, end) = match range {
+            Ok((Some(start), Some(end))) if start < end => (start, end),
+            Ok((Some(_), Some(_))) => {
+                pending
+                    .reject(invalid_params_rpc_err("start block must be before end block"))
+                    .await;
+                return Ok(())
+            }
+            Ok(_) => {
+                pending.reject(internal_rpc_err("pending block not supported")).await;
+                return Ok(())
+            }
+            Err(err) => {
+                pending.reject(EthApiError::from(err)).await;
+                return Ok(())
+            }
+        };
+
+        // ensure that the range is not too large, every block is traced
+        if end - start > TRACE_CHAIN_MAX_BLOCKS {
+            pending
+                .reject(invalid_params_rpc_err(format!(
+                    "block range too large; currently limited to {TRACE_CHAIN_MAX_BLOCKS} blocks"
+                )))
+                .await;
+            return Ok(())
+        }
+
+        let sink = pending.accept().await?;
+        let mut traces = self.trace_chain(start, end, opts.unwrap_or_default());
+        loop {
+            tokio::select! {
+                _ = sink.closed() => break,
+                maybe_trace = traces.next() => {
+                    let Some(trace) = maybe_trace else { break };
+                    // a block that can't be traced closes the subscription with an error
+                    // notification
+                    let trace = trace.inspect_err(|err| {
+                        debug!(target: "rpc::debug", %err, "Failed to trace chain");
+                    })?;
+                    let msg = SubscriptionMessage::from_json(&trace)?;
+                    if sink.send(msg).await.is_err() {
+                        break
+                    }
+                }
+            }
+        }
+
+        Ok(())
     }
 
     /// Handler for `debug_traceBlock`
@@ -993,44 +1750,53 @@ where
         Ok(())
     }
 
+    /// Handler for `debug_accountRange`
     async fn debug_account_range(
         &self,
-        _block_number: BlockNumberOrTag,
-        _start: Bytes,
-        _max_results: u64,
-        _nocode: bool,
-        _nostorage: bool,
+        block_number: BlockNumberOrTag,
+        start: Bytes,
+        max_results: u64,
+        nocode: bool,
+        nostorage: bool,
         _incompletes: bool,
-    ) -> RpcResult<()> {
-        Ok(())
+    ) -> RpcResult<AccountRangeResult> {
+        Self::debug_account_range(self, block_number, start, max_results, nocode, nostorage)
+            .await
+            .map_err(Into::into)
     }
 
     async fn debug_block_profile(&self, _file: String, _seconds: u64) -> RpcResult<()> {
         Ok(())
     }
 
+    /// Handler for `debug_chaindbCompact`
     async fn debug_chaindb_compact(&self) -> RpcResult<()> {
-        Ok(())
+        Err(EthApiError::Unsupported("the database can not be compacted while the node is running")
+            .into())
     }
 
-    async fn debug_chaindb_property(&self, _property: String) -> RpcResult<()> {
-        Ok(())
+    /// Handler for `debug_chaindbProperty`
+    async fn debug_chaindb_property(&self, property: String) -> RpcResult<String> {
+        Self::debug_chaindb_property(self, property).await.map_err(Into::into)
     }
 
     async fn debug_cpu_profile(&self, _file: String, _seconds: u64) -> RpcResult<()> {
         Ok(())
     }
 
-    async fn debug_db_ancient(&self, _kind: String, _number: u64) -> RpcResult<()> {
-        Ok(())
+    /// Handler for `debug_dbAncient`
+    async fn debug_db_ancient(&self, kind: String, number: u64) -> RpcResult<Bytes> {
+        Self::debug_db_ancient(self, kind, number).await.map_err(Into::into)
     }
 
-    async fn debug_db_ancients(&self) -> RpcResult<()> {
-        Ok(())
+    /// Handler for `debug_dbAncients`
+    async fn debug_db_ancients(&self) -> RpcResult<u64> {
+        Self::debug_db_ancients(self).await.map_err(Into::into)
     }
 
-    async fn debug_db_get(&self, _key: String) -> RpcResult<()> {
-        Ok(())
+    /// Handler for `debug_dbGet`
+    async fn debug_db_get(&self, key: String) -> RpcResult<Bytes> {
+        Self::debug_db_get(self, key).await.map_err(Into::into)
     }
 
     async fn debug_dump_block(&self, _number: BlockId) -> RpcResult<()> {
@@ -1057,32 +1823,40 @@ where
         Ok(())
     }
 
+    /// Handler for `debug_getModifiedAccountsByHash`
     async fn debug_get_modified_accounts_by_hash(
         &self,
-        _start_hash: B256,
-        _end_hash: B256,
-    ) -> RpcResult<()> {
-        Ok(())
+        start_hash: B256,
+        end_hash: Option<B256>,
+    ) -> RpcResult<Vec<Address>> {
+        Self::debug_get_modified_accounts_by_hash(self, start_hash, end_hash)
+            .await
+            .map_err(Into::into)
     }
 
+    /// Handler for `debug_getModifiedAccountsByNumber`
     async fn debug_get_modified_accounts_by_number(
         &self,
-        _start_number: u64,
-        _end_number: u64,
-    ) -> RpcResult<()> {
-        Ok(())
+        start_number: u64,
+        end_number: Option<u64>,
+    ) -> RpcResult<Vec<Address>> {
+        Self::debug_get_modified_accounts_by_number(self, start_number, end_number)
+            .await
+            .map_err(Into::into)
     }
 
     async fn debug_go_trace(&self, _file: String, _seconds: u64) -> RpcResult<()> {
         Ok(())
     }
 
+    /// Handler for `debug_intermediateRoots`
     async fn debug_intermediate_roots(
         &self,
-        _block_hash: B256,
-        _opts: Option<GethDebugTracingCallOptions>,
-    ) -> RpcResult<()> {
-        Ok(())
+        block_hash: B256,
+        _opts: Option<GethDebugTracingOptions>,
+    ) -> RpcResult<Vec<B256>> {
+        let _permit = self.acquire_trace_permit().await;
+        Self::debug_intermediate_roots(self, block_hash).await.map_err(Into::into)
     }
 
     async fn debug_mem_stats(&self) -> RpcResult<()> {
@@ -1093,8 +1867,9 @@ where
         Ok(())
     }
 
-    async fn debug_preimage(&self, _hash: B256) -> RpcResult<()> {
-        Ok(())
+    /// Handler for `debug_preimage`
+    async fn debug_preimage(&self, _hash: B256) -> RpcResult<Bytes> {
+        Err(EthApiError::Unsupported("reth does not record preimages").into())
     }
 
     async fn debug_print_block(&self, _number: u64) -> RpcResult<()> {
@@ -1113,8 +1888,9 @@ where
         Ok(())
     }
 
-    async fn debug_set_head(&self, _number: u64) -> RpcResult<()> {
-        Ok(())
+    /// Handler for `debug_setHead`
+    async fn debug_set_head(&self, number: U64) -> RpcResult<()> {
+        Self::debug_set_head(self, number.to()).await.map_err(Into::into)
     }
 
     async fn debug_set_mutex_profile_fraction(&self, _rate: i32) -> RpcResult<()> {
@@ -1131,18 +1907,25 @@ where
 
     async fn debug_standard_trace_bad_block_to_file(
         &self,
-        _block: BlockNumberOrTag,
-        _opts: Option<GethDebugTracingCallOptions>,
-    ) -> RpcResult<()> {
-        Ok(())
+        block_hash: B256,
+        opts: Option<GethDebugTracingOptions>,
+    ) -> RpcResult<Vec<String>> {
+        let _permit = self.acquire_trace_permit().await;
+        Self::standard_trace_bad_block_to_file(self, block_hash, opts.unwrap_or_default())
+            .await
+            .map_err(Into::into)
     }
 
+    /// Handler for `debug_standardTraceBlockToFile`
     async fn debug_standard_trace_block_to_file(
         &self,
-        _block: BlockNumberOrTag,
-        _opts: Option<GethDebugTracingCallOptions>,
-    ) -> RpcResult<()> {
-        Ok(())
+        block_hash: B256,
+        opts: Option<GethDebugTracingOptions>,
+    ) -> RpcResult<Vec<String>> {
+        let _permit = self.acquire_trace_permit().await;
+        Self::debug_standard_trace_block_to_file(self, block_hash, opts.unwrap_or_default())
+            .await
+            .map_err(Into::into)
     }
 
     async fn debug_start_cpu_profile(&self, _file: String) -> RpcResult<()> {
@@ -1161,31 +1944,65 @@ where
         Ok(())
     }
 
+    /// Handler for `debug_storageRangeAt`
     async fn debug_storage_range_at(
         &self,
-        _block_hash: B256,
-        _tx_idx: usize,
-        _contract_address: Address,
-        _key_start: B256,
-        _max_result: u64,
-    ) -> RpcResult<()> {
-        Ok(())
+        block_hash: B256,
+        tx_idx: usize,
+        contract_address: Address,
+        key_start: B256,
+        max_result: u64,
+    ) -> RpcResult<StorageRangeResult> {
+        let _permit = self.acquire_trace_permit().await;
+        Self::debug_storage_range_at(
+            self,
+            block_hash,
+            tx_idx,
+            contract_address,
+            key_start,
+            max_result,
+        )
+        .await
+        .map_err(Into::into)
     }
 
     async fn debug_trace_bad_block(
         &self,
-        _block_hash: B256,
-        _opts: Option<GethDebugTracingCallOptions>,
-    ) -> RpcResult<()> {
-        Ok(())
-    }
-
-    async fn debug_verbosity(&self, _level: usize) -> RpcResult<()> {
-        Ok(())
+        block_hash: B256,
+        opts: Option<GethDebugTracingOptions>,
+    ) -> RpcResult<Vec<TraceResult>> {
+        let _permit = self.acquire_trace_permit().await;
+        Self::debug_trace_bad_block(self, block_hash, opts.unwrap_or_default())
+            .await
+            .map_err(Into::into)
     }
 
-    async fn debug_vmodule(&self, _pattern: String) -> RpcResult<()> {
-        Ok(())
+    /// Handler for `debug_verbosity`
+    async fn debug_verbosity(&self, level: usize) -> RpcResult<()> {
+        let handle = reth_tracing::log_filter_handle()
+            .ok_or(EthApiError::Unsupported("the log filters can not be changed"))?;
+        handle
+            .set_verbosity(geth_level_filter(level))
+            .map_err(|err| internal_rpc_err(err.to_string()))
+    }
+
+    /// Handler for `debug_vmodule`
+    async fn debug_vmodule(&self, pattern: String) -> RpcResult<()> {
+        let handle = reth_tracing::log_filter_handle()
+            .ok_or(EthApiError::Unsupported("the log filters can not be changed"))?;
+        let directives = pattern
+            .split(',')
+            .filter(|directive| !directive.is_empty())
+            .map(|directive| match directive.split_once('=') {
+                Some((target, level)) => match level.parse::<usize>() {
+                    Ok(level) => format!("{target}={}", geth_level_filter(level)),
+                    Err(_) => directive.to_string(),
+                },
+                None => directive.to_string(),
+            })
+            .collect::<Vec<_>>()
+            .join(",");
+        handle.set_directives(&directives).map_err(|err| invalid_params_rpc_err(err.to_string()))
     }
 
     async fn debug_write_block_profile(&self, _file: String) -> RpcResult<()> {
@@ -1201,6 +2018,18 @@ where
     }
 }
 
+/// Converts a geth log level to a [`LevelFilter`].
+const fn geth_level_filter(level: usize) -> LevelFilter {
+    match level {
+        0 => LevelFilter::OFF,
+        1 => LevelFilter::ERROR,
+        2 => LevelFilter::WARN,
+        3 => LevelFilter::INFO,
+        4 => LevelFilter::DEBUG,
+        _ => LevelFilter::TRACE,
+    }
+}
+
 impl<Eth, BlockExecutor> std::fmt::Debug for DebugApi<Eth, BlockExecutor> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("DebugApi").finish_non_exhaustive()
@@ -1209,7 +2038,12 @@ impl<Eth, BlockExecutor> std::fmt::Debug for DebugApi<Eth, BlockExecutor> {
 
 impl<Eth, BlockExecutor> Clone for DebugApi<Eth, BlockExecutor> {
     fn clone(&self) -> Self {
-        Self { inner: Arc::clone(&self.inner) }
+        Self {
+            inner: Arc::clone(&self.inner),
+            bad_blocks: self.bad_blocks.clone(),
+            chain_db: self.chain_db.clone(),
+            head_setter: self.head_setter.clone(),
+        }
     }
 }
 
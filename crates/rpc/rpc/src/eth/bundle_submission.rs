@@ -0,0 +1,200 @@
+//! `Eth` bundle submission implementation.
+
+use alloy_primitives::{Bytes, B256};
+use alloy_rpc_types_mev::{
+    BundleItem, CancelBundleRequest, EthBundleHash, EthSendBundle, SendBundleRequest,
+    SendBundleResponse,
+};
+use jsonrpsee::core::RpcResult;
+use reth_bundle_pool::{Bundle, BundlePool, BundleStatus};
+use reth_primitives::{PooledTransaction, RecoveredTx, TransactionSigned};
+use reth_provider::BlockNumReader;
+use reth_rpc_api::MevSendBundleApiServer;
+use reth_rpc_eth_api::{helpers::EthTransactions, EthSendBundleApiServer, FromEthApiError};
+use reth_rpc_eth_types::{utils::recover_raw_transaction, EthApiError};
+
+/// Maximum depth of the nested bundles of `mev_sendBundle`.
+const MAX_NESTED_BUNDLE_DEPTH: usize = 5;
+
+/// `Eth` bundle submission implementation.
+///
+/// The bundles of `eth_sendBundle` and `mev_sendBundle` are added to the [`BundlePool`], which
+/// provides them to the local payload builder. The API is installed by the rpc registry once the
+/// pool is set with `RpcRegistryInner::set_bundle_pool`.
+#[derive(Debug, Clone)]
+pub struct EthBundleSubmission<Eth> {
+    /// Access to commonly used code of the `eth` namespace
+    eth_api: Eth,
+    /// The pool of the submitted bundles
+    pool: BundlePool,
+}
+
+impl<Eth> EthBundleSubmission<Eth> {
+    /// Create a new `EthBundleSubmission` instance.
+    pub const fn new(eth_api: Eth, pool: BundlePool) -> Self {
+        Self { eth_api, pool }
+    }
+
+    /// Access the underlying `Eth` API.
+    pub const fn eth_api(&self) -> &Eth {
+        &self.eth_api
+    }
+
+    /// Access the underlying bundle pool.
+    pub const fn pool(&self) -> &BundlePool {
+        &self.pool
+    }
+}
+
+impl<Eth> EthBundleSubmission<Eth>
+where
+    Eth: EthTransactions + 'static,
+{
+    /// Adds the bundle of `eth_sendBundle` to the pool.
+    ///
+    /// The block number of the bundle must be higher than the latest block.
+    pub async fn send_bundle(&self, bundle: EthSendBundle) -> Result<EthBundleHash, Eth::Error> {
+        let EthSendBundle {
+            txs,
+            block_number,
+            min_timestamp,
+            max_timestamp,
+            reverting_tx_hashes,
+            replacement_uuid,
+            ..
+        } = bundle;
+        self.ensure_future_block(block_number)?;
+
+        let transactions =
+            txs.iter().map(|tx| self.recover_transaction(tx)).collect::<Result<Vec<_>, _>>()?;
+        let bundle = Bundle::new(transactions, block_number)
+            .with_timestamps(min_timestamp, max_timestamp)
+            .with_reverting_tx_hashes(reverting_tx_hashes)
+            .with_replacement_uuid(replacement_uuid);
+
+        let bundle_hash = self.add_bundle(bundle)?;
+        Ok(EthBundleHash { bundle_hash })
+    }
+
+    /// Adds the bundle of `mev_sendBundle` to the pool.
+    ///
+    /// Nested bundles are flattened into a single bundle with the inclusion of the request, and
+    /// the transactions that can revert are allowed to revert. Bundle items that reference other
+    /// bundles by hash and refunds are not supported.
+    pub async fn mev_send_bundle(
+        &self,
+        request: SendBundleRequest,
+    ) -> Result<SendBundleResponse, Eth::Error> {
+        let block_number = request.inclusion.block_number();
+        let max_block_number = request.inclusion.max_block_number().unwrap_or(block_number);
+        self.ensure_future_block(max_block_number)?;
+
+        let mut transactions = Vec::new();
+        let mut reverting_tx_hashes = Vec::new();
+        self.flatten_bundle(&request, 1, &mut transactions, &mut reverting_tx_hashes)?;
+        let bundle = Bundle::new(transactions, block_number)
+            .with_max_block_number(max_block_number)
+            .with_reverting_tx_hashes(reverting_tx_hashes);
+
+        let bundle_hash = self.add_bundle(bundle)?;
+        Ok(SendBundleResponse { bundle_hash })
+    }
+
+    /// Appends the transactions of the bundle and its nested bundles.
+    fn flatten_bundle(
+        &self,
+        request: &SendBundleRequest,
+        depth: usize,
+        transactions: &mut Vec<RecoveredTx>,
+        reverting_tx_hashes: &mut Vec<B256>,
+    ) -> Result<(), Eth::Error> {
+        if depth > MAX_NESTED_BUNDLE_DEPTH {
+            return Err(invalid_params("max bundle depth exceeded"))
+        }
+        if request
+            .validity
+            .as_ref()
+            .is_some_and(|validity| validity.refund.is_some() || validity.refund_config.is_some())
+        {
+            return Err(invalid_params("bundle refunds are not supported"))
+        }
+
+        for item in &request.bundle_body {
+            match item {
+                BundleItem::Tx { tx, can_revert } => {
+                    let tx = self.recover_transaction(tx)?;
+                    if *can_revert {
+                        reverting_tx_hashes.push(tx.hash());
+                    }
+                    transactions.push(tx);
+                }
+                BundleItem::Bundle { bundle } => {
+                    self.flatten_bundle(bundle, depth + 1, transactions, reverting_tx_hashes)?
+                }
+                BundleItem::Hash { hash: _ } => {
+                    return Err(invalid_params("bundle items by hash are not supported"))
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Decodes and recovers a raw transaction of a bundle.
+    fn recover_transaction(&self, tx: &Bytes) -> Result<RecoveredTx, Eth::Error> {
+        let tx = recover_raw_transaction::<PooledTransaction>(tx)?;
+        Ok(tx.map_transaction(TransactionSigned::from))
+    }
+
+    /// Ensures that a bundle for the given block can still be included.
+    fn ensure_future_block(&self, block_number: u64) -> Result<(), Eth::Error> {
+        let latest =
+            self.eth_api.provider().best_block_number().map_err(Eth::Error::from_eth_err)?;
+        if block_number <= latest {
+            return Err(invalid_params(format!(
+                "bundle block {block_number} is not higher than the latest block {latest}"
+            )))
+        }
+        Ok(())
+    }
+
+    /// Adds the bundle to the pool.
+    fn add_bundle(&self, bundle: Bundle) -> Result<B256, Eth::Error> {
+        self.pool.add_bundle(bundle).map_err(|err| invalid_params(err.to_string()))
+    }
+}
+
+#[async_trait::async_trait]
+impl<Eth> EthSendBundleApiServer for EthBundleSubmission<Eth>
+where
+    Eth: EthTransactions + 'static,
+{
+    async fn send_bundle(&self, bundle: EthSendBundle) -> RpcResult<EthBundleHash> {
+        Self::send_bundle(self, bundle).await.map_err(Into::into)
+    }
+
+    async fn cancel_bundle(&self, request: CancelBundleRequest) -> RpcResult<()> {
+        // the bundle hash of the request is the replacement UUID of the bundle
+        self.pool.cancel_bundle(&request.bundle_hash);
+        Ok(())
+    }
+
+    async fn get_bundle_status(&self, bundle_hash: B256) -> RpcResult<Option<BundleStatus>> {
+        Ok(self.pool.status(&bundle_hash))
+    }
+}
+
+#[async_trait::async_trait]
+impl<Eth> MevSendBundleApiServer for EthBundleSubmission<Eth>
+where
+    Eth: EthTransactions + 'static,
+{
+    async fn send_bundle(&self, request: SendBundleRequest) -> RpcResult<SendBundleResponse> {
+        Self::mev_send_bundle(self, request).await.map_err(Into::into)
+    }
+}
+
+/// Converts the message into an invalid params error.
+fn invalid_params<E: FromEthApiError>(message: impl Into<String>) -> E {
+    E::from_eth_err(EthApiError::InvalidParams(message.into()))
+}
@@ -458,14 +458,19 @@ mod tests {
     use alloy_primitives::{PrimitiveSignature as Signature, B256, U64};
     use alloy_rpc_types::FeeHistory;
     use jsonrpsee_types::error::INVALID_PARAMS_CODE;
-    use reth_chainspec::{BaseFeeParams, ChainSpec};
+    use reth_chainspec::{BaseFeeParams, ChainSpec, MAINNET};
+    use reth_db::test_utils::{create_test_rw_db, create_test_static_files_dir};
     use reth_evm_ethereum::EthEvmConfig;
     use reth_network_api::noop::NoopNetwork;
-    use reth_primitives::{Block, BlockBody, TransactionSigned};
+    use reth_primitives::{Block, BlockBody, StaticFileSegment, TransactionSigned};
     use reth_provider::{
-        test_utils::{MockEthProvider, NoopProvider},
-        BlockReader, BlockReaderIdExt, ChainSpecProvider, EvmEnvProvider, StateProviderFactory,
+        providers::{BlockchainProvider2, StaticFileProvider, StaticFileWriter},
+        test_utils::{MockEthProvider, MockNodeTypesWithDB, NoopProvider},
+        BlockReader, BlockReaderIdExt, BlockWriter, ChainSpecProvider, DatabaseProviderFactory,
+        EvmEnvProvider, ProviderFactory, StateProviderFactory, StaticFileProviderFactory,
+        StorageLocation,
     };
+    use reth_prune::{PruneMode, PruneModes, PrunerBuilder, MINIMUM_PRUNING_DISTANCE};
     use reth_rpc_eth_api::EthApiServer;
     use reth_rpc_eth_types::{
         EthStateCache, FeeHistoryCache, FeeHistoryCacheConfig, GasCap, GasPriceOracle,
@@ -474,7 +479,10 @@ mod tests {
         DEFAULT_ETH_PROOF_WINDOW, DEFAULT_MAX_SIMULATE_BLOCKS, DEFAULT_PROOF_PERMITS,
     };
     use reth_tasks::pool::BlockingTaskPool;
-    use reth_testing_utils::{generators, generators::Rng};
+    use reth_testing_utils::{
+        generators,
+        generators::{random_block_range, BlockRangeParams, Rng},
+    };
     use reth_transaction_pool::test_utils::{testing_pool, TestPool};
 
     fn build_test_eth_api<
@@ -754,4 +762,79 @@ mod tests {
             "all: no percentiles were requested, so there should be no rewards result"
         );
     }
+
+    /// The blocks and transactions whose bodies were expired are reported as missing, while their
+    /// headers are still served.
+    #[tokio::test]
+    async fn test_expired_bodies() {
+        let (_static_dir, static_dir_path) = create_test_static_files_dir();
+        let factory = ProviderFactory::<MockNodeTypesWithDB>::new(
+            create_test_rw_db(),
+            MAINNET.clone(),
+            StaticFileProvider::read_write(static_dir_path)
+                .unwrap()
+                .with_custom_blocks_per_file(10),
+        );
+        let static_file_provider = factory.static_file_provider();
+
+        let mut rng = generators::rng();
+        let blocks = random_block_range(
+            &mut rng,
+            0..=29,
+            BlockRangeParams { parent: Some(B256::ZERO), tx_count: 1..3, ..Default::default() },
+        );
+        {
+            let provider_rw = factory.database_provider_rw().unwrap();
+            let mut tx_num = 0;
+            for block in &blocks {
+                provider_rw
+                    .insert_block(
+                        block.clone().seal_with_senders().unwrap(),
+                        StorageLocation::StaticFiles,
+                    )
+                    .unwrap();
+
+                let mut receipts_writer =
+                    static_file_provider.latest_writer(StaticFileSegment::Receipts).unwrap();
+                receipts_writer.increment_block(block.number).unwrap();
+                for _ in &block.body.transactions {
+                    receipts_writer.append_receipt(tx_num, &Default::default()).unwrap();
+                    tx_num += 1;
+                }
+            }
+            static_file_provider.commit().unwrap();
+            provider_rw.commit().unwrap();
+        }
+
+        // The tip only determines the target block of the segment, which has to be at the
+        // minimum pruning distance.
+        let mut pruner = PrunerBuilder::default()
+            .segments(PruneModes {
+                bodies_history: Some(PruneMode::Before(25)),
+                ..PruneModes::none()
+            })
+            .build_with_provider_factory(factory.clone());
+        pruner.run(25 + MINIMUM_PRUNING_DISTANCE).unwrap();
+
+        let provider =
+            BlockchainProvider2::with_latest(factory, blocks[29].header.clone()).unwrap();
+        let eth_api = build_test_eth_api(provider);
+
+        // The static file of blocks 0..=9 was deleted.
+        let expired = &blocks[5];
+        let number = BlockNumberOrTag::Number(expired.number);
+        assert!(eth_api.block_by_number(number, false).await.unwrap().is_none());
+        assert!(eth_api.block_receipts(number.into()).await.unwrap().is_none());
+        let hash = expired.body.transactions[0].hash();
+        assert!(eth_api.transaction_by_hash(hash).await.unwrap().is_none());
+        assert_eq!(eth_api.header_by_number(number).await.unwrap().unwrap().hash, expired.hash());
+
+        // The static file of blocks 20..=29 still contains blocks that are retained.
+        let retained = &blocks[22];
+        let number = BlockNumberOrTag::Number(retained.number);
+        assert!(eth_api.block_by_number(number, false).await.unwrap().is_some());
+        assert!(eth_api.block_receipts(number.into()).await.unwrap().is_some());
+        let hash = retained.body.transactions[0].hash();
+        assert!(eth_api.transaction_by_hash(hash).await.unwrap().is_some());
+    }
 }
@@ -1,17 +1,23 @@
 //! Sever implementation of `eth` namespace API.
 
 pub mod bundle;
+pub mod bundle_submission;
 pub mod core;
 pub mod filter;
 pub mod helpers;
+pub mod private;
 pub mod pubsub;
 pub mod sim_bundle;
+pub mod user_operation;
 
 /// Implementation of `eth` namespace API.
 pub use bundle::EthBundle;
+pub use bundle_submission::EthBundleSubmission;
 pub use core::EthApi;
 pub use filter::EthFilter;
+pub use private::EthPrivateTransaction;
 pub use pubsub::EthPubSub;
+pub use user_operation::EthUserOperation;
 
 pub use helpers::{
     signer::DevSigner,
@@ -0,0 +1,76 @@
+//! `Eth` private transaction implementation.
+
+use alloy_primitives::{Bytes, B256};
+use alloy_rpc_types_mev::PrivateTransactionRequest;
+use jsonrpsee::core::RpcResult;
+use reth_provider::BlockNumReader;
+use reth_rpc_eth_api::{helpers::EthTransactions, EthPrivateTransactionApiServer, FromEthApiError};
+use reth_rpc_eth_types::EthApiError;
+
+/// `Eth` private transaction implementation.
+///
+/// Private transactions are added to the local transaction pool, which never propagates them to
+/// the network, so they can only be included by the local payload builder.
+#[derive(Debug, Clone)]
+pub struct EthPrivateTransaction<Eth> {
+    /// Access to commonly used code of the `eth` namespace
+    eth_api: Eth,
+}
+
+impl<Eth> EthPrivateTransaction<Eth> {
+    /// Create a new `EthPrivateTransaction` instance.
+    pub const fn new(eth_api: Eth) -> Self {
+        Self { eth_api }
+    }
+
+    /// Access the underlying `Eth` API.
+    pub const fn eth_api(&self) -> &Eth {
+        &self.eth_api
+    }
+}
+
+impl<Eth> EthPrivateTransaction<Eth>
+where
+    Eth: EthTransactions + 'static,
+{
+    /// Submits the private transaction of the request to the pool.
+    ///
+    /// The max block number must be higher than the latest block. The preferences of the request
+    /// only apply to relays and are ignored.
+    pub async fn send_private_transaction(
+        &self,
+        request: PrivateTransactionRequest,
+    ) -> Result<B256, Eth::Error> {
+        let PrivateTransactionRequest { tx, max_block_number, preferences: _ } = request;
+
+        if let Some(max_block_number) = max_block_number {
+            let latest =
+                self.eth_api.provider().best_block_number().map_err(Eth::Error::from_eth_err)?;
+            if max_block_number <= latest {
+                return Err(Eth::Error::from_eth_err(EthApiError::InvalidParams(format!(
+                    "maxBlockNumber {max_block_number} is not higher than the latest block \
+                     {latest}"
+                ))))
+            }
+        }
+
+        self.eth_api.send_private_raw_transaction(tx, max_block_number).await
+    }
+}
+
+#[async_trait::async_trait]
+impl<Eth> EthPrivateTransactionApiServer for EthPrivateTransaction<Eth>
+where
+    Eth: EthTransactions + 'static,
+{
+    async fn send_private_transaction(
+        &self,
+        request: PrivateTransactionRequest,
+    ) -> RpcResult<B256> {
+        Self::send_private_transaction(self, request).await.map_err(Into::into)
+    }
+
+    async fn send_private_raw_transaction(&self, bytes: Bytes) -> RpcResult<B256> {
+        self.eth_api.send_private_raw_transaction(bytes, None).await.map_err(Into::into)
+    }
+}
@@ -0,0 +1,369 @@
+//! `Eth` ERC-4337 user operation implementation.
+
+use alloy_eips::BlockId;
+use alloy_network::Network;
+use alloy_primitives::{Address, Bytes, B256, U256};
+use alloy_rpc_types_eth::{
+    erc4337::{PackedUserOperation, UserOperationGasEstimation, UserOperationReceipt},
+    state::EvmOverrides,
+    TransactionReceipt, TransactionRequest,
+};
+use alloy_sol_types::{SolCall, SolInterface};
+use jsonrpsee::{core::RpcResult, types::ErrorObject};
+use reth_rpc_api::EthUserOperationApiServer;
+use reth_rpc_eth_api::{
+    helpers::{EthCall, EthTransactions, LoadReceipt},
+    RpcReceipt,
+};
+use reth_user_operation_pool::{
+    entry_point::IEntryPoint, pre_verification_gas, validate_user_operation, UserOperation,
+    UserOperationError, UserOperationPool, ValidationInspector, ValidationPhase,
+};
+use revm_primitives::ExecutionResult;
+
+/// The prefixes of the `FailedOp` reasons of the entry point for invalid signatures, which are
+/// expected when the gas of a user operation with a dummy signature is estimated.
+const SIGNATURE_ERROR_PREFIXES: [&str; 2] = ["AA24", "AA34"];
+
+/// `Eth` ERC-4337 user operation implementation.
+///
+/// The user operations are validated by simulating `handleOps` with the [`ValidationInspector`],
+/// and added to the [`UserOperationPool`]. The node builder installs the API in the `eth` namespace
+/// if the node is started with `--txpool.user-operations`.
+#[derive(Debug, Clone)]
+pub struct EthUserOperation<Eth> {
+    /// Access to commonly used code of the `eth` namespace
+    eth_api: Eth,
+    /// The pool of the user operations
+    pool: UserOperationPool,
+}
+
+impl<Eth> EthUserOperation<Eth> {
+    /// Create a new `EthUserOperation` instance.
+    pub const fn new(eth_api: Eth, pool: UserOperationPool) -> Self {
+        Self { eth_api, pool }
+    }
+
+    /// Access the underlying `Eth` API.
+    pub const fn eth_api(&self) -> &Eth {
+        &self.eth_api
+    }
+
+    /// Access the underlying user operation pool.
+    pub const fn pool(&self) -> &UserOperationPool {
+        &self.pool
+    }
+
+    /// Creates the [`UserOperation`] for the entry point, which must be supported by the pool.
+    fn user_operation(
+        &self,
+        user_operation: PackedUserOperation,
+        entry_point: Address,
+    ) -> Result<UserOperation, UserOperationError> {
+        let config = self.pool.config();
+        if entry_point != config.entry_point {
+            return Err(UserOperationError::UnsupportedEntryPoint(entry_point))
+        }
+        Ok(UserOperation::new(user_operation, entry_point, config.chain_id))
+    }
+}
+
+impl<Eth> EthUserOperation<Eth>
+where
+    Eth: EthCall + EthTransactions + LoadReceipt + 'static,
+    Eth::NetworkTypes: Network<ReceiptResponse = TransactionReceipt>,
+{
+    /// Validates the user operation and adds it to the pool.
+    pub async fn send_user_operation(
+        &self,
+        user_operation: PackedUserOperation,
+        entry_point: Address,
+    ) -> RpcResult<B256> {
+        let op = self.user_operation(user_operation, entry_point).map_err(user_operation_error)?;
+        validate_user_operation(&op, self.pool.config()).map_err(user_operation_error)?;
+        self.pool.check_reputation(&op).map_err(user_operation_error)?;
+
+        let (result, mut inspector) = self.simulate(op.clone()).await?;
+        if let Some(violation) = inspector.take_violation() {
+            return Err(user_operation_error(violation))
+        }
+        if let ExecutionResult::Revert { output, .. } = result {
+            return Err(user_operation_error(simulation_error(&output)))
+        }
+
+        self.pool.add_operation(op).map_err(user_operation_error)
+    }
+
+    /// Estimates the gas limits of the user operation.
+    ///
+    /// The verification gas is measured by simulating `handleOps` with the maximum verification
+    /// gas limits and without fees, while the call gas limit is estimated with a call from the
+    /// entry point to the sender.
+    pub async fn estimate_user_operation_gas(
+        &self,
+        mut user_operation: PackedUserOperation,
+        entry_point: Address,
+    ) -> RpcResult<UserOperationGasEstimation> {
+        let max_verification_gas = U256::from(self.pool.config().max_verification_gas);
+        user_operation.verification_gas_limit = max_verification_gas;
+        if user_operation.paymaster.is_some() {
+            user_operation.paymaster_verification_gas_limit = Some(max_verification_gas);
+            user_operation.paymaster_post_op_gas_limit.get_or_insert(U256::ZERO);
+        }
+        user_operation.call_gas_limit = U256::ZERO;
+        user_operation.max_fee_per_gas = U256::ZERO;
+        user_operation.max_priority_fee_per_gas = U256::ZERO;
+        let op = self.user_operation(user_operation, entry_point).map_err(user_operation_error)?;
+
+        let (result, mut inspector) = self.simulate(op.clone()).await?;
+        if let Some(violation) = inspector.take_violation() {
+            return Err(user_operation_error(violation))
+        }
+        if let ExecutionResult::Revert { output, .. } = result {
+            let err = simulation_error(&output);
+            let is_signature_error = matches!(
+                &err,
+                UserOperationError::SimulationFailed(reason)
+                    if SIGNATURE_ERROR_PREFIXES.iter().any(|prefix| reason.starts_with(prefix))
+            );
+            if !is_signature_error {
+                return Err(user_operation_error(err))
+            }
+        }
+
+        let call_request = TransactionRequest::default()
+            .from(entry_point)
+            .to(op.sender())
+            .input(op.inner().call_data.clone().into());
+        let call_gas_limit =
+            EthCall::estimate_gas_at(&self.eth_api, call_request, BlockId::latest(), None)
+                .await
+                .map_err(Into::into)?;
+
+        Ok(UserOperationGasEstimation {
+            pre_verification_gas: U256::from(pre_verification_gas(&op)),
+            verification_gas: U256::from(
+                inspector.gas_used(ValidationPhase::Factory) +
+                    inspector.gas_used(ValidationPhase::Account),
+            ),
+            paymaster_verification_gas: U256::from(inspector.gas_used(ValidationPhase::Paymaster)),
+            call_gas_limit,
+        })
+    }
+
+    /// Returns the receipt of the included user operation.
+    pub async fn get_user_operation_receipt(
+        &self,
+        user_operation_hash: B256,
+    ) -> RpcResult<Option<UserOperationReceipt>> {
+        let Some(included) = self.pool.included(&user_operation_hash) else { return Ok(None) };
+        let Some(receipt): Option<RpcReceipt<Eth::NetworkTypes>> = self
+            .eth_api
+            .transaction_receipt(included.transaction_hash)
+            .await
+            .map_err(Into::into)?
+        else {
+            return Ok(None)
+        };
+
+        Ok(Some(UserOperationReceipt {
+            user_op_hash: Bytes::copy_from_slice(user_operation_hash.as_slice()),
+            entry_point: self.pool.config().entry_point,
+            sender: included.sender,
+            nonce: included.nonce,
+            paymaster: included.paymaster,
+            actual_gas_cost: included.actual_gas_cost,
+            actual_gas_used: included.actual_gas_used,
+            success: included.success,
+            reason: included.reason,
+            logs: receipt.inner.logs().get(included.logs).unwrap_or_default().to_vec(),
+            receipt,
+        }))
+    }
+
+    /// Simulates `handleOps` for the user operation on top of the latest block.
+    async fn simulate(
+        &self,
+        op: UserOperation,
+    ) -> RpcResult<(ExecutionResult, ValidationInspector)> {
+        let entry_point = self.pool.config().entry_point;
+        let request = TransactionRequest::default().to(entry_point).input(
+            Bytes::from(
+                IEntryPoint::handleOpsCall { ops: vec![op.pack()], beneficiary: Address::ZERO }
+                    .abi_encode(),
+            )
+            .into(),
+        );
+
+        let this = self.eth_api.clone();
+        self.eth_api
+            .spawn_with_call_at(
+                request,
+                BlockId::latest(),
+                EvmOverrides::default(),
+                move |db, env| {
+                    // wrapper is hack to get around 'higher-ranked lifetime error', see
+                    // <https://github.com/rust-lang/rust/issues/100013>
+                    let db = db.0;
+
+                    let mut inspector = ValidationInspector::new(entry_point, &op);
+                    let (res, _) = this.transact_with_inspector(&mut *db, env, &mut inspector)?;
+                    Ok((res.result, inspector))
+                },
+            )
+            .await
+            .map_err(Into::into)
+    }
+}
+
+#[async_trait::async_trait]
+impl<Eth> EthUserOperationApiServer for EthUserOperation<Eth>
+where
+    Eth: EthCall + EthTransactions + LoadReceipt + 'static,
+    Eth::NetworkTypes: Network<ReceiptResponse = TransactionReceipt>,
+{
+    async fn send_user_operation(
+        &self,
+        user_operation: PackedUserOperation,
+        entry_point: Address,
+    ) -> RpcResult<B256> {
+        Self::send_user_operation(self, user_operation, entry_point).await
+    }
+
+    async fn estimate_user_operation_gas(
+        &self,
+        user_operation: PackedUserOperation,
+        entry_point: Address,
+    ) -> RpcResult<UserOperationGasEstimation> {
+        Self::estimate_user_operation_gas(self, user_operation, entry_point).await
+    }
+
+    async fn get_user_operation_receipt(
+        &self,
+        user_operation_hash: B256,
+    ) -> RpcResult<Option<UserOperationReceipt>> {
+        Self::get_user_operation_receipt(self, user_operation_hash).await
+    }
+
+    async fn supported_entry_points(&self) -> RpcResult<Vec<Address>> {
+        Ok(vec![self.pool.config().entry_point])
+    }
+}
+
+/// Converts the revert output of the `handleOps` simulation into an error.
+fn simulation_error(output: &Bytes) -> UserOperationError {
+    let reason = match IEntryPoint::IEntryPointErrors::abi_decode(output, true) {
+        Ok(IEntryPoint::IEntryPointErrors::FailedOp(err)) => err.reason,
+        Ok(IEntryPoint::IEntryPointErrors::FailedOpWithRevert(err)) => {
+            format!("{} {}", err.reason, err.inner)
+        }
+        Err(_) => format!("execution reverted: {output}"),
+    };
+    UserOperationError::SimulationFailed(reason)
+}
+
+/// Converts the error into an rpc error with the ERC-4337 error code.
+fn user_operation_error(err: UserOperationError) -> ErrorObject<'static> {
+    ErrorObject::owned(err.error_code(), err.to_string(), None::<()>)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::EthApi;
+    use reth_chainspec::MAINNET;
+    use reth_evm_ethereum::EthEvmConfig;
+    use reth_network_api::noop::NoopNetwork;
+    use reth_provider::test_utils::MockEthProvider;
+    use reth_rpc_eth_types::{
+        EthStateCache, FeeHistoryCache, FeeHistoryCacheConfig, GasCap, GasPriceOracle,
+    };
+    use reth_rpc_server_types::constants::{
+        DEFAULT_ETH_PROOF_WINDOW, DEFAULT_MAX_SIMULATE_BLOCKS, DEFAULT_PROOF_PERMITS,
+    };
+    use reth_tasks::pool::BlockingTaskPool;
+    use reth_transaction_pool::test_utils::{testing_pool, TestPool};
+    use reth_user_operation_pool::{entry_point::ENTRY_POINT_V07_ADDRESS, UserOperationPoolConfig};
+
+    type TestEthApi = EthApi<MockEthProvider, TestPool, NoopNetwork, EthEvmConfig>;
+
+    fn user_operation_api() -> EthUserOperation<TestEthApi> {
+        let provider = MockEthProvider::default();
+        let cache = EthStateCache::spawn(provider.clone(), Default::default());
+        let eth_api = EthApi::new(
+            provider.clone(),
+            testing_pool(),
+            NoopNetwork::default(),
+            cache.clone(),
+            GasPriceOracle::new(provider, Default::default(), cache),
+            GasCap::default(),
+            DEFAULT_MAX_SIMULATE_BLOCKS,
+            DEFAULT_ETH_PROOF_WINDOW,
+            BlockingTaskPool::build().expect("failed to build tracing pool"),
+            FeeHistoryCache::new(FeeHistoryCacheConfig::default()),
+            EthEvmConfig::new(MAINNET.clone()),
+            DEFAULT_PROOF_PERMITS,
+        );
+        EthUserOperation::new(eth_api, UserOperationPool::new(UserOperationPoolConfig::new(1)))
+    }
+
+    fn user_operation() -> PackedUserOperation {
+        PackedUserOperation {
+            sender: Address::random(),
+            nonce: U256::ZERO,
+            factory: None,
+            factory_data: None,
+            call_data: Bytes::new(),
+            call_gas_limit: U256::from(100_000),
+            verification_gas_limit: U256::from(100_000),
+            pre_verification_gas: U256::from(100_000),
+            max_fee_per_gas: U256::from(10),
+            max_priority_fee_per_gas: U256::from(1),
+            paymaster: None,
+            paymaster_verification_gas_limit: None,
+            paymaster_post_op_gas_limit: None,
+            paymaster_data: None,
+            signature: Bytes::new(),
+        }
+    }
+
+    #[tokio::test]
+    async fn rpc_methods() {
+        let api = user_operation_api();
+        let pool = api.pool().clone();
+        let module = api.into_rpc();
+
+        let entry_points: Vec<Address> =
+            module.call("eth_supportedEntryPoints", jsonrpsee::rpc_params![]).await.unwrap();
+        assert_eq!(entry_points, vec![ENTRY_POINT_V07_ADDRESS]);
+
+        let receipt: Option<UserOperationReceipt> = module
+            .call("eth_getUserOperationReceipt", jsonrpsee::rpc_params![B256::random()])
+            .await
+            .unwrap();
+        assert!(receipt.is_none());
+
+        // user operations of other entry points are rejected
+        let err = module
+            .call::<_, B256>(
+                "eth_sendUserOperation",
+                jsonrpsee::rpc_params![user_operation(), Address::random()],
+            )
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("unsupported entry point"), "{err}");
+
+        // the static checks run before the simulation
+        let mut op = user_operation();
+        op.verification_gas_limit = U256::from(pool.config().max_verification_gas + 1);
+        let err = module
+            .call::<_, B256>(
+                "eth_sendUserOperation",
+                jsonrpsee::rpc_params![op, ENTRY_POINT_V07_ADDRESS],
+            )
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("verification gas limit"), "{err}");
+        assert!(pool.is_empty());
+    }
+}
@@ -33,9 +33,12 @@ use pin_project as _;
 use tower as _;
 
 mod admin;
+mod anvil;
+mod chain_db;
 mod debug;
 mod engine;
 pub mod eth;
+mod hardhat;
 mod miner;
 mod net;
 mod otterscan;
@@ -47,9 +50,17 @@ mod validation;
 mod web3;
 
 pub use admin::AdminApi;
-pub use debug::DebugApi;
+pub use anvil::{AnvilApi, SerializableState};
+pub use chain_db::{
+    AccountPage, ChainDb, StoragePage, MAX_CHAIN_DB_BLOCK_RANGE, MAX_CHAIN_DB_RESULTS,
+};
+pub use debug::{DebugApi, SetHead};
 pub use engine::{EngineApi, EngineEthApi};
-pub use eth::{EthApi, EthBundle, EthFilter, EthPubSub};
+pub use eth::{
+    EthApi, EthBundle, EthBundleSubmission, EthFilter, EthPrivateTransaction, EthPubSub,
+    EthUserOperation,
+};
+pub use hardhat::HardhatApi;
 pub use miner::MinerApi;
 pub use net::NetApi;
 pub use otterscan::OtterscanApi;
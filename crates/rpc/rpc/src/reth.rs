@@ -1,14 +1,16 @@
 use std::{collections::HashMap, future::Future, sync::Arc};
 
-use alloy_eips::BlockId;
-use alloy_primitives::{Address, U256};
+use alloy_consensus::BlockHeader;
+use alloy_eips::{BlockId, BlockNumHash};
+use alloy_primitives::{Address, B256, U256};
 use async_trait::async_trait;
 use jsonrpsee::core::RpcResult;
-use reth_errors::RethResult;
+use reth_errors::{RethError, RethResult};
 use reth_provider::{BlockReaderIdExt, ChangeSetReader, StateProviderFactory};
 use reth_rpc_api::RethApiServer;
 use reth_rpc_eth_types::{EthApiError, EthResult};
 use reth_tasks::TaskSpawner;
+use reth_transaction_pool::blobstore::{ArchivedBlob, BlobArchive, BlobStoreError};
 use tokio::sync::oneshot;
 
 /// `reth` API implementation.
@@ -16,6 +18,8 @@ use tokio::sync::oneshot;
 /// This type provides the functionality for handling `reth` prototype RPC requests.
 pub struct RethApi<Provider> {
     inner: Arc<RethApiInner<Provider>>,
+    /// The archive the blob sidecars are served from, if enabled.
+    blob_archive: Option<BlobArchive>,
 }
 
 // === impl RethApi ===
@@ -29,7 +33,18 @@ impl<Provider> RethApi<Provider> {
     /// Create a new instance of the [`RethApi`]
     pub fn new(provider: Provider, task_spawner: Box<dyn TaskSpawner>) -> Self {
         let inner = Arc::new(RethApiInner { provider, task_spawner });
-        Self { inner }
+        Self { inner, blob_archive: None }
+    }
+
+    /// Sets the archive the blob sidecars are served from.
+    pub fn with_blob_archive(mut self, blob_archive: BlobArchive) -> Self {
+        self.blob_archive = Some(blob_archive);
+        self
+    }
+
+    /// Returns the blob archive, or an error if it's not enabled.
+    fn blob_archive(&self) -> EthResult<&BlobArchive> {
+        self.blob_archive.as_ref().ok_or(EthApiError::Unsupported("blob archive is not enabled"))
     }
 }
 
@@ -83,6 +98,34 @@ where
         )?;
         Ok(hash_map)
     }
+
+    /// Returns the archived blobs of a block.
+    pub async fn blob_sidecars_by_block(&self, block_id: BlockId) -> EthResult<Vec<ArchivedBlob>> {
+        self.blob_archive()?;
+        self.on_blocking_task(|this| async move {
+            let Some(header) = this.provider().sealed_header_by_id(block_id)? else {
+                return Err(EthApiError::HeaderNotFound(block_id))
+            };
+            this.blob_archive()?
+                .get_block(BlockNumHash::new(header.number(), header.hash()))
+                .map_err(blob_archive_error)
+        })
+        .await
+    }
+
+    /// Returns the archived blobs for the given versioned hashes.
+    pub async fn blob_sidecars_by_versioned_hashes(
+        &self,
+        versioned_hashes: Vec<B256>,
+    ) -> EthResult<Vec<Option<ArchivedBlob>>> {
+        self.blob_archive()?;
+        self.on_blocking_task(|this| async move {
+            this.blob_archive()?
+                .get_by_versioned_hashes(&versioned_hashes)
+                .map_err(blob_archive_error)
+        })
+        .await
+    }
 }
 
 #[async_trait]
@@ -97,6 +140,26 @@ where
     ) -> RpcResult<HashMap<Address, U256>> {
         Ok(Self::balance_changes_in_block(self, block_id).await?)
     }
+
+    /// Handler for `reth_getBlobSidecarsByBlock`
+    async fn reth_get_blob_sidecars_by_block(
+        &self,
+        block_id: BlockId,
+    ) -> RpcResult<Vec<ArchivedBlob>> {
+        Ok(Self::blob_sidecars_by_block(self, block_id).await?)
+    }
+
+    /// Handler for `reth_getBlobSidecarsByVersionedHashes`
+    async fn reth_get_blob_sidecars_by_versioned_hashes(
+        &self,
+        versioned_hashes: Vec<B256>,
+    ) -> RpcResult<Vec<Option<ArchivedBlob>>> {
+        Ok(Self::blob_sidecars_by_versioned_hashes(self, versioned_hashes).await?)
+    }
+}
+
+fn blob_archive_error(err: BlobStoreError) -> EthApiError {
+    EthApiError::Internal(RethError::msg(err))
 }
 
 impl<Provider> std::fmt::Debug for RethApi<Provider> {
@@ -107,7 +170,7 @@ impl<Provider> std::fmt::Debug for RethApi<Provider> {
 
 impl<Provider> Clone for RethApi<Provider> {
     fn clone(&self) -> Self {
-        Self { inner: Arc::clone(&self.inner) }
+        Self { inner: Arc::clone(&self.inner), blob_archive: self.blob_archive.clone() }
     }
 }
 
@@ -2,7 +2,7 @@ use core::fmt;
 use std::collections::BTreeMap;
 
 use alloy_consensus::Transaction;
-use alloy_primitives::Address;
+use alloy_primitives::{Address, TxHash};
 use alloy_rpc_types_txpool::{
     TxpoolContent, TxpoolContentFrom, TxpoolInspect, TxpoolInspectSummary, TxpoolStatus,
 };
@@ -11,7 +11,7 @@ use jsonrpsee::core::RpcResult;
 use reth_rpc_api::TxPoolApiServer;
 use reth_rpc_types_compat::{transaction::from_recovered, TransactionCompat};
 use reth_transaction_pool::{
-    AllPoolTransactions, PoolConsensusTx, PoolTransaction, TransactionPool,
+    AllPoolTransactions, PoolConsensusTx, PoolTransaction, TransactionHistoryEvent, TransactionPool,
 };
 use tracing::trace;
 
@@ -150,6 +150,17 @@ where
         trace!(target: "rpc::eth", "Serving txpool_content");
         Ok(self.content().map_err(Into::into)?)
     }
+
+    /// Returns the recent events of the transaction with the given hash, oldest first.
+    ///
+    /// Handler for `txpool_getTransactionHistory`
+    async fn txpool_get_transaction_history(
+        &self,
+        hash: TxHash,
+    ) -> RpcResult<Vec<TransactionHistoryEvent>> {
+        trace!(target: "rpc::eth", ?hash, "Serving txpool_getTransactionHistory");
+        Ok(self.pool.transaction_history(hash))
+    }
 }
 
 impl<Pool, Eth> fmt::Debug for TxPoolApi<Pool, Eth> {
@@ -250,6 +250,9 @@ impl<N: ProviderNodeTypes> Pipeline<N> {
     ///   [`StageId::Execution`]
     /// - [`StaticFileSegment::Transactions`](reth_static_file_types::StaticFileSegment::Transactions)
     ///   -> [`StageId::Bodies`]
+    /// - [`StaticFileSegment::AccountChangeSets`](reth_static_file_types::StaticFileSegment::AccountChangeSets)
+    ///   and [`StaticFileSegment::StorageChangeSets`](reth_static_file_types::StaticFileSegment::StorageChangeSets)
+    ///   -> [`StageId::Execution`]
     ///
     /// CAUTION: This method locks the static file producer Mutex, hence can block the thread if the
     /// lock is occupied.
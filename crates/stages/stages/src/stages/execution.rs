@@ -1,4 +1,4 @@
-use crate::stages::MERKLE_STAGE_DEFAULT_CLEAN_THRESHOLD;
+use crate::stages::{ExecutionStateRoot, MerkleStage, MERKLE_STAGE_DEFAULT_CLEAN_THRESHOLD};
 use alloy_consensus::{BlockHeader, Header, Sealable};
 use alloy_eips::{eip1898::BlockWithParent, NumHash};
 use alloy_primitives::BlockNumber;
@@ -10,15 +10,16 @@ use reth_evm::{
     execute::{BatchExecutor, BlockExecutorProvider},
     metrics::ExecutorMetrics,
 };
-use reth_execution_types::Chain;
+use reth_execution_types::{Chain, ExecutionOutcome};
 use reth_exex::{ExExManagerHandle, ExExNotification, ExExNotificationSource};
-use reth_primitives::StaticFileSegment;
+use reth_primitives::{SealedHeader, StaticFileSegment};
 use reth_primitives_traits::{format_gas_throughput, Block, BlockBody, NodePrimitives};
 use reth_provider::{
     providers::{StaticFileProvider, StaticFileWriter},
     BlockHashReader, BlockReader, DBProvider, HeaderProvider, LatestStateProviderRef,
-    OriginalValuesKnown, ProviderError, StateCommitmentProvider, StateWriter,
-    StaticFileProviderFactory, StatsReader, StorageLocation, TransactionVariant,
+    OriginalValuesKnown, ProviderError, StageCheckpointReader, StageCheckpointWriter,
+    StateCommitmentProvider, StateWriter, StaticFileProviderFactory, StatsReader, StorageLocation,
+    TransactionVariant, TrieWriter,
 };
 use reth_prune_types::PruneModes;
 use reth_revm::database::StateProviderDatabase;
@@ -27,6 +28,8 @@ use reth_stages_api::{
     ExecutionCheckpoint, ExecutionStageThresholds, Stage, StageCheckpoint, StageError, StageId,
     UnwindInput, UnwindOutput,
 };
+use reth_trie::HashedPostState;
+use reth_trie_db::StateCommitment;
 use std::{
     cmp::Ordering,
     ops::RangeInclusive,
@@ -66,6 +69,11 @@ use super::missing_static_data_error;
 /// - [`tables::AccountsHistory`] to remove change set and apply old values to
 /// - [`tables::PlainAccountState`] [`tables::StoragesHistory`] to remove change set and apply old
 ///   values to [`tables::PlainStorageState`]
+///
+/// In the combined execution and merkle mode (see [`ExecutionStage::with_state_root`]) the stage
+/// additionally updates [`tables::HashedAccounts`], [`tables::HashedStorages`],
+/// [`tables::AccountsTrie`] and [`tables::StoragesTrie`], and the checkpoints of the hashing and
+/// merkle stages. These tables are still unwound by the hashing and merkle stages.
 // false positive, we cannot derive it if !DB: Debug.
 #[allow(missing_debug_implementations)]
 pub struct ExecutionStage<E>
@@ -83,6 +91,8 @@ where
     external_clean_threshold: u64,
     /// Pruning configuration.
     prune_modes: PruneModes,
+    /// Combined execution and merkle mode, if enabled.
+    state_root: Option<ExecutionStateRoot>,
     /// Input for the post execute commit hook.
     /// Set after every [`ExecutionStage::execute`] and cleared after
     /// [`ExecutionStage::post_execute_commit`].
@@ -114,6 +124,7 @@ where
             executor_provider,
             thresholds,
             prune_modes,
+            state_root: None,
             post_execute_commit_input: None,
             post_unwind_commit_input: None,
             exex_manager_handle,
@@ -150,6 +161,37 @@ where
         )
     }
 
+    /// Enables the combined execution and merkle mode, see [`ExecutionStateRoot`].
+    ///
+    /// In this mode the stage does the work of the [`super::AccountHashingStage`],
+    /// [`super::StorageHashingStage`] and [`MerkleStage`] for the executed blocks, and advances
+    /// their checkpoints to its own. A batch is only executed in this mode if these stages are at
+    /// the execution checkpoint and no trie rebuild is in progress. The stage doesn't unwind the
+    /// hashed state and the trie, so it must run in a pipeline that contains the hashing and merkle
+    /// stages, which unwind them before the execution stage is unwound.
+    pub fn with_state_root(mut self, state_root: ExecutionStateRoot) -> Self {
+        self.state_root = Some(state_root);
+        self
+    }
+
+    /// Returns `true` if the hashed state and the trie have been built up to the given execution
+    /// checkpoint, so that state roots of the following blocks can be computed on top of them.
+    fn is_merkle_in_sync(
+        provider: &impl StageCheckpointReader,
+        checkpoint: BlockNumber,
+    ) -> Result<bool, StageError> {
+        for stage_id in [StageId::AccountHashing, StageId::StorageHashing, StageId::MerkleExecute] {
+            if provider.get_stage_checkpoint(stage_id)?.unwrap_or_default().block_number !=
+                checkpoint
+            {
+                return Ok(false)
+            }
+        }
+
+        // An unfinished trie rebuild leaves the trie tables incomplete
+        Ok(MerkleStage::default_execution().get_execution_checkpoint(provider)?.is_none())
+    }
+
     /// Adjusts the prune modes related to changesets.
     ///
     /// This function verifies whether the [`super::MerkleStage`] or Hashing stages will run from
@@ -273,7 +315,10 @@ where
         + StatsReader
         + BlockHashReader
         + StateWriter<Receipt = <E::Primitives as NodePrimitives>::Receipt>
-        + StateCommitmentProvider,
+        + StateCommitmentProvider
+        + StageCheckpointReader
+        + StageCheckpointWriter
+        + TrieWriter,
 {
     /// Return the id of the stage
     fn id(&self) -> StageId {
@@ -303,10 +348,35 @@ where
 
         self.ensure_consistency(provider, input.checkpoint().block_number, None)?;
 
-        let db = StateProviderDatabase(LatestStateProviderRef::new(provider));
-        let mut executor = self.executor_provider.batch_executor(db);
-        executor.set_tip(max_block);
-        executor.set_prune_modes(prune_modes);
+        let new_executor = || {
+            let db = StateProviderDatabase(LatestStateProviderRef::new(provider));
+            let mut executor = self.executor_provider.batch_executor(db);
+            executor.set_tip(max_block);
+            executor.set_prune_modes(prune_modes.clone());
+            executor
+        };
+        let mut executor = new_executor();
+
+        // In the combined execution and merkle mode, every chunk is written as soon as it's
+        // executed and its state root is computed while the next chunk is executed.
+        let mut chunk_roots = match &self.state_root {
+            Some(state_root)
+                if Self::is_merkle_in_sync(provider, input.checkpoint().block_number)? =>
+            {
+                Some(state_root.begin())
+            }
+            Some(_) => {
+                debug!(target: "sync::stages::execution", "Hashed state and trie are not in sync with the execution checkpoint, skipping chunk state roots");
+                None
+            }
+            None => None,
+        };
+        let chunk_size = self.state_root.as_ref().map_or(u64::MAX, |root| root.chunk_size());
+        let mut chunk_start = start_block;
+        // Size hint of the chunks that were already written
+        let mut written_size_hint = 0;
+        // Output of the written chunks, only kept for ExExes
+        let mut written_state: Option<ExecutionOutcome<_>> = None;
 
         // Progress tracking
         let mut stage_progress = start_block;
@@ -390,26 +460,78 @@ where
             stage_progress = block_number;
             stage_checkpoint.progress.processed += block.header().gas_used();
 
-            // If we have ExExes we need to save the block in memory for later
-            if self.exex_manager_handle.has_exexs() {
-                blocks.push(block);
-            }
-
             // Check if we should commit now
-            let bundle_size_hint = executor.size_hint().unwrap_or_default() as u64;
-            if self.thresholds.is_end_of_batch(
+            let bundle_size_hint =
+                written_size_hint + executor.size_hint().unwrap_or_default() as u64;
+            let end_of_batch = self.thresholds.is_end_of_batch(
                 block_number - start_block,
                 bundle_size_hint,
                 cumulative_gas,
                 batch_start.elapsed(),
-            ) {
+            );
+
+            if let Some(chunk_roots) = &mut chunk_roots {
+                if end_of_batch ||
+                    block_number == max_block ||
+                    block_number - chunk_start + 1 >= chunk_size
+                {
+                    let chunk = std::mem::replace(&mut executor, new_executor()).finalize();
+                    let hashed_state = HashedPostState::from_bundle_state::<
+                        <Provider::StateCommitment as StateCommitment>::KeyHasher,
+                    >(chunk.state().state());
+                    written_size_hint += chunk.state().size_hint() as u64;
+
+                    if self.exex_manager_handle.has_exexs() {
+                        match &mut written_state {
+                            Some(state) => state.extend(chunk.clone()),
+                            None => written_state = Some(chunk.clone()),
+                        }
+                    }
+                    // the next chunk is executed on top of the written state
+                    provider.write_state(
+                        chunk,
+                        OriginalValuesKnown::Yes,
+                        StorageLocation::StaticFiles,
+                    )?;
+
+                    let header = block.header();
+                    chunk_roots.on_chunk(
+                        chunk_start,
+                        SealedHeader::new(header.clone(), header.hash_slow()),
+                        hashed_state,
+                    )?;
+                    chunk_start = block_number + 1;
+                }
+            }
+
+            // If we have ExExes we need to save the block in memory for later
+            if self.exex_manager_handle.has_exexs() {
+                blocks.push(block);
+            }
+
+            if end_of_batch {
                 break
             }
         }
 
         // prepare execution output for writing
         let time = Instant::now();
-        let state = executor.finalize();
+        let state = match chunk_roots {
+            // all chunks are already written, only the hashed state and the trie are left
+            Some(chunk_roots) => {
+                let (hashed_state, trie_updates) = chunk_roots.finish()?;
+                provider.write_hashed_state(&hashed_state)?;
+                provider.write_trie_updates(&trie_updates)?;
+                for stage_id in
+                    [StageId::AccountHashing, StageId::StorageHashing, StageId::MerkleExecute]
+                {
+                    provider
+                        .save_stage_checkpoint(stage_id, StageCheckpoint::new(stage_progress))?;
+                }
+                None
+            }
+            None => Some(executor.finalize()),
+        };
         let write_preparation_duration = time.elapsed();
 
         // log the gas per second for the range we just executed
@@ -431,8 +553,9 @@ where
                 block.seal_unchecked(hash)
             });
 
+            let state = state.clone().or(written_state).unwrap_or_default();
             let previous_input =
-                self.post_execute_commit_input.replace(Chain::new(blocks, state.clone(), None));
+                self.post_execute_commit_input.replace(Chain::new(blocks, state, None));
 
             if previous_input.is_some() {
                 // Not processing the previous post execute commit input is a critical error, as it
@@ -446,7 +569,9 @@ where
         let time = Instant::now();
 
         // write output
-        provider.write_state(state, OriginalValuesKnown::Yes, StorageLocation::StaticFiles)?;
+        if let Some(state) = state {
+            provider.write_state(state, OriginalValuesKnown::Yes, StorageLocation::StaticFiles)?;
+        }
 
         let db_write_duration = time.elapsed();
         debug!(
@@ -649,19 +774,24 @@ fn calculate_gas_used_from_headers<N: NodePrimitives>(
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::test_utils::TestStageDB;
+    use crate::{
+        stages::{AccountHashingStage, StorageHashingStage},
+        test_utils::TestStageDB,
+    };
     use alloy_primitives::{address, hex_literal::hex, keccak256, Address, B256, U256};
     use alloy_rlp::Decodable;
     use assert_matches::assert_matches;
     use reth_chainspec::ChainSpecBuilder;
+    use reth_consensus::ConsensusError;
     use reth_db_api::{models::AccountBeforeTx, transaction::DbTxMut};
     use reth_evm::execute::BasicBlockExecutorProvider;
     use reth_evm_ethereum::execute::EthExecutionStrategyFactory;
     use reth_execution_errors::BlockValidationError;
     use reth_primitives::{Account, Bytecode, SealedBlock, StorageEntry};
     use reth_provider::{
-        test_utils::create_test_provider_factory, AccountReader, DatabaseProviderFactory,
-        ReceiptProvider, StaticFileProviderFactory,
+        test_utils::{create_test_provider_factory, MockNodeTypesWithDB},
+        AccountReader, DatabaseProviderFactory, ProviderFactory, ReceiptProvider,
+        StaticFileProviderFactory,
     };
     use reth_prune_types::{PruneMode, ReceiptsLogPruneConfig};
     use reth_stages_api::StageUnitCheckpoint;
@@ -831,11 +961,11 @@ mod tests {
         }) if total == block.gas_used);
     }
 
-    #[tokio::test]
-    async fn sanity_execution_of_block() {
-        let factory = create_test_provider_factory();
+    /// Inserts genesis and block 1 of the sanity fixture together with the pre state of block 1.
+    ///
+    /// Returns block 1 and the code hash of the called contract.
+    fn insert_sanity_block(factory: &ProviderFactory<MockNodeTypesWithDB>) -> (SealedBlock, B256) {
         let provider = factory.provider_rw().unwrap();
-        let input = ExecInput { target: Some(1), checkpoint: None };
         let mut genesis_rlp = hex!("f901faf901f5a00000000000000000000000000000000000000000000000000000000000000000a01dcc4de8dec75d7aab85b567b6ccd41ad312451b948a7413f0a142fd40d49347942adc25665018aa1fe0e6bc666dac8fc2697ff9baa045571b40ae66ca7480791bbb2887286e4e4c4b1b298b191c889d6959023a32eda056e81f171bcc55a6ff8345e692c0f86e5b48e01b996cadc001622fb5e363b421a056e81f171bcc55a6ff8345e692c0f86e5b48e01b996cadc001622fb5e363b421b901000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000083020000808502540be400808000a00000000000000000000000000000000000000000000000000000000000000000880000000000000000c0c0").as_slice();
         let genesis = SealedBlock::decode(&mut genesis_rlp).unwrap();
         let mut block_rlp = hex!("f90262f901f9a075c371ba45999d87f4542326910a11af515897aebce5265d3f6acd1f1161f82fa01dcc4de8dec75d7aab85b567b6ccd41ad312451b948a7413f0a142fd40d49347942adc25665018aa1fe0e6bc666dac8fc2697ff9baa098f2dcd87c8ae4083e7017a05456c14eea4b1db2032126e27b3b1563d57d7cc0a08151d548273f6683169524b66ca9fe338b9ce42bc3540046c828fd939ae23bcba03f4e5c2ec5b2170b711d97ee755c160457bb58d8daa338e835ec02ae6860bbabb901000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000083020000018502540be40082a8798203e800a00000000000000000000000000000000000000000000000000000000000000000880000000000000000f863f861800a8405f5e10094100000000000000000000000000000000000000080801ba07e09e26678ed4fac08a249ebe8ed680bf9051a5e14ad223e4b2b9d26e0208f37a05f6e3f188e3e6eab7d7d3b6568f5eac7d687b08d307d3154ccd8c87b4630509bc0").as_slice();
@@ -881,6 +1011,15 @@ mod tests {
         db_tx.put::<tables::Bytecodes>(code_hash, Bytecode::new_raw(code.to_vec().into())).unwrap();
         provider.commit().unwrap();
 
+        (block, code_hash)
+    }
+
+    #[tokio::test]
+    async fn sanity_execution_of_block() {
+        let factory = create_test_provider_factory();
+        let input = ExecInput { target: Some(1), checkpoint: None };
+        let (block, code_hash) = insert_sanity_block(&factory);
+
         // execute
 
         // If there is a pruning configuration, then it's forced to use the database.
@@ -980,6 +1119,135 @@ mod tests {
         }
     }
 
+    /// Hashes the pre state inserted by [`insert_sanity_block`], the trie is built from scratch by
+    /// the first state root.
+    fn insert_hashed_pre_state(factory: &ProviderFactory<MockNodeTypesWithDB>) {
+        let provider = factory.provider_rw().unwrap();
+        let accounts = provider
+            .tx_ref()
+            .cursor_read::<tables::PlainAccountState>()
+            .unwrap()
+            .walk(None)
+            .unwrap()
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        for (address, account) in accounts {
+            provider.tx_ref().put::<tables::HashedAccounts>(keccak256(address), account).unwrap();
+        }
+        provider.commit().unwrap();
+    }
+
+    #[tokio::test]
+    async fn execution_with_chunk_state_roots() {
+        let input = ExecInput { target: Some(1), checkpoint: None };
+        let sender = address!("a94f5374fce5edbc8e2a8697c15331677e6ebf0b");
+
+        for corrupt_hashed_state in [false, true] {
+            let factory = create_test_provider_factory();
+            insert_sanity_block(&factory);
+
+            insert_hashed_pre_state(&factory);
+            let provider = factory.provider_rw().unwrap();
+            if corrupt_hashed_state {
+                provider
+                    .tx_ref()
+                    .put::<tables::HashedAccounts>(B256::random(), Account::default())
+                    .unwrap();
+            }
+            provider.commit().unwrap();
+
+            let provider = factory.database_provider_rw().unwrap();
+            let mut execution_stage =
+                stage().with_state_root(ExecutionStateRoot::new(factory.clone(), 1));
+            let result = execution_stage.execute(&provider, input);
+
+            if corrupt_hashed_state {
+                assert_matches!(
+                    result,
+                    Err(StageError::Block {
+                        error: BlockErrorKind::Validation(ConsensusError::BodyStateRootDiff(_)),
+                        ..
+                    })
+                );
+                continue
+            }
+
+            assert_matches!(result, Ok(ExecOutput { checkpoint, done: true }) if checkpoint.block_number == 1);
+            provider.commit().unwrap();
+
+            // the hashing and merkle stages have nothing left to do
+            let provider = factory.provider().unwrap();
+            for stage_id in
+                [StageId::AccountHashing, StageId::StorageHashing, StageId::MerkleExecute]
+            {
+                assert_eq!(
+                    provider.get_stage_checkpoint(stage_id).unwrap(),
+                    Some(StageCheckpoint::new(1))
+                );
+            }
+            assert_eq!(
+                provider
+                    .tx_ref()
+                    .get::<tables::HashedAccounts>(keccak256(sender))
+                    .unwrap()
+                    .map(|account| account.nonce),
+                Some(1)
+            );
+            assert!(provider.tx_ref().entries::<tables::AccountsTrie>().unwrap() > 0);
+        }
+    }
+
+    #[tokio::test]
+    async fn execution_with_chunk_state_roots_unwind() {
+        let input = ExecInput { target: Some(1), checkpoint: None };
+        let sender = address!("a94f5374fce5edbc8e2a8697c15331677e6ebf0b");
+        let factory = create_test_provider_factory();
+        insert_sanity_block(&factory);
+        insert_hashed_pre_state(&factory);
+
+        let mut execution_stage =
+            stage().with_state_root(ExecutionStateRoot::new(factory.clone(), 1));
+        // the second execution computes the state roots on top of the unwound hashed state and trie
+        for _ in 0..2 {
+            let provider = factory.database_provider_rw().unwrap();
+            let output = execution_stage.execute(&provider, input).unwrap();
+            assert_eq!(output.checkpoint.block_number, 1);
+            provider.commit().unwrap();
+
+            // unwind in the pipeline order, the hashing and merkle stages unwind the hashed state
+            // and the trie written by the execution stage
+            let provider = factory.database_provider_rw().unwrap();
+            let unwind =
+                UnwindInput { checkpoint: StageCheckpoint::new(1), unwind_to: 0, bad_block: None };
+            MerkleStage::default_execution().unwind(&provider, unwind).unwrap();
+            StorageHashingStage::default().unwind(&provider, unwind).unwrap();
+            AccountHashingStage::default().unwind(&provider, unwind).unwrap();
+            MerkleStage::default_unwind().unwind(&provider, unwind).unwrap();
+            execution_stage.unwind(&provider, unwind).unwrap();
+            for stage_id in
+                [StageId::AccountHashing, StageId::StorageHashing, StageId::MerkleExecute]
+            {
+                provider.save_stage_checkpoint(stage_id, StageCheckpoint::new(0)).unwrap();
+            }
+            provider.commit().unwrap();
+
+            let provider = factory.provider().unwrap();
+            assert_eq!(
+                provider
+                    .tx_ref()
+                    .get::<tables::HashedAccounts>(keccak256(sender))
+                    .unwrap()
+                    .map(|account| account.nonce),
+                Some(0)
+            );
+            assert_eq!(
+                provider.basic_account(sender).unwrap().map(|account| account.nonce),
+                Some(0)
+            );
+            assert_eq!(provider.tx_ref().entries::<tables::AccountsTrie>().unwrap(), 0);
+        }
+    }
+
     #[tokio::test]
     async fn sanity_execute_unwind() {
         let factory = create_test_provider_factory();
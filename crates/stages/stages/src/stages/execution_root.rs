@@ -0,0 +1,206 @@
+use super::INVALID_STATE_ROOT_ERROR_MESSAGE;
+use alloy_consensus::BlockHeader;
+use alloy_primitives::{BlockNumber, B256};
+use reth_consensus::ConsensusError;
+use reth_primitives::{GotExpected, SealedHeader};
+use reth_provider::{
+    providers::ConsistentDbView, BlockReader, DatabaseProviderFactory, StateCommitmentProvider,
+};
+use reth_stages_api::{BlockErrorKind, StageError};
+use reth_trie::{
+    prefix_set::TriePrefixSetsMut,
+    updates::{TrieUpdates, TrieUpdatesSorted},
+    HashedPostState, HashedPostStateSorted,
+};
+use reth_trie_parallel::root::{ParallelStateRoot, ParallelStateRootError};
+use std::{fmt, sync::Arc, thread::JoinHandle};
+use tracing::*;
+
+/// Computes the state root of the given sorted trie nodes, hashed state and prefix sets on top of
+/// the committed hashed state and trie.
+type StateRootFn = dyn Fn(
+        Arc<TrieUpdatesSorted>,
+        Arc<HashedPostStateSorted>,
+        TriePrefixSetsMut,
+    ) -> Result<(B256, TrieUpdates), ParallelStateRootError>
+    + Send
+    + Sync;
+
+/// Combined execution and merkle mode of the [`ExecutionStage`](super::ExecutionStage).
+///
+/// The execution stage writes every chunk of `chunk_size` blocks as soon as it's executed and
+/// computes the state root of the chunk's last block in the background with
+/// [`ParallelStateRoot`], while the next chunk is being executed. A wrong state root is therefore
+/// reported for the chunk that produced it, instead of only after the
+/// [`MerkleStage`](super::MerkleStage) has processed the whole range.
+///
+/// The hashed state and the trie updates of the verified chunks are kept sorted in memory, and are
+/// owned by the background computation of the next chunk, which merges the chunk's state into
+/// them. This way the accumulated state is never copied, and the execution of the next chunk
+/// isn't blocked by merging it.
+///
+/// At the end of each batch the stage writes the hashed state and the trie updates of all verified
+/// chunks, and advances the checkpoints of the hashing and merkle stages, which then have nothing
+/// left to do. This mode is only used if the hashing and merkle stages are at the same checkpoint
+/// as the execution stage, i.e. if the committed hashed state and trie belong to the parent of the
+/// first executed block.
+#[derive(Clone)]
+pub struct ExecutionStateRoot {
+    /// The number of blocks executed between two state root computations.
+    chunk_size: u64,
+    /// Computes the state root on top of the committed database state.
+    state_root: Arc<StateRootFn>,
+}
+
+impl ExecutionStateRoot {
+    /// Creates a new combined execution and merkle mode that computes state roots with the given
+    /// provider factory every `chunk_size` blocks.
+    pub fn new<Factory>(factory: Factory, chunk_size: u64) -> Self
+    where
+        Factory: DatabaseProviderFactory<Provider: BlockReader>
+            + StateCommitmentProvider
+            + Clone
+            + Send
+            + Sync
+            + 'static,
+    {
+        Self {
+            chunk_size: chunk_size.max(1),
+            state_root: Arc::new(move |nodes, state, prefix_sets| {
+                ParallelStateRoot::new_sorted(
+                    ConsistentDbView::new(factory.clone(), None),
+                    nodes,
+                    state,
+                    prefix_sets,
+                )
+                .incremental_root_with_updates()
+            }),
+        }
+    }
+
+    /// Returns the number of blocks executed between two state root computations.
+    pub const fn chunk_size(&self) -> u64 {
+        self.chunk_size
+    }
+
+    /// Starts the state root computations of a new execution batch.
+    pub(crate) fn begin<H>(&self) -> ChunkStateRoots<H> {
+        ChunkStateRoots {
+            state_root: self.state_root.clone(),
+            verified: VerifiedChunks::default(),
+            pending: None,
+        }
+    }
+}
+
+impl fmt::Debug for ExecutionStateRoot {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ExecutionStateRoot").field("chunk_size", &self.chunk_size).finish()
+    }
+}
+
+/// The state root computations of the chunks of a single execution batch.
+pub(crate) struct ChunkStateRoots<H> {
+    /// Computes the state root on top of the committed database state.
+    state_root: Arc<StateRootFn>,
+    /// The hashed state and the trie updates of all verified chunks. Moved into the computation
+    /// of the pending chunk while it's running.
+    verified: VerifiedChunks,
+    /// The state root computation of the last executed chunk.
+    pending: Option<PendingChunk<H>>,
+}
+
+/// The sorted hashed state and trie updates of the verified chunks.
+#[derive(Default)]
+struct VerifiedChunks {
+    /// The trie updates of the verified chunks.
+    nodes: TrieUpdatesSorted,
+    /// The hashed state changes of the verified chunks.
+    state: HashedPostStateSorted,
+}
+
+/// A chunk whose state root is being computed.
+struct PendingChunk<H> {
+    /// The first block of the chunk.
+    first_block: BlockNumber,
+    /// The header of the last block of the chunk.
+    header: SealedHeader<H>,
+    /// The background state root computation, returning the state root of the chunk and the
+    /// verified chunks extended with the chunk.
+    handle: JoinHandle<Result<(B256, VerifiedChunks), ParallelStateRootError>>,
+}
+
+impl<H: BlockHeader + fmt::Debug> ChunkStateRoots<H> {
+    /// Verifies the state root of the previous chunk and starts computing the state root of the
+    /// chunk `first_block..=header.number()` with the given hashed state changes.
+    pub(crate) fn on_chunk(
+        &mut self,
+        first_block: BlockNumber,
+        header: SealedHeader<H>,
+        state: HashedPostState,
+    ) -> Result<(), StageError> {
+        self.verify_pending()?;
+
+        let verified = std::mem::take(&mut self.verified);
+        let state_root = self.state_root.clone();
+        let handle = std::thread::Builder::new().name("Execution State Root".to_string()).spawn(
+            move || -> Result<_, ParallelStateRootError> {
+                let prefix_sets = state.construct_prefix_sets();
+                let VerifiedChunks { nodes, state: mut hashed_state } = verified;
+                hashed_state.extend_ref(&state.into_sorted());
+
+                let nodes = Arc::new(nodes);
+                let hashed_state = Arc::new(hashed_state);
+                let (root, updates) = state_root(nodes.clone(), hashed_state.clone(), prefix_sets)?;
+
+                // The storage root tasks have usually released their references by now, so the
+                // verified state is taken back without copying it.
+                let mut nodes = Arc::unwrap_or_clone(nodes);
+                nodes.extend_ref(&updates.into_sorted());
+                Ok((root, VerifiedChunks { nodes, state: Arc::unwrap_or_clone(hashed_state) }))
+            },
+        )?;
+
+        self.pending = Some(PendingChunk { first_block, header, handle });
+        Ok(())
+    }
+
+    /// Verifies the state root of the last chunk and returns the hashed state and the trie updates
+    /// of the whole batch.
+    pub(crate) fn finish(mut self) -> Result<(HashedPostStateSorted, TrieUpdates), StageError> {
+        self.verify_pending()?;
+        Ok((self.verified.state, self.verified.nodes.into()))
+    }
+
+    /// Waits for the state root computation of the pending chunk and checks it against the header
+    /// of the chunk's last block.
+    fn verify_pending(&mut self) -> Result<(), StageError> {
+        let Some(PendingChunk { first_block, header, handle }) = self.pending.take() else {
+            return Ok(())
+        };
+
+        let (root, verified) = handle
+            .join()
+            .map_err(|_| {
+                StageError::Fatal(Box::new(ParallelStateRootError::Other(
+                    "state root computation panicked".to_string(),
+                )))
+            })?
+            .map_err(|error| StageError::Fatal(Box::new(error)))?;
+
+        let last_block = header.number();
+        debug!(target: "sync::stages::execution", first_block, last_block, ?root, "Computed chunk state root");
+        if root != header.state_root() {
+            error!(target: "sync::stages::execution", chunk = ?(first_block..=last_block), got = ?root, expected = ?header.state_root(), "Failed to verify chunk state root! {INVALID_STATE_ROOT_ERROR_MESSAGE}");
+            return Err(StageError::Block {
+                error: BlockErrorKind::Validation(ConsensusError::BodyStateRootDiff(
+                    GotExpected { got: root, expected: header.state_root() }.into(),
+                )),
+                block: Box::new(header.block_with_parent()),
+            })
+        }
+
+        self.verified = verified;
+        Ok(())
+    }
+}
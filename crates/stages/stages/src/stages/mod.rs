@@ -2,6 +2,8 @@
 mod bodies;
 /// The execution stage that generates state diff.
 mod execution;
+/// Combined execution and merkle mode of the execution stage.
+mod execution_root;
 /// The finish stage
 mod finish;
 /// Account hashing stage.
@@ -24,6 +26,7 @@ mod tx_lookup;
 
 pub use bodies::*;
 pub use execution::*;
+pub use execution_root::*;
 pub use finish::*;
 pub use hashing_account::*;
 pub use hashing_storage::*;
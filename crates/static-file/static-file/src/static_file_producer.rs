@@ -140,6 +140,12 @@ where
         if let Some(block_range) = targets.receipts.clone() {
             segments.push((Box::new(segments::Receipts), block_range));
         }
+        if let Some(block_range) = targets.account_changesets.clone() {
+            segments.push((Box::new(segments::AccountChangeSets), block_range));
+        }
+        if let Some(block_range) = targets.storage_changesets.clone() {
+            segments.push((Box::new(segments::StorageChangeSets), block_range));
+        }
 
         segments.par_iter().try_for_each(|(segment, block_range)| -> ProviderResult<()> {
             debug!(target: "static_file", segment = %segment.segment(), ?block_range, "StaticFileProducer segment");
@@ -187,6 +193,8 @@ where
             headers: stages_checkpoints[0],
             receipts: stages_checkpoints[1],
             transactions: stages_checkpoints[2],
+            account_changesets: stages_checkpoints[1],
+            storage_changesets: stages_checkpoints[1],
         };
         let targets = self.get_static_file_targets(highest_static_files)?;
         self.run(targets)?;
@@ -202,6 +210,13 @@ where
         finalized_block_numbers: HighestStaticFiles,
     ) -> ProviderResult<StaticFileTargets> {
         let highest_static_files = self.provider.static_file_provider().get_highest_static_files();
+        // Changesets of blocks that are not executed yet are indistinguishable from empty ones, so
+        // they're never moved past the execution checkpoint
+        let executed_block_number = self
+            .provider
+            .database_provider_ro()?
+            .get_stage_checkpoint(StageId::Execution)?
+            .map(|checkpoint| checkpoint.block_number);
 
         let targets = StaticFileTargets {
             headers: finalized_block_numbers.headers.and_then(|finalized_block_number| {
@@ -226,6 +241,32 @@ where
                     finalized_block_number,
                 )
             }),
+            // StaticFile changesets only if the history is not pruned according to the user
+            // configuration
+            account_changesets: if self.prune_modes.account_history.is_none() {
+                finalized_block_numbers.account_changesets.zip(executed_block_number).and_then(
+                    |(finalized_block_number, executed_block_number)| {
+                        self.get_static_file_target(
+                            highest_static_files.account_changesets,
+                            finalized_block_number.min(executed_block_number),
+                        )
+                    },
+                )
+            } else {
+                None
+            },
+            storage_changesets: if self.prune_modes.storage_history.is_none() {
+                finalized_block_numbers.storage_changesets.zip(executed_block_number).and_then(
+                    |(finalized_block_number, executed_block_number)| {
+                        self.get_static_file_target(
+                            highest_static_files.storage_changesets,
+                            finalized_block_number.min(executed_block_number),
+                        )
+                    },
+                )
+            } else {
+                None
+            },
         };
 
         trace!(
@@ -255,15 +296,23 @@ mod tests {
     use crate::static_file_producer::{
         StaticFileProducer, StaticFileProducerInner, StaticFileTargets,
     };
-    use alloy_primitives::{B256, U256};
+    use alloy_primitives::{Address, B256, U256};
     use assert_matches::assert_matches;
-    use reth_db_api::{database::Database, transaction::DbTx};
+    use reth_db::tables;
+    use reth_db_api::{
+        cursor::DbCursorRO,
+        database::Database,
+        models::{AccountBeforeTx, BlockNumberAddress},
+        transaction::{DbTx, DbTxMut},
+    };
+    use reth_primitives_traits::StorageEntry;
     use reth_provider::{
-        providers::StaticFileWriter, test_utils::MockNodeTypesWithDB, ProviderError,
-        ProviderFactory, StaticFileProviderFactory,
+        providers::StaticFileWriter, test_utils::MockNodeTypesWithDB, ChangeSetReader,
+        ProviderError, ProviderFactory, StaticFileProviderFactory, StorageChangeSetReader,
     };
-    use reth_prune_types::PruneModes;
+    use reth_prune_types::{PruneMode, PruneModes};
     use reth_stages::test_utils::{StorageKind, TestStageDB};
+    use reth_stages_types::{StageCheckpoint, StageId};
     use reth_static_file_types::{HighestStaticFiles, StaticFileSegment};
     use reth_testing_utils::generators::{
         self, random_block_range, random_receipt, BlockRangeParams,
@@ -322,6 +371,7 @@ mod tests {
                 headers: Some(1),
                 receipts: Some(1),
                 transactions: Some(1),
+                ..Default::default()
             })
             .expect("get static file targets");
         assert_eq!(
@@ -329,13 +379,19 @@ mod tests {
             StaticFileTargets {
                 headers: Some(0..=1),
                 receipts: Some(0..=1),
-                transactions: Some(0..=1)
+                transactions: Some(0..=1),
+                ..Default::default()
             }
         );
         assert_matches!(static_file_producer.run(targets), Ok(_));
         assert_eq!(
             provider_factory.static_file_provider().get_highest_static_files(),
-            HighestStaticFiles { headers: Some(1), receipts: Some(1), transactions: Some(1) }
+            HighestStaticFiles {
+                headers: Some(1),
+                receipts: Some(1),
+                transactions: Some(1),
+                ..Default::default()
+            }
         );
 
         let targets = static_file_producer
@@ -343,6 +399,7 @@ mod tests {
                 headers: Some(3),
                 receipts: Some(3),
                 transactions: Some(3),
+                ..Default::default()
             })
             .expect("get static file targets");
         assert_eq!(
@@ -350,13 +407,19 @@ mod tests {
             StaticFileTargets {
                 headers: Some(2..=3),
                 receipts: Some(2..=3),
-                transactions: Some(2..=3)
+                transactions: Some(2..=3),
+                ..Default::default()
             }
         );
         assert_matches!(static_file_producer.run(targets), Ok(_));
         assert_eq!(
             provider_factory.static_file_provider().get_highest_static_files(),
-            HighestStaticFiles { headers: Some(3), receipts: Some(3), transactions: Some(3) }
+            HighestStaticFiles {
+                headers: Some(3),
+                receipts: Some(3),
+                transactions: Some(3),
+                ..Default::default()
+            }
         );
 
         let targets = static_file_producer
@@ -364,6 +427,7 @@ mod tests {
                 headers: Some(4),
                 receipts: Some(4),
                 transactions: Some(4),
+                ..Default::default()
             })
             .expect("get static file targets");
         assert_eq!(
@@ -371,7 +435,8 @@ mod tests {
             StaticFileTargets {
                 headers: Some(4..=4),
                 receipts: Some(4..=4),
-                transactions: Some(4..=4)
+                transactions: Some(4..=4),
+                ..Default::default()
             }
         );
         assert_matches!(
@@ -380,10 +445,108 @@ mod tests {
         );
         assert_eq!(
             provider_factory.static_file_provider().get_highest_static_files(),
-            HighestStaticFiles { headers: Some(3), receipts: Some(3), transactions: Some(3) }
+            HighestStaticFiles {
+                headers: Some(3),
+                receipts: Some(3),
+                transactions: Some(3),
+                ..Default::default()
+            }
         );
     }
 
+    #[test]
+    fn run_changesets() {
+        let (provider_factory, _temp_static_files_dir) = setup();
+
+        let tx = provider_factory.db_ref().tx_mut().expect("init tx");
+        for block in [0, 2] {
+            tx.put::<tables::AccountChangeSets>(
+                block,
+                AccountBeforeTx { address: Address::with_last_byte(1), info: None },
+            )
+            .expect("insert account changeset");
+            tx.put::<tables::StorageChangeSets>(
+                (block, Address::with_last_byte(1)).into(),
+                StorageEntry { key: B256::with_last_byte(2), value: U256::from(block) },
+            )
+            .expect("insert storage changeset");
+        }
+        tx.put::<tables::StageCheckpoints>(StageId::Execution.to_string(), StageCheckpoint::new(3))
+            .expect("save execution checkpoint");
+        tx.commit().expect("commit tx");
+
+        // History pruning keeps the changesets in the database
+        let static_file_producer = StaticFileProducerInner::new(
+            provider_factory.clone(),
+            PruneModes { account_history: Some(PruneMode::Full), ..Default::default() },
+        );
+        let targets = static_file_producer
+            .get_static_file_targets(HighestStaticFiles {
+                account_changesets: Some(3),
+                storage_changesets: Some(3),
+                ..Default::default()
+            })
+            .expect("get static file targets");
+        assert_eq!(
+            targets,
+            StaticFileTargets { storage_changesets: Some(0..=3), ..Default::default() }
+        );
+
+        let static_file_producer =
+            StaticFileProducerInner::new(provider_factory.clone(), PruneModes::default());
+        let targets = static_file_producer
+            .get_static_file_targets(HighestStaticFiles {
+                account_changesets: Some(3),
+                storage_changesets: Some(3),
+                ..Default::default()
+            })
+            .expect("get static file targets");
+        assert_eq!(
+            targets,
+            StaticFileTargets {
+                account_changesets: Some(0..=3),
+                storage_changesets: Some(0..=3),
+                ..Default::default()
+            }
+        );
+        assert_matches!(static_file_producer.run(targets), Ok(_));
+
+        let static_file_provider = provider_factory.static_file_provider();
+        assert_eq!(
+            static_file_provider.get_highest_static_files(),
+            HighestStaticFiles {
+                account_changesets: Some(3),
+                storage_changesets: Some(3),
+                ..Default::default()
+            }
+        );
+        for block in 0..=3 {
+            let provider = provider_factory.provider().expect("provider");
+            assert_eq!(
+                static_file_provider.account_block_changeset(block).unwrap(),
+                provider
+                    .tx_ref()
+                    .cursor_read::<tables::AccountChangeSets>()
+                    .unwrap()
+                    .walk_range(block..=block)
+                    .unwrap()
+                    .map(|entry| entry.unwrap().1)
+                    .collect::<Vec<_>>()
+            );
+            assert_eq!(
+                static_file_provider.storage_changeset(block).unwrap(),
+                provider
+                    .tx_ref()
+                    .cursor_read::<tables::StorageChangeSets>()
+                    .unwrap()
+                    .walk_range(BlockNumberAddress::range(block..=block))
+                    .unwrap()
+                    .collect::<Result<Vec<_>, _>>()
+                    .unwrap()
+            );
+        }
+    }
+
     /// Tests that a cloneable [`StaticFileProducer`] type is not susceptible to any race condition.
     #[test]
     fn only_one() {
@@ -408,6 +571,7 @@ mod tests {
                         headers: Some(1),
                         receipts: Some(1),
                         transactions: Some(1),
+                        ..Default::default()
                     })
                     .expect("get static file targets");
                 assert_matches!(locked_producer.run(targets.clone()), Ok(_));
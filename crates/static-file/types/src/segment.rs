@@ -34,6 +34,14 @@ pub enum StaticFileSegment {
     #[strum(serialize = "receipts")]
     /// Static File segment responsible for the `Receipts` table.
     Receipts,
+    #[strum(serialize = "account-changesets")]
+    #[cfg_attr(feature = "clap", value(name = "account-changesets"))]
+    /// Static File segment responsible for the `AccountChangeSets` table.
+    AccountChangeSets,
+    #[strum(serialize = "storage-changesets")]
+    #[cfg_attr(feature = "clap", value(name = "storage-changesets"))]
+    /// Static File segment responsible for the `StorageChangeSets` table.
+    StorageChangeSets,
 }
 
 impl StaticFileSegment {
@@ -43,6 +51,8 @@ impl StaticFileSegment {
             Self::Headers => "headers",
             Self::Transactions => "transactions",
             Self::Receipts => "receipts",
+            Self::AccountChangeSets => "account-changesets",
+            Self::StorageChangeSets => "storage-changesets",
         }
     }
 
@@ -55,7 +65,10 @@ impl StaticFileSegment {
     pub const fn columns(&self) -> usize {
         match self {
             Self::Headers => 3,
-            Self::Transactions | Self::Receipts => 1,
+            Self::Transactions |
+            Self::Receipts |
+            Self::AccountChangeSets |
+            Self::StorageChangeSets => 1,
         }
     }
 
@@ -128,6 +141,18 @@ impl StaticFileSegment {
     pub const fn is_tx_based(&self) -> bool {
         matches!(self, Self::Receipts | Self::Transactions)
     }
+
+    /// Returns `true` if the segment is `StaticFileSegment::AccountChangeSets` or
+    /// `StaticFileSegment::StorageChangeSets`.
+    pub const fn is_changesets(&self) -> bool {
+        matches!(self, Self::AccountChangeSets | Self::StorageChangeSets)
+    }
+
+    /// Returns `true` if the segment stores one row per block, which is the case for
+    /// `StaticFileSegment::Headers` and the changeset segments.
+    pub const fn is_block_based(&self) -> bool {
+        !self.is_tx_based()
+    }
 }
 
 /// A segment header that contains information common to all segments. Used for storage.
@@ -229,7 +254,9 @@ impl SegmentHeader {
     /// Increments tx end range depending on segment
     pub fn increment_tx(&mut self) {
         match self.segment {
-            StaticFileSegment::Headers => (),
+            StaticFileSegment::Headers |
+            StaticFileSegment::AccountChangeSets |
+            StaticFileSegment::StorageChangeSets => (),
             StaticFileSegment::Transactions | StaticFileSegment::Receipts => {
                 if let Some(tx_range) = &mut self.tx_range {
                     tx_range.end += 1;
@@ -243,7 +270,9 @@ impl SegmentHeader {
     /// Removes `num` elements from end of tx or block range.
     pub fn prune(&mut self, num: u64) {
         match self.segment {
-            StaticFileSegment::Headers => {
+            StaticFileSegment::Headers |
+            StaticFileSegment::AccountChangeSets |
+            StaticFileSegment::StorageChangeSets => {
                 if let Some(range) = &mut self.block_range {
                     if num > range.end - range.start {
                         self.block_range = None;
@@ -287,7 +316,9 @@ impl SegmentHeader {
     /// Returns the row offset which depends on whether the segment is block or transaction based.
     pub fn start(&self) -> Option<u64> {
         match self.segment {
-            StaticFileSegment::Headers => self.block_start(),
+            StaticFileSegment::Headers |
+            StaticFileSegment::AccountChangeSets |
+            StaticFileSegment::StorageChangeSets => self.block_start(),
             StaticFileSegment::Transactions | StaticFileSegment::Receipts => self.tx_start(),
         }
     }
@@ -368,6 +399,18 @@ mod tests {
                 "static_file_transactions_1123233_11223233",
                 None,
             ),
+            (
+                StaticFileSegment::AccountChangeSets,
+                0..=499_999,
+                "static_file_account-changesets_0_499999",
+                None,
+            ),
+            (
+                StaticFileSegment::StorageChangeSets,
+                500_000..=999_999,
+                "static_file_storage-changesets_500000_999999",
+                None,
+            ),
             (
                 StaticFileSegment::Headers,
                 2..=30,
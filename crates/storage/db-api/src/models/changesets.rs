@@ -0,0 +1,145 @@
+//! Changeset related models and types.
+
+use alloy_primitives::{bytes::Buf, Address};
+use reth_codecs::{add_arbitrary_tests, Compact};
+use reth_db_models::AccountBeforeTx;
+use reth_primitives::StorageEntry;
+use serde::Serialize;
+
+/// The account changeset of a block, as it is stored in the static files.
+///
+/// Entries are sorted by address, the same way they are in the `AccountChangeSets` table.
+#[derive(Debug, Default, Eq, PartialEq, Clone, Serialize)]
+#[cfg_attr(any(test, feature = "arbitrary"), derive(arbitrary::Arbitrary, serde::Deserialize))]
+#[add_arbitrary_tests(compact)]
+pub struct StoredAccountChangeSet {
+    /// The accounts as they were before the block.
+    pub changes: Vec<AccountBeforeTx>,
+}
+
+impl Compact for StoredAccountChangeSet {
+    fn to_compact<B>(&self, buf: &mut B) -> usize
+    where
+        B: bytes::BufMut + AsMut<[u8]>,
+    {
+        let mut buffer = bytes::BytesMut::new();
+        self.changes.to_compact(&mut buffer);
+        let total_length = buffer.len();
+        buf.put(buffer);
+        total_length
+    }
+
+    fn from_compact(buf: &[u8], _len: usize) -> (Self, &[u8]) {
+        let (changes, new_buf) = Vec::from_compact(buf, buf.len());
+        (Self { changes }, new_buf)
+    }
+}
+
+/// Storage slot of an account as it was before the transaction.
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Serialize)]
+#[cfg_attr(any(test, feature = "arbitrary"), derive(arbitrary::Arbitrary, serde::Deserialize))]
+#[add_arbitrary_tests(compact)]
+pub struct StorageBeforeTx {
+    /// Address of the account.
+    pub address: Address,
+    /// Storage slot before the transaction.
+    pub entry: StorageEntry,
+}
+
+impl Compact for StorageBeforeTx {
+    fn to_compact<B>(&self, buf: &mut B) -> usize
+    where
+        B: bytes::BufMut + AsMut<[u8]>,
+    {
+        buf.put_slice(self.address.as_slice());
+        self.entry.to_compact(buf) + 20
+    }
+
+    fn from_compact(mut buf: &[u8], len: usize) -> (Self, &[u8]) {
+        // the length of an entry is read from the static file, make sure that a corrupted length
+        // can't underflow or read past the buffer
+        assert!(
+            len >= 20 && buf.len() >= len,
+            "invalid storage changeset entry length {len}, {} bytes left",
+            buf.len()
+        );
+
+        let address = Address::from_slice(&buf[..20]);
+        buf.advance(20);
+
+        let (entry, buf) = StorageEntry::from_compact(buf, len - 20);
+        (Self { address, entry }, buf)
+    }
+}
+
+/// The storage changeset of a block, as it is stored in the static files.
+///
+/// Entries are sorted by address and storage key, the same way they are in the
+/// `StorageChangeSets` table.
+#[derive(Debug, Default, Eq, PartialEq, Clone, Serialize)]
+#[cfg_attr(any(test, feature = "arbitrary"), derive(arbitrary::Arbitrary, serde::Deserialize))]
+#[add_arbitrary_tests(compact)]
+pub struct StoredStorageChangeSet {
+    /// The storage slots as they were before the block.
+    pub changes: Vec<StorageBeforeTx>,
+}
+
+impl Compact for StoredStorageChangeSet {
+    fn to_compact<B>(&self, buf: &mut B) -> usize
+    where
+        B: bytes::BufMut + AsMut<[u8]>,
+    {
+        let mut buffer = bytes::BytesMut::new();
+        self.changes.to_compact(&mut buffer);
+        let total_length = buffer.len();
+        buf.put(buffer);
+        total_length
+    }
+
+    fn from_compact(buf: &[u8], _len: usize) -> (Self, &[u8]) {
+        let (changes, new_buf) = Vec::from_compact(buf, buf.len());
+        (Self { changes }, new_buf)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::table::{Compress, Decompress};
+    use alloy_primitives::{B256, U256};
+    use reth_primitives_traits::Account;
+
+    #[test]
+    fn changesets_roundtrip() {
+        let accounts = StoredAccountChangeSet {
+            changes: vec![
+                AccountBeforeTx { address: Address::with_last_byte(1), info: None },
+                AccountBeforeTx {
+                    address: Address::with_last_byte(2),
+                    info: Some(Account { nonce: 1, ..Default::default() }),
+                },
+            ],
+        };
+        assert_eq!(
+            accounts.clone(),
+            StoredAccountChangeSet::decompress(&accounts.compress()).unwrap()
+        );
+
+        let storages = StoredStorageChangeSet {
+            changes: vec![StorageBeforeTx {
+                address: Address::with_last_byte(1),
+                entry: StorageEntry { key: B256::with_last_byte(2), value: U256::from(3) },
+            }],
+        };
+        assert_eq!(
+            storages.clone(),
+            StoredStorageChangeSet::decompress(&storages.compress()).unwrap()
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "invalid storage changeset entry length")]
+    fn storage_before_tx_rejects_short_entry() {
+        StorageBeforeTx::from_compact(&[0; 19], 19);
+    }
+}
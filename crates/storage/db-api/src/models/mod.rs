@@ -17,12 +17,14 @@ use serde::{Deserialize, Serialize};
 
 pub mod accounts;
 pub mod blocks;
+pub mod changesets;
 pub mod integer_list;
 pub mod sharded_key;
 pub mod storage_sharded_key;
 
 pub use accounts::*;
 pub use blocks::*;
+pub use changesets::*;
 pub use integer_list::IntegerList;
 pub use reth_db_models::{
     AccountBeforeTx, ClientVersion, StoredBlockBodyIndices, StoredBlockWithdrawals,
@@ -231,6 +233,8 @@ impl_compression_for_compact!(
     StageCheckpoint,
     PruneCheckpoint,
     ClientVersion,
+    StoredAccountChangeSet,
+    StoredStorageChangeSet,
     // Non-DB
     GenesisAccount
 );
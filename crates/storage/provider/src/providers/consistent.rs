@@ -767,6 +767,10 @@ impl<N: ProviderNodeTypes> BlockNumReader for ConsistentProvider<N> {
         self.storage_provider.last_block_number()
     }
 
+    fn earliest_history_height(&self) -> ProviderResult<BlockNumber> {
+        self.storage_provider.earliest_history_height()
+    }
+
     fn block_number(&self, hash: B256) -> ProviderResult<Option<BlockNumber>> {
         self.get_in_memory_or_storage_by_block(
             hash.into(),
@@ -77,7 +77,7 @@ use std::{
     cmp::Ordering,
     collections::{BTreeMap, BTreeSet},
     fmt::Debug,
-    ops::{Deref, DerefMut, Range, RangeBounds, RangeInclusive},
+    ops::{Bound, Deref, DerefMut, Range, RangeBounds, RangeInclusive},
     sync::{mpsc, Arc},
 };
 use tokio::sync::watch;
@@ -266,11 +266,7 @@ impl<TX: DbTx + DbTxMut + 'static, N: NodeTypesForProvider> DatabaseProvider<TX,
         &self,
         range: RangeInclusive<BlockNumber>,
     ) -> ProviderResult<()> {
-        let changed_accounts = self
-            .tx
-            .cursor_read::<tables::AccountChangeSets>()?
-            .walk_range(range.clone())?
-            .collect::<Result<Vec<_>, _>>()?;
+        let changed_accounts = self.account_changesets_range(range.clone())?;
 
         // Unwind account hashes. Add changed accounts to account prefix set.
         let hashed_addresses = self.unwind_account_hashing(changed_accounts.iter())?;
@@ -287,11 +283,7 @@ impl<TX: DbTx + DbTxMut + 'static, N: NodeTypesForProvider> DatabaseProvider<TX,
         self.unwind_account_history_indices(changed_accounts.iter())?;
         let storage_range = BlockNumberAddress::range(range.clone());
 
-        let changed_storages = self
-            .tx
-            .cursor_read::<tables::StorageChangeSets>()?
-            .walk_range(storage_range)?
-            .collect::<Result<Vec<_>, _>>()?;
+        let changed_storages = self.storage_changesets_range(storage_range)?;
 
         // Unwind storage hashes. Add changed account and storage keys to corresponding prefix
         // sets.
@@ -345,6 +337,69 @@ impl<TX: DbTx + DbTxMut + 'static, N: NodeTypesForProvider> DatabaseProvider<TX,
         Ok(())
     }
 
+    /// Takes the account changesets of the blocks in the range, which starts right after `block`.
+    ///
+    /// The changesets are removed from the database and, if they were already moved there, from
+    /// the static files.
+    fn take_account_changesets_above(
+        &self,
+        block: BlockNumber,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<(BlockNumber, AccountBeforeTx)>> {
+        let mut changeset = self.take::<tables::AccountChangeSets>(range)?;
+
+        // The database may still hold the changesets that were copied to the static files, so the
+        // static file changesets take precedence up to their highest block.
+        if let Some(highest) = self
+            .static_file_provider
+            .get_highest_static_file_block(StaticFileSegment::AccountChangeSets)
+            .filter(|highest| *highest > block)
+        {
+            changeset.retain(|(number, _)| *number > highest);
+            changeset.splice(
+                0..0,
+                self.static_file_provider.account_changesets_range(block + 1..=highest)?,
+            );
+            self.static_file_provider
+                .latest_writer(StaticFileSegment::AccountChangeSets)?
+                .prune_account_changesets(highest - block)?;
+        }
+
+        Ok(changeset)
+    }
+
+    /// Takes the storage changesets of the blocks in the range, which starts right after `block`.
+    ///
+    /// The changesets are removed from the database and, if they were already moved there, from
+    /// the static files.
+    fn take_storage_changesets_above(
+        &self,
+        block: BlockNumber,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<(BlockNumberAddress, StorageEntry)>> {
+        let mut changeset =
+            self.take::<tables::StorageChangeSets>(BlockNumberAddress::range(range))?;
+
+        // The database may still hold the changesets that were copied to the static files, so the
+        // static file changesets take precedence up to their highest block.
+        if let Some(highest) = self
+            .static_file_provider
+            .get_highest_static_file_block(StaticFileSegment::StorageChangeSets)
+            .filter(|highest| *highest > block)
+        {
+            changeset.retain(|(key, _)| key.block_number() > highest);
+            changeset.splice(
+                0..0,
+                self.static_file_provider.storage_changesets_range(block + 1..=highest)?,
+            );
+            self.static_file_provider
+                .latest_writer(StaticFileSegment::StorageChangeSets)?
+                .prune_storage_changesets(highest - block)?;
+        }
+
+        Ok(changeset)
+    }
+
     /// Removes receipts from all transactions starting with provided number (inclusive).
     fn remove_receipts_from(
         &self,
@@ -862,6 +917,79 @@ impl<TX: DbTxMut + DbTx + 'static, N: NodeTypes> DatabaseProvider<TX, N> {
     }
 }
 
+impl<TX: DbTx, N: NodeTypes> DatabaseProvider<TX, N> {
+    /// Returns the account changesets of the block range, ordered by block number and address.
+    ///
+    /// Blocks that were moved to the static files are read from there.
+    pub fn account_changesets_range(
+        &self,
+        range: impl RangeBounds<BlockNumber>,
+    ) -> ProviderResult<Vec<(BlockNumber, AccountBeforeTx)>> {
+        self.static_file_provider.get_range_with_static_file_or_database(
+            StaticFileSegment::AccountChangeSets,
+            to_range(range),
+            |static_file, range, _| static_file.account_changesets_range(range),
+            |range, _| {
+                Ok(self
+                    .tx
+                    .cursor_read::<tables::AccountChangeSets>()?
+                    .walk_range(range)?
+                    .collect::<Result<Vec<_>, _>>()?)
+            },
+            |_| true,
+        )
+    }
+
+    /// Returns the storage changesets of the range, ordered by block number, address and storage
+    /// key.
+    ///
+    /// Blocks that were moved to the static files are read from there.
+    pub fn storage_changesets_range(
+        &self,
+        range: impl RangeBounds<BlockNumberAddress>,
+    ) -> ProviderResult<Vec<(BlockNumberAddress, StorageEntry)>> {
+        let mut changesets = Vec::new();
+        let mut database_start = range.start_bound().cloned();
+
+        if let Some(highest_static_file_block) = self
+            .static_file_provider
+            .get_highest_static_file_block(StaticFileSegment::StorageChangeSets)
+        {
+            let start = match range.start_bound() {
+                Bound::Included(key) | Bound::Excluded(key) => key.block_number(),
+                Bound::Unbounded => 0,
+            };
+            let end = match range.end_bound() {
+                Bound::Included(key) | Bound::Excluded(key) => key.block_number(),
+                Bound::Unbounded => highest_static_file_block,
+            }
+            .min(highest_static_file_block);
+
+            if start <= highest_static_file_block {
+                changesets.extend(
+                    self.static_file_provider
+                        .storage_changesets_range(start..=end)?
+                        .into_iter()
+                        .filter(|(key, _)| range.contains(key)),
+                );
+                database_start = Bound::Included(BlockNumberAddress((
+                    highest_static_file_block + 1,
+                    Address::ZERO,
+                )));
+            }
+        }
+
+        changesets.extend(
+            self.tx
+                .cursor_read::<tables::StorageChangeSets>()?
+                .walk_range((database_start, range.end_bound().cloned()))?
+                .collect::<Result<Vec<_>, _>>()?,
+        );
+
+        Ok(changesets)
+    }
+}
+
 impl<TX: DbTx, N: NodeTypes> AccountReader for DatabaseProvider<TX, N> {
     fn basic_account(&self, address: Address) -> ProviderResult<Option<Account>> {
         Ok(self.tx.get::<tables::PlainAccountState>(address)?)
@@ -873,13 +1001,11 @@ impl<TX: DbTx, N: NodeTypes> AccountExtReader for DatabaseProvider<TX, N> {
         &self,
         range: impl RangeBounds<BlockNumber>,
     ) -> ProviderResult<BTreeSet<Address>> {
-        self.tx
-            .cursor_read::<tables::AccountChangeSets>()?
-            .walk_range(range)?
-            .map(|entry| {
-                entry.map(|(_, account_before)| account_before.address).map_err(Into::into)
-            })
-            .collect()
+        Ok(self
+            .account_changesets_range(range)?
+            .into_iter()
+            .map(|(_, account_before)| account_before.address)
+            .collect())
     }
 
     fn basic_accounts(
@@ -897,16 +1023,13 @@ impl<TX: DbTx, N: NodeTypes> AccountExtReader for DatabaseProvider<TX, N> {
         &self,
         range: RangeInclusive<BlockNumber>,
     ) -> ProviderResult<BTreeMap<Address, Vec<u64>>> {
-        let mut changeset_cursor = self.tx.cursor_read::<tables::AccountChangeSets>()?;
-
-        let account_transitions = changeset_cursor.walk_range(range)?.try_fold(
+        let account_transitions = self.account_changesets_range(range)?.into_iter().fold(
             BTreeMap::new(),
-            |mut accounts: BTreeMap<Address, Vec<u64>>, entry| -> ProviderResult<_> {
-                let (index, account) = entry?;
+            |mut accounts: BTreeMap<Address, Vec<u64>>, (index, account)| {
                 accounts.entry(account.address).or_default().push(index);
-                Ok(accounts)
+                accounts
             },
-        )?;
+        );
 
         Ok(account_transitions)
     }
@@ -917,6 +1040,14 @@ impl<TX: DbTx, N: NodeTypes> StorageChangeSetReader for DatabaseProvider<TX, N>
         &self,
         block_number: BlockNumber,
     ) -> ProviderResult<Vec<(BlockNumberAddress, StorageEntry)>> {
+        if self
+            .static_file_provider
+            .get_highest_static_file_block(StaticFileSegment::StorageChangeSets)
+            .is_some_and(|highest| highest >= block_number)
+        {
+            return self.static_file_provider.storage_changeset(block_number)
+        }
+
         let range = block_number..=block_number;
         let storage_range = BlockNumberAddress::range(range);
         self.tx
@@ -932,6 +1063,14 @@ impl<TX: DbTx, N: NodeTypes> ChangeSetReader for DatabaseProvider<TX, N> {
         &self,
         block_number: BlockNumber,
     ) -> ProviderResult<Vec<AccountBeforeTx>> {
+        if self
+            .static_file_provider
+            .get_highest_static_file_block(StaticFileSegment::AccountChangeSets)
+            .is_some_and(|highest| highest >= block_number)
+        {
+            return self.static_file_provider.account_block_changeset(block_number)
+        }
+
         let range = block_number..=block_number;
         self.tx
             .cursor_read::<tables::AccountChangeSets>()?
@@ -1155,6 +1294,10 @@ impl<TX: DbTx + 'static, N: NodeTypes> BlockNumReader for DatabaseProvider<TX, N
             .unwrap_or_default())
     }
 
+    fn earliest_history_height(&self) -> ProviderResult<BlockNumber> {
+        self.static_file_provider.earliest_history_height()
+    }
+
     fn block_number(&self, hash: B256) -> ProviderResult<Option<BlockNumber>> {
         Ok(self.tx.get::<tables::HeaderNumbers>(hash)?)
     }
@@ -1746,36 +1889,36 @@ impl<TX: DbTx + 'static, N: NodeTypes> StorageReader for DatabaseProvider<TX, N>
         &self,
         range: RangeInclusive<BlockNumber>,
     ) -> ProviderResult<BTreeMap<Address, BTreeSet<B256>>> {
-        self.tx
-            .cursor_read::<tables::StorageChangeSets>()?
-            .walk_range(BlockNumberAddress::range(range))?
+        Ok(self
+            .storage_changesets_range(BlockNumberAddress::range(range))?
+            .into_iter()
             // fold all storages and save its old state so we can remove it from HashedStorage
             // it is needed as it is dup table.
-            .try_fold(BTreeMap::new(), |mut accounts: BTreeMap<Address, BTreeSet<B256>>, entry| {
-                let (BlockNumberAddress((_, address)), storage_entry) = entry?;
-                accounts.entry(address).or_default().insert(storage_entry.key);
-                Ok(accounts)
-            })
+            .fold(
+                BTreeMap::new(),
+                |mut accounts: BTreeMap<Address, BTreeSet<B256>>,
+                 (BlockNumberAddress((_, address)), storage_entry)| {
+                    accounts.entry(address).or_default().insert(storage_entry.key);
+                    accounts
+                },
+            ))
     }
 
     fn changed_storages_and_blocks_with_range(
         &self,
         range: RangeInclusive<BlockNumber>,
     ) -> ProviderResult<BTreeMap<(Address, B256), Vec<u64>>> {
-        let mut changeset_cursor = self.tx.cursor_read::<tables::StorageChangeSets>()?;
-
         let storage_changeset_lists =
-            changeset_cursor.walk_range(BlockNumberAddress::range(range))?.try_fold(
+            self.storage_changesets_range(BlockNumberAddress::range(range))?.into_iter().fold(
                 BTreeMap::new(),
-                |mut storages: BTreeMap<(Address, B256), Vec<u64>>, entry| -> ProviderResult<_> {
-                    let (index, storage) = entry?;
+                |mut storages: BTreeMap<(Address, B256), Vec<u64>>, (index, storage)| {
                     storages
                         .entry((index.address(), storage.key))
                         .or_default()
                         .push(index.block_number());
-                    Ok(storages)
+                    storages
                 },
-            )?;
+            );
 
         Ok(storage_changeset_lists)
     }
@@ -2059,10 +2202,8 @@ impl<TX: DbTxMut + DbTx + 'static, N: NodeTypesForProvider> StateWriter
         let from_transaction_num =
             block_bodies.first().expect("already checked if there are blocks").1.first_tx_num();
 
-        let storage_range = BlockNumberAddress::range(range.clone());
-
-        let storage_changeset = self.take::<tables::StorageChangeSets>(storage_range)?;
-        let account_changeset = self.take::<tables::AccountChangeSets>(range)?;
+        let storage_changeset = self.take_storage_changesets_above(block, range.clone())?;
+        let account_changeset = self.take_account_changesets_above(block, range)?;
 
         // This is not working for blocks that are not at tip. as plain state is not the last
         // state of end range. We should rename the functions or add support to access
@@ -2157,10 +2298,8 @@ impl<TX: DbTxMut + DbTx + 'static, N: NodeTypesForProvider> StateWriter
         let to_transaction_num =
             block_bodies.last().expect("already checked if there are blocks").1.last_tx_num();
 
-        let storage_range = BlockNumberAddress::range(range.clone());
-
-        let storage_changeset = self.take::<tables::StorageChangeSets>(storage_range)?;
-        let account_changeset = self.take::<tables::AccountChangeSets>(range)?;
+        let storage_changeset = self.take_storage_changesets_above(block, range.clone())?;
+        let account_changeset = self.take_account_changesets_above(block, range)?;
 
         // This is not working for blocks that are not at tip. as plain state is not the last
         // state of end range. We should rename the functions or add support to access
@@ -2380,11 +2519,7 @@ impl<TX: DbTxMut + DbTx + 'static, N: NodeTypes> HashingWriter for DatabaseProvi
         &self,
         range: impl RangeBounds<BlockNumber>,
     ) -> ProviderResult<BTreeMap<B256, Option<Account>>> {
-        let changesets = self
-            .tx
-            .cursor_read::<tables::AccountChangeSets>()?
-            .walk_range(range)?
-            .collect::<Result<Vec<_>, _>>()?;
+        let changesets = self.account_changesets_range(range)?;
         self.unwind_account_hashing(changesets.iter())
     }
 
@@ -2444,11 +2579,7 @@ impl<TX: DbTxMut + DbTx + 'static, N: NodeTypes> HashingWriter for DatabaseProvi
         &self,
         range: impl RangeBounds<BlockNumberAddress>,
     ) -> ProviderResult<HashMap<B256, BTreeSet<B256>>> {
-        let changesets = self
-            .tx
-            .cursor_read::<tables::StorageChangeSets>()?
-            .walk_range(range)?
-            .collect::<Result<Vec<_>, _>>()?;
+        let changesets = self.storage_changesets_range(range)?;
         self.unwind_storage_hashing(changesets.into_iter())
     }
 
@@ -2611,11 +2742,7 @@ impl<TX: DbTxMut + DbTx + 'static, N: NodeTypes> HistoryWriter for DatabaseProvi
         &self,
         range: impl RangeBounds<BlockNumber>,
     ) -> ProviderResult<usize> {
-        let changesets = self
-            .tx
-            .cursor_read::<tables::AccountChangeSets>()?
-            .walk_range(range)?
-            .collect::<Result<Vec<_>, _>>()?;
+        let changesets = self.account_changesets_range(range)?;
         self.unwind_account_history_indices(changesets.iter())
     }
 
@@ -2669,11 +2796,7 @@ impl<TX: DbTxMut + DbTx + 'static, N: NodeTypes> HistoryWriter for DatabaseProvi
         &self,
         range: impl RangeBounds<BlockNumberAddress>,
     ) -> ProviderResult<usize> {
-        let changesets = self
-            .tx
-            .cursor_read::<tables::StorageChangeSets>()?
-            .walk_range(range)?
-            .collect::<Result<Vec<_>, _>>()?;
+        let changesets = self.storage_changesets_range(range)?;
         self.unwind_storage_history_indices(changesets.into_iter())
     }
 
@@ -333,6 +333,10 @@ impl<N: ProviderNodeTypes> BlockNumReader for BlockchainProvider<N> {
         self.database.last_block_number()
     }
 
+    fn earliest_history_height(&self) -> ProviderResult<BlockNumber> {
+        self.database.earliest_history_height()
+    }
+
     fn block_number(&self, hash: B256) -> ProviderResult<Option<BlockNumber>> {
         self.database.block_number(hash)
     }
@@ -1,19 +1,22 @@
 use crate::{
     providers::state::macros::delegate_provider_impls, AccountReader, BlockHashReader,
     HashedPostStateProvider, ProviderError, StateProvider, StateRootProvider,
+    StaticFileProviderFactory,
 };
 use alloy_eips::merge::EPOCH_SLOTS;
 use alloy_primitives::{
-    map::B256HashMap, Address, BlockNumber, Bytes, StorageKey, StorageValue, B256,
+    keccak256, map::B256HashMap, Address, BlockNumber, Bytes, StorageKey, StorageValue, B256,
 };
 use reth_db::{tables, BlockNumberList};
 use reth_db_api::{
     cursor::{DbCursorRO, DbDupCursorRO},
-    models::{storage_sharded_key::StorageShardedKey, ShardedKey},
+    models::{
+        storage_sharded_key::StorageShardedKey, AccountBeforeTx, BlockNumberAddress, ShardedKey,
+    },
     table::Table,
     transaction::DbTx,
 };
-use reth_primitives::{Account, Bytecode};
+use reth_primitives::{Account, Bytecode, StaticFileSegment};
 use reth_storage_api::{
     BlockNumReader, DBProvider, StateCommitmentProvider, StateProofProvider, StorageRootProvider,
 };
@@ -22,8 +25,8 @@ use reth_trie::{
     proof::{Proof, StorageProof},
     updates::TrieUpdates,
     witness::TrieWitness,
-    AccountProof, HashedPostState, HashedStorage, MultiProof, MultiProofTargets, StateRoot,
-    StorageMultiProof, StorageRoot, TrieInput,
+    AccountProof, HashedPostState, HashedStorage, KeyHasher, MultiProof, MultiProofTargets,
+    StateRoot, StorageMultiProof, StorageRoot, TrieInput,
 };
 use reth_trie_db::{
     DatabaseHashedPostState, DatabaseHashedStorage, DatabaseProof, DatabaseStateRoot,
@@ -60,8 +63,10 @@ pub enum HistoryInfo {
     MaybeInPlainState,
 }
 
-impl<'b, Provider: DBProvider + BlockNumReader + StateCommitmentProvider>
-    HistoricalStateProviderRef<'b, Provider>
+impl<
+        'b,
+        Provider: DBProvider + BlockNumReader + StaticFileProviderFactory + StateCommitmentProvider,
+    > HistoricalStateProviderRef<'b, Provider>
 {
     /// Create new `StateProvider` for historical block number
     pub fn new(provider: &'b Provider, block_number: BlockNumber) -> Self {
@@ -135,9 +140,52 @@ impl<'b, Provider: DBProvider + BlockNumReader + StateCommitmentProvider>
             );
         }
 
-        Ok(HashedPostState::from_reverts::<
-            <Provider::StateCommitment as StateCommitment>::KeyHasher,
-        >(self.tx(), self.block_number)?)
+        type KeyHasherOf<P> =
+            <<P as StateCommitmentProvider>::StateCommitment as StateCommitment>::KeyHasher;
+
+        let mut state =
+            HashedPostState::from_reverts::<KeyHasherOf<Provider>>(self.tx(), self.block_number)?;
+        // Changesets of earlier blocks take precedence, so the ones in the static files override
+        // the ones in the database.
+        state.extend(self.static_file_revert_state::<KeyHasherOf<Provider>>()?);
+
+        Ok(state)
+    }
+
+    /// Retrieve revert hashed state of the changesets that were moved to the static files.
+    fn static_file_revert_state<KH: KeyHasher>(&self) -> ProviderResult<HashedPostState> {
+        let static_file_provider = self.provider.static_file_provider();
+        let mut state = HashedPostState::default();
+
+        if let Some(highest_block) = static_file_provider
+            .get_highest_static_file_block(StaticFileSegment::AccountChangeSets)
+            .filter(|highest_block| *highest_block >= self.block_number)
+        {
+            for (_, AccountBeforeTx { address, info }) in
+                static_file_provider.account_changesets_range(self.block_number..=highest_block)?
+            {
+                state.accounts.entry(KH::hash_key(address)).or_insert(info);
+            }
+        }
+
+        if let Some(highest_block) = static_file_provider
+            .get_highest_static_file_block(StaticFileSegment::StorageChangeSets)
+            .filter(|highest_block| *highest_block >= self.block_number)
+        {
+            for (BlockNumberAddress((_, address)), entry) in
+                static_file_provider.storage_changesets_range(self.block_number..=highest_block)?
+            {
+                state
+                    .storages
+                    .entry(KH::hash_key(address))
+                    .or_insert_with(|| HashedStorage::new(false))
+                    .storage
+                    .entry(KH::hash_key(entry.key))
+                    .or_insert(entry.value);
+            }
+        }
+
+        Ok(state)
     }
 
     /// Retrieve revert hashed storage for this history provider and target address.
@@ -154,7 +202,27 @@ impl<'b, Provider: DBProvider + BlockNumReader + StateCommitmentProvider>
             );
         }
 
-        Ok(HashedStorage::from_reverts(self.tx(), address, self.block_number)?)
+        let mut storage = HashedStorage::from_reverts(self.tx(), address, self.block_number)?;
+
+        // Changesets of earlier blocks take precedence, so the ones in the static files override
+        // the ones in the database.
+        let static_file_provider = self.provider.static_file_provider();
+        if let Some(highest_block) = static_file_provider
+            .get_highest_static_file_block(StaticFileSegment::StorageChangeSets)
+            .filter(|highest_block| *highest_block >= self.block_number)
+        {
+            let mut static_file_storage = HashedStorage::new(false);
+            for (_, entry) in static_file_provider
+                .storage_changesets_range(self.block_number..=highest_block)?
+                .into_iter()
+                .filter(|(key, _)| key.address() == address)
+            {
+                static_file_storage.storage.entry(keccak256(entry.key)).or_insert(entry.value);
+            }
+            storage.extend(&static_file_storage);
+        }
+
+        Ok(storage)
     }
 
     fn history_info<T, K>(
@@ -245,18 +313,31 @@ impl<Provider: DBProvider + BlockNumReader> HistoricalStateProviderRef<'_, Provi
     }
 }
 
-impl<Provider: DBProvider + BlockNumReader + StateCommitmentProvider> AccountReader
-    for HistoricalStateProviderRef<'_, Provider>
+impl<
+        Provider: DBProvider + BlockNumReader + StaticFileProviderFactory + StateCommitmentProvider,
+    > AccountReader for HistoricalStateProviderRef<'_, Provider>
 {
     /// Get basic account information.
     fn basic_account(&self, address: Address) -> ProviderResult<Option<Account>> {
         match self.account_history_lookup(address)? {
             HistoryInfo::NotYetWritten => Ok(None),
             HistoryInfo::InChangeset(changeset_block_number) => Ok(self
-                .tx()
-                .cursor_dup_read::<tables::AccountChangeSets>()?
-                .seek_by_key_subkey(changeset_block_number, address)?
-                .filter(|acc| acc.address == address)
+                .provider
+                .static_file_provider()
+                .get_with_static_file_or_database(
+                    StaticFileSegment::AccountChangeSets,
+                    changeset_block_number,
+                    |static_file| {
+                        static_file.account_changeset_entry(changeset_block_number, address)
+                    },
+                    || {
+                        Ok(self
+                            .tx()
+                            .cursor_dup_read::<tables::AccountChangeSets>()?
+                            .seek_by_key_subkey(changeset_block_number, address)?
+                            .filter(|acc| acc.address == address))
+                    },
+                )?
                 .ok_or(ProviderError::AccountChangesetNotFound {
                     block_number: changeset_block_number,
                     address,
@@ -286,8 +367,9 @@ impl<Provider: DBProvider + BlockNumReader + BlockHashReader> BlockHashReader
     }
 }
 
-impl<Provider: DBProvider + BlockNumReader + StateCommitmentProvider> StateRootProvider
-    for HistoricalStateProviderRef<'_, Provider>
+impl<
+        Provider: DBProvider + BlockNumReader + StaticFileProviderFactory + StateCommitmentProvider,
+    > StateRootProvider for HistoricalStateProviderRef<'_, Provider>
 {
     fn state_root(&self, hashed_state: HashedPostState) -> ProviderResult<B256> {
         let mut revert_state = self.revert_state()?;
@@ -322,8 +404,9 @@ impl<Provider: DBProvider + BlockNumReader + StateCommitmentProvider> StateRootP
     }
 }
 
-impl<Provider: DBProvider + BlockNumReader + StateCommitmentProvider> StorageRootProvider
-    for HistoricalStateProviderRef<'_, Provider>
+impl<
+        Provider: DBProvider + BlockNumReader + StaticFileProviderFactory + StateCommitmentProvider,
+    > StorageRootProvider for HistoricalStateProviderRef<'_, Provider>
 {
     fn storage_root(
         &self,
@@ -361,8 +444,9 @@ impl<Provider: DBProvider + BlockNumReader + StateCommitmentProvider> StorageRoo
     }
 }
 
-impl<Provider: DBProvider + BlockNumReader + StateCommitmentProvider> StateProofProvider
-    for HistoricalStateProviderRef<'_, Provider>
+impl<
+        Provider: DBProvider + BlockNumReader + StaticFileProviderFactory + StateCommitmentProvider,
+    > StateProofProvider for HistoricalStateProviderRef<'_, Provider>
 {
     /// Get account and storage proofs.
     fn proof(
@@ -404,8 +488,13 @@ impl<Provider: StateCommitmentProvider> HashedPostStateProvider
     }
 }
 
-impl<Provider: DBProvider + BlockNumReader + BlockHashReader + StateCommitmentProvider>
-    StateProvider for HistoricalStateProviderRef<'_, Provider>
+impl<
+        Provider: DBProvider
+            + BlockNumReader
+            + BlockHashReader
+            + StaticFileProviderFactory
+            + StateCommitmentProvider,
+    > StateProvider for HistoricalStateProviderRef<'_, Provider>
 {
     /// Get storage.
     fn storage(
@@ -416,10 +505,29 @@ impl<Provider: DBProvider + BlockNumReader + BlockHashReader + StateCommitmentPr
         match self.storage_history_lookup(address, storage_key)? {
             HistoryInfo::NotYetWritten => Ok(None),
             HistoryInfo::InChangeset(changeset_block_number) => Ok(Some(
-                self.tx()
-                    .cursor_dup_read::<tables::StorageChangeSets>()?
-                    .seek_by_key_subkey((changeset_block_number, address).into(), storage_key)?
-                    .filter(|entry| entry.key == storage_key)
+                self.provider
+                    .static_file_provider()
+                    .get_with_static_file_or_database(
+                        StaticFileSegment::StorageChangeSets,
+                        changeset_block_number,
+                        |static_file| {
+                            static_file.storage_changeset_entry(
+                                changeset_block_number,
+                                address,
+                                storage_key,
+                            )
+                        },
+                        || {
+                            Ok(self
+                                .tx()
+                                .cursor_dup_read::<tables::StorageChangeSets>()?
+                                .seek_by_key_subkey(
+                                    (changeset_block_number, address).into(),
+                                    storage_key,
+                                )?
+                                .filter(|entry| entry.key == storage_key))
+                        },
+                    )?
                     .ok_or_else(|| ProviderError::StorageChangesetNotFound {
                         block_number: changeset_block_number,
                         address,
@@ -461,8 +569,9 @@ pub struct HistoricalStateProvider<Provider> {
     lowest_available_blocks: LowestAvailableBlocks,
 }
 
-impl<Provider: DBProvider + BlockNumReader + StateCommitmentProvider>
-    HistoricalStateProvider<Provider>
+impl<
+        Provider: DBProvider + BlockNumReader + StaticFileProviderFactory + StateCommitmentProvider,
+    > HistoricalStateProvider<Provider>
 {
     /// Create new `StateProvider` for historical block number
     pub fn new(provider: Provider, block_number: BlockNumber) -> Self {
@@ -505,7 +614,7 @@ impl<Provider: StateCommitmentProvider> StateCommitmentProvider
 }
 
 // Delegates all provider impls to [HistoricalStateProviderRef]
-delegate_provider_impls!(HistoricalStateProvider<Provider> where [Provider: DBProvider + BlockNumReader + BlockHashReader + StateCommitmentProvider]);
+delegate_provider_impls!(HistoricalStateProvider<Provider> where [Provider: DBProvider + BlockNumReader + BlockHashReader + StaticFileProviderFactory + StateCommitmentProvider]);
 
 /// Lowest blocks at which different parts of the state are available.
 /// They may be [Some] if pruning is enabled.
@@ -538,17 +647,24 @@ impl LowestAvailableBlocks {
 #[cfg(test)]
 mod tests {
     use crate::{
-        providers::state::historical::{HistoryInfo, LowestAvailableBlocks},
+        providers::{
+            state::historical::{HistoryInfo, LowestAvailableBlocks},
+            StaticFileWriter,
+        },
         test_utils::create_test_provider_factory,
         AccountReader, HistoricalStateProvider, HistoricalStateProviderRef, StateProvider,
+        StaticFileProviderFactory,
     };
     use alloy_primitives::{address, b256, Address, B256, U256};
     use reth_db::{tables, BlockNumberList};
     use reth_db_api::{
-        models::{storage_sharded_key::StorageShardedKey, AccountBeforeTx, ShardedKey},
+        models::{
+            storage_sharded_key::StorageShardedKey, AccountBeforeTx, ShardedKey, StorageBeforeTx,
+            StoredAccountChangeSet, StoredStorageChangeSet,
+        },
         transaction::{DbTx, DbTxMut},
     };
-    use reth_primitives::{Account, StorageEntry};
+    use reth_primitives::{Account, StaticFileSegment, StorageEntry};
     use reth_storage_api::{
         BlockHashReader, BlockNumReader, DBProvider, DatabaseProviderFactory,
         StateCommitmentProvider,
@@ -562,7 +678,11 @@ mod tests {
     const fn assert_state_provider<T: StateProvider>() {}
     #[allow(dead_code)]
     const fn assert_historical_state_provider<
-        T: DBProvider + BlockNumReader + BlockHashReader + StateCommitmentProvider,
+        T: DBProvider
+            + BlockNumReader
+            + BlockHashReader
+            + StaticFileProviderFactory
+            + StateCommitmentProvider,
     >() {
         assert_state_provider::<HistoricalStateProvider<T>>();
     }
@@ -674,6 +794,82 @@ mod tests {
         );
     }
 
+    #[test]
+    fn history_provider_get_from_static_files() {
+        let factory = create_test_provider_factory();
+        let tx = factory.provider_rw().unwrap().into_tx();
+
+        tx.put::<tables::AccountsHistory>(
+            ShardedKey { key: ADDRESS, highest_block_number: u64::MAX },
+            BlockNumberList::new([1, 3]).unwrap(),
+        )
+        .unwrap();
+        tx.put::<tables::StoragesHistory>(
+            StorageShardedKey {
+                address: ADDRESS,
+                sharded_key: ShardedKey { key: STORAGE, highest_block_number: u64::MAX },
+            },
+            BlockNumberList::new([3]).unwrap(),
+        )
+        .unwrap();
+
+        let acc_plain = Account { nonce: 100, balance: U256::ZERO, bytecode_hash: None };
+        let acc_at3 = Account { nonce: 3, balance: U256::ZERO, bytecode_hash: None };
+        let entry_plain = StorageEntry { key: STORAGE, value: U256::from(100) };
+        let entry_at3 = StorageEntry { key: STORAGE, value: U256::from(3) };
+
+        tx.put::<tables::PlainAccountState>(ADDRESS, acc_plain).unwrap();
+        tx.put::<tables::PlainStorageState>(ADDRESS, entry_plain).unwrap();
+        tx.commit().unwrap();
+
+        // the changesets only exist in the static files
+        let static_file_provider = factory.static_file_provider();
+        let mut account_writer =
+            static_file_provider.latest_writer(StaticFileSegment::AccountChangeSets).unwrap();
+        let mut storage_writer =
+            static_file_provider.latest_writer(StaticFileSegment::StorageChangeSets).unwrap();
+        for block in 0..=3 {
+            let mut account_changeset = StoredAccountChangeSet::default();
+            let mut storage_changeset = StoredStorageChangeSet::default();
+            if block == 1 {
+                account_changeset.changes.push(AccountBeforeTx { address: ADDRESS, info: None });
+            }
+            if block == 3 {
+                account_changeset
+                    .changes
+                    .push(AccountBeforeTx { address: ADDRESS, info: Some(acc_at3) });
+                storage_changeset
+                    .changes
+                    .push(StorageBeforeTx { address: ADDRESS, entry: entry_at3 });
+            }
+            account_writer.append_account_changeset(block, &account_changeset).unwrap();
+            storage_writer.append_storage_changeset(block, &storage_changeset).unwrap();
+        }
+        account_writer.commit().unwrap();
+        storage_writer.commit().unwrap();
+        drop((account_writer, storage_writer));
+
+        let db = factory.provider().unwrap();
+
+        assert_eq!(HistoricalStateProviderRef::new(&db, 1).basic_account(ADDRESS), Ok(None));
+        assert_eq!(
+            HistoricalStateProviderRef::new(&db, 2).basic_account(ADDRESS),
+            Ok(Some(acc_at3))
+        );
+        assert_eq!(
+            HistoricalStateProviderRef::new(&db, 4).basic_account(ADDRESS),
+            Ok(Some(acc_plain))
+        );
+        assert_eq!(
+            HistoricalStateProviderRef::new(&db, 3).storage(ADDRESS, STORAGE),
+            Ok(Some(entry_at3.value))
+        );
+        assert_eq!(
+            HistoricalStateProviderRef::new(&db, 4).storage(ADDRESS, STORAGE),
+            Ok(Some(entry_plain.value))
+        );
+    }
+
     #[test]
     fn history_provider_get_storage() {
         let factory = create_test_provider_factory();
@@ -3,21 +3,23 @@ use super::{
     LoadedJarRef,
 };
 use crate::{
-    to_range, BlockHashReader, BlockNumReader, HeaderProvider, ReceiptProvider,
-    TransactionsProvider,
+    to_range, BlockHashReader, BlockNumReader, ChangeSetReader, HeaderProvider, ReceiptProvider,
+    StorageChangeSetReader, TransactionsProvider,
 };
 use alloy_eips::{eip2718::Encodable2718, BlockHashOrNumber};
 use alloy_primitives::{Address, BlockHash, BlockNumber, TxHash, TxNumber, B256, U256};
 use reth_chainspec::ChainInfo;
 use reth_db::{
     static_file::{
-        BlockHashMask, HeaderMask, HeaderWithHashMask, ReceiptMask, StaticFileCursor,
-        TDWithHashMask, TotalDifficultyMask, TransactionMask,
+        AccountChangeSetMask, BlockHashMask, HeaderMask, HeaderWithHashMask, ReceiptMask,
+        StaticFileCursor, StorageChangeSetMask, TDWithHashMask, TotalDifficultyMask,
+        TransactionMask,
     },
     table::{Decompress, Value},
 };
+use reth_db_api::models::{AccountBeforeTx, BlockNumberAddress};
 use reth_node_types::NodePrimitives;
-use reth_primitives::{transaction::recover_signers, SealedHeader, TransactionMeta};
+use reth_primitives::{transaction::recover_signers, SealedHeader, StorageEntry, TransactionMeta};
 use reth_primitives_traits::SignedTransaction;
 use reth_storage_errors::provider::{ProviderError, ProviderResult};
 use std::{
@@ -350,3 +352,35 @@ impl<N: NodePrimitives<SignedTx: Decompress + SignedTransaction, Receipt: Decomp
         Ok(receipts)
     }
 }
+
+impl<N: NodePrimitives> ChangeSetReader for StaticFileJarProvider<'_, N> {
+    fn account_block_changeset(
+        &self,
+        block_number: BlockNumber,
+    ) -> ProviderResult<Vec<AccountBeforeTx>> {
+        Ok(self
+            .cursor()?
+            .get_one::<AccountChangeSetMask>(block_number.into())?
+            .map(|changeset| changeset.changes)
+            .unwrap_or_default())
+    }
+}
+
+impl<N: NodePrimitives> StorageChangeSetReader for StaticFileJarProvider<'_, N> {
+    fn storage_changeset(
+        &self,
+        block_number: BlockNumber,
+    ) -> ProviderResult<Vec<(BlockNumberAddress, StorageEntry)>> {
+        Ok(self
+            .cursor()?
+            .get_one::<StorageChangeSetMask>(block_number.into())?
+            .map(|changeset| {
+                changeset
+                    .changes
+                    .into_iter()
+                    .map(|change| ((block_number, change.address).into(), change.entry))
+                    .collect()
+            })
+            .unwrap_or_default())
+    }
+}
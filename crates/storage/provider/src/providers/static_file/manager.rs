@@ -3,9 +3,9 @@ use super::{
     StaticFileJarProvider, StaticFileProviderRW, StaticFileProviderRWRefMut,
 };
 use crate::{
-    to_range, BlockHashReader, BlockNumReader, BlockReader, BlockSource, HeaderProvider,
-    ReceiptProvider, StageCheckpointReader, StatsReader, TransactionVariant, TransactionsProvider,
-    TransactionsProviderExt, WithdrawalsProvider,
+    to_range, BlockHashReader, BlockNumReader, BlockReader, BlockSource, ChangeSetReader,
+    HeaderProvider, ReceiptProvider, StageCheckpointReader, StatsReader, StorageChangeSetReader,
+    TransactionVariant, TransactionsProvider, TransactionsProviderExt, WithdrawalsProvider,
 };
 use alloy_consensus::Header;
 use alloy_eips::{
@@ -21,14 +21,17 @@ use reth_chainspec::{ChainInfo, ChainSpecProvider};
 use reth_db::{
     lockfile::StorageLock,
     static_file::{
-        iter_static_files, BlockHashMask, HeaderMask, HeaderWithHashMask, ReceiptMask,
-        StaticFileCursor, TDWithHashMask, TransactionMask,
+        iter_static_files, AccountChangeSetMask, BlockHashMask, HeaderMask, HeaderWithHashMask,
+        ReceiptMask, StaticFileCursor, StorageChangeSetMask, TDWithHashMask, TransactionMask,
     },
     table::{Decompress, Value},
     tables,
 };
 use reth_db_api::{
-    cursor::DbCursorRO, models::StoredBlockBodyIndices, table::Table, transaction::DbTx,
+    cursor::DbCursorRO,
+    models::{AccountBeforeTx, BlockNumberAddress, StoredBlockBodyIndices},
+    table::Table,
+    transaction::DbTx,
 };
 use reth_nippy_jar::{NippyJar, NippyJarChecker, CONFIG_FILE_EXTENSION};
 use reth_node_types::{FullNodePrimitives, NodePrimitives};
@@ -39,7 +42,7 @@ use reth_primitives::{
     },
     transaction::recover_signers,
     BlockWithSenders, Receipt, SealedBlockFor, SealedBlockWithSenders, SealedHeader,
-    StaticFileSegment, TransactionMeta, TransactionSigned,
+    StaticFileSegment, StorageEntry, TransactionMeta, TransactionSigned,
 };
 use reth_primitives_traits::SignedTransaction;
 use reth_stages_types::{PipelineTarget, StageId};
@@ -220,6 +223,8 @@ pub struct StaticFileProviderInner<N> {
     map: DashMap<(BlockNumber, StaticFileSegment), LoadedJar>,
     /// Max static file block for each segment
     static_files_max_block: RwLock<HashMap<StaticFileSegment, u64>>,
+    /// Min static file block for each segment. Static files below it have been deleted.
+    static_files_min_block: RwLock<HashMap<StaticFileSegment, u64>>,
     /// Available static file block ranges on disk indexed by max transactions.
     static_files_tx_index: RwLock<SegmentRanges>,
     /// Directory where `static_files` are located
@@ -251,6 +256,7 @@ impl<N: NodePrimitives> StaticFileProviderInner<N> {
             map: Default::default(),
             writers: Default::default(),
             static_files_max_block: Default::default(),
+            static_files_min_block: Default::default(),
             static_files_tx_index: Default::default(),
             path: path.as_ref().to_path_buf(),
             metrics: None,
@@ -436,6 +442,52 @@ impl<N: NodePrimitives> StaticFileProvider<N> {
         Ok(())
     }
 
+    /// Given a segment and block, it deletes all jars whose block range ends before the block.
+    /// The jar holding the highest block of the segment is never deleted.
+    ///
+    /// Returns the [`SegmentHeader`]s of the deleted jars.
+    ///
+    /// CAUTION: destructive. Deletes files on disk.
+    pub fn delete_segment_below_block(
+        &self,
+        segment: StaticFileSegment,
+        block: BlockNumber,
+    ) -> ProviderResult<Vec<SegmentHeader>> {
+        let Some(highest_block) = self.get_highest_static_file_block(segment) else {
+            return Ok(Vec::new())
+        };
+        let highest_block_range = self.find_fixed_range(highest_block);
+
+        let mut deleted = Vec::new();
+        while let Some(lowest_block) = self.get_lowest_static_file_block(segment) {
+            let fixed_block_range = self.find_fixed_range(lowest_block);
+            if fixed_block_range.end() >= block ||
+                fixed_block_range.end() >= highest_block_range.start()
+            {
+                break
+            }
+
+            let key = (fixed_block_range.end(), segment);
+            let jar = if let Some((_, jar)) = self.map.remove(&key) {
+                jar.jar
+            } else {
+                NippyJar::<SegmentHeader>::load(
+                    &self.path.join(segment.filename(&fixed_block_range)),
+                )
+                .map_err(|e| ProviderError::NippyJar(e.to_string()))?
+            };
+            deleted.push(jar.user_header().clone());
+            jar.delete().map_err(|e| ProviderError::NippyJar(e.to_string()))?;
+
+            self.static_files_min_block.write().insert(segment, fixed_block_range.end() + 1);
+            if let Some(index) = self.static_files_tx_index.write().get_mut(&segment) {
+                index.retain(|_, block_range| block_range.start() > fixed_block_range.end());
+            }
+        }
+
+        Ok(deleted)
+    }
+
     /// Given a segment and block range it returns a cached
     /// [`StaticFileJarProvider`]. TODO(joshie): we should check the size and pop N if there's too
     /// many.
@@ -471,6 +523,10 @@ impl<N: NodePrimitives> StaticFileProvider<N> {
         segment: StaticFileSegment,
         block: u64,
     ) -> Option<SegmentRangeInclusive> {
+        if self.get_lowest_static_file_block(segment).is_some_and(|lowest| lowest > block) {
+            return None
+        }
+
         self.static_files_max_block
             .read()
             .get(&segment)
@@ -517,6 +573,7 @@ impl<N: NodePrimitives> StaticFileProvider<N> {
         segment_max_block: Option<BlockNumber>,
     ) -> ProviderResult<()> {
         let mut max_block = self.static_files_max_block.write();
+        let mut min_block = self.static_files_min_block.write();
         let mut tx_index = self.static_files_tx_index.write();
 
         match segment_max_block {
@@ -524,6 +581,7 @@ impl<N: NodePrimitives> StaticFileProvider<N> {
                 // Update the max block for the segment
                 max_block.insert(segment, segment_max_block);
                 let fixed_range = self.find_fixed_range(segment_max_block);
+                min_block.entry(segment).or_insert_with(|| fixed_range.start());
 
                 let jar = NippyJar::<SegmentHeader>::load(
                     &self.path.join(segment.filename(&fixed_range)),
@@ -579,6 +637,7 @@ impl<N: NodePrimitives> StaticFileProvider<N> {
             None => {
                 tx_index.remove(&segment);
                 max_block.remove(&segment);
+                min_block.remove(&segment);
             }
         };
 
@@ -588,9 +647,11 @@ impl<N: NodePrimitives> StaticFileProvider<N> {
     /// Initializes the inner transaction and block index
     pub fn initialize_index(&self) -> ProviderResult<()> {
         let mut max_block = self.static_files_max_block.write();
+        let mut min_block = self.static_files_min_block.write();
         let mut tx_index = self.static_files_tx_index.write();
 
         max_block.clear();
+        min_block.clear();
         tx_index.clear();
 
         for (segment, ranges) in
@@ -601,6 +662,11 @@ impl<N: NodePrimitives> StaticFileProvider<N> {
                 max_block.insert(segment, block_range.end());
             }
 
+            // Update first block for each segment
+            if let Some((block_range, _)) = ranges.first() {
+                min_block.insert(segment, self.find_fixed_range(block_range.start()).start());
+            }
+
             // Update tx -> block_range index
             for (block_range, tx_range) in ranges {
                 if let Some(tx_range) = tx_range {
@@ -781,6 +847,9 @@ impl<N: NodePrimitives> StaticFileProvider<N> {
                     highest_tx,
                     highest_block,
                 )?,
+                StaticFileSegment::AccountChangeSets | StaticFileSegment::StorageChangeSets => {
+                    self.ensure_changeset_invariants(provider, segment, highest_block)?
+                }
             } {
                 update_unwind_target(unwind);
             }
@@ -865,7 +934,9 @@ impl<N: NodePrimitives> StaticFileProvider<N> {
             .get_stage_checkpoint(match segment {
                 StaticFileSegment::Headers => StageId::Headers,
                 StaticFileSegment::Transactions => StageId::Bodies,
-                StaticFileSegment::Receipts => StageId::Execution,
+                StaticFileSegment::Receipts |
+                StaticFileSegment::AccountChangeSets |
+                StaticFileSegment::StorageChangeSets => StageId::Execution,
             })?
             .unwrap_or_default()
             .block_number;
@@ -910,6 +981,56 @@ impl<N: NodePrimitives> StaticFileProvider<N> {
         Ok(None)
     }
 
+    /// Check invariants of a changeset static file segment:
+    ///
+    /// * its highest block should not be higher than the [`StageId::Execution`] checkpoint. If it
+    ///   is, then we failed to do a database commit **but committed** to static files on unwinding
+    ///   a stage, and the extra static file rows are pruned.
+    ///
+    /// Changesets are only moved to static files after they have been written to the database, so
+    /// a checkpoint ahead of the static files is expected, and [`None`] is always returned.
+    fn ensure_changeset_invariants<Provider>(
+        &self,
+        provider: &Provider,
+        segment: StaticFileSegment,
+        highest_static_file_block: Option<BlockNumber>,
+    ) -> ProviderResult<Option<BlockNumber>>
+    where
+        Provider: StageCheckpointReader,
+    {
+        let Some(highest_static_file_block) = highest_static_file_block else { return Ok(None) };
+        let checkpoint_block_number =
+            provider.get_stage_checkpoint(StageId::Execution)?.unwrap_or_default().block_number;
+
+        if checkpoint_block_number < highest_static_file_block {
+            info!(
+                target: "reth::providers",
+                ?segment,
+                from = highest_static_file_block,
+                to = checkpoint_block_number,
+                "Unwinding static file segment."
+            );
+            let to_delete = highest_static_file_block - checkpoint_block_number;
+            let mut writer = self.latest_writer(segment)?;
+            if segment == StaticFileSegment::AccountChangeSets {
+                writer.prune_account_changesets(to_delete)?;
+            } else {
+                writer.prune_storage_changesets(to_delete)?;
+            }
+            writer.commit()?;
+        }
+
+        Ok(None)
+    }
+
+    /// Gets the lowest static file block if it exists for a static file segment.
+    ///
+    /// Static files of the segment below this block have been deleted, e.g. by the pruner. If
+    /// there is nothing on disk for the given segment, this will return [`None`].
+    pub fn get_lowest_static_file_block(&self, segment: StaticFileSegment) -> Option<BlockNumber> {
+        self.static_files_min_block.read().get(&segment).copied()
+    }
+
     /// Gets the highest static file block if it exists for a static file segment.
     ///
     /// If there is nothing on disk for the given segment, this will return [`None`].
@@ -933,6 +1054,10 @@ impl<N: NodePrimitives> StaticFileProvider<N> {
             headers: self.get_highest_static_file_block(StaticFileSegment::Headers),
             receipts: self.get_highest_static_file_block(StaticFileSegment::Receipts),
             transactions: self.get_highest_static_file_block(StaticFileSegment::Transactions),
+            account_changesets: self
+                .get_highest_static_file_block(StaticFileSegment::AccountChangeSets),
+            storage_changesets: self
+                .get_highest_static_file_block(StaticFileSegment::StorageChangeSets),
         }
     }
 
@@ -944,8 +1069,9 @@ impl<N: NodePrimitives> StaticFileProvider<N> {
         func: impl Fn(StaticFileJarProvider<'_, N>) -> ProviderResult<Option<T>>,
     ) -> ProviderResult<Option<T>> {
         if let Some(highest_block) = self.get_highest_static_file_block(segment) {
+            let lowest_block = self.get_lowest_static_file_block(segment).unwrap_or_default();
             let mut range = self.find_fixed_range(highest_block);
-            while range.end() > 0 {
+            while range.end() > 0 && range.end() >= lowest_block {
                 if let Some(res) = func(self.get_or_create_jar_provider(segment, &range)?)? {
                     return Ok(Some(res))
                 }
@@ -976,7 +1102,9 @@ impl<N: NodePrimitives> StaticFileProvider<N> {
         P: FnMut(&T) -> bool,
     {
         let get_provider = |start: u64| match segment {
-            StaticFileSegment::Headers => {
+            StaticFileSegment::Headers |
+            StaticFileSegment::AccountChangeSets |
+            StaticFileSegment::StorageChangeSets => {
                 self.get_segment_provider_from_block(segment, start, None)
             }
             StaticFileSegment::Transactions | StaticFileSegment::Receipts => {
@@ -1014,7 +1142,7 @@ impl<N: NodePrimitives> StaticFileProvider<N> {
                                 "Could not find block or tx number on a range request"
                             );
 
-                            let err = if segment.is_headers() {
+                            let err = if segment.is_block_based() {
                                 ProviderError::MissingStaticFileBlock(segment, number)
                             } else {
                                 ProviderError::MissingStaticFileTx(segment, number)
@@ -1052,7 +1180,9 @@ impl<N: NodePrimitives> StaticFileProvider<N> {
         T: std::fmt::Debug,
     {
         let get_provider = move |start: u64| match segment {
-            StaticFileSegment::Headers => {
+            StaticFileSegment::Headers |
+            StaticFileSegment::AccountChangeSets |
+            StaticFileSegment::StorageChangeSets => {
                 self.get_segment_provider_from_block(segment, start, None)
             }
             StaticFileSegment::Transactions | StaticFileSegment::Receipts => {
@@ -1104,7 +1234,9 @@ impl<N: NodePrimitives> StaticFileProvider<N> {
     {
         // If there is, check the maximum block or transaction number of the segment.
         let static_file_upper_bound = match segment {
-            StaticFileSegment::Headers => self.get_highest_static_file_block(segment),
+            StaticFileSegment::Headers |
+            StaticFileSegment::AccountChangeSets |
+            StaticFileSegment::StorageChangeSets => self.get_highest_static_file_block(segment),
             StaticFileSegment::Transactions | StaticFileSegment::Receipts => {
                 self.get_highest_static_file_tx(segment)
             }
@@ -1146,7 +1278,9 @@ impl<N: NodePrimitives> StaticFileProvider<N> {
 
         // If there is, check the maximum block or transaction number of the segment.
         if let Some(static_file_upper_bound) = match segment {
-            StaticFileSegment::Headers => self.get_highest_static_file_block(segment),
+            StaticFileSegment::Headers |
+            StaticFileSegment::AccountChangeSets |
+            StaticFileSegment::StorageChangeSets => self.get_highest_static_file_block(segment),
             StaticFileSegment::Transactions | StaticFileSegment::Receipts => {
                 self.get_highest_static_file_tx(segment)
             }
@@ -1169,6 +1303,96 @@ impl<N: NodePrimitives> StaticFileProvider<N> {
         Ok(data)
     }
 
+    /// Returns the account of `address` as it was before `block_number`, if it was changed in the
+    /// block.
+    pub fn account_changeset_entry(
+        &self,
+        block_number: BlockNumber,
+        address: Address,
+    ) -> ProviderResult<Option<AccountBeforeTx>> {
+        let mut changeset = self.account_block_changeset(block_number)?;
+        Ok(changeset
+            .binary_search_by_key(&address, |change| change.address)
+            .ok()
+            .map(|index| changeset.swap_remove(index)))
+    }
+
+    /// Returns the storage slot `key` of `address` as it was before `block_number`, if it was
+    /// changed in the block.
+    pub fn storage_changeset_entry(
+        &self,
+        block_number: BlockNumber,
+        address: Address,
+        key: B256,
+    ) -> ProviderResult<Option<StorageEntry>> {
+        let changeset = self
+            .get_segment_provider_from_block(
+                StaticFileSegment::StorageChangeSets,
+                block_number,
+                None,
+            )?
+            .cursor()?
+            .get_one::<StorageChangeSetMask>(block_number.into())?
+            .unwrap_or_default();
+        Ok(changeset
+            .changes
+            .binary_search_by(|change| (change.address, change.entry.key).cmp(&(address, key)))
+            .ok()
+            .map(|index| changeset.changes[index].entry))
+    }
+
+    /// Returns the account changesets of the block range, ordered by block number and address.
+    pub fn account_changesets_range(
+        &self,
+        range: impl RangeBounds<BlockNumber>,
+    ) -> ProviderResult<Vec<(BlockNumber, AccountBeforeTx)>> {
+        let changesets = self.fetch_range_with_predicate(
+            StaticFileSegment::AccountChangeSets,
+            to_range(range),
+            |cursor, number| {
+                Ok(cursor
+                    .get_one::<AccountChangeSetMask>(number.into())?
+                    .map(|changeset| (number, changeset)))
+            },
+            |_| true,
+        )?;
+
+        Ok(changesets
+            .into_iter()
+            .flat_map(|(number, changeset)| {
+                changeset.changes.into_iter().map(move |change| (number, change))
+            })
+            .collect())
+    }
+
+    /// Returns the storage changesets of the block range, ordered by block number, address and
+    /// storage key.
+    pub fn storage_changesets_range(
+        &self,
+        range: impl RangeBounds<BlockNumber>,
+    ) -> ProviderResult<Vec<(BlockNumberAddress, StorageEntry)>> {
+        let changesets = self.fetch_range_with_predicate(
+            StaticFileSegment::StorageChangeSets,
+            to_range(range),
+            |cursor, number| {
+                Ok(cursor
+                    .get_one::<StorageChangeSetMask>(number.into())?
+                    .map(|changeset| (number, changeset)))
+            },
+            |_| true,
+        )?;
+
+        Ok(changesets
+            .into_iter()
+            .flat_map(|(number, changeset)| {
+                changeset
+                    .changes
+                    .into_iter()
+                    .map(move |change| ((number, change.address).into(), change.entry))
+            })
+            .collect())
+    }
+
     /// Returns `static_files` directory
     #[cfg(any(test, feature = "test-utils"))]
     pub fn path(&self) -> &Path {
@@ -1584,6 +1808,15 @@ impl<N: NodePrimitives> BlockNumReader for StaticFileProvider<N> {
         Err(ProviderError::UnsupportedProvider)
     }
 
+    fn earliest_history_height(&self) -> ProviderResult<BlockNumber> {
+        // Transactions and receipts are expired by deleting their lowest static files.
+        Ok([StaticFileSegment::Transactions, StaticFileSegment::Receipts]
+            .into_iter()
+            .filter_map(|segment| self.get_lowest_static_file_block(segment))
+            .max()
+            .unwrap_or_default())
+    }
+
     fn block_number(&self, _hash: B256) -> ProviderResult<Option<BlockNumber>> {
         // Required data not present in static_files
         Err(ProviderError::UnsupportedProvider)
@@ -1696,6 +1929,34 @@ impl<N: Send + Sync> BlockBodyIndicesProvider for StaticFileProvider<N> {
     }
 }
 
+impl<N: NodePrimitives> ChangeSetReader for StaticFileProvider<N> {
+    fn account_block_changeset(
+        &self,
+        block_number: BlockNumber,
+    ) -> ProviderResult<Vec<AccountBeforeTx>> {
+        self.get_segment_provider_from_block(
+            StaticFileSegment::AccountChangeSets,
+            block_number,
+            None,
+        )?
+        .account_block_changeset(block_number)
+    }
+}
+
+impl<N: NodePrimitives> StorageChangeSetReader for StaticFileProvider<N> {
+    fn storage_changeset(
+        &self,
+        block_number: BlockNumber,
+    ) -> ProviderResult<Vec<(BlockNumberAddress, StorageEntry)>> {
+        self.get_segment_provider_from_block(
+            StaticFileSegment::StorageChangeSets,
+            block_number,
+            None,
+        )?
+        .storage_changeset(block_number)
+    }
+}
+
 impl<N: NodePrimitives> StatsReader for StaticFileProvider<N> {
     fn count_entries<T: Table>(&self) -> ProviderResult<usize> {
         match T::NAME {
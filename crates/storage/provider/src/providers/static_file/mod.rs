@@ -59,18 +59,23 @@ mod tests {
         test_utils::create_test_provider_factory, HeaderProvider, StaticFileProviderFactory,
     };
     use alloy_consensus::{Header, Transaction};
-    use alloy_primitives::{BlockHash, TxNumber, B256, U256};
+    use alloy_primitives::{Address, BlockHash, TxNumber, B256, U256};
     use rand::seq::SliceRandom;
     use reth_db::{
         test_utils::create_test_static_files_dir, CanonicalHeaders, HeaderNumbers,
         HeaderTerminalDifficulties, Headers,
     };
-    use reth_db_api::transaction::DbTxMut;
+    use reth_db_api::{
+        models::{
+            AccountBeforeTx, StorageBeforeTx, StoredAccountChangeSet, StoredStorageChangeSet,
+        },
+        transaction::DbTxMut,
+    };
     use reth_primitives::{
         static_file::{find_fixed_range, SegmentRangeInclusive, DEFAULT_BLOCKS_PER_STATIC_FILE},
-        EthPrimitives, Receipt, TransactionSigned,
+        Account, EthPrimitives, Receipt, StorageEntry, TransactionSigned,
     };
-    use reth_storage_api::{ReceiptProvider, TransactionsProvider};
+    use reth_storage_api::{ChangeSetReader, ReceiptProvider, TransactionsProvider};
     use reth_testing_utils::generators::{self, random_header_range};
     use std::{fmt::Debug, fs, ops::Range, path::Path};
 
@@ -550,6 +555,86 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_changesets() {
+        let blocks_per_file = 10;
+        let (static_dir, _) = create_test_static_files_dir();
+        let sf_rw = StaticFileProvider::<EthPrimitives>::read_write(&static_dir)
+            .expect("Failed to create static file provider")
+            .with_custom_blocks_per_file(blocks_per_file);
+
+        let account = |block: u64| AccountBeforeTx {
+            address: Address::with_last_byte(block as u8),
+            info: Some(Account { nonce: block, ..Default::default() }),
+        };
+        let storage = |block: u64| StorageBeforeTx {
+            address: Address::with_last_byte(block as u8),
+            entry: StorageEntry { key: B256::with_last_byte(1), value: U256::from(block) },
+        };
+
+        // Blocks with an odd number have no changes
+        let last_block = blocks_per_file * 3 - 1;
+        {
+            let mut account_writer =
+                sf_rw.latest_writer(StaticFileSegment::AccountChangeSets).unwrap();
+            let mut storage_writer =
+                sf_rw.latest_writer(StaticFileSegment::StorageChangeSets).unwrap();
+            for block in 0..=last_block {
+                let changes = (block % 2 == 0).then_some(block);
+                account_writer
+                    .append_account_changeset(
+                        block,
+                        &StoredAccountChangeSet {
+                            changes: changes.into_iter().map(account).collect(),
+                        },
+                    )
+                    .unwrap();
+                storage_writer
+                    .append_storage_changeset(
+                        block,
+                        &StoredStorageChangeSet {
+                            changes: changes.into_iter().map(storage).collect(),
+                        },
+                    )
+                    .unwrap();
+            }
+            account_writer.commit().unwrap();
+            storage_writer.commit().unwrap();
+        }
+
+        assert_eq!(sf_rw.account_changeset_entry(4, account(4).address).unwrap(), Some(account(4)));
+        assert_eq!(sf_rw.account_changeset_entry(4, account(6).address).unwrap(), None);
+        assert_eq!(
+            sf_rw.storage_changeset_entry(12, storage(12).address, storage(12).entry.key).unwrap(),
+            Some(storage(12).entry)
+        );
+        assert_eq!(sf_rw.account_block_changeset(5).unwrap(), Vec::new());
+        assert_eq!(
+            sf_rw.account_changesets_range(8..13).unwrap(),
+            vec![(8, account(8)), (10, account(10)), (12, account(12))]
+        );
+        assert_eq!(
+            sf_rw.storage_changesets_range(8..13).unwrap(),
+            [8, 10, 12]
+                .map(|block| ((block, storage(block).address).into(), storage(block).entry))
+                .to_vec()
+        );
+
+        // The file with the highest block is never deleted
+        for segment in [StaticFileSegment::AccountChangeSets, StaticFileSegment::StorageChangeSets]
+        {
+            let deleted = sf_rw.delete_segment_below_block(segment, last_block + 1).unwrap();
+            assert_eq!(deleted.len(), 2);
+            assert_eq!(sf_rw.get_lowest_static_file_block(segment), Some(blocks_per_file * 2));
+            assert_eq!(sf_rw.get_highest_static_file_block(segment), Some(last_block));
+        }
+        assert!(sf_rw.account_changesets_range(0..blocks_per_file * 2).is_err());
+        assert_eq!(
+            sf_rw.account_block_changeset(blocks_per_file * 2).unwrap(),
+            vec![account(blocks_per_file * 2)]
+        );
+    }
+
     /// Returns the number of files in the provided path, excluding ".lock" files.
     fn count_files_without_lockfile(path: impl AsRef<Path>) -> eyre::Result<usize> {
         let is_lockfile = |entry: &fs::DirEntry| {
@@ -19,6 +19,14 @@ pub trait BlockNumReader: BlockHashReader + Send + Sync {
     /// Returns the last block number associated with the last canonical header in the database.
     fn last_block_number(&self) -> ProviderResult<BlockNumber>;
 
+    /// Returns the lowest block number whose transactions and receipts are available.
+    ///
+    /// Headers are always retained, but the history of the blocks below this number may have
+    /// been expired.
+    fn earliest_history_height(&self) -> ProviderResult<BlockNumber> {
+        Ok(0)
+    }
+
     /// Gets the `BlockNumber` for the given hash. Returns `None` if no block with this hash exists.
     fn block_number(&self, hash: B256) -> ProviderResult<Option<BlockNumber>>;
 
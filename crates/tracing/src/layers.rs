@@ -2,9 +2,9 @@ use std::path::{Path, PathBuf};
 
 use rolling_file::{RollingConditionBasic, RollingFileAppender};
 use tracing_appender::non_blocking::WorkerGuard;
-use tracing_subscriber::{filter::Directive, EnvFilter, Layer, Registry};
+use tracing_subscriber::{filter::Directive, reload, EnvFilter, Layer, Registry};
 
-use crate::formatter::LogFormat;
+use crate::{formatter::LogFormat, reload::ReloadableFilter};
 
 /// A worker guard returned by the file layer.
 ///
@@ -33,17 +33,20 @@ const DEFAULT_ENV_FILTER_DIRECTIVES: [&str; 5] = [
 /// Each layer can be configured separately and then combined into a tracing subscriber.
 pub(crate) struct Layers {
     inner: Vec<BoxedLayer<Registry>>,
+    /// The reloadable filters of the stdout and file layers.
+    reloadable_filters: Vec<ReloadableFilter>,
 }
 
 impl Layers {
     /// Creates a new `Layers` instance.
     pub(crate) fn new() -> Self {
-        Self { inner: vec![] }
+        Self { inner: vec![], reloadable_filters: vec![] }
     }
 
-    /// Consumes the `Layers` instance, returning the inner vector of layers.
-    pub(crate) fn into_inner(self) -> Vec<BoxedLayer<Registry>> {
-        self.inner
+    /// Consumes the `Layers` instance, returning the inner vector of layers and the reloadable
+    /// filters of the layers.
+    pub(crate) fn into_parts(self) -> (Vec<BoxedLayer<Registry>>, Vec<ReloadableFilter>) {
+        (self.inner, self.reloadable_filters)
     }
 
     /// Adds a journald layer to the layers collection.
@@ -80,7 +83,13 @@ impl Layers {
         filters: &str,
         color: Option<String>,
     ) -> eyre::Result<()> {
-        let filter = build_env_filter(Some(default_directive), filters)?;
+        let filter = build_env_filter(Some(default_directive.clone()), filters)?;
+        let (filter, handle) = reload::Layer::new(filter);
+        self.reloadable_filters.push(ReloadableFilter::new(
+            handle,
+            Some(default_directive),
+            filters,
+        ));
         let layer = format.apply(filter, color, None);
         self.inner.push(layer.boxed());
         Ok(())
@@ -103,6 +112,8 @@ impl Layers {
     ) -> eyre::Result<FileWorkerGuard> {
         let (writer, guard) = file_info.create_log_writer();
         let file_filter = build_env_filter(None, filter)?;
+        let (file_filter, handle) = reload::Layer::new(file_filter);
+        self.reloadable_filters.push(ReloadableFilter::new(handle, None, filter));
         let layer = format.apply(file_filter, None, Some(writer));
         self.inner.push(layer);
         Ok(guard)
@@ -166,7 +177,7 @@ impl FileInfo {
 ///
 /// # Returns
 /// An `eyre::Result<EnvFilter>` that can be used to configure a tracing subscriber.
-fn build_env_filter(
+pub(crate) fn build_env_filter(
     default_directive: Option<Directive>,
     directives: &str,
 ) -> eyre::Result<EnvFilter> {
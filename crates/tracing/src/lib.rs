@@ -51,13 +51,15 @@ pub use tracing_subscriber;
 // Re-export our types
 pub use formatter::LogFormat;
 pub use layers::{FileInfo, FileWorkerGuard};
+pub use reload::{log_filter_handle, LogFilterHandle};
 pub use test_tracer::TestTracer;
 
 mod formatter;
 mod layers;
+mod reload;
 mod test_tracer;
 
-use crate::layers::Layers;
+use crate::{layers::Layers, reload::set_log_filter_handle};
 use tracing::level_filters::LevelFilter;
 use tracing_appender::non_blocking::WorkerGuard;
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
@@ -187,6 +189,9 @@ impl Tracer for RethTracer {
     ///
     ///  The default layer is stdout.
     ///
+    ///  If the global subscriber is installed, the filters of the stdout and file layers can be
+    ///  changed at runtime with the handle returned by [`log_filter_handle`].
+    ///
     ///  # Returns
     ///  An `eyre::Result` which is `Ok` with an optional `WorkerGuard` if a file layer is used,
     ///  or an `Err` in case of an error during initialization.
@@ -210,9 +215,13 @@ impl Tracer for RethTracer {
             None
         };
 
+        let (layers, reloadable_filters) = layers.into_parts();
+
         // The error is returned if the global default subscriber is already set,
         // so it's safe to ignore it
-        let _ = tracing_subscriber::registry().with(layers.into_inner()).try_init();
+        if tracing_subscriber::registry().with(layers).try_init().is_ok() {
+            set_log_filter_handle(LogFilterHandle::new(reloadable_filters));
+        }
         Ok(file_guard)
     }
 }
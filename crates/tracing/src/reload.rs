@@ -0,0 +1,114 @@
+use std::sync::{Arc, Mutex, OnceLock};
+
+use tracing::level_filters::LevelFilter;
+use tracing_subscriber::{filter::Directive, reload, EnvFilter, Registry};
+
+use crate::layers::build_env_filter;
+
+/// The handle of the filters of the global tracer, set by [`RethTracer`](crate::RethTracer).
+static LOG_FILTER_HANDLE: OnceLock<LogFilterHandle> = OnceLock::new();
+
+/// Returns the [`LogFilterHandle`] of the global tracer.
+///
+/// Returns `None` if the global tracer was not initialized with
+/// [`RethTracer`](crate::RethTracer).
+pub fn log_filter_handle() -> Option<LogFilterHandle> {
+    LOG_FILTER_HANDLE.get().cloned()
+}
+
+/// Installs the handle of the global tracer, this is a noop if a handle is already installed.
+pub(crate) fn set_log_filter_handle(handle: LogFilterHandle) {
+    let _ = LOG_FILTER_HANDLE.set(handle);
+}
+
+/// A handle to reconfigure the log filters of the stdout and file layers at runtime.
+///
+/// The configured verbosity and directives are applied on top of the filters the layers were
+/// initialized with.
+#[derive(Debug, Clone)]
+pub struct LogFilterHandle {
+    inner: Arc<Mutex<LogFilterHandleInner>>,
+}
+
+#[derive(Debug)]
+struct LogFilterHandleInner {
+    /// The reloadable filters of all layers.
+    filters: Vec<ReloadableFilter>,
+    /// The verbosity that overrides the default directive of the layers.
+    verbosity: Option<LevelFilter>,
+    /// Additional directives that are applied on top of the filters of the layers.
+    directives: Vec<Directive>,
+}
+
+impl LogFilterHandle {
+    /// Creates a new handle for the given filters.
+    pub(crate) fn new(filters: Vec<ReloadableFilter>) -> Self {
+        Self {
+            inner: Arc::new(Mutex::new(LogFilterHandleInner {
+                filters,
+                verbosity: None,
+                directives: Vec::new(),
+            })),
+        }
+    }
+
+    /// Sets the maximum level of the events that are logged by targets without a more specific
+    /// directive.
+    pub fn set_verbosity(&self, verbosity: LevelFilter) -> eyre::Result<()> {
+        let mut inner = self.inner.lock().expect("not poisoned");
+        inner.verbosity = Some(verbosity);
+        inner.reload()
+    }
+
+    /// Replaces the additional directives, e.g. `net=debug,engine::tree=trace`.
+    ///
+    /// An empty string removes all additional directives.
+    pub fn set_directives(&self, directives: &str) -> eyre::Result<()> {
+        let directives = directives
+            .split(',')
+            .filter(|directive| !directive.is_empty())
+            .map(|directive| directive.parse())
+            .collect::<Result<Vec<Directive>, _>>()?;
+
+        let mut inner = self.inner.lock().expect("not poisoned");
+        inner.directives = directives;
+        inner.reload()
+    }
+}
+
+impl LogFilterHandleInner {
+    /// Rebuilds the filters of all layers from the current configuration.
+    fn reload(&self) -> eyre::Result<()> {
+        for filter in &self.filters {
+            let mut env_filter =
+                build_env_filter(filter.default_directive.clone(), &filter.directives)?;
+            if let Some(verbosity) = self.verbosity {
+                env_filter = env_filter.add_directive(verbosity.into());
+            }
+            for directive in &self.directives {
+                env_filter = env_filter.add_directive(directive.clone());
+            }
+            filter.handle.reload(env_filter)?;
+        }
+        Ok(())
+    }
+}
+
+/// The reloadable filter of a layer, together with the configuration it was built from.
+#[derive(Debug)]
+pub(crate) struct ReloadableFilter {
+    handle: reload::Handle<EnvFilter, Registry>,
+    default_directive: Option<Directive>,
+    directives: String,
+}
+
+impl ReloadableFilter {
+    /// Creates a new reloadable filter.
+    pub(crate) fn new(
+        handle: reload::Handle<EnvFilter, Registry>,
+        default_directive: Option<Directive>,
+        directives: &str,
+    ) -> Self {
+        Self { handle, default_directive, directives: directives.to_string() }
+    }
+}
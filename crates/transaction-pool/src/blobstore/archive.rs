@@ -0,0 +1,535 @@
+//! A long-lived archive of the blob sidecars of canonical blocks.
+
+use crate::blobstore::BlobStoreError;
+use alloy_eips::{
+    eip4844::{kzg_to_versioned_hash, BlobTransactionSidecar, BlobTransactionSidecarItem},
+    BlockNumHash,
+};
+use alloy_primitives::{TxHash, B256};
+use alloy_rlp::{Decodable, RlpDecodable, RlpEncodable};
+use parking_lot::Mutex;
+use reth_fs_util::{self as fs, FsPathError};
+use std::{
+    collections::{hash_map::Entry, HashMap},
+    io::{self, Write},
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+use tracing::trace;
+
+/// The default number of blocks the [`BlobArchive`] keeps the blob sidecars of.
+///
+/// This matches the 4096 epochs (~18 days) the consensus layer is required to serve blob sidecars
+/// for.
+pub const DEFAULT_BLOB_ARCHIVE_RETENTION_BLOCKS: u64 = 4096 * 32;
+
+/// A blob of a block in the [`BlobArchive`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct ArchivedBlob {
+    /// Hash of the block that included the blob.
+    pub block_hash: B256,
+    /// Number of the block that included the blob.
+    #[cfg_attr(feature = "serde", serde(with = "alloy_serde::quantity"))]
+    pub block_number: u64,
+    /// Hash of the transaction that carried the blob.
+    pub transaction_hash: TxHash,
+    /// Versioned hash of the blob.
+    pub versioned_hash: B256,
+    /// The blob with its commitment and proof, in the layout of the beacon API blob sidecars.
+    ///
+    /// The index is the index of the blob in the block, across all blob transactions.
+    #[cfg_attr(feature = "serde", serde(flatten))]
+    pub sidecar: BlobTransactionSidecarItem,
+}
+
+/// The blob sidecar of a transaction in a block, as it is stored in the archive.
+#[derive(Debug, Clone, PartialEq, Eq, RlpEncodable, RlpDecodable)]
+pub struct BlockBlobSidecar {
+    /// Hash of the transaction.
+    pub tx_hash: TxHash,
+    /// Index of the first blob of the transaction in the block, across all blob transactions.
+    pub first_blob_index: u64,
+    /// The blob sidecar of the transaction.
+    pub sidecar: BlobTransactionSidecar,
+}
+
+/// An on-disk archive of the blob sidecars of canonical blocks.
+///
+/// Unlike the [`BlobStore`](crate::blobstore::BlobStore) of the pool, which drops sidecars once
+/// their block is finalized, the archive keeps them for a configurable number of blocks, or
+/// forever.
+///
+/// The sidecars of every block are stored in a separate `blocks/<number>/<hash>` file in the
+/// archive directory, in the order of their transactions in the block. Every versioned hash is
+/// indexed by a `versioned_hashes/<hash>` file, that points to the block that last included it.
+///
+/// The archive tracks the lowest archived block number, so pruning only visits the blocks that
+/// fell out of the retention window. All methods do blocking file I/O.
+///
+/// The archive is cheap to clone, all clones access the same directory.
+#[derive(Debug, Clone)]
+pub struct BlobArchive {
+    inner: Arc<BlobArchiveInner>,
+}
+
+#[derive(Debug)]
+struct BlobArchiveInner {
+    /// The directory the blob sidecars are stored in.
+    directory: PathBuf,
+    /// The number of blocks to keep the blob sidecars of, `None` keeps them forever.
+    retention: Option<u64>,
+    /// Serializes writes to the archive, and holds the lowest archived block number.
+    ///
+    /// The lowest number is read from the directory on the first prune, and `None` until then.
+    write_lock: Mutex<Option<LowestBlock>>,
+}
+
+/// The lowest block number in the [`BlobArchive`].
+#[derive(Debug, Clone, Copy)]
+enum LowestBlock {
+    /// The archive is empty.
+    Empty,
+    /// There are no archived blocks below this number.
+    Number(u64),
+}
+
+impl LowestBlock {
+    /// Returns the lowest block number after a block with the given number was archived.
+    const fn with_block(self, number: u64) -> Self {
+        match self {
+            Self::Number(lowest) if lowest <= number => self,
+            _ => Self::Number(number),
+        }
+    }
+}
+
+impl BlobArchive {
+    /// Creates a new archive in the given directory that keeps the blob sidecars of the last
+    /// `retention` blocks, or forever if `None`.
+    ///
+    /// The directory is created on the first insert.
+    pub fn new(directory: impl Into<PathBuf>, retention: Option<u64>) -> Self {
+        Self {
+            inner: Arc::new(BlobArchiveInner {
+                directory: directory.into(),
+                retention,
+                write_lock: Mutex::new(None),
+            }),
+        }
+    }
+
+    /// Returns the directory of the archive.
+    pub fn directory(&self) -> &Path {
+        &self.inner.directory
+    }
+
+    /// Returns the number of blocks the blob sidecars are kept for, `None` if they're kept forever.
+    pub fn retention(&self) -> Option<u64> {
+        self.inner.retention
+    }
+
+    /// Archives the blob sidecars of a block, given in the order of their transactions in the
+    /// block.
+    ///
+    /// The sidecars of some blob transactions of the block may be missing, which is why every
+    /// sidecar carries the index of its first blob in the block.
+    ///
+    /// Replaces the sidecars that were archived for the block before, if any.
+    pub fn insert_block(
+        &self,
+        block: BlockNumHash,
+        sidecars: Vec<BlockBlobSidecar>,
+    ) -> Result<(), BlobStoreError> {
+        if sidecars.is_empty() {
+            return Ok(())
+        }
+
+        let mut lowest = self.inner.write_lock.lock();
+        self.remove_block_unlocked(block)?;
+
+        fs::create_dir_all(self.block_dir(block.number)).map_err(into_store_error)?;
+        fs::create_dir_all(self.versioned_hashes_dir()).map_err(into_store_error)?;
+
+        let versioned_hashes =
+            sidecars.iter().flat_map(|tx| tx.sidecar.versioned_hashes()).collect::<Vec<_>>();
+        let encoded = alloy_rlp::encode(&sidecars);
+
+        trace!(target: "txpool::blob::archive", number = block.number, hash = %block.hash, blobs = versioned_hashes.len(), "Archiving blob sidecars");
+        fs::atomic_write_file(&self.block_file(block), |file| file.write_all(&encoded))
+            .map_err(into_store_error)?;
+
+        let mut pointer = Vec::with_capacity(40);
+        pointer.extend_from_slice(&block.number.to_be_bytes());
+        pointer.extend_from_slice(block.hash.as_slice());
+        for versioned_hash in versioned_hashes {
+            fs::write(self.versioned_hash_file(versioned_hash), &pointer)
+                .map_err(into_store_error)?;
+        }
+
+        *lowest = lowest.map(|lowest| lowest.with_block(block.number));
+        Ok(())
+    }
+
+    /// Removes the blob sidecars of a block from the archive.
+    ///
+    /// Returns `true` if the block was archived.
+    pub fn remove_block(&self, block: BlockNumHash) -> Result<bool, BlobStoreError> {
+        let _lock = self.inner.write_lock.lock();
+        self.remove_block_unlocked(block)
+    }
+
+    /// Removes the blob sidecars of all blocks that are outside of the retention window, given the
+    /// current tip of the chain.
+    ///
+    /// Only the block numbers from the lowest archived one up to the end of the retention window
+    /// are visited, except for the first prune, which reads the lowest archived block number from
+    /// the directory.
+    ///
+    /// Returns the number of removed blocks.
+    pub fn prune(&self, tip: u64) -> Result<usize, BlobStoreError> {
+        let Some(retention) = self.inner.retention else { return Ok(0) };
+        let Some(end) = tip.checked_sub(retention) else { return Ok(0) };
+
+        let mut lowest = self.inner.write_lock.lock();
+        let start = match lowest.map_or_else(|| self.read_lowest_block(), Ok)? {
+            LowestBlock::Empty => {
+                *lowest = Some(LowestBlock::Empty);
+                return Ok(0)
+            }
+            LowestBlock::Number(number) => number,
+        };
+
+        let mut removed = 0;
+        for number in start..=end {
+            for block in self.blocks_by_number(number)? {
+                if self.remove_block_unlocked(block)? {
+                    removed += 1;
+                }
+            }
+            // the directories of the numbers are removed with their last block
+            let _ = std::fs::remove_dir(self.block_dir(number));
+        }
+        *lowest = Some(LowestBlock::Number(start.max(end.saturating_add(1))));
+
+        if removed > 0 {
+            trace!(target: "txpool::blob::archive", tip, removed, "Pruned archived blob sidecars");
+        }
+        Ok(removed)
+    }
+
+    /// Returns the archived blobs of a block, in the order of their index in the block.
+    ///
+    /// Returns an empty list if the block has no blobs or is not archived.
+    pub fn get_block(&self, block: BlockNumHash) -> Result<Vec<ArchivedBlob>, BlobStoreError> {
+        Ok(self.read_block(block)?.map(|txs| archived_blobs(block, txs)).unwrap_or_default())
+    }
+
+    /// Returns the archived blobs for the given versioned hashes, in the same order.
+    ///
+    /// If the same blob was included multiple times, the blob of the block that was archived last
+    /// is returned.
+    pub fn get_by_versioned_hashes(
+        &self,
+        versioned_hashes: &[B256],
+    ) -> Result<Vec<Option<ArchivedBlob>>, BlobStoreError> {
+        let mut blocks = HashMap::<BlockNumHash, Vec<ArchivedBlob>>::new();
+        let mut result = Vec::with_capacity(versioned_hashes.len());
+        for versioned_hash in versioned_hashes {
+            let Some(block) = self.read_versioned_hash(*versioned_hash)? else {
+                result.push(None);
+                continue
+            };
+            let blobs = match blocks.entry(block) {
+                Entry::Occupied(entry) => entry.into_mut(),
+                Entry::Vacant(entry) => entry.insert(self.get_block(block)?),
+            };
+            result.push(blobs.iter().find(|blob| blob.versioned_hash == *versioned_hash).cloned());
+        }
+        Ok(result)
+    }
+
+    /// Returns all archived blocks, in no particular order.
+    ///
+    /// This reads the whole archive directory.
+    pub fn blocks(&self) -> Result<Vec<BlockNumHash>, BlobStoreError> {
+        let mut blocks = Vec::new();
+        for number in self.block_numbers()? {
+            blocks.extend(self.blocks_by_number(number)?);
+        }
+        Ok(blocks)
+    }
+
+    /// Returns the numbers of all archived blocks, in no particular order.
+    fn block_numbers(&self) -> Result<Vec<u64>, BlobStoreError> {
+        Ok(read_dir_names(&self.blocks_dir())?
+            .into_iter()
+            .filter_map(|name| name.parse().ok())
+            .collect())
+    }
+
+    /// Returns the archived blocks with the given number.
+    fn blocks_by_number(&self, number: u64) -> Result<Vec<BlockNumHash>, BlobStoreError> {
+        Ok(read_dir_names(&self.block_dir(number))?
+            .into_iter()
+            .filter_map(|name| Some(BlockNumHash::new(number, name.parse().ok()?)))
+            .collect())
+    }
+
+    /// Reads the lowest archived block number from the directory.
+    fn read_lowest_block(&self) -> Result<LowestBlock, BlobStoreError> {
+        Ok(self.block_numbers()?.into_iter().min().map_or(LowestBlock::Empty, LowestBlock::Number))
+    }
+
+    /// Removes the sidecars of the block and the versioned hashes that still point to it.
+    fn remove_block_unlocked(&self, block: BlockNumHash) -> Result<bool, BlobStoreError> {
+        let Some(transactions) = self.read_block(block)? else { return Ok(false) };
+
+        for versioned_hash in transactions.iter().flat_map(|tx| tx.sidecar.versioned_hashes()) {
+            if self.read_versioned_hash(versioned_hash)? == Some(block) {
+                fs::remove_file(self.versioned_hash_file(versioned_hash))
+                    .map_err(into_store_error)?;
+            }
+        }
+        fs::remove_file(self.block_file(block)).map_err(into_store_error)?;
+
+        trace!(target: "txpool::blob::archive", number = block.number, hash = %block.hash, "Removed archived blob sidecars");
+        Ok(true)
+    }
+
+    /// Reads the archived sidecars of a block.
+    fn read_block(
+        &self,
+        block: BlockNumHash,
+    ) -> Result<Option<Vec<BlockBlobSidecar>>, BlobStoreError> {
+        let Some(data) = read_file(&self.block_file(block))? else { return Ok(None) };
+        Ok(Some(Vec::<BlockBlobSidecar>::decode(&mut data.as_slice())?))
+    }
+
+    /// Reads the block the versioned hash points to.
+    fn read_versioned_hash(
+        &self,
+        versioned_hash: B256,
+    ) -> Result<Option<BlockNumHash>, BlobStoreError> {
+        let Some(data) = read_file(&self.versioned_hash_file(versioned_hash))? else {
+            return Ok(None)
+        };
+        if data.len() != 40 {
+            return Err(alloy_rlp::Error::UnexpectedLength.into())
+        }
+        let number = u64::from_be_bytes(data[..8].try_into().expect("8 bytes"));
+        Ok(Some(BlockNumHash::new(number, B256::from_slice(&data[8..]))))
+    }
+
+    fn blocks_dir(&self) -> PathBuf {
+        self.inner.directory.join("blocks")
+    }
+
+    fn versioned_hashes_dir(&self) -> PathBuf {
+        self.inner.directory.join("versioned_hashes")
+    }
+
+    fn block_dir(&self, number: u64) -> PathBuf {
+        self.blocks_dir().join(number.to_string())
+    }
+
+    fn block_file(&self, block: BlockNumHash) -> PathBuf {
+        self.block_dir(block.number).join(block.hash.to_string())
+    }
+
+    fn versioned_hash_file(&self, versioned_hash: B256) -> PathBuf {
+        self.versioned_hashes_dir().join(format!("{versioned_hash:x}"))
+    }
+}
+
+/// Reads the file, returning `None` if it doesn't exist.
+fn read_file(path: &Path) -> Result<Option<Vec<u8>>, BlobStoreError> {
+    match std::fs::read(path) {
+        Ok(data) => Ok(Some(data)),
+        Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(None),
+        Err(err) => Err(into_store_error(FsPathError::read(err, path))),
+    }
+}
+
+/// Returns the names of the entries of the directory, or none if it doesn't exist.
+fn read_dir_names(dir: &Path) -> Result<Vec<String>, BlobStoreError> {
+    let entries = match std::fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(err) => return Err(into_store_error(FsPathError::read_dir(err, dir))),
+    };
+
+    let mut names = Vec::new();
+    for entry in entries {
+        let entry = entry.map_err(|err| into_store_error(FsPathError::read_dir(err, dir)))?;
+        names.extend(entry.file_name().into_string().ok());
+    }
+    Ok(names)
+}
+
+fn into_store_error(err: FsPathError) -> BlobStoreError {
+    BlobStoreError::Other(Box::new(err))
+}
+
+/// Flattens the archived sidecars of a block into its blobs.
+fn archived_blobs(block: BlockNumHash, sidecars: Vec<BlockBlobSidecar>) -> Vec<ArchivedBlob> {
+    let mut blobs = Vec::new();
+    for BlockBlobSidecar { tx_hash, first_blob_index, sidecar } in sidecars {
+        for (index, mut item) in sidecar.into_iter().enumerate() {
+            item.index = first_blob_index + index as u64;
+            blobs.push(ArchivedBlob {
+                block_hash: block.hash,
+                block_number: block.number,
+                transaction_hash: tx_hash,
+                versioned_hash: kzg_to_versioned_hash(item.kzg_commitment.as_slice()),
+                sidecar: item,
+            });
+        }
+    }
+    blobs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_eips::eip4844::{Blob, Bytes48};
+
+    fn rng_sidecar(num_blobs: usize) -> BlobTransactionSidecar {
+        BlobTransactionSidecar::new(
+            (0..num_blobs).map(|_| Blob::random()).collect(),
+            (0..num_blobs).map(|_| Bytes48::random()).collect(),
+            (0..num_blobs).map(|_| Bytes48::random()).collect(),
+        )
+    }
+
+    fn block_sidecar(
+        tx_hash: TxHash,
+        first_blob_index: u64,
+        sidecar: BlobTransactionSidecar,
+    ) -> BlockBlobSidecar {
+        BlockBlobSidecar { tx_hash, first_blob_index, sidecar }
+    }
+
+    #[test]
+    fn insert_get_remove() {
+        let dir = tempfile::tempdir().unwrap();
+        let archive = BlobArchive::new(dir.path(), None);
+        let block = BlockNumHash::new(1, B256::random());
+        let (tx1, tx2) = (TxHash::random(), TxHash::random());
+        let (sidecar1, sidecar2) = (rng_sidecar(2), rng_sidecar(1));
+        let versioned_hashes = sidecar1.versioned_hashes().chain(sidecar2.versioned_hashes());
+        let versioned_hashes = versioned_hashes.collect::<Vec<_>>();
+
+        archive
+            .insert_block(
+                block,
+                vec![
+                    block_sidecar(tx1, 0, sidecar1.clone()),
+                    block_sidecar(tx2, 2, sidecar2.clone()),
+                ],
+            )
+            .unwrap();
+
+        let blobs = archive.get_block(block).unwrap();
+        assert_eq!(blobs.len(), 3);
+        assert_eq!(blobs.iter().map(|blob| blob.sidecar.index).collect::<Vec<_>>(), [0, 1, 2]);
+        assert_eq!(
+            blobs.iter().map(|blob| blob.transaction_hash).collect::<Vec<_>>(),
+            [tx1, tx1, tx2]
+        );
+        assert_eq!(
+            blobs.iter().map(|blob| blob.versioned_hash).collect::<Vec<_>>(),
+            versioned_hashes
+        );
+        assert_eq!(*blobs[2].sidecar.blob, sidecar2.blobs[0]);
+
+        let missing = B256::random();
+        let found = archive.get_by_versioned_hashes(&[versioned_hashes[2], missing]).unwrap();
+        assert_eq!(found, vec![Some(blobs[2].clone()), None]);
+
+        // blocks with the same number are kept apart
+        assert!(archive.get_block(BlockNumHash::new(1, B256::random())).unwrap().is_empty());
+
+        assert!(archive.remove_block(block).unwrap());
+        assert!(!archive.remove_block(block).unwrap());
+        assert!(archive.get_block(block).unwrap().is_empty());
+        assert_eq!(archive.get_by_versioned_hashes(&versioned_hashes[..1]).unwrap(), vec![None]);
+    }
+
+    #[test]
+    fn missing_sidecar() {
+        let dir = tempfile::tempdir().unwrap();
+        let archive = BlobArchive::new(dir.path(), None);
+        let block = BlockNumHash::new(1, B256::random());
+        let (tx1, tx3) = (TxHash::random(), TxHash::random());
+
+        // the sidecar of the second blob transaction, with the blobs 2 and 3, is missing
+        archive
+            .insert_block(
+                block,
+                vec![block_sidecar(tx1, 0, rng_sidecar(2)), block_sidecar(tx3, 4, rng_sidecar(2))],
+            )
+            .unwrap();
+
+        let blobs = archive.get_block(block).unwrap();
+        assert_eq!(blobs.iter().map(|blob| blob.sidecar.index).collect::<Vec<_>>(), [0, 1, 4, 5]);
+        assert_eq!(
+            blobs.iter().map(|blob| blob.transaction_hash).collect::<Vec<_>>(),
+            [tx1, tx1, tx3, tx3]
+        );
+    }
+
+    #[test]
+    fn reincluded_blob() {
+        let dir = tempfile::tempdir().unwrap();
+        let archive = BlobArchive::new(dir.path(), None);
+        let sidecar = rng_sidecar(1);
+        let versioned_hash = sidecar.versioned_hashes().next().unwrap();
+        let (old, new) =
+            (BlockNumHash::new(1, B256::random()), BlockNumHash::new(2, B256::random()));
+
+        archive
+            .insert_block(old, vec![block_sidecar(TxHash::random(), 0, sidecar.clone())])
+            .unwrap();
+        archive.insert_block(new, vec![block_sidecar(TxHash::random(), 0, sidecar)]).unwrap();
+
+        // removing the old block keeps the versioned hash of the new one
+        archive.remove_block(old).unwrap();
+        let found = archive.get_by_versioned_hashes(&[versioned_hash]).unwrap();
+        assert_eq!(found[0].as_ref().map(|blob| blob.block_hash), Some(new.hash));
+    }
+
+    #[test]
+    fn prune_retention() {
+        let dir = tempfile::tempdir().unwrap();
+        let archive = BlobArchive::new(dir.path(), Some(10));
+        let blocks = (1..=5).map(|number| BlockNumHash::new(number, B256::random()));
+        let blocks = blocks.collect::<Vec<_>>();
+        for block in &blocks {
+            archive
+                .insert_block(*block, vec![block_sidecar(TxHash::random(), 0, rng_sidecar(1))])
+                .unwrap();
+        }
+
+        assert_eq!(archive.prune(10).unwrap(), 0);
+        assert_eq!(archive.prune(13).unwrap(), 3);
+
+        let mut remaining = archive.blocks().unwrap();
+        remaining.sort_unstable_by_key(|block| block.number);
+        assert_eq!(remaining, blocks[3..]);
+
+        // blocks below the lowest pruned number are still found after they're archived
+        let reorged = BlockNumHash::new(2, B256::random());
+        archive
+            .insert_block(reorged, vec![block_sidecar(TxHash::random(), 0, rng_sidecar(1))])
+            .unwrap();
+        assert_eq!(archive.prune(14).unwrap(), 2);
+        assert_eq!(archive.blocks().unwrap(), blocks[4..]);
+
+        // archives without a retention window are never pruned
+        let archive = BlobArchive::new(dir.path(), None);
+        assert_eq!(archive.prune(u64::MAX).unwrap(), 0);
+        assert_eq!(archive.blocks().unwrap().len(), 2);
+    }
+}
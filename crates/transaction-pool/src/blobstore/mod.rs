@@ -2,6 +2,9 @@
 
 use alloy_eips::eip4844::{BlobAndProofV1, BlobTransactionSidecar};
 use alloy_primitives::B256;
+pub use archive::{
+    ArchivedBlob, BlobArchive, BlockBlobSidecar, DEFAULT_BLOB_ARCHIVE_RETENTION_BLOCKS,
+};
 pub use disk::{DiskFileBlobStore, DiskFileBlobStoreConfig, OpenDiskFileBlobStore};
 pub use mem::InMemoryBlobStore;
 pub use noop::NoopBlobStore;
@@ -14,6 +17,7 @@ use std::{
 };
 pub use tracker::{BlobStoreCanonTracker, BlobStoreUpdates};
 
+mod archive;
 pub mod disk;
 mod mem;
 mod noop;
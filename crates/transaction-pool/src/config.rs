@@ -30,6 +30,9 @@ pub const REPLACE_BLOB_PRICE_BUMP: u128 = 100;
 /// Default maximum new transactions for broadcasting.
 pub const MAX_NEW_PENDING_TXS_NOTIFICATIONS: usize = 200;
 
+/// The default maximum number of recent transaction events kept in the pool's history.
+pub const TXPOOL_MAX_HISTORY_EVENTS_DEFAULT: usize = 50_000;
+
 /// Configuration options for the Transaction pool.
 #[derive(Debug, Clone)]
 pub struct PoolConfig {
@@ -58,6 +61,11 @@ pub struct PoolConfig {
     pub new_tx_listener_buffer_size: usize,
     /// How many new pending transactions to buffer and send iterators in progress.
     pub max_new_pending_txs_notifications: usize,
+    /// Max number of recent transaction events to keep for
+    /// [`TransactionPool::transaction_history`](crate::TransactionPool::transaction_history).
+    ///
+    /// The history is disabled if this is 0.
+    pub max_history_events: usize,
 }
 
 impl PoolConfig {
@@ -86,6 +94,7 @@ impl Default for PoolConfig {
             pending_tx_listener_buffer_size: PENDING_TX_LISTENER_BUFFER_SIZE,
             new_tx_listener_buffer_size: NEW_TX_LISTENER_BUFFER_SIZE,
             max_new_pending_txs_notifications: MAX_NEW_PENDING_TXS_NOTIFICATIONS,
+            max_history_events: TXPOOL_MAX_HISTORY_EVENTS_DEFAULT,
         }
     }
 }
@@ -175,6 +184,9 @@ pub struct LocalTransactionConfig {
     pub local_addresses: HashSet<Address>,
     /// Flag indicating whether local transactions should be propagated.
     pub propagate_local_transactions: bool,
+    /// Flag indicating whether private transactions that were not included up to their deadline
+    /// should be made public as local transactions, instead of being dropped.
+    pub propagate_expired_private_transactions: bool,
 }
 
 impl Default for LocalTransactionConfig {
@@ -183,6 +195,7 @@ impl Default for LocalTransactionConfig {
             no_exemptions: false,
             local_addresses: HashSet::default(),
             propagate_local_transactions: true,
+            propagate_expired_private_transactions: false,
         }
     }
 }
@@ -221,6 +234,16 @@ impl LocalTransactionConfig {
         self.propagate_local_transactions = propagate_local_txs;
         self
     }
+
+    /// Sets toggle to make private transactions public once they missed their deadline (e.g.
+    /// the max block number of `eth_sendPrivateTransaction`), instead of dropping them.
+    pub const fn set_propagate_expired_private_transactions(
+        mut self,
+        propagate_expired_private_txs: bool,
+    ) -> Self {
+        self.propagate_expired_private_transactions = propagate_expired_private_txs;
+        self
+    }
 }
 
 #[cfg(test)]
@@ -333,6 +356,15 @@ mod tests {
         assert!(!new_config.propagate_local_transactions);
     }
 
+    #[test]
+    fn test_set_propagate_expired_private_transactions() {
+        let config = LocalTransactionConfig::default();
+        assert!(!config.propagate_expired_private_transactions);
+
+        let new_config = config.set_propagate_expired_private_transactions(true);
+        assert!(new_config.propagate_expired_private_transactions);
+    }
+
     #[test]
     fn scale_pool_limit() {
         let limit = SubPoolLimit::default();
@@ -1,7 +1,7 @@
 //! Transaction pool errors
 
 use alloy_eips::eip4844::BlobTransactionValidationError;
-use alloy_primitives::{Address, TxHash, U256};
+use alloy_primitives::{Address, Selector, TxHash, U256};
 use reth_primitives::InvalidTransactionError;
 
 /// Transaction pool result type.
@@ -227,6 +227,35 @@ pub enum InvalidPoolTransactionError {
     /// invocation.
     #[error("intrinsic gas too low")]
     IntrinsicGasTooLow,
+    /// Thrown if the sender of the transaction is on the deny list of the
+    /// [`DenyListPolicy`](crate::validate::DenyListPolicy).
+    #[error("sender {0} is denied")]
+    DeniedSender(Address),
+    /// Thrown if the recipient of the transaction is on the deny list of the
+    /// [`DenyListPolicy`](crate::validate::DenyListPolicy).
+    #[error("recipient {0} is denied")]
+    DeniedRecipient(Address),
+    /// Thrown if the sender exceeded the number of transactions allowed by the
+    /// [`SenderRateLimitPolicy`](crate::validate::SenderRateLimitPolicy).
+    #[error("sender {0} exceeded the transaction rate limit")]
+    SenderRateLimited(Address),
+    /// Thrown if the tip of the transaction is below the minimum of the
+    /// [`MinTipPolicy`](crate::validate::MinTipPolicy).
+    #[error("transaction tip {tip} below minimum {minimum}")]
+    TipBelowMinimum {
+        /// The priority fee (or gas price) of the transaction.
+        tip: u128,
+        /// The minimum tip of the subpool the transaction belongs to.
+        minimum: u128,
+    },
+    /// Thrown if the transaction calls a function selector denied by the
+    /// [`SelectorDenyListPolicy`](crate::validate::SelectorDenyListPolicy).
+    #[error("function selector {0} is denied")]
+    DeniedSelector(Selector),
+    /// Thrown if the input data of the transaction exceeds the limit of the
+    /// [`MaxCalldataPolicy`](crate::validate::MaxCalldataPolicy).
+    #[error("transaction calldata size {0} exceeds limit {1}")]
+    CalldataTooLarge(usize, usize),
 }
 
 // === impl InvalidPoolTransactionError ===
@@ -314,6 +343,15 @@ impl InvalidPoolTransactionError {
             Self::Eip7702(eip7702_err) => match eip7702_err {
                 Eip7702PoolTransactionError::MissingEip7702AuthorizationList => false,
             },
+            Self::DeniedSender(_) |
+            Self::DeniedRecipient(_) |
+            Self::SenderRateLimited(_) |
+            Self::TipBelowMinimum { .. } |
+            Self::DeniedSelector(_) |
+            Self::CalldataTooLarge(_, _) => {
+                // rejected by a local admission policy, the transaction itself is valid
+                false
+            }
         }
     }
 
@@ -322,4 +360,18 @@ impl InvalidPoolTransactionError {
         matches!(self, Self::Consensus(InvalidTransactionError::NonceNotConsistent { .. })) ||
             matches!(self, Self::Eip4844(Eip4844PoolTransactionError::Eip4844NonceGap))
     }
+
+    /// Returns `true` if the transaction was rejected by an
+    /// [`AdmissionPolicy`](crate::validate::AdmissionPolicy).
+    pub const fn is_admission_policy_rejection(&self) -> bool {
+        matches!(
+            self,
+            Self::DeniedSender(_) |
+                Self::DeniedRecipient(_) |
+                Self::SenderRateLimited(_) |
+                Self::TipBelowMinimum { .. } |
+                Self::DeniedSelector(_) |
+                Self::CalldataTooLarge(_, _)
+        )
+    }
 }
@@ -156,13 +156,17 @@ pub use crate::{
         LocalTransactionConfig, PoolConfig, PriceBumpConfig, SubPoolLimit, DEFAULT_PRICE_BUMP,
         DEFAULT_TXPOOL_ADDITIONAL_VALIDATION_TASKS, MAX_NEW_PENDING_TXS_NOTIFICATIONS,
         REPLACE_BLOB_PRICE_BUMP, TXPOOL_MAX_ACCOUNT_SLOTS_PER_SENDER,
-        TXPOOL_SUBPOOL_MAX_SIZE_MB_DEFAULT, TXPOOL_SUBPOOL_MAX_TXS_DEFAULT,
+        TXPOOL_MAX_HISTORY_EVENTS_DEFAULT, TXPOOL_SUBPOOL_MAX_SIZE_MB_DEFAULT,
+        TXPOOL_SUBPOOL_MAX_TXS_DEFAULT,
     },
     error::PoolResult,
-    ordering::{CoinbaseTipOrdering, Priority, TransactionOrdering},
+    ordering::{
+        CoinbaseTipOrdering, Priority, SimulatedPaymentOrdering, SimulatedPayments,
+        TransactionOrdering,
+    },
     pool::{
-        blob_tx_priority, fee_delta, state::SubPool, AllTransactionsEvents, FullTransactionEvent,
-        TransactionEvent, TransactionEvents,
+        blob_tx_priority, fee_delta, state::SubPool, AllTransactionsEvents, DiscardReason,
+        FullTransactionEvent, TransactionEvent, TransactionEvents, TransactionHistoryEvent,
     },
     traits::*,
     validate::{
@@ -172,7 +176,7 @@ pub use crate::{
 };
 use crate::{identifier::TransactionId, pool::PoolInner};
 use alloy_eips::eip4844::{BlobAndProofV1, BlobTransactionSidecar};
-use alloy_primitives::{Address, TxHash, B256, U256};
+use alloy_primitives::{Address, BlockNumber, TxHash, B256, U256};
 use aquamarine as _;
 use reth_eth_wire_types::HandleMempoolData;
 use reth_execution_types::ChangedAccount;
@@ -370,6 +374,18 @@ where
         self.pool.add_transactions(origin, validated.into_iter().map(|(_, tx)| tx))
     }
 
+    async fn add_private_transaction(
+        &self,
+        transaction: Self::Transaction,
+        max_block_number: Option<BlockNumber>,
+    ) -> PoolResult<TxHash> {
+        let hash = self.add_transaction(TransactionOrigin::Private, transaction).await?;
+        if let Some(max_block_number) = max_block_number {
+            self.pool.set_private_transaction_deadline(hash, max_block_number);
+        }
+        Ok(hash)
+    }
+
     fn transaction_event_listener(&self, tx_hash: TxHash) -> Option<TransactionEvents> {
         self.pool.add_transaction_event_listener(tx_hash)
     }
@@ -481,6 +497,20 @@ where
         self.pool.remove_transactions_by_sender(sender)
     }
 
+    fn remove_expired_private_transactions(
+        &self,
+        block_number: BlockNumber,
+    ) -> Vec<Arc<ValidPoolTransaction<Self::Transaction>>> {
+        self.pool.remove_expired_private_transactions(block_number)
+    }
+
+    fn remove_mined_private_transactions(
+        &self,
+        tx_hashes: Vec<TxHash>,
+    ) -> Vec<(TxHash, Option<BlockNumber>)> {
+        self.pool.remove_mined_private_transactions(tx_hashes)
+    }
+
     fn retain_unknown<A>(&self, announcement: &mut A)
     where
         A: HandleMempoolData,
@@ -500,6 +530,10 @@ where
         self.inner().on_propagated(txs)
     }
 
+    fn transaction_history(&self, tx_hash: TxHash) -> Vec<TransactionHistoryEvent> {
+        self.inner().transaction_history(&tx_hash)
+    }
+
     fn get_transactions_by_sender(
         &self,
         sender: Address,
@@ -560,6 +594,10 @@ where
         self.pool.get_transactions_by_origin(origin)
     }
 
+    fn private_transaction_deadline(&self, tx_hash: &TxHash) -> Option<BlockNumber> {
+        self.pool.private_transaction_deadline(tx_hash)
+    }
+
     /// Returns all pending transactions filtered by [`TransactionOrigin`]
     fn get_pending_transactions_by_origin(
         &self,
@@ -626,6 +664,10 @@ where
         self.pool.update_accounts(accounts);
     }
 
+    fn reprioritize_pending_transactions(&self, senders: Vec<Address>) {
+        self.pool.reprioritize_pending_transactions(senders)
+    }
+
     fn delete_blob(&self, tx: TxHash) {
         self.pool.delete_blob(tx)
     }
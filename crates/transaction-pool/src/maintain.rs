@@ -1,18 +1,22 @@
 //! Support for maintaining the state of the transaction pool
 
 use crate::{
-    blobstore::{BlobStoreCanonTracker, BlobStoreUpdates},
+    blobstore::{BlobArchive, BlobStoreCanonTracker, BlobStoreUpdates, BlockBlobSidecar},
     error::PoolError,
-    metrics::MaintainPoolMetrics,
+    metrics::{JournalMetrics, MaintainPoolMetrics},
     traits::{CanonicalStateUpdate, EthPoolTransaction, TransactionPool, TransactionPoolExt},
-    BlockInfo, PoolTransaction, PoolUpdateKind,
+    validate::Readmissions,
+    BlockInfo, PoolTransaction, PoolUpdateKind, TransactionOrigin,
 };
-use alloy_consensus::{BlockHeader, Typed2718};
-use alloy_eips::BlockNumberOrTag;
-use alloy_primitives::{Address, BlockHash, BlockNumber};
-use alloy_rlp::Encodable;
+use alloy_consensus::{BlockHeader, Transaction as _, Typed2718};
+use alloy_eips::{
+    eip2718::{Decodable2718, Encodable2718},
+    BlockNumHash, BlockNumberOrTag,
+};
+use alloy_primitives::{Address, BlockHash, BlockNumber, Bytes, TxHash};
+use alloy_rlp::{Encodable, RlpDecodable, RlpEncodable};
 use futures_util::{
-    future::{BoxFuture, Fuse, FusedFuture},
+    future::{self, BoxFuture, Either, Fuse, FusedFuture},
     FutureExt, Stream, StreamExt,
 };
 use reth_chain_state::CanonStateNotification;
@@ -22,21 +26,27 @@ use reth_fs_util::FsPathError;
 use reth_primitives::{
     transaction::SignedTransactionIntoRecoveredExt, SealedHeader, TransactionSigned,
 };
-use reth_primitives_traits::{NodePrimitives, SignedTransaction};
+use reth_primitives_traits::{BlockBody, NodePrimitives, SignedTransaction};
 use reth_storage_api::{errors::provider::ProviderError, BlockReaderIdExt, StateProviderFactory};
-use reth_tasks::TaskSpawner;
+use reth_tasks::{shutdown::GracefulShutdown, TaskSpawner};
 use std::{
     borrow::Borrow,
-    collections::HashSet,
+    collections::{HashMap, HashSet},
     hash::{Hash, Hasher},
+    io::Write,
     path::{Path, PathBuf},
+    pin::pin,
     sync::Arc,
+    time::Duration,
+};
+use tokio::{
+    sync::oneshot,
+    time::{Instant, MissedTickBehavior},
 };
-use tokio::sync::oneshot;
 use tracing::{debug, error, info, trace, warn};
 
 /// Additional settings for maintaining the transaction pool
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone)]
 pub struct MaintainPoolConfig {
     /// Maximum (reorg) depth we handle when updating the transaction pool: `new.number -
     /// last_seen.number`
@@ -47,11 +57,22 @@ pub struct MaintainPoolConfig {
     ///
     /// Default: 100
     pub max_reload_accounts: usize,
+    /// The transactions reinjected after a reorg are marked as [`Readmissions`], which exempts
+    /// them from some admission policies.
+    pub readmissions: Readmissions,
+}
+
+impl MaintainPoolConfig {
+    /// Sets the [`Readmissions`] handle of the validator's admission policies.
+    pub fn with_readmissions(mut self, readmissions: Readmissions) -> Self {
+        self.readmissions = readmissions;
+        self
+    }
 }
 
 impl Default for MaintainPoolConfig {
     fn default() -> Self {
-        Self { max_update_depth: 64, max_reload_accounts: 100 }
+        Self { max_update_depth: 64, max_reload_accounts: 100, readmissions: Default::default() }
     }
 }
 
@@ -69,6 +90,46 @@ impl LocalTransactionBackupConfig {
     }
 }
 
+/// The default interval at which the transaction pool journal is saved.
+pub const DEFAULT_JOURNAL_INTERVAL: Duration = Duration::from_secs(60);
+
+/// The number of journaled transactions that are validated and reinserted at once.
+const JOURNAL_RELOAD_BATCH_SIZE: usize = 1_000;
+
+/// Settings for the transaction pool journal task
+#[derive(Debug, Clone)]
+pub struct TransactionPoolJournalConfig {
+    /// Path to the journal file
+    pub path: PathBuf,
+    /// Interval at which the journal is saved
+    ///
+    /// Default: 60s
+    pub interval: Duration,
+    /// The transactions restored from the journal are marked as [`Readmissions`], which exempts
+    /// them from some admission policies.
+    pub readmissions: Readmissions,
+}
+
+impl TransactionPoolJournalConfig {
+    /// Creates a new config that saves the journal to the given path every
+    /// [`DEFAULT_JOURNAL_INTERVAL`].
+    pub fn new(path: PathBuf) -> Self {
+        Self { path, interval: DEFAULT_JOURNAL_INTERVAL, readmissions: Default::default() }
+    }
+
+    /// Sets the interval at which the journal is saved.
+    pub const fn with_interval(mut self, interval: Duration) -> Self {
+        self.interval = interval;
+        self
+    }
+
+    /// Sets the [`Readmissions`] handle of the validator's admission policies.
+    pub fn with_readmissions(mut self, readmissions: Readmissions) -> Self {
+        self.readmissions = readmissions;
+        self
+    }
+}
+
 /// Returns a spawnable future for maintaining the state of the transaction pool.
 pub fn maintain_transaction_pool_future<N, Client, P, St, Tasks>(
     client: Client,
@@ -115,7 +176,7 @@ pub async fn maintain_transaction_pool<N, Client, P, St, Tasks>(
     Tasks: TaskSpawner + 'static,
 {
     let metrics = MaintainPoolMetrics::default();
-    let MaintainPoolConfig { max_update_depth, max_reload_accounts, .. } = config;
+    let MaintainPoolConfig { max_update_depth, max_reload_accounts, readmissions } = config;
     // ensure the pool points to latest state
     if let Ok(Some(latest)) = client.header_by_number_or_tag(BlockNumberOrTag::Latest) {
         let latest = SealedHeader::seal(latest);
@@ -369,11 +430,17 @@ pub async fn maintain_transaction_pool<N, Client, P, St, Tasks>(
                 // all transactions that were mined in the old chain but not in the new chain need
                 // to be re-injected
                 //
-                // Note: we no longer know if the tx was local or external
+                // Note: we no longer know if the tx was local or external, only private
+                // transactions are tracked
                 // Because the transactions are not finalized, the corresponding blobs are still in
                 // blob store (if we previously received them from the network)
                 metrics.inc_reinserted_transactions(pruned_old_transactions.len());
-                let _ = pool.add_external_transactions(pruned_old_transactions).await;
+                readmissions.extend(pruned_old_transactions.iter().map(|tx| *tx.hash()));
+                reinject_reorged_transactions(&pool, pruned_old_transactions).await;
+
+                // expire after re-injecting, so reorged private transactions past their max block
+                // number are removed as well
+                expire_private_transactions(&pool, new_tip.number()).await;
 
                 // keep track of new mined blob transactions
                 blob_store_tracker.add_new_chain_blocks(&new_blocks);
@@ -414,6 +481,7 @@ pub async fn maintain_transaction_pool<N, Client, P, St, Tasks>(
                         pending_blob_fee: pending_block_blob_fee,
                     };
                     pool.set_block_info(info);
+                    expire_private_transactions(&pool, tip.number()).await;
 
                     // keep track of mined blob transactions
                     blob_store_tracker.add_new_chain_blocks(&blocks);
@@ -448,6 +516,7 @@ pub async fn maintain_transaction_pool<N, Client, P, St, Tasks>(
                     update_kind: PoolUpdateKind::Commit,
                 };
                 pool.on_canonical_state_change(update);
+                expire_private_transactions(&pool, tip.number()).await;
 
                 // keep track of mined blob transactions
                 blob_store_tracker.add_new_chain_blocks(&blocks);
@@ -456,6 +525,41 @@ pub async fn maintain_transaction_pool<N, Client, P, St, Tasks>(
     }
 }
 
+/// Removes the private transactions that were not included up to their max block number, and
+/// re-injects them as local transactions if they should be made public.
+async fn expire_private_transactions<P>(pool: &P, block_number: BlockNumber)
+where
+    P: TransactionPoolExt,
+{
+    let public = pool.remove_expired_private_transactions(block_number);
+    if !public.is_empty() {
+        let transactions = public.iter().map(|tx| tx.transaction.clone()).collect();
+        let _ = pool.add_transactions(TransactionOrigin::Local, transactions).await;
+    }
+}
+
+/// Re-injects the transactions of reorged blocks.
+///
+/// Private transactions are re-added as private with their max block number, all other
+/// transactions are re-added as external.
+async fn reinject_reorged_transactions<P>(pool: &P, transactions: Vec<P::Transaction>)
+where
+    P: TransactionPoolExt,
+{
+    let mined_private: HashMap<_, _> = pool
+        .remove_mined_private_transactions(transactions.iter().map(|tx| *tx.hash()).collect())
+        .into_iter()
+        .collect();
+    let (private, external): (Vec<_>, Vec<_>) =
+        transactions.into_iter().partition(|tx| mined_private.contains_key(tx.hash()));
+
+    for tx in private {
+        let max_block_number = mined_private[tx.hash()];
+        let _ = pool.add_private_transaction(tx, max_block_number).await;
+    }
+    let _ = pool.add_external_transactions(external).await;
+}
+
 struct FinalizedBlockTracker {
     last_finalized_block: Option<BlockNumber>,
 }
@@ -641,6 +745,9 @@ pub enum TransactionsBackupError {
     /// Error adding transactions to the transaction pool
     #[error("failed to insert transactions to the transactions pool. Encountered pool error: {0}")]
     Pool(#[from] PoolError),
+    /// Error joining the blocking task that reads or writes the file
+    #[error("failed to access transactions backup. Encountered task error: {0}")]
+    Task(#[from] tokio::task::JoinError),
 }
 
 /// Task which manages saving local transactions to the persistent file in case of shutdown.
@@ -669,18 +776,357 @@ pub async fn backup_local_transactions_task<P>(
     drop(graceful_guard)
 }
 
+/// A transaction of the transaction pool journal.
+#[derive(Debug, Clone, PartialEq, Eq, RlpEncodable, RlpDecodable)]
+struct JournalEntry {
+    /// The [`TransactionOrigin`] of the transaction.
+    origin: u8,
+    /// The EIP-2718 encoded pooled transaction, which includes the sidecar of a blob transaction.
+    transaction: Bytes,
+    /// The max block number of a private transaction, see
+    /// [`TransactionPool::add_private_transaction`].
+    #[rlp(trailing)]
+    max_block_number: Option<BlockNumber>,
+}
+
+impl JournalEntry {
+    const fn new(
+        origin: TransactionOrigin,
+        transaction: Bytes,
+        max_block_number: Option<BlockNumber>,
+    ) -> Self {
+        let origin = match origin {
+            TransactionOrigin::Local => 0,
+            TransactionOrigin::External => 1,
+            TransactionOrigin::Private => 2,
+        };
+        Self { origin, transaction, max_block_number }
+    }
+
+    /// Returns the origin of the transaction, or `None` if it's unknown.
+    const fn origin(&self) -> Option<TransactionOrigin> {
+        match self.origin {
+            0 => Some(TransactionOrigin::Local),
+            1 => Some(TransactionOrigin::External),
+            2 => Some(TransactionOrigin::Private),
+            _ => None,
+        }
+    }
+}
+
+/// A decoded transaction of the transaction pool journal.
+#[derive(Debug)]
+struct JournaledTransaction<T> {
+    origin: TransactionOrigin,
+    max_block_number: Option<BlockNumber>,
+    transaction: T,
+}
+
+/// Loads the transaction pool journal and reinserts its transactions into the pool with their
+/// original origin, and the max block number of private transactions.
+///
+/// The transactions are reinserted in order of sender and nonce, so that there are no nonce gaps,
+/// which aren't allowed for blob transactions. They are validated again in batches of
+/// [`JOURNAL_RELOAD_BATCH_SIZE`], which is tracked by the reload metrics.
+async fn load_journal<P>(
+    pool: &P,
+    file_path: &Path,
+    readmissions: &Readmissions,
+    metrics: &JournalMetrics,
+) -> Result<(), TransactionsBackupError>
+where
+    P: TransactionPool,
+{
+    let path = file_path.to_path_buf();
+    let entries = tokio::task::spawn_blocking(move || {
+        if !path.exists() {
+            return Ok(Vec::new())
+        }
+        let data = reth_fs_util::read(&path)?;
+        if data.is_empty() {
+            return Ok(Vec::new())
+        }
+        Ok::<Vec<JournalEntry>, TransactionsBackupError>(alloy_rlp::Decodable::decode(
+            &mut data.as_slice(),
+        )?)
+    })
+    .await??;
+    if entries.is_empty() {
+        return Ok(())
+    }
+
+    info!(target: "txpool", journal=?file_path, num_txs=%entries.len(), "Reloading transactions from the journal");
+    metrics.reload_total_transactions.set(entries.len() as f64);
+
+    let total = entries.len();
+    let mut transactions = entries
+        .into_iter()
+        .filter_map(|entry| {
+            let origin = entry.origin()?;
+            let pooled = <P::Transaction as PoolTransaction>::Pooled::decode_2718(
+                &mut entry.transaction.as_ref(),
+            )
+            .inspect_err(|err| {
+                debug!(target: "txpool", %err, "failed to decode journaled transaction");
+            })
+            .ok()?;
+            let transaction = pooled.try_ecrecovered().map(P::Transaction::from_pooled)?;
+            Some(JournaledTransaction {
+                origin,
+                max_block_number: entry.max_block_number,
+                transaction,
+            })
+        })
+        .collect::<Vec<_>>();
+    transactions.sort_by_key(|tx| (tx.transaction.sender(), tx.transaction.nonce()));
+    readmissions.extend(transactions.iter().map(|tx| *tx.transaction.hash()));
+
+    let mut processed = total - transactions.len();
+    let mut reloaded = 0;
+    for batch in transactions.chunks(JOURNAL_RELOAD_BATCH_SIZE) {
+        // consecutive transactions of the same origin are validated together, which keeps the
+        // order of sender and nonce
+        for run in batch.chunk_by(|a, b| a.origin == b.origin) {
+            let origin = run[0].origin;
+            let added = if origin == TransactionOrigin::Private {
+                let mut added = 0;
+                for tx in run {
+                    if pool
+                        .add_private_transaction(tx.transaction.clone(), tx.max_block_number)
+                        .await
+                        .is_ok()
+                    {
+                        added += 1;
+                    }
+                }
+                added
+            } else {
+                let transactions = run.iter().map(|tx| tx.transaction.clone()).collect();
+                let outcome = pool.add_transactions(origin, transactions).await;
+                outcome.iter().filter(|res| res.is_ok()).count()
+            };
+            reloaded += added;
+            metrics.reloaded_transactions.increment(added as u64);
+        }
+
+        processed += batch.len();
+        metrics.reload_processed_transactions.set(processed as f64);
+        debug!(target: "txpool", processed, total, "Reloaded batch of journaled transactions");
+    }
+
+    info!(target: "txpool", journal=?file_path, num_txs=%reloaded, "Successfully reinserted transactions from the journal");
+    Ok(())
+}
+
+/// Writes all transactions of the pool to the journal, together with the max block number of
+/// private transactions.
+///
+/// The transactions are sorted by sender and nonce, so that they are reinserted without nonce
+/// gaps, which aren't allowed for blob transactions.
+async fn save_journal<P>(pool: &P, file_path: &Path, metrics: &JournalMetrics)
+where
+    P: TransactionPool,
+{
+    let mut transactions =
+        [TransactionOrigin::Local, TransactionOrigin::External, TransactionOrigin::Private]
+            .into_iter()
+            .flat_map(|origin| pool.get_transactions_by_origin(origin))
+            .collect::<Vec<_>>();
+    transactions.sort_unstable_by_key(|tx| (tx.sender(), tx.nonce()));
+
+    let mut blob_sidecars = 0;
+    let entries = transactions
+        .iter()
+        .filter_map(|tx| {
+            // the transaction may have been removed in the meantime
+            let pooled = pool.get_pooled_transaction_element(*tx.hash())?;
+            if tx.is_eip4844() {
+                blob_sidecars += 1;
+            }
+            let max_block_number = tx
+                .origin
+                .is_private()
+                .then(|| pool.private_transaction_deadline(tx.hash()))
+                .flatten();
+            Some(JournalEntry::new(
+                tx.origin,
+                pooled.into_signed().encoded_2718().into(),
+                max_block_number,
+            ))
+        })
+        .collect::<Vec<_>>();
+    let num_txs = entries.len();
+
+    let path = file_path.to_path_buf();
+    let res = tokio::task::spawn_blocking(move || {
+        let mut buf = Vec::new();
+        alloy_rlp::encode_list(&entries, &mut buf);
+
+        path.parent()
+            .map(reth_fs_util::create_dir_all)
+            .transpose()
+            .and_then(|_| reth_fs_util::atomic_write_file(&path, |file| file.write_all(&buf)))
+    })
+    .await
+    .map_err(TransactionsBackupError::from)
+    .and_then(|res| res.map_err(Into::into));
+    match res {
+        Ok(_) => {
+            debug!(target: "txpool", journal=?file_path, num_txs, blob_sidecars, "Wrote transaction pool journal");
+            metrics.journaled_transactions.set(num_txs as f64);
+            metrics.journaled_blob_sidecars.set(blob_sidecars as f64);
+        }
+        Err(err) => {
+            warn!(target: "txpool", %err, journal=?file_path, "Failed to write transaction pool journal");
+            metrics.failed_writes.increment(1);
+        }
+    }
+}
+
+/// Task which journals all transactions of the pool, including the sidecars of blob transactions,
+/// to the persistent file at the configured interval and on shutdown.
+///
+/// Reloads the journal on boot up and reinserts the transactions into the pool with their original
+/// origin, after validating them again.
+pub async fn journal_transactions_task<P>(
+    shutdown: GracefulShutdown,
+    pool: P,
+    config: TransactionPoolJournalConfig,
+) where
+    P: TransactionPool,
+{
+    let TransactionPoolJournalConfig { path, interval, readmissions } = config;
+    let metrics = JournalMetrics::default();
+
+    if let Err(err) = load_journal(&pool, &path, &readmissions, &metrics).await {
+        error!(target: "txpool", "{}", err)
+    }
+
+    let mut shutdown = shutdown;
+    let mut interval = tokio::time::interval_at(Instant::now() + interval, interval);
+    interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+    loop {
+        match future::select(&mut shutdown, pin!(interval.tick())).await {
+            Either::Left((graceful_guard, _)) => {
+                // write transactions to disk
+                save_journal(&pool, &path, &metrics).await;
+                drop(graceful_guard);
+                return
+            }
+            Either::Right(_) => save_journal(&pool, &path, &metrics).await,
+        }
+    }
+}
+
+/// Task which archives the blob sidecars of the transactions included in canonical blocks into the
+/// [`BlobArchive`], and prunes the blocks that fall out of its retention window.
+///
+/// The sidecars are taken from the blob store of the pool, which keeps them until the block is
+/// finalized. Sidecars of transactions that were never in the pool are not available and are
+/// skipped. The sidecars of reorged blocks are removed from the archive.
+///
+/// The archive and the blob store are accessed on a blocking task.
+pub async fn archive_blob_sidecars_task<N, P, St>(pool: P, archive: BlobArchive, mut events: St)
+where
+    N: NodePrimitives,
+    P: TransactionPool + 'static,
+    St: Stream<Item = CanonStateNotification<N>> + Send + Unpin + 'static,
+{
+    while let Some(event) = events.next().await {
+        let pool = pool.clone();
+        let archive = archive.clone();
+        if let Err(err) =
+            tokio::task::spawn_blocking(move || archive_blob_sidecars(&pool, &archive, &event))
+                .await
+        {
+            warn!(target: "txpool", %err, "Failed to archive blob sidecars");
+        }
+    }
+}
+
+/// Archives the blob sidecars of the blocks of a [`CanonStateNotification`], see
+/// [`archive_blob_sidecars_task`].
+fn archive_blob_sidecars<N, P>(pool: &P, archive: &BlobArchive, event: &CanonStateNotification<N>)
+where
+    N: NodePrimitives,
+    P: TransactionPool,
+{
+    if let Some(old) = event.reverted() {
+        for block in old.blocks().values() {
+            let block = BlockNumHash::new(block.number(), block.hash());
+            if let Err(err) = archive.remove_block(block) {
+                warn!(target: "txpool", %err, ?block, "Failed to remove reorged blob sidecars from the archive");
+            }
+        }
+    }
+
+    let new = event.committed();
+    for block in new.blocks().values() {
+        let blob_txs = blob_transactions(block.body().transactions());
+        if blob_txs.is_empty() {
+            continue
+        }
+
+        let num_hash = BlockNumHash::new(block.number(), block.hash());
+        let tx_hashes = blob_txs.iter().map(|(tx_hash, _)| *tx_hash).collect();
+        let mut sidecars = match pool.get_all_blobs(tx_hashes) {
+            Ok(sidecars) => sidecars.into_iter().collect::<HashMap<_, _>>(),
+            Err(err) => {
+                warn!(target: "txpool", %err, block = ?num_hash, "Failed to read blob sidecars of included transactions");
+                continue
+            }
+        };
+        if sidecars.len() < blob_txs.len() {
+            debug!(target: "txpool", block = ?num_hash, missing = blob_txs.len() - sidecars.len(), "Blob sidecars of included transactions are not in the blob store");
+        }
+
+        // the blobs of missing sidecars leave gaps in the indexes of the archived blobs
+        let sidecars = blob_txs
+            .into_iter()
+            .filter_map(|(tx_hash, first_blob_index)| {
+                let sidecar = Arc::unwrap_or_clone(sidecars.remove(&tx_hash)?);
+                Some(BlockBlobSidecar { tx_hash, first_blob_index, sidecar })
+            })
+            .collect();
+        if let Err(err) = archive.insert_block(num_hash, sidecars) {
+            warn!(target: "txpool", %err, block = ?num_hash, "Failed to archive blob sidecars");
+        }
+    }
+
+    if let Err(err) = archive.prune(new.tip().number()) {
+        warn!(target: "txpool", %err, "Failed to prune the blob archive");
+    }
+}
+
+/// Returns the hashes of the blob transactions of a block, with the index of their first blob in
+/// the block.
+fn blob_transactions<'a, T: SignedTransaction + 'a>(
+    transactions: impl IntoIterator<Item = &'a T>,
+) -> Vec<(TxHash, u64)> {
+    let mut blob_index = 0;
+    let mut blob_txs = Vec::new();
+    for tx in transactions {
+        let Some(versioned_hashes) = tx.blob_versioned_hashes() else { continue };
+        blob_txs.push((*tx.tx_hash(), blob_index));
+        blob_index += versioned_hashes.len() as u64;
+    }
+    blob_txs
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::{
-        blobstore::InMemoryBlobStore, validate::EthTransactionValidatorBuilder,
-        CoinbaseTipOrdering, EthPooledTransaction, Pool, TransactionOrigin,
+        blobstore::InMemoryBlobStore, test_utils::TransactionGenerator,
+        validate::EthTransactionValidatorBuilder, CoinbaseTipOrdering, EthPooledTransaction, Pool,
+        TransactionOrigin,
     };
     use alloy_eips::eip2718::Decodable2718;
     use alloy_primitives::{hex, U256};
     use reth_chainspec::MAINNET;
     use reth_fs_util as fs;
-    use reth_primitives::PooledTransaction;
+    use reth_primitives::{Header, PooledTransaction, SealedBlock};
     use reth_provider::test_utils::{ExtendedAccount, MockEthProvider};
     use reth_tasks::TaskManager;
 
@@ -743,6 +1189,156 @@ mod tests {
         temp_dir.close().unwrap();
     }
 
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_journal_roundtrip() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let journal_path = temp_dir.path().join("txpool-journal").with_extension(EXTENSION);
+        let provider = MockEthProvider::default();
+        let blob_store = InMemoryBlobStore::default();
+        let validator = EthTransactionValidatorBuilder::new(MAINNET.clone())
+            .build(provider.clone(), blob_store.clone());
+        let metrics = JournalMetrics::default();
+
+        let txpool = Pool::new(
+            validator.clone(),
+            CoinbaseTipOrdering::default(),
+            blob_store.clone(),
+            Default::default(),
+        );
+
+        let mut hashes = Vec::new();
+        for origin in
+            [TransactionOrigin::Local, TransactionOrigin::External, TransactionOrigin::Private]
+        {
+            // a new signer for every transaction, so the transactions are distinct
+            let transaction =
+                TransactionGenerator::with_num_signers(rand::thread_rng(), 1).gen_eip1559_pooled();
+            provider.add_account(transaction.sender(), ExtendedAccount::new(0, U256::MAX));
+            hashes.push((txpool.add_transaction(origin, transaction).await.unwrap(), origin));
+        }
+
+        save_journal(&txpool, &journal_path, &metrics).await;
+
+        let reloaded_pool =
+            Pool::new(validator, CoinbaseTipOrdering::default(), blob_store, Default::default());
+        load_journal(&reloaded_pool, &journal_path, &Default::default(), &metrics).await.unwrap();
+
+        assert_eq!(reloaded_pool.len(), hashes.len());
+        for (hash, origin) in hashes {
+            assert_eq!(reloaded_pool.get(&hash).unwrap().origin, origin);
+        }
+
+        temp_dir.close().unwrap();
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_journal_reload_order_and_deadlines() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let journal_path = temp_dir.path().join("txpool-journal").with_extension(EXTENSION);
+        let provider = MockEthProvider::default();
+        let blob_store = InMemoryBlobStore::default();
+        let validator = EthTransactionValidatorBuilder::new(MAINNET.clone())
+            .build(provider.clone(), blob_store.clone());
+        let metrics = JournalMetrics::default();
+
+        let txpool = Pool::new(
+            validator.clone(),
+            CoinbaseTipOrdering::default(),
+            blob_store.clone(),
+            Default::default(),
+        );
+
+        // a private transaction after an external one of the same sender
+        let mut gen = TransactionGenerator::with_num_signers(rand::thread_rng(), 1);
+        let external: EthPooledTransaction = gen
+            .transaction()
+            .nonce(0)
+            .into_eip1559()
+            .into_ecrecovered()
+            .unwrap()
+            .try_into()
+            .unwrap();
+        let private: EthPooledTransaction = gen
+            .transaction()
+            .nonce(1)
+            .into_eip1559()
+            .into_ecrecovered()
+            .unwrap()
+            .try_into()
+            .unwrap();
+        provider.add_account(external.sender(), ExtendedAccount::new(0, U256::MAX));
+
+        let private_hash = txpool.add_private_transaction(private, Some(100)).await.unwrap();
+        let external_hash = txpool.add_external_transaction(external).await.unwrap();
+
+        save_journal(&txpool, &journal_path, &metrics).await;
+
+        let reloaded_pool =
+            Pool::new(validator, CoinbaseTipOrdering::default(), blob_store, Default::default());
+        let readmissions = Readmissions::default();
+        load_journal(&reloaded_pool, &journal_path, &readmissions, &metrics).await.unwrap();
+
+        assert_eq!(reloaded_pool.pending_transactions().len(), 2);
+        assert_eq!(reloaded_pool.get(&external_hash).unwrap().origin, TransactionOrigin::External);
+        assert_eq!(reloaded_pool.get(&private_hash).unwrap().origin, TransactionOrigin::Private);
+        assert_eq!(reloaded_pool.private_transaction_deadline(&private_hash), Some(100));
+        assert_eq!(reloaded_pool.private_transaction_deadline(&external_hash), None);
+
+        temp_dir.close().unwrap();
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_reinject_reorged_private_transactions() {
+        let provider = MockEthProvider::default();
+        let blob_store = InMemoryBlobStore::default();
+        let validator = EthTransactionValidatorBuilder::new(MAINNET.clone())
+            .build(provider.clone(), blob_store.clone());
+        let txpool =
+            Pool::new(validator, CoinbaseTipOrdering::default(), blob_store, Default::default());
+
+        let mut transactions = Vec::new();
+        for _ in 0..3 {
+            let transaction =
+                TransactionGenerator::with_num_signers(rand::thread_rng(), 1).gen_eip1559_pooled();
+            provider.add_account(transaction.sender(), ExtendedAccount::new(0, U256::MAX));
+            transactions.push(transaction);
+        }
+        let [private, undated, external] = transactions.clone().try_into().unwrap();
+        let private_hash = txpool.add_private_transaction(private, Some(100)).await.unwrap();
+        let undated_hash = txpool.add_private_transaction(undated, None).await.unwrap();
+        let external_hash = txpool.add_external_transaction(external).await.unwrap();
+
+        // mine all transactions, then reorg them out
+        let block = SealedBlock::new(
+            SealedHeader::seal(Header { number: 10, ..Default::default() }),
+            reth_primitives::BlockBody::default(),
+        );
+        txpool.on_canonical_state_change(CanonicalStateUpdate {
+            new_tip: &block,
+            pending_block_base_fee: 0,
+            pending_block_blob_fee: None,
+            changed_accounts: vec![],
+            mined_transactions: vec![private_hash, undated_hash, external_hash],
+            update_kind: PoolUpdateKind::Commit,
+        });
+        assert!(txpool.is_empty());
+        assert_eq!(txpool.private_transaction_deadline(&private_hash), None);
+
+        reinject_reorged_transactions(&txpool, transactions).await;
+
+        assert_eq!(txpool.len(), 3);
+        assert_eq!(txpool.get(&private_hash).unwrap().origin, TransactionOrigin::Private);
+        assert_eq!(txpool.private_transaction_deadline(&private_hash), Some(100));
+        assert_eq!(txpool.get(&undated_hash).unwrap().origin, TransactionOrigin::Private);
+        assert_eq!(txpool.private_transaction_deadline(&undated_hash), None);
+        assert_eq!(txpool.get(&external_hash).unwrap().origin, TransactionOrigin::External);
+
+        // the private transactions are no longer tracked as mined
+        assert!(txpool
+            .remove_mined_private_transactions(vec![private_hash, undated_hash])
+            .is_empty());
+    }
+
     #[test]
     fn test_update_with_higher_finalized_block() {
         let mut tracker = FinalizedBlockTracker::new(Some(10));
@@ -784,4 +1380,38 @@ mod tests {
         assert_eq!(tracker.update(None), None);
         assert_eq!(tracker.last_finalized_block, None);
     }
+
+    #[test]
+    fn blob_transaction_indexes() {
+        use alloy_consensus::{TxEip1559, TxEip4844};
+        use alloy_primitives::{PrimitiveSignature as Signature, B256};
+        use reth_primitives::Transaction;
+
+        let blob_tx = |blobs: usize| {
+            let tx = Transaction::Eip4844(TxEip4844 {
+                blob_versioned_hashes: vec![B256::random(); blobs],
+                ..Default::default()
+            });
+            TransactionSigned::new_unhashed(tx, Signature::test_signature())
+        };
+        let transactions = [
+            blob_tx(2),
+            TransactionSigned::new_unhashed(
+                Transaction::Eip1559(TxEip1559::default()),
+                Signature::test_signature(),
+            ),
+            blob_tx(1),
+            blob_tx(3),
+        ];
+
+        // the indexes only count the blobs of earlier transactions
+        assert_eq!(
+            blob_transactions(&transactions),
+            vec![
+                (*transactions[0].tx_hash(), 0),
+                (*transactions[2].tx_hash(), 2),
+                (*transactions[3].tx_hash(), 3),
+            ]
+        );
+    }
 }
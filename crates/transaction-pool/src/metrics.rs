@@ -106,6 +106,24 @@ impl MaintainPoolMetrics {
     }
 }
 
+/// Transaction pool journal metrics
+#[derive(Metrics)]
+#[metrics(scope = "transaction_pool.journal")]
+pub struct JournalMetrics {
+    /// Number of transactions written to the journal the last time it was saved
+    pub(crate) journaled_transactions: Gauge,
+    /// Number of blob sidecars written to the journal the last time it was saved
+    pub(crate) journaled_blob_sidecars: Gauge,
+    /// Number of times the journal failed to be saved
+    pub(crate) failed_writes: Counter,
+    /// Number of transactions in the journal that is being reloaded
+    pub(crate) reload_total_transactions: Gauge,
+    /// Number of journaled transactions that have been processed by the validator so far
+    pub(crate) reload_processed_transactions: Gauge,
+    /// Number of journaled transactions that have been reinserted into the pool
+    pub(crate) reloaded_transactions: Counter,
+}
+
 /// All Transactions metrics
 #[derive(Metrics)]
 #[metrics(scope = "transaction_pool")]
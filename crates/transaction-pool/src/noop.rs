@@ -13,14 +13,14 @@ use crate::{
     validate::ValidTransaction,
     AllPoolTransactions, AllTransactionsEvents, BestTransactions, BlockInfo, EthPoolTransaction,
     EthPooledTransaction, NewTransactionEvent, PoolResult, PoolSize, PoolTransaction,
-    PropagatedTransactions, TransactionEvents, TransactionOrigin, TransactionPool,
-    TransactionValidationOutcome, TransactionValidator, ValidPoolTransaction,
+    PropagatedTransactions, TransactionEvents, TransactionHistoryEvent, TransactionOrigin,
+    TransactionPool, TransactionValidationOutcome, TransactionValidator, ValidPoolTransaction,
 };
 use alloy_eips::{
     eip1559::ETHEREUM_BLOCK_GAS_LIMIT,
     eip4844::{BlobAndProofV1, BlobTransactionSidecar},
 };
-use alloy_primitives::{Address, TxHash, B256, U256};
+use alloy_primitives::{Address, BlockNumber, TxHash, B256, U256};
 use reth_eth_wire_types::HandleMempoolData;
 use reth_primitives::RecoveredTx;
 use std::{collections::HashSet, marker::PhantomData, sync::Arc};
@@ -83,6 +83,15 @@ impl TransactionPool for NoopTransactionPool {
             .collect()
     }
 
+    async fn add_private_transaction(
+        &self,
+        transaction: Self::Transaction,
+        _max_block_number: Option<BlockNumber>,
+    ) -> PoolResult<TxHash> {
+        let hash = *transaction.hash();
+        Err(PoolError::other(hash, Box::new(NoopInsertError::new(transaction))))
+    }
+
     fn transaction_event_listener(&self, _tx_hash: TxHash) -> Option<TransactionEvents> {
         None
     }
@@ -200,6 +209,20 @@ impl TransactionPool for NoopTransactionPool {
         vec![]
     }
 
+    fn remove_expired_private_transactions(
+        &self,
+        _block_number: BlockNumber,
+    ) -> Vec<Arc<ValidPoolTransaction<Self::Transaction>>> {
+        vec![]
+    }
+
+    fn remove_mined_private_transactions(
+        &self,
+        _tx_hashes: Vec<TxHash>,
+    ) -> Vec<(TxHash, Option<BlockNumber>)> {
+        vec![]
+    }
+
     fn retain_unknown<A>(&self, _announcement: &mut A)
     where
         A: HandleMempoolData,
@@ -216,6 +239,10 @@ impl TransactionPool for NoopTransactionPool {
 
     fn on_propagated(&self, _txs: PropagatedTransactions) {}
 
+    fn transaction_history(&self, _tx_hash: TxHash) -> Vec<TransactionHistoryEvent> {
+        vec![]
+    }
+
     fn get_transactions_by_sender(
         &self,
         _sender: Address,
@@ -274,6 +301,10 @@ impl TransactionPool for NoopTransactionPool {
         vec![]
     }
 
+    fn private_transaction_deadline(&self, _tx_hash: &TxHash) -> Option<BlockNumber> {
+        None
+    }
+
     fn get_pending_transactions_by_origin(
         &self,
         _origin: TransactionOrigin,
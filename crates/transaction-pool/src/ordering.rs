@@ -1,6 +1,7 @@
 use crate::traits::PoolTransaction;
-use alloy_primitives::U256;
-use std::{fmt, marker::PhantomData};
+use alloy_primitives::{map::B256HashMap, TxHash, U256};
+use parking_lot::RwLock;
+use std::{fmt, marker::PhantomData, sync::Arc};
 
 /// Priority of the transaction that can be missing.
 ///
@@ -79,3 +80,129 @@ impl<T> Clone for CoinbaseTipOrdering<T> {
         Self::default()
     }
 }
+
+/// The simulated payments of transactions to the coinbase per unit of gas.
+///
+/// This is shared between the [`SimulatedPaymentOrdering`] and the task that simulates the
+/// pending transactions. The payments depend on the state the transactions were simulated
+/// against, so they must be updated whenever the parent of the next block changes.
+#[derive(Debug, Clone, Default)]
+pub struct SimulatedPayments {
+    payments: Arc<RwLock<B256HashMap<U256>>>,
+}
+
+impl SimulatedPayments {
+    /// Returns the simulated payment per gas of the transaction.
+    pub fn get(&self, hash: &TxHash) -> Option<U256> {
+        self.payments.read().get(hash).copied()
+    }
+
+    /// Records the simulated payment per gas of the transaction, and returns the previous one.
+    pub fn insert(&self, hash: TxHash, payment_per_gas: U256) -> Option<U256> {
+        self.payments.write().insert(hash, payment_per_gas)
+    }
+
+    /// Removes the simulated payment per gas of the transaction, and returns it.
+    pub fn remove(&self, hash: &TxHash) -> Option<U256> {
+        self.payments.write().remove(hash)
+    }
+
+    /// Only keeps the simulated payments of the transactions for which the predicate returns
+    /// `true`.
+    pub fn retain(&self, mut f: impl FnMut(&TxHash) -> bool) {
+        self.payments.write().retain(|hash, _| f(hash));
+    }
+
+    /// Returns the number of simulated transactions.
+    pub fn len(&self) -> usize {
+        self.payments.read().len()
+    }
+
+    /// Returns `true` if there are no simulated transactions.
+    pub fn is_empty(&self) -> bool {
+        self.payments.read().is_empty()
+    }
+
+    /// Removes all simulated payments.
+    pub fn clear(&self) {
+        self.payments.write().clear();
+    }
+}
+
+/// Ordering by the simulated payment to the coinbase per unit of gas.
+///
+/// The payment is the balance change of the coinbase when the transaction is executed, which
+/// includes both the priority fee and direct transfers to the coinbase. Transactions that were not
+/// simulated yet are ordered by their coinbase tip, like with the [`CoinbaseTipOrdering`].
+///
+/// The [`SimulatedPayments`] are filled by a separate task, which must call
+/// [`TransactionPoolExt::reprioritize_pending_transactions`](crate::TransactionPoolExt::reprioritize_pending_transactions)
+/// for the new payments to take effect.
+#[derive(Debug)]
+pub struct SimulatedPaymentOrdering<T> {
+    payments: SimulatedPayments,
+    _transaction: PhantomData<T>,
+}
+
+impl<T> SimulatedPaymentOrdering<T> {
+    /// Creates a new ordering backed by the given simulated payments.
+    pub const fn new(payments: SimulatedPayments) -> Self {
+        Self { payments, _transaction: PhantomData }
+    }
+
+    /// Returns the simulated payments of the ordering.
+    pub const fn payments(&self) -> &SimulatedPayments {
+        &self.payments
+    }
+}
+
+impl<T> TransactionOrdering for SimulatedPaymentOrdering<T>
+where
+    T: PoolTransaction + 'static,
+{
+    type PriorityValue = U256;
+    type Transaction = T;
+
+    fn priority(
+        &self,
+        transaction: &Self::Transaction,
+        base_fee: u64,
+    ) -> Priority<Self::PriorityValue> {
+        self.payments
+            .get(transaction.hash())
+            .or_else(|| transaction.effective_tip_per_gas(base_fee).map(U256::from))
+            .into()
+    }
+}
+
+impl<T> Default for SimulatedPaymentOrdering<T> {
+    fn default() -> Self {
+        Self::new(SimulatedPayments::default())
+    }
+}
+
+impl<T> Clone for SimulatedPaymentOrdering<T> {
+    fn clone(&self) -> Self {
+        Self::new(self.payments.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::MockTransaction;
+
+    #[test]
+    fn simulated_payment_priority() {
+        let ordering = SimulatedPaymentOrdering::<MockTransaction>::default();
+        let tx = MockTransaction::eip1559().with_max_fee(10).with_priority_fee(2);
+
+        assert_eq!(ordering.priority(&tx, 5), Priority::Value(U256::from(2)));
+
+        ordering.payments().insert(*tx.hash(), U256::from(100));
+        assert_eq!(ordering.priority(&tx, 5), Priority::Value(U256::from(100)));
+
+        ordering.payments().clear();
+        assert_eq!(ordering.priority(&tx, 11), Priority::None);
+    }
+}
@@ -1,6 +1,6 @@
-use crate::{traits::PropagateKind, PoolTransaction, ValidPoolTransaction};
+use crate::{pool::state::SubPool, traits::PropagateKind, PoolTransaction, ValidPoolTransaction};
 use alloy_primitives::{TxHash, B256};
-use std::sync::Arc;
+use std::{fmt, sync::Arc};
 
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
@@ -83,3 +83,50 @@ impl TransactionEvent {
         matches!(self, Self::Replaced(_) | Self::Mined(_) | Self::Discarded)
     }
 }
+
+/// The reason why a transaction was discarded from the pool.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DiscardReason {
+    /// Evicted because the sub-pool exceeded its configured
+    /// [`SubPoolLimit::max_txs`](crate::SubPoolLimit::max_txs).
+    CountLimit(SubPool),
+    /// Evicted because the sub-pool exceeded its configured
+    /// [`SubPoolLimit::max_size`](crate::SubPoolLimit::max_size).
+    SizeLimit(SubPool),
+    /// Removed because a transaction of the same sender with a lower nonce was evicted.
+    EvictedAncestor(TxHash),
+    /// The nonce of the transaction is lower than the nonce of the sender's account.
+    NonceTooLow,
+    /// The transaction failed validation.
+    Invalid(String),
+    /// The transaction was removed from the pool on request, e.g. because it expired.
+    Removed,
+}
+
+impl fmt::Display for DiscardReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::CountLimit(pool) => write!(f, "evicted from {pool:?} subpool: count limit"),
+            Self::SizeLimit(pool) => write!(f, "evicted from {pool:?} subpool: size limit"),
+            Self::EvictedAncestor(hash) => write!(f, "ancestor {hash} was evicted"),
+            Self::NonceTooLow => f.write_str("nonce is lower than the account nonce"),
+            Self::Invalid(err) => write!(f, "invalid: {err}"),
+            Self::Removed => f.write_str("removed from the pool"),
+        }
+    }
+}
+
+/// An event that happened to a transaction, recorded in the pool's history of recent events.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct TransactionHistoryEvent {
+    /// The hash of the transaction.
+    pub hash: TxHash,
+    /// The UNIX timestamp in milliseconds at which the event happened.
+    pub timestamp: u64,
+    /// The event.
+    pub event: TransactionEvent,
+    /// Why the event happened, e.g. the [`DiscardReason`] of a discarded transaction.
+    pub reason: Option<String>,
+}
@@ -1,17 +1,21 @@
 //! Listeners for the transaction-pool
 
 use crate::{
-    pool::events::{FullTransactionEvent, TransactionEvent},
+    config::TXPOOL_MAX_HISTORY_EVENTS_DEFAULT,
+    pool::events::{
+        DiscardReason, FullTransactionEvent, TransactionEvent, TransactionHistoryEvent,
+    },
     traits::PropagateKind,
     PoolTransaction, ValidPoolTransaction,
 };
 use alloy_primitives::{TxHash, B256};
 use futures_util::Stream;
 use std::{
-    collections::{hash_map::Entry, HashMap},
+    collections::{hash_map::Entry, HashMap, VecDeque},
     pin::Pin,
     sync::Arc,
     task::{Context, Poll},
+    time::{SystemTime, UNIX_EPOCH},
 };
 use tokio::sync::mpsc::{
     error::TrySendError, Receiver, Sender, UnboundedReceiver, UnboundedSender,
@@ -78,25 +82,41 @@ pub(crate) struct PoolEventBroadcast<T: PoolTransaction> {
     all_events_broadcaster: AllPoolEventsBroadcaster<T>,
     /// All listeners for events for a certain transaction hash.
     broadcasters_by_hash: HashMap<TxHash, PoolEventBroadcaster>,
+    /// The most recent events of all transactions.
+    history: TransactionHistory,
 }
 
 impl<T: PoolTransaction> Default for PoolEventBroadcast<T> {
     fn default() -> Self {
+        Self::new(TXPOOL_MAX_HISTORY_EVENTS_DEFAULT)
+    }
+}
+
+impl<T: PoolTransaction> PoolEventBroadcast<T> {
+    /// Creates a new instance that keeps up to `max_history_events` recent events.
+    pub(crate) fn new(max_history_events: usize) -> Self {
         Self {
             all_events_broadcaster: AllPoolEventsBroadcaster::default(),
             broadcasters_by_hash: HashMap::default(),
+            history: TransactionHistory::new(max_history_events),
         }
     }
-}
 
-impl<T: PoolTransaction> PoolEventBroadcast<T> {
+    /// Returns the recorded events of the transaction, oldest first.
+    pub(crate) fn history(&self, hash: &TxHash) -> Vec<TransactionHistoryEvent> {
+        self.history.get(hash)
+    }
+
     /// Calls the broadcast callback with the `PoolEventBroadcaster` that belongs to the hash.
     fn broadcast_event(
         &mut self,
         hash: &TxHash,
         event: TransactionEvent,
         pool_event: FullTransactionEvent<T>,
+        reason: Option<String>,
     ) {
+        self.history.record(*hash, event.clone(), reason);
+
         // Broadcast to all listeners for the transaction hash.
         if let Entry::Occupied(mut sink) = self.broadcasters_by_hash.entry(*hash) {
             sink.get_mut().broadcast(event.clone());
@@ -133,28 +153,39 @@ impl<T: PoolTransaction> PoolEventBroadcast<T> {
     }
 
     /// Notify listeners about a transaction that was added to the pending queue.
-    pub(crate) fn pending(&mut self, tx: &TxHash, replaced: Option<Arc<ValidPoolTransaction<T>>>) {
-        self.broadcast_event(tx, TransactionEvent::Pending, FullTransactionEvent::Pending(*tx));
-
-        if let Some(replaced) = replaced {
-            // notify listeners that this transaction was replaced
-            self.replaced(replaced, *tx);
-        }
+    pub(crate) fn pending(&mut self, tx: &TxHash) {
+        self.broadcast_event(
+            tx,
+            TransactionEvent::Pending,
+            FullTransactionEvent::Pending(*tx),
+            None,
+        );
     }
 
     /// Notify listeners about a transaction that was replaced.
-    pub(crate) fn replaced(&mut self, tx: Arc<ValidPoolTransaction<T>>, replaced_by: TxHash) {
+    pub(crate) fn replaced(
+        &mut self,
+        tx: Arc<ValidPoolTransaction<T>>,
+        replaced_by: &ValidPoolTransaction<T>,
+    ) {
+        // the fee bump of the replacement in %
+        let old_fee = tx.max_fee_per_gas();
+        let bump = replaced_by.max_fee_per_gas().saturating_sub(old_fee) * 100 / old_fee.max(1);
+        let reason = format!("replaced by {} at +{bump}%", replaced_by.hash());
+
         let transaction = Arc::clone(&tx);
+        let replaced_by = *replaced_by.hash();
         self.broadcast_event(
             tx.hash(),
             TransactionEvent::Replaced(replaced_by),
             FullTransactionEvent::Replaced { transaction, replaced_by },
+            Some(reason),
         );
     }
 
     /// Notify listeners about a transaction that was added to the queued pool.
     pub(crate) fn queued(&mut self, tx: &TxHash) {
-        self.broadcast_event(tx, TransactionEvent::Queued, FullTransactionEvent::Queued(*tx));
+        self.broadcast_event(tx, TransactionEvent::Queued, FullTransactionEvent::Queued(*tx), None);
     }
 
     /// Notify listeners about a transaction that was propagated.
@@ -164,12 +195,18 @@ impl<T: PoolTransaction> PoolEventBroadcast<T> {
             tx,
             TransactionEvent::Propagated(Arc::clone(&peers)),
             FullTransactionEvent::Propagated(peers),
+            None,
         );
     }
 
     /// Notify listeners about a transaction that was discarded.
-    pub(crate) fn discarded(&mut self, tx: &TxHash) {
-        self.broadcast_event(tx, TransactionEvent::Discarded, FullTransactionEvent::Discarded(*tx));
+    pub(crate) fn discarded(&mut self, tx: &TxHash, reason: DiscardReason) {
+        self.broadcast_event(
+            tx,
+            TransactionEvent::Discarded,
+            FullTransactionEvent::Discarded(*tx),
+            Some(reason.to_string()),
+        );
     }
 
     /// Notify listeners that the transaction was mined
@@ -178,10 +215,69 @@ impl<T: PoolTransaction> PoolEventBroadcast<T> {
             tx,
             TransactionEvent::Mined(block_hash),
             FullTransactionEvent::Mined { tx_hash: *tx, block_hash },
+            None,
         );
     }
 }
 
+/// A ring buffer of the most recent events of all transactions.
+///
+/// The events are indexed by transaction hash, so looking up the events of a transaction doesn't
+/// scan the whole buffer while the lock of the pool's listeners is held.
+#[derive(Debug)]
+struct TransactionHistory {
+    /// The recorded events, oldest first.
+    events: VecDeque<TransactionHistoryEvent>,
+    /// The sequence numbers of the recorded events of each transaction, oldest first.
+    by_hash: HashMap<TxHash, VecDeque<u64>>,
+    /// The sequence number of the oldest recorded event.
+    first_seq: u64,
+    /// The maximum number of events to keep.
+    max_events: usize,
+}
+
+impl TransactionHistory {
+    fn new(max_events: usize) -> Self {
+        Self { events: VecDeque::new(), by_hash: HashMap::default(), first_seq: 0, max_events }
+    }
+
+    /// Records the event, evicting the oldest event if the history is full.
+    fn record(&mut self, hash: TxHash, event: TransactionEvent, reason: Option<String>) {
+        if self.max_events == 0 {
+            return
+        }
+        if self.events.len() >= self.max_events {
+            self.evict_oldest();
+        }
+        let timestamp =
+            SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_millis() as u64;
+        let seq = self.first_seq + self.events.len() as u64;
+        self.events.push_back(TransactionHistoryEvent { hash, timestamp, event, reason });
+        self.by_hash.entry(hash).or_default().push_back(seq);
+    }
+
+    /// Removes the oldest event, which is also the oldest indexed event of its transaction.
+    fn evict_oldest(&mut self) {
+        let Some(evicted) = self.events.pop_front() else { return };
+        if let Entry::Occupied(mut entry) = self.by_hash.entry(evicted.hash) {
+            entry.get_mut().pop_front();
+            if entry.get().is_empty() {
+                entry.remove();
+            }
+        }
+        self.first_seq += 1;
+    }
+
+    /// Returns all recorded events of the transaction, oldest first.
+    fn get(&self, hash: &TxHash) -> Vec<TransactionHistoryEvent> {
+        let Some(seqs) = self.by_hash.get(hash) else { return Vec::new() };
+        seqs.iter()
+            .filter_map(|seq| self.events.get((seq - self.first_seq) as usize))
+            .cloned()
+            .collect()
+    }
+}
+
 /// All Sender half(s) of the event channels for all transactions.
 ///
 /// This mimics [`tokio::sync::broadcast`] but uses separate channels.
@@ -227,3 +323,37 @@ impl PoolEventBroadcaster {
         self.senders.retain(|sender| sender.send(event.clone()).is_ok())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn history_index_follows_evictions() {
+        let mut history = TransactionHistory::new(3);
+        let (a, b) = (TxHash::random(), TxHash::random());
+
+        history.record(a, TransactionEvent::Pending, None);
+        history.record(b, TransactionEvent::Queued, None);
+        history.record(a, TransactionEvent::Discarded, Some("nonce too low".to_string()));
+        assert_eq!(history.get(&a).len(), 2);
+
+        // the oldest event of `a` is evicted, then the only event of `b`
+        history.record(b, TransactionEvent::Pending, None);
+        history.record(b, TransactionEvent::Mined(B256::ZERO), None);
+        let events = history.get(&a);
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].event, TransactionEvent::Discarded);
+        assert_eq!(events[0].reason.as_deref(), Some("nonce too low"));
+        assert_eq!(
+            history.get(&b).into_iter().map(|event| event.event).collect::<Vec<_>>(),
+            vec![TransactionEvent::Pending, TransactionEvent::Mined(B256::ZERO)]
+        );
+        assert!(history.get(&TxHash::random()).is_empty());
+
+        // the index only keeps the hashes of recorded events
+        history.record(b, TransactionEvent::Pending, None);
+        assert!(history.get(&a).is_empty());
+        assert_eq!(history.by_hash.len(), 1);
+    }
+}
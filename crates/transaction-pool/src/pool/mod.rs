@@ -81,7 +81,7 @@ use crate::{
     CanonicalStateUpdate, EthPoolTransaction, PoolConfig, TransactionOrdering,
     TransactionValidator,
 };
-use alloy_primitives::{Address, TxHash, B256};
+use alloy_primitives::{Address, BlockNumber, TxHash, B256};
 use best::BestTransactions;
 use parking_lot::{Mutex, RwLock, RwLockReadGuard, RwLockWriteGuard};
 use reth_eth_wire_types::HandleMempoolData;
@@ -105,7 +105,7 @@ pub use best::{
     BestPayloadTransactions, BestTransactionFilter, BestTransactionsWithPrioritizedSenders,
 };
 pub use blob::{blob_tx_priority, fee_delta};
-pub use events::{FullTransactionEvent, TransactionEvent};
+pub use events::{DiscardReason, FullTransactionEvent, TransactionEvent, TransactionHistoryEvent};
 pub use listener::{AllTransactionsEvents, TransactionEvents};
 pub use parked::{BasefeeOrd, ParkedOrd, ParkedPool, QueuedOrd};
 pub use pending::PendingPool;
@@ -128,6 +128,13 @@ pub const NEW_TX_LISTENER_BUFFER_SIZE: usize = 1024;
 
 const BLOB_SIDECAR_LISTENER_BUFFER_SIZE: usize = 512;
 
+/// How many blocks mined private transactions are remembered for, to re-add them as private if
+/// their block is reorged out.
+///
+/// This matches the default max update depth of the pool maintenance, deeper reorgs are not
+/// re-injected.
+const MAX_PRIVATE_TRANSACTION_REORG_DEPTH: u64 = 64;
+
 /// Transaction pool internals.
 pub struct PoolInner<V, T, S>
 where
@@ -153,6 +160,11 @@ where
     blob_transaction_sidecar_listener: Mutex<Vec<BlobTransactionSidecarListener>>,
     /// Metrics for the blob store
     blob_store_metrics: BlobStoreMetrics,
+    /// The highest block numbers in which the private transactions should be included.
+    private_transaction_deadlines: RwLock<FxHashMap<TxHash, BlockNumber>>,
+    /// The private transactions that were mined recently, so they can be re-added as private if
+    /// their block is reorged out.
+    mined_private_transactions: RwLock<FxHashMap<TxHash, MinedPrivateTransaction>>,
 }
 
 // === impl PoolInner ===
@@ -168,7 +180,7 @@ where
         Self {
             identifiers: Default::default(),
             validator,
-            event_listener: Default::default(),
+            event_listener: RwLock::new(PoolEventBroadcast::new(config.max_history_events)),
             pool: RwLock::new(TxPool::new(ordering, config.clone())),
             pending_transaction_listener: Default::default(),
             transaction_listener: Default::default(),
@@ -176,6 +188,8 @@ where
             config,
             blob_store,
             blob_store_metrics: Default::default(),
+            private_transaction_deadlines: Default::default(),
+            mined_private_transactions: Default::default(),
         }
     }
 
@@ -198,6 +212,15 @@ where
         self.pool.write().set_block_info(info)
     }
 
+    /// Recomputes the priorities of the pending transactions of the given senders.
+    pub fn reprioritize_pending_transactions(&self, senders: Vec<Address>) {
+        let senders = {
+            let identifiers = self.identifiers.read();
+            senders.iter().filter_map(|sender| identifiers.sender_id(sender)).collect::<Vec<_>>()
+        };
+        self.pool.write().reprioritize_pending(senders)
+    }
+
     /// Returns the internal [`SenderId`] for this address
     pub fn get_sender_id(&self, addr: Address) -> SenderId {
         self.identifiers.write().sender_id_or_create(addr)
@@ -350,7 +373,9 @@ where
         let transactions = self.get_all(tx_hashes);
         let mut elements = Vec::with_capacity(transactions.len());
         let mut size = 0;
-        for transaction in transactions {
+        // transactions that are not allowed to be propagated, e.g. private transactions, must not
+        // be revealed to peers that request them by hash
+        for transaction in transactions.into_iter().filter(|tx| tx.propagate) {
             let encoded_len = transaction.encoded_length();
             let Some(pooled) = self.to_pooled_transaction(transaction) else {
                 continue;
@@ -395,12 +420,20 @@ where
         let changed_senders = self.changed_senders(changed_accounts.into_iter());
 
         // update the pool
-        let outcome = self.pool.write().on_canonical_state_change(
-            block_info,
-            mined_transactions,
-            changed_senders,
-            update_kind,
-        );
+        let outcome = {
+            let mut pool = self.pool.write();
+            self.track_mined_private_transactions(
+                &pool,
+                block_info.last_seen_block_number,
+                &mined_transactions,
+            );
+            pool.on_canonical_state_change(
+                block_info,
+                mined_transactions,
+                changed_senders,
+                update_kind,
+            )
+        };
 
         // This will discard outdated transactions based on the account's nonce
         self.delete_discarded_blobs(outcome.discarded.iter());
@@ -409,6 +442,29 @@ where
         self.notify_on_new_state(outcome);
     }
 
+    /// Remembers the private transactions of the pool that were mined in the given block, with
+    /// their deadline, so they can be re-added as private after a reorg.
+    fn track_mined_private_transactions(
+        &self,
+        pool: &TxPool<T>,
+        block_number: BlockNumber,
+        mined_transactions: &[TxHash],
+    ) {
+        let mut deadlines = self.private_transaction_deadlines.write();
+        let mut mined_private = self.mined_private_transactions.write();
+        for tx_hash in mined_transactions {
+            if pool.get(tx_hash).is_some_and(|tx| tx.origin.is_private()) {
+                mined_private.insert(
+                    *tx_hash,
+                    MinedPrivateTransaction {
+                        block_number,
+                        max_block_number: deadlines.remove(tx_hash),
+                    },
+                );
+            }
+        }
+    }
+
     /// Performs account updates on the pool.
     ///
     /// This will either promote or discard transactions based on the new account state.
@@ -418,8 +474,8 @@ where
             self.pool.write().update_accounts(changed_senders);
         let mut listener = self.event_listener.write();
 
-        promoted.iter().for_each(|tx| listener.pending(tx.hash(), None));
-        discarded.iter().for_each(|tx| listener.discarded(tx.hash()));
+        promoted.iter().for_each(|tx| listener.pending(tx.hash()));
+        discarded.iter().for_each(|tx| listener.discarded(tx.hash(), DiscardReason::NonceTooLow));
 
         // This deletes outdated blob txs from the blob store, based on the account's nonce. This is
         // called during txpool maintenance when the pool drifted.
@@ -502,12 +558,12 @@ where
             }
             TransactionValidationOutcome::Invalid(tx, err) => {
                 let mut listener = self.event_listener.write();
-                listener.discarded(tx.hash());
+                listener.discarded(tx.hash(), DiscardReason::Invalid(err.to_string()));
                 Err(PoolError::new(*tx.hash(), err))
             }
             TransactionValidationOutcome::Error(tx_hash, err) => {
                 let mut listener = self.event_listener.write();
-                listener.discarded(&tx_hash);
+                listener.discarded(&tx_hash, DiscardReason::Invalid(err.to_string()));
                 Err(PoolError::other(tx_hash, err))
             }
         }
@@ -558,15 +614,18 @@ where
 
         if !discarded.is_empty() {
             // Delete any blobs associated with discarded blob transactions
-            self.delete_discarded_blobs(discarded.iter());
-
-            let discarded_hashes =
-                discarded.into_iter().map(|tx| *tx.hash()).collect::<HashSet<_>>();
+            self.delete_discarded_blobs(discarded.iter().map(|(tx, _)| tx));
 
-            {
+            let discarded_hashes = {
                 let mut listener = self.event_listener.write();
-                discarded_hashes.iter().for_each(|hash| listener.discarded(hash));
-            }
+                discarded
+                    .into_iter()
+                    .map(|(tx, reason)| {
+                        listener.discarded(tx.hash(), reason);
+                        *tx.hash()
+                    })
+                    .collect::<HashSet<_>>()
+            };
 
             // A newly added transaction may be immediately discarded, so we need to
             // adjust the result here
@@ -661,8 +720,8 @@ where
         let mut listener = self.event_listener.write();
 
         mined.iter().for_each(|tx| listener.mined(tx, block_hash));
-        promoted.iter().for_each(|tx| listener.pending(tx.hash(), None));
-        discarded.iter().for_each(|tx| listener.discarded(tx.hash()));
+        promoted.iter().for_each(|tx| listener.pending(tx.hash()));
+        discarded.iter().for_each(|tx| listener.discarded(tx.hash(), DiscardReason::NonceTooLow));
     }
 
     /// Fire events for the newly added transaction if there are any.
@@ -673,14 +732,20 @@ where
             AddedTransaction::Pending(tx) => {
                 let AddedPendingTransaction { transaction, promoted, discarded, replaced } = tx;
 
-                listener.pending(transaction.hash(), replaced.clone());
-                promoted.iter().for_each(|tx| listener.pending(tx.hash(), None));
-                discarded.iter().for_each(|tx| listener.discarded(tx.hash()));
+                listener.pending(transaction.hash());
+                if let Some(replaced) = replaced {
+                    // notify listeners that this transaction was replaced
+                    listener.replaced(replaced.clone(), transaction);
+                }
+                promoted.iter().for_each(|tx| listener.pending(tx.hash()));
+                discarded
+                    .iter()
+                    .for_each(|tx| listener.discarded(tx.hash(), DiscardReason::NonceTooLow));
             }
             AddedTransaction::Parked { transaction, replaced, .. } => {
                 listener.queued(transaction.hash());
                 if let Some(replaced) = replaced {
-                    listener.replaced(replaced.clone(), *transaction.hash());
+                    listener.replaced(replaced.clone(), transaction);
                 }
             }
         }
@@ -728,6 +793,77 @@ where
         }
     }
 
+    /// Sets the highest block number in which the given private transaction should be included.
+    pub fn set_private_transaction_deadline(&self, tx_hash: TxHash, max_block_number: BlockNumber) {
+        self.private_transaction_deadlines.write().insert(tx_hash, max_block_number);
+    }
+
+    /// Returns the highest block number in which the given private transaction should be included.
+    pub fn private_transaction_deadline(&self, tx_hash: &TxHash) -> Option<BlockNumber> {
+        self.private_transaction_deadlines.read().get(tx_hash).copied()
+    }
+
+    /// Removes the private transactions that were not included up to their deadline, given the
+    /// number of the new canonical block.
+    ///
+    /// Returns the removed transactions that should be made public, see
+    /// [`LocalTransactionConfig::propagate_expired_private_transactions`](crate::LocalTransactionConfig::propagate_expired_private_transactions).
+    pub fn remove_expired_private_transactions(
+        &self,
+        block_number: BlockNumber,
+    ) -> Vec<Arc<ValidPoolTransaction<T::Transaction>>> {
+        let expired = {
+            let pool = self.pool.read();
+            let mut expired = Vec::new();
+            self.private_transaction_deadlines.write().retain(|tx_hash, max_block_number| {
+                // the transaction was already mined or removed from the pool
+                if !pool.contains(tx_hash) {
+                    return false
+                }
+                if *max_block_number <= block_number {
+                    expired.push(*tx_hash);
+                    return false
+                }
+                true
+            });
+            expired
+        };
+        // a block deeper than the max reorg depth is not reorged out, and a reorged transaction
+        // past its deadline would be expired right away
+        self.mined_private_transactions.write().retain(|_, tx| {
+            tx.block_number + MAX_PRIVATE_TRANSACTION_REORG_DEPTH > block_number &&
+                tx.max_block_number
+                    .is_none_or(|max_block_number| max_block_number > block_number)
+        });
+        if expired.is_empty() {
+            return Vec::new()
+        }
+
+        let removed = self.remove_transactions(expired);
+        debug!(target: "txpool", count = removed.len(), block_number, "Removed expired private transactions");
+
+        if !self.config.local_transactions_config.propagate_expired_private_transactions {
+            return Vec::new()
+        }
+        // the sidecars of blob transactions are not kept in the pool, so they can't be re-added
+        removed.into_iter().filter(|tx| !tx.is_eip4844()).collect()
+    }
+
+    /// Removes the given transactions from the mined private transactions and returns those that
+    /// were tracked, with their deadline if they had one.
+    ///
+    /// This is used to re-add the private transactions of reorged blocks as private.
+    pub fn remove_mined_private_transactions(
+        &self,
+        hashes: Vec<TxHash>,
+    ) -> Vec<(TxHash, Option<BlockNumber>)> {
+        let mut mined_private = self.mined_private_transactions.write();
+        hashes
+            .into_iter()
+            .filter_map(|hash| mined_private.remove(&hash).map(|tx| (hash, tx.max_block_number)))
+            .collect()
+    }
+
     /// Removes and returns all matching transactions from the pool.
     pub fn remove_transactions(
         &self,
@@ -740,7 +876,7 @@ where
 
         let mut listener = self.event_listener.write();
 
-        removed.iter().for_each(|tx| listener.discarded(tx.hash()));
+        removed.iter().for_each(|tx| listener.discarded(tx.hash(), DiscardReason::Removed));
 
         removed
     }
@@ -758,7 +894,7 @@ where
 
         let mut listener = self.event_listener.write();
 
-        removed.iter().for_each(|tx| listener.discarded(tx.hash()));
+        removed.iter().for_each(|tx| listener.discarded(tx.hash(), DiscardReason::Removed));
 
         removed
     }
@@ -773,7 +909,7 @@ where
 
         let mut listener = self.event_listener.write();
 
-        removed.iter().for_each(|tx| listener.discarded(tx.hash()));
+        removed.iter().for_each(|tx| listener.discarded(tx.hash(), DiscardReason::Removed));
 
         removed
     }
@@ -900,6 +1036,11 @@ where
         txs.0.into_iter().for_each(|(hash, peers)| listener.propagated(&hash, peers))
     }
 
+    /// Returns the recent events of the transaction, oldest first.
+    pub fn transaction_history(&self, tx_hash: &TxHash) -> Vec<TransactionHistoryEvent> {
+        self.event_listener.read().history(tx_hash)
+    }
+
     /// Number of transactions in the entire pool
     pub fn len(&self) -> usize {
         self.get_pool_data().len()
@@ -1050,6 +1191,15 @@ struct BlobTransactionSidecarListener {
     sender: mpsc::Sender<NewBlobSidecar>,
 }
 
+/// A private transaction that was mined in a recent block.
+#[derive(Debug, Clone, Copy)]
+struct MinedPrivateTransaction {
+    /// The number of the block the transaction was mined in.
+    block_number: BlockNumber,
+    /// The highest block number in which the transaction should have been included, if any.
+    max_block_number: Option<BlockNumber>,
+}
+
 /// Tracks an added transaction and all graph changes caused by adding it.
 #[derive(Debug, Clone)]
 pub struct AddedPendingTransaction<T: PoolTransaction> {
@@ -1266,7 +1416,8 @@ mod tests {
         blobstore::{BlobStore, InMemoryBlobStore},
         test_utils::{MockTransaction, TestPoolBuilder},
         validate::ValidTransaction,
-        BlockInfo, PoolConfig, SubPoolLimit, TransactionOrigin, TransactionValidationOutcome, U256,
+        BlockInfo, GetPooledTransactionLimit, LocalTransactionConfig, PoolConfig, SubPoolLimit,
+        TransactionEvent, TransactionOrigin, TransactionValidationOutcome, U256,
     };
     use alloy_eips::eip4844::BlobTransactionSidecar;
     use reth_primitives::kzg::Blob;
@@ -1346,4 +1497,137 @@ mod tests {
         // Assert that the pool's blob store matches the expected blob store.
         assert_eq!(*test_pool.blob_store(), blob_store);
     }
+
+    fn private_transaction_outcome(
+        transaction: MockTransaction,
+    ) -> TransactionValidationOutcome<MockTransaction> {
+        TransactionValidationOutcome::Valid {
+            balance: U256::from(1_000),
+            state_nonce: 0,
+            transaction: ValidTransaction::Valid(transaction),
+            propagate: false,
+        }
+    }
+
+    #[test]
+    fn test_remove_expired_private_transactions() {
+        let test_pool = &TestPoolBuilder::default().pool;
+
+        let expiring = MockTransaction::eip1559();
+        let pending = MockTransaction::eip1559();
+        let mined = MockTransaction::eip1559();
+        for tx in [&expiring, &pending, &mined] {
+            test_pool.add_transactions(
+                TransactionOrigin::Private,
+                [private_transaction_outcome(tx.clone())],
+            );
+        }
+        test_pool.set_private_transaction_deadline(*expiring.get_hash(), 10);
+        test_pool.set_private_transaction_deadline(*pending.get_hash(), 11);
+        test_pool.set_private_transaction_deadline(*mined.get_hash(), 10);
+        test_pool.remove_transactions(vec![*mined.get_hash()]);
+
+        // expired private transactions are dropped by default
+        assert!(test_pool.remove_expired_private_transactions(9).is_empty());
+        assert!(test_pool.remove_expired_private_transactions(10).is_empty());
+        assert!(test_pool.get(expiring.get_hash()).is_none());
+        assert!(test_pool.get(pending.get_hash()).is_some());
+        assert_eq!(test_pool.private_transaction_deadlines.read().len(), 1);
+    }
+
+    #[test]
+    fn test_propagate_expired_private_transactions() {
+        let test_pool = &TestPoolBuilder::default()
+            .with_config(PoolConfig {
+                local_transactions_config: LocalTransactionConfig::default()
+                    .set_propagate_expired_private_transactions(true),
+                ..Default::default()
+            })
+            .pool;
+
+        let tx = MockTransaction::eip1559();
+        test_pool.add_transactions(
+            TransactionOrigin::Private,
+            [private_transaction_outcome(tx.clone())],
+        );
+        test_pool.set_private_transaction_deadline(*tx.get_hash(), 10);
+
+        let public = test_pool.remove_expired_private_transactions(10);
+        assert_eq!(public.len(), 1);
+        assert_eq!(public[0].hash(), tx.get_hash());
+        assert!(test_pool.get(tx.get_hash()).is_none());
+    }
+
+    #[test]
+    fn test_private_transactions_are_not_pooled_elements() {
+        let test_pool = &TestPoolBuilder::default().pool;
+
+        let private = MockTransaction::eip1559();
+        let public = MockTransaction::eip1559();
+        test_pool.add_transactions(
+            TransactionOrigin::Private,
+            [private_transaction_outcome(private.clone())],
+        );
+        test_pool.add_transactions(
+            TransactionOrigin::External,
+            [TransactionValidationOutcome::Valid {
+                balance: U256::from(1_000),
+                state_nonce: 0,
+                transaction: ValidTransaction::Valid(public.clone()),
+                propagate: true,
+            }],
+        );
+
+        let elements = test_pool.get_pooled_transaction_elements(
+            vec![*private.get_hash(), *public.get_hash()],
+            GetPooledTransactionLimit::None,
+        );
+        assert_eq!(elements.len(), 1);
+        assert_eq!(elements[0].hash(), public.get_hash());
+    }
+
+    #[test]
+    fn test_transaction_history() {
+        let pending_limit = SubPoolLimit::new(2, usize::MAX);
+        let test_pool = &TestPoolBuilder::default()
+            .with_config(PoolConfig { pending_limit, ..Default::default() })
+            .pool;
+
+        let replaced = MockTransaction::eip1559().with_gas_price(100);
+        let replacement = replaced.clone().rng_hash().with_gas_price(112);
+        let other = MockTransaction::eip1559().with_gas_price(200);
+        let evicted = MockTransaction::eip1559().with_gas_price(50);
+        for tx in [&replaced, &replacement, &other, &evicted] {
+            test_pool.add_transactions(
+                TransactionOrigin::External,
+                [TransactionValidationOutcome::Valid {
+                    balance: U256::from(1_000_000),
+                    state_nonce: 0,
+                    transaction: ValidTransaction::Valid(tx.clone()),
+                    propagate: true,
+                }],
+            );
+        }
+
+        let history = test_pool.transaction_history(replaced.get_hash());
+        assert_eq!(
+            history.iter().map(|event| event.event.clone()).collect::<Vec<_>>(),
+            vec![TransactionEvent::Pending, TransactionEvent::Replaced(*replacement.get_hash())]
+        );
+        assert_eq!(
+            history[1].reason,
+            Some(format!("replaced by {} at +12%", replacement.get_hash()))
+        );
+
+        let history = test_pool.transaction_history(evicted.get_hash());
+        assert_eq!(
+            history.iter().map(|event| event.event.clone()).collect::<Vec<_>>(),
+            vec![TransactionEvent::Pending, TransactionEvent::Discarded]
+        );
+        assert_eq!(history[1].reason.as_deref(), Some("evicted from Pending subpool: count limit"));
+
+        let history = test_pool.transaction_history(other.get_hash());
+        assert_eq!(history.len(), 1);
+        assert_eq!(history[0].reason, None);
+    }
 }
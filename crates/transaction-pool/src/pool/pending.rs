@@ -243,6 +243,37 @@ impl<T: TransactionOrdering> PendingPool<T> {
         removed
     }
 
+    /// Recomputes the priorities of the transactions of the given senders, e.g. because the inputs
+    /// of the [`TransactionOrdering`] changed for them.
+    ///
+    /// Unlike [`Self::update_base_fee`], this leaves the transactions of all other senders
+    /// untouched.
+    pub(crate) fn reprioritize_senders(
+        &mut self,
+        senders: impl IntoIterator<Item = SenderId>,
+        base_fee: u64,
+    ) {
+        for sender in senders {
+            for (_, tx) in self
+                .by_id
+                .range_mut((sender.start_bound(), Unbounded))
+                .take_while(|(id, _)| id.sender == sender)
+            {
+                tx.priority = self.ordering.priority(&tx.transaction.transaction, base_fee);
+            }
+
+            for tx in [
+                self.independent_transactions.get_mut(&sender),
+                self.highest_nonces.get_mut(&sender),
+            ]
+            .into_iter()
+            .flatten()
+            {
+                tx.priority = self.ordering.priority(&tx.transaction.transaction, base_fee);
+            }
+        }
+    }
+
     /// Updates the independent transaction and highest nonces set, assuming the given transaction
     /// is being _added_ to the pool.
     fn update_independents_and_highest_nonces(&mut self, tx: &PendingTransaction<T>) {
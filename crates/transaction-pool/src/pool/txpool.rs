@@ -8,6 +8,7 @@ use crate::{
     pool::{
         best::BestTransactions,
         blob::BlobTransactions,
+        events::DiscardReason,
         parked::{BasefeeOrd, ParkedPool, QueuedOrd},
         pending::PendingPool,
         state::{SubPool, TxState},
@@ -257,6 +258,13 @@ impl<T: TransactionOrdering> TxPool<T> {
         }
     }
 
+    /// Recomputes the priorities of the pending transactions of the given senders, e.g. because
+    /// the inputs of the [`TransactionOrdering`] changed for them.
+    pub(crate) fn reprioritize_pending(&mut self, senders: impl IntoIterator<Item = SenderId>) {
+        self.pending_pool
+            .reprioritize_senders(senders, self.all_transactions.pending_fees.base_fee);
+    }
+
     /// Updates the tracked basefee
     ///
     /// Depending on the change in direction of the basefee, this will promote or demote
@@ -954,13 +962,16 @@ impl<T: TransactionOrdering> TxPool<T> {
     /// If the current size exceeds the given bounds, the worst transactions are evicted from the
     /// pool and returned.
     ///
-    /// This returns all transactions that were removed from the entire pool.
-    pub(crate) fn discard_worst(&mut self) -> Vec<Arc<ValidPoolTransaction<T::Transaction>>> {
+    /// This returns all transactions that were removed from the entire pool, with the reason why
+    /// they were removed.
+    pub(crate) fn discard_worst(
+        &mut self,
+    ) -> Vec<(Arc<ValidPoolTransaction<T::Transaction>>, DiscardReason)> {
         let mut removed = Vec::new();
 
         // Helper macro that discards the worst transactions for the pools
         macro_rules! discard_worst {
-            ($this:ident, $removed:ident, [$($limit:ident => $pool:ident),* $(,)*]) => {
+            ($this:ident, $removed:ident, [$($limit:ident => $pool:ident: $subpool:expr),* $(,)*]) => {
                 $ (
                 while $this.$pool.exceeds(&$this.config.$limit)
                     {
@@ -973,6 +984,12 @@ impl<T: TransactionOrdering> TxPool<T> {
                             $this.$pool.len(),
                         );
 
+                        let reason = if $this.$pool.len() > $this.config.$limit.max_txs {
+                            DiscardReason::CountLimit($subpool)
+                        } else {
+                            DiscardReason::SizeLimit($subpool)
+                        };
+
                         // 1. first remove the worst transaction from the subpool
                         let removed_from_subpool = $this.$pool.truncate_pool($this.config.$limit.clone());
 
@@ -991,12 +1008,17 @@ impl<T: TransactionOrdering> TxPool<T> {
                             $this.all_transactions.remove_transaction(tx.id());
 
                             let id = *tx.id();
+                            let hash = *tx.hash();
 
                             // keep track of removed transaction
-                            removed.push(tx);
+                            $removed.push((tx, reason.clone()));
 
                             // 3. remove all its descendants from the entire pool
-                            $this.remove_descendants(&id, &mut $removed);
+                            let mut descendants = Vec::new();
+                            $this.remove_descendants(&id, &mut descendants);
+                            $removed.extend(descendants.into_iter().map(|tx| {
+                                (tx, DiscardReason::EvictedAncestor(hash))
+                            }));
                         }
                     }
 
@@ -1006,10 +1028,10 @@ impl<T: TransactionOrdering> TxPool<T> {
 
         discard_worst!(
             self, removed, [
-                pending_limit => pending_pool,
-                basefee_limit => basefee_pool,
-                blob_limit    => blob_pool,
-                queued_limit  => queued_pool,
+                pending_limit => pending_pool: SubPool::Pending,
+                basefee_limit => basefee_pool: SubPool::BaseFee,
+                blob_limit    => blob_pool: SubPool::Blob,
+                queued_limit  => queued_pool: SubPool::Queued,
             ]
         );
 
@@ -1976,7 +1998,7 @@ mod tests {
     use crate::{
         test_utils::{MockOrdering, MockTransaction, MockTransactionFactory, MockTransactionSet},
         traits::TransactionOrigin,
-        SubPoolLimit,
+        SimulatedPaymentOrdering, SubPoolLimit,
     };
     use alloy_primitives::address;
     use reth_primitives::TxType;
@@ -3364,4 +3386,31 @@ mod tests {
 
         assert_eq!(pool.pending_pool.independent().len(), 1);
     }
+
+    #[test]
+    fn reprioritize_simulated_payments() {
+        let on_chain_balance = U256::from(1_000_000_000_000u64);
+        let mut f = MockTransactionFactory::default();
+        let ordering = SimulatedPaymentOrdering::default();
+        let payments = ordering.payments().clone();
+        let mut pool = TxPool::new(ordering, Default::default());
+
+        let low = f.validated(MockTransaction::eip1559().with_priority_fee(1).with_max_fee(100));
+        let high = f.validated(MockTransaction::eip1559().with_priority_fee(2).with_max_fee(100));
+        pool.add_transaction(low.clone(), on_chain_balance, 0).unwrap();
+        pool.add_transaction(high.clone(), on_chain_balance, 0).unwrap();
+        assert_eq!(pool.best_transactions().next().unwrap().hash(), high.hash());
+
+        // The simulated payment only takes effect once the sender is reprioritized
+        payments.insert(*low.hash(), U256::from(10));
+        assert_eq!(pool.best_transactions().next().unwrap().hash(), high.hash());
+
+        pool.reprioritize_pending([high.sender_id()]);
+        assert_eq!(pool.best_transactions().next().unwrap().hash(), high.hash());
+
+        pool.reprioritize_pending([low.sender_id()]);
+        assert_eq!(pool.best_transactions().next().unwrap().hash(), low.hash());
+        assert_eq!(pool.pending_transactions().len(), 2);
+        pool.assert_invariants();
+    }
 }
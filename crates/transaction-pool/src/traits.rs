@@ -1,7 +1,7 @@
 use crate::{
     blobstore::BlobStoreError,
     error::{InvalidPoolTransactionError, PoolResult},
-    pool::{state::SubPool, BestTransactionFilter, TransactionEvents},
+    pool::{state::SubPool, BestTransactionFilter, TransactionEvents, TransactionHistoryEvent},
     validate::ValidPoolTransaction,
     AllTransactionsEvents,
 };
@@ -14,7 +14,7 @@ use alloy_eips::{
     eip2930::AccessList,
     eip4844::{BlobAndProofV1, BlobTransactionSidecar, BlobTransactionValidationError},
 };
-use alloy_primitives::{Address, TxHash, TxKind, B256, U256};
+use alloy_primitives::{Address, BlockNumber, TxHash, TxKind, B256, U256};
 use futures_util::{ready, Stream};
 use reth_eth_wire_types::HandleMempoolData;
 use reth_execution_types::ChangedAccount;
@@ -122,6 +122,19 @@ pub trait TransactionPool: Send + Sync + Clone {
         transactions: Vec<Self::Transaction>,
     ) -> impl Future<Output = Vec<PoolResult<TxHash>>> + Send;
 
+    /// Adds an _unvalidated_ transaction into the pool as [`TransactionOrigin::Private`].
+    ///
+    /// The transaction is never propagated to the network, but it's still offered to the payload
+    /// builder. If a max block number is given and the transaction is not included up to it, it's
+    /// removed from the pool, see [`TransactionPoolExt::remove_expired_private_transactions`].
+    ///
+    /// Consumer: RPC
+    fn add_private_transaction(
+        &self,
+        transaction: Self::Transaction,
+        max_block_number: Option<BlockNumber>,
+    ) -> impl Future<Output = PoolResult<TxHash>> + Send;
+
     /// Returns a new transaction change event stream for the given transaction.
     ///
     /// Returns `None` if the transaction is not in the pool.
@@ -335,6 +348,31 @@ pub trait TransactionPool: Send + Sync + Clone {
         sender: Address,
     ) -> Vec<Arc<ValidPoolTransaction<Self::Transaction>>>;
 
+    /// Removes all private transactions whose max block number is lower than or equal to the
+    /// given block number, see [`TransactionPool::add_private_transaction`].
+    ///
+    /// Returns the removed transactions that should be made public, if enabled by
+    /// [`LocalTransactionConfig::propagate_expired_private_transactions`](crate::LocalTransactionConfig::propagate_expired_private_transactions).
+    ///
+    /// Consumer: Utility
+    fn remove_expired_private_transactions(
+        &self,
+        block_number: BlockNumber,
+    ) -> Vec<Arc<ValidPoolTransaction<Self::Transaction>>>;
+
+    /// Returns the given transactions that were mined in a recent block as private transactions,
+    /// with the max block number they were added with, if any, see
+    /// [`TransactionPool::add_private_transaction`].
+    ///
+    /// The returned transactions are no longer tracked. This is used to re-add the transactions
+    /// of reorged blocks as private.
+    ///
+    /// Consumer: Utility
+    fn remove_mined_private_transactions(
+        &self,
+        tx_hashes: Vec<TxHash>,
+    ) -> Vec<(TxHash, Option<BlockNumber>)>;
+
     /// Retains only those hashes that are unknown to the pool.
     /// In other words, removes all transactions from the given set that are currently present in
     /// the pool. Returns hashes already known to the pool.
@@ -362,6 +400,15 @@ pub trait TransactionPool: Send + Sync + Clone {
     /// Consumer: P2P
     fn on_propagated(&self, txs: PropagatedTransactions);
 
+    /// Returns the recent events of the transaction with the given hash, oldest first, including
+    /// the reason why it was discarded or replaced.
+    ///
+    /// Only the most recent events of all transactions are kept, see
+    /// [`PoolConfig::max_history_events`](crate::PoolConfig::max_history_events).
+    ///
+    /// Consumer: RPC
+    fn transaction_history(&self, tx_hash: TxHash) -> Vec<TransactionHistoryEvent>;
+
     /// Returns all transactions sent by a given user
     fn get_transactions_by_sender(
         &self,
@@ -436,6 +483,10 @@ pub trait TransactionPool: Send + Sync + Clone {
         self.get_transactions_by_origin(TransactionOrigin::Private)
     }
 
+    /// Returns the max block number of the given private transaction, if it was added with one,
+    /// see [`TransactionPool::add_private_transaction`].
+    fn private_transaction_deadline(&self, tx_hash: &TxHash) -> Option<BlockNumber>;
+
     /// Returns all transactions that where submitted as [TransactionOrigin::External]
     fn get_external_transactions(&self) -> Vec<Arc<ValidPoolTransaction<Self::Transaction>>> {
         self.get_transactions_by_origin(TransactionOrigin::External)
@@ -525,6 +576,14 @@ pub trait TransactionPoolExt: TransactionPool {
     /// Updates the accounts in the pool
     fn update_accounts(&self, accounts: Vec<ChangedAccount>);
 
+    /// Recomputes the priorities of the pending transactions of the given senders.
+    ///
+    /// This must be called whenever the inputs of the
+    /// [`TransactionOrdering`](crate::TransactionOrdering) change for the transactions of these
+    /// senders, e.g. when their simulated payments for the
+    /// [`SimulatedPaymentOrdering`](crate::SimulatedPaymentOrdering) changed.
+    fn reprioritize_pending_transactions(&self, senders: Vec<Address>);
+
     /// Deletes the blob sidecar for the given transaction from the blob store
     fn delete_blob(&self, tx: B256);
 
@@ -0,0 +1,528 @@
+//! Configurable admission policies of the transaction pool.
+//!
+//! An [`AdmissionPolicy`] is checked by the
+//! [`EthTransactionValidator`](super::EthTransactionValidator) after all other checks of a
+//! transaction, and can reject transactions based on the local preferences of the node operator,
+//! for example a deny list of sanctioned addresses. Multiple policies can be chained with
+//! [`AdmissionPolicies`].
+//!
+//! Transactions that the pool already held before, which are reinjected after a reorg or restored
+//! from the journal, can be marked as [`Readmissions`].
+
+use crate::{error::InvalidPoolTransactionError, PoolTransaction, TransactionOrigin};
+use alloy_primitives::{Address, Selector, TxHash};
+use parking_lot::Mutex;
+use std::{
+    collections::{HashMap, HashSet},
+    fmt,
+    path::Path,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+/// The number of tracked senders above which the expired rate limit windows are pruned.
+const RATE_LIMIT_PRUNE_THRESHOLD: usize = 10_000;
+
+/// The properties of a transaction that are checked by an [`AdmissionPolicy`].
+#[derive(Debug, Clone, Copy)]
+pub struct AdmissionRequest<'a> {
+    /// The hash of the transaction.
+    pub hash: TxHash,
+    /// The origin of the transaction.
+    pub origin: TransactionOrigin,
+    /// Whether the transaction is considered local, see
+    /// [`LocalTransactionConfig::is_local`](crate::LocalTransactionConfig::is_local).
+    pub is_local: bool,
+    /// The sender of the transaction.
+    pub sender: Address,
+    /// The recipient of the transaction, `None` for contract creations.
+    pub to: Option<Address>,
+    /// The input data of the transaction.
+    pub input: &'a [u8],
+    /// The max priority fee per gas of the transaction, or the gas price for legacy transactions.
+    pub priority_fee: u128,
+    /// Whether the transaction is an EIP-4844 blob transaction.
+    pub is_eip4844: bool,
+    /// Whether the transaction is readmitted to the pool, see [`Readmissions`].
+    pub is_readmission: bool,
+}
+
+impl<'a> AdmissionRequest<'a> {
+    /// Creates the request for the given transaction.
+    pub fn new<T: PoolTransaction>(
+        transaction: &'a T,
+        origin: TransactionOrigin,
+        is_local: bool,
+    ) -> Self {
+        Self {
+            hash: *transaction.hash(),
+            origin,
+            is_local,
+            sender: transaction.sender(),
+            to: transaction.to(),
+            input: transaction.input(),
+            priority_fee: transaction.priority_fee_or_price(),
+            is_eip4844: transaction.is_eip4844(),
+            is_readmission: false,
+        }
+    }
+
+    /// Sets whether the transaction is readmitted to the pool.
+    pub const fn with_readmission(mut self, is_readmission: bool) -> Self {
+        self.is_readmission = is_readmission;
+        self
+    }
+}
+
+/// The hashes of transactions that are about to be readmitted to the pool, because they were
+/// reinjected after a reorg or restored from the journal.
+///
+/// The handle is shared between the [`AdmissionPolicies`] of the validator and the tasks that
+/// readmit the transactions. A hash is removed once the transaction was validated.
+#[derive(Debug, Clone, Default)]
+pub struct Readmissions {
+    hashes: Arc<Mutex<HashSet<TxHash>>>,
+}
+
+impl Readmissions {
+    /// Marks the transactions with the given hashes as readmitted.
+    pub fn extend(&self, hashes: impl IntoIterator<Item = TxHash>) {
+        self.hashes.lock().extend(hashes);
+    }
+
+    /// Returns `true` if the transaction with the given hash was marked as readmitted, and
+    /// removes the mark.
+    pub fn take(&self, hash: &TxHash) -> bool {
+        let mut hashes = self.hashes.lock();
+        !hashes.is_empty() && hashes.remove(hash)
+    }
+}
+
+/// A policy that decides whether a transaction is admitted into the pool.
+pub trait AdmissionPolicy: fmt::Debug + Send + Sync {
+    /// Checks the transaction, returning the reason of the rejection if it's not admitted.
+    fn check(&self, request: &AdmissionRequest<'_>) -> Result<(), InvalidPoolTransactionError>;
+}
+
+impl<T: AdmissionPolicy + ?Sized> AdmissionPolicy for Arc<T> {
+    fn check(&self, request: &AdmissionRequest<'_>) -> Result<(), InvalidPoolTransactionError> {
+        (**self).check(request)
+    }
+}
+
+/// A chain of [`AdmissionPolicy`]s.
+///
+/// A transaction is admitted only if it passes all policies, which are checked in the order they
+/// were added. The first rejection is returned.
+#[derive(Debug, Clone, Default)]
+pub struct AdmissionPolicies {
+    policies: Vec<Arc<dyn AdmissionPolicy>>,
+    readmissions: Readmissions,
+}
+
+impl AdmissionPolicies {
+    /// Creates an empty chain that admits all transactions.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the handle of the transactions that are about to be readmitted to the pool.
+    pub fn readmissions(&self) -> Readmissions {
+        self.readmissions.clone()
+    }
+
+    /// Returns `true` if the transaction with the given hash is readmitted, see
+    /// [`Readmissions::take`].
+    pub(crate) fn take_readmission(&self, hash: &TxHash) -> bool {
+        self.readmissions.take(hash)
+    }
+
+    /// Appends the policy to the chain.
+    pub fn with_policy(mut self, policy: impl AdmissionPolicy + 'static) -> Self {
+        self.push(policy);
+        self
+    }
+
+    /// Appends the policy to the chain.
+    pub fn push(&mut self, policy: impl AdmissionPolicy + 'static) {
+        self.policies.push(Arc::new(policy));
+    }
+
+    /// Returns the number of policies in the chain.
+    pub fn len(&self) -> usize {
+        self.policies.len()
+    }
+
+    /// Returns `true` if the chain has no policies.
+    pub fn is_empty(&self) -> bool {
+        self.policies.is_empty()
+    }
+}
+
+impl AdmissionPolicy for AdmissionPolicies {
+    fn check(&self, request: &AdmissionRequest<'_>) -> Result<(), InvalidPoolTransactionError> {
+        self.policies.iter().try_for_each(|policy| policy.check(request))
+    }
+}
+
+/// Rejects transactions sent from or to any of the denied addresses, for example the addresses
+/// of the OFAC sanctions list.
+///
+/// This also applies to local transactions.
+#[derive(Debug, Clone, Default)]
+pub struct DenyListPolicy {
+    addresses: HashSet<Address>,
+}
+
+impl DenyListPolicy {
+    /// Creates a new policy denying the given addresses.
+    pub fn new(addresses: impl IntoIterator<Item = Address>) -> Self {
+        Self { addresses: addresses.into_iter().collect() }
+    }
+
+    /// Reads the denied addresses from the file at the given path.
+    ///
+    /// The file must contain one address per line. Empty lines and lines starting with `#` are
+    /// ignored.
+    pub fn from_file(path: impl AsRef<Path>) -> std::io::Result<Self> {
+        let contents = std::fs::read_to_string(path)?;
+        let mut addresses = HashSet::new();
+        for (idx, line) in contents.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue
+            }
+            let address = line.parse().map_err(|err| {
+                std::io::Error::new(
+                    std::io::ErrorKind::InvalidData,
+                    format!("invalid address on line {}: {err}", idx + 1),
+                )
+            })?;
+            addresses.insert(address);
+        }
+        Ok(Self { addresses })
+    }
+
+    /// Adds the given addresses to the deny list.
+    pub fn extend(&mut self, addresses: impl IntoIterator<Item = Address>) {
+        self.addresses.extend(addresses);
+    }
+
+    /// Returns `true` if the address is denied.
+    pub fn is_denied(&self, address: &Address) -> bool {
+        self.addresses.contains(address)
+    }
+
+    /// Returns the number of denied addresses.
+    pub fn len(&self) -> usize {
+        self.addresses.len()
+    }
+
+    /// Returns `true` if no address is denied.
+    pub fn is_empty(&self) -> bool {
+        self.addresses.is_empty()
+    }
+}
+
+impl AdmissionPolicy for DenyListPolicy {
+    fn check(&self, request: &AdmissionRequest<'_>) -> Result<(), InvalidPoolTransactionError> {
+        if self.is_denied(&request.sender) {
+            return Err(InvalidPoolTransactionError::DeniedSender(request.sender))
+        }
+        if let Some(to) = request.to.filter(|to| self.is_denied(to)) {
+            return Err(InvalidPoolTransactionError::DeniedRecipient(to))
+        }
+        Ok(())
+    }
+}
+
+/// Limits the number of transactions a sender can submit to the pool per interval.
+///
+/// Local transactions are exempt, and so are [`Readmissions`], which the pool accepted before.
+#[derive(Debug)]
+pub struct SenderRateLimitPolicy {
+    /// The maximum number of transactions per sender in an interval.
+    max_transactions: usize,
+    /// The length of the interval.
+    interval: Duration,
+    /// The start of the current interval and the number of transactions in it, by sender.
+    windows: Mutex<HashMap<Address, (Instant, usize)>>,
+}
+
+impl SenderRateLimitPolicy {
+    /// Creates a new policy that allows `max_transactions` per sender in every `interval`.
+    pub fn new(max_transactions: usize, interval: Duration) -> Self {
+        Self { max_transactions, interval, windows: Default::default() }
+    }
+}
+
+impl AdmissionPolicy for SenderRateLimitPolicy {
+    fn check(&self, request: &AdmissionRequest<'_>) -> Result<(), InvalidPoolTransactionError> {
+        if request.is_local || request.is_readmission {
+            return Ok(())
+        }
+
+        let now = Instant::now();
+        let mut windows = self.windows.lock();
+        if windows.len() >= RATE_LIMIT_PRUNE_THRESHOLD && !windows.contains_key(&request.sender) {
+            windows.retain(|_, (start, _)| now.duration_since(*start) < self.interval);
+        }
+
+        let (start, count) = windows.entry(request.sender).or_insert((now, 0));
+        if now.duration_since(*start) >= self.interval {
+            *start = now;
+            *count = 0;
+        }
+        if *count >= self.max_transactions {
+            return Err(InvalidPoolTransactionError::SenderRateLimited(request.sender))
+        }
+        *count += 1;
+        Ok(())
+    }
+}
+
+/// Rejects transactions with a tip below the minimum of the subpool they're destined for.
+///
+/// Blob transactions are checked against the minimum of the blob subpool, all other transactions
+/// against the minimum of the regular subpools. Local transactions are exempt.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct MinTipPolicy {
+    /// The minimum tip of non-blob transactions.
+    min_priority_fee: Option<u128>,
+    /// The minimum tip of blob transactions.
+    min_blob_priority_fee: Option<u128>,
+}
+
+impl MinTipPolicy {
+    /// Creates a new policy with the given minimum tips for non-blob and blob transactions.
+    pub const fn new(min_priority_fee: Option<u128>, min_blob_priority_fee: Option<u128>) -> Self {
+        Self { min_priority_fee, min_blob_priority_fee }
+    }
+}
+
+impl AdmissionPolicy for MinTipPolicy {
+    fn check(&self, request: &AdmissionRequest<'_>) -> Result<(), InvalidPoolTransactionError> {
+        if request.is_local {
+            return Ok(())
+        }
+
+        let minimum =
+            if request.is_eip4844 { self.min_blob_priority_fee } else { self.min_priority_fee };
+        match minimum {
+            Some(minimum) if request.priority_fee < minimum => {
+                Err(InvalidPoolTransactionError::TipBelowMinimum {
+                    tip: request.priority_fee,
+                    minimum,
+                })
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Rejects contract calls of any of the denied 4-byte function selectors.
+///
+/// This also applies to local transactions.
+#[derive(Debug, Clone, Default)]
+pub struct SelectorDenyListPolicy {
+    selectors: HashSet<Selector>,
+}
+
+impl SelectorDenyListPolicy {
+    /// Creates a new policy denying the given selectors.
+    pub fn new(selectors: impl IntoIterator<Item = Selector>) -> Self {
+        Self { selectors: selectors.into_iter().collect() }
+    }
+}
+
+impl AdmissionPolicy for SelectorDenyListPolicy {
+    fn check(&self, request: &AdmissionRequest<'_>) -> Result<(), InvalidPoolTransactionError> {
+        if request.to.is_none() {
+            // contract creations don't call a function
+            return Ok(())
+        }
+        let Some(selector) = request.input.get(..4).map(Selector::from_slice) else {
+            return Ok(())
+        };
+        if self.selectors.contains(&selector) {
+            return Err(InvalidPoolTransactionError::DeniedSelector(selector))
+        }
+        Ok(())
+    }
+}
+
+/// Rejects transactions with input data larger than the limit.
+///
+/// Unlike [`DEFAULT_MAX_TX_INPUT_BYTES`](super::DEFAULT_MAX_TX_INPUT_BYTES), which limits the
+/// encoded size of the whole transaction, this only limits the calldata. This also applies to
+/// local transactions.
+#[derive(Debug, Clone, Copy)]
+pub struct MaxCalldataPolicy {
+    max_size: usize,
+}
+
+impl MaxCalldataPolicy {
+    /// Creates a new policy with the given max calldata size in bytes.
+    pub const fn new(max_size: usize) -> Self {
+        Self { max_size }
+    }
+}
+
+impl AdmissionPolicy for MaxCalldataPolicy {
+    fn check(&self, request: &AdmissionRequest<'_>) -> Result<(), InvalidPoolTransactionError> {
+        if request.input.len() > self.max_size {
+            return Err(InvalidPoolTransactionError::CalldataTooLarge(
+                request.input.len(),
+                self.max_size,
+            ))
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::MockTransaction;
+    use alloy_primitives::Bytes;
+    use assert_matches::assert_matches;
+
+    #[test]
+    fn deny_list_policy() {
+        let tx = MockTransaction::eip1559();
+        let sender = tx.sender();
+        let recipient = tx.to().unwrap();
+        let policy = DenyListPolicy::new([recipient]);
+
+        let request = AdmissionRequest::new(&tx, TransactionOrigin::Local, true);
+        assert_matches!(
+            policy.check(&request),
+            Err(InvalidPoolTransactionError::DeniedRecipient(to)) if to == recipient
+        );
+
+        let policy = DenyListPolicy::new([sender]);
+        assert_matches!(
+            policy.check(&request),
+            Err(InvalidPoolTransactionError::DeniedSender(from)) if from == sender
+        );
+
+        let tx = MockTransaction::eip1559();
+        assert!(policy
+            .check(&AdmissionRequest::new(&tx, TransactionOrigin::External, false))
+            .is_ok());
+    }
+
+    #[test]
+    fn deny_list_from_file() {
+        let address = Address::random();
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("deny.txt");
+        std::fs::write(&path, format!("# sanctioned\n\n{address}\n")).unwrap();
+
+        let policy = DenyListPolicy::from_file(&path).unwrap();
+        assert_eq!(policy.len(), 1);
+        assert!(policy.is_denied(&address));
+
+        std::fs::write(&path, "not an address\n").unwrap();
+        assert!(DenyListPolicy::from_file(&path).is_err());
+    }
+
+    #[test]
+    fn sender_rate_limit_policy() {
+        let policy = SenderRateLimitPolicy::new(2, Duration::from_secs(3600));
+        let tx = MockTransaction::eip1559();
+        let request = AdmissionRequest::new(&tx, TransactionOrigin::External, false);
+
+        assert!(policy.check(&request).is_ok());
+        assert!(policy.check(&request).is_ok());
+        assert_matches!(
+            policy.check(&request),
+            Err(InvalidPoolTransactionError::SenderRateLimited(sender)) if sender == tx.sender()
+        );
+
+        // local transactions are exempt
+        let request = AdmissionRequest::new(&tx, TransactionOrigin::Local, true);
+        assert!(policy.check(&request).is_ok());
+
+        // and so are readmitted transactions
+        let request =
+            AdmissionRequest::new(&tx, TransactionOrigin::External, false).with_readmission(true);
+        assert!(policy.check(&request).is_ok());
+
+        // the limit is reset after the interval
+        let policy = SenderRateLimitPolicy::new(1, Duration::ZERO);
+        let request = AdmissionRequest::new(&tx, TransactionOrigin::External, false);
+        assert!(policy.check(&request).is_ok());
+        assert!(policy.check(&request).is_ok());
+    }
+
+    #[test]
+    fn min_tip_policy() {
+        let policy = MinTipPolicy::new(Some(10), Some(100));
+
+        let tx = MockTransaction::eip1559().with_priority_fee(9).with_max_fee(1000);
+        assert_matches!(
+            policy.check(&AdmissionRequest::new(&tx, TransactionOrigin::External, false)),
+            Err(InvalidPoolTransactionError::TipBelowMinimum { tip: 9, minimum: 10 })
+        );
+        assert!(policy.check(&AdmissionRequest::new(&tx, TransactionOrigin::Local, true)).is_ok());
+
+        let tx = MockTransaction::eip1559().with_priority_fee(10).with_max_fee(1000);
+        assert!(policy
+            .check(&AdmissionRequest::new(&tx, TransactionOrigin::External, false))
+            .is_ok());
+
+        let tx = MockTransaction::eip4844().with_priority_fee(10).with_max_fee(1000);
+        assert_matches!(
+            policy.check(&AdmissionRequest::new(&tx, TransactionOrigin::External, false)),
+            Err(InvalidPoolTransactionError::TipBelowMinimum { tip: 10, minimum: 100 })
+        );
+    }
+
+    #[test]
+    fn selector_deny_list_policy() {
+        let selector = Selector::from([0xa9, 0x05, 0x9c, 0xbb]);
+        let policy = SelectorDenyListPolicy::new([selector]);
+
+        let tx = MockTransaction::eip1559()
+            .with_input(Bytes::from_static(&[0xa9, 0x05, 0x9c, 0xbb, 0x00]));
+        assert_matches!(
+            policy.check(&AdmissionRequest::new(&tx, TransactionOrigin::External, false)),
+            Err(InvalidPoolTransactionError::DeniedSelector(denied)) if denied == selector
+        );
+
+        let tx = tx.with_input(Bytes::from_static(&[0xa9, 0x05, 0x9c]));
+        assert!(policy
+            .check(&AdmissionRequest::new(&tx, TransactionOrigin::External, false))
+            .is_ok());
+    }
+
+    #[test]
+    fn admission_policies_chain() {
+        let tx = MockTransaction::eip1559().with_input(Bytes::from_static(&[0; 33]));
+        let request = AdmissionRequest::new(&tx, TransactionOrigin::External, false);
+
+        let policies = AdmissionPolicies::new();
+        assert!(policies.check(&request).is_ok());
+
+        let policies = policies
+            .with_policy(MaxCalldataPolicy::new(32))
+            .with_policy(DenyListPolicy::new([tx.sender()]));
+        assert_eq!(policies.len(), 2);
+        assert_matches!(
+            policies.check(&request),
+            Err(InvalidPoolTransactionError::CalldataTooLarge(33, 32))
+        );
+    }
+
+    #[test]
+    fn readmissions() {
+        let readmissions = AdmissionPolicies::new().readmissions();
+        let hash = TxHash::random();
+        assert!(!readmissions.take(&hash));
+
+        readmissions.extend([hash]);
+        assert!(readmissions.take(&hash));
+        assert!(!readmissions.take(&hash));
+    }
+}
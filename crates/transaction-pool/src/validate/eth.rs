@@ -7,7 +7,10 @@ use crate::{
         Eip4844PoolTransactionError, Eip7702PoolTransactionError, InvalidPoolTransactionError,
     },
     traits::TransactionOrigin,
-    validate::{ValidTransaction, ValidationTask, MAX_INIT_CODE_BYTE_SIZE},
+    validate::{
+        AdmissionPolicies, AdmissionPolicy, AdmissionRequest, ValidTransaction, ValidationTask,
+        MAX_INIT_CODE_BYTE_SIZE,
+    },
     EthBlobTransactionSidecar, EthPoolTransaction, LocalTransactionConfig,
     TransactionValidationOutcome, TransactionValidationTaskExecutor, TransactionValidator,
 };
@@ -128,7 +131,7 @@ where
 /// - Maximum transaction size
 /// - Maximum gas limit
 ///
-/// And adheres to the configured [`LocalTransactionConfig`].
+/// And adheres to the configured [`LocalTransactionConfig`] and [`AdmissionPolicies`].
 #[derive(Debug)]
 pub(crate) struct EthTransactionValidatorInner<Client, T> {
     /// Spec of the chain
@@ -157,6 +160,8 @@ pub(crate) struct EthTransactionValidatorInner<Client, T> {
     local_transactions_config: LocalTransactionConfig,
     /// Maximum size in bytes a single transaction can have in order to be accepted into the pool.
     max_tx_input_bytes: usize,
+    /// Policies checked before a transaction is accepted into the pool.
+    admission_policies: AdmissionPolicies,
     /// Marker for the transaction type
     _marker: PhantomData<T>,
 }
@@ -181,6 +186,9 @@ where
         origin: TransactionOrigin,
         mut transaction: Tx,
     ) -> TransactionValidationOutcome<Tx> {
+        // Taken before any check, so that the mark doesn't outlive an invalid transaction
+        let is_readmission = self.admission_policies.take_readmission(transaction.hash());
+
         // Checks for tx_type
         match transaction.tx_type() {
             LEGACY_TX_TYPE_ID => {
@@ -463,6 +471,19 @@ where
             }
         }
 
+        // Check the configured admission policies after all other checks, so that rate limits are
+        // only consumed by otherwise valid transactions
+        if let Err(err) = self.admission_policies.check(
+            &AdmissionRequest::new(
+                &transaction,
+                origin,
+                self.local_transactions_config.is_local(origin, transaction.sender_ref()),
+            )
+            .with_readmission(is_readmission),
+        ) {
+            return TransactionValidationOutcome::Invalid(transaction, err)
+        }
+
         // Return the valid transaction
         TransactionValidationOutcome::Valid {
             balance: account.balance,
@@ -534,6 +555,8 @@ pub struct EthTransactionValidatorBuilder {
     local_transactions_config: LocalTransactionConfig,
     /// Max size in bytes of a single transaction allowed
     max_tx_input_bytes: usize,
+    /// Policies checked before a transaction is accepted into the pool.
+    admission_policies: AdmissionPolicies,
 }
 
 impl EthTransactionValidatorBuilder {
@@ -554,6 +577,7 @@ impl EthTransactionValidatorBuilder {
             kzg_settings: EnvKzgSettings::Default,
             local_transactions_config: Default::default(),
             max_tx_input_bytes: DEFAULT_MAX_TX_INPUT_BYTES,
+            admission_policies: Default::default(),
 
             // by default all transaction types are allowed
             eip2718: true,
@@ -659,6 +683,12 @@ impl EthTransactionValidatorBuilder {
         self
     }
 
+    /// Sets the [`AdmissionPolicies`] a transaction must pass for acceptance into the pool.
+    pub fn with_admission_policies(mut self, admission_policies: AdmissionPolicies) -> Self {
+        self.admission_policies = admission_policies;
+        self
+    }
+
     /// Sets the number of additional tasks to spawn.
     pub const fn with_additional_tasks(mut self, additional_tasks: usize) -> Self {
         self.additional_tasks = additional_tasks;
@@ -712,6 +742,7 @@ impl EthTransactionValidatorBuilder {
             kzg_settings,
             local_transactions_config,
             max_tx_input_bytes,
+            admission_policies,
             ..
         } = self;
 
@@ -735,6 +766,7 @@ impl EthTransactionValidatorBuilder {
             kzg_settings,
             local_transactions_config,
             max_tx_input_bytes,
+            admission_policies,
             _marker: Default::default(),
         };
 
@@ -936,4 +968,82 @@ mod tests {
         let tx = pool.get(transaction.hash());
         assert!(tx.is_none());
     }
+
+    #[tokio::test]
+    async fn invalid_on_admission_policy() {
+        let transaction = get_transaction();
+
+        let provider = MockEthProvider::default();
+        provider.add_account(
+            transaction.sender(),
+            ExtendedAccount::new(transaction.nonce(), U256::MAX),
+        );
+
+        let blob_store = InMemoryBlobStore::default();
+        let validator = EthTransactionValidatorBuilder::new(MAINNET.clone())
+            .with_admission_policies(
+                AdmissionPolicies::new()
+                    .with_policy(crate::validate::DenyListPolicy::new([transaction.sender()])),
+            )
+            .build(provider, blob_store);
+
+        let outcome = validator.validate_one(TransactionOrigin::Local, transaction.clone());
+        assert!(matches!(
+            outcome,
+            TransactionValidationOutcome::Invalid(_, InvalidPoolTransactionError::DeniedSender(sender))
+                if sender == transaction.sender()
+        ));
+    }
+
+    #[tokio::test]
+    async fn rate_limit_after_stateful_validation() {
+        let transaction = get_transaction();
+
+        let provider = MockEthProvider::default();
+        // nonce too low, the transaction fails the stateful checks
+        provider.add_account(
+            transaction.sender(),
+            ExtendedAccount::new(transaction.nonce() + 1, U256::MAX),
+        );
+
+        let blob_store = InMemoryBlobStore::default();
+        let admission_policies = AdmissionPolicies::new().with_policy(
+            crate::validate::SenderRateLimitPolicy::new(1, std::time::Duration::from_secs(60)),
+        );
+        let readmissions = admission_policies.readmissions();
+        let validator = EthTransactionValidatorBuilder::new(MAINNET.clone())
+            .with_admission_policies(admission_policies)
+            .build(provider.clone(), blob_store);
+
+        let outcome = validator.validate_one(TransactionOrigin::External, transaction.clone());
+        assert!(matches!(
+            outcome,
+            TransactionValidationOutcome::Invalid(
+                _,
+                InvalidPoolTransactionError::Consensus(
+                    InvalidTransactionError::NonceNotConsistent { .. }
+                )
+            )
+        ));
+
+        // the invalid transaction did not consume the quota of the sender
+        provider.add_account(
+            transaction.sender(),
+            ExtendedAccount::new(transaction.nonce(), U256::MAX),
+        );
+        let outcome = validator.validate_one(TransactionOrigin::External, transaction.clone());
+        assert!(outcome.is_valid());
+
+        let outcome = validator.validate_one(TransactionOrigin::External, transaction.clone());
+        assert!(outcome.is_invalid());
+
+        // reinjected and restored transactions are exempt from the rate limit
+        readmissions.extend([*transaction.hash()]);
+        let outcome = validator.validate_one(TransactionOrigin::External, transaction.clone());
+        assert!(outcome.is_valid());
+
+        // the readmission mark is consumed by the validation
+        let outcome = validator.validate_one(TransactionOrigin::External, transaction);
+        assert!(outcome.is_invalid());
+    }
 }
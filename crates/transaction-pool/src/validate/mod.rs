@@ -12,10 +12,17 @@ use futures_util::future::Either;
 use reth_primitives::{RecoveredTx, SealedBlock};
 use std::{fmt, future::Future, time::Instant};
 
+mod admission;
 mod constants;
 mod eth;
 mod task;
 
+/// Configurable policies for the admission of transactions into the pool.
+pub use admission::{
+    AdmissionPolicies, AdmissionPolicy, AdmissionRequest, DenyListPolicy, MaxCalldataPolicy,
+    MinTipPolicy, Readmissions, SelectorDenyListPolicy, SenderRateLimitPolicy,
+};
+
 /// A `TransactionValidator` implementation that validates ethereum transaction.
 pub use eth::*;
 
@@ -3,6 +3,7 @@ use alloy_primitives::{
     map::{B256HashMap, B256HashSet, HashMap, HashSet},
     B256,
 };
+use itertools::{EitherOrBoth, Itertools};
 
 /// The aggregation of trie updates.
 #[derive(PartialEq, Eq, Clone, Default, Debug)]
@@ -365,6 +366,36 @@ impl TrieUpdatesSorted {
     pub const fn storage_tries_ref(&self) -> &B256HashMap<StorageTrieUpdatesSorted> {
         &self.storage_tries
     }
+
+    /// Extends the sorted trie updates with the contents of another.
+    /// The updates in the second collection take precedence.
+    ///
+    /// Equivalent to [`TrieUpdates::extend_ref`], but keeps the updated nodes sorted.
+    pub fn extend_ref(&mut self, other: &Self) {
+        self.account_nodes = merge_sorted_nodes(
+            std::mem::take(&mut self.account_nodes),
+            &other.account_nodes,
+            &other.removed_nodes,
+        );
+        self.removed_nodes.extend(other.removed_nodes.iter().cloned());
+        for (hashed_address, storage_trie) in &other.storage_tries {
+            self.storage_tries.entry(*hashed_address).or_default().extend_ref(storage_trie);
+        }
+    }
+}
+
+impl From<TrieUpdatesSorted> for TrieUpdates {
+    fn from(sorted: TrieUpdatesSorted) -> Self {
+        Self {
+            account_nodes: sorted.account_nodes.into_iter().collect(),
+            removed_nodes: sorted.removed_nodes,
+            storage_tries: sorted
+                .storage_tries
+                .into_iter()
+                .map(|(hashed_address, updates)| (hashed_address, updates.into()))
+                .collect(),
+        }
+    }
 }
 
 /// Sorted trie updates used for lookups and insertions.
@@ -393,6 +424,53 @@ impl StorageTrieUpdatesSorted {
     pub const fn removed_nodes_ref(&self) -> &HashSet<Nibbles> {
         &self.removed_nodes
     }
+
+    /// Extends the sorted storage trie updates with the contents of another.
+    /// The updates in the second collection take precedence.
+    ///
+    /// Equivalent to [`StorageTrieUpdates::extend_ref`], but keeps the updated nodes sorted.
+    pub fn extend_ref(&mut self, other: &Self) {
+        if other.is_deleted {
+            self.storage_nodes.clear();
+            self.removed_nodes.clear();
+        }
+        self.is_deleted |= other.is_deleted;
+        self.storage_nodes = merge_sorted_nodes(
+            std::mem::take(&mut self.storage_nodes),
+            &other.storage_nodes,
+            &other.removed_nodes,
+        );
+        self.removed_nodes.extend(other.removed_nodes.iter().cloned());
+    }
+}
+
+impl From<StorageTrieUpdatesSorted> for StorageTrieUpdates {
+    fn from(sorted: StorageTrieUpdatesSorted) -> Self {
+        Self {
+            is_deleted: sorted.is_deleted,
+            storage_nodes: sorted.storage_nodes.into_iter().collect(),
+            removed_nodes: sorted.removed_nodes,
+        }
+    }
+}
+
+/// Merges the sorted nodes of `other` into the sorted `current` nodes, skipping the current nodes
+/// that were removed by `other`. The nodes of `other` take precedence.
+fn merge_sorted_nodes(
+    current: Vec<(Nibbles, BranchNodeCompact)>,
+    other: &[(Nibbles, BranchNodeCompact)],
+    removed: &HashSet<Nibbles>,
+) -> Vec<(Nibbles, BranchNodeCompact)> {
+    current
+        .into_iter()
+        .filter(|(nibbles, _)| !removed.contains(nibbles))
+        .merge_join_by(other.iter().cloned(), |(current, _), (other, _)| current.cmp(other))
+        .map(|entry| match entry {
+            EitherOrBoth::Left(node) | EitherOrBoth::Right(node) | EitherOrBoth::Both(_, node) => {
+                node
+            }
+        })
+        .collect()
 }
 
 /// Excludes empty nibbles from the given iterator.
@@ -620,6 +698,37 @@ pub mod serde_bincode_compat {
 #[cfg(all(test, feature = "serde"))]
 mod tests {
     use super::*;
+    use crate::TrieMask;
+
+    #[test]
+    fn sorted_trie_updates_extend() {
+        let node =
+            |mask| BranchNodeCompact { state_mask: TrieMask::new(mask), ..Default::default() };
+        let path = |nibbles: &[u8]| Nibbles::from_nibbles_unchecked(nibbles);
+        let hashed_address = B256::with_last_byte(1);
+
+        let mut current = TrieUpdates::default();
+        current.account_nodes.extend([(path(&[0x1]), node(1)), (path(&[0x2]), node(1))]);
+        current.account_nodes.insert(path(&[0x3]), node(1));
+        current.storage_tries.insert(
+            hashed_address,
+            StorageTrieUpdates {
+                storage_nodes: HashMap::from_iter([(path(&[0x1]), node(1))]),
+                ..Default::default()
+            },
+        );
+
+        let mut other = TrieUpdates::default();
+        other.account_nodes.extend([(path(&[0x2]), node(2)), (path(&[0x4]), node(2))]);
+        other.removed_nodes.insert(path(&[0x3]));
+        other.storage_tries.insert(hashed_address, StorageTrieUpdates::deleted());
+
+        let mut sorted = current.clone().into_sorted();
+        sorted.extend_ref(&other.clone().into_sorted());
+        current.extend(other);
+        assert_eq!(sorted, current.clone().into_sorted());
+        assert_eq!(TrieUpdates::from(sorted), current);
+    }
 
     #[test]
     fn test_trie_updates_serde_roundtrip() {
@@ -13,10 +13,11 @@ use reth_provider::{
 use reth_trie::{
     hashed_cursor::{HashedCursorFactory, HashedPostStateCursorFactory},
     node_iter::{TrieElement, TrieNodeIter},
+    prefix_set::TriePrefixSetsMut,
     trie_cursor::{InMemoryTrieCursorFactory, TrieCursorFactory},
-    updates::TrieUpdates,
+    updates::{TrieUpdates, TrieUpdatesSorted},
     walker::TrieWalker,
-    HashBuilder, Nibbles, StorageRoot, TrieInput, TRIE_ACCOUNT_RLP_MAX_SIZE,
+    HashBuilder, HashedPostStateSorted, Nibbles, StorageRoot, TrieInput, TRIE_ACCOUNT_RLP_MAX_SIZE,
 };
 use reth_trie_db::{DatabaseHashedCursorFactory, DatabaseTrieCursorFactory};
 use std::{collections::HashMap, sync::Arc};
@@ -38,8 +39,12 @@ use tracing::*;
 pub struct ParallelStateRoot<Factory> {
     /// Consistent view of the database.
     view: ConsistentDbView<Factory>,
-    /// Trie input.
-    input: TrieInput,
+    /// Sorted in-memory trie nodes.
+    nodes: Arc<TrieUpdatesSorted>,
+    /// Sorted in-memory hashed state.
+    state: Arc<HashedPostStateSorted>,
+    /// Prefix sets of the changed state.
+    prefix_sets: TriePrefixSetsMut,
     /// Parallel state root metrics.
     #[cfg(feature = "metrics")]
     metrics: ParallelStateRootMetrics,
@@ -48,9 +53,30 @@ pub struct ParallelStateRoot<Factory> {
 impl<Factory> ParallelStateRoot<Factory> {
     /// Create new parallel state root calculator.
     pub fn new(view: ConsistentDbView<Factory>, input: TrieInput) -> Self {
+        Self::new_sorted(
+            view,
+            Arc::new(input.nodes.into_sorted()),
+            Arc::new(input.state.into_sorted()),
+            input.prefix_sets,
+        )
+    }
+
+    /// Create new parallel state root calculator from already sorted trie nodes and hashed state.
+    ///
+    /// The storage root tasks release their references to `nodes` and `state` as soon as they
+    /// finish, so the caller can usually take them back with [`Arc::try_unwrap`] once the root is
+    /// computed.
+    pub fn new_sorted(
+        view: ConsistentDbView<Factory>,
+        nodes: Arc<TrieUpdatesSorted>,
+        state: Arc<HashedPostStateSorted>,
+        prefix_sets: TriePrefixSetsMut,
+    ) -> Self {
         Self {
             view,
-            input,
+            nodes,
+            state,
+            prefix_sets,
             #[cfg(feature = "metrics")]
             metrics: ParallelStateRootMetrics::default(),
         }
@@ -83,9 +109,9 @@ where
         retain_updates: bool,
     ) -> Result<(B256, TrieUpdates), ParallelStateRootError> {
         let mut tracker = ParallelTrieTracker::default();
-        let trie_nodes_sorted = Arc::new(self.input.nodes.into_sorted());
-        let hashed_state_sorted = Arc::new(self.input.state.into_sorted());
-        let prefix_sets = self.input.prefix_sets.freeze();
+        let trie_nodes_sorted = self.nodes;
+        let hashed_state_sorted = self.state;
+        let prefix_sets = self.prefix_sets.freeze();
         let storage_root_targets = StorageRootTargets::new(
             prefix_sets.account_prefix_set.iter().map(|nibbles| B256::from_slice(&nibbles.pack())),
             prefix_sets.storage_prefix_sets,
@@ -107,7 +133,9 @@ where
             let (tx, rx) = std::sync::mpsc::sync_channel(1);
 
             rayon::spawn_fifo(move || {
-                let result = (|| -> Result<_, ParallelStateRootError> {
+                // The inner closure takes ownership of the shared state, so it's released before
+                // the result is sent.
+                let result = (move || -> Result<_, ParallelStateRootError> {
                     let provider_ro = view.provider_ro()?;
                     let trie_cursor_factory = InMemoryTrieCursorFactory::new(
                         DatabaseTrieCursorFactory::new(provider_ro.tx_ref()),
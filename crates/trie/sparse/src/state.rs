@@ -87,6 +87,35 @@ impl<F: BlindedProviderFactory> SparseStateTrie<F> {
         self
     }
 
+    /// Replaces the blinded node provider factory, keeping all revealed nodes.
+    ///
+    /// This allows to keep the trie around after the provider it was revealed with is gone, and to
+    /// continue with a new one.
+    pub fn with_provider_factory<BPF: BlindedProviderFactory>(
+        self,
+        provider_factory: BPF,
+    ) -> SparseStateTrie<BPF> {
+        SparseStateTrie {
+            state: self.state.with_provider(provider_factory.account_node_provider()),
+            storages: self
+                .storages
+                .into_iter()
+                .map(|(address, trie)| {
+                    (address, trie.with_provider(provider_factory.storage_node_provider(address)))
+                })
+                .collect(),
+            provider_factory,
+            revealed: self.revealed,
+            retain_updates: self.retain_updates,
+            account_rlp_buf: self.account_rlp_buf,
+        }
+    }
+
+    /// Returns the revealed account keys and the revealed storage keys of each account.
+    pub const fn revealed(&self) -> &B256HashMap<B256HashSet> {
+        &self.revealed
+    }
+
     /// Returns `true` if account was already revealed.
     pub fn is_account_revealed(&self, account: &B256) -> bool {
         self.revealed.contains_key(account)
@@ -316,6 +345,29 @@ impl<F: BlindedProviderFactory> SparseStateTrie<F> {
         self.state.root()
     }
 
+    /// Prunes the account trie and the storage tries to the provided depth, see
+    /// [`RevealedSparseTrie::prune`].
+    ///
+    /// Accounts and storage slots whose leaves were pruned are not revealed anymore, and the
+    /// storage tries of those accounts are removed.
+    pub fn prune(&mut self, depth: usize) {
+        let Some(state) = self.state.as_revealed_mut() else { return };
+        state.prune(depth);
+
+        self.revealed
+            .retain(|account, _| state.get_leaf_value(&Nibbles::unpack(account)).is_some());
+        self.storages.retain(|account, _| self.revealed.contains_key(account));
+        for (account, slots) in &mut self.revealed {
+            match self.storages.get_mut(account).and_then(|trie| trie.as_revealed_mut()) {
+                Some(trie) => {
+                    trie.prune(depth);
+                    slots.retain(|slot| trie.get_leaf_value(&Nibbles::unpack(slot)).is_some());
+                }
+                None => slots.clear(),
+            }
+        }
+    }
+
     /// Returns [`TrieUpdates`] by taking the updates from the revealed sparse tries.
     ///
     /// Returns `None` if the accounts trie is not revealed.
@@ -328,8 +380,8 @@ impl<F: BlindedProviderFactory> SparseStateTrie<F> {
                 storage_tries: self
                     .storages
                     .iter_mut()
+                    .filter_map(|(address, trie)| Some((address, trie.as_revealed_mut()?)))
                     .map(|(address, trie)| {
-                        let trie = trie.as_revealed_mut().unwrap();
                         let updates = trie.take_updates();
                         let updates = StorageTrieUpdates {
                             is_deleted: updates.wiped,
@@ -625,5 +677,55 @@ mod tests {
                 removed_nodes: HashSet::default()
             }
         );
+
+        // the updates are taken, but keep being retained
+        assert_eq!(sparse.take_trie_updates(), Some(TrieUpdates::default()));
+
+        // blind storage tries have no updates
+        sparse.insert_storage_trie(address_3, SparseTrie::Blind);
+        sparse
+            .update_storage_leaf(address_1, Nibbles::unpack(slot_3), alloy_rlp::encode(value_1))
+            .unwrap();
+        trie_account_1.storage_root = sparse.storage_root(address_1).unwrap();
+        sparse.update_account_leaf(address_path_1, alloy_rlp::encode(trie_account_1)).unwrap();
+        sparse.root();
+        let sparse_updates = sparse.take_trie_updates().unwrap();
+        assert!(!sparse_updates.storage_tries.contains_key(&address_3));
+    }
+
+    #[test]
+    fn prune() {
+        let account = Account { nonce: 1, ..Default::default() };
+        let mut account_rlp = Vec::new();
+        account.into_trie_account(EMPTY_ROOT_HASH).encode(&mut account_rlp);
+        let slot_value = alloy_rlp::encode_fixed_size(&U256::from(1)).to_vec();
+
+        // account 1 is a leaf at depth 1, account 2 at depth 2
+        let address_1 = b256!("1000000000000000000000000000000000000000000000000000000000000000");
+        let address_2 = b256!("2000000000000000000000000000000000000000000000000000000000000000");
+        let address_3 = b256!("2100000000000000000000000000000000000000000000000000000000000000");
+        let slot_1 = b256!("1000000000000000000000000000000000000000000000000000000000000000");
+        let slot_2 = b256!("1100000000000000000000000000000000000000000000000000000000000000");
+
+        let mut sparse = SparseStateTrie::default().with_updates(true);
+        sparse.state = SparseTrie::revealed_empty();
+        let mut storage = SparseTrie::revealed_empty();
+        for slot in [slot_1, slot_2] {
+            storage.update_leaf(Nibbles::unpack(slot), slot_value.clone()).unwrap();
+        }
+        sparse.insert_storage_trie(address_1, storage.clone());
+        sparse.insert_storage_trie(address_2, storage);
+        for address in [address_1, address_2, address_3] {
+            sparse.update_account_leaf(Nibbles::unpack(address), account_rlp.clone()).unwrap();
+            sparse.revealed.insert(address, B256HashSet::from_iter([slot_1, slot_2]));
+        }
+        let root = sparse.root();
+
+        sparse.prune(2);
+        assert_eq!(sparse.root(), root);
+        // the leaf of account 1 and its storage trie are kept, but the storage slots below the
+        // depth are pruned
+        assert_eq!(sparse.revealed, B256HashMap::from_iter([(address_1, B256HashSet::default())]));
+        assert_eq!(sparse.storages.keys().collect::<Vec<_>>(), vec![&address_1]);
     }
 }
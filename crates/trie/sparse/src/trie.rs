@@ -120,6 +120,16 @@ impl<P> SparseTrie<P> {
     pub fn calculate_below_level(&mut self, level: usize) {
         self.as_revealed_mut().unwrap().update_rlp_node_level(level);
     }
+
+    /// Set new blinded node provider on the sparse trie, keeping all revealed nodes.
+    pub fn with_provider<BP>(self, provider: BP) -> SparseTrie<BP> {
+        match self {
+            Self::Blind => SparseTrie::Blind,
+            Self::Revealed(revealed) => {
+                SparseTrie::Revealed(Box::new(revealed.with_provider(provider)))
+            }
+        }
+    }
 }
 
 impl<P> SparseTrie<P>
@@ -270,9 +280,11 @@ impl<P> RevealedSparseTrie<P> {
         self.values.get(path)
     }
 
-    /// Takes and returns the retained sparse node updates
+    /// Takes and returns the retained sparse node updates.
+    ///
+    /// The updates keep being retained afterwards if retention was enabled.
     pub fn take_updates(&mut self) -> SparseTrieUpdates {
-        self.updates.take().unwrap_or_default()
+        self.updates.as_mut().map(core::mem::take).unwrap_or_default()
     }
 
     /// Reveal the trie node only if it was not known already.
@@ -523,6 +535,77 @@ impl<P> RevealedSparseTrie<P> {
         }
     }
 
+    /// Replaces the revealed nodes at the provided depth and below with their hashes, so that they
+    /// are retrieved from the blinded node provider again once they're needed.
+    ///
+    /// Only the children of branch nodes are blinded, and only if they're not embedded into their
+    /// parent. The hash masks of their parents are updated, so that the trie updates calculated
+    /// afterwards are the same as without pruning.
+    pub fn prune(&mut self, depth: usize) {
+        // calculate the hashes of all nodes
+        self.root();
+
+        let mut nodes = HashMap::default();
+        let mut values = HashMap::default();
+        let mut stack = vec![(Nibbles::default(), false)];
+        while let Some((path, is_branch_child)) = stack.pop() {
+            let Some(node) = self.nodes.remove(&path) else { continue };
+
+            if is_branch_child && path.len() >= depth {
+                let hash = match &node {
+                    SparseNode::Leaf { hash, .. } |
+                    SparseNode::Extension { hash, .. } |
+                    SparseNode::Branch { hash, .. } => *hash,
+                    SparseNode::Empty | SparseNode::Hash(_) => None,
+                };
+                if let Some(hash) = hash {
+                    let parent = path.slice(..path.len() - 1);
+                    let nibble = path.last().unwrap();
+                    // the hash mask bit of a blinded child is read from the hash mask of its
+                    // parent, while it's always set for revealed branch nodes
+                    if matches!(node, SparseNode::Branch { .. }) {
+                        self.branch_node_hash_masks.entry(parent).or_default().set_bit(nibble);
+                    } else if let Some(hash_mask) = self.branch_node_hash_masks.get_mut(&parent) {
+                        hash_mask.unset_bit(nibble);
+                    }
+                    nodes.insert(path, SparseNode::Hash(hash));
+                    continue
+                }
+            }
+
+            match &node {
+                SparseNode::Empty | SparseNode::Hash(_) => {}
+                SparseNode::Leaf { key, .. } => {
+                    let mut full = path.clone();
+                    full.extend_from_slice_unchecked(key);
+                    if let Some(value) = self.values.remove(&full) {
+                        values.insert(full, value);
+                    }
+                }
+                SparseNode::Extension { key, .. } => {
+                    let mut child_path = path.clone();
+                    child_path.extend_from_slice_unchecked(key);
+                    stack.push((child_path, false));
+                }
+                SparseNode::Branch { state_mask, .. } => {
+                    for bit in CHILD_INDEX_RANGE {
+                        if state_mask.is_bit_set(bit) {
+                            let mut child_path = path.clone();
+                            child_path.push_unchecked(bit);
+                            stack.push((child_path, true));
+                        }
+                    }
+                }
+            }
+            nodes.insert(path, node);
+        }
+
+        self.nodes = nodes;
+        self.values = values;
+        self.branch_node_hash_masks
+            .retain(|path, _| matches!(self.nodes.get(path), Some(SparseNode::Branch { .. })));
+    }
+
     /// Update hashes of the nodes that are located at a level deeper than or equal to the provided
     /// depth. Root node has a level of 0.
     pub fn update_rlp_node_level(&mut self, depth: usize) {
@@ -1485,6 +1568,85 @@ mod tests {
         assert_eq_sparse_trie_proof_nodes(&sparse, hash_builder_proof_nodes);
     }
 
+    #[test]
+    fn sparse_trie_take_updates_keeps_retention() {
+        let paths = (0..=16).map(|b| Nibbles::unpack(B256::with_last_byte(b))).collect::<Vec<_>>();
+        let value = |nonce| Account { nonce, ..Default::default() };
+        let value_encoded = |nonce| {
+            let mut account_rlp = Vec::new();
+            value(nonce).into_trie_account(EMPTY_ROOT_HASH).encode(&mut account_rlp);
+            account_rlp
+        };
+
+        let mut sparse = RevealedSparseTrie::default().with_updates(true);
+        for path in &paths {
+            sparse.update_leaf(path.clone(), value_encoded(0)).unwrap();
+        }
+        sparse.root();
+        assert!(!sparse.take_updates().updated_nodes.is_empty());
+
+        // update a single leaf after the updates were taken
+        sparse.update_leaf(paths[0].clone(), value_encoded(1)).unwrap();
+        let sparse_root = sparse.root();
+        let sparse_updates = sparse.take_updates();
+
+        let (hash_builder_root, hash_builder_updates, _, _) = run_hash_builder(
+            paths.iter().cloned().enumerate().map(|(i, path)| (path, value((i == 0) as u64))),
+            Default::default(),
+            paths.clone(),
+        );
+
+        assert_eq!(sparse_root, hash_builder_root);
+        assert_eq!(sparse_updates.updated_nodes, hash_builder_updates.account_nodes);
+    }
+
+    #[test]
+    fn sparse_trie_prune() {
+        let value_encoded = || {
+            let mut account_rlp = Vec::new();
+            Account::default().into_trie_account(EMPTY_ROOT_HASH).encode(&mut account_rlp);
+            account_rlp
+        };
+        let paths = [
+            // leaves of a branch node at depth 2
+            [0x0, 0x0, 0x0],
+            [0x0, 0x0, 0x1],
+            // leaf at depth 2
+            [0x0, 0x1, 0x0],
+            // leaf at depth 1
+            [0x1, 0x0, 0x0],
+        ]
+        .map(|nibbles| pad_nibbles_right(Nibbles::from_nibbles(nibbles)));
+
+        let mut sparse = RevealedSparseTrie::default().with_updates(true);
+        for path in &paths {
+            sparse.update_leaf(path.clone(), value_encoded()).unwrap();
+        }
+        let root = sparse.root();
+        sparse.take_updates();
+
+        let mut pruned = sparse.clone();
+        pruned.prune(2);
+        assert_eq!(pruned.root(), root);
+        assert_matches!(
+            pruned.nodes.get(&Nibbles::from_nibbles([0x0, 0x0])),
+            Some(SparseNode::Hash(_))
+        );
+        assert_matches!(
+            pruned.nodes.get(&Nibbles::from_nibbles([0x0, 0x1])),
+            Some(SparseNode::Hash(_))
+        );
+        assert!(!pruned.nodes.contains_key(&Nibbles::from_nibbles([0x0, 0x0, 0x0])));
+        assert_eq!(pruned.values.keys().collect::<Vec<_>>(), vec![&paths[3]]);
+
+        // a new leaf next to the blinded nodes results in the same root and trie updates
+        let path = pad_nibbles_right(Nibbles::from_nibbles([0x0, 0x2]));
+        sparse.update_leaf(path.clone(), value_encoded()).unwrap();
+        pruned.update_leaf(path, value_encoded()).unwrap();
+        assert_eq!(pruned.root(), sparse.root());
+        assert_eq!(pruned.take_updates(), sparse.take_updates());
+    }
+
     #[test]
     fn sparse_trie_empty_update_multiple_upper_nibbles() {
         let paths = (239..=255).map(|b| Nibbles::unpack(B256::repeat_byte(b))).collect::<Vec<_>>();
@@ -7,7 +7,7 @@ use alloy_primitives::{
     map::{hash_map, B256HashMap, B256HashSet, HashMap, HashSet},
     Address, B256, U256,
 };
-use itertools::Itertools;
+use itertools::{EitherOrBoth, Itertools};
 use rayon::prelude::{IntoParallelIterator, ParallelIterator};
 use reth_primitives::Account;
 use reth_trie_common::KeyHasher;
@@ -302,6 +302,24 @@ impl HashedPostStateSorted {
     pub const fn account_storages(&self) -> &B256HashMap<HashedStorageSorted> {
         &self.storages
     }
+
+    /// Extend this sorted hashed post state with contents of another.
+    /// Entries in the second hashed post state take precedence.
+    ///
+    /// Equivalent to [`HashedPostState::extend_ref`], but keeps the entries sorted.
+    pub fn extend_ref(&mut self, other: &Self) {
+        self.accounts.extend_ref(&other.accounts);
+        for (hashed_address, storage) in &other.storages {
+            match self.storages.entry(*hashed_address) {
+                hash_map::Entry::Vacant(entry) => {
+                    entry.insert(storage.clone());
+                }
+                hash_map::Entry::Occupied(mut entry) => {
+                    entry.get_mut().extend_ref(storage);
+                }
+            }
+        }
+    }
 }
 
 /// Sorted account state optimized for iterating during state trie calculation.
@@ -322,6 +340,18 @@ impl HashedAccountsSorted {
             .chain(self.destroyed_accounts.iter().map(|address| (*address, None)))
             .sorted_by_key(|entry| *entry.0)
     }
+
+    /// Extend sorted accounts with contents of other.
+    /// The entries in second sorted accounts take precedence.
+    pub fn extend_ref(&mut self, other: &Self) {
+        self.destroyed_accounts.retain(|address| !contains_sorted(&other.accounts, address));
+        self.destroyed_accounts.extend(&other.destroyed_accounts);
+        self.accounts = merge_sorted(
+            std::mem::take(&mut self.accounts),
+            &other.accounts,
+            &other.destroyed_accounts,
+        );
+    }
 }
 
 /// Sorted hashed storage optimized for iterating during state trie calculation.
@@ -349,6 +379,47 @@ impl HashedStorageSorted {
             .chain(self.zero_valued_slots.iter().map(|hashed_slot| (*hashed_slot, U256::ZERO)))
             .sorted_by_key(|entry| *entry.0)
     }
+
+    /// Extend sorted hashed storage with contents of other.
+    /// The entries in second sorted hashed storage take precedence.
+    pub fn extend_ref(&mut self, other: &Self) {
+        if other.wiped {
+            self.wiped = true;
+            self.non_zero_valued_slots.clear();
+            self.zero_valued_slots.clear();
+        }
+        self.zero_valued_slots.retain(|slot| !contains_sorted(&other.non_zero_valued_slots, slot));
+        self.zero_valued_slots.extend(&other.zero_valued_slots);
+        self.non_zero_valued_slots = merge_sorted(
+            std::mem::take(&mut self.non_zero_valued_slots),
+            &other.non_zero_valued_slots,
+            &other.zero_valued_slots,
+        );
+    }
+}
+
+/// Returns `true` if the sorted entries contain the given key.
+fn contains_sorted<T>(entries: &[(B256, T)], key: &B256) -> bool {
+    entries.binary_search_by(|(entry, _)| entry.cmp(key)).is_ok()
+}
+
+/// Merges the sorted entries of `other` into the sorted `current` entries, skipping the current
+/// entries whose keys are in `removed`. The entries of `other` take precedence.
+fn merge_sorted<T: Clone>(
+    current: Vec<(B256, T)>,
+    other: &[(B256, T)],
+    removed: &B256HashSet,
+) -> Vec<(B256, T)> {
+    current
+        .into_iter()
+        .filter(|(key, _)| !removed.contains(key))
+        .merge_join_by(other.iter().cloned(), |(current, _), (other, _)| current.cmp(other))
+        .map(|entry| match entry {
+            EitherOrBoth::Left(entry) |
+            EitherOrBoth::Right(entry) |
+            EitherOrBoth::Both(_, entry) => entry,
+        })
+        .collect()
 }
 
 #[cfg(test)]
@@ -576,4 +647,48 @@ mod tests {
             .with_accounts(vec![(keccak256(Address::random()), Some(Account::default()))]);
         assert!(!non_empty_state.is_empty());
     }
+
+    #[test]
+    fn sorted_hashed_state_extension() {
+        let account = |nonce| Account { nonce, ..Default::default() };
+        let (address1, address2, address3) =
+            (B256::with_last_byte(1), B256::with_last_byte(2), B256::with_last_byte(3));
+        let (slot1, slot2, slot3) =
+            (B256::with_last_byte(1), B256::with_last_byte(2), B256::with_last_byte(3));
+
+        let mut state = HashedPostState::default()
+            .with_accounts([
+                (address1, Some(account(1))),
+                (address2, Some(account(1))),
+                (address3, None),
+            ])
+            .with_storages([
+                (
+                    address1,
+                    HashedStorage::from_iter(
+                        false,
+                        [(slot1, U256::from(1)), (slot2, U256::from(1)), (slot3, U256::ZERO)],
+                    ),
+                ),
+                (address2, HashedStorage::from_iter(false, [(slot1, U256::from(1))])),
+            ]);
+        let other = HashedPostState::default()
+            .with_accounts([(address2, None), (address3, Some(account(2)))])
+            .with_storages([
+                (
+                    address1,
+                    HashedStorage::from_iter(
+                        false,
+                        [(slot1, U256::ZERO), (slot2, U256::from(2)), (slot3, U256::from(2))],
+                    ),
+                ),
+                (address2, HashedStorage::from_iter(true, [(slot2, U256::from(2))])),
+                (address3, HashedStorage::from_iter(false, [(slot1, U256::from(2))])),
+            ]);
+
+        let mut sorted = state.clone().into_sorted();
+        sorted.extend_ref(&other.clone().into_sorted());
+        state.extend(other);
+        assert_eq!(sorted, state.into_sorted());
+    }
 }
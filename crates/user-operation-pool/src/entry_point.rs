@@ -0,0 +1,67 @@
+//! Interface of the `EntryPoint` v0.7 contract.
+
+use alloy_primitives::{address, Address};
+use alloy_sol_types::sol;
+
+/// The address of the `EntryPoint` v0.7 contract.
+pub const ENTRY_POINT_V07_ADDRESS: Address = address!("0000000071727De22E5E9d8BAf0edAc6f37da032");
+
+sol! {
+    /// A user operation as it's passed to the `EntryPoint` v0.7.
+    #[allow(missing_docs)]
+    #[derive(Debug, PartialEq, Eq)]
+    struct PackedUserOp {
+        address sender;
+        uint256 nonce;
+        bytes initCode;
+        bytes callData;
+        bytes32 accountGasLimits;
+        uint256 preVerificationGas;
+        bytes32 gasFees;
+        bytes paymasterAndData;
+        bytes signature;
+    }
+
+    #[allow(missing_docs)]
+    interface IEntryPoint {
+        event UserOperationEvent(
+            bytes32 indexed userOpHash,
+            address indexed sender,
+            address indexed paymaster,
+            uint256 nonce,
+            bool success,
+            uint256 actualGasCost,
+            uint256 actualGasUsed
+        );
+        event UserOperationRevertReason(
+            bytes32 indexed userOpHash,
+            address indexed sender,
+            uint256 nonce,
+            bytes revertReason
+        );
+        event BeforeExecution();
+
+        error FailedOp(uint256 opIndex, string reason);
+        error FailedOpWithRevert(uint256 opIndex, string reason, bytes inner);
+
+        function handleOps(PackedUserOp[] calldata ops, address payable beneficiary) external;
+    }
+
+    #[allow(missing_docs)]
+    interface IAccount {
+        function validateUserOp(
+            PackedUserOp calldata userOp,
+            bytes32 userOpHash,
+            uint256 missingAccountFunds
+        ) external returns (uint256 validationData);
+    }
+
+    #[allow(missing_docs)]
+    interface IPaymaster {
+        function validatePaymasterUserOp(
+            PackedUserOp calldata userOp,
+            bytes32 userOpHash,
+            uint256 maxCost
+        ) external returns (bytes memory context, uint256 validationData);
+    }
+}
@@ -0,0 +1,90 @@
+use crate::{ReputationStatus, ValidationPhase};
+use alloy_primitives::{Address, U256};
+
+/// Error returned when a user operation is rejected by the
+/// [`UserOperationPool`](crate::UserOperationPool).
+#[derive(Debug, thiserror::Error)]
+pub enum UserOperationError {
+    /// The user operation targets an entry point that isn't supported by the pool.
+    #[error("unsupported entry point {0}")]
+    UnsupportedEntryPoint(Address),
+    /// A field of the user operation is invalid.
+    #[error("invalid user operation: {0}")]
+    InvalidField(&'static str),
+    /// The pre-verification gas doesn't cover the calldata and the overhead of the bundle.
+    #[error("pre-verification gas {actual} is lower than the required {required}")]
+    PreVerificationGasTooLow {
+        /// The pre-verification gas of the user operation.
+        actual: U256,
+        /// The minimum pre-verification gas.
+        required: u64,
+    },
+    /// The verification gas limit exceeds the maximum of the pool.
+    #[error("verification gas limit {actual} exceeds the maximum {max}")]
+    VerificationGasLimitTooHigh {
+        /// The verification gas limit of the user operation.
+        actual: U256,
+        /// The maximum verification gas limit.
+        max: u64,
+    },
+    /// The max priority fee per gas is higher than the max fee per gas.
+    #[error("max priority fee per gas higher than max fee per gas")]
+    TipAboveFeeCap,
+    /// The user operation is already in the pool.
+    #[error("user operation already known")]
+    AlreadyKnown,
+    /// The user operation replaces another one of the same sender and nonce without paying
+    /// enough.
+    #[error("replacement user operation underpriced")]
+    ReplacementUnderpriced,
+    /// The sender already has the maximum number of user operations in the pool.
+    #[error("sender {0} has too many user operations in the pool")]
+    SenderLimitExceeded(Address),
+    /// The pool is full and the user operation pays less than all of the pooled ones.
+    #[error("user operation pool is full")]
+    PoolFull,
+    /// The simulation of the user operation failed.
+    #[error("simulation failed: {0}")]
+    SimulationFailed(String),
+    /// An opcode that is banned during the validation was used.
+    #[error("{phase} used the banned opcode {opcode}")]
+    BannedOpcode {
+        /// The phase of the validation.
+        phase: ValidationPhase,
+        /// The name of the opcode.
+        opcode: &'static str,
+    },
+    /// The storage of a contract that isn't associated with the sender was accessed during the
+    /// validation.
+    #[error("{phase} accessed the storage slot {slot} of {address}")]
+    StorageAccess {
+        /// The phase of the validation.
+        phase: ValidationPhase,
+        /// The address of the contract.
+        address: Address,
+        /// The accessed storage slot.
+        slot: U256,
+    },
+    /// A factory or paymaster of the user operation is throttled or banned.
+    #[error("{entity} {address} is {status}")]
+    Reputation {
+        /// The kind of entity.
+        entity: &'static str,
+        /// The address of the entity.
+        address: Address,
+        /// The reputation status of the entity.
+        status: ReputationStatus,
+    },
+}
+
+impl UserOperationError {
+    /// Returns the JSON-RPC error code of the error as defined by ERC-4337.
+    pub const fn error_code(&self) -> i32 {
+        match self {
+            Self::SimulationFailed(_) => -32500,
+            Self::BannedOpcode { .. } | Self::StorageAccess { .. } => -32502,
+            Self::Reputation { .. } => -32504,
+            _ => -32602,
+        }
+    }
+}
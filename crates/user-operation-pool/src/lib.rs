@@ -0,0 +1,48 @@
+//! An alt-mempool for ERC-4337 user operations of the `EntryPoint` v0.7.
+//!
+//! The [`UserOperationPool`] keeps the user operations that passed validation next to the regular
+//! transaction pool. A user operation is validated in two steps:
+//! 1. The static checks of [`validate_user_operation`], e.g. the gas limits and fees.
+//! 2. A simulation of `handleOps` with the [`ValidationInspector`], which enforces the opcode and
+//!    storage access rules of ERC-7562 during the validation phase.
+//!
+//! The entities of the user operations, i.e. factories and paymasters, are tracked by the
+//! [`Reputation`] of the pool, and are throttled or banned if their user operations are seen but
+//! not included.
+//!
+//! The [`maintain_user_operation_pool`] task removes the included user operations from the pool and
+//! keeps them for the receipts. If a [`BundlerConfig`] is set, it also bundles the best user
+//! operations into a `handleOps` transaction on every new block, which is submitted to the regular
+//! transaction pool, so it's included by the payload builder like any other transaction.
+//!
+//! The RPC methods `eth_sendUserOperation`, `eth_estimateUserOperationGas` and
+//! `eth_getUserOperationReceipt` are implemented by `reth_rpc::EthUserOperation`.
+#![doc(
+    html_logo_url = "https://raw.githubusercontent.com/paradigmxyz/reth/main/assets/reth-docs.png",
+    html_favicon_url = "https://avatars0.githubusercontent.com/u/97369466?s=256",
+    issue_tracker_base_url = "https://github.com/paradigmxyz/reth/issues/"
+)]
+#![cfg_attr(not(test), warn(unused_crate_dependencies))]
+#![cfg_attr(docsrs, feature(doc_cfg, doc_auto_cfg))]
+
+pub mod entry_point;
+
+mod error;
+pub use error::UserOperationError;
+
+mod maintain;
+pub use maintain::{maintain_user_operation_pool, BundlerConfig};
+
+mod operation;
+pub use operation::{IncludedUserOperation, UserOperation};
+
+mod pool;
+pub use pool::{UserOperationPool, UserOperationPoolConfig};
+
+mod reputation;
+pub use reputation::{Reputation, ReputationStatus};
+
+mod validate;
+pub use validate::{
+    pre_verification_gas, validate_user_operation, ValidationInspector, ValidationPhase,
+};
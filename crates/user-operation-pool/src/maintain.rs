@@ -0,0 +1,211 @@
+use crate::{entry_point::IEntryPoint, IncludedUserOperation, UserOperationPool};
+use alloy_consensus::{BlockHeader, SignableTransaction, TxEip1559};
+use alloy_primitives::{map::B256HashMap, Address, Bytes, TxKind, U256};
+use alloy_signer::SignerSync;
+use alloy_signer_local::PrivateKeySigner;
+use alloy_sol_types::{SolCall, SolEvent};
+use futures_util::{Stream, StreamExt};
+use reth_chain_state::CanonStateNotification;
+use reth_chainspec::{ChainSpecProvider, EthChainSpec};
+use reth_execution_types::Chain;
+use reth_primitives::{RecoveredTx, Transaction, TransactionSigned};
+use reth_storage_api::StateProviderFactory;
+use reth_transaction_pool::{PoolTransaction, TransactionOrigin, TransactionPool};
+use std::path::Path;
+use tracing::{debug, trace, warn};
+
+/// The interval of block time in seconds at which the reputation decays.
+const REPUTATION_DECAY_INTERVAL: u64 = 60 * 60;
+
+/// Configuration of the bundler of the [`maintain_user_operation_pool`] task.
+#[derive(Debug, Clone)]
+pub struct BundlerConfig {
+    /// The signer of the bundle transactions, which pays for their gas.
+    pub signer: PrivateKeySigner,
+    /// The beneficiary of the fees paid by the user operations.
+    pub beneficiary: Address,
+    /// The maximum gas limit of a bundle transaction.
+    pub max_bundle_gas: u64,
+}
+
+impl BundlerConfig {
+    /// Creates a new [`BundlerConfig`] that pays the fees to the address of the signer.
+    pub const fn new(signer: PrivateKeySigner) -> Self {
+        Self { beneficiary: signer.address(), signer, max_bundle_gas: 10_000_000 }
+    }
+
+    /// Creates a new [`BundlerConfig`] with the hex encoded private key in the given file.
+    pub fn from_key_file(path: &Path) -> eyre::Result<Self> {
+        let key = std::fs::read_to_string(path)
+            .map_err(|err| eyre::eyre!("failed to read bundler key {}: {err}", path.display()))?;
+        let signer = key.trim().parse::<PrivateKeySigner>()?;
+        Ok(Self::new(signer))
+    }
+}
+
+/// Keeps the [`UserOperationPool`] up to date with the canonical chain.
+///
+/// On every new block, the user operations included by the entry point are removed from the pool
+/// and recorded for the receipts, and the reputation of the entities decays every hour of block
+/// time.
+///
+/// If a [`BundlerConfig`] is set, the best user operations are bundled into a `handleOps`
+/// transaction, which is added to the transaction pool as a local transaction, so the payload
+/// builder includes it like any other transaction. A new bundle is only created once the previous
+/// one was included. The user operations are not simulated again before they are bundled.
+pub async fn maintain_user_operation_pool<Client, P, St>(
+    pool: UserOperationPool,
+    client: Client,
+    transaction_pool: P,
+    mut events: St,
+    bundler: Option<BundlerConfig>,
+) where
+    Client: StateProviderFactory + ChainSpecProvider + Send + 'static,
+    P: TransactionPool<Transaction: PoolTransaction<Consensus = TransactionSigned>> + 'static,
+    St: Stream<Item = CanonStateNotification> + Send + Unpin + 'static,
+{
+    let entry_point = pool.config().entry_point;
+    let mut last_decay = None;
+
+    while let Some(event) = events.next().await {
+        if let Some(reverted) = event.reverted() {
+            pool.on_reorged(included_operations(&reverted, entry_point).map(|op| op.hash));
+        }
+        let committed = event.committed();
+        pool.on_included(included_operations(&committed, entry_point));
+
+        let tip = committed.tip();
+        let last_decay = last_decay.get_or_insert(tip.timestamp());
+        if tip.timestamp() >= *last_decay + REPUTATION_DECAY_INTERVAL {
+            pool.decay_reputation();
+            *last_decay = tip.timestamp();
+        }
+
+        let Some(bundler) = &bundler else { continue };
+        let chain_spec = client.chain_spec();
+        let Some(base_fee) =
+            tip.next_block_base_fee(chain_spec.base_fee_params_at_timestamp(tip.timestamp() + 12))
+        else {
+            continue
+        };
+        if let Err(err) = submit_bundle(
+            &pool,
+            &client,
+            &transaction_pool,
+            bundler,
+            chain_spec.chain_id(),
+            base_fee,
+            bundler.max_bundle_gas.min(tip.gas_limit()),
+        )
+        .await
+        {
+            warn!(target: "userop::pool", %err, "Failed to submit bundle");
+        }
+    }
+}
+
+/// Bundles the best user operations into a `handleOps` transaction, and adds it to the
+/// transaction pool.
+async fn submit_bundle<Client, P>(
+    pool: &UserOperationPool,
+    client: &Client,
+    transaction_pool: &P,
+    bundler: &BundlerConfig,
+    chain_id: u64,
+    base_fee: u64,
+    max_gas: u64,
+) -> eyre::Result<()>
+where
+    Client: StateProviderFactory,
+    P: TransactionPool<Transaction: PoolTransaction<Consensus = TransactionSigned>>,
+{
+    let address = bundler.signer.address();
+    if !transaction_pool.get_transactions_by_sender(address).is_empty() {
+        trace!(target: "userop::pool", "Previous bundle is still pending");
+        return Ok(())
+    }
+
+    let ops = pool.best_operations(base_fee, max_gas);
+    if ops.is_empty() {
+        return Ok(())
+    }
+
+    let nonce = client.latest()?.account_nonce(address)?.unwrap_or_default();
+    let max_fee_per_gas = ops.iter().map(|op| op.max_fee_per_gas()).min().unwrap_or_default();
+    let max_priority_fee_per_gas =
+        ops.iter().map(|op| op.max_priority_fee_per_gas()).min().unwrap_or_default();
+    let transaction = TxEip1559 {
+        chain_id,
+        nonce,
+        gas_limit: ops.iter().map(|op| op.gas_limit()).sum(),
+        max_fee_per_gas,
+        max_priority_fee_per_gas,
+        to: TxKind::Call(pool.config().entry_point),
+        value: U256::ZERO,
+        access_list: Default::default(),
+        input: IEntryPoint::handleOpsCall {
+            ops: ops.iter().map(|op| op.pack()).collect(),
+            beneficiary: bundler.beneficiary,
+        }
+        .abi_encode()
+        .into(),
+    };
+    let signature = bundler.signer.sign_hash_sync(&transaction.signature_hash())?;
+    let signed = TransactionSigned::new_unhashed(Transaction::Eip1559(transaction), signature);
+    let transaction =
+        P::Transaction::try_from_consensus(RecoveredTx::from_signed_transaction(signed, address))
+            .map_err(|_| eyre::eyre!("bundle transaction is not a valid pool transaction"))?;
+
+    let hash = transaction_pool.add_transaction(TransactionOrigin::Local, transaction).await?;
+    debug!(target: "userop::pool", %hash, ops = ops.len(), "Submitted bundle");
+
+    Ok(())
+}
+
+/// Returns the user operations included by the entry point in the chain.
+///
+/// The logs of a user operation are the logs emitted after the `BeforeExecution` event or the
+/// previous `UserOperationEvent`, up to and including its own `UserOperationEvent`.
+fn included_operations(
+    chain: &Chain,
+    entry_point: Address,
+) -> impl Iterator<Item = IncludedUserOperation> + '_ {
+    chain.blocks_and_receipts().flat_map(move |(block, receipts)| {
+        block.body.transactions.iter().zip(receipts).flat_map(move |(transaction, receipt)| {
+            let mut included = Vec::new();
+            let Some(receipt) = receipt else { return included };
+
+            let mut start = 0;
+            let mut reasons = B256HashMap::<Bytes>::default();
+            for (index, log) in receipt.logs.iter().enumerate() {
+                if log.address != entry_point {
+                    continue
+                }
+                if IEntryPoint::BeforeExecution::decode_log(log, true).is_ok() {
+                    start = index + 1;
+                } else if let Ok(event) =
+                    IEntryPoint::UserOperationRevertReason::decode_log(log, true)
+                {
+                    reasons.insert(event.userOpHash, event.revertReason.clone());
+                } else if let Ok(event) = IEntryPoint::UserOperationEvent::decode_log(log, true) {
+                    included.push(IncludedUserOperation {
+                        hash: event.userOpHash,
+                        sender: event.sender,
+                        nonce: event.nonce,
+                        paymaster: event.paymaster,
+                        success: event.success,
+                        actual_gas_cost: event.actualGasCost,
+                        actual_gas_used: event.actualGasUsed,
+                        reason: reasons.remove(&event.userOpHash).unwrap_or_default(),
+                        transaction_hash: transaction.hash(),
+                        block_hash: block.hash(),
+                        block_number: block.number,
+                        logs: start..index + 1,
+                    });
+                    start = index + 1;
+                }
+            }
+            included
+        })
+    })
+}
@@ -0,0 +1,261 @@
+use crate::entry_point::PackedUserOp;
+use alloy_primitives::{keccak256, Address, Bytes, FixedBytes, B256, U256};
+use alloy_rpc_types_eth::erc4337::PackedUserOperation;
+use alloy_sol_types::SolValue;
+use std::ops::Range;
+
+/// A user operation of the `EntryPoint` v0.7 with its hash.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UserOperation {
+    inner: PackedUserOperation,
+    hash: B256,
+}
+
+impl UserOperation {
+    /// Creates a new [`UserOperation`] for the given entry point and chain.
+    pub fn new(inner: PackedUserOperation, entry_point: Address, chain_id: u64) -> Self {
+        let packed = pack(&inner);
+        let hash = keccak256(
+            (
+                keccak256(
+                    (
+                        packed.sender,
+                        packed.nonce,
+                        keccak256(&packed.initCode),
+                        keccak256(&packed.callData),
+                        packed.accountGasLimits,
+                        packed.preVerificationGas,
+                        packed.gasFees,
+                        keccak256(&packed.paymasterAndData),
+                    )
+                        .abi_encode(),
+                ),
+                entry_point,
+                U256::from(chain_id),
+            )
+                .abi_encode(),
+        );
+        Self { inner, hash }
+    }
+
+    /// Returns the hash of the user operation.
+    pub const fn hash(&self) -> B256 {
+        self.hash
+    }
+
+    /// Returns the user operation as it was submitted.
+    pub const fn inner(&self) -> &PackedUserOperation {
+        &self.inner
+    }
+
+    /// Returns the sender of the user operation.
+    pub const fn sender(&self) -> Address {
+        self.inner.sender
+    }
+
+    /// Returns the nonce of the user operation.
+    pub const fn nonce(&self) -> U256 {
+        self.inner.nonce
+    }
+
+    /// Returns the factory of the user operation, if the sender must be deployed.
+    pub const fn factory(&self) -> Option<Address> {
+        self.inner.factory
+    }
+
+    /// Returns the paymaster of the user operation.
+    pub const fn paymaster(&self) -> Option<Address> {
+        self.inner.paymaster
+    }
+
+    /// Returns the max fee per gas.
+    pub fn max_fee_per_gas(&self) -> u128 {
+        self.inner.max_fee_per_gas.saturating_to()
+    }
+
+    /// Returns the max priority fee per gas.
+    pub fn max_priority_fee_per_gas(&self) -> u128 {
+        self.inner.max_priority_fee_per_gas.saturating_to()
+    }
+
+    /// Returns the tip per gas paid to the bundler with the given base fee, or `None` if the max
+    /// fee per gas is lower than the base fee.
+    pub fn effective_tip_per_gas(&self, base_fee: u64) -> Option<u128> {
+        let max_fee = self.max_fee_per_gas();
+        let base_fee = base_fee as u128;
+        (max_fee >= base_fee).then(|| (max_fee - base_fee).min(self.max_priority_fee_per_gas()))
+    }
+
+    /// Returns the sum of all gas limits of the user operation, which bounds the gas the bundle
+    /// transaction can spend on it.
+    pub fn gas_limit(&self) -> u64 {
+        [
+            self.inner.pre_verification_gas,
+            self.inner.verification_gas_limit,
+            self.inner.call_gas_limit,
+            self.inner.paymaster_verification_gas_limit.unwrap_or_default(),
+            self.inner.paymaster_post_op_gas_limit.unwrap_or_default(),
+        ]
+        .into_iter()
+        .fold(0u64, |total, gas| total.saturating_add(gas.saturating_to()))
+    }
+
+    /// Returns the user operation in the form passed to the entry point.
+    pub fn pack(&self) -> PackedUserOp {
+        pack(&self.inner)
+    }
+}
+
+/// Packs the user operation into the form passed to the entry point.
+fn pack(op: &PackedUserOperation) -> PackedUserOp {
+    let init_code = op
+        .factory
+        .map(|factory| {
+            [factory.as_slice(), op.factory_data.as_ref().map_or(&[][..], |data| data)]
+                .concat()
+                .into()
+        })
+        .unwrap_or_default();
+    let paymaster_and_data = op
+        .paymaster
+        .map(|paymaster| {
+            [
+                paymaster.as_slice(),
+                &op.paymaster_verification_gas_limit.unwrap_or_default().to_be_bytes::<32>()[16..],
+                &op.paymaster_post_op_gas_limit.unwrap_or_default().to_be_bytes::<32>()[16..],
+                op.paymaster_data.as_ref().map_or(&[][..], |data| data),
+            ]
+            .concat()
+            .into()
+        })
+        .unwrap_or_default();
+
+    PackedUserOp {
+        sender: op.sender,
+        nonce: op.nonce,
+        initCode: init_code,
+        callData: op.call_data.clone(),
+        accountGasLimits: pack_u128s(op.verification_gas_limit, op.call_gas_limit),
+        preVerificationGas: op.pre_verification_gas,
+        gasFees: pack_u128s(op.max_priority_fee_per_gas, op.max_fee_per_gas),
+        paymasterAndData: paymaster_and_data,
+        signature: op.signature.clone(),
+    }
+}
+
+/// Packs two values into the high and low 128 bits of a word.
+fn pack_u128s(high: U256, low: U256) -> FixedBytes<32> {
+    ((high << 128usize) | (low & U256::from(u128::MAX))).into()
+}
+
+/// A user operation that was included in a block.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IncludedUserOperation {
+    /// The hash of the user operation.
+    pub hash: B256,
+    /// The sender of the user operation.
+    pub sender: Address,
+    /// The nonce of the user operation.
+    pub nonce: U256,
+    /// The paymaster of the user operation, or zero if there's none.
+    pub paymaster: Address,
+    /// Whether the execution of the user operation succeeded.
+    pub success: bool,
+    /// The gas cost paid by the sender or paymaster.
+    pub actual_gas_cost: U256,
+    /// The gas used by the user operation.
+    pub actual_gas_used: U256,
+    /// The revert reason of the execution, if it failed.
+    pub reason: Bytes,
+    /// The hash of the transaction that included the user operation.
+    pub transaction_hash: B256,
+    /// The hash of the block that included the user operation.
+    pub block_hash: B256,
+    /// The number of the block that included the user operation.
+    pub block_number: u64,
+    /// The range of the logs of the user operation in the logs of the transaction receipt.
+    pub logs: Range<usize>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entry_point::ENTRY_POINT_V07_ADDRESS;
+    use alloy_primitives::{address, bytes};
+
+    fn user_operation() -> PackedUserOperation {
+        PackedUserOperation {
+            sender: address!("1111111111111111111111111111111111111111"),
+            nonce: U256::from(1),
+            factory: None,
+            factory_data: None,
+            call_data: bytes!("deadbeef"),
+            call_gas_limit: U256::from(100_000),
+            verification_gas_limit: U256::from(200_000),
+            pre_verification_gas: U256::from(50_000),
+            max_fee_per_gas: U256::from(30),
+            max_priority_fee_per_gas: U256::from(2),
+            paymaster: None,
+            paymaster_verification_gas_limit: None,
+            paymaster_post_op_gas_limit: None,
+            paymaster_data: None,
+            signature: bytes!("01"),
+        }
+    }
+
+    #[test]
+    fn pack_user_operation() {
+        let mut op = user_operation();
+        op.factory = Some(address!("2222222222222222222222222222222222222222"));
+        op.factory_data = Some(bytes!("aa"));
+        op.paymaster = Some(address!("3333333333333333333333333333333333333333"));
+        op.paymaster_verification_gas_limit = Some(U256::from(1));
+        op.paymaster_post_op_gas_limit = Some(U256::from(2));
+        op.paymaster_data = Some(bytes!("bb"));
+
+        let op = UserOperation::new(op, ENTRY_POINT_V07_ADDRESS, 1);
+        let packed = op.pack();
+        assert_eq!(packed.initCode, bytes!("2222222222222222222222222222222222222222aa"));
+        assert_eq!(
+            packed.paymasterAndData,
+            bytes!(
+                "3333333333333333333333333333333333333333"
+                "00000000000000000000000000000001"
+                "00000000000000000000000000000002"
+                "bb"
+            )
+        );
+        assert_eq!(
+            U256::from_be_bytes(packed.accountGasLimits.0),
+            (U256::from(200_000) << 128usize) | U256::from(100_000)
+        );
+        assert_eq!(
+            U256::from_be_bytes(packed.gasFees.0),
+            (U256::from(2) << 128usize) | U256::from(30)
+        );
+        assert_eq!(op.gas_limit(), 350_003);
+    }
+
+    #[test]
+    fn user_operation_hash() {
+        let op = UserOperation::new(user_operation(), ENTRY_POINT_V07_ADDRESS, 1);
+        assert_ne!(
+            op.hash(),
+            UserOperation::new(user_operation(), ENTRY_POINT_V07_ADDRESS, 2).hash()
+        );
+        assert_ne!(op.hash(), UserOperation::new(user_operation(), Address::ZERO, 1).hash());
+
+        // The signature is not part of the hash
+        let mut signed = user_operation();
+        signed.signature = bytes!("02");
+        assert_eq!(op.hash(), UserOperation::new(signed, ENTRY_POINT_V07_ADDRESS, 1).hash());
+    }
+
+    #[test]
+    fn effective_tip() {
+        let op = UserOperation::new(user_operation(), ENTRY_POINT_V07_ADDRESS, 1);
+        assert_eq!(op.effective_tip_per_gas(10), Some(2));
+        assert_eq!(op.effective_tip_per_gas(29), Some(1));
+        assert_eq!(op.effective_tip_per_gas(31), None);
+    }
+}
@@ -0,0 +1,393 @@
+use crate::{
+    entry_point::ENTRY_POINT_V07_ADDRESS, IncludedUserOperation, Reputation, ReputationStatus,
+    UserOperation, UserOperationError,
+};
+use alloy_primitives::{
+    map::{AddressHashMap, B256HashMap},
+    Address, B256, U256,
+};
+use parking_lot::RwLock;
+use schnellru::{ByLength, LruMap};
+use std::{collections::BTreeMap, fmt, sync::Arc};
+
+/// Configuration of the [`UserOperationPool`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UserOperationPoolConfig {
+    /// The supported entry point.
+    pub entry_point: Address,
+    /// The chain ID that is part of the user operation hashes.
+    pub chain_id: u64,
+    /// The maximum number of user operations in the pool.
+    pub max_operations: usize,
+    /// The maximum number of user operations of a sender in the pool.
+    pub max_operations_per_sender: usize,
+    /// The maximum number of user operations in the pool that use a throttled factory or
+    /// paymaster.
+    pub max_throttled_entity_operations: usize,
+    /// The minimum fee bump in percent for a user operation to replace another one with the same
+    /// sender and nonce.
+    pub price_bump: u128,
+    /// The maximum verification gas limit of a user operation.
+    pub max_verification_gas: u64,
+    /// The maximum number of included user operations that are kept for the receipts.
+    pub max_included_operations: u32,
+}
+
+impl UserOperationPoolConfig {
+    /// Creates a new configuration for the `EntryPoint` v0.7 on the given chain.
+    pub const fn new(chain_id: u64) -> Self {
+        Self {
+            entry_point: ENTRY_POINT_V07_ADDRESS,
+            chain_id,
+            max_operations: 4096,
+            max_operations_per_sender: 4,
+            max_throttled_entity_operations: 4,
+            price_bump: 10,
+            max_verification_gas: 5_000_000,
+            max_included_operations: 10_000,
+        }
+    }
+}
+
+/// A pool of validated ERC-4337 user operations.
+///
+/// This is an alt-mempool that is independent of the regular transaction pool. The user
+/// operations are validated before they're added, see the crate documentation.
+#[derive(Clone)]
+pub struct UserOperationPool {
+    config: Arc<UserOperationPoolConfig>,
+    inner: Arc<RwLock<PoolInner>>,
+}
+
+impl UserOperationPool {
+    /// Creates a new empty pool.
+    pub fn new(config: UserOperationPoolConfig) -> Self {
+        let inner = PoolInner {
+            operations: Default::default(),
+            by_sender: Default::default(),
+            reputation: Default::default(),
+            included: LruMap::new(ByLength::new(config.max_included_operations)),
+        };
+        Self { config: Arc::new(config), inner: Arc::new(RwLock::new(inner)) }
+    }
+
+    /// Returns the configuration of the pool.
+    pub fn config(&self) -> &UserOperationPoolConfig {
+        &self.config
+    }
+
+    /// Returns the number of user operations in the pool.
+    pub fn len(&self) -> usize {
+        self.inner.read().operations.len()
+    }
+
+    /// Returns `true` if the pool is empty.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the user operation with the given hash.
+    pub fn get(&self, hash: &B256) -> Option<Arc<UserOperation>> {
+        self.inner.read().operations.get(hash).cloned()
+    }
+
+    /// Returns the included user operation with the given hash, if it's still known.
+    pub fn included(&self, hash: &B256) -> Option<IncludedUserOperation> {
+        self.inner.write().included.get(hash).cloned()
+    }
+
+    /// Returns the reputation status of a factory or paymaster.
+    pub fn reputation_status(&self, address: &Address) -> ReputationStatus {
+        self.inner.read().reputation.status(address)
+    }
+
+    /// Checks the reputation of the factory and paymaster of the user operation.
+    pub fn check_reputation(&self, op: &UserOperation) -> Result<(), UserOperationError> {
+        self.inner.read().check_reputation(op, self.config.max_throttled_entity_operations)
+    }
+
+    /// Adds a validated user operation to the pool, and returns its hash.
+    ///
+    /// A user operation with the same sender and nonce as a pooled one replaces it if both of its
+    /// fees are higher by at least the price bump. If the pool is full, the user operation with
+    /// the lowest priority fee is evicted.
+    pub fn add_operation(&self, op: UserOperation) -> Result<B256, UserOperationError> {
+        let mut inner = self.inner.write();
+        let hash = op.hash();
+        if inner.operations.contains_key(&hash) {
+            return Err(UserOperationError::AlreadyKnown)
+        }
+        inner.check_reputation(&op, self.config.max_throttled_entity_operations)?;
+
+        let replaced = inner
+            .by_sender
+            .get(&op.sender())
+            .and_then(|nonces| nonces.get(&op.nonce()))
+            .and_then(|hash| inner.operations.get(hash))
+            .cloned();
+        if let Some(replaced) = replaced {
+            let bump = |fee: u128| fee.saturating_mul(100 + self.config.price_bump) / 100;
+            if op.max_fee_per_gas() < bump(replaced.max_fee_per_gas()) ||
+                op.max_priority_fee_per_gas() < bump(replaced.max_priority_fee_per_gas())
+            {
+                return Err(UserOperationError::ReplacementUnderpriced)
+            }
+            inner.remove(&replaced.hash());
+        } else if inner.by_sender.get(&op.sender()).map_or(0, BTreeMap::len) >=
+            self.config.max_operations_per_sender
+        {
+            return Err(UserOperationError::SenderLimitExceeded(op.sender()))
+        } else if inner.operations.len() >= self.config.max_operations {
+            let worst = inner
+                .operations
+                .values()
+                .min_by_key(|op| op.max_priority_fee_per_gas())
+                .filter(|worst| worst.max_priority_fee_per_gas() < op.max_priority_fee_per_gas())
+                .map(|worst| worst.hash())
+                .ok_or(UserOperationError::PoolFull)?;
+            inner.remove(&worst);
+        }
+
+        for entity in op.factory().into_iter().chain(op.paymaster()) {
+            inner.reputation.add_seen(entity);
+        }
+        inner.by_sender.entry(op.sender()).or_default().insert(op.nonce(), hash);
+        inner.operations.insert(hash, Arc::new(op));
+
+        Ok(hash)
+    }
+
+    /// Removes the user operations with the given hashes from the pool.
+    pub fn remove_operations(&self, hashes: impl IntoIterator<Item = B256>) {
+        let mut inner = self.inner.write();
+        for hash in hashes {
+            inner.remove(&hash);
+        }
+    }
+
+    /// Returns the best user operations to bundle with the given base fee, up to the given total
+    /// gas limit.
+    ///
+    /// Only the user operation with the lowest nonce of each sender is returned, ordered by their
+    /// effective tip.
+    pub fn best_operations(&self, base_fee: u64, max_gas: u64) -> Vec<Arc<UserOperation>> {
+        let inner = self.inner.read();
+        let mut candidates = inner
+            .by_sender
+            .values()
+            .filter_map(|nonces| nonces.values().next())
+            .filter_map(|hash| inner.operations.get(hash))
+            .filter_map(|op| Some((op.effective_tip_per_gas(base_fee)?, op)))
+            .collect::<Vec<_>>();
+        candidates.sort_by(|(a, _), (b, _)| b.cmp(a));
+
+        let mut gas = 0u64;
+        candidates
+            .into_iter()
+            .filter(|(_, op)| {
+                let Some(total) = gas.checked_add(op.gas_limit()).filter(|total| *total <= max_gas)
+                else {
+                    return false
+                };
+                gas = total;
+                true
+            })
+            .map(|(_, op)| Arc::clone(op))
+            .collect()
+    }
+
+    /// Records the user operations that were included in a block.
+    ///
+    /// The included user operations and all pooled ones with the same sender and nonce are
+    /// removed, and the inclusion is credited to the factory and paymaster.
+    pub fn on_included(&self, included: impl IntoIterator<Item = IncludedUserOperation>) {
+        let mut inner = self.inner.write();
+        for included in included {
+            let pooled = inner
+                .by_sender
+                .get(&included.sender)
+                .and_then(|nonces| nonces.get(&included.nonce))
+                .copied();
+            if let Some(op) = pooled.and_then(|hash| inner.remove(&hash)) {
+                if let Some(factory) = op.factory() {
+                    inner.reputation.add_included(factory);
+                }
+            }
+            if !included.paymaster.is_zero() {
+                inner.reputation.add_included(included.paymaster);
+            }
+            inner.included.insert(included.hash, included);
+        }
+    }
+
+    /// Forgets the included user operations with the given hashes, e.g. because their blocks
+    /// were reorged.
+    pub fn on_reorged(&self, hashes: impl IntoIterator<Item = B256>) {
+        let mut inner = self.inner.write();
+        for hash in hashes {
+            inner.included.remove(&hash);
+        }
+    }
+
+    /// Decays the reputation of all entities.
+    ///
+    /// This is expected to be called every hour.
+    pub fn decay_reputation(&self) {
+        self.inner.write().reputation.decay();
+    }
+}
+
+impl fmt::Debug for UserOperationPool {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("UserOperationPool")
+            .field("config", &self.config)
+            .field("len", &self.len())
+            .finish_non_exhaustive()
+    }
+}
+
+struct PoolInner {
+    /// All user operations by hash.
+    operations: B256HashMap<Arc<UserOperation>>,
+    /// The hashes of the user operations by sender and nonce.
+    by_sender: AddressHashMap<BTreeMap<U256, B256>>,
+    /// The reputation of the factories and paymasters.
+    reputation: Reputation,
+    /// The included user operations, for the receipts.
+    included: LruMap<B256, IncludedUserOperation>,
+}
+
+impl PoolInner {
+    fn check_reputation(
+        &self,
+        op: &UserOperation,
+        max_throttled_entity_operations: usize,
+    ) -> Result<(), UserOperationError> {
+        let entities = [("factory", op.factory()), ("paymaster", op.paymaster())];
+        for (entity, address) in entities {
+            let Some(address) = address else { continue };
+            let status = self.reputation.status(&address);
+            let rejected = match status {
+                ReputationStatus::Ok => false,
+                ReputationStatus::Throttled => {
+                    self.operations
+                        .values()
+                        .filter(|op| {
+                            op.factory() == Some(address) || op.paymaster() == Some(address)
+                        })
+                        .count() >=
+                        max_throttled_entity_operations
+                }
+                ReputationStatus::Banned => true,
+            };
+            if rejected {
+                return Err(UserOperationError::Reputation { entity, address, status })
+            }
+        }
+        Ok(())
+    }
+
+    fn remove(&mut self, hash: &B256) -> Option<Arc<UserOperation>> {
+        let op = self.operations.remove(hash)?;
+        if let Some(nonces) = self.by_sender.get_mut(&op.sender()) {
+            nonces.remove(&op.nonce());
+            if nonces.is_empty() {
+                self.by_sender.remove(&op.sender());
+            }
+        }
+        Some(op)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::Bytes;
+    use alloy_rpc_types_eth::erc4337::PackedUserOperation;
+
+    fn user_operation(sender: Address, nonce: u64, max_fee: u64, tip: u64) -> UserOperation {
+        let op = PackedUserOperation {
+            sender,
+            nonce: U256::from(nonce),
+            factory: None,
+            factory_data: None,
+            call_data: Bytes::new(),
+            call_gas_limit: U256::from(100_000),
+            verification_gas_limit: U256::from(100_000),
+            pre_verification_gas: U256::from(50_000),
+            max_fee_per_gas: U256::from(max_fee),
+            max_priority_fee_per_gas: U256::from(tip),
+            paymaster: None,
+            paymaster_verification_gas_limit: None,
+            paymaster_post_op_gas_limit: None,
+            paymaster_data: None,
+            signature: Bytes::new(),
+        };
+        UserOperation::new(op, ENTRY_POINT_V07_ADDRESS, 1)
+    }
+
+    #[test]
+    fn replace_user_operation() {
+        let pool = UserOperationPool::new(UserOperationPoolConfig::new(1));
+        let sender = Address::random();
+
+        let hash = pool.add_operation(user_operation(sender, 0, 100, 10)).unwrap();
+        assert!(matches!(
+            pool.add_operation(user_operation(sender, 0, 100, 10)),
+            Err(UserOperationError::AlreadyKnown)
+        ));
+        assert!(matches!(
+            pool.add_operation(user_operation(sender, 0, 109, 11)),
+            Err(UserOperationError::ReplacementUnderpriced)
+        ));
+
+        let replacement = pool.add_operation(user_operation(sender, 0, 110, 11)).unwrap();
+        assert!(pool.get(&hash).is_none());
+        assert!(pool.get(&replacement).is_some());
+        assert_eq!(pool.len(), 1);
+    }
+
+    #[test]
+    fn user_operation_limits() {
+        let mut config = UserOperationPoolConfig::new(1);
+        config.max_operations = 5;
+        let pool = UserOperationPool::new(config);
+
+        let sender = Address::random();
+        for nonce in 0..4 {
+            pool.add_operation(user_operation(sender, nonce, 100, 10)).unwrap();
+        }
+        assert!(matches!(
+            pool.add_operation(user_operation(sender, 4, 100, 10)),
+            Err(UserOperationError::SenderLimitExceeded(_))
+        ));
+
+        let cheap = pool.add_operation(user_operation(Address::random(), 0, 100, 1)).unwrap();
+        assert!(matches!(
+            pool.add_operation(user_operation(Address::random(), 0, 100, 1)),
+            Err(UserOperationError::PoolFull)
+        ));
+
+        // A better paying user operation evicts the worst one
+        pool.add_operation(user_operation(Address::random(), 0, 100, 2)).unwrap();
+        assert!(pool.get(&cheap).is_none());
+        assert_eq!(pool.len(), 5);
+    }
+
+    #[test]
+    fn best_user_operations() {
+        let pool = UserOperationPool::new(UserOperationPoolConfig::new(1));
+        let (a, b, c) = (Address::random(), Address::random(), Address::random());
+        let a0 = pool.add_operation(user_operation(a, 0, 100, 5)).unwrap();
+        pool.add_operation(user_operation(a, 1, 100, 50)).unwrap();
+        let b0 = pool.add_operation(user_operation(b, 0, 100, 20)).unwrap();
+        pool.add_operation(user_operation(c, 0, 40, 20)).unwrap();
+
+        let best = pool.best_operations(50, u64::MAX);
+        assert_eq!(best.iter().map(|op| op.hash()).collect::<Vec<_>>(), vec![b0, a0]);
+
+        // Each user operation has a gas limit of 250k
+        let best = pool.best_operations(50, 400_000);
+        assert_eq!(best.iter().map(|op| op.hash()).collect::<Vec<_>>(), vec![b0]);
+    }
+}
@@ -0,0 +1,126 @@
+use alloy_primitives::{map::AddressHashMap, Address};
+use std::fmt;
+
+/// The denominator of the minimum ratio of included to seen user operations of an entity.
+const MIN_INCLUSION_RATE_DENOMINATOR: u64 = 10;
+
+/// The number of user operations above the minimum inclusion rate before an entity is throttled.
+const THROTTLING_SLACK: u64 = 10;
+
+/// The number of user operations above the minimum inclusion rate before an entity is banned.
+const BAN_SLACK: u64 = 50;
+
+/// The divisor of the hourly decay of the counters.
+const DECAY_DIVISOR: u64 = 24;
+
+/// The reputation status of an entity as defined by ERC-7562.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReputationStatus {
+    /// The user operations of the entity are accepted.
+    Ok,
+    /// Only a limited number of user operations of the entity are accepted.
+    Throttled,
+    /// No user operations of the entity are accepted.
+    Banned,
+}
+
+impl fmt::Display for ReputationStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Ok => f.write_str("ok"),
+            Self::Throttled => f.write_str("throttled"),
+            Self::Banned => f.write_str("banned"),
+        }
+    }
+}
+
+/// The counters of an entity.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+struct ReputationEntry {
+    /// The number of user operations of the entity that passed the validation.
+    ops_seen: u64,
+    /// The number of user operations of the entity that were included in a block.
+    ops_included: u64,
+}
+
+/// Tracks the reputation of the factories and paymasters of the user operations.
+///
+/// An entity whose user operations are seen much more often than included is throttled, and
+/// eventually banned. The counters decay every hour with [`Reputation::decay`].
+#[derive(Debug, Clone, Default)]
+pub struct Reputation {
+    entries: AddressHashMap<ReputationEntry>,
+}
+
+impl Reputation {
+    /// Returns the reputation status of the entity.
+    pub fn status(&self, address: &Address) -> ReputationStatus {
+        let Some(entry) = self.entries.get(address) else { return ReputationStatus::Ok };
+        let max_seen = entry.ops_seen / MIN_INCLUSION_RATE_DENOMINATOR;
+        if max_seen <= entry.ops_included + THROTTLING_SLACK {
+            ReputationStatus::Ok
+        } else if max_seen <= entry.ops_included + BAN_SLACK {
+            ReputationStatus::Throttled
+        } else {
+            ReputationStatus::Banned
+        }
+    }
+
+    /// Records a user operation of the entity that passed the validation.
+    pub fn add_seen(&mut self, address: Address) {
+        self.entries.entry(address).or_default().ops_seen += 1;
+    }
+
+    /// Records a user operation of the entity that was included in a block.
+    pub fn add_included(&mut self, address: Address) {
+        self.entries.entry(address).or_default().ops_included += 1;
+    }
+
+    /// Decays the counters of all entities, and forgets the entities without any.
+    ///
+    /// This is expected to be called every hour.
+    pub fn decay(&mut self) {
+        self.entries.retain(|_, entry| {
+            entry.ops_seen -= entry.ops_seen / DECAY_DIVISOR;
+            entry.ops_included -= entry.ops_included / DECAY_DIVISOR;
+            entry.ops_seen > 0 || entry.ops_included > 0
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reputation_status() {
+        let entity = Address::random();
+        let mut reputation = Reputation::default();
+        assert_eq!(reputation.status(&entity), ReputationStatus::Ok);
+
+        for _ in 0..100 {
+            reputation.add_seen(entity);
+        }
+        assert_eq!(reputation.status(&entity), ReputationStatus::Ok);
+
+        for _ in 0..10 {
+            reputation.add_seen(entity);
+        }
+        assert_eq!(reputation.status(&entity), ReputationStatus::Throttled);
+
+        // Inclusions restore the reputation
+        reputation.add_included(entity);
+        assert_eq!(reputation.status(&entity), ReputationStatus::Ok);
+
+        for _ in 0..500 {
+            reputation.add_seen(entity);
+        }
+        assert_eq!(reputation.status(&entity), ReputationStatus::Banned);
+
+        // The counters decay over time
+        for _ in 0..100 {
+            reputation.decay();
+        }
+        assert_eq!(reputation.status(&entity), ReputationStatus::Ok);
+    }
+}
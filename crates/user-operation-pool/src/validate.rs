@@ -0,0 +1,407 @@
+use crate::{
+    entry_point::{IAccount, IPaymaster},
+    UserOperation, UserOperationError, UserOperationPoolConfig,
+};
+use alloy_primitives::{keccak256, map::HashSet, Address, Bytes, B256, U256};
+use alloy_sol_types::{SolCall, SolValue};
+use revm::{
+    interpreter::{
+        opcode::{self, OpCode},
+        CallInputs, CallOutcome, CreateInputs, CreateOutcome, Interpreter,
+    },
+    Database, EvmContext, Inspector,
+};
+use std::fmt;
+
+/// The fixed gas of a bundle transaction, charged to the pre-verification gas of every user
+/// operation.
+const FIXED_GAS: u64 = 21_000;
+
+/// The gas overhead of the entry point for every user operation.
+const PER_USER_OPERATION_GAS: u64 = 18_300;
+
+/// The gas overhead of every word of the encoded user operation.
+const PER_USER_OPERATION_WORD_GAS: u64 = 4;
+
+/// The maximum distance of a storage slot from a slot derived from the sender address to be
+/// associated with the sender.
+const MAX_ASSOCIATED_SLOT_OFFSET: u64 = 128;
+
+/// The opcodes that must not be used during the validation.
+const BANNED_OPCODES: [u8; 15] = [
+    opcode::GASPRICE,
+    opcode::GASLIMIT,
+    opcode::DIFFICULTY,
+    opcode::TIMESTAMP,
+    opcode::BASEFEE,
+    opcode::BLOCKHASH,
+    opcode::NUMBER,
+    opcode::SELFBALANCE,
+    opcode::BALANCE,
+    opcode::ORIGIN,
+    opcode::CREATE,
+    opcode::COINBASE,
+    opcode::SELFDESTRUCT,
+    opcode::BLOBHASH,
+    opcode::BLOBBASEFEE,
+];
+
+/// Returns the minimum pre-verification gas of the user operation.
+///
+/// It covers the calldata of the user operation in the bundle transaction, and its share of the
+/// fixed overhead of the bundle, assuming a bundle of a single user operation.
+pub fn pre_verification_gas(op: &UserOperation) -> u64 {
+    let encoded = op.pack().abi_encode();
+    let calldata_gas: u64 = encoded.iter().map(|byte| if *byte == 0 { 4 } else { 16 }).sum();
+    let words = encoded.len().div_ceil(32) as u64;
+    FIXED_GAS + PER_USER_OPERATION_GAS + PER_USER_OPERATION_WORD_GAS * words + calldata_gas
+}
+
+/// Performs the static checks of the user operation, which don't require a simulation.
+pub fn validate_user_operation(
+    op: &UserOperation,
+    config: &UserOperationPoolConfig,
+) -> Result<(), UserOperationError> {
+    let inner = op.inner();
+
+    let u128_fields = [
+        inner.call_gas_limit,
+        inner.verification_gas_limit,
+        inner.pre_verification_gas,
+        inner.max_fee_per_gas,
+        inner.max_priority_fee_per_gas,
+        inner.paymaster_verification_gas_limit.unwrap_or_default(),
+        inner.paymaster_post_op_gas_limit.unwrap_or_default(),
+    ];
+    if u128_fields.iter().any(|value| *value > U256::from(u128::MAX)) {
+        return Err(UserOperationError::InvalidField("gas limit or fee exceeds 128 bits"))
+    }
+    if inner.factory.is_none() && inner.factory_data.is_some() {
+        return Err(UserOperationError::InvalidField("factory data without factory"))
+    }
+    if inner.paymaster.is_none() &&
+        (inner.paymaster_verification_gas_limit.is_some() ||
+            inner.paymaster_post_op_gas_limit.is_some() ||
+            inner.paymaster_data.is_some())
+    {
+        return Err(UserOperationError::InvalidField("paymaster fields without paymaster"))
+    }
+    if inner.paymaster.is_some() &&
+        (inner.paymaster_verification_gas_limit.is_none() ||
+            inner.paymaster_post_op_gas_limit.is_none())
+    {
+        return Err(UserOperationError::InvalidField("missing paymaster gas limits"))
+    }
+
+    if inner.max_priority_fee_per_gas > inner.max_fee_per_gas {
+        return Err(UserOperationError::TipAboveFeeCap)
+    }
+
+    let verification_gas_limit = inner
+        .verification_gas_limit
+        .saturating_add(inner.paymaster_verification_gas_limit.unwrap_or_default());
+    if verification_gas_limit > U256::from(config.max_verification_gas) {
+        return Err(UserOperationError::VerificationGasLimitTooHigh {
+            actual: verification_gas_limit,
+            max: config.max_verification_gas,
+        })
+    }
+
+    let required = pre_verification_gas(op);
+    if inner.pre_verification_gas < U256::from(required) {
+        return Err(UserOperationError::PreVerificationGasTooLow {
+            actual: inner.pre_verification_gas,
+            required,
+        })
+    }
+
+    Ok(())
+}
+
+/// A phase of the handling of a user operation by the entry point.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ValidationPhase {
+    /// The deployment of the sender by the factory.
+    Factory,
+    /// The validation of the user operation by the sender.
+    Account,
+    /// The validation of the user operation by the paymaster.
+    Paymaster,
+    /// The execution of the user operation by the sender.
+    Execution,
+}
+
+impl ValidationPhase {
+    /// Returns `true` if the phase is part of the validation, which is restricted by the rules of
+    /// ERC-7562.
+    pub const fn is_validation(&self) -> bool {
+        !matches!(self, Self::Execution)
+    }
+
+    const fn index(&self) -> usize {
+        *self as usize
+    }
+}
+
+impl fmt::Display for ValidationPhase {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Factory => f.write_str("factory"),
+            Self::Account => f.write_str("account"),
+            Self::Paymaster => f.write_str("paymaster"),
+            Self::Execution => f.write_str("execution"),
+        }
+    }
+}
+
+/// An [`Inspector`] that enforces the validation rules of ERC-7562 while `handleOps` is simulated
+/// for a single user operation, and measures the gas used by every phase.
+///
+/// The following subset of the rules is enforced:
+/// - The banned opcodes, e.g. `TIMESTAMP` or `BALANCE`, must not be used during the validation.
+/// - `CREATE2` may only be used by the factory.
+/// - Only the storage of the sender, of the entry point, and the slots associated with the sender
+///   may be accessed during the validation. A slot is associated with the sender if it is at most
+///   128 slots after the `keccak256` of a value that starts with the sender address, e.g. the slot
+///   of a mapping keyed by the sender.
+///
+/// Staked entities are not supported, so the factory and paymaster can't access their own storage.
+#[derive(Debug)]
+pub struct ValidationInspector {
+    entry_point: Address,
+    sender: Address,
+    factory: Option<Address>,
+    paymaster: Option<Address>,
+    /// The phase of every active frame, and whether the frame entered it.
+    frames: Vec<(Option<ValidationPhase>, bool)>,
+    /// The `keccak256` hashes of the values that start with the sender address.
+    sender_slots: HashSet<U256>,
+    /// The gas used by every phase.
+    gas_used: [u64; 4],
+    /// The output of the execution, if it reverted.
+    execution_revert: Option<Bytes>,
+    /// The first violation of the rules.
+    violation: Option<UserOperationError>,
+}
+
+impl ValidationInspector {
+    /// Creates a new [`ValidationInspector`] for the user operation handled by the given entry
+    /// point.
+    pub fn new(entry_point: Address, op: &UserOperation) -> Self {
+        Self {
+            entry_point,
+            sender: op.sender(),
+            factory: op.factory(),
+            paymaster: op.paymaster(),
+            frames: Vec::new(),
+            sender_slots: HashSet::default(),
+            gas_used: [0; 4],
+            execution_revert: None,
+            violation: None,
+        }
+    }
+
+    /// Returns the gas used by the given phase.
+    pub const fn gas_used(&self, phase: ValidationPhase) -> u64 {
+        self.gas_used[phase.index()]
+    }
+
+    /// Returns the output of the execution, if it reverted.
+    pub const fn execution_revert(&self) -> Option<&Bytes> {
+        self.execution_revert.as_ref()
+    }
+
+    /// Returns the first violation of the validation rules, if any.
+    pub fn take_violation(&mut self) -> Option<UserOperationError> {
+        self.violation.take()
+    }
+
+    /// Returns the phase of the current frame, if it's part of the validation.
+    fn validation_phase(&self) -> Option<ValidationPhase> {
+        self.frames.last().and_then(|(phase, _)| *phase).filter(ValidationPhase::is_validation)
+    }
+
+    /// Returns `true` if the storage slot of the contract may be accessed during the validation.
+    fn is_storage_allowed(&self, address: Address, slot: U256) -> bool {
+        address == self.sender ||
+            address == self.entry_point ||
+            self.sender_slots.iter().any(|sender_slot| {
+                slot.checked_sub(*sender_slot)
+                    .is_some_and(|offset| offset <= U256::from(MAX_ASSOCIATED_SLOT_OFFSET))
+            })
+    }
+
+    /// Records the hash of the `KECCAK256` operation if its input starts with the sender address.
+    fn record_sender_slot(&mut self, interp: &Interpreter) {
+        let (Ok(offset), Ok(size)) = (interp.stack().peek(0), interp.stack().peek(1)) else {
+            return
+        };
+        let (Ok(offset), Ok(size)) = (usize::try_from(offset), usize::try_from(size)) else {
+            return
+        };
+        if size < 32 || offset.saturating_add(size) > interp.shared_memory.len() {
+            return
+        }
+        let input = interp.shared_memory.slice(offset, size);
+        if input[..32] == B256::left_padding_from(self.sender.as_slice()) {
+            self.sender_slots.insert(keccak256(input).into());
+        }
+    }
+
+    /// Enters a new frame, and returns the phase it entered if any.
+    fn enter_frame(&mut self, caller: Address, target: Address, input: &[u8]) {
+        let parent = self.frames.last().and_then(|(phase, _)| *phase);
+        let selector = input.get(..4);
+        let phase = if caller == self.entry_point &&
+            target == self.sender &&
+            selector == Some(IAccount::validateUserOpCall::SELECTOR.as_slice())
+        {
+            Some(ValidationPhase::Account)
+        } else if caller == self.entry_point &&
+            Some(target) == self.paymaster &&
+            selector == Some(IPaymaster::validatePaymasterUserOpCall::SELECTOR.as_slice())
+        {
+            Some(ValidationPhase::Paymaster)
+        } else if parent.is_none() && Some(target) == self.factory {
+            Some(ValidationPhase::Factory)
+        } else if caller == self.entry_point && target == self.sender {
+            Some(ValidationPhase::Execution)
+        } else {
+            None
+        };
+
+        match phase {
+            Some(phase) => self.frames.push((Some(phase), true)),
+            None => self.frames.push((parent, false)),
+        }
+    }
+}
+
+impl<DB: Database> Inspector<DB> for ValidationInspector {
+    fn step(&mut self, interp: &mut Interpreter, _context: &mut EvmContext<DB>) {
+        let opcode = interp.current_opcode();
+        if opcode == opcode::KECCAK256 {
+            self.record_sender_slot(interp);
+        }
+
+        if self.violation.is_some() {
+            return
+        }
+        let Some(phase) = self.validation_phase() else { return };
+
+        if BANNED_OPCODES.contains(&opcode) ||
+            (opcode == opcode::CREATE2 && phase != ValidationPhase::Factory)
+        {
+            self.violation = Some(UserOperationError::BannedOpcode {
+                phase,
+                opcode: OpCode::name_by_op(opcode),
+            });
+        } else if matches!(opcode, opcode::SLOAD | opcode::SSTORE) {
+            let Ok(slot) = interp.stack().peek(0) else { return };
+            let address = interp.contract.target_address;
+            if !self.is_storage_allowed(address, slot) {
+                self.violation = Some(UserOperationError::StorageAccess { phase, address, slot });
+            }
+        }
+    }
+
+    fn call(
+        &mut self,
+        _context: &mut EvmContext<DB>,
+        inputs: &mut CallInputs,
+    ) -> Option<CallOutcome> {
+        self.enter_frame(inputs.caller, inputs.target_address, &inputs.input);
+        None
+    }
+
+    fn call_end(
+        &mut self,
+        _context: &mut EvmContext<DB>,
+        _inputs: &CallInputs,
+        outcome: CallOutcome,
+    ) -> CallOutcome {
+        if let Some((Some(phase), true)) = self.frames.pop() {
+            self.gas_used[phase.index()] += outcome.gas().spent();
+            if phase == ValidationPhase::Execution && !outcome.result.is_ok() {
+                self.execution_revert = Some(outcome.output().clone());
+            }
+        }
+        outcome
+    }
+
+    fn create(
+        &mut self,
+        _context: &mut EvmContext<DB>,
+        _inputs: &mut CreateInputs,
+    ) -> Option<CreateOutcome> {
+        let parent = self.frames.last().and_then(|(phase, _)| *phase);
+        self.frames.push((parent, false));
+        None
+    }
+
+    fn create_end(
+        &mut self,
+        _context: &mut EvmContext<DB>,
+        _inputs: &CreateInputs,
+        outcome: CreateOutcome,
+    ) -> CreateOutcome {
+        self.frames.pop();
+        outcome
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entry_point::ENTRY_POINT_V07_ADDRESS;
+    use alloy_rpc_types_eth::erc4337::PackedUserOperation;
+
+    fn user_operation() -> PackedUserOperation {
+        PackedUserOperation {
+            sender: Address::random(),
+            nonce: U256::ZERO,
+            factory: None,
+            factory_data: None,
+            call_data: Bytes::new(),
+            call_gas_limit: U256::from(100_000),
+            verification_gas_limit: U256::from(100_000),
+            pre_verification_gas: U256::from(100_000),
+            max_fee_per_gas: U256::from(10),
+            max_priority_fee_per_gas: U256::from(1),
+            paymaster: None,
+            paymaster_verification_gas_limit: None,
+            paymaster_post_op_gas_limit: None,
+            paymaster_data: None,
+            signature: Bytes::new(),
+        }
+    }
+
+    #[test]
+    fn validate_static_fields() {
+        let config = UserOperationPoolConfig::new(1);
+        let validate = |op| {
+            validate_user_operation(&UserOperation::new(op, ENTRY_POINT_V07_ADDRESS, 1), &config)
+        };
+
+        assert!(validate(user_operation()).is_ok());
+
+        let mut op = user_operation();
+        op.max_priority_fee_per_gas = U256::from(11);
+        assert!(matches!(validate(op), Err(UserOperationError::TipAboveFeeCap)));
+
+        let mut op = user_operation();
+        op.paymaster_data = Some(Bytes::new());
+        assert!(matches!(validate(op), Err(UserOperationError::InvalidField(_))));
+
+        let mut op = user_operation();
+        op.verification_gas_limit = U256::from(config.max_verification_gas + 1);
+        assert!(matches!(
+            validate(op),
+            Err(UserOperationError::VerificationGasLimitTooHigh { .. })
+        ));
+
+        let mut op = user_operation();
+        op.pre_verification_gas = U256::from(FIXED_GAS);
+        assert!(matches!(validate(op), Err(UserOperationError::PreVerificationGasTooLow { .. })));
+    }
+}
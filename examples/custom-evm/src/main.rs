@@ -20,7 +20,7 @@ use reth::{
     },
     rpc::types::engine::PayloadAttributes,
     tasks::TaskManager,
-    transaction_pool::{PoolTransaction, TransactionPool},
+    transaction_pool::{PoolTransaction, TransactionPoolExt},
 };
 use reth_chainspec::{Chain, ChainSpec};
 use reth_evm::env::EvmEnv;
@@ -186,7 +186,7 @@ impl<Types, Node, Pool> PayloadServiceBuilder<Node, Pool> for MyPayloadBuilder
 where
     Types: NodeTypesWithEngine<ChainSpec = ChainSpec, Primitives = EthPrimitives>,
     Node: FullNodeTypes<Types = Types>,
-    Pool: TransactionPool<Transaction: PoolTransaction<Consensus = TransactionSigned>>
+    Pool: TransactionPoolExt<Transaction: PoolTransaction<Consensus = TransactionSigned>>
         + Unpin
         + 'static,
     Types::Engine: PayloadTypes<
@@ -7,7 +7,8 @@
 //! cargo run -p custom-payload-builder -- node
 //! ```
 //!
-//! This launch the regular reth node overriding the engine api payload builder with our custom.
+//! This launch the regular reth node overriding the engine api payload builder with our custom,
+//! which includes the transactions of a [`PayloadTransactionSource`] after the pool transactions.
 
 #![cfg_attr(not(test), warn(unused_crate_dependencies))]
 
@@ -25,6 +26,10 @@ use reth_ethereum_payload_builder::EthereumBuilderConfig;
 use reth_node_api::NodeTypesWithEngine;
 use reth_node_ethereum::{node::EthereumAddOns, EthEngineTypes, EthEvmConfig, EthereumNode};
 use reth_payload_builder::PayloadBuilderService;
+use reth_payload_util::{
+    BuildPhase, PayloadSourceContext, PayloadTransactionGroup, PayloadTransactionSource,
+    PayloadTransactionSources, SourceBudget,
+};
 use reth_primitives::{EthPrimitives, TransactionSigned};
 
 pub mod generator;
@@ -34,6 +39,31 @@ pub mod job;
 #[non_exhaustive]
 pub struct CustomPayloadBuilder;
 
+/// A [`PayloadTransactionSource`] that's asked for its transactions once the transactions of the
+/// pool are included, e.g. to backrun them.
+#[derive(Debug, Clone, Copy, Default)]
+#[non_exhaustive]
+pub struct BackrunSource;
+
+impl PayloadTransactionSource<TransactionSigned> for BackrunSource {
+    fn transactions(
+        &self,
+        ctx: &PayloadSourceContext<'_, TransactionSigned>,
+    ) -> Vec<PayloadTransactionGroup<TransactionSigned>> {
+        tracing::debug!(
+            block_number = ctx.block_number,
+            executed = ctx.executed_transactions.len(),
+            gas_used = ctx.gas_used,
+            "searching for backruns"
+        );
+
+        // Return the backruns of `ctx.executed_transactions` here, e.g. as
+        // `PayloadTransactionGroup::single(tx).with_min_profit_per_gas(1)`. The transactions of a
+        // group are included all together or not at all.
+        Vec::new()
+    }
+}
+
 impl<Node, Pool> PayloadServiceBuilder<Node, Pool> for CustomPayloadBuilder
 where
     Node: FullNodeTypes<
@@ -67,7 +97,14 @@ where
             payload_job_config,
             reth_ethereum_payload_builder::EthereumPayloadBuilder::new(
                 EthEvmConfig::new(ctx.chain_spec()),
-                EthereumBuilderConfig::new(conf.extra_data_bytes()),
+                EthereumBuilderConfig::new(conf.extra_data_bytes()).with_transaction_sources(
+                    // the backruns may use at most 1M gas of each block
+                    PayloadTransactionSources::new().with_source(
+                        BuildPhase::EndOfBlock,
+                        SourceBudget::unlimited().with_gas_limit(1_000_000),
+                        BackrunSource,
+                    ),
+                ),
             ),
         );
 